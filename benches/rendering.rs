@@ -0,0 +1,83 @@
+use std::{io::Write, sync::mpsc::channel, time::Duration};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tic_tac_toe2::{
+    conway::{conways_game::ConwaysGame, print_mode::PrintMode},
+    handler::{handle::Handle, memory_handle::MemoryHandle},
+    rendering::{
+        colors::TerminalColors, message_helper::MessageHelper, message_style::MessageStyle,
+    },
+    shared::usize2d::Coord,
+};
+
+/// The side lengths of the square boards that are drawn
+const GRID_SIZES: [usize; 3] = [16, 32, 64];
+
+/// Write a full screen of colored rows and flush it, the way a panel writes a frame
+fn bench_frame_flush(c: &mut Criterion) {
+    let mut group = c.benchmark_group("memory_handle_frame_flush");
+    for (width, height) in [(80, 24), (160, 48), (320, 96)] {
+        let row = vec![b'#'; width];
+        group.throughput(Throughput::Bytes((width * height) as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", width, height)),
+            &height,
+            |b, &height| {
+                let mut handle = MemoryHandle::new();
+                b.iter(|| {
+                    for y in 0..height {
+                        let color = if y % 2 == 0 {
+                            TerminalColors::Red
+                        } else {
+                            TerminalColors::LightGreen
+                        };
+                        let _ = handle.set_background_color(color);
+                        let _ = handle.write_to_location(&row, Coord::new(0, y));
+                    }
+                    let _ = handle.flush();
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Draw a whole board in both print modes
+fn bench_board_print(c: &mut Criterion) {
+    let mut group = c.benchmark_group("conway_print");
+    for size in GRID_SIZES {
+        group.throughput(Throughput::Elements((size * size) as u64));
+        for mode in [PrintMode::PRETTY, PrintMode::DEBUG] {
+            group.bench_with_input(
+                BenchmarkId::new(mode.to_string(), size),
+                &size,
+                |b, &size| {
+                    let (_, receiver) = channel();
+                    let mut game = ConwaysGame::builder(size, size)
+                        .seed(42)
+                        .render_mode(mode)
+                        .speed(Duration::from_millis(1))
+                        .receiver(receiver)
+                        .handle(Box::new(MemoryHandle::new()))
+                        .build()
+                        .unwrap();
+                    b.iter(|| game.print(mode));
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_banner(c: &mut Criterion) {
+    let style = MessageStyle::default();
+    let mut helper = MessageHelper::init(Box::new(MemoryHandle::new()));
+    c.bench_function("message_helper_banner", |b| {
+        b.iter(|| {
+            let _ = helper.print(black_box("PAUSED".to_string()), Coord::new(1, 1), &style);
+        })
+    });
+}
+
+criterion_group!(benches, bench_frame_flush, bench_board_print, bench_banner);
+criterion_main!(benches);
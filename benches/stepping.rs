@@ -0,0 +1,82 @@
+use std::{sync::mpsc::channel, time::Duration};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tic_tac_toe2::{
+    conway::{
+        bit_grid::BitGrid, conways_game::ConwaysGame, print_mode::PrintMode, rule::Rule,
+        settings::Boundary,
+    },
+    handler::memory_handle::MemoryHandle,
+};
+
+/// The side lengths of the square boards that are stepped
+const GRID_SIZES: [usize; 4] = [16, 64, 128, 256];
+
+fn new_game(size: usize) -> ConwaysGame {
+    let (_, receiver) = channel();
+    ConwaysGame::builder(size, size)
+        .seed(42)
+        .render_mode(PrintMode::PRETTY)
+        .speed(Duration::from_millis(1))
+        .receiver(receiver)
+        .handle(Box::new(MemoryHandle::new()))
+        .build()
+        .unwrap()
+}
+
+/// The number of generations a board runs before it is benchmarked as settled, by then most of
+/// a random board is still lifes and blinkers
+const SETTLE_GENERATIONS: usize = 500;
+
+fn bench_next(c: &mut Criterion) {
+    let mut group = c.benchmark_group("conway_next");
+    for size in GRID_SIZES {
+        group.throughput(Throughput::Elements((size * size) as u64));
+        group.bench_with_input(BenchmarkId::new("dense", size), &size, |b, &size| {
+            let mut game = new_game(size);
+            b.iter(|| game.next());
+        });
+        group.bench_with_input(
+            BenchmarkId::new("dense_full_scan", size),
+            &size,
+            |b, &size| {
+                let mut game = new_game(size);
+                b.iter(|| game.next_full_scan());
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("settled", size), &size, |b, &size| {
+            let mut game = new_game(size);
+            (0..SETTLE_GENERATIONS).for_each(|_| game.next());
+            b.iter(|| game.next());
+        });
+        group.bench_with_input(
+            BenchmarkId::new("settled_full_scan", size),
+            &size,
+            |b, &size| {
+                let mut game = new_game(size);
+                (0..SETTLE_GENERATIONS).for_each(|_| game.next());
+                b.iter(|| game.next_full_scan());
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Steps the bit-packed board, `step` is the word at a time path with the `simd` feature
+fn bench_bit_grid(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bit_grid_step");
+    for size in GRID_SIZES {
+        let grid = BitGrid::from_cells(new_game(size).cells(), Boundary::Wrap);
+        group.throughput(Throughput::Elements((size * size) as u64));
+        group.bench_with_input(BenchmarkId::new("scalar", size), &grid, |b, grid| {
+            b.iter(|| grid.step_scalar(Rule::conway()));
+        });
+        group.bench_with_input(BenchmarkId::new("step", size), &grid, |b, grid| {
+            b.iter(|| grid.step(Rule::conway()));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_next, bench_bit_grid);
+criterion_main!(benches);
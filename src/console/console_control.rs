@@ -1,7 +1,7 @@
 use windows_sys::Win32::Foundation::{HANDLE, INVALID_HANDLE_VALUE};
 use windows_sys::Win32::System::Console::{
-    GetConsoleMode, GetStdHandle, ReadConsoleInputA, SetConsoleMode, CONSOLE_MODE, INPUT_RECORD,
-    STD_INPUT_HANDLE,
+    GetConsoleMode, GetStdHandle, ReadConsoleInputA, SetConsoleMode, CONSOLE_MODE,
+    ENABLE_VIRTUAL_TERMINAL_PROCESSING, INPUT_RECORD, STD_INPUT_HANDLE, STD_OUTPUT_HANDLE,
 };
 
 use super::errors::ConsoleControlErr;
@@ -10,10 +10,18 @@ use super::mode::{self, ConsoleMode};
 
 pub struct ConsoleControl {
     handle: HANDLE,
+    output_handle: HANDLE,
+    /// The mode of the output before the processing of ANSI escape codes was enabled, restored
+    /// when the control is dropped. `None` if the mode was not changed
+    original_output_mode: Option<CONSOLE_MODE>,
 }
 impl ConsoleControl {
     ///Initialize an instance of ConsoleControl
     ///
+    /// Enables the processing of ANSI escape codes on the output so that the codes written by
+    /// `StdIOHandle` are not printed as literal text on classic consoles. An output that is not a
+    /// console, like one redirected to a file, is left as it is
+    ///
     /// #Returns
     ///
     /// An a Result that is either an instance of ConsoleControl or an error
@@ -31,9 +39,53 @@ impl ConsoleControl {
         if input_handle == INVALID_HANDLE_VALUE {
             return Err(ConsoleControlErr::NoHandle);
         }
-        Ok(ConsoleControl {
+        let output_handle: HANDLE = unsafe { GetStdHandle(STD_OUTPUT_HANDLE) };
+        if output_handle == INVALID_HANDLE_VALUE {
+            return Err(ConsoleControlErr::NoHandle);
+        }
+        let mut control = ConsoleControl {
             handle: input_handle,
-        })
+            output_handle,
+            original_output_mode: None,
+        };
+        control.enable_virtual_terminal()?;
+        Ok(control)
+    }
+
+    /// Enable the processing of ANSI escape codes on the console output. Nothing changes if the
+    /// output is not a console, since a file or a pipe takes the codes as they are
+    ///
+    /// #Returns
+    ///
+    /// An error if the console does not support virtual terminal sequences, e.g. on Windows
+    /// versions before Windows 10
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// control.enable_virtual_terminal()?;
+    /// ```
+    pub fn enable_virtual_terminal(&mut self) -> Result<(), ConsoleControlErr> {
+        let mut mode: CONSOLE_MODE = 0;
+        let success = unsafe { GetConsoleMode(self.output_handle, &mut mode) };
+        if success == 0 {
+            // The output is redirected to a file or a pipe
+            return Ok(());
+        }
+        if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 {
+            return Ok(());
+        }
+        let success = unsafe {
+            SetConsoleMode(
+                self.output_handle,
+                mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+            )
+        };
+        if success == 0 {
+            return Err(ConsoleControlErr::VirtualTerminalUnsupported);
+        }
+        self.original_output_mode = Some(mode);
+        Ok(())
     }
 
     /// Get current console mode
@@ -144,6 +196,14 @@ impl ConsoleControl {
         if success == 0 {
             return Err(ConsoleControlErr::NoModeResponse);
         }
-        return Ok(());
+        Ok(())
+    }
+}
+impl Drop for ConsoleControl {
+    /// Give the console output back in the mode it was in before
+    fn drop(&mut self) {
+        if let Some(mode) = self.original_output_mode.take() {
+            unsafe { SetConsoleMode(self.output_handle, mode) };
+        }
     }
 }
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal,
+};
+
+use super::{
+    errors::ConsoleControlErr,
+    input_record::{Key, KeyEvent},
+    input_source::InputSource,
+};
+
+/// The keys of the terminal read through crossterm, with the `crossterm-backend` feature
+///
+/// The terminal is switched to raw input while the input is kept, so that keys arrive as they
+/// are pressed, and switched back once it is dropped. Chords arrive as the same control
+/// characters as from the console, so Ctrl+S is `SAVE_KEY` on every platform. Keys the games
+/// have no use for, like the arrows, are left out
+///
+/// # Example
+///
+/// ```no_run
+/// # use tic_tac_toe2::{
+/// #     console::crossterm_input::CrosstermInput, handler::crossterm_handle::CrosstermHandle,
+/// #     ConwaysGame,
+/// # };
+/// let input = CrosstermInput::new().expect("The terminal has a raw mode");
+/// let mut game = ConwaysGame::builder(40, 20)
+///     .input(Box::new(input))
+///     .handle(Box::new(CrosstermHandle::new()))
+///     .build()
+///     .unwrap();
+/// game.run();
+/// ```
+#[derive(Debug)]
+pub struct CrosstermInput {
+    _private: (),
+}
+impl CrosstermInput {
+    /// Switch the terminal to raw input and start taking its keys
+    pub fn new() -> Result<Self, ConsoleControlErr> {
+        terminal::enable_raw_mode().map_err(|_| ConsoleControlErr::SetModeFailed)?;
+        Ok(CrosstermInput { _private: () })
+    }
+}
+impl InputSource for CrosstermInput {
+    fn poll(&mut self) -> Option<KeyEvent> {
+        while event::poll(Duration::ZERO).ok()? {
+            if let Event::Key(key) = event::read().ok()? {
+                if let Some(event) = key_event(key) {
+                    return Some(event);
+                }
+            }
+        }
+        None
+    }
+}
+impl Drop for CrosstermInput {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Turn a crossterm key event into the key event of the games
+///
+/// # Returns
+///
+/// `None` for keys that have no character, like the arrows
+pub fn key_event(key: event::KeyEvent) -> Option<KeyEvent> {
+    let is_ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let key_of_event = match key.code {
+        KeyCode::Char(c) if is_ctrl && c.is_ascii_alphabetic() => Key::Ctrl(c),
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Escape,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Tab => Key::Tab,
+        _ => return None,
+    };
+    Some(KeyEvent {
+        is_down: key.kind != KeyEventKind::Release,
+        ..KeyEvent::key_down(key_of_event.to_char())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent as CrosstermKey, KeyEventKind, KeyModifiers};
+
+    use crate::console::input_record::{KeyEvent, QUIT_KEY, SAVE_KEY};
+
+    use super::key_event;
+
+    #[test]
+    fn key_events() {
+        let key = |code, modifiers| CrosstermKey::new(code, modifiers);
+        let test_cases = [
+            (
+                "letter",
+                key(KeyCode::Char('q'), KeyModifiers::NONE),
+                Some('q'),
+            ),
+            (
+                "shifted letter",
+                key(KeyCode::Char('Q'), KeyModifiers::SHIFT),
+                Some('Q'),
+            ),
+            (
+                "save chord",
+                key(KeyCode::Char('s'), KeyModifiers::CONTROL),
+                Some(SAVE_KEY),
+            ),
+            (
+                "quit chord with shift",
+                key(
+                    KeyCode::Char('Q'),
+                    KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+                ),
+                Some(QUIT_KEY),
+            ),
+            (
+                "symbol with control",
+                key(KeyCode::Char('@'), KeyModifiers::CONTROL),
+                Some('@'),
+            ),
+            ("enter", key(KeyCode::Enter, KeyModifiers::NONE), Some('\r')),
+            (
+                "escape",
+                key(KeyCode::Esc, KeyModifiers::NONE),
+                Some('\x1b'),
+            ),
+            (
+                "backspace",
+                key(KeyCode::Backspace, KeyModifiers::NONE),
+                Some('\x08'),
+            ),
+            ("arrow", key(KeyCode::Up, KeyModifiers::NONE), None),
+        ];
+        for (test_case, key, expected) in test_cases {
+            assert_eq!(
+                key_event(key).map(|event| event.command),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+
+        let released = CrosstermKey::new_with_kind(
+            KeyCode::Char('w'),
+            KeyModifiers::NONE,
+            KeyEventKind::Release,
+        );
+        assert_eq!(
+            key_event(released),
+            Some(KeyEvent {
+                is_down: false,
+                ..KeyEvent::key_down('w')
+            })
+        );
+    }
+}
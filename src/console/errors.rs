@@ -8,6 +8,7 @@ pub enum ConsoleControlErr {
     SetModeFailed,
     NoInputRead,
     WrongEventType,
+    VirtualTerminalUnsupported,
 }
 impl Display for ConsoleControlErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -0,0 +1,375 @@
+use std::fmt::Display;
+
+#[cfg(all(windows, feature = "windows-console"))]
+use windows_sys::Win32::System::Console::{
+    INPUT_RECORD, INPUT_RECORD_0, KEY_EVENT, KEY_EVENT_RECORD, KEY_EVENT_RECORD_0,
+};
+
+use crate::shared::usize2d::Coord;
+
+#[cfg(all(windows, feature = "windows-console"))]
+use super::errors::ConsoleControlErr;
+
+#[derive(Clone, Copy)]
+pub enum EventType {
+    KeyEvent,
+    MouseEvent,
+}
+impl Display for EventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventType::KeyEvent => write!(f, "key_pressed"),
+            EventType::MouseEvent => write!(f, "mouse_thing"),
+        }
+    }
+}
+#[derive(Clone, Copy)]
+#[cfg(all(windows, feature = "windows-console"))]
+pub struct InputRecord {
+    pub event_type: EventType,
+    pub event: InputEvent,
+}
+
+#[cfg(all(windows, feature = "windows-console"))]
+impl InputRecord {
+    pub fn new_raw() -> INPUT_RECORD {
+        let key_event_rec = KEY_EVENT_RECORD_0 { UnicodeChar: 0 };
+        let key_event = KEY_EVENT_RECORD {
+            bKeyDown: 0,
+            wRepeatCount: 0,
+            wVirtualKeyCode: 0,
+            wVirtualScanCode: 0,
+            uChar: key_event_rec,
+            dwControlKeyState: 0,
+        };
+        let event = INPUT_RECORD_0 {
+            KeyEvent: key_event,
+        };
+        INPUT_RECORD {
+            EventType: 0,
+            Event: event,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows-console"))]
+impl TryFrom<INPUT_RECORD> for InputRecord {
+    type Error = ConsoleControlErr;
+    // TODO: unit test this b#tch
+    fn try_from(input_rec: INPUT_RECORD) -> Result<Self, ConsoleControlErr> {
+        match input_rec.EventType as u32 {
+            KEY_EVENT => Ok(InputRecord {
+                event_type: EventType::KeyEvent,
+                event: InputEvent {
+                    key_event: KeyEvent {
+                        command: unsafe {
+                            decode_char(
+                                input_rec.Event.KeyEvent.uChar.UnicodeChar as u32,
+                                input_rec.Event.KeyEvent.wVirtualKeyCode,
+                                input_rec.Event.KeyEvent.dwControlKeyState,
+                            )
+                        },
+                        repreat_count: unsafe { input_rec.Event.KeyEvent.wRepeatCount },
+                        is_down: unsafe { input_rec.Event.KeyEvent.bKeyDown } == 1,
+                    },
+                },
+            }),
+            _ => Err(ConsoleControlErr::WrongEventType),
+        }
+        // TODO: do this mapping right, i currently assume everything is a keyevent
+    }
+}
+
+#[derive(Clone, Copy)]
+#[cfg(all(windows, feature = "windows-console"))]
+pub union InputEvent {
+    pub mouse_event: MouseEvent,
+    pub key_event: KeyEvent,
+    //pub WindowBufferSizeEvent: WINDOW_BUFFER_SIZE_RECORD,
+    //pub MenuEvent: MENU_EVENT_RECORD,
+    //pub FocusEvent: FOCUS_EVENT_RECORD,
+}
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyEvent {
+    pub command: char,
+    pub repreat_count: u16,
+    pub is_down: bool,
+}
+impl KeyEvent {
+    /// Create the event for a key that was pressed once
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::mpsc::channel;
+    /// # use tic_tac_toe2::console::input_record::KeyEvent;
+    /// # let (key_sender, _keys) = channel();
+    /// let _ = key_sender.send(KeyEvent::key_down('q'));
+    /// ```
+    pub fn key_down(command: char) -> Self {
+        KeyEvent {
+            command,
+            repreat_count: 1,
+            is_down: true,
+        }
+    }
+    /// Decode the key of the event
+    pub fn key(&self) -> Key {
+        Key::decode(self.command)
+    }
+}
+
+/// The chord that saves in the scenes that can save, Ctrl+S
+pub const SAVE_KEY: char = Key::Ctrl('s').to_char();
+/// The chord that quits every scene, Ctrl+Q
+pub const QUIT_KEY: char = Key::Ctrl('q').to_char();
+/// The chord that writes the frame on the screen to text files, Ctrl+T
+pub const DUMP_FRAME_KEY: char = Key::Ctrl('t').to_char();
+
+/// The key of a `KeyEvent`, with control chords and the named keys told apart from the
+/// characters that are typed
+///
+/// A key event holds the character the terminal sends for a key. Control and a letter arrive as
+/// the position of the letter in the alphabet, so Ctrl+S is `\x13`, which is also how enter, tab
+/// and backspace arrive
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::console::input_record::{Key, KeyEvent};
+/// # fn save() {}
+/// # fn type_char(_: char) {}
+/// # let event = KeyEvent::key_down('a');
+/// match event.key() {
+///     Key::Ctrl('s') => save(),
+///     Key::Char(c) => type_char(c),
+///     _ => (),
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    /// A character that was typed, like `q` or `?`
+    Char(char),
+    /// Control and a letter, the letter is in lower case
+    Ctrl(char),
+    Enter,
+    Escape,
+    Backspace,
+    Tab,
+}
+impl Key {
+    /// Decode the character of a key event
+    pub fn decode(c: char) -> Key {
+        match c {
+            '\r' | '\n' => Key::Enter,
+            '\x1b' => Key::Escape,
+            '\x08' | '\x7f' => Key::Backspace,
+            '\t' => Key::Tab,
+            '\x01'..='\x1a' => Key::Ctrl((b'a' + c as u8 - 1) as char),
+            c => Key::Char(c),
+        }
+    }
+    /// Read a key the way it is displayed, like `ctrl+s` or `space`
+    pub fn parse(text: &str) -> Option<Key> {
+        let key = match text {
+            "space" => Key::Char(' '),
+            "enter" => Key::Enter,
+            "esc" => Key::Escape,
+            "backspace" => Key::Backspace,
+            "tab" => Key::Tab,
+            _ if text.starts_with("ctrl+") => match text.as_bytes() {
+                [_, _, _, _, _, letter @ b'a'..=b'z'] => Key::Ctrl(*letter as char),
+                _ => return None,
+            },
+            _ => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Key::Char(c),
+                    _ => return None,
+                }
+            }
+        };
+        Some(key)
+    }
+    /// Get the character a terminal sends for the key
+    pub const fn to_char(self) -> char {
+        match self {
+            Key::Char(c) => c,
+            Key::Ctrl(letter) if letter.is_ascii_alphabetic() => {
+                (letter.to_ascii_lowercase() as u8 - b'a' + 1) as char
+            }
+            Key::Ctrl(c) => c,
+            Key::Enter => '\r',
+            Key::Escape => '\x1b',
+            Key::Backspace => '\x08',
+            Key::Tab => '\t',
+        }
+    }
+    /// Check if the key was pressed together with control
+    pub fn is_chord(self) -> bool {
+        matches!(self, Key::Ctrl(_))
+    }
+}
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Key::Char(' ') => write!(f, "space"),
+            Key::Char(c) => write!(f, "{}", c),
+            Key::Ctrl(letter) => write!(f, "ctrl+{}", letter),
+            Key::Enter => write!(f, "enter"),
+            Key::Escape => write!(f, "esc"),
+            Key::Backspace => write!(f, "backspace"),
+            Key::Tab => write!(f, "tab"),
+        }
+    }
+}
+
+/// Decode the character of a console key event
+///
+/// Control and a letter should arrive as a control character, but some keyboard layouts send
+/// the letter itself, or no character at all with only the virtual key code of the letter. Both
+/// are turned into the control character, so that a chord reads the same on every layout
+///
+/// # Arguments
+///
+/// * `unicode` - the character of the event
+/// * `virtual_key` - the virtual key code, which is the upper case letter for the letter keys
+/// * `ctrl_key_state` - the modifier flags of the event, see `LEFT_CTRL_PRESSED`
+pub fn decode_char(unicode: u32, virtual_key: u16, ctrl_key_state: u32) -> char {
+    let c = char::from_u32(unicode).unwrap_or(char::REPLACEMENT_CHARACTER);
+    let is_ctrl = ctrl_key_state & (LEFT_CTRL_PRESSED | RIGHT_CTRL_PRESSED) != 0;
+    let letter = match c {
+        _ if !is_ctrl => return c,
+        'a'..='z' | 'A'..='Z' => c,
+        '\0' => match char::from_u32(virtual_key as u32) {
+            Some(key @ 'A'..='Z') => key,
+            _ => return c,
+        },
+        _ => return c,
+    };
+    Key::Ctrl(letter).to_char()
+}
+
+/// The bits of `ctrl_key_state` that are set while the left or the right control key is held
+pub const LEFT_CTRL_PRESSED: u32 = 0x8;
+pub const RIGHT_CTRL_PRESSED: u32 = 0x4;
+/// The bit of `button_state` that is set while the left button is held down
+pub const LEFT_BUTTON_PRESSED: u32 = 0x1;
+/// The flag of an event where the mouse moved instead of a button being pressed or released
+pub const MOUSE_MOVED: u32 = 0x1;
+
+/// A mouse event with the same fields as the console reports them. The position is in the
+/// coordinates the screen is written with
+#[derive(Clone, Copy, Debug)]
+pub struct MouseEvent {
+    pub pos: Coord,
+    pub button_state: u32,
+    pub ctrl_key_state: u32,
+    pub flags: u32,
+}
+impl MouseEvent {
+    /// Create the event for the left button being pressed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{console::input_record::MouseEvent, shared::usize2d::Coord, ConwaysGame};
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.handle_mouse(MouseEvent::press(Coord::new(2, 1)));
+    /// ```
+    pub fn press(pos: Coord) -> Self {
+        MouseEvent {
+            pos,
+            button_state: LEFT_BUTTON_PRESSED,
+            ctrl_key_state: 0,
+            flags: 0,
+        }
+    }
+    /// Create the event for the mouse moving while the left button is held down
+    pub fn drag(pos: Coord) -> Self {
+        MouseEvent {
+            flags: MOUSE_MOVED,
+            ..MouseEvent::press(pos)
+        }
+    }
+    /// Create the event for the left button being released
+    pub fn release(pos: Coord) -> Self {
+        MouseEvent {
+            button_state: 0,
+            ..MouseEvent::press(pos)
+        }
+    }
+    /// Check if the left button is held down
+    pub fn is_left_down(&self) -> bool {
+        self.button_state & LEFT_BUTTON_PRESSED != 0
+    }
+    /// Check if the mouse moved, as opposed to a button being pressed or released
+    pub fn is_move(&self) -> bool {
+        self.flags & MOUSE_MOVED != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_char, Key, KeyEvent, LEFT_CTRL_PRESSED, QUIT_KEY, RIGHT_CTRL_PRESSED};
+
+    #[test]
+    fn keys() {
+        let test_cases = [
+            ("letter", 'q', Key::Char('q'), "q"),
+            ("space", ' ', Key::Char(' '), "space"),
+            ("chord", '\x13', Key::Ctrl('s'), "ctrl+s"),
+            ("first letter", '\x01', Key::Ctrl('a'), "ctrl+a"),
+            ("enter", '\r', Key::Enter, "enter"),
+            ("escape", '\x1b', Key::Escape, "esc"),
+            ("backspace", '\x7f', Key::Backspace, "backspace"),
+            ("tab", '\t', Key::Tab, "tab"),
+        ];
+        for (test_case, c, expected, name) in test_cases {
+            let key = KeyEvent::key_down(c).key();
+            assert_eq!(key, expected, "{}", test_case);
+            assert_eq!(key.to_string(), name, "{}", test_case);
+            assert_eq!(Key::parse(name), Some(key), "{}: read back", test_case);
+            if c != '\x7f' {
+                assert_eq!(key.to_char(), c, "{}: back to the character", test_case);
+            }
+        }
+        for name in ["ctrl+", "ctrl+1", "shift", ""] {
+            assert_eq!(Key::parse(name), None, "'{}' is not a key", name);
+        }
+        assert_eq!(QUIT_KEY, '\x11');
+        assert!(Key::decode(QUIT_KEY).is_chord());
+    }
+
+    #[test]
+    fn decode_chords() {
+        let test_cases = [
+            ("typed", 'q' as u32, 0x51, 0, 'q'),
+            ("control character", 0x13, 0x53, LEFT_CTRL_PRESSED, '\x13'),
+            (
+                "letter with control",
+                'S' as u32,
+                0x53,
+                RIGHT_CTRL_PRESSED,
+                '\x13',
+            ),
+            ("only the key code", 0, 0x51, LEFT_CTRL_PRESSED, '\x11'),
+            ("control alone", 0, 0x11, LEFT_CTRL_PRESSED, '\0'),
+            (
+                "symbol with alt gr",
+                '@' as u32,
+                0x51,
+                LEFT_CTRL_PRESSED | 0x2,
+                '@',
+            ),
+        ];
+        for (test_case, unicode, virtual_key, state, expected) in test_cases {
+            assert_eq!(
+                decode_char(unicode, virtual_key, state),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    fs,
+    io::BufRead,
+    path::Path,
+    sync::mpsc::{Receiver, Sender},
+    thread::{spawn, JoinHandle},
+};
+
+use super::input_record::{Key, KeyEvent};
+use crate::conway::key_bindings::key_name;
+
+/// Where a game gets its key events from, so that a game runs the same on the console, in
+/// tests and from a replay
+pub trait InputSource: Send {
+    /// Take the next event without waiting for one. Called once per iteration of the game loop
+    ///
+    /// # Returns
+    ///
+    /// The event, or `None` if there is no event for this iteration
+    fn poll(&mut self) -> Option<KeyEvent>;
+    /// Take the events for this iteration of the game loop without waiting for one. A source
+    /// that hands out an event per poll only takes the next one
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - the most events to take, the rest wait for the next iteration
+    fn drain(&mut self, max: usize) -> Vec<KeyEvent> {
+        self.poll().into_iter().take(max).collect()
+    }
+}
+
+/// The channel the console input thread sends its key events on. Held keys send an event per
+/// repeat, so every event that is waiting is taken at once
+impl InputSource for Receiver<KeyEvent> {
+    fn poll(&mut self) -> Option<KeyEvent> {
+        self.try_recv().ok()
+    }
+    fn drain(&mut self, max: usize) -> Vec<KeyEvent> {
+        self.try_iter().take(max).collect()
+    }
+}
+
+/// Key events that are handed out one per poll, in order
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::console::input_source::ScriptedInput;
+/// let input = ScriptedInput::from_keys("  q");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ScriptedInput {
+    events: VecDeque<KeyEvent>,
+}
+impl ScriptedInput {
+    pub fn new(events: Vec<KeyEvent>) -> Self {
+        ScriptedInput {
+            events: events.into(),
+        }
+    }
+    /// Script a press of every key in a text
+    pub fn from_keys(keys: &str) -> Self {
+        ScriptedInput::new(keys.chars().map(KeyEvent::key_down).collect())
+    }
+    /// Get the number of events that were not polled yet
+    pub fn remaining(&self) -> usize {
+        self.events.len()
+    }
+}
+impl InputSource for ScriptedInput {
+    fn poll(&mut self) -> Option<KeyEvent> {
+        self.events.pop_front()
+    }
+}
+
+/// Why a replay could not be read
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    ReadFailed,
+    /// A line is not a poll number followed by a key, the line number starts at 1
+    BadLine(usize),
+}
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ReadFailed => write!(f, "The replay could not be read"),
+            Self::BadLine(line) => write!(f, "Line {} of the replay is not valid", line),
+        }
+    }
+}
+
+/// Key events played back at the polls they were recorded at
+///
+/// A replay file has an event per line, the number of the poll followed by the key. Keys are
+/// written like the help overlay shows them, so `space`, `enter` and `esc` are named. Events for
+/// the same poll are delayed to the following polls, empty lines and lines starting with `#` are
+/// skipped
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::console::input_source::{ReplayError, ReplayInput};
+/// # fn main() -> Result<(), ReplayError> {
+/// let input = ReplayInput::parse("# pause and quit\n0 space\n10 q")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReplayInput {
+    events: VecDeque<(u64, KeyEvent)>,
+    polls: u64,
+}
+impl ReplayInput {
+    /// Read a replay from the text of a replay file
+    pub fn parse(text: &str) -> Result<Self, ReplayError> {
+        let mut events = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (poll, key) = line
+                .split_once(' ')
+                .ok_or(ReplayError::BadLine(index + 1))?;
+            let poll: u64 = poll.parse().map_err(|_| ReplayError::BadLine(index + 1))?;
+            let key = parse_key(key).ok_or(ReplayError::BadLine(index + 1))?;
+            events.push((poll, KeyEvent::key_down(key)));
+        }
+        // A stable sort keeps the order of the events of one poll
+        events.sort_by_key(|(poll, _)| *poll);
+        Ok(ReplayInput {
+            events: events.into(),
+            polls: 0,
+        })
+    }
+    /// Read a replay file
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        let text = fs::read_to_string(path).map_err(|_| ReplayError::ReadFailed)?;
+        ReplayInput::parse(&text)
+    }
+    /// Write events in the format that `parse` reads
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - the poll every key was pressed at with the key
+    pub fn format(events: &[(u64, KeyEvent)]) -> String {
+        events
+            .iter()
+            .map(|(poll, event)| format!("{} {}\n", poll, key_name(event.command)))
+            .collect()
+    }
+    /// Check if every event was played
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+impl InputSource for ReplayInput {
+    fn poll(&mut self) -> Option<KeyEvent> {
+        let poll = self.polls;
+        self.polls += 1;
+        match self.events.front() {
+            Some((at, _)) if *at <= poll => self.events.pop_front().map(|(_, event)| event),
+            _ => None,
+        }
+    }
+}
+
+/// Send a key event for every character of the lines that are read, for terminals that are not
+/// switched to raw input and only pass on what was typed once enter is pressed. An empty line is
+/// sent as enter, so that menus and text fields can be confirmed. Stops at the end
+/// of the input or once the game stopped taking events, so a quit key typed into a text field
+/// does not stop the input
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::{io::BufReader, sync::mpsc::channel};
+/// # use tic_tac_toe2::console::{input_record::KeyEvent, input_source::listen_and_notify_line_inputs};
+/// # let (sender, receiver) = channel::<KeyEvent>();
+/// let (sender, receiver) = channel();
+/// let input = listen_and_notify_line_inputs(BufReader::new(std::io::stdin()), sender);
+/// ```
+pub fn listen_and_notify_line_inputs(
+    mut reader: impl BufRead + Send + 'static,
+    sender: Sender<KeyEvent>,
+) -> JoinHandle<()> {
+    spawn(move || {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => (),
+            }
+            let keys = match line.trim_end_matches(['\r', '\n']) {
+                "" => "\r",
+                keys => keys,
+            };
+            for key in keys.chars() {
+                if sender.send(KeyEvent::key_down(key)).is_err() {
+                    return;
+                }
+            }
+        }
+    })
+}
+
+/// Read a key the way `key_name` writes it
+fn parse_key(text: &str) -> Option<char> {
+    Key::parse(text).map(Key::to_char)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufReader, Cursor, Read},
+        sync::mpsc,
+    };
+
+    use crate::console::input_record::KeyEvent;
+
+    use super::{
+        listen_and_notify_line_inputs, InputSource, ReplayError, ReplayInput, ScriptedInput,
+    };
+
+    /// Input that types `q` and enter forever
+    struct EndlessQuits;
+    impl Read for EndlessQuits {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            for (index, byte) in buf.iter_mut().enumerate() {
+                *byte = [b'q', b'\n'][index % 2];
+            }
+            Ok(buf.len())
+        }
+    }
+
+    fn poll_keys(input: &mut dyn InputSource, polls: usize) -> String {
+        (0..polls)
+            .map(|_| input.poll().map_or('.', |event| event.command))
+            .collect()
+    }
+
+    #[test]
+    fn sources() {
+        let (sender, receiver) = mpsc::channel();
+        sender.send(KeyEvent::key_down('a')).unwrap();
+        sender.send(KeyEvent::key_down('b')).unwrap();
+        let replay = ReplayInput::parse("# comment\n\n1 x\n3 space\n1 y\n").unwrap();
+        let test_cases: [(&str, Box<dyn InputSource>, &str); 3] = [
+            ("channel", Box::new(receiver), "ab.."),
+            ("script", Box::new(ScriptedInput::from_keys("wsd")), "wsd."),
+            ("replay", Box::new(replay), ".xy "),
+        ];
+        for (test_case, mut input, expected) in test_cases {
+            assert_eq!(poll_keys(input.as_mut(), 4), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn drains() {
+        let (sender, receiver) = mpsc::channel();
+        for key in "wwwwd".chars() {
+            sender.send(KeyEvent::key_down(key)).unwrap();
+        }
+        let replay = ReplayInput::parse("0 x\n0 y\n").unwrap();
+        let test_cases: [(&str, Box<dyn InputSource>, [&str; 3]); 3] = [
+            ("channel", Box::new(receiver), ["www", "wd", ""]),
+            (
+                "script",
+                Box::new(ScriptedInput::from_keys("ws")),
+                ["w", "s", ""],
+            ),
+            ("replay", Box::new(replay), ["x", "y", ""]),
+        ];
+        for (test_case, mut input, expected) in test_cases {
+            for (drain, expected) in expected.into_iter().enumerate() {
+                let keys: String = input.drain(3).iter().map(|event| event.command).collect();
+                assert_eq!(keys, expected, "{} drain {}", test_case, drain);
+            }
+        }
+    }
+
+    #[test]
+    fn line_inputs() {
+        let test_cases = [
+            ("keys of every line", "ws\r\nd\n", "wsd"),
+            ("an empty line is enter", "s\n\n\r\n", "s\r\r"),
+            ("keys after the quit key", "wq\n\x11d\n", "wq\x11d"),
+            ("end without a newline", "ws", "ws"),
+        ];
+        for (test_case, text, expected) in test_cases {
+            let (sender, receiver) = mpsc::channel();
+            listen_and_notify_line_inputs(Cursor::new(text.to_string()), sender)
+                .join()
+                .unwrap();
+            let keys: String = receiver.try_iter().map(|event| event.command).collect();
+            assert_eq!(keys, expected, "{}", test_case);
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        drop(receiver);
+        assert!(
+            listen_and_notify_line_inputs(BufReader::new(EndlessQuits), sender)
+                .join()
+                .is_ok(),
+            "Stops once the game stopped taking events"
+        );
+    }
+
+    #[test]
+    fn replay_format() {
+        let events = vec![
+            (0, KeyEvent::key_down(' ')),
+            (4, KeyEvent::key_down('\x1b')),
+            (6, KeyEvent::key_down('\x1a')),
+            (9, KeyEvent::key_down('q')),
+        ];
+        let text = ReplayInput::format(&events);
+        assert_eq!(text, "0 space\n4 esc\n6 ctrl+z\n9 q\n");
+        let mut replay = ReplayInput::parse(&text).unwrap();
+        assert_eq!(poll_keys(&mut replay, 10), " ...\x1b.\x1a..q");
+        assert!(replay.is_finished());
+
+        let test_cases = [
+            ("no key", "1 x\n2", ReplayError::BadLine(2)),
+            ("bad poll", "-1 x", ReplayError::BadLine(1)),
+            ("unknown name", "\n3 shift", ReplayError::BadLine(2)),
+            ("control without letter", "3 ctrl+", ReplayError::BadLine(1)),
+        ];
+        for (test_case, text, expected) in test_cases {
+            assert_eq!(
+                ReplayInput::parse(text).err(),
+                Some(expected),
+                "{}",
+                test_case
+            );
+        }
+    }
+}
@@ -24,7 +24,7 @@ impl Display for ConsoleMode {
 }
 impl ConsoleMode {
     pub fn update_mode(current_mode: CONSOLE_MODE, new_mode: Self) -> CONSOLE_MODE {
-        let mut mode = current_mode.clone();
+        let mut mode = current_mode;
         match new_mode {
             ConsoleMode::Cooked => {
                 mode |= ENABLE_ECHO_INPUT;
@@ -36,11 +36,7 @@ impl ConsoleMode {
                 mode &= !ENABLE_LINE_INPUT;
                 mode &= !ENABLE_PROCESSED_INPUT;
             }
-            _ => assert!(
-                false,
-                "Why are you trying to set the mode to '{}'?",
-                new_mode
-            ),
+            _ => panic!("Why are you trying to set the mode to '{}'?", new_mode),
         }
 
         mode
@@ -66,9 +62,7 @@ impl ConsoleMode {
             {
                 Ok(ConsoleMode::UncookedPartial)
             }
-            _ => {
-                return Err(ConsoleControlErr::ModeTypeUnknown);
-            }
+            _ => Err(ConsoleControlErr::ModeTypeUnknown),
         }
     }
 }
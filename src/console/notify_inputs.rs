@@ -8,6 +8,9 @@ use super::{
     mode::ConsoleMode,
 };
 
+/// Send the key events of the console in raw mode until the game stopped taking them, so a quit
+/// key typed into a text field does not stop the input. The console is set back to cooked mode
+/// after the next key once the game stopped, the program does that itself when it exits first
 pub fn listen_and_notify_key_inputs(
     sender: Sender<KeyEvent>,
 ) -> JoinHandle<Result<(), ConsoleControlErr>> {
@@ -21,19 +24,8 @@ pub fn listen_and_notify_key_inputs(
             // TODO: Handle this err
             match cm.read_console_input() {
                 Ok(ch) => {
-                    match sender.send(ch.clone()) {
-                        Ok(_) => (),
-                        Err(e) => {
-                            // TODO: this is bad
-                            eprintln!(
-                                "Exception occurred when sending command on channel with error: {}",
-                                e
-                            );
-                            break;
-                        }
-                    }
-
-                    if ch.command == 'q' {
+                    // The receiver is dropped once the game stopped
+                    if sender.send(ch).is_err() {
                         break;
                     }
                 }
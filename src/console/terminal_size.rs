@@ -0,0 +1,76 @@
+use crate::shared::usize2d::Usize2d;
+
+/// Get the number of columns and rows of the terminal the process writes to
+///
+/// # Returns
+///
+/// The size as `Usize2d { x: columns, y: rows }`, or `None` when standard out is not a terminal
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::console::terminal_size::terminal_size;
+/// if let Some(size) = terminal_size() {
+///     println!("{} columns, {} rows", size.x, size.y);
+/// }
+/// ```
+pub fn terminal_size() -> Option<Usize2d> {
+    query_terminal_size()
+}
+
+#[cfg(feature = "crossterm-backend")]
+fn query_terminal_size() -> Option<Usize2d> {
+    match crossterm::terminal::size() {
+        Ok((columns, rows)) if columns > 0 && rows > 0 => {
+            Some(Usize2d::new(columns as usize, rows as usize))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(all(
+    windows,
+    feature = "windows-console",
+    not(feature = "crossterm-backend")
+))]
+fn query_terminal_size() -> Option<Usize2d> {
+    use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows_sys::Win32::System::Console::{
+        GetConsoleScreenBufferInfo, GetStdHandle, CONSOLE_SCREEN_BUFFER_INFO, STD_OUTPUT_HANDLE,
+    };
+
+    let handle = unsafe { GetStdHandle(STD_OUTPUT_HANDLE) };
+    if handle == INVALID_HANDLE_VALUE {
+        return None;
+    }
+    let mut info: CONSOLE_SCREEN_BUFFER_INFO = unsafe { std::mem::zeroed() };
+    let success = unsafe { GetConsoleScreenBufferInfo(handle, &mut info) };
+    if success == 0 {
+        return None;
+    }
+    // The window is the visible part of the screen buffer, its bounds are inclusive
+    let window = info.srWindow;
+    Some(Usize2d::new(
+        (window.Right - window.Left + 1).max(0) as usize,
+        (window.Bottom - window.Top + 1).max(0) as usize,
+    ))
+}
+
+#[cfg(all(unix, feature = "unix-console", not(feature = "crossterm-backend")))]
+fn query_terminal_size() -> Option<Usize2d> {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+    if result != 0 || size.ws_col == 0 || size.ws_row == 0 {
+        return None;
+    }
+    Some(Usize2d::new(size.ws_col as usize, size.ws_row as usize))
+}
+
+#[cfg(not(any(
+    all(windows, feature = "windows-console"),
+    all(unix, feature = "unix-console"),
+    feature = "crossterm-backend"
+)))]
+fn query_terminal_size() -> Option<Usize2d> {
+    None
+}
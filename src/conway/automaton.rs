@@ -0,0 +1,70 @@
+use crate::shared::usize2d::Usize2d;
+
+/// A cellular automaton on a grid where every cell changes at the same time, once per
+/// generation, by the cells around it
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::conway::automaton::Automaton;
+/// fn run<A: Automaton>(automaton: &mut A, generations: u64) {
+///     for _ in 0..generations {
+///         automaton.next();
+///     }
+/// }
+/// ```
+pub trait Automaton {
+    /// The state of a single cell
+    type Cell: Copy + PartialEq;
+    /// Calculate the next generation
+    fn next(&mut self);
+    /// Get the cells of the current generation, indexed as `cells[y][x]`
+    fn cells(&self) -> &Vec<Vec<Self::Cell>>;
+    /// Get the number of columns and rows of the grid
+    fn size(&self) -> Usize2d {
+        let cells = self.cells();
+        Usize2d::new(cells.first().map_or(0, |row| row.len()), cells.len())
+    }
+}
+
+/// Get the eight cells around a cell, wrapping around the edges of the grid
+///
+/// # Arguments
+///
+/// * `cells` - the grid, indexed as `cells[y][x]`
+/// * `x` - the column of the cell
+/// * `y` - the row of the cell
+pub fn moore_neighbors<T: Copy>(cells: &[Vec<T>], x: usize, y: usize) -> Vec<T> {
+    let (x_len, y_len) = (cells[0].len() as i64, cells.len() as i64);
+    let mut neighbors = Vec::with_capacity(8);
+    for y_delta in -1i64..=1 {
+        for x_delta in -1i64..=1 {
+            if x_delta == 0 && y_delta == 0 {
+                continue;
+            }
+            let neighbor_x = (x as i64 + x_delta).rem_euclid(x_len) as usize;
+            let neighbor_y = (y as i64 + y_delta).rem_euclid(y_len) as usize;
+            neighbors.push(cells[neighbor_y][neighbor_x]);
+        }
+    }
+    neighbors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::moore_neighbors;
+
+    #[test]
+    fn neighbors() {
+        let cells: Vec<Vec<usize>> = (0..3)
+            .map(|y| (0..4).map(|x| y * 4 + x).collect())
+            .collect();
+        let test_cases = [
+            ("inside", (1, 1), vec![0, 1, 2, 4, 6, 8, 9, 10]),
+            ("corner", (0, 0), vec![11, 8, 9, 3, 1, 7, 4, 5]),
+        ];
+        for (test_case, (x, y), expected) in test_cases {
+            assert_eq!(moore_neighbors(&cells, x, y), expected, "{}", test_case);
+        }
+    }
+}
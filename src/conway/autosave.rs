@@ -0,0 +1,152 @@
+use std::{
+    env, fs,
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+use super::save::{SaveError, SavedGame};
+
+/// The name of the autosave file in the temp directory
+pub const AUTOSAVE_FILE_NAME: &str = "conways_game_autosave.json";
+
+/// A file that a running game is saved to every now and then
+///
+/// The file is removed when the session shuts down cleanly. If it is still there on the next
+/// launch the previous session crashed and the game can be resumed from it
+pub struct Autosave {
+    path: PathBuf,
+}
+impl Default for Autosave {
+    fn default() -> Self {
+        Autosave::new(env::temp_dir().join(AUTOSAVE_FILE_NAME))
+    }
+}
+impl Autosave {
+    pub fn new(path: PathBuf) -> Self {
+        Autosave { path }
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// Replace the autosave with the current state of a game
+    pub fn write(&self, game: &SavedGame) -> Result<(), SaveError> {
+        game.save(&self.path)
+    }
+    /// Get the game that was left behind by a session that did not shut down cleanly
+    ///
+    /// # Returns
+    ///
+    /// `None` if there is no autosave or it can not be read
+    pub fn recover(&self) -> Option<SavedGame> {
+        if !self.path.exists() {
+            return None;
+        }
+        SavedGame::load(&self.path).ok()
+    }
+    /// Remove the autosave, called when the session shuts down cleanly
+    pub fn discard(&self) {
+        let _ = fs::remove_file(&self.path);
+    }
+    /// Ask the player if the game of a session that did not shut down cleanly should be resumed.
+    /// Should be called before the terminal is switched to raw mode
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - the answer of the player is read from here, e.g. stdin
+    /// * `output` - the question is written here, e.g. stdout
+    ///
+    /// # Returns
+    ///
+    /// The recovered game if the player answered yes. Otherwise the autosave is discarded
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// # use tic_tac_toe2::conway::autosave::Autosave;
+    /// let autosave = Autosave::default();
+    /// let resumed = autosave.offer_resume(&mut io::stdin().lock(), &mut io::stdout());
+    /// ```
+    pub fn offer_resume(
+        &self,
+        input: &mut dyn BufRead,
+        output: &mut dyn Write,
+    ) -> Option<SavedGame> {
+        let saved = self.recover()?;
+        let _ = write!(
+            output,
+            "The previous session did not exit cleanly (round {}). Resume it? [y/N] ",
+            saved.rounds
+        );
+        let _ = output.flush();
+        let mut answer = String::new();
+        let _ = input.read_line(&mut answer);
+        if matches!(answer.trim(), "y" | "Y" | "yes") {
+            return Some(saved);
+        }
+        self.discard();
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use crate::conway::{rule::Rule, save::SavedGame};
+
+    use super::Autosave;
+
+    fn autosave(name: &str) -> Autosave {
+        Autosave::new(env::temp_dir().join(format!(
+            "conway_autosave_test_{}_{}.json",
+            name,
+            process::id()
+        )))
+    }
+
+    #[test]
+    fn recover_after_crash() {
+        let autosave = autosave("recover");
+        assert_eq!(
+            autosave.recover(),
+            None,
+            "Nothing to recover after a clean exit"
+        );
+
+        let game = SavedGame::new(&[vec![true, false]], 1, Rule::conway(), 12);
+        assert_eq!(autosave.write(&game), Ok(()));
+        assert_eq!(autosave.recover(), Some(game));
+
+        autosave.discard();
+        assert!(!autosave.path().exists());
+        assert_eq!(autosave.recover(), None);
+    }
+
+    #[test]
+    fn offer_resume() {
+        let test_cases = vec![(1, "y\n", true), (2, "n\n", false), (3, "\n", false)];
+        for (test_case, answer, expected_resume) in test_cases {
+            let autosave = autosave(&format!("offer_{}", test_case));
+            let game = SavedGame::new(&[vec![true]], 1, Rule::conway(), 12);
+            assert_eq!(autosave.write(&game), Ok(()));
+
+            let mut output = Vec::new();
+            let resumed = autosave.offer_resume(&mut answer.as_bytes(), &mut output);
+            assert_eq!(
+                resumed.is_some(),
+                expected_resume,
+                "Test case {}: The answer was not followed",
+                test_case
+            );
+            assert!(String::from_utf8(output).unwrap().contains("round 12"));
+            assert_eq!(
+                autosave.path().exists(),
+                expected_resume,
+                "Test case {}: A declined autosave should be removed",
+                test_case
+            );
+            autosave.discard();
+        }
+    }
+}
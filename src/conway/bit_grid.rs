@@ -0,0 +1,329 @@
+use super::{rule::Rule, settings::Boundary};
+
+/// The number of cells packed in a word
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A Game of Life board with a bit per cell, a row is packed in words of 64 cells
+///
+/// Bit `i` of word `w` of a row is the cell at `x = 64 * w + i`. The bits beyond the width of
+/// the board are always 0
+///
+/// A generation is calculated with `step`. With the `simd` feature a word of 64 cells is
+/// calculated at once with bit tricks, otherwise the neighbors of every cell are counted one by
+/// one like `ConwaysGame` does
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{conway::{bit_grid::BitGrid, settings::Boundary}, ConwaysGame, Rule};
+/// # let mut game = ConwaysGame::headless(16, 16, 42);
+/// let grid = BitGrid::from_cells(game.cells(), Boundary::Wrap);
+/// let next = grid.step(Rule::conway());
+/// game.step();
+/// assert_eq!(&next.to_cells(), game.cells());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+    boundary: Boundary,
+}
+impl BitGrid {
+    /// Create a board of dead cells
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the number of columns, at least one
+    /// * `height` - the number of rows, at least one
+    /// * `boundary` - what is beyond the edges of the board
+    pub fn new(width: usize, height: usize, boundary: Boundary) -> Self {
+        assert!(width > 0 && height > 0, "The board needs at least one cell");
+        let words_per_row = width.div_ceil(WORD_BITS);
+        BitGrid {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+            boundary,
+        }
+    }
+    /// Pack a board of cells, indexed as `cells[y][x]`
+    pub fn from_cells(cells: &[Vec<bool>], boundary: Boundary) -> Self {
+        let mut grid = BitGrid::new(cells[0].len(), cells.len(), boundary);
+        for (y, row) in cells.iter().enumerate() {
+            for (x, is_alive) in row.iter().enumerate() {
+                grid.set(x, y, *is_alive);
+            }
+        }
+        grid
+    }
+    /// Unpack the board into cells, indexed as `cells[y][x]`
+    pub fn to_cells(&self) -> Vec<Vec<bool>> {
+        (0..self.height)
+            .map(|y| (0..self.width).map(|x| self.get(x, y)).collect())
+            .collect()
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.words[self.word_index(x, y)] >> (x % WORD_BITS) & 1 == 1
+    }
+    pub fn set(&mut self, x: usize, y: usize, is_alive: bool) {
+        assert!(
+            x < self.width && y < self.height,
+            "The cell is not on the board"
+        );
+        let index = self.word_index(x, y);
+        let bit = 1 << (x % WORD_BITS);
+        match is_alive {
+            true => self.words[index] |= bit,
+            false => self.words[index] &= !bit,
+        }
+    }
+    /// Get the number of living cells
+    pub fn population(&self) -> u64 {
+        self.words.iter().map(|word| word.count_ones() as u64).sum()
+    }
+    fn word_index(&self, x: usize, y: usize) -> usize {
+        y * self.words_per_row + x / WORD_BITS
+    }
+    /// Calculate the next generation, with the fastest path that was compiled in
+    pub fn step(&self, rule: Rule) -> BitGrid {
+        #[cfg(feature = "simd")]
+        return self.step_simd(rule);
+        #[cfg(not(feature = "simd"))]
+        return self.step_scalar(rule);
+    }
+    /// Calculate the next generation by counting the neighbors of every cell
+    pub fn step_scalar(&self, rule: Rule) -> BitGrid {
+        let mut next = BitGrid::new(self.width, self.height, self.boundary);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let is_alive = rule.is_alive(self.get(x, y), self.count_neighbors(x, y));
+                next.set(x, y, is_alive);
+            }
+        }
+        next
+    }
+    /// Count the living neighbors of a cell. On a tiny wrapping board a cell that is reached
+    /// from more than one side is counted once per side, like `ConwaysGame` does
+    fn count_neighbors(&self, x: usize, y: usize) -> u8 {
+        let mut count = 0;
+        for y_delta in -1i64..=1 {
+            for x_delta in -1i64..=1 {
+                if x_delta == 0 && y_delta == 0 {
+                    continue;
+                }
+                let (x, y) = (x as i64 + x_delta, y as i64 + y_delta);
+                let (width, height) = (self.width as i64, self.height as i64);
+                let is_outside = x < 0 || y < 0 || x >= width || y >= height;
+                if is_outside && self.boundary == Boundary::Dead {
+                    continue;
+                }
+                let (x, y) = (x.rem_euclid(width), y.rem_euclid(height));
+                count += self.get(x as usize, y as usize) as u8;
+            }
+        }
+        count
+    }
+}
+
+#[cfg(feature = "simd")]
+impl BitGrid {
+    /// Calculate the next generation a word of 64 cells at a time
+    ///
+    /// The eight neighbors of the cells of a word are eight words, each shifted by a cell. They
+    /// are added up bit by bit into four words that hold the bits of the counts of the 64
+    /// cells, and the rule picks the counts that are born or survive
+    pub fn step_simd(&self, rule: Rule) -> BitGrid {
+        let masks = RuleMasks::new(rule);
+        let mut next = BitGrid::new(self.width, self.height, self.boundary);
+        for y in 0..self.height {
+            let above = self.neighbor_row(y, -1);
+            let below = self.neighbor_row(y, 1);
+            for word in 0..self.words_per_row {
+                let mut counts = [0u64; 4];
+                for row in [above, below].into_iter().flatten() {
+                    add(&mut counts, self.west(row, word));
+                    add(&mut counts, self.words[row * self.words_per_row + word]);
+                    add(&mut counts, self.east(row, word));
+                }
+                add(&mut counts, self.west(y, word));
+                add(&mut counts, self.east(y, word));
+                let alive = self.words[y * self.words_per_row + word];
+                let next_word = (alive & masks.select(&counts, &masks.survival))
+                    | (!alive & masks.select(&counts, &masks.birth));
+                next.words[y * self.words_per_row + word] = next_word & self.word_mask(word);
+            }
+        }
+        next
+    }
+    /// Get the row above or below a row, `None` if it is beyond a dead edge
+    fn neighbor_row(&self, y: usize, delta: i64) -> Option<usize> {
+        let row = y as i64 + delta;
+        if (row < 0 || row >= self.height as i64) && self.boundary == Boundary::Dead {
+            return None;
+        }
+        Some(row.rem_euclid(self.height as i64) as usize)
+    }
+    /// Get the cells west of the cells of a word, so bit `i` is the cell at `x - 1`
+    fn west(&self, row: usize, word: usize) -> u64 {
+        let words = &self.words[row * self.words_per_row..(row + 1) * self.words_per_row];
+        let carry = match word {
+            0 if self.boundary == Boundary::Wrap => {
+                words[self.words_per_row - 1] >> ((self.width - 1) % WORD_BITS) & 1
+            }
+            0 => 0,
+            _ => words[word - 1] >> (WORD_BITS - 1),
+        };
+        words[word] << 1 | carry
+    }
+    /// Get the cells east of the cells of a word, so bit `i` is the cell at `x + 1`
+    fn east(&self, row: usize, word: usize) -> u64 {
+        let words = &self.words[row * self.words_per_row..(row + 1) * self.words_per_row];
+        let last = self.words_per_row - 1;
+        let carry = match word {
+            _ if word == last && self.boundary == Boundary::Wrap => {
+                (words[0] & 1) << ((self.width - 1) % WORD_BITS)
+            }
+            _ if word == last => 0,
+            _ => (words[word + 1] & 1) << (WORD_BITS - 1),
+        };
+        words[word] >> 1 | carry
+    }
+    /// Get the bits of a word that are cells of the board
+    fn word_mask(&self, word: usize) -> u64 {
+        let cells = (self.width - word * WORD_BITS).min(WORD_BITS);
+        match cells {
+            WORD_BITS => u64::MAX,
+            _ => (1 << cells) - 1,
+        }
+    }
+}
+
+/// Add a bit to the count of every cell of a word, the counts are four words with a bit of the
+/// count each, the lowest bit first
+#[cfg(feature = "simd")]
+fn add(counts: &mut [u64; 4], bits: u64) {
+    let mut carry = bits;
+    for bit in counts.iter_mut() {
+        let next_carry = *bit & carry;
+        *bit ^= carry;
+        carry = next_carry;
+    }
+}
+
+/// The neighbor counts at which a rule lets a cell be born or survive
+#[cfg(feature = "simd")]
+struct RuleMasks {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+#[cfg(feature = "simd")]
+impl RuleMasks {
+    fn new(rule: Rule) -> Self {
+        RuleMasks {
+            birth: std::array::from_fn(|count| rule.is_alive(false, count as u8)),
+            survival: std::array::from_fn(|count| rule.is_alive(true, count as u8)),
+        }
+    }
+    /// Get the cells of a word whose count is one of the counts that are set
+    fn select(&self, counts: &[u64; 4], selected: &[bool; 9]) -> u64 {
+        let mut cells = 0;
+        for (count, _) in selected.iter().enumerate().filter(|(_, is_set)| **is_set) {
+            cells |= counts
+                .iter()
+                .enumerate()
+                .fold(u64::MAX, |cells, (bit, word)| match count >> bit & 1 {
+                    1 => cells & word,
+                    _ => cells & !word,
+                });
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::conway::{rule::Rule, settings::Boundary};
+
+    use super::BitGrid;
+
+    /// A random board with about a third of the cells alive
+    fn random_cells(width: usize, height: usize, seed: u64) -> Vec<Vec<bool>> {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        (0..height)
+            .map(|_| (0..width).map(|_| rng.gen_ratio(1, 3)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn packs_cells() {
+        let cells = random_cells(70, 3, 1);
+        let grid = BitGrid::from_cells(&cells, Boundary::Wrap);
+        assert_eq!(grid.to_cells(), cells);
+        let alive = cells.iter().flatten().filter(|is_alive| **is_alive).count();
+        assert_eq!(grid.population(), alive as u64);
+    }
+
+    #[test]
+    fn steps_like_the_neighbor_counts() {
+        use crate::conway::neighbor_counts::NeighborCounts;
+
+        for boundary in [Boundary::Wrap, Boundary::Dead] {
+            let mut cells = random_cells(23, 17, 4);
+            let mut grid = BitGrid::from_cells(&cells, boundary);
+            let mut counts = NeighborCounts::new(&cells, Rule::conway(), boundary);
+            let mut next = cells.clone();
+            for round in 0..20 {
+                grid = grid.step_scalar(Rule::conway());
+                counts.step(&cells, &mut next);
+                std::mem::swap(&mut cells, &mut next);
+                assert_eq!(grid.to_cells(), cells, "{} round {}", boundary, round);
+            }
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_matches_scalar() {
+        let sizes = [
+            (1, 1),
+            (2, 1),
+            (3, 2),
+            (63, 5),
+            (64, 4),
+            (65, 7),
+            (130, 9),
+            (128, 1),
+        ];
+        let rules = [
+            Rule::conway(),
+            "B36/S23".parse().unwrap(),
+            Rule::new(&[0, 1], &[8]),
+        ];
+        for (width, height) in sizes {
+            for boundary in [Boundary::Wrap, Boundary::Dead] {
+                for rule in rules {
+                    let test_case = format!("{}x{} {} {}", width, height, boundary, rule);
+                    let mut scalar = BitGrid::from_cells(&random_cells(width, height, 7), boundary);
+                    let mut simd = scalar.clone();
+                    for round in 0..8 {
+                        scalar = scalar.step_scalar(rule);
+                        simd = simd.step_simd(rule);
+                        assert_eq!(simd, scalar, "{} round {}", test_case, round);
+                    }
+                }
+            }
+        }
+    }
+}
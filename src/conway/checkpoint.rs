@@ -0,0 +1,121 @@
+use super::rule::Rule;
+
+/// The number of checkpoint slots, one per digit key
+pub const SLOT_COUNT: usize = 9;
+/// The keys that save to the slots, which are Shift+1 to Shift+9 on a US keyboard
+const SAVE_KEYS: [char; SLOT_COUNT] = ['!', '@', '#', '$', '%', '^', '&', '*', '('];
+
+/// What a key does with the checkpoint slots
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlotKey {
+    /// Keep the game in a slot
+    Save(usize),
+    /// Continue the game from a slot
+    Load(usize),
+}
+impl SlotKey {
+    /// Find the slot a key saves to or loads from, the slots are numbered from 1
+    ///
+    /// # Returns
+    ///
+    /// `None` if the key is not a digit from 1 to 9 or a shifted digit
+    pub fn from_key(key: char) -> Option<Self> {
+        if let Some(index) = SAVE_KEYS.iter().position(|save_key| *save_key == key) {
+            return Some(SlotKey::Save(index + 1));
+        }
+        match key.to_digit(10) {
+            Some(slot @ 1..=9) => Some(SlotKey::Load(slot as usize)),
+            _ => None,
+        }
+    }
+}
+
+/// The state of a simulation, kept in memory so that a game can be continued from it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    /// The cells of the generation, indexed as `cells[y][x]`
+    pub cells: Vec<Vec<bool>>,
+    /// The cells of the generation before, so that a stable board stays stable
+    pub previous: Vec<Vec<bool>>,
+    pub rounds: u64,
+    pub seed: u64,
+    pub rule: Rule,
+}
+
+/// The quick save slots of a game. Nothing is written to disk, the slots are lost when the game
+/// is closed
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{conway::checkpoint::{Checkpoint, Checkpoints, SlotKey}, Rule};
+/// # let (key, mut checkpoints) = ('1', Checkpoints::default());
+/// # let board = vec![vec![false; 4]; 4];
+/// # let mut checkpoint = Checkpoint { cells: board.clone(), previous: board, rounds: 0, seed: 42, rule: Rule::conway() };
+/// match SlotKey::from_key(key) {
+///     Some(SlotKey::Save(slot)) => checkpoints.save(slot, checkpoint.clone()),
+///     Some(SlotKey::Load(slot)) => checkpoint = checkpoints.get(slot).cloned().unwrap_or(checkpoint),
+///     None => {}
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Checkpoints {
+    slots: [Option<Checkpoint>; SLOT_COUNT],
+}
+impl Checkpoints {
+    /// Keep a checkpoint in a slot, replacing the checkpoint that was in it
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - the slot, from 1 to `SLOT_COUNT`
+    /// * `checkpoint` - the state to keep
+    pub fn save(&mut self, slot: usize, checkpoint: Checkpoint) {
+        self.slots[slot - 1] = Some(checkpoint);
+    }
+    /// Get the checkpoint of a slot, `None` if nothing was saved to it
+    pub fn get(&self, slot: usize) -> Option<&Checkpoint> {
+        self.slots.get(slot.checked_sub(1)?)?.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::rule::Rule;
+
+    use super::{Checkpoint, Checkpoints, SlotKey};
+
+    #[test]
+    fn keys() {
+        let test_cases = [
+            ("first digit", '1', Some(SlotKey::Load(1))),
+            ("last digit", '9', Some(SlotKey::Load(9))),
+            ("zero", '0', None),
+            ("shift 1", '!', Some(SlotKey::Save(1))),
+            ("shift 9", '(', Some(SlotKey::Save(9))),
+            ("letter", 'a', None),
+        ];
+        for (test_case, key, expected) in test_cases {
+            assert_eq!(SlotKey::from_key(key), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn slots() {
+        let checkpoint = |rounds: u64| Checkpoint {
+            cells: vec![vec![true]],
+            previous: vec![vec![false]],
+            rounds,
+            seed: 1,
+            rule: Rule::conway(),
+        };
+        let mut checkpoints = Checkpoints::default();
+        checkpoints.save(3, checkpoint(10));
+        checkpoints.save(9, checkpoint(20));
+        checkpoints.save(3, checkpoint(30));
+        assert_eq!(checkpoints.get(3).map(|c| c.rounds), Some(30));
+        assert_eq!(checkpoints.get(9).map(|c| c.rounds), Some(20));
+        assert!(checkpoints.get(1).is_none());
+        assert!(checkpoints.get(0).is_none());
+        assert!(checkpoints.get(10).is_none());
+    }
+}
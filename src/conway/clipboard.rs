@@ -0,0 +1,416 @@
+use std::path::Path;
+
+use crate::shared::square::Square;
+
+use super::rule::Rule;
+
+/// The longest line of an RLE file
+const RLE_LINE_WIDTH: usize = 70;
+
+/// Why a pattern file could not be read
+#[derive(Debug, PartialEq, Eq)]
+pub enum PatternFileError {
+    ReadFailed,
+    /// The file is not in the RLE or the plaintext format
+    BadFormat,
+}
+
+/// A region of the board that was copied in the editor, kept like a pattern so that it can be
+/// pasted anywhere or written to an RLE file
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::fs;
+/// # use tic_tac_toe2::{conway::clipboard::Clipboard, shared::{square::Square, usize2d::Usize2d}, ConwaysGame, Rule};
+/// # fn main() -> Result<(), std::io::Error> {
+/// # let game = ConwaysGame::headless(16, 16, 42);
+/// # let selection = Square::new(Usize2d::new(0, 0), Usize2d::new(7, 7));
+/// let clipboard = Clipboard::copy(game.cells(), &selection);
+/// fs::write("copied.rle", clipboard.to_rle(&Rule::conway()))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clipboard {
+    /// The copied cells, indexed as `cells[y][x]` from the top left of the region
+    pub cells: Vec<Vec<bool>>,
+}
+impl Clipboard {
+    /// Copy a region of a board
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the board, indexed as `cells[y][x]`
+    /// * `area` - the region to copy, inside the board
+    pub fn copy(cells: &[Vec<bool>], area: &Square) -> Self {
+        let (top_left, bottom_right) = area.get_boundary();
+        Clipboard {
+            cells: cells[top_left.y..=bottom_right.y]
+                .iter()
+                .map(|row| row[top_left.x..=bottom_right.x].to_vec())
+                .collect(),
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.cells.first().map_or(0, |row| row.len())
+    }
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+    /// Write the cells in the run length encoded format that most Game of Life programs read
+    ///
+    /// Dead cells at the end of a row and empty rows at the end are left out, like the format
+    /// allows, and the lines are wrapped without splitting a run
+    pub fn to_rle(&self, rule: &Rule) -> String {
+        let mut runs: Vec<String> = Vec::new();
+        let mut skipped_rows = 0;
+        for (index, row) in self.cells.iter().enumerate() {
+            if index > 0 {
+                skipped_rows += 1;
+            }
+            let length = row
+                .iter()
+                .rposition(|cell| *cell)
+                .map_or(0, |last| last + 1);
+            if length == 0 {
+                continue;
+            }
+            if skipped_rows > 0 {
+                runs.push(format!("{}$", run_count(skipped_rows)));
+                skipped_rows = 0;
+            }
+            let mut column = 0;
+            while column < length {
+                let is_alive = row[column];
+                let run = row[column..length]
+                    .iter()
+                    .take_while(|cell| **cell == is_alive)
+                    .count();
+                runs.push(format!(
+                    "{}{}",
+                    run_count(run),
+                    if is_alive { 'o' } else { 'b' }
+                ));
+                column += run;
+            }
+        }
+        runs.push("!".to_string());
+
+        let mut rle = format!(
+            "x = {}, y = {}, rule = {}\n",
+            self.width(),
+            self.height(),
+            rule
+        );
+        let mut line_width = 0;
+        for run in runs {
+            if line_width + run.len() > RLE_LINE_WIDTH {
+                rle.push('\n');
+                line_width = 0;
+            }
+            line_width += run.len();
+            rle.push_str(&run);
+        }
+        rle.push('\n');
+        rle
+    }
+    /// Read the cells of an RLE file, like the ones `to_rle` writes
+    ///
+    /// The comments and the rule are skipped. The pattern is as large as the header says, or
+    /// larger if its rows do not fit. `b` and `.` are dead cells and any other letter is alive,
+    /// so that the live cells of patterns with more states are kept
+    pub fn from_rle(rle: &str) -> Result<Self, PatternFileError> {
+        let (mut width, mut height) = (0, 0);
+        let mut cells: Vec<Vec<bool>> = vec![Vec::new()];
+        let mut count = 0;
+        let lines = rle
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'));
+        'lines: for line in lines {
+            if line.starts_with('x') {
+                for (key, value) in line.split(',').filter_map(|part| part.split_once('=')) {
+                    let size = || {
+                        value
+                            .trim()
+                            .parse()
+                            .map_err(|_| PatternFileError::BadFormat)
+                    };
+                    match key.trim() {
+                        "x" => width = size()?,
+                        "y" => height = size()?,
+                        _ => (),
+                    }
+                }
+                continue;
+            }
+            for c in line.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    count = count * 10 + digit as usize;
+                    continue;
+                }
+                let run = count.max(1);
+                count = 0;
+                let row = cells.last_mut().ok_or(PatternFileError::BadFormat)?;
+                match c {
+                    '!' => break 'lines,
+                    '$' => cells.extend((0..run).map(|_| Vec::new())),
+                    'b' | '.' => row.extend(vec![false; run]),
+                    _ if c.is_ascii_alphabetic() => row.extend(vec![true; run]),
+                    _ if c.is_whitespace() => (),
+                    _ => return Err(PatternFileError::BadFormat),
+                }
+            }
+        }
+        let mut clipboard = Clipboard::padded(cells, width);
+        let width = clipboard.width();
+        if clipboard.height() < height {
+            clipboard.cells.resize(height, vec![false; width]);
+        }
+        Ok(clipboard)
+    }
+    /// Read the cells of a plaintext pattern, with `O` for a live cell and `.` for a dead one.
+    /// The lines that start with `!` are comments
+    pub fn from_cells(text: &str) -> Result<Self, PatternFileError> {
+        let cells = text
+            .lines()
+            .filter(|line| !line.starts_with('!'))
+            .map(|line| {
+                line.trim_end()
+                    .chars()
+                    .map(|c| match c {
+                        'O' | '*' => Ok(true),
+                        '.' => Ok(false),
+                        _ => Err(PatternFileError::BadFormat),
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<bool>>, _>>()?;
+        Ok(Clipboard::padded(cells, 0))
+    }
+    /// Read a pattern that was pasted as text, like one copied from a website. The text is read
+    /// as RLE when it has a header line and as a plaintext pattern otherwise
+    ///
+    /// Text without a live cell is not a pattern, so that any other copied text is not pasted
+    /// as an empty region
+    pub fn from_text(text: &str) -> Result<Self, PatternFileError> {
+        let text = text.trim_matches(['\r', '\n']);
+        let has_header = text
+            .lines()
+            .map(str::trim_start)
+            .any(|line| line.starts_with('x') && line.contains('='));
+        let clipboard = match has_header {
+            true => Clipboard::from_rle(text)?,
+            false => Clipboard::from_cells(text)?,
+        };
+        match clipboard.cells.iter().flatten().any(|is_alive| *is_alive) {
+            true => Ok(clipboard),
+            false => Err(PatternFileError::BadFormat),
+        }
+    }
+    /// Read a pattern file, in the format its extension names: `.rle` or `.cells`
+    pub fn load(path: &Path) -> Result<Self, PatternFileError> {
+        let text = std::fs::read_to_string(path).map_err(|_| PatternFileError::ReadFailed)?;
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        match extension.map(str::to_ascii_lowercase).as_deref() {
+            Some("rle") => Clipboard::from_rle(&text),
+            Some("cells") => Clipboard::from_cells(&text),
+            _ => Err(PatternFileError::BadFormat),
+        }
+    }
+    /// Make the rows of read cells as wide as the widest row and at least `width`, and drop the
+    /// empty rows at the end
+    fn padded(mut cells: Vec<Vec<bool>>, width: usize) -> Self {
+        while cells.last().is_some_and(|row| row.is_empty()) && cells.len() > 1 {
+            cells.pop();
+        }
+        let width = cells.iter().map(Vec::len).max().unwrap_or(0).max(width);
+        for row in cells.iter_mut() {
+            row.resize(width, false);
+        }
+        Clipboard { cells }
+    }
+}
+
+/// Write the length of a run, a run of one has no number
+fn run_count(count: usize) -> String {
+    match count {
+        1 => String::new(),
+        count => count.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        conway::rule::Rule,
+        shared::{square::Square, usize2d::Usize2d},
+    };
+
+    use super::{Clipboard, PatternFileError};
+
+    fn board(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|cell| cell == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn copy() {
+        let cells = board(&["#...", ".##.", "..#.", "...."]);
+        let area = Square::new(Usize2d::new(1, 1), Usize2d::new(2, 3));
+        let clipboard = Clipboard::copy(&cells, &area);
+        assert_eq!(clipboard.cells, board(&["##", ".#", ".."]));
+        assert_eq!((clipboard.width(), clipboard.height()), (2, 3));
+    }
+
+    #[test]
+    fn rle() {
+        let test_cases = [
+            (
+                "glider",
+                vec![".#.", "..#", "###"],
+                "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n",
+            ),
+            (
+                "empty rows",
+                vec!["#..", "...", "...", "..#", "..."],
+                "x = 3, y = 5, rule = B3/S23\no3$2bo!\n",
+            ),
+            (
+                "empty",
+                vec!["..", ".."],
+                "x = 2, y = 2, rule = B3/S23\n!\n",
+            ),
+        ];
+        for (test_case, rows, expected) in test_cases {
+            let clipboard = Clipboard {
+                cells: board(&rows),
+            };
+            assert_eq!(clipboard.to_rle(&Rule::conway()), expected, "{}", test_case);
+            assert_eq!(
+                Clipboard::from_rle(expected),
+                Ok(clipboard),
+                "{}: read back",
+                test_case
+            );
+        }
+
+        let stripes = Clipboard {
+            cells: vec![(0..100).map(|x| x % 2 == 0).collect()],
+        };
+        let rle = stripes.to_rle(&Rule::conway());
+        assert!(rle.lines().all(|line| line.len() <= 70), "{}", rle);
+        assert_eq!(
+            rle.lines().skip(1).collect::<String>(),
+            "ob".repeat(49) + "o!"
+        );
+    }
+
+    #[test]
+    fn read_patterns() {
+        let test_cases = [
+            (
+                "rle with comments",
+                Clipboard::from_rle("#N Blinker\n#C a comment\nx = 3, y = 1\n3o!"),
+                Ok(vec!["###"]),
+            ),
+            (
+                "long runs over lines",
+                Clipboard::from_rle("x = 12, y = 2\n12o$\n11bo!"),
+                Ok(vec!["############", "...........#"]),
+            ),
+            (
+                "rows wider than the header",
+                Clipboard::from_rle("x = 1, y = 1\n2o!"),
+                Ok(vec!["##"]),
+            ),
+            (
+                "other states are alive",
+                Clipboard::from_rle("x = 3, y = 1\n.AB!"),
+                Ok(vec![".##"]),
+            ),
+            (
+                "bad rle",
+                Clipboard::from_rle("x = 3, y = 1\n3o?!"),
+                Err(PatternFileError::BadFormat),
+            ),
+            (
+                "bad header",
+                Clipboard::from_rle("x = three, y = 1\n3o!"),
+                Err(PatternFileError::BadFormat),
+            ),
+            (
+                "cells",
+                Clipboard::from_cells("!Name: Glider\n.O\n..O\nOOO\n"),
+                Ok(vec![".#.", "..#", "###"]),
+            ),
+            (
+                "bad cells",
+                Clipboard::from_cells(".O\n.X"),
+                Err(PatternFileError::BadFormat),
+            ),
+        ];
+        for (test_case, clipboard, expected) in test_cases {
+            let expected = expected.map(|rows| Clipboard {
+                cells: board(&rows),
+            });
+            assert_eq!(clipboard, expected, "{}", test_case);
+        }
+
+        let test_cases = [
+            (
+                "rle from a website",
+                "#N Glider\r\nx = 3, y = 3, rule = B3/S23\r\nbo$2bo$3o!\r\n",
+                Ok(vec![".#.", "..#", "###"]),
+            ),
+            (
+                "cells after empty lines",
+                "\n\n.O\n..O\nOOO\n",
+                Ok(vec![".#.", "..#", "###"]),
+            ),
+            ("other text", "hello", Err(PatternFileError::BadFormat)),
+            (
+                "no live cells",
+                "...\n...",
+                Err(PatternFileError::BadFormat),
+            ),
+            ("nothing", "", Err(PatternFileError::BadFormat)),
+        ];
+        for (test_case, text, expected) in test_cases {
+            let expected = expected.map(|rows| Clipboard {
+                cells: board(&rows),
+            });
+            assert_eq!(Clipboard::from_text(text), expected, "{}", test_case);
+        }
+
+        let dir = std::env::temp_dir();
+        let test_cases = [
+            ("rle", "glider.rle", "x = 3, y = 3\nbo$2bo$3o!", Ok(3)),
+            ("cells", "glider.CELLS", ".O\n..O\nOOO", Ok(3)),
+            (
+                "other files",
+                "glider.txt",
+                ".O\n..O\nOOO",
+                Err(PatternFileError::BadFormat),
+            ),
+        ];
+        for (test_case, name, text, expected) in test_cases {
+            let path = dir.join(format!("clipboard_test_{}_{}", std::process::id(), name));
+            std::fs::write(&path, text).unwrap();
+            let loaded = Clipboard::load(&path);
+            let _ = std::fs::remove_file(&path);
+            assert_eq!(
+                loaded.map(|clipboard| clipboard.height()),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+        assert_eq!(
+            Clipboard::load(&dir.join("clipboard_test_missing.rle")),
+            Err(PatternFileError::ReadFailed)
+        );
+    }
+}
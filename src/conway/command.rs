@@ -0,0 +1,236 @@
+use core::fmt::Display;
+use std::time::Instant;
+
+use crate::console::input_record::{KeyEvent, MouseEvent};
+
+use super::key_bindings::key_name;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Command {
+    QUIT,
+    RESET,
+    RESEED,
+    PAUSEPLAY,
+    TOGGLEMODE,
+    TOGGLEDEBUGPANEL,
+    TOGGLEINPUTLOG,
+    TOGGLEFPS,
+    TOGGLEHEATMAP,
+    FASTFORWARD,
+    TOGGLEHELP,
+    OPENMENU,
+    CLOSEMENU,
+    MOVESELECTION,
+    SAVE,
+    OPENOPTIONS,
+    CHANGEOPTION,
+    CLOSEOPTIONS,
+    SHOWSCORES,
+    SHOWACHIEVEMENTS,
+    CLOSEPAGE,
+    OPENINPUT,
+    TYPE,
+    CLOSEINPUT,
+    OPENFILES,
+    LOAD,
+    CLOSEFILES,
+    TIMETRAVEL,
+    SCRUB,
+    CLOSETIMETRAVEL,
+    SCREENSHOT,
+    DUMPFRAME,
+    SAVESEED,
+    RECORD,
+    MOVELEFT,
+    MOVERIGHT,
+    MOVEUP,
+    MOVEDOWN,
+    PICKPATTERN,
+    PLACEPATTERN,
+    CLOSEPICKER,
+    OPENEDITOR,
+    CLOSEEDITOR,
+    MOVECURSOR,
+    TOGGLECELL,
+    NEXTBRUSH,
+    ROTATEBRUSH,
+    STAMP,
+    PAINTCELLS,
+    MIRRORHORIZONTALLY,
+    MIRRORVERTICALLY,
+    ROTATEBOARD,
+    GROWGRID,
+    SHRINKGRID,
+    SAVECHECKPOINT,
+    LOADCHECKPOINT,
+    UNDO,
+    REDO,
+    SELECT,
+    COPY,
+    CUT,
+    PASTE,
+    CLEARSELECTION,
+    EXPORTCLIPBOARD,
+    RECORDMACRO,
+    PLAYMACRO,
+    NOMAPPING,
+    NONE,
+}
+impl Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::QUIT => write!(f, "Quit"),
+            Command::RESET => write!(f, "Reset"),
+            Command::RESEED => write!(f, "Reset with a new seed"),
+            Command::PAUSEPLAY => write!(f, "Toggle pause"),
+            Command::TOGGLEMODE => write!(f, "Toggle print mode"),
+            Command::TOGGLEDEBUGPANEL => write!(f, "Collapse the debug panel"),
+            Command::TOGGLEINPUTLOG => write!(f, "Toggle the input log"),
+            Command::TOGGLEFPS => write!(f, "Toggle fps"),
+            Command::TOGGLEHEATMAP => write!(f, "Toggle the heatmap"),
+            Command::FASTFORWARD => write!(f, "Fast forward"),
+            Command::TOGGLEHELP => write!(f, "Toggle the help"),
+            Command::OPENMENU => write!(f, "Open the menu"),
+            Command::CLOSEMENU => write!(f, "Close the menu"),
+            Command::MOVESELECTION => write!(f, "Move the selection"),
+            Command::SAVE => write!(f, "Save the game"),
+            Command::OPENOPTIONS => write!(f, "Open the options"),
+            Command::CHANGEOPTION => write!(f, "Change an option"),
+            Command::CLOSEOPTIONS => write!(f, "Close the options"),
+            Command::SHOWSCORES => write!(f, "Show the high scores"),
+            Command::SHOWACHIEVEMENTS => write!(f, "Show the achievements"),
+            Command::CLOSEPAGE => write!(f, "Close the page"),
+            Command::OPENINPUT => write!(f, "Open a text field"),
+            Command::TYPE => write!(f, "Type in the text field"),
+            Command::CLOSEINPUT => write!(f, "Close the text field"),
+            Command::OPENFILES => write!(f, "Browse the files"),
+            Command::LOAD => write!(f, "Load a file"),
+            Command::CLOSEFILES => write!(f, "Close the files"),
+            Command::TIMETRAVEL => write!(f, "Travel back in time"),
+            Command::SCRUB => write!(f, "Scrub through the history"),
+            Command::CLOSETIMETRAVEL => write!(f, "Close the time travel"),
+            Command::SCREENSHOT => write!(f, "Save a screenshot"),
+            Command::DUMPFRAME => write!(f, "Save the frame as text"),
+            Command::SAVESEED => write!(f, "Save the seed"),
+            Command::RECORD => write!(f, "Record a GIF"),
+            Command::MOVELEFT => write!(f, "Move the board left"),
+            Command::MOVERIGHT => write!(f, "Move the board right"),
+            Command::MOVEUP => write!(f, "Move the board up"),
+            Command::MOVEDOWN => write!(f, "Move the board down"),
+            Command::PICKPATTERN => write!(f, "Pick a pattern"),
+            Command::PLACEPATTERN => write!(f, "Place the pattern"),
+            Command::CLOSEPICKER => write!(f, "Close the pattern picker"),
+            Command::OPENEDITOR => write!(f, "Open the editor"),
+            Command::CLOSEEDITOR => write!(f, "Close the editor"),
+            Command::MOVECURSOR => write!(f, "Move the cursor"),
+            Command::TOGGLECELL => write!(f, "Toggle the cell"),
+            Command::NEXTBRUSH => write!(f, "Select a brush"),
+            Command::ROTATEBRUSH => write!(f, "Rotate the brush"),
+            Command::STAMP => write!(f, "Stamp the brush"),
+            Command::PAINTCELLS => write!(f, "Paint cells"),
+            Command::MIRRORHORIZONTALLY => write!(f, "Mirror the board horizontally"),
+            Command::MIRRORVERTICALLY => write!(f, "Mirror the board vertically"),
+            Command::ROTATEBOARD => write!(f, "Rotate the board"),
+            Command::GROWGRID => write!(f, "Grow the grid"),
+            Command::SHRINKGRID => write!(f, "Shrink the grid"),
+            Command::SAVECHECKPOINT => write!(f, "Save a checkpoint"),
+            Command::LOADCHECKPOINT => write!(f, "Load a checkpoint"),
+            Command::UNDO => write!(f, "Undo the last change"),
+            Command::REDO => write!(f, "Redo the edit"),
+            Command::SELECT => write!(f, "Start or end a selection"),
+            Command::COPY => write!(f, "Copy the selection"),
+            Command::CUT => write!(f, "Cut the selection"),
+            Command::PASTE => write!(f, "Paste the clipboard"),
+            Command::CLEARSELECTION => write!(f, "Clear the selection"),
+            Command::EXPORTCLIPBOARD => write!(f, "Export the clipboard"),
+            Command::RECORDMACRO => write!(f, "Start or stop a macro"),
+            Command::PLAYMACRO => write!(f, "Play a macro"),
+            Command::NOMAPPING => write!(f, "Key not mapped"),
+            Command::NONE => write!(f, "NONE"),
+        }
+    }
+}
+impl Command {
+    /// Get the command that reverts this one, `None` if it can not be reverted by another command
+    pub fn inverse(self) -> Option<Command> {
+        match self {
+            Command::MOVELEFT => Some(Command::MOVERIGHT),
+            Command::MOVERIGHT => Some(Command::MOVELEFT),
+            Command::MOVEUP => Some(Command::MOVEDOWN),
+            Command::MOVEDOWN => Some(Command::MOVEUP),
+            Command::PAUSEPLAY
+            | Command::TOGGLEMODE
+            | Command::TOGGLEDEBUGPANEL
+            | Command::TOGGLEINPUTLOG
+            | Command::TOGGLEFPS
+            | Command::TOGGLEHEATMAP => Some(self),
+            _ => None,
+        }
+    }
+}
+
+/// What made the game run a command
+#[derive(Clone, Copy, Debug)]
+pub enum CommandSource {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    /// The command reverted an earlier one
+    Undo,
+}
+
+/// A command the game ran, with where it came from, when it ran and how to revert it
+///
+/// The input log shows them, replays are written from them and the latest one that can be
+/// reverted is undone with its inverse
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{console::input_record::KeyEvent, conway::command::{Command, CommandSource, GameCommand}, utils::clock::{Clock, SystemClock}};
+/// # let (event, clock) = (KeyEvent::key_down('a'), SystemClock);
+/// let command = GameCommand::new(Command::MOVELEFT, CommandSource::Key(event), clock.now());
+/// assert!(command.inverse == Some(Command::MOVERIGHT));
+/// ```
+#[derive(Clone, Copy)]
+pub struct GameCommand {
+    pub command: Command,
+    pub source: CommandSource,
+    pub at: Instant,
+    /// The command that reverts this one, `None` if it can not be reverted or was undone
+    pub inverse: Option<Command>,
+}
+impl GameCommand {
+    /// Create a command, only a command that a pressed key ran can be reverted
+    pub fn new(command: Command, source: CommandSource, at: Instant) -> Self {
+        let is_key_down = matches!(source, CommandSource::Key(event) if event.is_down);
+        GameCommand {
+            command,
+            source,
+            at,
+            inverse: command.inverse().filter(|_| is_key_down),
+        }
+    }
+    /// Get the number of times the command ran, a held key runs it once for every repeat
+    pub fn repeats(&self) -> usize {
+        match self.source {
+            CommandSource::Key(event) => event.repreat_count.max(1) as usize,
+            CommandSource::Mouse(_) | CommandSource::Undo => 1,
+        }
+    }
+    /// Name the input that ran the command, like `space`, `mouse` or `undo`
+    pub fn input(&self) -> String {
+        match self.source {
+            CommandSource::Key(event) => key_name(event.command),
+            CommandSource::Mouse(_) => "mouse".to_string(),
+            CommandSource::Undo => "undo".to_string(),
+        }
+    }
+    /// Check if the input was pressed rather than released
+    pub fn is_down(&self) -> bool {
+        match self.source {
+            CommandSource::Key(event) => event.is_down,
+            CommandSource::Mouse(event) => event.is_left_down(),
+            CommandSource::Undo => true,
+        }
+    }
+}
@@ -0,0 +1,6264 @@
+use rand::prelude::*;
+use std::fs;
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use std::thread::{spawn, JoinHandle};
+use std::time::Duration;
+use std::time::Instant;
+
+#[cfg(feature = "serde")]
+use crate::console::input_record::Key;
+use crate::console::input_record::{KeyEvent, MouseEvent};
+use crate::console::input_source::InputSource;
+use crate::console::terminal_size::terminal_size;
+use crate::conway::automaton::Automaton;
+#[cfg(feature = "serde")]
+use crate::conway::autosave::Autosave;
+use crate::conway::checkpoint::{Checkpoint, Checkpoints, SlotKey};
+use crate::conway::clipboard::Clipboard;
+use crate::conway::command::{Command, CommandSource, GameCommand};
+use crate::conway::cycle::{Cycle, CycleDetector};
+use crate::conway::divergence::board_hash;
+#[cfg(feature = "serde")]
+use crate::conway::divergence::{Divergence, VerifyError};
+use crate::conway::editor::{
+    CellChange, Edit, Editor, Stroke, COPY_KEY, CUT_KEY, EXPORT_KEY, PASTE_KEY, REDO_KEY, UNDO_KEY,
+};
+use crate::conway::errors::ConwayError;
+#[cfg(feature = "serde")]
+use crate::conway::ghost::Ghost;
+use crate::conway::heatmap::Heatmap;
+use crate::conway::history::{GenerationHistory, TimeTravel};
+use crate::conway::input_log::InputLog;
+use crate::conway::key_bindings::KeyBindings;
+use crate::conway::macros::Macros;
+use crate::conway::options::{GameOptions, OptionsScreen};
+use crate::conway::patterns::{PatternPlacement, PATTERNS};
+use crate::conway::pause_menu::{PauseMenu, PauseMenuItem, TextPrompt};
+use crate::conway::rule::Rule;
+#[cfg(feature = "serde")]
+use crate::conway::save::{SaveError, SavedGame};
+use crate::conway::settings::Boundary;
+use crate::conway::shared_clipboard::SharedClipboard;
+#[cfg(feature = "serde")]
+use crate::conway::timeline::{RecordedSession, Timeline, TimelineEvent};
+use crate::conway::transform::BoardTransform;
+use crate::coordination::service::{guard_terminal_size, TerminalSizeSource};
+#[cfg(feature = "export-image")]
+use crate::export::gif::{GifExport, GifRecorder};
+#[cfg(feature = "export-image")]
+use crate::export::image::{Image, ImageOptions};
+#[cfg(feature = "export-image")]
+use crate::export::png::write_png;
+#[cfg(feature = "export-image")]
+use crate::export::svg::frame_svg;
+use crate::handler::handle::Handle;
+use crate::handler::memory_handle::MemoryHandle;
+use crate::handler::shared_handle::SharedHandle;
+use crate::handler::terminal_guard::{restore_terminal, restore_terminal_on_panic};
+#[cfg(feature = "net")]
+use crate::net::spectator::SpectatorServer;
+use crate::panel::file_picker::{FilePicker, PickerAction};
+use crate::panel::toast::ToastLayer;
+use crate::rendering::colors::TerminalColors;
+use crate::rendering::hud::{
+    Column, Hud, InputAction, KeyHints, Label, MenuAction, ProgressBar, Readout, SparklineWidget,
+    Table, TextInput, Widget,
+};
+use crate::rendering::message_helper::MessageHelper;
+use crate::rendering::message_style::MessageStyle;
+use crate::scores::achievements::{Achievements, GameEvent};
+#[cfg(feature = "serde")]
+use crate::scores::score_board::ScoreBoard;
+use crate::shared::frame::{Frame, Pixel};
+use crate::shared::frame_dump::write_frame_dump;
+use crate::shared::square::Square;
+use crate::shared::usize2d::{Coord, Usize2d};
+use crate::utils::buffer_pool::BufferPool;
+use crate::utils::clock::{Clock, SystemClock};
+use crate::utils::metrics::{
+    Metrics, SessionSummary, COMMANDS, GENERATIONS, POPULATION, RENDER_TIME, SERIES_LENGTH,
+    UPDATE_TIME,
+};
+use crate::utils::progress::Progress;
+use crate::utils::rng::{RngService, GRID_STREAM};
+use crate::utils::step_scheduler::StepScheduler;
+use crate::utils::timer::Timers;
+
+use super::neighbor_counts::NeighborCounts;
+use super::print_mode::PrintMode;
+use super::settings::ConwaysSettings;
+use super::viewport::{EdgeActivity, Viewport};
+
+/// The width that lines of debug info are padded to so that longer previous lines are cleared
+const DEBUG_LINE_WIDTH: usize = 60;
+/// The number of lines of debug info, including the line for the latest error
+const DEBUG_LINE_COUNT: usize = 12;
+/// The number of bars of the frame timing graphs in the debug panel
+const FRAME_GRAPH_WIDTH: usize = 40;
+/// The title of the debug panel, the only line of the panel while it is collapsed
+const DEBUG_TITLE_COLLAPSED: &str = "[+] Debug";
+const DEBUG_TITLE_EXPANDED: &str = "[-] Debug";
+/// The columns between the right edge of the board and the debug panel
+const DEBUG_PANEL_GAP: usize = 2;
+/// The number of lines below the board, for the pattern picker, the editor and the progress of
+/// a fast forward
+const INFO_LINE_COUNT: usize = 3;
+/// The number of times the progress is shown during a fast forward
+const FAST_FORWARD_UPDATES: u64 = 20;
+/// The number of patterns the pattern picker lists at once
+const PICKER_ROWS: usize = 6;
+/// The most key events handled in one iteration of the game loop, the rest wait for the next one
+const MAX_EVENTS_PER_TICK: usize = 64;
+/// How often the size of the terminal is read again to follow a resize
+const RESIZE_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+/// The number of files the file picker lists at once
+const FILE_PICKER_ROWS: usize = 8;
+/// The most characters of a path that is typed to save the game to
+const SAVE_PATH_LEN: usize = 60;
+/// The most characters of the name of a macro
+const MACRO_NAME_LEN: usize = 20;
+/// How long a toast, like the one of an unlocked achievement, is shown
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+/// The file in the export directory the saved seeds are added to
+const SEEDS_FILE: &str = "conway_seeds.txt";
+/// The background of the dead cells that are alive in the recorded run behind the board
+#[cfg(feature = "serde")]
+const GHOST_COLOR: TerminalColors = TerminalColors::Grey;
+
+pub struct ConwaysGame {
+    current: Vec<Vec<bool>>,
+    previous: Vec<Vec<bool>>,
+    state: ConwaysState,
+    settings: ConwaysSettings,
+    screen: MessageHelper,
+    input: Box<dyn InputSource>,
+    timers: Timers<ConwaysTimer>,
+    /// Steps the generations once the game started, and skips renders when the steps fall behind
+    step_scheduler: Option<StepScheduler>,
+    /// The status line says the game is running behind real time
+    is_behind_shown: bool,
+    clock: Box<dyn Clock>,
+    last_fps_sample: Instant,
+    /// The cells the editor brush would change, collected once per frame
+    brush_preview: Vec<Coord>,
+    heatmap: Heatmap,
+    /// The latest generations, for the time travel
+    history: GenerationHistory,
+    /// The latest key events with the commands they ran
+    input_log: InputLog,
+    /// Finds the oscillators and spaceships that pause the game with `auto_pause`
+    cycle_detector: CycleDetector,
+    /// The region that was copied or cut in the editor, kept after the editor is closed and
+    /// shared with the scenes and the system clipboard it was given
+    clipboard: SharedClipboard,
+    /// The cells toggled and painted since the mouse button was pressed
+    stroke: Option<Stroke>,
+    /// The quick save slots, kept in memory only
+    checkpoints: Checkpoints,
+    /// The recorded macros, kept in memory only
+    macros: Macros,
+    /// The keys of the macro that is played after the key that started it
+    macro_keys: Vec<KeyEvent>,
+    /// The keys of a macro are not added to the timeline, replaying the key that played the
+    /// macro plays them again
+    is_playing_macro: bool,
+    key_bindings: KeyBindings,
+    achievements: Achievements,
+    /// Announces the achievements that are unlocked
+    toasts: ToastLayer,
+    /// The generations that are being recorded for a GIF
+    #[cfg(feature = "export-image")]
+    recorder: Option<GifRecorder>,
+    /// The recording that is being saved on a worker thread
+    #[cfg(feature = "export-image")]
+    gif_export: Option<GifExport>,
+    #[cfg(feature = "serde")]
+    autosave: Option<Autosave>,
+    #[cfg(feature = "net")]
+    spectators: Option<SpectatorServer>,
+    metrics: Metrics,
+    /// The commands and checkpoints of the session, written to JSON when it ends
+    #[cfg(feature = "serde")]
+    timeline: Timeline,
+    /// A recorded run that is drawn dimmed behind the board
+    #[cfg(feature = "serde")]
+    ghost: Option<Ghost>,
+    /// Recycles the boards that are replaced every generation
+    buffer_pool: BufferPool<bool>,
+    /// The neighbor counts of the last generation that was stepped. Every change of the board
+    /// other than by `next` sets it to `None`, the next generation then counts the neighbors again
+    neighbor_counts: Option<NeighborCounts>,
+    /// The cells that were born or died in the last generation
+    changed_cells: Vec<(usize, usize)>,
+    /// The colors the visible cells were last drawn in, `None` after the screen was cleared
+    drawn_cells: Option<DrawnCells>,
+    /// Something was drawn over the board in the last frame, like a menu or a toast
+    was_board_covered: bool,
+    /// Reads the size of the terminal while the game runs, `None` keeps the screen size that
+    /// was set
+    screen_size_source: Option<TerminalSizeSource>,
+    /// The smallest screen the game needed when the screen said it is too small, until the
+    /// screen is cleared
+    too_small_shown_for: Option<Usize2d>,
+}
+
+/// The cells of the board as they were last drawn, so that a frame only writes the cells that
+/// look different. The cells are drawn again if the board moved on the screen
+struct DrawnCells {
+    /// The first visible cell, the number of visible cells, the board origin and the cell size
+    layout: [usize; 8],
+    /// The background and foreground colors of every visible cell
+    colors: Vec<Vec<Option<(TerminalColors, TerminalColors)>>>,
+}
+
+/// The events scheduled on the timers of the game
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ConwaysTimer {
+    FpsSample,
+    ResizeCheck,
+    #[cfg(feature = "serde")]
+    Autosave,
+}
+
+struct ConwaysState {
+    fps_last: u64,    //lol
+    fps_current: u64, //lol
+    latest_input: char,
+    latest_command: Command,
+    command_count: u64,
+    latest_err: String,
+    is_paused: bool,
+    is_fps_limited: bool,
+    rounds: u64,
+    print_mode: PrintMode,
+    is_reset_active: bool,
+    /// The index in `PATTERNS` of the pattern selected in the picker, if it is open
+    picked_pattern: Option<usize>,
+    editor: Option<Editor>,
+    /// Color the cells by how often they were alive instead of by their state
+    is_heatmap: bool,
+    /// Show the key bindings over the dimmed board until the next key
+    is_help_open: bool,
+    /// The menu that is opened with escape, the game is paused while it is open
+    pause_menu: Option<PauseMenu>,
+    /// The options screen that is opened from the pause menu
+    options_screen: Option<OptionsScreen>,
+    /// A page of text that is opened from the pause menu, like the high scores
+    menu_page: Option<Vec<String>>,
+    /// A text field over the pause menu, like the seed the game starts over with
+    text_prompt: Option<(TextPrompt, TextInput<'static>)>,
+    /// Lists the saves and pattern files that can be loaded, opened from the pause menu
+    file_picker: Option<FilePicker>,
+    /// Scrubs the board through the history while the game is paused
+    time_travel: Option<TimeTravel>,
+    /// The cell that is described by the debug info
+    inspector: Coord,
+    /// Show only the title of the debug panel
+    is_debug_collapsed: bool,
+    /// List the latest key events over the top left of the board
+    is_input_log_open: bool,
+    /// Write the frame to text files once it is drawn, see `dump_frame`
+    is_frame_dump_pending: bool,
+    /// The cycle the game was paused for by `auto_pause`, until the board stops repeating
+    detected_cycle: Option<Cycle>,
+}
+
+impl ConwaysGame {
+    ///Initialize an instance of ConwaysGame
+    ///
+    /// # Arguments
+    ///
+    /// * `x_len` - number of cells in a row
+    /// * `y_len` - number of rows in the grid
+    /// * `seed` - a seed for the randomness used to do the initialization
+    /// * `mode` - the initial print mode
+    /// * `duration` - the time between generations
+    /// * `receiver` - receives the key inputs for the game
+    /// * `handle` - the handle the game is drawn to
+    ///
+    /// #Returns
+    ///
+    /// An instance of ConwaysGame
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(deprecated)]
+    /// use std::{sync::mpsc::channel, time::Duration};
+    /// use tic_tac_toe2::{ConwaysGame, PrintMode, SharedHandle};
+    ///
+    /// let (_, receiver) = channel();
+    /// let handle = Box::new(SharedHandle::init_std_out());
+    /// let game = ConwaysGame::init(
+    ///     1,
+    ///     9001,
+    ///     42,
+    ///     PrintMode::PRETTY,
+    ///     Duration::from_millis(1000),
+    ///     receiver,
+    ///     handle,
+    /// );
+    /// ```
+    #[deprecated(note = "use `ConwaysGame::builder`, which reports an empty board as an error")]
+    pub fn init(
+        x_len: usize,
+        y_len: usize,
+        seed: u64,
+        mode: PrintMode,
+        duration: Duration,
+        receiver: Receiver<KeyEvent>,
+        handle: Box<dyn Handle>,
+    ) -> Self {
+        assert!(x_len > 0);
+        assert!(y_len > 0);
+        ConwaysGame::create(
+            x_len,
+            y_len,
+            seed,
+            mode,
+            duration,
+            Box::new(receiver),
+            handle,
+        )
+    }
+    /// Get a builder for a game with a board of a size, the other settings start at their
+    /// defaults
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::mpsc::channel;
+    /// use tic_tac_toe2::{conway::settings::Boundary, ConwayError, ConwaysGame, SharedHandle};
+    ///
+    /// # fn main() -> Result<(), ConwayError> {
+    /// let (_, receiver) = channel();
+    /// let game = ConwaysGame::builder(40, 20)
+    ///     .seed(42)
+    ///     .boundary(Boundary::Dead)
+    ///     .receiver(receiver)
+    ///     .handle(Box::new(SharedHandle::init_std_out()))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder(x_len: usize, y_len: usize) -> ConwaysGameBuilder {
+        ConwaysGameBuilder {
+            x_len,
+            y_len,
+            seed: None,
+            rule: Rule::conway(),
+            boundary: Boundary::Wrap,
+            round_duration: Duration::from_millis(1000),
+            print_mode: PrintMode::PRETTY,
+            auto_pause: false,
+            skip: 0,
+            pattern: None,
+            clipboard: SharedClipboard::default(),
+            #[cfg(feature = "serde")]
+            resume: None,
+            #[cfg(feature = "net")]
+            spectators: None,
+            input: None,
+            handle: None,
+        }
+    }
+    /// Create a game with a random board, the sizes are checked by the callers
+    fn create(
+        x_len: usize,
+        y_len: usize,
+        seed: u64,
+        mode: PrintMode,
+        duration: Duration,
+        input: Box<dyn InputSource>,
+        handle: Box<dyn Handle>,
+    ) -> Self {
+        let mut _rng = RngService::new(seed).stream(GRID_STREAM);
+
+        let mut new_state = vec![vec![false; x_len]; y_len];
+        let new_prev = new_state.clone();
+        for row in new_state.iter_mut() {
+            for cell in row.iter_mut() {
+                *cell = _rng.gen();
+            }
+        }
+        let settings = ConwaysSettings::init(x_len, y_len, duration, seed);
+        let mut heatmap = Heatmap::new(settings.heatmap_window);
+        heatmap.record(&new_state);
+        let mut history = GenerationHistory::new(settings.history_length);
+        history.record(0, &new_state);
+        let input_log = InputLog::new(settings.input_log_length);
+        let mut cycle_detector = CycleDetector::new(settings.cycle_max_period);
+        cycle_detector.record(&new_state);
+        ConwaysGame {
+            current: new_state.clone(),
+            previous: new_prev,
+            screen: MessageHelper::init(handle),
+            state: ConwaysState {
+                print_mode: mode,
+                latest_command: Command::NONE,
+                command_count: 0,
+                latest_input: ' ',
+                rounds: 0,
+                fps_last: 0,
+                fps_current: 0,
+                is_paused: false,
+                is_fps_limited: false,
+                is_reset_active: false,
+                latest_err: "".to_string(),
+                picked_pattern: None,
+                editor: None,
+                is_heatmap: false,
+                is_help_open: false,
+                pause_menu: None,
+                options_screen: None,
+                menu_page: None,
+                text_prompt: None,
+                file_picker: None,
+                time_travel: None,
+                inspector: Coord::new(x_len / 2, y_len / 2),
+                is_debug_collapsed: false,
+                is_input_log_open: false,
+                is_frame_dump_pending: false,
+                detected_cycle: None,
+            },
+            input,
+            settings,
+            timers: Timers::default(),
+            step_scheduler: None,
+            is_behind_shown: false,
+            clock: Box::new(SystemClock),
+            last_fps_sample: Instant::now(),
+            brush_preview: Vec::new(),
+            heatmap,
+            history,
+            input_log,
+            cycle_detector,
+            clipboard: SharedClipboard::default(),
+            stroke: None,
+            checkpoints: Checkpoints::default(),
+            macros: Macros::default(),
+            macro_keys: Vec::new(),
+            is_playing_macro: false,
+            key_bindings: KeyBindings::default(),
+            achievements: Achievements::default(),
+            toasts: ToastLayer::default(),
+            #[cfg(feature = "export-image")]
+            recorder: None,
+            #[cfg(feature = "export-image")]
+            gif_export: None,
+            #[cfg(feature = "serde")]
+            autosave: None,
+            #[cfg(feature = "net")]
+            spectators: None,
+            metrics: Metrics::new(Instant::now()),
+            #[cfg(feature = "serde")]
+            timeline: Timeline::new(Instant::now()),
+            #[cfg(feature = "serde")]
+            ghost: None,
+            buffer_pool: BufferPool::default(),
+            neighbor_counts: None,
+            changed_cells: Vec::new(),
+            drawn_cells: None,
+            was_board_covered: false,
+            screen_size_source: None,
+            too_small_shown_for: None,
+        }
+    }
+    /// Initialize a game that is never drawn or sent input, for running many boards in batches.
+    /// The rounds are advanced with `step` or `fast_forward`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// # let seed = 42;
+    /// let mut game = ConwaysGame::headless(64, 64, seed);
+    /// game.fast_forward(1000);
+    /// ```
+    pub fn headless(x_len: usize, y_len: usize, seed: u64) -> Self {
+        let (_, receiver) = channel();
+        ConwaysGame::builder(x_len, y_len)
+            .seed(seed)
+            .speed(Duration::ZERO)
+            .receiver(receiver)
+            .handle(Box::new(MemoryHandle::new()))
+            .build()
+            .expect("A headless board needs at least one row and one column")
+    }
+    /// Run a game on its own thread, drawn to the standard output unless the builder has a
+    /// handle. The options, high scores and achievements are loaded before the game starts and
+    /// the summary is printed once it is finished
+    ///
+    /// # Arguments
+    ///
+    /// * `builder` - the settings of the game and where its key inputs come from
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::sync::mpsc::channel;
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let (_keys, receiver) = channel();
+    /// let game = ConwaysGame::run_async(ConwaysGame::builder(40, 20).skip(100).receiver(receiver));
+    /// game.join().expect("the game does not panic");
+    /// ```
+    pub fn run_async(builder: ConwaysGameBuilder) -> JoinHandle<()> {
+        restore_terminal_on_panic();
+        let game_closure = move || {
+            let builder = match builder.handle {
+                Some(_) => builder,
+                None => builder.handle(Box::new(SharedHandle::init_std_out())),
+            };
+            let mut gs = builder
+                .build()
+                .expect("The board needs at least one row and one column");
+            gs.set_screen_size_source(Box::new(terminal_size));
+            #[cfg(feature = "serde")]
+            gs.set_autosave(Some(Autosave::default()));
+            #[cfg(feature = "serde")]
+            if let Ok(options) = GameOptions::load(&gs.settings.options_path) {
+                gs.apply_options(&options);
+            }
+            #[cfg(feature = "serde")]
+            if let Ok(achievements) = Achievements::load(&gs.settings.achievements_path) {
+                gs.set_achievements(achievements);
+            }
+            gs.run();
+            let mut report = String::new();
+            #[cfg(feature = "serde")]
+            if let Ok(true) = gs.record_score() {
+                report += &format!("A new longest run of {} generations\n", gs.state.rounds);
+            }
+            #[cfg(feature = "serde")]
+            let _ = gs.achievements().save(&gs.settings.achievements_path);
+            #[cfg(feature = "serde")]
+            {
+                let path = gs
+                    .settings
+                    .export_dir
+                    .join(format!("conway_{}_timeline.json", gs.settings.seed));
+                if gs.write_timeline(&path).is_ok() {
+                    report += &format!("The session timeline was written to {}\n", path.display());
+                }
+            }
+            report += &format!("{}\n", gs.summary());
+            // The input gives the terminal back, like out of raw mode, once it is dropped
+            drop(gs);
+            print!("{}", report);
+        };
+        spawn(game_closure)
+    }
+
+    fn reset(&mut self) {
+        let mut _rng = RngService::new(self.settings.seed).stream(GRID_STREAM);
+        let mut new_state = vec![vec![false; self.settings.x_len]; self.settings.y_len];
+        let new_prev = new_state.clone();
+        for row in new_state.iter_mut() {
+            for cell in row.iter_mut() {
+                *cell = _rng.gen();
+            }
+        }
+        self.heatmap.clear();
+        self.heatmap.record(&new_state);
+        self.history.clear();
+        self.history.record(0, &new_state);
+        self.cycle_detector.clear();
+        self.cycle_detector.record(&new_state);
+        self.current = new_state;
+        self.previous = new_prev;
+        self.neighbor_counts = None;
+        self.state = ConwaysState {
+            print_mode: self.state.print_mode,
+            latest_command: Command::NONE,
+            command_count: 0,
+            latest_input: ' ',
+            rounds: 0,
+            fps_last: 0,
+            fps_current: 0,
+            is_paused: false,
+            is_fps_limited: false,
+            is_reset_active: false,
+            latest_err: "".to_string(),
+            picked_pattern: None,
+            editor: None,
+            is_heatmap: self.state.is_heatmap,
+            is_help_open: false,
+            pause_menu: None,
+            options_screen: None,
+            menu_page: None,
+            text_prompt: None,
+            file_picker: None,
+            time_travel: None,
+            inspector: self.state.inspector,
+            is_debug_collapsed: self.state.is_debug_collapsed,
+            is_input_log_open: self.state.is_input_log_open,
+            is_frame_dump_pending: false,
+            detected_cycle: None,
+        };
+    }
+
+    /// Run the game until it is stable or the player quits
+    ///
+    /// The generations are stepped by a repeating timer using the round duration from the
+    /// settings
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::sync::mpsc::channel;
+    /// # use tic_tac_toe2::{ConwayError, ConwaysGame, SharedHandle};
+    /// # fn main() -> Result<(), ConwayError> {
+    /// # let (_keys, receiver) = channel();
+    /// # let handle = Box::new(SharedHandle::init_std_out());
+    /// let mut game = ConwaysGame::builder(1, 9001).receiver(receiver).handle(handle).build()?;
+    /// game.run();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run(&mut self) {
+        self.start();
+        while self.tick() {}
+        self.finish();
+    }
+    /// Prepare the screen and schedule the timers of the game. Called once before `tick`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.start();
+    /// while game.tick() {}
+    /// game.finish();
+    /// ```
+    pub fn start(&mut self) {
+        let _ = self.screen.handle.set_alternate_screen(true);
+        self.clear_screen();
+        let _ = self.screen.handle.set_cursor_visible(false);
+        let start = self.clock.now();
+        self.timers = Timers::default();
+        self.step_scheduler = Some(StepScheduler::new(self.settings.round_duration, start));
+        self.timers
+            .repeating(Duration::from_millis(100), ConwaysTimer::FpsSample, start);
+        if self.screen_size_source.is_some() {
+            self.timers
+                .repeating(RESIZE_CHECK_INTERVAL, ConwaysTimer::ResizeCheck, start);
+        }
+        #[cfg(feature = "serde")]
+        if self.autosave.is_some() {
+            self.timers.repeating(
+                self.settings.autosave_interval,
+                ConwaysTimer::Autosave,
+                start,
+            );
+        }
+        self.last_fps_sample = start;
+        self.metrics.reset(start);
+        self.metrics.gauge(POPULATION, self.population() as u64);
+        #[cfg(feature = "serde")]
+        {
+            self.timeline.reset(start);
+            self.timeline.set_start(self.save());
+            self.timeline
+                .checkpoint(start, self.state.rounds, &self.current);
+        }
+        self.update_title();
+        if self.settings.start_skip > 0 {
+            self.fast_forward(self.settings.start_skip);
+        }
+    }
+    /// Run a single iteration of the game loop: handle the waiting input, step the due
+    /// generations, fire the due timers and render, unless the steps are running behind
+    ///
+    /// # Returns
+    ///
+    /// `false` once the player quit or the board is stable, a stable board only pauses the game
+    /// with `auto_pause`
+    pub fn tick(&mut self) -> bool {
+        let started = self.clock.now();
+        let events = self.input.drain(MAX_EVENTS_PER_TICK);
+        self.handle_keys(events);
+        if self.state.latest_command == Command::QUIT {
+            return false;
+        }
+        let now = self.clock.now();
+        let due_steps = match &mut self.step_scheduler {
+            Some(scheduler) => scheduler.due_steps(now),
+            None => 0,
+        };
+        for _ in 0..due_steps {
+            let step_started = self.clock.now();
+            self.step();
+            let cost = self.clock.now().saturating_duration_since(step_started);
+            if let Some(scheduler) = &mut self.step_scheduler {
+                scheduler.record_step(cost);
+            }
+        }
+        for event in self.timers.tick(now) {
+            match event {
+                ConwaysTimer::FpsSample => {
+                    let secs = (now - self.last_fps_sample).as_secs_f64();
+                    self.state.fps_last = (self.state.fps_current as f64 / secs).floor() as u64;
+                    self.state.fps_current = 0;
+                    self.last_fps_sample = now;
+                }
+                ConwaysTimer::ResizeCheck => self.check_screen_size(),
+                #[cfg(feature = "serde")]
+                ConwaysTimer::Autosave => self.write_autosave(),
+            }
+        }
+        self.metrics.sample(
+            UPDATE_TIME,
+            self.clock.now().saturating_duration_since(started),
+        );
+        self.state.fps_current += 1;
+        if self.state.is_fps_limited {
+            thread::sleep(Duration::from_millis(16));
+        }
+
+        let render_started = self.clock.now();
+        let should_render = match &mut self.step_scheduler {
+            Some(scheduler) => scheduler.should_render(render_started),
+            None => true,
+        };
+        if should_render {
+            self.render();
+            self.metrics.sample(
+                RENDER_TIME,
+                self.clock.now().saturating_duration_since(render_started),
+            );
+        }
+        if self.is_stable() && !self.settings.auto_pause {
+            let _ = self.screen.handle.bell();
+            return false;
+        }
+        true
+    }
+    /// Cancel the timers and restore the terminal, including the screen content from before the
+    /// game started, once the game stopped. The autosave is removed, as the session shut down
+    /// cleanly
+    pub fn finish(&mut self) {
+        self.timers = Timers::default();
+        self.step_scheduler = None;
+        #[cfg(feature = "serde")]
+        if let Some(autosave) = &self.autosave {
+            autosave.discard();
+        }
+        restore_terminal(self.screen.handle.as_mut());
+    }
+    /// Set the file the game is saved to every `autosave_interval` while it runs. Has to be set
+    /// before `start`
+    #[cfg(feature = "serde")]
+    pub fn set_autosave(&mut self, autosave: Option<Autosave>) {
+        self.autosave = autosave;
+    }
+    /// Broadcast every generation to the spectators watching this server
+    #[cfg(feature = "net")]
+    pub fn set_spectators(&mut self, spectators: Option<SpectatorServer>) {
+        self.spectators = spectators;
+    }
+    /// Share the clipboard of the editor with the scenes and the system clipboard that the
+    /// clipboard was created with
+    pub fn set_clipboard(&mut self, clipboard: SharedClipboard) {
+        self.clipboard = clipboard;
+    }
+    pub fn clipboard(&self) -> &SharedClipboard {
+        &self.clipboard
+    }
+    /// Draw a recorded run dimmed behind the board at the generation of the board, `None` to
+    /// stop drawing it
+    #[cfg(feature = "serde")]
+    pub fn set_ghost(&mut self, ghost: Option<Ghost>) {
+        self.ghost = ghost;
+        self.drawn_cells = None;
+    }
+    /// Send the current generation to the spectators, greeting any that connected since the last
+    /// one
+    fn broadcast_to_spectators(&mut self) {
+        #[cfg(feature = "net")]
+        if self.spectators.is_some() {
+            let board = self.save();
+            if let Some(spectators) = &mut self.spectators {
+                spectators.accept_pending();
+                spectators.broadcast(&board);
+            }
+        }
+    }
+    /// Switch between drawing the cells as blocks and with their debug info
+    fn set_print_mode(&mut self, print_mode: PrintMode) {
+        if print_mode == self.state.print_mode {
+            return;
+        }
+        self.clear_screen();
+        let _ = self.screen.handle.set_cursor_visible(false);
+        self.state.print_mode = print_mode;
+        self.update_title();
+    }
+    /// Get the settings that can be changed on the options screen
+    pub fn options(&self) -> GameOptions {
+        GameOptions {
+            round_duration: self.settings.round_duration,
+            boundary: self.settings.boundary,
+            theme: self.settings.theme,
+            print_mode: self.state.print_mode,
+        }
+    }
+    /// Change the settings of the options screen, also while the game runs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// let mut options = game.options();
+    /// options.round_duration = Duration::from_millis(50);
+    /// game.apply_options(&options);
+    /// ```
+    pub fn apply_options(&mut self, options: &GameOptions) {
+        if options.round_duration != self.settings.round_duration {
+            self.settings.round_duration = options.round_duration;
+            if let Some(scheduler) = &mut self.step_scheduler {
+                scheduler.set_interval(options.round_duration, self.clock.now());
+            }
+        }
+        self.settings.boundary = options.boundary;
+        self.settings.theme = options.theme;
+        self.set_print_mode(options.print_mode);
+    }
+    /// Write the options to the options file from the settings
+    ///
+    /// # Returns
+    ///
+    /// A message for the player that says if the options were saved
+    fn save_options(&mut self) -> String {
+        #[cfg(feature = "serde")]
+        {
+            let path = self.settings.options_path.clone();
+            match self.options().save(&path) {
+                Ok(_) => format!("Options saved to {}", path.display()),
+                Err(e) => {
+                    self.state.latest_err = format!("{:?}", e);
+                    "Failed to save the options".to_string()
+                }
+            }
+        }
+        #[cfg(not(feature = "serde"))]
+        "Saving the options needs the serde feature".to_string()
+    }
+    /// Get the visible part of the board the way it is drawn in pretty mode, with a gap around
+    /// every cell
+    pub fn frame(&self) -> Frame {
+        let viewport = self.viewport();
+        let (width, height) = (
+            self.settings.cell_view_width,
+            self.settings.cell_view_height,
+        );
+        let mut frame = vec![
+            vec![Pixel::default(); 1 + viewport.size.x * (width + 1)];
+            1 + viewport.size.y * (height + 1)
+        ];
+        for view_y in 0..viewport.size.y {
+            for view_x in 0..viewport.size.x {
+                let (x, y) = (viewport.first.x + view_x, viewport.first.y + view_y);
+                let (background, foreground) = self.cell_colors(x, y, self.current[y][x]);
+                let pixel = Pixel::new(' ', background, foreground);
+                for row in 0..height {
+                    for column in 0..width {
+                        frame[1 + view_y * (height + 1) + row][1 + view_x * (width + 1) + column] =
+                            pixel.clone();
+                    }
+                }
+            }
+        }
+        frame
+    }
+    /// Write the board that was drawn last to a text file and to a file with ANSI colors in the
+    /// export directory, named after the seed and the round. Called at the end of a render, so
+    /// that the files hold the frame that is on the screen
+    fn dump_frame(&mut self) {
+        let path = self.settings.export_dir.join(format!(
+            "conway_{}_{}",
+            self.settings.seed, self.state.rounds
+        ));
+        match write_frame_dump(&self.frame(), &path) {
+            Ok((text_path, _)) => self.toasts.push(
+                format!("Saved {} and .ans", text_path.display()),
+                TOAST_DURATION,
+                self.clock.now(),
+            ),
+            Err(_) => self.state.latest_err = "Failed to dump the frame".to_string(),
+        }
+    }
+    /// Add the arguments that start the game with the seed and the size of the board to the seeds
+    /// file in the export directory, so that a random run can be started again
+    ///
+    /// # Returns
+    ///
+    /// The path of the seeds file
+    fn save_seed(&self) -> std::io::Result<std::path::PathBuf> {
+        use std::io::Write;
+
+        let path = self.settings.export_dir.join(SEEDS_FILE);
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        writeln!(
+            file,
+            "--seed {} --x-len {} --y-len {}",
+            self.settings.seed, self.settings.x_len, self.settings.y_len
+        )?;
+        Ok(path)
+    }
+    /// Write the board to a PNG in the export directory, named after the seed and the round
+    ///
+    /// # Returns
+    ///
+    /// The path of the screenshot
+    #[cfg(feature = "export-image")]
+    pub fn save_screenshot(&self) -> std::io::Result<std::path::PathBuf> {
+        let path = self.settings.export_dir.join(format!(
+            "conway_{}_{}.png",
+            self.settings.seed, self.state.rounds
+        ));
+        let image = Image::from_frame(&self.frame(), &ImageOptions::default());
+        write_png(&image, &path)?;
+        Ok(path)
+    }
+    /// Write the visible part of the board to an SVG file, which scales without blurring the
+    /// cells
+    #[cfg(feature = "export-image")]
+    pub fn export_svg(&self, path: &std::path::Path) -> std::io::Result<()> {
+        std::fs::write(path, frame_svg(&self.frame(), &ImageOptions::default()))
+    }
+    /// Start recording the next generations, or start writing the recording to a GIF in the
+    /// export directory when one was started. The GIF is written on a worker thread, see
+    /// `finish_gif_export`
+    #[cfg(feature = "export-image")]
+    pub fn toggle_recording(&mut self) {
+        let now = self.clock.now();
+        let recorder = match self.recorder.take() {
+            Some(recorder) => recorder,
+            None => {
+                let mut recorder = GifRecorder::new(
+                    self.settings.gif_generations,
+                    self.settings.gif_options,
+                    self.settings.gif_frame_delay,
+                );
+                recorder.push(&self.frame());
+                self.recorder = Some(recorder);
+                let message = format!("Recording {} generations", self.settings.gif_generations);
+                self.toasts.push(message, TOAST_DURATION, now);
+                return;
+            }
+        };
+        let path = self.settings.export_dir.join(format!(
+            "conway_{}_{}.gif",
+            self.settings.seed, self.state.rounds
+        ));
+        // Only one recording is written at a time
+        if let Some(export) = self.gif_export.take() {
+            self.report_gif_export(export.join());
+        }
+        self.gif_export = Some(recorder.save_in_background(path, now));
+    }
+    /// Show how far the recording that is being written got below the board, and announce it
+    /// once it is written
+    ///
+    /// # Returns
+    ///
+    /// `true` once no recording is being written anymore
+    #[cfg(feature = "export-image")]
+    pub fn finish_gif_export(&mut self) -> bool {
+        let Some(export) = &self.gif_export else {
+            return true;
+        };
+        if !export.is_finished() {
+            let line = ProgressBar::tracking(
+                "Saving GIF",
+                FAST_FORWARD_UPDATES as usize,
+                export.progress(),
+                self.clock.as_ref(),
+            )
+            .rows()
+            .remove(0);
+            self.print_info_line(1, &line);
+            return false;
+        }
+        if let Some(export) = self.gif_export.take() {
+            self.print_info_line(1, "");
+            self.report_gif_export(export.join());
+        }
+        true
+    }
+    #[cfg(feature = "export-image")]
+    fn report_gif_export(&mut self, result: std::io::Result<std::path::PathBuf>) {
+        match result {
+            Ok(path) => self.toasts.push(
+                format!("Saved {}", path.display()),
+                TOAST_DURATION,
+                self.clock.now(),
+            ),
+            Err(_) => self.state.latest_err = "Failed to save the recording".to_string(),
+        }
+    }
+    /// Add the board to the recording, if one is running
+    #[cfg(feature = "export-image")]
+    fn record_frame(&mut self) {
+        if self
+            .recorder
+            .as_ref()
+            .is_none_or(|recorder| recorder.is_full())
+        {
+            return;
+        }
+        let frame = self.frame();
+        if let Some(recorder) = &mut self.recorder {
+            if !recorder.push(&frame) {
+                self.toasts.push(
+                    "Recording finished".to_string(),
+                    TOAST_DURATION,
+                    self.clock.now(),
+                );
+            }
+        }
+    }
+    /// Replace the achievements, to continue with the progress of earlier games
+    pub fn set_achievements(&mut self, achievements: Achievements) {
+        self.achievements = achievements;
+    }
+    /// Get the achievements, including the ones unlocked during this game
+    pub fn achievements(&self) -> &Achievements {
+        &self.achievements
+    }
+    /// Apply an event to the achievements and announce the ones it unlocked
+    fn record_event(&mut self, event: GameEvent) {
+        let now = self.clock.now();
+        for achievement in self.achievements.record(event) {
+            self.toasts.push(
+                format!("Achievement unlocked: {}", achievement),
+                TOAST_DURATION,
+                now,
+            );
+        }
+    }
+    /// Read the high scores from the scores file in the settings
+    ///
+    /// # Returns
+    ///
+    /// The lines of the high score table, or a line that says why there are no scores
+    fn scores_table(&mut self) -> Vec<String> {
+        #[cfg(feature = "serde")]
+        match ScoreBoard::load(&self.settings.scores_path) {
+            Ok(scores) => scores.table(),
+            Err(e) => {
+                self.state.latest_err = format!("{:?}", e);
+                vec!["Failed to read the scores".to_string()]
+            }
+        }
+        #[cfg(not(feature = "serde"))]
+        vec!["Scores need the serde feature".to_string()]
+    }
+    /// Add the length of this game to the scores file in the settings
+    ///
+    /// # Returns
+    ///
+    /// `true` if the game is the new longest run
+    #[cfg(feature = "serde")]
+    pub fn record_score(&self) -> Result<bool, SaveError> {
+        ScoreBoard::update(&self.settings.scores_path, |scores| {
+            scores.record_conway_run(self.state.rounds, self.settings.seed)
+        })
+    }
+    /// Write the current state to the save file from the settings
+    ///
+    /// # Returns
+    ///
+    /// A message for the player that says if the game was saved
+    fn save_to_file(&mut self) -> String {
+        #[cfg(feature = "serde")]
+        {
+            let path = self.settings.save_path.clone();
+            match self.save().save(&path) {
+                Ok(_) => format!("Saved to {}", path.display()),
+                Err(e) => {
+                    self.state.latest_err = format!("{:?}", e);
+                    "Failed to save the game".to_string()
+                }
+            }
+        }
+        #[cfg(not(feature = "serde"))]
+        "Saving needs the serde feature".to_string()
+    }
+    /// Write the current state to the autosave, if there is one
+    #[cfg(feature = "serde")]
+    fn write_autosave(&mut self) {
+        if let Some(autosave) = &self.autosave {
+            if autosave.write(&self.save()).is_err() {
+                self.state.latest_err = "Failed to write the autosave".to_string();
+            }
+        }
+    }
+    /// Capture the current generation so that it can be written to a file
+    #[cfg(feature = "serde")]
+    pub fn save(&self) -> SavedGame {
+        SavedGame::new(
+            &self.current,
+            self.settings.seed,
+            self.settings.rule,
+            self.state.rounds,
+        )
+    }
+    /// Continue a saved game. The grid takes the size of the saved game
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::path::Path;
+    /// # use tic_tac_toe2::conway::save::{SaveError, SavedGame};
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # fn main() -> Result<(), SaveError> {
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// # let path = Path::new("conway.json");
+    /// game.restore(&SavedGame::load(path)?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn restore(&mut self, saved: &SavedGame) -> Result<(), SaveError> {
+        let cells = saved.grid()?;
+        self.settings.rule = saved.rule()?;
+        self.settings.seed = saved.seed;
+        self.state.rounds = saved.rounds;
+        self.state.editor = None;
+        self.state.picked_pattern = None;
+        self.state.inspector = Coord::new(cells[0].len() / 2, cells.len() / 2);
+        self.set_grid(cells);
+        Ok(())
+    }
+    /// Summarize the session so far, printed once the game is finished
+    pub fn summary(&self) -> SessionSummary {
+        self.metrics.summary(self.settings.seed, self.clock.now())
+    }
+    /// Add the current generation to the timeline every `timeline_checkpoint_interval` rounds
+    fn checkpoint_timeline(&mut self) {
+        #[cfg(feature = "serde")]
+        if self.settings.timeline_checkpoint_interval > 0
+            && self
+                .state
+                .rounds
+                .is_multiple_of(self.settings.timeline_checkpoint_interval)
+        {
+            self.timeline
+                .checkpoint(self.clock.now(), self.state.rounds, &self.current);
+        }
+    }
+    /// Write the timeline of the session to a JSON file, ending with a checkpoint of the current
+    /// generation, so that the run can be analyzed or attached to a bug report
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::path::Path;
+    /// # use tic_tac_toe2::conway::save::SaveError;
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # fn main() -> Result<(), SaveError> {
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.finish();
+    /// game.write_timeline(Path::new("conway_timeline.json"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn write_timeline(&mut self, path: &std::path::Path) -> Result<(), SaveError> {
+        self.timeline
+            .checkpoint(self.clock.now(), self.state.rounds, &self.current);
+        self.timeline
+            .session(&self.settings, &self.summary())
+            .save(path)
+    }
+    /// Replay a recorded session from the board it started with and check that every checkpoint
+    /// of it is reached with the same board, to catch changes that make the stepping
+    /// nondeterministic
+    ///
+    /// The commands are replayed in the generation their key arrived in, regardless of the time
+    /// between them. The edits of the mouse are not replayed, a session with them diverges
+    ///
+    /// # Returns
+    ///
+    /// The number of checkpoints that matched, or where the replay went apart from the recording
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::path::Path;
+    /// # use tic_tac_toe2::{conway::{divergence::VerifyError, timeline::RecordedSession}, ConwaysGame};
+    /// # fn main() -> Result<(), VerifyError> {
+    /// let session = RecordedSession::load(Path::new("conway_55_timeline.json"))
+    ///     .expect("the timeline was written by the game");
+    /// println!("{} checkpoints match", ConwaysGame::verify_session(&session)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn verify_session(session: &RecordedSession) -> Result<usize, VerifyError> {
+        let mut game = ConwaysGame::replay_of(session)?;
+        // The replay keeps every generation it reaches, also those a command skips over
+        game.settings.timeline_checkpoint_interval = 1;
+        let now = game.clock.now();
+        game.timeline
+            .checkpoint(now, game.state.rounds, &game.current);
+        let mut checked = 0;
+        for event in session.events.iter() {
+            let TimelineEvent::Checkpoint {
+                generation, hash, ..
+            } = event
+            else {
+                game.replay_command(event);
+                continue;
+            };
+            game.advance_to(*generation);
+            let expected =
+                u64::from_str_radix(hash, 16).map_err(|_| VerifyError::BadHash(hash.clone()))?;
+            let actual = match game.state.rounds == *generation {
+                true => Some(game.state_hash()),
+                false => game.timeline.checkpoint_hash(*generation),
+            };
+            if actual != Some(expected) {
+                return Err(VerifyError::Diverged(Divergence {
+                    generation: *generation,
+                    reached: game.state.rounds,
+                    expected,
+                    actual,
+                }));
+            }
+            checked += 1;
+        }
+        Ok(checked)
+    }
+    /// Create a game that is never drawn at the board a recorded session started with, with the
+    /// settings that change how the session was stepped
+    #[cfg(feature = "serde")]
+    pub(crate) fn replay_of(session: &RecordedSession) -> Result<ConwaysGame, VerifyError> {
+        let start = session.start.as_ref().ok_or(VerifyError::NoStart)?;
+        let mut game = ConwaysGame::headless(1, 1, start.seed);
+        game.restore(start).map_err(|_| VerifyError::NoStart)?;
+        game.settings.boundary = session.boundary.parse().unwrap_or_default();
+        game.settings.auto_pause = session.auto_pause;
+        game.settings.cycle_max_period = session.cycle_max_period;
+        game.settings.skip_generations = session.skip_generations;
+        Ok(game)
+    }
+    /// Step to the generation of a recorded command and handle its key again, checkpoints are
+    /// skipped
+    #[cfg(feature = "serde")]
+    pub(crate) fn replay_command(&mut self, event: &TimelineEvent) {
+        let TimelineEvent::Command {
+            generation,
+            input,
+            is_down,
+            repeats,
+            ..
+        } = event
+        else {
+            return;
+        };
+        self.advance_to(*generation);
+        // The reverts of undo are replayed by the key that undid them
+        if let Some(key) = Key::parse(input) {
+            self.handle_key(KeyEvent {
+                command: key.to_char(),
+                repreat_count: *repeats as u16,
+                is_down: *is_down,
+            });
+        }
+    }
+    /// Step the board until a generation is reached, regardless of a pause, the way the steps of
+    /// the game loop do
+    #[cfg(feature = "serde")]
+    pub(crate) fn advance_to(&mut self, generation: u64) {
+        while self.state.rounds < generation {
+            self.next();
+            self.state.rounds += 1;
+            self.history.record(self.state.rounds, &self.current);
+            self.detect_cycle();
+            self.checkpoint_timeline();
+        }
+    }
+    /// Get the stable hash of the current generation, see `board_hash`
+    pub fn state_hash(&self) -> u64 {
+        board_hash(&self.current)
+    }
+    /// Replace the clock the game reads the time from, e.g. with a `VirtualClock` in tests
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.last_fps_sample = clock.now();
+        self.metrics.reset(clock.now());
+        #[cfg(feature = "serde")]
+        self.timeline.reset(clock.now());
+        self.clock = clock;
+    }
+    /// Advance the game by a number of rounds without drawing the board in between, stopping
+    /// early once it is stable. The progress is shown below the board while the rounds are
+    /// calculated
+    ///
+    /// # Arguments
+    ///
+    /// * `generations` - the number of rounds to advance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.fast_forward(1000);
+    /// ```
+    pub fn fast_forward(&mut self, generations: u64) {
+        let update_every = (generations / FAST_FORWARD_UPDATES).max(1);
+        let progress = Progress::new(generations, self.clock.now());
+        for generation in 1..=generations {
+            self.next();
+            self.state.rounds += 1;
+            self.history.record(self.state.rounds, &self.current);
+            let is_paused = self.detect_cycle();
+            self.metrics.increment(GENERATIONS);
+            self.checkpoint_timeline();
+            self.record_event(GameEvent::Generations(1));
+            if is_paused || self.is_stable() {
+                break;
+            }
+            if generation % update_every == 0 {
+                progress.set(generation);
+                let line = ProgressBar::tracking(
+                    "Fast forward",
+                    FAST_FORWARD_UPDATES as usize,
+                    &progress,
+                    self.clock.as_ref(),
+                )
+                .rows()
+                .remove(0);
+                self.print_info_line(2, &line);
+                let _ = self.screen.handle.flush();
+            }
+        }
+        self.print_info_line(2, "");
+        self.update_title();
+    }
+    /// Advance the game by one round unless it is paused
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.step();
+    /// ```
+    pub fn step(&mut self) {
+        if !self.state.is_paused {
+            self.next();
+            self.state.rounds += 1;
+            self.history.record(self.state.rounds, &self.current);
+            self.detect_cycle();
+            self.metrics.increment(GENERATIONS);
+            self.checkpoint_timeline();
+            self.record_event(GameEvent::Generations(1));
+            self.update_title();
+            self.broadcast_to_spectators();
+            #[cfg(feature = "export-image")]
+            self.record_frame();
+        }
+    }
+    /// Pause the game with `auto_pause` once the board is stable or repeats itself. The game is
+    /// only paused again for a cycle after the board stopped repeating, e.g. after an edit
+    ///
+    /// # Returns
+    ///
+    /// `true` if the game was paused for a new cycle
+    fn detect_cycle(&mut self) -> bool {
+        let cycle = self.cycle_detector.record(&self.current);
+        if !self.settings.auto_pause {
+            return false;
+        }
+        // An empty board has no shape to repeat but is stable
+        let cycle = cycle.or(self.is_stable().then_some(Cycle {
+            period: 1,
+            displacement: (0, 0),
+        }));
+        match (cycle, self.state.detected_cycle) {
+            (None, _) => {
+                self.state.detected_cycle = None;
+                false
+            }
+            (Some(cycle), None) => {
+                self.state.detected_cycle = Some(cycle);
+                self.state.is_paused = true;
+                let _ = self.screen.handle.bell();
+                true
+            }
+            (Some(_), Some(_)) => false,
+        }
+    }
+    /// Draw the board in the current print mode, with the pause banner if the game is paused,
+    /// and flush it to the handle. A screen that can not fit a cell of the board and the lines
+    /// below it says "terminal too small" instead
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.render();
+    /// ```
+    pub fn render(&mut self) {
+        // A screen that is too small says so once, instead of drawing a broken frame every tick
+        let required = self.required_screen_size();
+        if self
+            .too_small_shown_for
+            .is_some_and(|shown| shown.x == required.x && shown.y == required.y)
+        {
+            return;
+        }
+        match guard_terminal_size(
+            self.settings.screen_size,
+            required,
+            self.screen.handle.as_mut(),
+        ) {
+            Ok(true) if self.too_small_shown_for.is_some() => {
+                self.too_small_shown_for = None;
+                self.clear_screen();
+            }
+            Ok(true) => (),
+            Ok(false) => {
+                self.too_small_shown_for = Some(required);
+                self.drawn_cells = None;
+                return;
+            }
+            Err(e) => self.state.latest_err = format!("{:?}", e),
+        }
+        // The cells below a menu, a banner or a toast are drawn again once it is gone
+        let is_board_covered = self.state.is_paused
+            || self.state.is_input_log_open
+            || self.state.is_help_open
+            || self.state.picked_pattern.is_some()
+            || self.state.menu_page.is_some()
+            || self.state.options_screen.is_some()
+            || self.state.pause_menu.is_some()
+            || !self.toasts.toasts().is_empty();
+        if is_board_covered || self.was_board_covered {
+            self.drawn_cells = None;
+        }
+        self.was_board_covered = is_board_covered;
+        self.print(self.state.print_mode);
+        if self.state.is_paused
+            && self.state.pause_menu.is_none()
+            && self.state.time_travel.is_none()
+        {
+            let center = self.find_center();
+            match self.screen.print_around_centerpoint(
+                "PAUSED".to_string(),
+                center,
+                &MessageStyle::default(),
+            ) {
+                Ok(_) => (),
+                Err(e) => self.state.latest_err = e.to_string(),
+            };
+        }
+        if let Some(picked) = self.state.picked_pattern {
+            let hints = KeyHints::new(&[("p", "next"), ("enter", "select"), ("esc", "close")]);
+            let line = format!("Pattern: < {} > ({})", PATTERNS[picked].name, hints.line());
+            self.print_info_line(0, &line);
+            let mut table =
+                Table::new(vec![Column::left("Pattern"), Column::right("Size")], || {
+                    PATTERNS
+                        .iter()
+                        .map(|pattern| {
+                            let size = format!("{}x{}", pattern.width(), pattern.height());
+                            vec![pattern.name.to_string(), size]
+                        })
+                        .collect()
+                })
+                .visible_rows(PICKER_ROWS);
+            table.highlight(Some(picked));
+            self.print_panel(&table.rows());
+        } else if let (Some(cycle), true, None) = (
+            self.state.detected_cycle,
+            self.state.is_paused,
+            &self.state.time_travel,
+        ) {
+            let line = format!("Repeating: {} (e: edit, b: reseed, space: continue)", cycle);
+            self.print_info_line(0, &line);
+        } else if self.is_status_line_shown() {
+            let line = self.status_line();
+            self.print_info_line(0, &line);
+        }
+        if let Some(editor) = &self.state.editor {
+            let line = editor.status();
+            self.print_info_line(1, &line);
+        }
+        self.print_behind_status();
+        #[cfg(feature = "export-image")]
+        self.finish_gif_export();
+        if let Some(time_travel) = &self.state.time_travel {
+            for (index, line) in time_travel.lines(&self.history).iter().enumerate() {
+                self.print_info_line(index, line);
+            }
+        }
+        if self.state.is_input_log_open {
+            self.print_input_log();
+        }
+        if let Some(page) = &self.state.menu_page {
+            let mut lines = page.clone();
+            lines.push("Press any key to go back".to_string());
+            self.print_panel(&lines);
+        } else if let Some(picker) = &self.state.file_picker {
+            let mut lines = vec!["Load".to_string()];
+            lines.extend(picker.rows());
+            lines.push(
+                KeyHints::new(&[
+                    ("w/s", "move"),
+                    ("enter", "open"),
+                    ("backspace", "up"),
+                    ("esc", "back"),
+                ])
+                .line(),
+            );
+            self.print_panel(&lines);
+        } else if let Some((prompt, input)) = &self.state.text_prompt {
+            let title = match prompt {
+                TextPrompt::Seed => "New seed",
+                TextPrompt::SavePath => "Save",
+                TextPrompt::MacroName => "Record a macro",
+                TextPrompt::PlayMacro => "Play a macro",
+            };
+            let mut lines = vec![title.to_string()];
+            lines.extend(input.rows());
+            lines.push(KeyHints::new(&[("enter", "ok"), ("esc", "back")]).line());
+            self.print_panel(&lines);
+        } else if let Some(screen) = &self.state.options_screen {
+            let mut lines = vec!["Options".to_string()];
+            lines.extend(screen.lines());
+            lines
+                .push(KeyHints::new(&[("w/s", "move"), ("a/d", "change"), ("esc", "back")]).line());
+            self.print_panel(&lines);
+        } else if let Some(menu) = &self.state.pause_menu {
+            let mut lines = vec!["Paused".to_string()];
+            lines.extend(menu.menu.rows());
+            lines.push(menu.status.clone());
+            lines.push(
+                KeyHints::new(&[("w/s", "move"), ("enter", "select"), ("esc", "resume")]).line(),
+            );
+            self.print_panel(&lines);
+        }
+        if self.state.is_help_open {
+            let mut lines = vec!["Keys".to_string()];
+            lines.extend(self.key_bindings.help_lines());
+            lines.push("Press any key to close".to_string());
+            self.print_panel(&lines);
+        }
+        self.print_toasts();
+        let _ = self.screen.handle.flush();
+        if self.state.is_frame_dump_pending {
+            self.state.is_frame_dump_pending = false;
+            self.dump_frame();
+        }
+    }
+    /// Print the toasts in the top right corner of the board. They are printed every frame, since
+    /// the board is drawn over them
+    fn print_toasts(&mut self) {
+        self.toasts.dismiss_expired(self.clock.now());
+        if self.toasts.toasts().is_empty() {
+            return;
+        }
+        let viewport = self.viewport();
+        let cell_size = self.cell_size();
+        let origin = self.board_origin();
+        let area = Square::new(
+            Usize2d::new(origin.x, origin.y),
+            Usize2d::new(
+                origin.x + (viewport.size.x * cell_size.x).max(1) - 1,
+                self.below_board().max(origin.y + 1) - 1,
+            ),
+        );
+        self.toasts.invalidate();
+        if self
+            .toasts
+            .write(self.screen.handle.as_mut(), &area)
+            .is_err()
+        {
+            self.state.latest_err = "Failed to write the toasts".to_string();
+        }
+    }
+    /// Print the input log in the top left corner of the board. It does not take the keys, so the
+    /// game can be played while the log shows how the keys are handled
+    fn print_input_log(&mut self) {
+        let mut lines = vec!["Input log".to_string()];
+        lines.extend(self.input_log.lines());
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0) + 2;
+        let origin = self.board_origin();
+        self.set_colors(TerminalColors::Black, TerminalColors::White);
+        for (index, line) in lines.iter().enumerate() {
+            self.write_at(
+                &format!(" {:<width$}", line, width = width - 1),
+                Coord::new(origin.x + 1, origin.y + 1 + index),
+            );
+        }
+    }
+    /// Print lines in a panel over the center of the board
+    fn print_panel(&mut self, lines: &[String]) {
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0) + 2;
+        let center = self.find_center();
+        let x = center.x.saturating_sub(width / 2);
+        let y = center.y.saturating_sub(lines.len() / 2);
+        self.set_colors(TerminalColors::Black, TerminalColors::White);
+        for (index, line) in lines.iter().enumerate() {
+            self.write_at(
+                &format!(" {:<width$}", line, width = width - 1),
+                Coord::new(x, y + index),
+            );
+        }
+    }
+    /// Say on the last line below the board that the generations are running behind real time,
+    /// and clear the line again once they caught up
+    fn print_behind_status(&mut self) {
+        let now = self.clock.now();
+        let Some(scheduler) = &self.step_scheduler else {
+            return;
+        };
+        let is_behind = !self.state.is_paused && scheduler.is_behind(now);
+        if is_behind {
+            let line = match scheduler.step_cost() {
+                Some(cost) => format!(
+                    "Running behind real-time: a round takes {:.1}ms of {:.1}ms",
+                    cost.as_secs_f64() * 1000.0,
+                    scheduler.interval().as_secs_f64() * 1000.0
+                ),
+                None => "Running behind real-time".to_string(),
+            };
+            self.print_info_line(2, &line);
+        } else if self.is_behind_shown {
+            self.print_info_line(2, "");
+        }
+        self.is_behind_shown = is_behind;
+    }
+    /// Test if the status line fits below the board. The expanded debug panel reaches below a
+    /// short board and shows the seed itself, and the panels over the board cover the line
+    fn is_status_line_shown(&self) -> bool {
+        let is_debug_expanded =
+            self.state.print_mode == PrintMode::DEBUG && !self.state.is_debug_collapsed;
+        !is_debug_expanded
+            && !self.state.is_help_open
+            && self.state.menu_page.is_none()
+            && self.state.options_screen.is_none()
+            && self.state.file_picker.is_none()
+    }
+    /// Get the line below the board with the round and the seed, so that a run can be started
+    /// again, and the key that saves the seed
+    fn status_line(&self) -> String {
+        let mut line = format!("Round {} | Seed {}", self.state.rounds, self.settings.seed);
+        let save_key = self
+            .key_bindings
+            .iter()
+            .find(|(_, command)| *command == Command::SAVESEED);
+        if let Some((key, _)) = save_key {
+            let key = key.to_string();
+            line += &format!(" ({})", KeyHints::new(&[(&key, "save seed")]).line());
+        }
+        line
+    }
+    /// Print a line of the pattern picker or the editor below the board
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - the line below the board, smaller than `INFO_LINE_COUNT`
+    /// * `line` - the text of the line
+    fn print_info_line(&mut self, index: usize, line: &str) {
+        assert!(index < INFO_LINE_COUNT);
+        let y = self.below_board() + index;
+        self.set_colors(TerminalColors::Black, TerminalColors::White);
+        self.write_at(
+            &format!("{:<width$}", line, width = DEBUG_LINE_WIDTH),
+            Coord::new(0, y),
+        );
+    }
+    /// Place a pattern on the board, wrapping around the edges of the grid
+    ///
+    /// # Arguments
+    ///
+    /// * `placement` - the pattern and the location of its top left corner. Without a location
+    ///   the pattern is centered on the grid
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::conway::patterns::PatternErr;
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # fn main() -> Result<(), PatternErr> {
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.place_pattern(&"glider@10,10".parse()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn place_pattern(&mut self, placement: &PatternPlacement) {
+        let pattern = placement.pattern;
+        let origin = placement.origin.unwrap_or(Coord::new(
+            self.settings.x_len.saturating_sub(pattern.width()) / 2,
+            self.settings.y_len.saturating_sub(pattern.height()) / 2,
+        ));
+        self.previous = self.current.clone();
+        self.neighbor_counts = None;
+        for cell in pattern.live_cells() {
+            let x = (origin.x + cell.x) % self.settings.x_len;
+            let y = (origin.y + cell.y) % self.settings.y_len;
+            self.current[y][x] = true;
+        }
+        self.record_event(GameEvent::PatternPlaced(pattern.name));
+    }
+    /// Place copied or loaded cells in the middle of the board, wrapping around its edges. Only
+    /// the live cells are placed, like the cells of a pattern
+    pub fn place_clipboard(&mut self, clipboard: &Clipboard) {
+        let origin = Coord::new(
+            self.settings.x_len.saturating_sub(clipboard.width()) / 2,
+            self.settings.y_len.saturating_sub(clipboard.height()) / 2,
+        );
+        self.previous = self.current.clone();
+        self.neighbor_counts = None;
+        for (y, row) in clipboard.cells.iter().enumerate() {
+            for (x, is_alive) in row.iter().enumerate() {
+                if *is_alive {
+                    let x = (origin.x + x) % self.settings.x_len;
+                    let y = (origin.y + y) % self.settings.y_len;
+                    self.current[y][x] = true;
+                }
+            }
+        }
+    }
+    /// Show the mode and round in the title of the terminal
+    fn update_title(&mut self) {
+        let title = format!(
+            "Conway's Game of Life - {} - round {} - seed {}",
+            self.state.print_mode, self.state.rounds, self.settings.seed
+        );
+        if self.screen.handle.set_title(&title).is_err() {
+            self.state.latest_err = "Failed to set the title".to_string();
+        }
+    }
+    /// Apply a key input to the game, the same way it is applied while the game is running
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::console::input_record::KeyEvent;
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.handle_key(KeyEvent::key_down(' '));
+    /// ```
+    pub fn handle_key(&mut self, command: KeyEvent) {
+        // The keys that record or play a macro, and the names typed for them, are not recorded
+        let is_macro_prompt = matches!(
+            self.state.text_prompt,
+            Some((TextPrompt::MacroName | TextPrompt::PlayMacro, _))
+        );
+        #[cfg(feature = "serde")]
+        if !self.is_playing_macro {
+            self.timeline.mark(self.state.rounds);
+        }
+        self.process_key_command(command);
+        let logged = GameCommand::new(
+            self.state.latest_command,
+            CommandSource::Key(command),
+            self.clock.now(),
+        );
+        self.log_command(logged);
+        let is_macro_command = matches!(
+            self.state.latest_command,
+            Command::RECORDMACRO | Command::PLAYMACRO
+        );
+        if !is_macro_prompt && !is_macro_command {
+            self.macros.record(command);
+        }
+        if self.state.is_reset_active {
+            self.reset();
+        }
+        let macro_keys = std::mem::take(&mut self.macro_keys);
+        if !macro_keys.is_empty() {
+            self.is_playing_macro = true;
+            self.handle_keys(macro_keys);
+            self.is_playing_macro = false;
+        }
+    }
+    /// Handle the key events of an iteration of the game loop in order, until the player quits.
+    /// The repeats of a key that moves the board are handled as a single move as far as all the
+    /// repeats, so a held key does not redraw the board for every step
+    pub fn handle_keys(&mut self, events: Vec<KeyEvent>) {
+        let mut events = events.into_iter().peekable();
+        while let Some(mut event) = events.next() {
+            if self.is_move_key(event) {
+                while let Some(repeat) = events.next_if(|next| next.command == event.command) {
+                    event.repreat_count = event.repreat_count.saturating_add(repeat.repreat_count);
+                }
+            }
+            self.handle_key(event);
+            if self.state.latest_command == Command::QUIT {
+                return;
+            }
+        }
+    }
+    /// Check if a key event moves the board, which is only the case while no menu, page, editor
+    /// or other mode takes the keys first
+    fn is_move_key(&self, event: KeyEvent) -> bool {
+        let is_taken = self.state.is_help_open
+            || self.state.menu_page.is_some()
+            || self.state.options_screen.is_some()
+            || self.state.pause_menu.is_some()
+            || self.state.time_travel.is_some()
+            || self.state.picked_pattern.is_some()
+            || self.state.editor.is_some()
+            || self.state.print_mode == PrintMode::DEBUG
+            || SlotKey::from_key(event.command).is_some();
+        event.is_down
+            && !is_taken
+            && matches!(
+                self.key_bindings.command(event.command),
+                Command::MOVEUP | Command::MOVELEFT | Command::MOVEDOWN | Command::MOVERIGHT
+            )
+    }
+    /// React to the mouse: pressing the left button toggles the cell under it and dragging paints
+    /// the cells it passes over alive. In the editor the cursor follows the mouse and every stroke
+    /// is undone as a whole. The mouse does nothing while the board is covered by a menu, a page
+    /// or the help
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{console::input_record::MouseEvent, shared::usize2d::Coord};
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.handle_mouse(MouseEvent::press(Coord::new(2, 1)));
+    /// ```
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        if self.is_board_covered() {
+            self.stroke = None;
+            return;
+        }
+        if !event.is_left_down() {
+            self.finish_stroke();
+            return;
+        }
+        let Some(cell) = self.cell_at(event.pos) else {
+            return;
+        };
+        if !event.is_move() {
+            self.finish_stroke();
+            self.previous = self.current.clone();
+            self.neighbor_counts = None;
+            self.stroke = Some(Stroke::start(&mut self.current, cell));
+            self.state.latest_command = Command::TOGGLECELL;
+        } else if let Some(stroke) = self.stroke.as_mut() {
+            self.previous = self.current.clone();
+            self.neighbor_counts = None;
+            stroke.paint_to(&mut self.current, cell);
+            self.state.latest_command = Command::PAINTCELLS;
+        } else {
+            // The button was pressed outside of the board
+            return;
+        }
+        let logged = GameCommand::new(
+            self.state.latest_command,
+            CommandSource::Mouse(event),
+            self.clock.now(),
+        );
+        self.log_command(logged);
+        if let Some(editor) = self.state.editor.as_mut() {
+            editor.cursor = cell;
+        }
+    }
+    /// End the stroke of the mouse, keeping it to be undone while the editor is open
+    fn finish_stroke(&mut self) {
+        let Some(stroke) = self.stroke.take() else {
+            return;
+        };
+        if let Some(editor) = self.state.editor.as_mut() {
+            editor.record(stroke.edit);
+        }
+    }
+    /// Check if the board is covered by something that takes all keys, like the help or a menu
+    fn is_board_covered(&self) -> bool {
+        self.state.is_help_open
+            || self.state.menu_page.is_some()
+            || self.state.options_screen.is_some()
+            || self.state.pause_menu.is_some()
+            || self.state.time_travel.is_some()
+            || self.state.picked_pattern.is_some()
+    }
+    /// Checks if the next and previous frames are the same
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.is_stable();
+    ///
+    /// ```
+    pub fn is_stable(&self) -> bool {
+        self.previous.eq(&self.current)
+    }
+    /// Get the cells of the current generation, indexed as `cells[y][x]`
+    pub fn cells(&self) -> &Vec<Vec<bool>> {
+        &self.current
+    }
+    /// Get the cells that were born or died in the last generation, as `(x, y)`
+    pub fn changed_cells(&self) -> &[(usize, usize)] {
+        &self.changed_cells
+    }
+    /// Replace the cells of the current generation
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the new cells, indexed as `cells[y][x]`, with the same size as the grid
+    pub fn set_cells(&mut self, cells: Vec<Vec<bool>>) {
+        assert_eq!(
+            cells.len(),
+            self.settings.y_len,
+            "the number of rows should not change"
+        );
+        for row in cells.iter() {
+            assert_eq!(
+                row.len(),
+                self.settings.x_len,
+                "the length of the row should not change"
+            );
+        }
+        self.previous = self.current.clone();
+        self.neighbor_counts = None;
+        self.cycle_detector.clear();
+        self.cycle_detector.record(&cells);
+        self.state.detected_cycle = None;
+        self.current = cells;
+    }
+    /// Count the live cells of the current generation
+    pub fn population(&self) -> usize {
+        self.current
+            .iter()
+            .map(|row| row.iter().filter(|cell| **cell).count())
+            .sum()
+    }
+    /// Get the number of rounds that were played
+    pub fn rounds(&self) -> u64 {
+        self.state.rounds
+    }
+    /// Set the size of the screen in characters. When the board does not fit only the part
+    /// around the scroll position is drawn, with indicators of the live cells beyond the edges
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the columns and rows of the screen, `None` to always draw the whole board
+    pub fn set_screen_size(&mut self, size: Option<Usize2d>) {
+        self.settings.screen_size = size;
+        self.too_small_shown_for = None;
+        self.clear_screen();
+    }
+    /// Follow the size of the terminal. The size is read now and again every 250 milliseconds
+    /// while the game runs, a new size lays out and draws the screen again
+    pub fn set_screen_size_source(&mut self, source: TerminalSizeSource) {
+        self.set_screen_size(source());
+        self.screen_size_source = Some(source);
+    }
+    /// Read the size of the terminal again and lay out the screen for it if it changed
+    fn check_screen_size(&mut self) {
+        let Some(source) = &self.screen_size_source else {
+            return;
+        };
+        let size = source();
+        let as_tuple = |size: Option<Usize2d>| size.map(|size| (size.x, size.y));
+        if as_tuple(size) != as_tuple(self.settings.screen_size) {
+            self.set_screen_size(size);
+        }
+    }
+    /// Get the smallest screen the game can be drawn on: a cell of the board with the padding of
+    /// the layout, the debug panel and the lines below the board
+    pub fn required_screen_size(&self) -> Usize2d {
+        let padding = 2 * self.settings.layout.padding;
+        let cell = self.cell_size();
+        Usize2d::new(
+            self.settings.origin.x + padding + self.debug_panel_width() + cell.x,
+            self.settings.origin.y + padding + INFO_LINE_COUNT + cell.y,
+        )
+    }
+    /// Get the part of the grid that fits on the screen
+    pub fn viewport(&self) -> Viewport {
+        let grid = Usize2d::new(self.settings.x_len, self.settings.y_len);
+        let screen = match self.settings.screen_size {
+            Some(screen) => screen,
+            None => return Viewport::full(grid),
+        };
+        let padding = 2 * self.settings.layout.padding;
+        let space = Usize2d::new(
+            screen
+                .x
+                .saturating_sub(self.settings.origin.x + padding + self.debug_panel_width()),
+            screen
+                .y
+                .saturating_sub(self.settings.origin.y + padding + INFO_LINE_COUNT),
+        );
+        Viewport::fit(grid, space, self.cell_size(), self.settings.scroll)
+    }
+    /// Get the top left corner of the board on the screen. The board is placed on the screen by
+    /// the layout in the settings, with the origin as an offset, leaving room for the debug panel
+    /// and the lines below the board. While the size of the screen is unknown the board is drawn
+    /// at the origin
+    fn board_origin(&self) -> Coord {
+        let origin = self.settings.origin;
+        let screen = match self.settings.screen_size {
+            Some(screen) => screen,
+            None => return origin,
+        };
+        let width = screen.x.saturating_sub(self.debug_panel_width());
+        let height = screen.y.saturating_sub(INFO_LINE_COUNT);
+        if width == 0 || height == 0 {
+            return origin;
+        }
+        let area = Square::new(Usize2d::default(), Usize2d::new(width - 1, height - 1));
+        let (visible, cell_size) = (self.viewport().size, self.cell_size());
+        let board = Usize2d::new(visible.x * cell_size.x + 1, visible.y * cell_size.y + 1);
+        self.settings.layout.content_origin(&area, board) + origin
+    }
+    /// Get the columns the debug panel takes up to the right of the board, including the gap
+    fn debug_panel_width(&self) -> usize {
+        match (self.state.print_mode, self.state.is_debug_collapsed) {
+            (PrintMode::PRETTY, _) => 0,
+            (PrintMode::DEBUG, true) => DEBUG_PANEL_GAP + DEBUG_TITLE_COLLAPSED.len(),
+            (PrintMode::DEBUG, false) => DEBUG_PANEL_GAP + DEBUG_LINE_WIDTH,
+        }
+    }
+    /// Get the cell drawn at a location on the screen, the reverse of where `print_cell` draws it
+    ///
+    /// # Returns
+    ///
+    /// The cell, or `None` for a location outside of the visible cells or in the gap between two
+    /// cells
+    fn cell_at(&self, pos: Coord) -> Option<Coord> {
+        let (viewport, origin, cell_size) =
+            (self.viewport(), self.board_origin(), self.cell_size());
+        let to_cell = |pos: usize, origin: usize, size: usize, first: usize, visible: usize| {
+            // The board starts with a gap, the first cell is drawn one character in
+            let offset = pos.checked_sub(origin + 1)?;
+            let (view, inside) = (offset / size, offset % size);
+            (view < visible && inside < size - 1).then_some(first + view)
+        };
+        Some(Coord::new(
+            to_cell(
+                pos.x,
+                origin.x,
+                cell_size.x,
+                viewport.first.x,
+                viewport.size.x,
+            )?,
+            to_cell(
+                pos.y,
+                origin.y,
+                cell_size.y,
+                viewport.first.y,
+                viewport.size.y,
+            )?,
+        ))
+    }
+    /// Get the characters a cell takes up on the screen, including the gap to the next cell
+    fn cell_size(&self) -> Usize2d {
+        Usize2d::new(
+            self.settings.cell_view_width + 1,
+            self.settings.cell_view_height + 1,
+        )
+    }
+    /// Set the number of generations that are skipped with the fast forward key, 100 by default
+    pub fn set_skip_generations(&mut self, generations: u64) {
+        self.settings.skip_generations = generations;
+    }
+    /// Replace the keys the game responds to. The help lists the new bindings
+    pub fn set_key_bindings(&mut self, key_bindings: KeyBindings) {
+        self.key_bindings = key_bindings;
+    }
+    /// Set the rule used to step the grid, `B3/S23` by default
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.settings.rule = rule;
+    }
+    /// Processes any incoming commands sent to the game
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// self.process_command();
+    ///
+    /// ```
+    fn process_key_command(&mut self, command: KeyEvent) {
+        if !command.is_down {
+            return;
+        }
+        self.state.command_count += 1;
+        self.metrics.increment(COMMANDS);
+        self.state.latest_input = command.command;
+        // A chord bound to quit quits from every menu and mode, unlike a letter that may be typed
+        if command.key().is_chord() && self.key_bindings.command(command.command) == Command::QUIT {
+            self.state.latest_command = Command::QUIT;
+            return;
+        }
+        if self.state.is_help_open {
+            // Any key closes the help without doing anything else
+            self.state.is_help_open = false;
+            self.clear_cells();
+            self.state.latest_command = Command::TOGGLEHELP;
+            return;
+        }
+        if self.state.menu_page.is_some() {
+            // Any key goes back to the pause menu
+            self.state.menu_page = None;
+            self.state.latest_command = Command::CLOSEPAGE;
+            return;
+        }
+        if self.state.file_picker.is_some() {
+            self.state.latest_command = self.process_file_key(command.command);
+            return;
+        }
+        if self.state.text_prompt.is_some() {
+            self.state.latest_command = self.process_text_key(command.command);
+            return;
+        }
+        if self.state.options_screen.is_some() {
+            self.state.latest_command = self.process_options_key(command.command);
+            return;
+        }
+        if self.state.pause_menu.is_some() {
+            self.state.latest_command = self.process_menu_key(command.command);
+            return;
+        }
+        if self.state.time_travel.is_some() {
+            self.state.latest_command = self.process_time_travel_key(command.command);
+            return;
+        }
+        if let Some(picked) = self.state.picked_pattern {
+            let picker_command = match command.command {
+                'p' | 'P' => {
+                    self.state.picked_pattern = Some((picked + 1) % PATTERNS.len());
+                    Some(Command::PICKPATTERN)
+                }
+                '\r' | '\n' => {
+                    self.state.picked_pattern = None;
+                    self.clear_cells();
+                    match self.state.editor.as_mut() {
+                        Some(editor) => {
+                            editor.select_brush(picked);
+                            Some(Command::NEXTBRUSH)
+                        }
+                        None => {
+                            let placement = PatternPlacement {
+                                pattern: PATTERNS[picked],
+                                origin: None,
+                            };
+                            self.place_pattern(&placement);
+                            Some(Command::PLACEPATTERN)
+                        }
+                    }
+                }
+                '\x1b' => {
+                    self.state.picked_pattern = None;
+                    self.clear_cells();
+                    Some(Command::CLOSEPICKER)
+                }
+                _ => None,
+            };
+            if let Some(picker_command) = picker_command {
+                self.state.latest_command = picker_command;
+                return;
+            }
+        }
+        if self.state.editor.is_some() {
+            if let Some(editor_command) = self.process_editor_key(command.command) {
+                self.state.latest_command = editor_command;
+                return;
+            }
+        } else if self.state.print_mode == PrintMode::DEBUG {
+            if let Some(inspector_command) = self.process_inspector_key(command.command) {
+                self.state.latest_command = inspector_command;
+                return;
+            }
+        }
+        if let Some(slot_key) = SlotKey::from_key(command.command) {
+            self.state.latest_command = match slot_key {
+                SlotKey::Save(slot) => {
+                    self.save_checkpoint(slot);
+                    Command::SAVECHECKPOINT
+                }
+                SlotKey::Load(slot) => {
+                    self.load_checkpoint(slot);
+                    Command::LOADCHECKPOINT
+                }
+            };
+            return;
+        }
+        // A held key that moves the board moves it by every repeat at once
+        let steps = command.repreat_count.max(1) as usize;
+        self.state.latest_command =
+            self.run_command(self.key_bindings.command(command.command), steps);
+    }
+    /// Run a command that a key is bound to
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - the command
+    /// * `steps` - how far a command that moves the board moves it
+    ///
+    /// # Returns
+    ///
+    /// The command that ran, `Command::NOMAPPING` if the command can not be run by a key
+    fn run_command(&mut self, command: Command, steps: usize) -> Command {
+        match command {
+            Command::QUIT => Command::QUIT,
+            Command::RESET => {
+                self.state.is_reset_active = true;
+                Command::RESET
+            }
+            Command::RESEED => {
+                self.settings.seed = self.settings.seed.wrapping_add(1);
+                self.state.is_reset_active = true;
+                Command::RESEED
+            }
+            Command::GROWGRID => {
+                let _ = self.resize(self.settings.x_len + 1, self.settings.y_len + 1);
+                Command::GROWGRID
+            }
+            Command::SHRINKGRID => {
+                let (x_len, y_len) = (self.settings.x_len, self.settings.y_len);
+                if x_len > 1 && y_len > 1 {
+                    let _ = self.resize(x_len - 1, y_len - 1);
+                }
+                Command::SHRINKGRID
+            }
+            Command::TOGGLEMODE => {
+                let print_mode = match self.state.print_mode {
+                    PrintMode::DEBUG => PrintMode::PRETTY,
+                    PrintMode::PRETTY => PrintMode::DEBUG,
+                };
+                self.set_print_mode(print_mode);
+                Command::TOGGLEMODE
+            }
+            Command::PAUSEPLAY => {
+                self.state.is_paused = !self.state.is_paused;
+                Command::PAUSEPLAY
+            }
+            Command::TOGGLEINPUTLOG => {
+                self.state.is_input_log_open = !self.state.is_input_log_open;
+                if !self.state.is_input_log_open {
+                    self.clear_cells();
+                }
+                Command::TOGGLEINPUTLOG
+            }
+            Command::TOGGLEDEBUGPANEL => {
+                self.state.is_debug_collapsed = !self.state.is_debug_collapsed;
+                // The board can take up more or less space next to the panel
+                self.clear_screen();
+                Command::TOGGLEDEBUGPANEL
+            }
+            Command::PICKPATTERN => {
+                self.state.is_paused = true;
+                self.state.picked_pattern = Some(0);
+                Command::PICKPATTERN
+            }
+            Command::TIMETRAVEL => {
+                self.state.is_paused = true;
+                self.state.time_travel = Some(TimeTravel::new(
+                    &self.history,
+                    self.current.clone(),
+                    self.previous.clone(),
+                ));
+                Command::TIMETRAVEL
+            }
+            Command::OPENEDITOR => {
+                self.state.is_paused = true;
+                self.state.editor = Some(Editor::default());
+                Command::OPENEDITOR
+            }
+            Command::TOGGLEHEATMAP => {
+                self.state.is_heatmap = !self.state.is_heatmap;
+                Command::TOGGLEHEATMAP
+            }
+            Command::FASTFORWARD => {
+                self.fast_forward(self.settings.skip_generations);
+                Command::FASTFORWARD
+            }
+            Command::TOGGLEFPS => {
+                self.state.is_fps_limited = !self.state.is_fps_limited;
+                Command::TOGGLEFPS
+            }
+            Command::MOVEUP => {
+                let viewport = self.viewport();
+                if viewport.size.y < self.settings.y_len {
+                    self.settings.scroll.y = viewport.first.y.saturating_sub(steps);
+                } else if self.settings.origin.y > 0 {
+                    self.settings.origin.y = self.settings.origin.y.saturating_sub(steps);
+                    self.clear_cells();
+                }
+                Command::MOVEUP
+            }
+            Command::MOVELEFT => {
+                let viewport = self.viewport();
+                if viewport.size.x < self.settings.x_len {
+                    self.settings.scroll.x = viewport.first.x.saturating_sub(steps);
+                } else if self.settings.origin.x > 0 {
+                    self.settings.origin.x = self.settings.origin.x.saturating_sub(steps);
+                    self.clear_cells();
+                }
+                Command::MOVELEFT
+            }
+            Command::MOVEDOWN => {
+                let viewport = self.viewport();
+                if viewport.size.y < self.settings.y_len {
+                    self.settings.scroll.y = viewport.first.y + steps;
+                } else {
+                    self.settings.origin.y += steps;
+                    self.clear_cells();
+                }
+                Command::MOVEDOWN
+            }
+            Command::MOVERIGHT => {
+                let viewport = self.viewport();
+                if viewport.size.x < self.settings.x_len {
+                    self.settings.scroll.x = viewport.first.x + steps;
+                } else {
+                    self.settings.origin.x += steps;
+                    self.clear_cells();
+                }
+                Command::MOVERIGHT
+            }
+            Command::TOGGLEHELP => {
+                self.state.is_help_open = true;
+                Command::TOGGLEHELP
+            }
+            #[cfg(feature = "export-image")]
+            Command::SCREENSHOT => {
+                match self.save_screenshot() {
+                    Ok(path) => self.toasts.push(
+                        format!("Saved {}", path.display()),
+                        TOAST_DURATION,
+                        self.clock.now(),
+                    ),
+                    Err(_) => self.state.latest_err = "Failed to save the screenshot".to_string(),
+                }
+                Command::SCREENSHOT
+            }
+            #[cfg(feature = "export-image")]
+            Command::RECORD => {
+                self.toggle_recording();
+                Command::RECORD
+            }
+            Command::OPENMENU => {
+                self.state.is_paused = true;
+                self.state.pause_menu = Some(PauseMenu::default());
+                Command::OPENMENU
+            }
+            Command::UNDO => {
+                self.undo_command();
+                Command::UNDO
+            }
+            Command::SAVE => {
+                let status = self.save_to_file();
+                self.toasts.push(status, TOAST_DURATION, self.clock.now());
+                Command::SAVE
+            }
+            Command::DUMPFRAME => {
+                // The frame is written after the next render, not halfway through a generation
+                self.state.is_frame_dump_pending = true;
+                Command::DUMPFRAME
+            }
+            Command::SAVESEED => {
+                match self.save_seed() {
+                    Ok(path) => self.toasts.push(
+                        format!("Seed {} saved to {}", self.settings.seed, path.display()),
+                        TOAST_DURATION,
+                        self.clock.now(),
+                    ),
+                    Err(_) => self.state.latest_err = "Failed to save the seed".to_string(),
+                }
+                Command::SAVESEED
+            }
+            Command::RECORDMACRO => {
+                self.toggle_macro_recording();
+                Command::RECORDMACRO
+            }
+            Command::PLAYMACRO => {
+                self.open_play_macro();
+                Command::PLAYMACRO
+            }
+            _ => Command::NOMAPPING,
+        }
+    }
+    /// Stop the macro that is being recorded, or ask for the name of a macro to record
+    fn toggle_macro_recording(&mut self) {
+        let now = self.clock.now();
+        match self.macros.stop() {
+            Some((name, 0)) => self.toasts.push(
+                format!("Nothing was recorded for {}", name),
+                TOAST_DURATION,
+                now,
+            ),
+            Some((name, count)) => self.toasts.push(
+                format!("Recorded {} with {} keys", name, count),
+                TOAST_DURATION,
+                now,
+            ),
+            None => {
+                let input = TextInput::new("Name", MACRO_NAME_LEN)
+                    .with_text(self.macros.latest().unwrap_or_default())
+                    .validate(|text| match text.trim().is_empty() {
+                        true => Err("Type a name".to_string()),
+                        false => Ok(()),
+                    });
+                self.state.text_prompt = Some((TextPrompt::MacroName, input));
+            }
+        }
+    }
+    /// Ask for the name of a macro to play, starting with the latest one
+    fn open_play_macro(&mut self) {
+        let names = self.macros.names();
+        if names.is_empty() {
+            self.toasts.push(
+                "No macros were recorded yet".to_string(),
+                TOAST_DURATION,
+                self.clock.now(),
+            );
+            return;
+        }
+        let input = TextInput::new("Name", MACRO_NAME_LEN)
+            .with_text(self.macros.latest().unwrap_or_default())
+            .validate(
+                move |text| match names.iter().any(|name| name == text.trim()) {
+                    true => Ok(()),
+                    false => Err(format!("Pick one of {}", names.join(", "))),
+                },
+            );
+        self.state.text_prompt = Some((TextPrompt::PlayMacro, input));
+    }
+    /// Revert the latest command of the input log that can be reverted, by running its inverse
+    fn undo_command(&mut self) {
+        let Some(undone) = self.input_log.take_undo() else {
+            return;
+        };
+        let Some(inverse) = undone.inverse else {
+            return;
+        };
+        self.run_command(inverse, undone.repeats());
+        let reverted = GameCommand::new(inverse, CommandSource::Undo, self.clock.now());
+        self.log_command(reverted);
+    }
+    /// Add a command that ran to the input log and to the timeline of the session
+    fn log_command(&mut self, command: GameCommand) {
+        #[cfg(feature = "serde")]
+        if !self.is_playing_macro {
+            self.timeline.record_command(&command, self.state.rounds);
+        }
+        self.input_log.record(command);
+    }
+
+    /// Apply a key to the pause menu. The menu takes all keys while it is open
+    ///
+    /// # Returns
+    ///
+    /// The command of the key
+    fn process_menu_key(&mut self, key: char) -> Command {
+        let menu = match self.state.pause_menu.as_mut() {
+            Some(menu) => menu,
+            None => return Command::NOMAPPING,
+        };
+        let item = match key {
+            '\x1b' => PauseMenuItem::Resume,
+            _ => match menu.menu.handle_key(key) {
+                MenuAction::Ignored => return Command::NOMAPPING,
+                MenuAction::Moved => return Command::MOVESELECTION,
+                MenuAction::Confirmed(item) => item,
+            },
+        };
+        match item {
+            PauseMenuItem::Resume => {
+                self.state.pause_menu = None;
+                self.state.is_paused = false;
+                self.clear_cells();
+                Command::CLOSEMENU
+            }
+            PauseMenuItem::Save => {
+                let path = self.settings.save_path.display().to_string();
+                let input = TextInput::new("File", SAVE_PATH_LEN)
+                    .with_text(&path)
+                    .validate(|text| match text.trim().is_empty() {
+                        true => Err("Type the name of a file".to_string()),
+                        false => Ok(()),
+                    });
+                self.state.text_prompt = Some((TextPrompt::SavePath, input));
+                Command::OPENINPUT
+            }
+            PauseMenuItem::Load => {
+                let dir = match self.settings.save_path.parent() {
+                    Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+                    _ => std::path::PathBuf::from("."),
+                };
+                #[allow(unused_mut)]
+                let mut extensions = vec!["rle", "cells"];
+                #[cfg(feature = "serde")]
+                extensions.push("json");
+                match FilePicker::open(&dir, &extensions, FILE_PICKER_ROWS) {
+                    Ok(picker) => self.state.file_picker = Some(picker),
+                    Err(e) => {
+                        self.state.latest_err = format!("{:?}", e);
+                        if let Some(menu) = self.state.pause_menu.as_mut() {
+                            menu.status = "Failed to list the files".to_string();
+                        }
+                    }
+                }
+                Command::OPENFILES
+            }
+            PauseMenuItem::Options => {
+                self.state.options_screen = Some(OptionsScreen::new(self.options()));
+                Command::OPENOPTIONS
+            }
+            PauseMenuItem::Scores => {
+                let mut page = vec!["High scores".to_string()];
+                page.extend(self.scores_table());
+                self.state.menu_page = Some(page);
+                Command::SHOWSCORES
+            }
+            PauseMenuItem::Achievements => {
+                let mut page = vec!["Achievements".to_string()];
+                page.extend(self.achievements.lines());
+                self.state.menu_page = Some(page);
+                Command::SHOWACHIEVEMENTS
+            }
+            PauseMenuItem::Seed => {
+                let seed = self.settings.seed.to_string();
+                let input = TextInput::new("Seed", u64::MAX.to_string().len())
+                    .with_text(&seed)
+                    .validate(|text| match text.parse::<u64>() {
+                        Ok(_) => Ok(()),
+                        Err(_) => Err("Type a whole number".to_string()),
+                    });
+                self.state.text_prompt = Some((TextPrompt::Seed, input));
+                Command::OPENINPUT
+            }
+            PauseMenuItem::Quit => Command::QUIT,
+        }
+    }
+
+    /// Apply a key to the file picker over the pause menu. A chosen file is loaded and the game
+    /// continues with it, escape goes back to the menu
+    ///
+    /// # Returns
+    ///
+    /// The command of the key
+    fn process_file_key(&mut self, key: char) -> Command {
+        let Some(picker) = self.state.file_picker.as_mut() else {
+            return Command::NOMAPPING;
+        };
+        let path = match picker.handle_key(key) {
+            PickerAction::Ignored => return Command::NOMAPPING,
+            PickerAction::Moved => return Command::MOVESELECTION,
+            PickerAction::Opened => return Command::OPENFILES,
+            PickerAction::Cancelled => {
+                self.state.file_picker = None;
+                return Command::CLOSEFILES;
+            }
+            PickerAction::Chosen(path) => path,
+        };
+        self.state.file_picker = None;
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().to_string(),
+        );
+        if let Err(e) = self.load_file(&path) {
+            self.state.latest_err = e;
+            if let Some(menu) = self.state.pause_menu.as_mut() {
+                menu.status = format!("Failed to load {}", name);
+            }
+            return Command::LOAD;
+        }
+        self.toasts
+            .push(format!("Loaded {}", name), TOAST_DURATION, self.clock.now());
+        self.state.pause_menu = None;
+        self.state.is_paused = false;
+        self.clear_cells();
+        Command::LOAD
+    }
+    /// Continue a saved game, or place the pattern of an RLE or plaintext file in the middle of
+    /// the board. A session timeline starts over from its first board with the recorded run as
+    /// the ghost behind the board
+    ///
+    /// # Returns
+    ///
+    /// The reason the file could not be loaded
+    fn load_file(&mut self, path: &std::path::Path) -> Result<(), String> {
+        #[cfg(feature = "serde")]
+        if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+        {
+            if let Ok(session) = RecordedSession::load(path) {
+                let start = session
+                    .start
+                    .clone()
+                    .ok_or(format!("{:?}", VerifyError::NoStart))?;
+                let ghost = Ghost::new(session).map_err(|e| format!("{:?}", e))?;
+                self.restore(&start).map_err(|e| format!("{:?}", e))?;
+                self.set_ghost(Some(ghost));
+                return Ok(());
+            }
+            let saved = SavedGame::load(path).map_err(|e| format!("{:?}", e))?;
+            return self.restore(&saved).map_err(|e| format!("{:?}", e));
+        }
+        let pattern = Clipboard::load(path).map_err(|e| format!("{:?}", e))?;
+        self.place_clipboard(&pattern);
+        Ok(())
+    }
+    /// Apply a key to the text field over the pause menu. The field takes all keys while it is
+    /// open, enter uses the text if it is valid and escape goes back to the menu
+    ///
+    /// # Returns
+    ///
+    /// The command of the key
+    fn process_text_key(&mut self, key: char) -> Command {
+        let Some((prompt, input)) = self.state.text_prompt.as_mut() else {
+            return Command::NOMAPPING;
+        };
+        let text = match input.handle_key(key) {
+            InputAction::Ignored => return Command::NOMAPPING,
+            InputAction::Edited | InputAction::Rejected => return Command::TYPE,
+            InputAction::Cancelled => {
+                self.state.text_prompt = None;
+                return Command::CLOSEINPUT;
+            }
+            InputAction::Submitted(text) => text,
+        };
+        let prompt = *prompt;
+        self.state.text_prompt = None;
+        match prompt {
+            TextPrompt::SavePath => {
+                self.settings.save_path = std::path::PathBuf::from(text.trim());
+                let status = self.save_to_file();
+                if let Some(menu) = self.state.pause_menu.as_mut() {
+                    menu.status = status;
+                }
+                Command::SAVE
+            }
+            TextPrompt::MacroName => {
+                let name = text.trim();
+                self.macros.start(name);
+                self.toasts.push(
+                    format!("Recording {}, ctrl+r stops", name),
+                    TOAST_DURATION,
+                    self.clock.now(),
+                );
+                Command::RECORDMACRO
+            }
+            TextPrompt::PlayMacro => {
+                self.macro_keys = self.macros.play(text.trim()).unwrap_or_default();
+                Command::PLAYMACRO
+            }
+            TextPrompt::Seed => {
+                if let Ok(seed) = text.parse() {
+                    self.settings.seed = seed;
+                }
+                self.state.pause_menu = None;
+                self.state.is_paused = false;
+                self.state.is_reset_active = true;
+                self.clear_cells();
+                Command::RESEED
+            }
+        }
+    }
+
+    /// Move the inspected cell in debug mode with the same keys as the editor cursor
+    ///
+    /// # Returns
+    ///
+    /// The command of the key, or `None` if the key does not move the inspector
+    fn process_inspector_key(&mut self, key: char) -> Option<Command> {
+        let (x_delta, y_delta) = match key {
+            'i' | 'I' => (0, -1),
+            'j' | 'J' => (-1, 0),
+            'k' | 'K' => (0, 1),
+            'l' | 'L' => (1, 0),
+            _ => return None,
+        };
+        let inspector = self.state.inspector;
+        self.state.inspector = Coord::new(
+            (inspector.x as i64 + x_delta).rem_euclid(self.settings.x_len as i64) as usize,
+            (inspector.y as i64 + y_delta).rem_euclid(self.settings.y_len as i64) as usize,
+        );
+        self.settings.scroll = self.viewport().follow(self.state.inspector);
+        Some(Command::MOVECURSOR)
+    }
+    /// Handle a key while the time travel is open. The left and right keys scrub through the
+    /// history, enter continues the game from the shown generation and escape returns to the
+    /// generation the game was paused at
+    fn process_time_travel_key(&mut self, key: char) -> Command {
+        let time_travel = match self.state.time_travel.as_mut() {
+            Some(time_travel) => time_travel,
+            None => return Command::NOMAPPING,
+        };
+        let offset = match key {
+            'a' | 'A' => -1,
+            'd' | 'D' => 1,
+            '\r' | '\n' => {
+                let index = time_travel.index();
+                self.history.truncate(index);
+                if let Some(generation) = self.history.get(index) {
+                    self.state.rounds = generation.round;
+                }
+                self.state.time_travel = None;
+                self.clear_cells();
+                return Command::CLOSETIMETRAVEL;
+            }
+            '\x1b' | 't' | 'T' => {
+                self.current = time_travel.live_cells.clone();
+                self.previous = time_travel.live_previous.clone();
+                self.neighbor_counts = None;
+                self.state.time_travel = None;
+                self.clear_cells();
+                return Command::CLOSETIMETRAVEL;
+            }
+            _ => return Command::NOMAPPING,
+        };
+        time_travel.scrub(&self.history, offset);
+        let index = time_travel.index();
+        if let Some(generation) = self.history.get(index) {
+            self.current = generation.cells.clone();
+            self.previous = match index {
+                0 => generation.cells.clone(),
+                _ => self
+                    .history
+                    .get(index - 1)
+                    .map_or_else(|| generation.cells.clone(), |before| before.cells.clone()),
+            };
+            self.neighbor_counts = None;
+        }
+        Command::SCRUB
+    }
+    /// Apply a key to the options screen. The changes are applied to the game right away and
+    /// written to the options file when the screen is closed
+    ///
+    /// # Returns
+    ///
+    /// The command of the key
+    fn process_options_key(&mut self, key: char) -> Command {
+        let screen = match self.state.options_screen.as_mut() {
+            Some(screen) => screen,
+            None => return Command::NOMAPPING,
+        };
+        match key {
+            'w' | 'W' | 's' | 'S' => {
+                screen.menu.handle_key(key);
+                return Command::MOVESELECTION;
+            }
+            'a' | 'A' => screen.change(-1),
+            'd' | 'D' => screen.change(1),
+            '\x1b' | '\r' | '\n' => {
+                self.state.options_screen = None;
+                let status = self.save_options();
+                if let Some(menu) = self.state.pause_menu.as_mut() {
+                    menu.status = status;
+                }
+                return Command::CLOSEOPTIONS;
+            }
+            _ => return Command::NOMAPPING,
+        };
+        let options = screen.options;
+        self.apply_options(&options);
+        Command::CHANGEOPTION
+    }
+
+    /// Apply a key to the editor
+    ///
+    /// # Returns
+    ///
+    /// The command of the key, or `None` if the editor does not use the key
+    fn process_editor_key(&mut self, key: char) -> Option<Command> {
+        let grid = Usize2d::new(self.settings.x_len, self.settings.y_len);
+        let editor = self.state.editor.as_mut()?;
+        let command = match key {
+            'i' | 'I' => {
+                editor.move_cursor(0, -1, grid);
+                Command::MOVECURSOR
+            }
+            'j' | 'J' => {
+                editor.move_cursor(-1, 0, grid);
+                Command::MOVECURSOR
+            }
+            'k' | 'K' => {
+                editor.move_cursor(0, 1, grid);
+                Command::MOVECURSOR
+            }
+            'l' | 'L' => {
+                editor.move_cursor(1, 0, grid);
+                Command::MOVECURSOR
+            }
+            'b' | 'B' => {
+                editor.next_brush();
+                Command::NEXTBRUSH
+            }
+            'r' | 'R' => {
+                if let Some(brush) = editor.brush.as_mut() {
+                    brush.rotate();
+                }
+                Command::ROTATEBRUSH
+            }
+            'm' | 'M' => {
+                editor.toggle_selection();
+                Command::SELECT
+            }
+            'x' | 'X' if editor.selection().is_some() => {
+                self.previous = self.current.clone();
+                self.neighbor_counts = None;
+                editor.clear_selection(&mut self.current);
+                Command::CLEARSELECTION
+            }
+            COPY_KEY | CUT_KEY => {
+                if let Some(selection) = editor.selection() {
+                    let copied = Clipboard::copy(&self.current, &selection);
+                    if self.clipboard.set(copied, &self.settings.rule).is_err() {
+                        self.state.latest_err =
+                            "Failed to copy to the system clipboard".to_string();
+                    }
+                    if key == CUT_KEY {
+                        self.previous = self.current.clone();
+                        self.neighbor_counts = None;
+                        editor.clear_selection(&mut self.current);
+                    }
+                }
+                match key {
+                    COPY_KEY => Command::COPY,
+                    _ => Command::CUT,
+                }
+            }
+            PASTE_KEY => {
+                if let Some(clipboard) = self.clipboard.get() {
+                    self.previous = self.current.clone();
+                    self.neighbor_counts = None;
+                    editor.paste(&mut self.current, &clipboard);
+                }
+                Command::PASTE
+            }
+            EXPORT_KEY => {
+                self.export_clipboard();
+                Command::EXPORTCLIPBOARD
+            }
+            'h' | 'H' => {
+                self.transform_board(BoardTransform::MirrorHorizontally);
+                Command::MIRRORHORIZONTALLY
+            }
+            'v' | 'V' => {
+                self.transform_board(BoardTransform::MirrorVertically);
+                Command::MIRRORVERTICALLY
+            }
+            't' | 'T' => {
+                self.transform_board(BoardTransform::RotateClockwise);
+                Command::ROTATEBOARD
+            }
+            'x' | 'X' => {
+                let cursor = editor.cursor;
+                self.previous = self.current.clone();
+                self.neighbor_counts = None;
+                let was_alive = self.current[cursor.y][cursor.x];
+                self.current[cursor.y][cursor.x] = !was_alive;
+                editor.record(Edit {
+                    changes: vec![CellChange {
+                        cell: cursor,
+                        was_alive,
+                        is_alive: !was_alive,
+                    }],
+                });
+                Command::TOGGLECELL
+            }
+            '\r' | '\n' => {
+                // Without a brush the single cell under the cursor is toggled
+                let is_stamp = editor.brush.is_some();
+                self.previous = self.current.clone();
+                self.neighbor_counts = None;
+                editor.apply(&mut self.current, is_stamp);
+                Command::STAMP
+            }
+            UNDO_KEY | REDO_KEY => {
+                let (edit, command) = match key {
+                    UNDO_KEY => (editor.undo(), Command::UNDO),
+                    _ => (editor.redo(), Command::REDO),
+                };
+                if let Some(edit) = edit {
+                    self.previous = self.current.clone();
+                    self.neighbor_counts = None;
+                    edit.apply(&mut self.current);
+                }
+                command
+            }
+            'e' | 'E' | '\x1b' => {
+                self.state.editor = None;
+                self.clear_cells();
+                Command::CLOSEEDITOR
+            }
+            _ => return None,
+        };
+        if command == Command::MOVECURSOR {
+            if let Some(editor) = &self.state.editor {
+                self.settings.scroll = self.viewport().follow(editor.cursor);
+            }
+        }
+        Some(command)
+    }
+
+    /// Mirror or rotate the whole board. A rotation of a board that is not square swaps the
+    /// number of columns and rows, which starts the heatmap and the history over. In the editor
+    /// the cursor moves with its cell and the transform can be undone, unless the grid changed
+    /// size
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::conway::transform::BoardTransform;
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.transform_board(BoardTransform::MirrorHorizontally);
+    /// ```
+    pub fn transform_board(&mut self, transform: BoardTransform) {
+        self.finish_stroke();
+        let grid = Usize2d::new(self.settings.x_len, self.settings.y_len);
+        let size = transform.size(grid);
+        let is_resized = size.x != grid.x || size.y != grid.y;
+        let cells = transform.apply(&self.current);
+        if let Some(editor) = self.state.editor.as_mut() {
+            editor.cursor = transform.map(editor.cursor, grid);
+            if !is_resized {
+                let changes = (0..grid.y)
+                    .flat_map(|y| (0..grid.x).map(move |x| Coord::new(x, y)))
+                    .filter(|cell| self.current[cell.y][cell.x] != cells[cell.y][cell.x])
+                    .map(|cell| CellChange {
+                        cell,
+                        was_alive: self.current[cell.y][cell.x],
+                        is_alive: cells[cell.y][cell.x],
+                    })
+                    .collect();
+                editor.record(Edit { changes });
+            }
+        }
+        if is_resized {
+            self.state.inspector = transform.map(self.state.inspector, grid);
+            self.set_grid(cells);
+        } else {
+            self.set_cells(cells);
+        }
+        self.toasts.push(
+            format!("Board {}", transform),
+            TOAST_DURATION,
+            self.clock.now(),
+        );
+    }
+    /// Keep the simulation in a quick save slot, replacing what was saved to the slot before
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - the slot, from 1 to `SLOT_COUNT`
+    pub fn save_checkpoint(&mut self, slot: usize) {
+        let checkpoint = Checkpoint {
+            cells: self.current.clone(),
+            previous: self.previous.clone(),
+            rounds: self.state.rounds,
+            seed: self.settings.seed,
+            rule: self.settings.rule,
+        };
+        self.checkpoints.save(slot, checkpoint);
+        self.toasts.push(
+            format!("Saved checkpoint {}", slot),
+            TOAST_DURATION,
+            self.clock.now(),
+        );
+    }
+    /// Continue the simulation from a quick save slot, the slot keeps the checkpoint so that
+    /// another experiment can start from it
+    ///
+    /// # Returns
+    ///
+    /// `false` if nothing was saved to the slot
+    pub fn load_checkpoint(&mut self, slot: usize) -> bool {
+        let Some(checkpoint) = self.checkpoints.get(slot).cloned() else {
+            self.state.latest_err = format!("Checkpoint {} is empty", slot);
+            return false;
+        };
+        self.finish_stroke();
+        self.settings.seed = checkpoint.seed;
+        self.settings.rule = checkpoint.rule;
+        self.state.rounds = checkpoint.rounds;
+        let grid = Usize2d::new(checkpoint.cells[0].len(), checkpoint.cells.len());
+        self.state.inspector = Coord::new(
+            self.state.inspector.x.min(grid.x - 1),
+            self.state.inspector.y.min(grid.y - 1),
+        );
+        if let Some(editor) = self.state.editor.as_mut() {
+            editor.cursor = Coord::new(
+                editor.cursor.x.min(grid.x - 1),
+                editor.cursor.y.min(grid.y - 1),
+            );
+        }
+        self.set_grid(checkpoint.cells);
+        self.previous = checkpoint.previous;
+        self.toasts.push(
+            format!("Loaded checkpoint {}", slot),
+            TOAST_DURATION,
+            self.clock.now(),
+        );
+        true
+    }
+    /// Change the number of columns and rows of the grid while the game runs. The live cells
+    /// that still fit keep their place from the top left corner, the new cells are dead
+    ///
+    /// # Returns
+    ///
+    /// A result indicating if the grid was resized, a grid needs at least one row and column
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::ConwayError;
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # fn main() -> Result<(), ConwayError> {
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.resize(40, 20)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resize(&mut self, x_len: usize, y_len: usize) -> Result<(), ConwayError> {
+        if x_len == 0 || y_len == 0 {
+            return Err(ConwayError::EmptyBoard);
+        }
+        let mut cells = vec![vec![false; x_len]; y_len];
+        for (row, old_row) in cells.iter_mut().zip(self.current.iter()) {
+            for (cell, old_cell) in row.iter_mut().zip(old_row.iter()) {
+                *cell = *old_cell;
+            }
+        }
+        self.finish_stroke();
+        self.state.inspector = Coord::new(
+            self.state.inspector.x.min(x_len - 1),
+            self.state.inspector.y.min(y_len - 1),
+        );
+        if let Some(editor) = self.state.editor.as_mut() {
+            editor.cursor = Coord::new(
+                editor.cursor.x.min(x_len - 1),
+                editor.cursor.y.min(y_len - 1),
+            );
+        }
+        self.set_grid(cells);
+        self.toasts.push(
+            format!("Grid resized to {}x{}", x_len, y_len),
+            TOAST_DURATION,
+            self.clock.now(),
+        );
+        Ok(())
+    }
+    /// Replace the board with one of another size. The heatmap, the history and the edits start
+    /// over, since their cells no longer line up with the board
+    fn set_grid(&mut self, cells: Vec<Vec<bool>>) {
+        self.settings.y_len = cells.len();
+        self.settings.x_len = cells[0].len();
+        self.settings.scroll = Coord::default();
+        self.stroke = None;
+        self.state.time_travel = None;
+        if let Some(editor) = self.state.editor.as_mut() {
+            editor.clear_history();
+        }
+        // An empty previous generation so that the new board does not count as stable
+        self.previous = vec![vec![false; self.settings.x_len]; self.settings.y_len];
+        self.neighbor_counts = None;
+        self.heatmap.clear();
+        self.heatmap.record(&cells);
+        self.history.clear();
+        self.history.record(self.state.rounds, &cells);
+        self.cycle_detector.clear();
+        self.cycle_detector.record(&cells);
+        self.state.detected_cycle = None;
+        self.current = cells;
+        self.clear_screen();
+        self.update_title();
+    }
+    /// Write the clipboard of the editor to the clipboard path as RLE and announce it with a
+    /// toast
+    fn export_clipboard(&mut self) {
+        let Some(clipboard) = self.clipboard.get() else {
+            self.state.latest_err = "The clipboard is empty".to_string();
+            return;
+        };
+        let rle = clipboard.to_rle(&self.settings.rule);
+        match fs::write(&self.settings.clipboard_path, rle) {
+            Ok(_) => self.toasts.push(
+                format!("Saved {}", self.settings.clipboard_path.display()),
+                TOAST_DURATION,
+                self.clock.now(),
+            ),
+            Err(_) => self.state.latest_err = "Failed to export the clipboard".to_string(),
+        }
+    }
+    /// Pretty print the current state
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::PrintMode;
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.print(PrintMode::PRETTY);
+    ///
+    /// ```
+    /// prints the following:
+    /// █   █
+    ///      
+    ///     █
+    pub fn print(&mut self, print_mode: PrintMode) {
+        let grid = Usize2d::new(self.settings.x_len, self.settings.y_len);
+        self.brush_preview = match &self.state.editor {
+            Some(editor) => match editor.selection() {
+                Some(selection) => {
+                    let (top_left, bottom_right) = selection.get_boundary();
+                    (top_left.y..=bottom_right.y)
+                        .flat_map(|y| (top_left.x..=bottom_right.x).map(move |x| Coord::new(x, y)))
+                        .collect()
+                }
+                None => editor.target_cells(grid),
+            },
+            None => Vec::new(),
+        };
+        #[cfg(feature = "serde")]
+        if let Some(ghost) = self.ghost.as_mut() {
+            ghost.seek(self.state.rounds);
+        }
+        let viewport = self.viewport();
+        let (origin, cell_size) = (self.board_origin(), self.cell_size());
+        let layout = [
+            viewport.first.x,
+            viewport.first.y,
+            viewport.size.x,
+            viewport.size.y,
+            origin.x,
+            origin.y,
+            cell_size.x,
+            cell_size.y,
+        ];
+        // Panning, zooming or moving the board draws every cell again
+        let mut drawn = match self.drawn_cells.take() {
+            Some(drawn) if drawn.layout == layout => drawn,
+            _ => DrawnCells {
+                layout,
+                colors: vec![vec![None; viewport.size.x]; viewport.size.y],
+            },
+        };
+        for y in viewport.first.y..viewport.first.y + viewport.size.y {
+            for x in viewport.first.x..viewport.first.x + viewport.size.x {
+                let colors = self.cell_colors(x, y, self.current[y][x]);
+                let drawn_colors = &mut drawn.colors[y - viewport.first.y][x - viewport.first.x];
+                if *drawn_colors != Some(colors) {
+                    *drawn_colors = Some(colors);
+                    self.print_cell_in(x, y, colors, &viewport);
+                }
+            }
+        }
+        self.drawn_cells = Some(drawn);
+        if viewport.is_partial(grid) {
+            self.print_edge_activity(&viewport);
+        }
+
+        if print_mode == PrintMode::DEBUG {
+            self.debug();
+        }
+    }
+    /// Print arrows in the gaps around the visible cells for the rows and columns that have live
+    /// cells beyond the edges of the screen
+    fn print_edge_activity(&mut self, viewport: &Viewport) {
+        let activity = EdgeActivity::count(&self.current, viewport);
+        let cell_size = self.cell_size();
+        let origin = self.board_origin();
+        let right = origin.x + viewport.size.x * cell_size.x;
+        let bottom = origin.y + viewport.size.y * cell_size.y;
+        let indicator = |count: usize, arrow: char, width: usize| -> String {
+            let arrow = if count > 0 { arrow } else { ' ' };
+            arrow.to_string().repeat(width)
+        };
+        self.set_colors(TerminalColors::Black, TerminalColors::Yellow);
+        for row in 0..viewport.size.y {
+            let y_start = origin.y + 1 + row * cell_size.y;
+            for y_offset in 0..self.settings.cell_view_height {
+                let y = y_start + y_offset;
+                self.write_at(
+                    &indicator(activity.left[row], '<', 1),
+                    Coord::new(origin.x, y),
+                );
+                self.write_at(
+                    &indicator(activity.right[row], '>', 1),
+                    Coord::new(right, y),
+                );
+            }
+        }
+        for column in 0..viewport.size.x {
+            let x = origin.x + 1 + column * cell_size.x;
+            let width = cell_size.x - 1;
+            self.write_at(
+                &indicator(activity.top[column], '^', width),
+                Coord::new(x, origin.y),
+            );
+            self.write_at(
+                &indicator(activity.bottom[column], 'v', width),
+                Coord::new(x, bottom),
+            );
+        }
+    }
+    /// Print the debug panel to the right of the board. While the panel is collapsed only its
+    /// title is printed
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// game.debug();
+    ///
+    /// ```
+    fn debug(&mut self) {
+        let [_, top_right] = self.find_corners();
+        let x_start = top_right.x + DEBUG_PANEL_GAP;
+        let y_start = top_right.y + 1;
+        self.set_colors(TerminalColors::White, TerminalColors::Red);
+        if self.state.is_debug_collapsed {
+            self.write_at(DEBUG_TITLE_COLLAPSED, Coord::new(x_start, top_right.y));
+            return;
+        }
+        self.write_at(
+            &format!("{:<width$}", DEBUG_TITLE_EXPANDED, width = DEBUG_LINE_WIDTH),
+            Coord::new(x_start, top_right.y),
+        );
+        let center = self.find_center();
+        let inspected = self.inspect();
+        let update_graph = self.frame_graph("Update", UPDATE_TIME);
+        let render_graph = self.frame_graph("Render", RENDER_TIME);
+        let hash = self.state_hash();
+        let seed = self.settings.seed;
+        let state = &self.state;
+        let lines = Hud::new()
+            .with(Label::new(|| format!("Round {}. ", state.rounds)))
+            .with(Readout::new("Seed", || seed))
+            .with(Readout::new("Hash", || format!("{:016x}", hash)))
+            .with(Label::new(|| {
+                format!(
+                    "Latest Command: cmd - '{}', input - '{}'",
+                    state.latest_command, state.latest_input
+                )
+            }))
+            .with(Readout::new("Cmd count", || state.command_count))
+            .with(Readout::new("Mode", || state.print_mode))
+            .with(Readout::new("Is Paused", || state.is_paused))
+            .with(Readout::new("Heatmap", || state.is_heatmap))
+            .with(Readout::new("FPS Count", || state.fps_last))
+            .with(Readout::new("Center", || center))
+            .with(Label::new(|| inspected.clone()))
+            .with(Label::new(|| update_graph.clone()))
+            .with(Label::new(|| render_graph.clone()))
+            .rows();
+        let line_count = lines.len();
+        for (index, line) in lines.into_iter().enumerate() {
+            self.write_at(
+                &format!("{:<width$}", line, width = DEBUG_LINE_WIDTH),
+                Coord::new(x_start, y_start + index),
+            );
+        }
+        if !self.state.latest_err.is_empty() {
+            self.set_colors(TerminalColors::Red, TerminalColors::White);
+            let line = format!("Error: {}", self.state.latest_err);
+            self.write_at(
+                &format!("{:<width$}", line, width = DEBUG_LINE_WIDTH),
+                Coord::new(x_start, y_start + line_count),
+            );
+        }
+    }
+    /// Draw the times of the latest frames as a graph, every bar shows the slowest of a few
+    /// frames so that a stutter stands out
+    fn frame_graph(&self, label: &str, series: &str) -> String {
+        let micros: Vec<u64> = self
+            .metrics
+            .samples(series)
+            .iter()
+            .map(|duration| duration.as_micros() as u64)
+            .collect();
+        let slowest = micros.iter().copied().max().unwrap_or(0);
+        let graph = SparklineWidget::new(
+            label,
+            FRAME_GRAPH_WIDTH,
+            SERIES_LENGTH.div_ceil(FRAME_GRAPH_WIDTH),
+            || micros.clone(),
+        );
+        format!("{} max {:.1}ms", graph.rows()[0], slowest as f64 / 1000.0)
+    }
+    /// Describe the cell under the inspector: its location, its state, its live neighbors and the
+    /// latest generation it was born or died in
+    fn inspect(&mut self) -> String {
+        let Coord { x, y } = self.state.inspector;
+        let state = if self.current[y][x] { "alive" } else { "dead" };
+        let neighbors = self.count_siblings(x, y);
+        let change = match self.history.last_change(x, y) {
+            Some(round) => format!("changed in generation {}", round),
+            None => format!(
+                "unchanged since generation {}",
+                self.history.get(0).map_or(0, |generation| generation.round)
+            ),
+        };
+        format!(
+            "Cell {}:{} {}, {} neighbors, {}",
+            x, y, state, neighbors, change
+        )
+    }
+    /// Get the first line below the board
+    fn below_board(&self) -> usize {
+        let rows = self.viewport().size.y;
+        (rows * self.settings.cell_view_height) + 1 + rows + self.board_origin().y
+    }
+    /// Set the background and foreground colors for the next writes
+    fn set_colors(&mut self, background: TerminalColors, foreground: TerminalColors) {
+        let result = self
+            .screen
+            .handle
+            .set_background_color(background)
+            .and_then(|_| self.screen.handle.set_foreground_color(foreground));
+        if result.is_err() {
+            self.state.latest_err = "Failed to set the colors".to_string();
+        }
+    }
+    /// Write text at a location on the screen using the current colors
+    fn write_at(&mut self, text: &str, coord: Coord) {
+        if self
+            .screen
+            .handle
+            .write_to_location(text.as_bytes(), coord)
+            .is_err()
+        {
+            self.state.latest_err = format!("Failed to write to {}", coord);
+        }
+    }
+
+    /// Print a cell in a background and a foreground color
+    fn print_cell_in(
+        &mut self,
+        x: usize,
+        y: usize,
+        (background, foreground): (TerminalColors, TerminalColors),
+        viewport: &Viewport,
+    ) {
+        // The location of the cell on the screen
+        let view_x = x - viewport.first.x;
+        let view_y = y - viewport.first.y;
+        let origin = self.board_origin();
+        let x_start = view_x * self.settings.cell_view_width + 1 + view_x + origin.x;
+        let y_start = (view_y * self.settings.cell_view_height) + 1 + view_y + origin.y;
+        self.set_colors(background, foreground);
+        let row = " ".repeat(self.settings.cell_view_width);
+        for y_offset in 0..self.settings.cell_view_height {
+            self.write_at(&row, Coord::new(x_start, y_start + y_offset));
+        }
+    }
+    /// Get the background and foreground colors of a cell, highlighting the editor cursor and the
+    /// cells the brush would stamp, or the inspected cell in debug mode. In heatmap mode the background shows how often the cell was
+    /// alive, while the help or the menu is open the board is dimmed. The dead cells that are
+    /// alive in the ghost are grey
+    fn cell_colors(&self, x: usize, y: usize, is_alive: bool) -> (TerminalColors, TerminalColors) {
+        if self.state.is_help_open || self.state.pause_menu.is_some() {
+            return match is_alive {
+                true => (TerminalColors::Grey, TerminalColors::Black),
+                false => (TerminalColors::DarkGrey, TerminalColors::Black),
+            };
+        }
+        if let Some(editor) = &self.state.editor {
+            if editor.cursor.x == x && editor.cursor.y == y {
+                return (TerminalColors::HotPink, TerminalColors::White);
+            }
+            let is_previewed = editor.brush.is_some() || editor.selection().is_some();
+            if is_previewed && self.brush_preview.iter().any(|c| c.x == x && c.y == y) {
+                return (TerminalColors::Yellow, TerminalColors::Black);
+            }
+        } else if self.state.print_mode == PrintMode::DEBUG
+            && self.state.inspector.x == x
+            && self.state.inspector.y == y
+        {
+            return (TerminalColors::HotPink, TerminalColors::White);
+        }
+        if self.state.is_heatmap {
+            return (self.heatmap.color(x, y), TerminalColors::White);
+        }
+        #[cfg(feature = "serde")]
+        if !is_alive && self.is_ghost_alive(x, y) {
+            return (GHOST_COLOR, self.settings.theme.colors(false).1);
+        }
+        self.settings.theme.colors(is_alive)
+    }
+    /// Check if a cell is alive in the recorded run behind the board
+    #[cfg(feature = "serde")]
+    fn is_ghost_alive(&self, x: usize, y: usize) -> bool {
+        self.ghost
+            .as_ref()
+            .and_then(Ghost::cells)
+            .and_then(|cells| cells.get(y)?.get(x).copied())
+            .unwrap_or(false)
+    }
+    /// Calculate and apply the next frame, the current frame becomes the previous one. The board
+    /// of the frame before that is recycled for the next calculation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.next();
+    /// ```
+    pub fn next(&mut self) {
+        let (rule, boundary) = (self.settings.rule, self.settings.boundary);
+        let (mut neighbor_counts, mut new_state) = match self.neighbor_counts.take() {
+            // The previous board is the generation before the current one, the counter only
+            // writes the cells that changed since then
+            Some(counts) if counts.matches(rule, boundary) => {
+                (counts, std::mem::take(&mut self.previous))
+            }
+            // The board, the rule or the boundary was changed since the last generation
+            _ => (
+                NeighborCounts::new(&self.current, rule, boundary),
+                self.buffer_pool
+                    .take(self.settings.x_len, self.settings.y_len, false),
+            ),
+        };
+        let population = neighbor_counts.step(&self.current, &mut new_state);
+        self.changed_cells.clear();
+        self.changed_cells
+            .extend_from_slice(neighbor_counts.changed());
+        self.neighbor_counts = Some(neighbor_counts);
+        self.apply_next(new_state, population);
+    }
+    /// Calculate and apply the next frame by counting the neighbors of every cell, the way `next`
+    /// did before it kept the counts up to date. Used to compare the two
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// game.next_full_scan();
+    /// ```
+    pub fn next_full_scan(&mut self) {
+        let mut new_state = self
+            .buffer_pool
+            .take(self.settings.x_len, self.settings.y_len, false);
+        let mut population = 0;
+        self.changed_cells.clear();
+        self.neighbor_counts = None;
+        for (y, row) in new_state.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                let live_siblings = self.count_siblings(x, y);
+                *cell = self
+                    .settings
+                    .rule
+                    .is_alive(self.current[y][x], live_siblings);
+                if *cell {
+                    population += 1;
+                }
+                if *cell != self.current[y][x] {
+                    self.changed_cells.push((x, y));
+                }
+            }
+            assert_eq!(
+                self.settings.x_len,
+                row.len(),
+                "the length of the row should not change"
+            );
+        }
+        assert_eq!(
+            self.settings.y_len,
+            new_state.len(),
+            "the number of rows should not change"
+        );
+        self.apply_next(new_state, population);
+    }
+    /// Make a calculated generation the current one
+    fn apply_next(&mut self, new_state: Vec<Vec<bool>>, population: u64) {
+        self.heatmap.record(&new_state);
+        self.metrics.gauge(POPULATION, population);
+        let previous = std::mem::replace(&mut self.current, new_state);
+        let recycled = std::mem::replace(&mut self.previous, previous);
+        // `next` draws the new generation over the previous board, nothing is left to recycle
+        if !recycled.is_empty() {
+            self.buffer_pool.give(recycled);
+        }
+    }
+    /// Count the number of living siblings at a location on the previous state
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - x location
+    /// * `y` - y location
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::ConwaysGame;
+    /// # let mut game = ConwaysGame::headless(16, 16, 42);
+    /// let siblings = game.count_siblings(1,4);
+    /// ```
+    pub fn count_siblings(&mut self, x_location: usize, y_location: usize) -> u8 {
+        let mut sibling_count = 0;
+        for x_delta in -1i64..=1 {
+            for y_delta in -1i64..=1 {
+                if x_delta == 0 && y_delta == 0 {
+                    continue;
+                }
+                let mut x_sibling = (x_location as i64) + x_delta;
+                let mut y_sibling = (y_location as i64) + y_delta;
+                let is_outside = x_sibling < 0
+                    || y_sibling < 0
+                    || x_sibling >= self.settings.x_len as i64
+                    || y_sibling >= self.settings.y_len as i64;
+                if is_outside && self.settings.boundary == Boundary::Dead {
+                    continue;
+                }
+                if x_sibling < 0 {
+                    x_sibling = (self.settings.x_len as i64) - 1;
+                } else if x_sibling >= self.settings.x_len as i64 {
+                    x_sibling = 0;
+                }
+                if y_sibling < 0 {
+                    y_sibling = (self.settings.y_len as i64) - 1;
+                } else if y_sibling >= self.settings.y_len as i64 {
+                    y_sibling = 0;
+                }
+                assert!(x_sibling >= 0);
+                assert!(y_sibling >= 0);
+                assert!(x_sibling < self.settings.x_len as i64);
+                assert!(y_sibling < self.settings.y_len as i64);
+                if self.current[y_sibling as usize][x_sibling as usize] {
+                    sibling_count += 1;
+                }
+            }
+        }
+        sibling_count
+    }
+    /// Clear the whole screen, the next frame draws every cell again
+    fn clear_screen(&mut self) {
+        self.drawn_cells = None;
+        let _ = self.screen.handle.clear_screen();
+    }
+    // Clear the screen manually
+    // TODO: this is a temporary fix so i dont have to call clear so often
+    //
+    // ### Usage
+    //
+    // ```
+    // self.clear_cells();
+    // ```
+    fn clear_cells(&mut self) {
+        self.drawn_cells = None;
+        let [_, top_right] = self.find_corners();
+        let empty = " ".repeat(top_right.x + 1);
+        let total_height = self.below_board();
+        let _ = self.screen.handle.reset_colors();
+        for y_loc in 0..total_height {
+            self.write_at(&empty, Coord::new(0, y_loc));
+        }
+        // The pattern picker and the editor below the board
+        let empty = " ".repeat(DEBUG_LINE_WIDTH);
+        for y_loc in total_height..total_height + INFO_LINE_COUNT {
+            self.write_at(&empty, Coord::new(0, y_loc));
+        }
+        if self.state.print_mode == PrintMode::DEBUG {
+            let x = top_right.x + DEBUG_PANEL_GAP;
+            for y_loc in top_right.y..top_right.y + 1 + DEBUG_LINE_COUNT {
+                self.write_at(&empty, Coord::new(x, y_loc));
+            }
+        }
+    }
+    // Find the opposite corners of the board
+    //
+    // ### Usage
+    //
+    // ```
+    // self.find_corners();
+    // ```
+    fn find_corners(&mut self) -> [Coord; 2] {
+        let visible = self.viewport().size;
+        let origin = self.board_origin();
+        let top = origin.y;
+        let bottom = (visible.y * self.settings.cell_view_height) + 1 + visible.y + top;
+
+        let left = origin.x;
+        let right = (visible.x * self.settings.cell_view_width) + 1 + visible.x + left;
+        [Coord { x: left, y: bottom }, Coord { x: right, y: top }]
+    }
+    // Find the center of the  board
+    //
+    // ### Usage
+    //
+    // ```
+    // self.find_center();
+    // ```
+    fn find_center(&mut self) -> Coord {
+        let corners = self.find_corners();
+        let x = ((corners[0].x + corners[1].x) as f32 / 2.0).floor() as usize;
+        let y = ((corners[0].y + corners[1].y) as f32 / 2.0).floor() as usize;
+
+        Coord { x, y }
+    }
+}
+
+/// Builder for a `ConwaysGame`, made with `ConwaysGame::builder`
+pub struct ConwaysGameBuilder {
+    x_len: usize,
+    y_len: usize,
+    seed: Option<u64>,
+    rule: Rule,
+    boundary: Boundary,
+    round_duration: Duration,
+    print_mode: PrintMode,
+    auto_pause: bool,
+    skip: u64,
+    pattern: Option<PatternPlacement>,
+    clipboard: SharedClipboard,
+    #[cfg(feature = "serde")]
+    resume: Option<SavedGame>,
+    #[cfg(feature = "net")]
+    spectators: Option<SpectatorServer>,
+    input: Option<Box<dyn InputSource>>,
+    handle: Option<Box<dyn Handle>>,
+}
+impl ConwaysGameBuilder {
+    /// Set the seed of the random board, without one a seed is picked from the entropy of the
+    /// system
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+    /// Set the rule the generations are calculated with
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+    /// Set what is beyond the edges of the board
+    pub fn boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+    /// Set the time between generations
+    pub fn speed(mut self, round_duration: Duration) -> Self {
+        self.round_duration = round_duration;
+        self
+    }
+    /// Set the print mode the game starts in
+    pub fn render_mode(mut self, print_mode: PrintMode) -> Self {
+        self.print_mode = print_mode;
+        self
+    }
+    /// Pause the game once the board is stable or repeats itself, instead of stopping it
+    pub fn auto_pause(mut self, auto_pause: bool) -> Self {
+        self.auto_pause = auto_pause;
+        self
+    }
+    /// Fast forward a number of generations once the game starts, without rendering the
+    /// generations in between. The fast forward key skips the same number of generations
+    pub fn skip(mut self, generations: u64) -> Self {
+        self.skip = generations;
+        self
+    }
+    /// Place a pattern on the random board, like `--pattern glider@10,10`
+    pub fn pattern(mut self, placement: PatternPlacement) -> Self {
+        self.pattern = Some(placement);
+        self
+    }
+    /// Share a clipboard with the scenes and the system clipboard it was created with, the game
+    /// has a clipboard of its own without one
+    pub fn clipboard(mut self, clipboard: SharedClipboard) -> Self {
+        self.clipboard = clipboard;
+        self
+    }
+    /// Continue a saved game, like the autosave of a session that did not shut down cleanly,
+    /// instead of starting with a random board
+    #[cfg(feature = "serde")]
+    pub fn resume(mut self, saved: SavedGame) -> Self {
+        self.resume = Some(saved);
+        self
+    }
+    /// Broadcast every generation to the spectators of a server, like the one of `--serve`
+    #[cfg(feature = "net")]
+    pub fn spectators(mut self, server: SpectatorServer) -> Self {
+        self.spectators = Some(server);
+        self
+    }
+    /// Set the channel the key inputs of the game arrive on, like the console input thread
+    pub fn receiver(self, receiver: Receiver<KeyEvent>) -> Self {
+        self.input(Box::new(receiver))
+    }
+    /// Set where the key inputs of the game come from
+    pub fn input(mut self, input: Box<dyn InputSource>) -> Self {
+        self.input = Some(input);
+        self
+    }
+    /// Set the handle the game is drawn to
+    pub fn handle(mut self, handle: Box<dyn Handle>) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+    /// Finish building the game
+    ///
+    /// # Returns
+    ///
+    /// The game with a random board, or why it could not be built
+    pub fn build(self) -> Result<ConwaysGame, ConwayError> {
+        if self.x_len == 0 || self.y_len == 0 {
+            return Err(ConwayError::EmptyBoard);
+        }
+        let input = self.input.ok_or(ConwayError::MissingInput)?;
+        let handle = self.handle.ok_or(ConwayError::MissingHandle)?;
+        let seed = self
+            .seed
+            .unwrap_or_else(|| RngService::from_entropy().master_seed());
+        let mut game = ConwaysGame::create(
+            self.x_len,
+            self.y_len,
+            seed,
+            self.print_mode,
+            self.round_duration,
+            input,
+            handle,
+        );
+        game.settings.rule = self.rule;
+        game.settings.boundary = self.boundary;
+        game.settings.auto_pause = self.auto_pause;
+        if self.skip > 0 {
+            game.settings.skip_generations = self.skip;
+            game.settings.start_skip = self.skip;
+        }
+        if let Some(placement) = self.pattern {
+            game.place_pattern(&placement);
+        }
+        game.set_clipboard(self.clipboard);
+        #[cfg(feature = "serde")]
+        if let Some(saved) = self.resume {
+            game.restore(&saved).map_err(|_| ConwayError::InvalidSave)?;
+        }
+        #[cfg(feature = "net")]
+        game.set_spectators(self.spectators);
+        Ok(game)
+    }
+}
+
+impl Automaton for ConwaysGame {
+    type Cell = bool;
+    fn next(&mut self) {
+        ConwaysGame::next(self);
+    }
+    fn cells(&self) -> &Vec<Vec<bool>> {
+        &self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{mpsc, Arc, Mutex},
+        time::Duration,
+    };
+
+    use std::io::Write;
+
+    use crate::{
+        console::input_record::{KeyEvent, MouseEvent},
+        conway::command::Command,
+        handler::{handle::Handle, memory_handle::MemoryHandle, shared_handle::SharedHandle},
+        rendering::colors::TerminalColors as TC,
+    };
+
+    use super::{BoardTransform, ConwaysGame, Coord, Usize2d};
+
+    fn memory_handle() -> Box<SharedHandle> {
+        Box::new(SharedHandle::init(Arc::new(
+            Mutex::new(MemoryHandle::new()),
+        )))
+    }
+
+    #[test]
+    fn init_works() {
+        let (_sen, rec) = mpsc::channel();
+        let game = ConwaysGame::builder(5, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::DEBUG)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+
+        let current_state = game.current.clone();
+
+        let scenarios = [
+            (0, 0, true),
+            (1, 0, true),
+            (2, 0, true),
+            (3, 0, false),
+            (4, 0, true),
+            (0, 1, true),
+            (1, 1, false),
+            (2, 1, false),
+            (3, 1, false),
+            (4, 1, true),
+            (0, 2, false),
+            (1, 2, true),
+            (2, 2, false),
+            (3, 2, true),
+            (4, 2, false),
+            (0, 3, true),
+            (1, 3, true),
+            (2, 3, false),
+            (3, 3, true),
+            (4, 3, true),
+            (0, 4, true),
+            (1, 4, false),
+            (2, 4, false),
+            (3, 4, false),
+            (4, 4, true),
+        ];
+
+        for (x, y, expected) in scenarios {
+            assert_eq!(
+                current_state[y][x], expected,
+                "x,y: {},{}, state: {}",
+                x, y, expected
+            );
+        }
+    }
+    #[test]
+    fn next() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::DEBUG)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.next();
+
+        let scenarios = [
+            (0, 0, false),
+            (1, 0, false),
+            (2, 0, false),
+            (3, 0, false),
+            (4, 0, false),
+            (0, 1, false),
+            (1, 1, false),
+            (2, 1, false),
+            (3, 1, false),
+            (4, 1, false),
+            (0, 2, false),
+            (1, 2, true),
+            (2, 2, false),
+            (3, 2, true),
+            (4, 2, false),
+            (0, 3, false),
+            (1, 3, true),
+            (2, 3, false),
+            (3, 3, true),
+            (4, 3, false),
+            (0, 4, false),
+            (1, 4, false),
+            (2, 4, false),
+            (3, 4, false),
+            (4, 4, false),
+        ];
+        let current_state = game.current.clone();
+
+        for (x, y, expected) in scenarios {
+            assert_eq!(
+                current_state[y][x], expected,
+                "x,y: {},{}, state expected: {}, actual: {}",
+                x, y, expected, current_state[y][x]
+            );
+        }
+    }
+    #[test]
+    fn render_draws_the_changed_cells() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 5)
+            .render_mode(super::PrintMode::PRETTY)
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        let mut cells = vec![vec![false; 5]; 5];
+        for row in &mut cells[1..=3] {
+            row[2] = true;
+        }
+        game.set_cells(cells);
+        game.render();
+        // Mark the corner cell, which stays dead, on the screen
+        let origin = game.board_origin();
+        game.write_at("@", Coord::new(origin.x + 1, origin.y + 1));
+        let is_marked =
+            || String::from_utf8_lossy(&buffer.lock().unwrap().get_buffer_content()).contains('@');
+
+        game.next();
+        let mut changed = game.changed_cells().to_vec();
+        changed.sort();
+        assert_eq!(changed, [(1, 2), (2, 1), (2, 3), (3, 2)]);
+        game.render();
+        assert!(is_marked(), "Only the changed cells are drawn");
+        assert!(game.cells()[2][1]);
+
+        game.set_screen_size(Some(Usize2d::new(80, 40)));
+        game.render();
+        assert!(!is_marked(), "A cleared screen is drawn again");
+    }
+    #[test]
+    fn next_recycles_boards() {
+        let mut game = ConwaysGame::headless(8, 6, 21);
+        for _ in 0..10 {
+            let current = game.current.clone();
+            game.next();
+            assert_eq!(
+                game.previous, current,
+                "The current board becomes the previous one"
+            );
+        }
+        assert_eq!(
+            game.buffer_pool.allocations(),
+            1,
+            "Only the first generation allocates a board"
+        );
+    }
+    #[test]
+    fn siblings_count() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::DEBUG)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+
+        let scenarios = [
+            (0, 0, 6),
+            (0, 1, 5),
+            (0, 2, 6),
+            (0, 3, 5),
+            (0, 4, 7),
+            (1, 0, 4),
+            (1, 1, 5),
+            (1, 2, 3),
+            (1, 3, 3),
+            (1, 4, 6),
+            (2, 0, 1),
+            (2, 1, 4),
+            (2, 2, 4),
+            (2, 3, 4),
+            (2, 4, 4),
+            (3, 0, 4),
+            (3, 1, 4),
+            (3, 2, 3),
+            (3, 3, 3),
+            (3, 4, 5),
+            (4, 0, 5),
+            (4, 1, 4),
+            (4, 2, 6),
+            (4, 3, 5),
+            (4, 4, 6),
+        ];
+
+        for (x, y, expected) in scenarios {
+            assert_eq!(
+                game.count_siblings(x, y),
+                expected,
+                "x,y: {},{}, state expected: {}, actual: {}",
+                x,
+                y,
+                expected,
+                game.count_siblings(x, y)
+            );
+        }
+    }
+    #[test]
+    fn run_until_stable() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_millis(1))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        game.run();
+        assert!(
+            game.is_stable(),
+            "The game should only stop once it is stable"
+        );
+        assert_eq!(
+            game.state.rounds, 3,
+            "The board dies out after 2 rounds and is stable after the 3rd"
+        );
+        assert!(
+            game.timers.is_empty(),
+            "The timers should be cleared on exit"
+        );
+        let handle = buffer.lock().unwrap();
+        assert_eq!(
+            handle.title,
+            Some("Conway's Game of Life - Pretty - round 3 - seed 55".to_string())
+        );
+        assert_eq!(
+            handle.bell_count, 1,
+            "The bell should ring once on game over"
+        );
+    }
+    #[test]
+    fn run_until_quit() {
+        let (sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::DEBUG)
+            .speed(Duration::from_secs(60))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let _ = sen.send(KeyEvent::key_down('q'));
+        game.run();
+        assert_eq!(game.state.rounds, 0, "The game should quit before any step");
+    }
+    #[test]
+    fn quits_after_typing_q() {
+        use crate::console::input_source::listen_and_notify_line_inputs;
+
+        let test_cases = [
+            (
+                "q typed into the seed prompt",
+                "\x1bww\r\x08\x08q\n\x1b\x1b\nq\n",
+            ),
+            ("q closing the help", "hq\nq\n"),
+            (
+                "q typed into the macro names",
+                "\x12quick\rx\x12\n\x10\x08\x08\x08\x08\x08quick\rq\n",
+            ),
+        ];
+        for (test_case, lines) in test_cases {
+            let (sen, rec) = mpsc::channel();
+            let mut game = ConwaysGame::builder(8, 8)
+                .seed(55)
+                .render_mode(super::PrintMode::PRETTY)
+                .speed(Duration::from_secs(60))
+                .auto_pause(true)
+                .receiver(rec)
+                .handle(memory_handle())
+                .build()
+                .unwrap();
+            // The keys are read as lines, like on a terminal that is not in raw mode
+            listen_and_notify_line_inputs(std::io::Cursor::new(lines), sen)
+                .join()
+                .unwrap();
+            game.start();
+            let ticks = (0..100).take_while(|_| game.tick()).count();
+            game.finish();
+            assert!(ticks < 100, "{}: the game should still quit", test_case);
+            assert!(
+                game.state.latest_command == Command::QUIT,
+                "{}: got {}",
+                test_case,
+                game.state.latest_command
+            );
+        }
+    }
+    #[test]
+    fn restores_main_screen() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        {
+            let mut handle = buffer.lock().unwrap();
+            let _ = handle.write_to_location(b"$ cargo run", Coord::new(0, 0));
+            let _ = handle.flush();
+        }
+        let (sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(60))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        game.start();
+        game.render();
+        assert_ne!(
+            buffer.lock().unwrap().get_buffer_content(),
+            b"$ cargo run".to_vec(),
+            "The game should be drawn on the alternate screen"
+        );
+        let _ = sen.send(KeyEvent::key_down('q'));
+        while game.tick() {}
+        game.finish();
+        assert_eq!(
+            String::from_utf8_lossy(&buffer.lock().unwrap().get_buffer_content()),
+            "$ cargo run",
+            "The main screen should be restored once the game stopped"
+        );
+    }
+    #[test]
+    fn place_pattern() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.set_cells(vec![vec![false; 8]; 8]);
+        game.place_pattern(&"glider@6,1".parse().unwrap());
+        let live = |game: &ConwaysGame| -> Vec<(usize, usize)> {
+            let mut live = Vec::new();
+            for (y, row) in game.cells().iter().enumerate() {
+                for (x, cell) in row.iter().enumerate() {
+                    if *cell {
+                        live.push((x, y));
+                    }
+                }
+            }
+            live
+        };
+        assert_eq!(
+            live(&game),
+            vec![(7, 1), (0, 2), (0, 3), (6, 3), (7, 3)],
+            "The glider should wrap around the right edge"
+        );
+        for _ in 0..4 {
+            game.next();
+        }
+        assert_eq!(
+            live(&game),
+            vec![(0, 2), (1, 3), (0, 4), (1, 4), (7, 4)],
+            "A glider moves one cell down and right every 4 generations"
+        );
+    }
+    #[test]
+    fn pattern_picker_table() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(20, 20)
+            .seed(55)
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        game.handle_keys(vec![KeyEvent::key_down('p'); 2]);
+        game.render();
+        let content = buffer.lock().unwrap().get_buffer_content();
+        let text = String::from_utf8_lossy(&content).to_string();
+        let rows: Vec<&str> = text
+            .lines()
+            .filter_map(|line| line.find("Pattern ").map(|start| &line[start..]))
+            .collect();
+        assert_eq!(rows.len(), 1, "The picker lists the patterns in a table");
+        let test_cases = [
+            ("not picked", "  glider "),
+            ("picked", "> lwss "),
+            ("size", "  pulsar            13x13"),
+        ];
+        for (test_case, expected) in test_cases {
+            assert!(text.contains(expected), "{}", test_case);
+        }
+    }
+    #[test]
+    fn pattern_picker() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(20, 20)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.set_cells(vec![vec![false; 20]; 20]);
+        game.handle_key(KeyEvent::key_down('p'));
+        assert!(
+            game.state.is_paused,
+            "Opening the picker should pause the game"
+        );
+        assert_eq!(game.state.picked_pattern, Some(0));
+        game.handle_key(KeyEvent::key_down('p'));
+        assert_eq!(game.state.picked_pattern, Some(1));
+        game.handle_key(KeyEvent::key_down('\r'));
+        assert_eq!(game.state.picked_pattern, None);
+        assert_eq!(game.population(), 9, "The lwss should be placed");
+        assert!(game.cells()[8][8], "The lwss should be centered");
+
+        game.handle_key(KeyEvent::key_down('p'));
+        game.handle_key(KeyEvent::key_down('\x1b'));
+        assert_eq!(game.state.picked_pattern, None);
+        assert_eq!(
+            game.population(),
+            9,
+            "Closing the picker should not place anything"
+        );
+    }
+    #[test]
+    fn editor_stamp() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(10, 10)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.set_cells(vec![vec![false; 10]; 10]);
+        for key in ['e', 'l', 'l', 'k', 'x'] {
+            game.handle_key(KeyEvent::key_down(key));
+        }
+        assert!(game.state.is_paused, "The editor should pause the game");
+        assert!(
+            game.cells()[1][2],
+            "The cell under the cursor should be toggled"
+        );
+        game.handle_key(KeyEvent::key_down('x'));
+        assert_eq!(game.population(), 0);
+
+        // Pick the glider as the brush, turn it once and stamp it at 2:1
+        for key in ['b', 'r', '\r'] {
+            game.handle_key(KeyEvent::key_down(key));
+        }
+        let expected = [(2, 1), (2, 2), (2, 3), (3, 3), (4, 2)];
+        for (x, y) in expected {
+            assert!(game.cells()[y][x], "{}:{} should be stamped", x, y);
+        }
+        assert_eq!(game.population(), expected.len());
+        assert_eq!(
+            game.state.rounds, 0,
+            "'r' should rotate the brush instead of resetting the game"
+        );
+        let test_cases = [
+            ("undo the stamp", '\x1a', Command::UNDO, 0),
+            ("undo the toggles", '\x1a', Command::UNDO, 1),
+            ("redo the toggle", '\x19', Command::REDO, 0),
+            ("redo the stamp", '\x19', Command::REDO, expected.len()),
+        ];
+        for (test_case, key, command, population) in test_cases {
+            game.handle_key(KeyEvent::key_down(key));
+            assert!(game.state.latest_command == command, "{}", test_case);
+            assert_eq!(game.population(), population, "{}", test_case);
+        }
+
+        // Select the lwss through the picker and leave the editor
+        for key in ['p', 'p', '\r'] {
+            game.handle_key(KeyEvent::key_down(key));
+        }
+        assert_eq!(
+            game.state
+                .editor
+                .as_ref()
+                .and_then(|e| e.brush)
+                .map(|b| b.pattern.name),
+            Some("lwss")
+        );
+        game.handle_key(KeyEvent::key_down('\x1b'));
+        assert!(game.state.editor.is_none());
+        assert_eq!(game.population(), expected.len());
+    }
+    #[test]
+    fn debug_panel() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(40, 10)
+            .seed(55)
+            .render_mode(super::PrintMode::DEBUG)
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        game.set_screen_size(Some(Usize2d::new(120, 40)));
+        let screen_text = |buffer: &Arc<Mutex<MemoryHandle>>| -> Vec<String> {
+            let content = buffer.lock().unwrap().get_buffer_content();
+            String::from_utf8_lossy(&content)
+                .lines()
+                .map(|line| line.to_string())
+                .collect()
+        };
+        let test_cases = [
+            ("expanded", ' ', 14, "[-] Debug"),
+            ("collapsed", 'o', 27, "[+] Debug"),
+            ("expanded again", 'o', 14, "[-] Debug"),
+        ];
+        for (test_case, key, columns, title) in test_cases {
+            if key != ' ' {
+                game.handle_key(KeyEvent::key_down(key));
+                assert!(
+                    game.state.latest_command == Command::TOGGLEDEBUGPANEL,
+                    "{}",
+                    test_case
+                );
+            }
+            assert_eq!(game.viewport().size.x, columns, "{}", test_case);
+            game.render();
+            let text = screen_text(&buffer);
+            let top_right = game.find_corners()[1];
+            assert_eq!(
+                text[top_right.y].get(top_right.x + 2..top_right.x + 2 + title.len()),
+                Some(title),
+                "{}",
+                test_case
+            );
+            let is_expanded = text[top_right.y + 1].contains("Round 0.");
+            assert_eq!(is_expanded, title == "[-] Debug", "{}", test_case);
+        }
+    }
+    #[test]
+    fn board_layout() {
+        use crate::panel::layout::{HorizontalAlign, PanelLayout, VerticalAlign};
+
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 3)
+            .seed(55)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        assert_eq!(
+            (game.board_origin().x, game.board_origin().y),
+            (0, 0),
+            "Without a screen size the board is drawn at the origin"
+        );
+        // The board is 21 by 10 characters and leaves 59 by 27 of the screen
+        game.set_screen_size(Some(Usize2d::new(80, 40)));
+        let test_cases = [
+            ("centered", PanelLayout::centered(0), (29, 13)),
+            ("padding", PanelLayout::centered(2), (29, 13)),
+            ("top left", PanelLayout::default(), (0, 0)),
+            (
+                "bottom right",
+                PanelLayout {
+                    padding: 1,
+                    horizontal: HorizontalAlign::Right,
+                    vertical: VerticalAlign::Bottom,
+                },
+                (58, 26),
+            ),
+        ];
+        for (test_case, layout, expected) in test_cases {
+            game.settings.layout = layout;
+            let origin = game.board_origin();
+            assert_eq!((origin.x, origin.y), expected, "{}", test_case);
+        }
+
+        game.settings.layout = PanelLayout::centered(0);
+        game.handle_key(KeyEvent::key_down('d'));
+        assert_eq!(
+            game.board_origin().x,
+            30,
+            "Moving the board offsets it from the layout"
+        );
+    }
+    #[test]
+    fn frame_graph() {
+        use crate::utils::metrics::{RENDER_TIME, UPDATE_TIME};
+
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::DEBUG)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.start();
+        for _ in 0..2 {
+            game.tick();
+        }
+        assert_eq!(game.metrics.samples(UPDATE_TIME).len(), 2);
+        assert_eq!(game.metrics.samples(RENDER_TIME).len(), 2);
+
+        game.metrics.reset(game.clock.now());
+        for millis in [1, 1, 1, 1, 8, 1, 1, 1, 1] {
+            game.metrics
+                .sample(RENDER_TIME, Duration::from_millis(millis));
+        }
+        assert_eq!(
+            game.frame_graph("Render", RENDER_TIME),
+            format!("Render {}▁█▁ max 8.0ms", " ".repeat(37)),
+            "A bar shows the slowest of three frames"
+        );
+    }
+    #[test]
+    fn input_log() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(20, 10)
+            .seed(55)
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        game.handle_key(KeyEvent::key_down('n'));
+        assert!(game.state.latest_command == Command::TOGGLEINPUTLOG);
+        game.handle_key(KeyEvent::key_down('y'));
+        game.handle_key(KeyEvent::key_down(' '));
+        game.render();
+
+        let content = buffer.lock().unwrap().get_buffer_content();
+        let text = String::from_utf8_lossy(&content).to_string();
+        let lines: Vec<&str> = text.lines().skip(1).take(4).collect();
+        let test_cases = [
+            ("title", "Input log"),
+            ("newest first", "space  down x1   Toggle pause"),
+            ("not mapped", "y      down x1   Key not mapped"),
+            (
+                "the key that opened the log",
+                "n      down x1   Toggle the input log",
+            ),
+        ];
+        for ((test_case, expected), line) in test_cases.iter().zip(lines) {
+            assert_eq!(
+                line.get(2..2 + expected.len()),
+                Some(*expected),
+                "{}",
+                test_case
+            );
+        }
+
+        game.handle_key(KeyEvent::key_down('n'));
+        assert!(!game.state.is_input_log_open);
+        assert_eq!(game.input_log.entries().count(), 4);
+    }
+    #[test]
+    fn too_small_screen() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(20, 20)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        let size = Arc::new(Mutex::new(Some(Usize2d::new(30, 4))));
+        let source = size.clone();
+        game.set_screen_size_source(Box::new(move || *source.lock().unwrap()));
+        let required = game.required_screen_size();
+        let text = |buffer: &Arc<Mutex<MemoryHandle>>| -> String {
+            let content = buffer.lock().unwrap().get_buffer_content();
+            String::from_utf8_lossy(&content).to_string()
+        };
+
+        game.render();
+        let message = format!("terminal too small (need {}x{})", required.x, required.y);
+        assert!(text(&buffer).contains(&message), "{}", text(&buffer));
+
+        *size.lock().unwrap() = Some(Usize2d::new(30, 20));
+        game.check_screen_size();
+        game.render();
+        assert!(!text(&buffer).contains("terminal too small"));
+        assert!(
+            text(&buffer).contains("vvv"),
+            "The board is drawn after the terminal grew: {}",
+            text(&buffer)
+        );
+    }
+    #[test]
+    fn edge_indicators() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(20, 20)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        game.set_screen_size(Some(Usize2d::new(30, 20)));
+        let mut cells = vec![vec![false; 20]; 20];
+        for (x, y) in [(10, 2), (11, 2), (10, 3), (11, 3)] {
+            cells[y][x] = true;
+        }
+        game.set_cells(cells);
+        let viewport = game.viewport();
+        assert_eq!((viewport.size.x, viewport.size.y), (7, 5));
+
+        let right_edge = |buffer: &Arc<Mutex<MemoryHandle>>| -> Vec<char> {
+            let content = buffer.lock().unwrap().get_buffer_content();
+            let text = String::from_utf8_lossy(&content).to_string();
+            text.lines()
+                .skip(1)
+                .take(15)
+                .map(|line| line.chars().nth(28).unwrap_or(' '))
+                .collect()
+        };
+        game.render();
+        assert_eq!(
+            right_edge(&buffer).iter().collect::<String>(),
+            "      >> >>    ",
+            "Rows 2 and 3 have live cells beyond the right edge"
+        );
+
+        for _ in 0..5 {
+            game.handle_key(KeyEvent::key_down('d'));
+        }
+        assert_eq!(game.viewport().first.x, 5, "'d' should scroll the view");
+        assert_eq!(game.settings.origin.x, 0, "The board should not move");
+        game.render();
+        assert!(
+            right_edge(&buffer).iter().all(|c| *c == ' '),
+            "The block is visible after scrolling"
+        );
+    }
+    #[test]
+    fn heatmap_colors_by_activity() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        // A blinker, its center is always alive and its ends are alive every other generation
+        let mut cells = vec![vec![false; 5]; 5];
+        for (x, y) in [(1, 2), (2, 2), (3, 2)] {
+            cells[y][x] = true;
+        }
+        game.set_cells(cells);
+        game.heatmap.clear();
+        for _ in 0..4 {
+            game.next();
+        }
+        game.handle_key(KeyEvent::key_down('x'));
+        assert!(game.state.latest_command == Command::TOGGLEHEATMAP);
+        game.render();
+
+        // The top left character of a cell at x, y
+        let color = |x: usize, y: usize| {
+            buffer.lock().unwrap().get_background_colors()[1 + y * 3][1 + x * 4]
+        };
+        assert_eq!(color(2, 2), TC::Red, "The center is always alive");
+        assert_eq!(color(1, 2), TC::LightGreen, "The ends blink");
+        assert_eq!(color(2, 1), TC::LightGreen, "The ends blink");
+        assert_eq!(color(0, 0), TC::Black, "The corner is never alive");
+
+        game.handle_key(KeyEvent::key_down('x'));
+        game.render();
+        assert_eq!(
+            color(0, 0),
+            TC::Red,
+            "Dead cells are red without the heatmap"
+        );
+    }
+    #[test]
+    fn help_overlay() {
+        use crate::conway::key_bindings::KeyBindings;
+
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(10, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        let mut bindings = KeyBindings::default();
+        bindings.bind('k', Command::TOGGLEHELP);
+        game.set_key_bindings(bindings);
+        game.set_cells(vec![vec![false; 10]; 5]);
+        game.handle_key(KeyEvent::key_down('K'));
+        assert!(game.state.latest_command == Command::TOGGLEHELP);
+        game.render();
+        let text =
+            String::from_utf8_lossy(&buffer.lock().unwrap().get_buffer_content()).to_string();
+        assert!(text.contains("space  Toggle pause"), "{}", text);
+        assert!(text.contains("k      Toggle the help"), "{}", text);
+        assert_eq!(
+            buffer.lock().unwrap().get_background_colors()[1][1],
+            TC::DarkGrey,
+            "The board is dimmed behind the help"
+        );
+
+        game.handle_key(KeyEvent::key_down('q'));
+        assert!(
+            game.state.latest_command == Command::TOGGLEHELP,
+            "Any key closes the help without running its command"
+        );
+        assert!(!game.state.is_help_open);
+        game.handle_key(KeyEvent::key_down('?'));
+        assert!(game.state.is_help_open, "Both keys open the help");
+        game.handle_key(KeyEvent::key_down('?'));
+        game.handle_key(KeyEvent::key_down('h'));
+        assert!(game.state.is_help_open, "Both keys open the help");
+    }
+    #[test]
+    fn pause_menu() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let path = std::env::temp_dir().join(format!(
+            "conway_game_pause_menu_test_{}.json",
+            std::process::id()
+        ));
+        game.settings.save_path = path.clone();
+        let mut test_cases = vec![
+            ("escape opens the menu", '\x1b', Command::OPENMENU),
+            ("keys the menu does not use", 'h', Command::NOMAPPING),
+            ("move down", 's', Command::MOVESELECTION),
+            ("move up", 'W', Command::MOVESELECTION),
+            ("select resume", '\n', Command::CLOSEMENU),
+            ("escape opens the menu again", '\x1b', Command::OPENMENU),
+            ("the quit chord quits from the menu", '\x11', Command::QUIT),
+            ("move up to quit", 'w', Command::MOVESELECTION),
+            ("select quit", '\r', Command::QUIT),
+        ];
+        if cfg!(feature = "serde") {
+            // Saving is disabled without serde, the selection skips it
+            test_cases.insert(3, ("select save", '\r', Command::OPENINPUT));
+            test_cases.insert(4, ("save to the typed file", '\n', Command::SAVE));
+        }
+        for (test_case, key, expected) in test_cases {
+            game.handle_key(KeyEvent::key_down(key));
+            assert!(
+                game.state.latest_command == expected,
+                "{}: got {}",
+                test_case,
+                game.state.latest_command
+            );
+            if expected == Command::CLOSEMENU {
+                assert!(!game.state.is_paused, "{}", test_case);
+            } else {
+                assert!(game.state.is_paused, "{}", test_case);
+            }
+        }
+        assert!(!game.state.is_heatmap, "The menu takes all keys");
+        let is_saved = path.exists();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            is_saved,
+            cfg!(feature = "serde"),
+            "The game should be saved from the menu"
+        );
+    }
+    #[test]
+    fn load_files() {
+        use crate::conway::pause_menu::PauseMenuItem;
+
+        let dir = std::env::temp_dir().join(format!("conway_load_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("glider.rle"), "x = 3, y = 3\nbo$2bo$3o!").unwrap();
+        std::fs::write(dir.join("broken.cells"), "O?").unwrap();
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.settings.save_path = dir.join("saved.json");
+        game.set_cells(vec![vec![false; 8]; 8]);
+        let open_files = |game: &mut ConwaysGame| {
+            game.handle_key(KeyEvent::key_down('\x1b'));
+            if let Some(menu) = game.state.pause_menu.as_mut() {
+                menu.menu.select(PauseMenuItem::Load);
+            }
+            game.handle_key(KeyEvent::key_down('\r'));
+        };
+
+        open_files(&mut game);
+        assert!(game.state.latest_command == Command::OPENFILES);
+        let entries = game.state.file_picker.as_ref().unwrap().entries();
+        assert_eq!(entries[1..], ["broken.cells", "glider.rle"]);
+        game.handle_key(KeyEvent::key_down('s'));
+        game.handle_key(KeyEvent::key_down('\r'));
+        assert!(game.state.latest_command == Command::LOAD);
+        assert!(game.state.file_picker.is_none());
+        assert_eq!(
+            game.state
+                .pause_menu
+                .as_ref()
+                .map(|menu| menu.status.as_str()),
+            Some("Failed to load broken.cells"),
+            "A broken file keeps the menu open"
+        );
+
+        game.handle_key(KeyEvent::key_down('\x1b'));
+        open_files(&mut game);
+        for key in ['w', '\r'] {
+            game.handle_key(KeyEvent::key_down(key));
+        }
+        assert!(game.state.latest_command == Command::LOAD);
+        assert!(game.state.pause_menu.is_none(), "The game continues");
+        assert!(!game.state.is_paused);
+        assert_eq!(game.population(), 5, "The glider is placed");
+        assert!(
+            game.current[2][3] && game.current[3][4],
+            "In the middle of the board"
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            game.save().save(&game.settings.save_path).unwrap();
+            let _ = game.resize(5, 4);
+            game.set_cells(vec![vec![false; 5]; 4]);
+            open_files(&mut game);
+            for key in ['w', '\r'] {
+                game.handle_key(KeyEvent::key_down(key));
+            }
+            assert!(game.state.latest_command == Command::LOAD);
+            assert_eq!(game.population(), 5, "The save is continued");
+            assert_eq!((game.settings.x_len, game.settings.y_len), (8, 8));
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    #[test]
+    fn macros() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let test_cases = [
+            ("nothing to play yet", "\x10", Command::PLAYMACRO, false),
+            ("ask for a name", "\x12", Command::RECORDMACRO, false),
+            (
+                "start recording",
+                "\x7f\x7fab\r",
+                Command::RECORDMACRO,
+                false,
+            ),
+            ("recorded keys run", "xxx", Command::TOGGLEHEATMAP, true),
+            ("stop recording", "\x12", Command::RECORDMACRO, true),
+            (
+                "play the latest macro",
+                "\x10\r",
+                Command::TOGGLEHEATMAP,
+                false,
+            ),
+            ("play it again", "\x10\r", Command::TOGGLEHEATMAP, true),
+            ("unknown name", "\x10\x7fx\r", Command::TYPE, true),
+        ];
+        for (test_case, keys, expected, is_heatmap) in test_cases {
+            for key in keys.chars() {
+                game.handle_key(KeyEvent::key_down(key));
+            }
+            assert!(
+                game.state.latest_command == expected,
+                "{}: got {}",
+                test_case,
+                game.state.latest_command
+            );
+            assert_eq!(game.state.is_heatmap, is_heatmap, "{}", test_case);
+        }
+        assert_eq!(game.macros.names(), vec!["ab"]);
+        assert_eq!(
+            game.macros.play("ab").map(|keys| keys.len()),
+            Some(3),
+            "Only the keys between the chords are recorded"
+        );
+    }
+
+    #[test]
+    fn seed_prompt() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        game.start();
+        let test_cases = [
+            ("open the menu", "\x1b", Command::OPENMENU),
+            ("select the seed", "ww\r", Command::OPENINPUT),
+            ("edit the seed", "\x08\x08x", Command::TYPE),
+            ("not a number", "\r", Command::TYPE),
+            ("back to the menu", "\x1b", Command::CLOSEINPUT),
+            ("open it again", "\r", Command::OPENINPUT),
+            ("keys the field does not use", "\t", Command::NOMAPPING),
+            ("type a seed", "\x08\x0842", Command::TYPE),
+        ];
+        for (test_case, keys, expected) in test_cases {
+            for key in keys.chars() {
+                game.handle_key(KeyEvent::key_down(key));
+            }
+            assert!(
+                game.state.latest_command == expected,
+                "{}: got {}",
+                test_case,
+                game.state.latest_command
+            );
+            assert!(game.state.is_paused, "{}", test_case);
+        }
+        game.render();
+        let text =
+            String::from_utf8_lossy(&buffer.lock().unwrap().get_buffer_content()).to_string();
+        assert!(text.contains("Seed: 42"), "{}", text);
+
+        game.handle_key(KeyEvent::key_down('\r'));
+        assert_eq!(game.settings.seed, 42);
+        assert!(game.state.pause_menu.is_none(), "The game starts over");
+        assert!(!game.state.is_paused);
+    }
+    #[test]
+    fn options_screen() {
+        use crate::{
+            conway::{pause_menu::PauseMenuItem, settings::Boundary},
+            rendering::theme::Theme,
+            utils::clock::VirtualClock,
+        };
+
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        let path = std::env::temp_dir().join(format!(
+            "conway_game_options_test_{}.json",
+            std::process::id()
+        ));
+        game.settings.options_path = path.clone();
+        let clock = VirtualClock::new();
+        game.set_clock(Box::new(clock.clone()));
+        game.start();
+        game.handle_key(KeyEvent::key_down('\x1b'));
+        if let Some(menu) = game.state.pause_menu.as_mut() {
+            menu.menu.select(PauseMenuItem::Options);
+        }
+        game.handle_key(KeyEvent::key_down('\r'));
+        assert!(game.state.latest_command == Command::OPENOPTIONS);
+        let test_cases = [
+            ("faster", 'd', Duration::from_millis(500), Boundary::Wrap),
+            (
+                "select the boundary",
+                's',
+                Duration::from_millis(500),
+                Boundary::Wrap,
+            ),
+            (
+                "change the boundary",
+                'a',
+                Duration::from_millis(500),
+                Boundary::Dead,
+            ),
+        ];
+        for (test_case, key, round_duration, boundary) in test_cases {
+            game.handle_key(KeyEvent::key_down(key));
+            assert_eq!(
+                game.settings.round_duration, round_duration,
+                "{}",
+                test_case
+            );
+            assert_eq!(game.settings.boundary, boundary, "{}", test_case);
+        }
+        for key in ['s', 'd'] {
+            game.handle_key(KeyEvent::key_down(key));
+        }
+        assert!(game.state.latest_command == Command::CHANGEOPTION);
+        assert_eq!(game.settings.theme, Theme::Mono);
+        game.set_cells(vec![vec![false; 5]; 5]);
+        game.render();
+        assert_eq!(
+            buffer.lock().unwrap().get_background_colors()[1][1],
+            TC::DarkGrey,
+            "The board stays dimmed while the options are open"
+        );
+
+        game.handle_key(KeyEvent::key_down('\x1b'));
+        assert!(game.state.latest_command == Command::CLOSEOPTIONS);
+        assert!(game.state.pause_menu.is_some(), "Back to the pause menu");
+        #[cfg(feature = "serde")]
+        {
+            use crate::conway::options::GameOptions;
+
+            let saved = GameOptions::load(&path);
+            let _ = std::fs::remove_file(&path);
+            assert_eq!(saved, Ok(game.options()));
+        }
+
+        // The speed is applied to the running game
+        game.handle_key(KeyEvent::key_down('\x1b'));
+        game.set_cells(vec![vec![true; 5]; 5]);
+        clock.advance(Duration::from_millis(500));
+        game.tick();
+        assert_eq!(game.state.rounds, 1);
+        // A full grid dies out in the middle, while the corners only have three dead neighbors
+        assert_eq!(game.population(), 4);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn high_scores() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        let path = std::env::temp_dir().join(format!(
+            "conway_game_scores_test_{}.json",
+            std::process::id()
+        ));
+        game.settings.scores_path = path.clone();
+        game.state.rounds = 12;
+        let first = game.record_score();
+        game.state.rounds = 3;
+        let second = game.record_score();
+        for key in ['\x1b', 's', 's', 's', 's', '\r'] {
+            game.handle_key(KeyEvent::key_down(key));
+        }
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(first, Ok(true));
+        assert_eq!(second, Ok(false), "A shorter run is not a high score");
+        assert!(game.state.latest_command == Command::SHOWSCORES);
+        game.render();
+        let text =
+            String::from_utf8_lossy(&buffer.lock().unwrap().get_buffer_content()).to_string();
+        assert!(
+            text.contains("Longest Conway run   12 (seed 55)"),
+            "{}",
+            text
+        );
+
+        game.handle_key(KeyEvent::key_down('q'));
+        assert!(game.state.latest_command == Command::CLOSEPAGE);
+        assert!(game.state.pause_menu.is_some(), "Back to the pause menu");
+    }
+    #[test]
+    fn achievements() {
+        use crate::{
+            conway::patterns::{find_pattern, PatternPlacement},
+            scores::achievements::{Achievement, Achievements, GameEvent},
+            utils::clock::VirtualClock,
+        };
+
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(40, 12)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        let clock = VirtualClock::new();
+        game.set_clock(Box::new(clock.clone()));
+        let mut achievements = Achievements::default();
+        achievements.record(GameEvent::Generations(9_999));
+        game.set_achievements(achievements);
+        game.step();
+        assert!(game.achievements().is_unlocked(Achievement::Marathon));
+        game.place_pattern(&PatternPlacement {
+            pattern: find_pattern("gosper-glider-gun").unwrap(),
+            origin: None,
+        });
+        assert!(game.achievements().is_unlocked(Achievement::GliderGun));
+        game.render();
+        let text =
+            String::from_utf8_lossy(&buffer.lock().unwrap().get_buffer_content()).to_string();
+        assert!(
+            text.contains("Achievement unlocked: Glider factory"),
+            "{}",
+            text
+        );
+        assert!(text.contains("Achievement unlocked: Marathon"), "{}", text);
+
+        clock.advance(Duration::from_secs(3));
+        game.clear_screen();
+        game.render();
+        let text =
+            String::from_utf8_lossy(&buffer.lock().unwrap().get_buffer_content()).to_string();
+        assert!(!text.contains("Achievement"), "The toasts expired");
+
+        for key in ['\x1b', 'w', 'w', 'w', '\r'] {
+            game.handle_key(KeyEvent::key_down(key));
+        }
+        assert!(game.state.latest_command == Command::SHOWACHIEVEMENTS);
+        game.render();
+        let text =
+            String::from_utf8_lossy(&buffer.lock().unwrap().get_buffer_content()).to_string();
+        assert!(text.contains("[x] Marathon"), "{}", text);
+        assert!(text.contains("[ ] Undefeated"), "{}", text);
+    }
+    #[test]
+    fn dump_frame() {
+        use std::{env, fs, process};
+
+        use crate::shared::frame_dump::{frame_ansi, frame_text};
+
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(6, 4)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let dir = env::temp_dir().join(format!("conway_dump_test_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        game.settings.export_dir = dir.clone();
+
+        game.handle_key(KeyEvent::key_down('\x14'));
+        assert!(game.state.latest_command == Command::DUMPFRAME);
+        let text_path = dir.join("conway_55_0.txt");
+        let is_written_early = text_path.exists();
+        game.render();
+        let files = [text_path, dir.join("conway_55_0.ans")].map(fs::read_to_string);
+        let _ = fs::remove_dir_all(&dir);
+        assert!(!is_written_early, "The frame is written once it is drawn");
+        let frame = game.frame();
+        assert_eq!(files[0].as_deref().ok(), Some(frame_text(&frame).as_str()));
+        assert_eq!(files[1].as_deref().ok(), Some(frame_ansi(&frame).as_str()));
+
+        game.settings.export_dir = dir.join("missing");
+        game.handle_key(KeyEvent::key_down('\x14'));
+        game.render();
+        assert_eq!(game.state.latest_err, "Failed to dump the frame");
+    }
+    #[test]
+    fn save_seed() {
+        use std::{env, fs, process};
+
+        let mut game = ConwaysGame::headless(6, 4, 55);
+        let dir = env::temp_dir().join(format!("conway_seed_test_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        game.settings.export_dir = dir.clone();
+
+        game.handle_key(KeyEvent::key_down('u'));
+        assert!(game.state.latest_command == Command::SAVESEED);
+        game.settings.seed = 7;
+        game.handle_key(KeyEvent::key_down('u'));
+        let seeds = fs::read_to_string(dir.join("conway_seeds.txt"));
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(
+            seeds.ok().as_deref(),
+            Some("--seed 55 --x-len 6 --y-len 4\n--seed 7 --x-len 6 --y-len 4\n"),
+            "The seeds are added to the file"
+        );
+
+        game.settings.export_dir = dir.join("missing");
+        game.handle_key(KeyEvent::key_down('u'));
+        assert_eq!(game.state.latest_err, "Failed to save the seed");
+
+        // The inspector of the debug mode moves with i, j, k and l
+        game.state.print_mode = super::PrintMode::DEBUG;
+        game.settings.export_dir = dir.clone();
+        fs::create_dir_all(&dir).unwrap();
+        game.handle_key(KeyEvent::key_down('u'));
+        let seeds = fs::read_to_string(dir.join("conway_seeds.txt"));
+        let _ = fs::remove_dir_all(&dir);
+        assert!(game.state.latest_command == Command::SAVESEED);
+        assert_eq!(
+            seeds.ok().as_deref(),
+            Some("--seed 7 --x-len 6 --y-len 4\n"),
+            "The seed is saved in the debug mode"
+        );
+    }
+    #[cfg(feature = "export-image")]
+    #[test]
+    fn screenshot() {
+        use std::{env, fs, process};
+
+        use crate::export::{
+            image::{Image, ImageOptions, DEFAULT_BACKGROUND},
+            png::encode,
+        };
+
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(6, 4)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        let dir = env::temp_dir().join(format!("conway_screenshot_test_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        game.settings.export_dir = dir.clone();
+
+        let frame = game.frame();
+        let viewport = game.viewport();
+        let (width, height) = (
+            game.settings.cell_view_width,
+            game.settings.cell_view_height,
+        );
+        assert_eq!(frame.len(), 1 + viewport.size.y * (height + 1));
+        assert_eq!(frame[0].len(), 1 + viewport.size.x * (width + 1));
+        let (background, _) = game.cell_colors(0, 0, game.current[0][0]);
+        assert!(frame[1][1].background_color() == background);
+        assert!(frame[0][0].background_color() == TC::Default);
+
+        game.handle_key(KeyEvent::key_down('c'));
+        assert!(game.state.latest_command == Command::SCREENSHOT);
+        let path = dir.join("conway_55_0.png");
+        let written = fs::read(&path);
+        let _ = fs::remove_dir_all(&dir);
+        let image = Image::from_frame(&frame, &ImageOptions::default());
+        assert_eq!(written.ok(), Some(encode(&image)));
+        assert_eq!(image.pixel(0, 0), DEFAULT_BACKGROUND);
+
+        game.settings.export_dir = dir.join("missing");
+        game.handle_key(KeyEvent::key_down('c'));
+        assert_eq!(game.state.latest_err, "Failed to save the screenshot");
+    }
+    #[test]
+    fn scripted_input() {
+        use crate::console::input_source::ScriptedInput;
+
+        let mut game = ConwaysGame::builder(6, 4)
+            .seed(55)
+            .input(Box::new(ScriptedInput::from_keys(" mq")))
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.start();
+        let test_cases = [
+            ("pause", true, Command::PAUSEPLAY),
+            ("print mode", true, Command::TOGGLEMODE),
+            ("quit", false, Command::QUIT),
+        ];
+        for (test_case, is_running, command) in test_cases {
+            assert_eq!(game.tick(), is_running, "{}", test_case);
+            assert!(game.state.latest_command == command, "{}", test_case);
+        }
+    }
+    #[test]
+    fn drains_held_keys() {
+        let (sender, receiver) = mpsc::channel();
+        let mut game = ConwaysGame::builder(6, 4)
+            .seed(55)
+            .receiver(receiver)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let test_cases = [
+            ("held key", "dddd", true, (4, 0), 1),
+            ("mixed", "ddsa", true, (5, 1), 4),
+            ("quit", "dqd", false, (6, 1), 6),
+        ];
+        for (test_case, keys, is_running, expected, commands) in test_cases {
+            for key in keys.chars() {
+                sender.send(KeyEvent::key_down(key)).unwrap();
+            }
+            assert_eq!(game.tick(), is_running, "{}", test_case);
+            let origin = game.settings.origin;
+            assert_eq!((origin.x, origin.y), expected, "{}", test_case);
+            assert_eq!(game.state.command_count, commands, "{}", test_case);
+        }
+    }
+    #[test]
+    fn undoes_commands() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(6, 4)
+            .seed(55)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.handle_keys(vec![KeyEvent::key_down('d'); 3]);
+        game.handle_key(KeyEvent::key_down(' '));
+        game.handle_key(KeyEvent::key_down('z'));
+        let is_paused = game.state.is_paused;
+        let test_cases = [
+            ("the pause", !is_paused, 3),
+            ("the held key", !is_paused, 0),
+            ("nothing left", !is_paused, 0),
+        ];
+        for (test_case, expected_paused, expected_x) in test_cases {
+            game.handle_key(KeyEvent::key_down('\x1a'));
+            assert!(game.state.latest_command == Command::UNDO, "{}", test_case);
+            assert_eq!(game.state.is_paused, expected_paused, "{}", test_case);
+            assert_eq!(game.settings.origin.x, expected_x, "{}", test_case);
+        }
+        assert_eq!(
+            game.input_log.lines()[2],
+            "undo   down x1   Move the board left"
+        );
+    }
+    #[test]
+    fn time_travel() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let mut boards = vec![game.current.clone()];
+        for _ in 0..3 {
+            game.step();
+            boards.push(game.current.clone());
+        }
+
+        game.handle_key(KeyEvent::key_down('t'));
+        assert!(game.state.latest_command == Command::TIMETRAVEL);
+        assert!(game.state.is_paused, "The time travel pauses the game");
+        let test_cases = [
+            ("back", 'a', 2),
+            ("back again", 'a', 1),
+            ("forward", 'd', 2),
+            ("stops at the latest", 'd', 3),
+            ("scrub back", 'a', 2),
+        ];
+        for (test_case, key, round) in test_cases {
+            game.handle_key(KeyEvent::key_down(key));
+            assert!(game.state.latest_command == Command::SCRUB, "{}", test_case);
+            assert_eq!(game.current, boards[round], "{}", test_case);
+            assert_eq!(game.previous, boards[round - 1], "{}", test_case);
+        }
+
+        game.handle_key(KeyEvent::key_down('\x1b'));
+        assert!(game.state.latest_command == Command::CLOSETIMETRAVEL);
+        assert_eq!(game.current, boards[3], "Escape returns to the live board");
+        assert_eq!(game.state.rounds, 3);
+
+        game.handle_key(KeyEvent::key_down('t'));
+        game.handle_key(KeyEvent::key_down('a'));
+        game.handle_key(KeyEvent::key_down('\r'));
+        assert!(game.state.time_travel.is_none());
+        assert!(game.state.is_paused);
+        assert_eq!(
+            game.current, boards[2],
+            "Enter continues from the shown board"
+        );
+        assert_eq!(game.state.rounds, 2);
+        assert_eq!(game.history.len(), 3);
+    }
+    #[test]
+    fn editor_clipboard() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(10, 10)
+            .seed(55)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let path =
+            std::env::temp_dir().join(format!("conways_clipboard_{}.rle", std::process::id()));
+        game.settings.clipboard_path = path.clone();
+        let save_path =
+            std::env::temp_dir().join(format!("conways_editor_save_{}.json", std::process::id()));
+        game.settings.save_path = save_path.clone();
+        game.set_cells(vec![vec![false; 10]; 10]);
+        game.place_pattern(&"glider@0,0".parse().unwrap());
+        let test_cases = [
+            ("select", 'e', Command::OPENEDITOR, 5),
+            ("anchor", 'm', Command::SELECT, 5),
+            ("grow", 'k', Command::MOVECURSOR, 5),
+            ("grow more", 'k', Command::MOVECURSOR, 5),
+            ("to the right", 'l', Command::MOVECURSOR, 5),
+            ("to the right again", 'l', Command::MOVECURSOR, 5),
+            ("cut", '\x18', Command::CUT, 0),
+            ("move away", 'l', Command::MOVECURSOR, 0),
+            ("paste", '\x16', Command::PASTE, 5),
+            ("paste again", 'j', Command::MOVECURSOR, 5),
+            ("overlapping paste", '\x16', Command::PASTE, 7),
+            ("undo the paste", '\x1a', Command::UNDO, 5),
+            ("select again", 'm', Command::SELECT, 5),
+            ("select right", 'l', Command::MOVECURSOR, 5),
+            ("select right again", 'l', Command::MOVECURSOR, 5),
+            ("select to the glider", 'l', Command::MOVECURSOR, 5),
+            ("select down", 'k', Command::MOVECURSOR, 5),
+            ("select down again", 'k', Command::MOVECURSOR, 5),
+            ("clear", 'x', Command::CLEARSELECTION, 0),
+            ("export", '\x05', Command::EXPORTCLIPBOARD, 0),
+            ("the save chord still saves", '\x13', Command::SAVE, 0),
+        ];
+        for (test_case, key, command, population) in test_cases {
+            game.handle_key(KeyEvent::key_down(key));
+            assert!(game.state.latest_command == command, "{}", test_case);
+            assert_eq!(game.population(), population, "{}", test_case);
+        }
+        let rle = std::fs::read_to_string(&path);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&save_path);
+        assert_eq!(rle.unwrap(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+
+        let scene = game.clipboard().clone();
+        assert_eq!(
+            scene.get().map(|clipboard| clipboard.height()),
+            Some(3),
+            "The cut region is shared"
+        );
+        let blinker = super::Clipboard::from_text("OOO").unwrap();
+        scene.set(blinker, &game.settings.rule).unwrap();
+        game.handle_key(KeyEvent::key_down('\x16'));
+        assert_eq!(game.population(), 3, "Paste what a scene copied");
+    }
+    #[test]
+    fn checkpoints() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(6, 6)
+            .seed(55)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.set_cells(vec![vec![false; 6]; 6]);
+        game.place_pattern(&"glider@0,0".parse().unwrap());
+        let glider = game.cells().clone();
+        game.handle_key(KeyEvent::key_down('!'));
+        assert!(game.state.latest_command == Command::SAVECHECKPOINT);
+        game.fast_forward(4);
+        game.handle_key(KeyEvent::key_down('+'));
+        game.handle_key(KeyEvent::key_down('@'));
+        let moved = game.cells().clone();
+
+        let test_cases = [
+            ("first slot", '1', 0, &glider),
+            ("second slot", '2', 4, &moved),
+            ("first slot again", '1', 0, &glider),
+        ];
+        for (test_case, key, rounds, cells) in test_cases {
+            game.handle_key(KeyEvent::key_down(key));
+            assert!(
+                game.state.latest_command == Command::LOADCHECKPOINT,
+                "{}",
+                test_case
+            );
+            assert_eq!(game.state.rounds, rounds, "{}", test_case);
+            assert_eq!(game.cells(), cells, "{}", test_case);
+            assert_eq!(
+                (game.settings.x_len, game.settings.y_len),
+                (cells[0].len(), cells.len()),
+                "{}",
+                test_case
+            );
+        }
+
+        game.handle_key(KeyEvent::key_down('5'));
+        assert_eq!(game.state.latest_err, "Checkpoint 5 is empty");
+        assert_eq!(game.cells(), &glider, "An empty slot keeps the game");
+    }
+    #[test]
+    fn resize() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 4)
+            .seed(55)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let mut cells = vec![vec![false; 5]; 4];
+        cells[0][0] = true;
+        cells[1][2] = true;
+        cells[3][4] = true;
+        game.set_cells(cells);
+        let test_cases = [
+            ("grow", '+', Command::GROWGRID, (6, 5), 3),
+            ("shrink", '-', Command::SHRINKGRID, (5, 4), 3),
+            ("drop a live cell", '-', Command::SHRINKGRID, (4, 3), 2),
+        ];
+        for (test_case, key, command, (x_len, y_len), population) in test_cases {
+            game.handle_key(KeyEvent::key_down(key));
+            assert!(game.state.latest_command == command, "{}", test_case);
+            assert_eq!(
+                (game.settings.x_len, game.settings.y_len),
+                (x_len, y_len),
+                "{}",
+                test_case
+            );
+            assert_eq!(
+                (game.cells()[0].len(), game.cells().len()),
+                (x_len, y_len),
+                "{}",
+                test_case
+            );
+            assert_eq!(game.population(), population, "{}", test_case);
+            assert!(game.cells()[0][0] && game.cells()[1][2], "{}", test_case);
+            assert_eq!(game.history.len(), 1, "{}", test_case);
+        }
+        assert_eq!(game.resize(0, 3), Err(super::ConwayError::EmptyBoard));
+        assert_eq!(game.resize(1, 1), Ok(()));
+        game.handle_key(KeyEvent::key_down('-'));
+        assert_eq!((game.settings.x_len, game.settings.y_len), (1, 1));
+        game.next();
+    }
+    #[test]
+    fn editor_transform() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(6, 4)
+            .seed(55)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.set_cells(vec![vec![false; 6]; 4]);
+        game.place_pattern(&"glider@0,0".parse().unwrap());
+        let glider = game.cells().clone();
+        let mirrored = BoardTransform::MirrorHorizontally.apply(&glider);
+        let rotated = BoardTransform::RotateClockwise.apply(&glider);
+        let test_cases = [
+            ("open", 'e', Command::OPENEDITOR, (6, 4), (0, 0), &glider),
+            (
+                "mirror",
+                'h',
+                Command::MIRRORHORIZONTALLY,
+                (6, 4),
+                (5, 0),
+                &mirrored,
+            ),
+            ("undo", '\x1a', Command::UNDO, (6, 4), (5, 0), &glider),
+            (
+                "rotate",
+                't',
+                Command::ROTATEBOARD,
+                (4, 6),
+                (3, 5),
+                &rotated,
+            ),
+            (
+                "nothing to undo",
+                '\x1a',
+                Command::UNDO,
+                (4, 6),
+                (3, 5),
+                &rotated,
+            ),
+        ];
+        for (test_case, key, command, (x_len, y_len), (x, y), cells) in test_cases {
+            game.handle_key(KeyEvent::key_down(key));
+            assert!(game.state.latest_command == command, "{}", test_case);
+            assert_eq!(
+                (game.settings.x_len, game.settings.y_len),
+                (x_len, y_len),
+                "{}",
+                test_case
+            );
+            let cursor = game.state.editor.as_ref().unwrap().cursor;
+            assert_eq!((cursor.x, cursor.y), (x, y), "{}", test_case);
+            assert_eq!(game.cells(), cells, "{}", test_case);
+        }
+        game.next();
+        assert_eq!(
+            (game.cells()[0].len(), game.cells().len()),
+            (4, 6),
+            "The next generation has the rotated size"
+        );
+    }
+    #[test]
+    fn mouse_painting() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(6, 6)
+            .seed(55)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        game.set_cells(vec![vec![false; 6]; 6]);
+        // A cell is 3 by 2 characters with a gap after it, the board starts with a gap
+        let test_cases = [
+            ("first cell", (1, 1), Some((0, 0))),
+            ("end of the first cell", (3, 2), Some((0, 0))),
+            ("gap", (4, 1), None),
+            ("second row", (5, 4), Some((1, 1))),
+            ("before the board", (0, 1), None),
+            ("after the board", (25, 1), None),
+        ];
+        for (test_case, (x, y), expected) in test_cases {
+            let cell = game.cell_at(Coord::new(x, y)).map(|cell| (cell.x, cell.y));
+            assert_eq!(cell, expected, "{}", test_case);
+        }
+
+        let test_cases = [
+            (
+                "press",
+                MouseEvent::press(Coord::new(1, 1)),
+                Command::TOGGLECELL,
+                1,
+            ),
+            (
+                "drag",
+                MouseEvent::drag(Coord::new(13, 1)),
+                Command::PAINTCELLS,
+                4,
+            ),
+            (
+                "drag back",
+                MouseEvent::drag(Coord::new(5, 2)),
+                Command::PAINTCELLS,
+                4,
+            ),
+            (
+                "release",
+                MouseEvent::release(Coord::new(5, 2)),
+                Command::PAINTCELLS,
+                4,
+            ),
+            (
+                "move",
+                MouseEvent::release(Coord::new(9, 4)),
+                Command::PAINTCELLS,
+                4,
+            ),
+            (
+                "click a live cell",
+                MouseEvent::press(Coord::new(9, 1)),
+                Command::TOGGLECELL,
+                3,
+            ),
+            (
+                "release again",
+                MouseEvent::release(Coord::new(9, 1)),
+                Command::TOGGLECELL,
+                3,
+            ),
+        ];
+        for (test_case, event, command, population) in test_cases {
+            game.handle_mouse(event);
+            assert!(game.state.latest_command == command, "{}", test_case);
+            assert_eq!(game.population(), population, "{}", test_case);
+        }
+
+        game.handle_key(KeyEvent::key_down('e'));
+        game.handle_mouse(MouseEvent::press(Coord::new(9, 7)));
+        game.handle_mouse(MouseEvent::drag(Coord::new(17, 7)));
+        game.handle_mouse(MouseEvent::release(Coord::new(17, 7)));
+        assert_eq!(game.population(), 6);
+        let cursor = game.state.editor.as_ref().unwrap().cursor;
+        assert_eq!((cursor.x, cursor.y), (4, 2), "The cursor follows the mouse");
+        game.handle_key(KeyEvent::key_down('\x1a'));
+        assert_eq!(game.population(), 3, "The stroke is undone as a whole");
+
+        game.handle_key(KeyEvent::key_down('?'));
+        game.handle_mouse(MouseEvent::press(Coord::new(1, 1)));
+        assert_eq!(game.population(), 3, "The help covers the board");
+    }
+    #[test]
+    fn auto_pause() {
+        let (_sen, rec) = mpsc::channel();
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .auto_pause(true)
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        let mut blinker = vec![vec![false; 8]; 8];
+        blinker[3][2..5].fill(true);
+        game.set_cells(blinker);
+        let test_cases = [
+            ("new shape", false),
+            ("back to the start", true),
+            ("paused", true),
+        ];
+        for (test_case, is_paused) in test_cases {
+            game.step();
+            assert_eq!(game.state.is_paused, is_paused, "{}", test_case);
+        }
+        assert_eq!(game.state.rounds, 2);
+        game.render();
+        let content = buffer.lock().unwrap().get_buffer_content();
+        assert!(
+            String::from_utf8_lossy(&content).contains("Repeating: oscillator with period 2"),
+            "The banner shows the period"
+        );
+
+        game.handle_key(KeyEvent::key_down(' '));
+        game.step();
+        game.step();
+        assert!(
+            !game.state.is_paused,
+            "The same oscillator does not pause the game again"
+        );
+
+        game.set_cells(vec![vec![false; 8]; 8]);
+        game.step();
+        assert!(game.state.is_paused, "An empty board is stable");
+        assert!(game.tick(), "A stable board does not stop the game");
+
+        let seed = game.settings.seed;
+        game.handle_key(KeyEvent::key_down('b'));
+        assert!(game.state.latest_command == Command::NONE);
+        assert_eq!(game.settings.seed, seed + 1);
+        assert!(!game.state.is_paused, "Reseeding starts a new board");
+        assert!(game.state.detected_cycle.is_none());
+    }
+    #[test]
+    fn inspector() {
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(5, 5)
+            .seed(55)
+            .render_mode(super::PrintMode::DEBUG)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        assert_eq!(
+            game.inspect(),
+            "Cell 2:2 dead, 4 neighbors, unchanged since generation 0"
+        );
+        let test_cases = [
+            ("left", 'j', "Cell 1:2 alive, 3 neighbors"),
+            ("up", 'i', "Cell 1:1 dead, 5 neighbors"),
+            ("left again", 'j', "Cell 0:1 alive, 5 neighbors"),
+            ("wraps around", 'j', "Cell 4:1 alive, 4 neighbors"),
+        ];
+        for (test_case, key, expected) in test_cases {
+            game.handle_key(KeyEvent::key_down(key));
+            assert!(
+                game.state.latest_command == Command::MOVECURSOR,
+                "{}",
+                test_case
+            );
+            assert!(
+                game.inspect().starts_with(expected),
+                "{}: {}",
+                test_case,
+                game.inspect()
+            );
+        }
+
+        let before = game.current.clone();
+        game.step();
+        let changed = (0..5)
+            .flat_map(|y| (0..5).map(move |x| (x, y)))
+            .find(|(x, y)| before[*y][*x] != game.current[*y][*x])
+            .unwrap();
+        game.state.inspector = Coord::new(changed.0, changed.1);
+        assert!(game.inspect().ends_with("changed in generation 1"));
+
+        game.handle_key(KeyEvent::key_down('m'));
+        game.handle_key(KeyEvent::key_down('i'));
+        assert!(
+            game.state.latest_command != Command::MOVECURSOR,
+            "The inspector only moves in debug mode"
+        );
+    }
+    #[test]
+    fn builder() {
+        use crate::conway::{errors::ConwayError, rule::Rule, settings::Boundary};
+
+        let (_sen, rec) = mpsc::channel();
+        let highlife: Rule = "B36/S23".parse().unwrap();
+        let game = ConwaysGame::builder(7, 5)
+            .seed(12)
+            .rule(highlife)
+            .boundary(Boundary::Dead)
+            .speed(Duration::from_millis(250))
+            .render_mode(super::PrintMode::DEBUG)
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        assert_eq!((game.settings.x_len, game.settings.y_len), (7, 5));
+        assert_eq!(game.settings.seed, 12);
+        assert!(game.settings.rule == highlife);
+        assert!(game.settings.boundary == Boundary::Dead);
+        assert_eq!(game.settings.round_duration, Duration::from_millis(250));
+        assert!(game.state.print_mode == super::PrintMode::DEBUG);
+
+        let (_sen, rec) = mpsc::channel();
+        #[allow(deprecated)]
+        let positional = ConwaysGame::init(
+            7,
+            5,
+            12,
+            super::PrintMode::DEBUG,
+            Duration::from_millis(250),
+            rec,
+            memory_handle(),
+        );
+        assert_eq!(positional.cells(), game.cells(), "The seed picks the board");
+
+        let test_cases = [
+            ("no columns", 0, 5, true, true, ConwayError::EmptyBoard),
+            ("no rows", 7, 0, true, true, ConwayError::EmptyBoard),
+            ("no receiver", 7, 5, false, true, ConwayError::MissingInput),
+            ("no handle", 7, 5, true, false, ConwayError::MissingHandle),
+        ];
+        for (test_case, x_len, y_len, has_receiver, has_handle, expected) in test_cases {
+            let mut builder = ConwaysGame::builder(x_len, y_len);
+            if has_receiver {
+                builder = builder.receiver(mpsc::channel().1);
+            }
+            if has_handle {
+                builder = builder.handle(memory_handle());
+            }
+            assert_eq!(builder.build().err(), Some(expected), "{}", test_case);
+        }
+    }
+    #[cfg(feature = "export-image")]
+    #[test]
+    fn svg_export() {
+        use std::{env, fs, process};
+
+        let (_sen, rec) = mpsc::channel();
+        let game = ConwaysGame::builder(6, 4)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let path = env::temp_dir().join(format!("conway_svg_test_{}.svg", process::id()));
+        assert!(game.export_svg(&path).is_ok());
+        let svg = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+        let svg = svg.unwrap();
+        assert!(svg.starts_with("<svg "), "{}", svg);
+        // A rectangle per cell, the gaps between the cells keep them apart
+        assert_eq!(svg.matches("<rect ").count(), 1 + 6 * 4 * 2, "{}", svg);
+    }
+    #[cfg(feature = "export-image")]
+    #[test]
+    fn recording() {
+        use std::{env, fs, process};
+
+        use crate::export::gif::GifRecorder;
+
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(6, 4)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let dir = env::temp_dir().join(format!("conway_recording_test_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        game.settings.export_dir = dir.clone();
+        game.settings.gif_generations = 3;
+
+        let mut expected =
+            GifRecorder::new(3, game.settings.gif_options, game.settings.gif_frame_delay);
+        game.handle_key(KeyEvent::key_down('v'));
+        assert!(game.state.latest_command == Command::RECORD);
+        for _ in 0..4 {
+            expected.push(&game.frame());
+            game.step();
+        }
+        assert!(game
+            .recorder
+            .as_ref()
+            .is_some_and(|recorder| recorder.is_full()));
+        game.handle_key(KeyEvent::key_down('v'));
+        while !game.finish_gif_export() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        let written = fs::read(dir.join("conway_55_4.gif"));
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(game.recorder.is_none(), "Saving ends the recording");
+        assert_eq!(written.ok(), Some(expected.encode()));
+    }
+    #[test]
+    fn fast_forward() {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        game.set_cells(vec![vec![false; 8]; 8]);
+        game.place_pattern(&"glider@2,2".parse().unwrap());
+        let start = game.cells().clone();
+
+        // A glider crosses the 8x8 grid and is back where it started after 32 generations
+        game.set_skip_generations(32);
+        game.handle_key(KeyEvent::key_down('g'));
+        assert!(game.state.latest_command == Command::FASTFORWARD);
+        assert_eq!(game.state.rounds, 32);
+        assert_eq!(game.cells(), &start);
+        let content = buffer.lock().unwrap().get_buffer_content();
+        assert!(
+            !String::from_utf8_lossy(&content).contains("Fast forward"),
+            "The progress should be cleared once the fast forward is done"
+        );
+
+        let mut block = vec![vec![false; 8]; 8];
+        for (x, y) in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            block[y][x] = true;
+        }
+        game.set_cells(block);
+        game.fast_forward(100);
+        assert_eq!(
+            game.state.rounds, 33,
+            "A stable board stops the fast forward"
+        );
+    }
+    #[test]
+    fn built_with_pattern() {
+        let (_sen, rec) = mpsc::channel();
+        let game = ConwaysGame::builder(16, 16)
+            .seed(55)
+            .pattern("glider@14,2".parse().unwrap())
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        for (x, y) in [(15, 2), (0, 3), (14, 4), (15, 4), (0, 4)] {
+            assert!(game.cells()[y][x], "({}, {}) wraps around the edge", x, y);
+        }
+    }
+    #[test]
+    fn next_after_edits() {
+        let game = || {
+            let (_sen, rec) = mpsc::channel();
+            ConwaysGame::builder(16, 12)
+                .seed(21)
+                .receiver(rec)
+                .handle(memory_handle())
+                .build()
+                .unwrap()
+        };
+        let (mut counted, mut scanned) = (game(), game());
+        for round in 0..40 {
+            match round {
+                10 => {
+                    counted.place_pattern(&"glider@3,3".parse().unwrap());
+                    scanned.place_pattern(&"glider@3,3".parse().unwrap());
+                }
+                20 => {
+                    counted.handle_key(KeyEvent::key_down('e'));
+                    counted.handle_key(KeyEvent::key_down('x'));
+                    assert_ne!(
+                        counted.cells(),
+                        scanned.cells(),
+                        "The editor toggled a cell"
+                    );
+                    scanned.set_cells(counted.cells().clone());
+                }
+                30 => {
+                    counted.set_rule("B36/S23".parse().unwrap());
+                    scanned.set_rule("B36/S23".parse().unwrap());
+                }
+                _ => (),
+            }
+            counted.next();
+            scanned.next_full_scan();
+            assert_eq!(counted.cells(), scanned.cells(), "round {}", round);
+            let mut changed = counted.changed_cells().to_vec();
+            changed.sort_by_key(|(x, y)| (*y, *x));
+            assert_eq!(changed, scanned.changed_cells(), "round {}", round);
+        }
+    }
+    #[test]
+    fn built_with_clipboard() {
+        let clipboard = super::SharedClipboard::default();
+        let (_sen, rec) = mpsc::channel();
+        let game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .clipboard(clipboard.clone())
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let blinker = super::Clipboard::from_text("OOO").unwrap();
+        clipboard.set(blinker.clone(), &game.settings.rule).unwrap();
+        assert_eq!(
+            game.clipboard().get(),
+            Some(blinker),
+            "A region copied in a scene is pasted in the game"
+        );
+    }
+    #[test]
+    fn skipped_start() {
+        let game = |skip: u64| {
+            let (_sen, rec) = mpsc::channel();
+            ConwaysGame::builder(16, 16)
+                .seed(55)
+                .skip(skip)
+                .receiver(rec)
+                .handle(memory_handle())
+                .build()
+                .unwrap()
+        };
+        let mut stepped = game(0);
+        stepped.start();
+        for _ in 0..10 {
+            stepped.next();
+        }
+        let mut skipped = game(10);
+        assert_eq!(
+            skipped.state.rounds, 0,
+            "Nothing is skipped before the start"
+        );
+        skipped.start();
+        assert_eq!(skipped.state.rounds, 10);
+        assert_eq!(skipped.cells(), stepped.cells());
+        assert_eq!(
+            skipped.settings.skip_generations, 10,
+            "The fast forward key skips as many generations"
+        );
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn autosave_and_restore() {
+        use crate::{conway::autosave::Autosave, utils::clock::VirtualClock};
+
+        let path = std::env::temp_dir().join(format!(
+            "conway_game_autosave_test_{}.json",
+            std::process::id()
+        ));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let clock = VirtualClock::new();
+        game.set_clock(Box::new(clock.clone()));
+        game.set_autosave(Some(Autosave::new(path.clone())));
+        game.settings.autosave_interval = Duration::from_secs(5);
+        game.set_cells(vec![vec![false; 8]; 8]);
+        game.place_pattern(&"glider@2,2".parse().unwrap());
+        game.start();
+        for _ in 0..5 {
+            clock.advance(Duration::from_secs(1));
+            game.tick();
+        }
+        let recovered = Autosave::new(path.clone()).recover();
+        game.finish();
+        assert!(!path.exists(), "A clean finish removes the autosave");
+        let recovered = recovered.expect("The game should be autosaved every 5 seconds");
+        assert_eq!(recovered.rounds, 5);
+        assert_eq!(recovered, game.save());
+
+        let (_sen, rec) = mpsc::channel();
+        let mut resumed = ConwaysGame::builder(3, 3)
+            .seed(1)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .resume(recovered.clone())
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        assert_eq!(resumed.cells(), game.cells());
+        assert_eq!(resumed.state.rounds, 5);
+        assert_eq!(resumed.settings.seed, 55);
+        assert!(!resumed.is_stable(), "A resumed game should keep running");
+        game.next();
+        resumed.next();
+        assert_eq!(resumed.cells(), game.cells());
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn timeline_export() {
+        use std::fs;
+
+        use crate::{conway::divergence::board_hash, utils::clock::VirtualClock};
+
+        let path = std::env::temp_dir().join(format!(
+            "conway_game_timeline_test_{}.json",
+            std::process::id()
+        ));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let clock = VirtualClock::new();
+        game.set_clock(Box::new(clock.clone()));
+        game.settings.timeline_checkpoint_interval = 2;
+        game.set_cells(vec![vec![false; 8]; 8]);
+        game.place_pattern(&"glider@2,2".parse().unwrap());
+        game.start();
+        for _ in 0..3 {
+            clock.advance(Duration::from_secs(1));
+            game.tick();
+        }
+        game.handle_key(KeyEvent::key_down(' '));
+        let written = game.write_timeline(&path);
+        let json = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(written, Ok(()));
+        let json: serde_json::Value = serde_json::from_str(&json.unwrap()).unwrap();
+        let events = json["events"].as_array().unwrap();
+        let summary: Vec<String> = events
+            .iter()
+            .map(|event| match event["type"].as_str() {
+                Some("checkpoint") => format!("{} {}", event["generation"], event["at_ms"]),
+                _ => format!("{} {}", event["input"], event["at_ms"]),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            ["0 0", "2 2000", "\"space\" 3000", "3 3000"],
+            "The start, every second generation, the commands and the end"
+        );
+        assert_eq!(
+            events[3]["hash"],
+            format!("{:016x}", board_hash(&game.current)),
+            "The last checkpoint is the current board"
+        );
+        assert_eq!(json["seed"], 55);
+        assert_eq!(json["generations"], 3);
+        assert_eq!(json["commands"], 1);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn verify_session() {
+        use crate::{
+            conway::{
+                divergence::{Divergence, VerifyError},
+                timeline::{RecordedSession, TimelineEvent},
+            },
+            utils::clock::VirtualClock,
+        };
+
+        let path = std::env::temp_dir().join(format!(
+            "conway_game_verify_test_{}.json",
+            std::process::id()
+        ));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let clock = VirtualClock::new();
+        game.set_clock(Box::new(clock.clone()));
+        game.settings.timeline_checkpoint_interval = 3;
+        game.settings.skip_generations = 7;
+        game.settings.boundary = crate::conway::settings::Boundary::Dead;
+        game.start();
+        let keys = [' ', ' ', 'g', 'x'];
+        for key in keys {
+            clock.advance(Duration::from_secs(1));
+            game.tick();
+            game.handle_key(KeyEvent::key_down(key));
+        }
+        clock.advance(Duration::from_secs(2));
+        game.tick();
+        game.write_timeline(&path).unwrap();
+        let session = RecordedSession::load(&path);
+        let _ = std::fs::remove_file(&path);
+        let mut session = session.unwrap();
+        let checkpoints = session
+            .events
+            .iter()
+            .filter(|event| matches!(event, TimelineEvent::Checkpoint { .. }))
+            .count();
+        assert!(checkpoints > 3, "The fast forward adds checkpoints");
+        assert_eq!(ConwaysGame::verify_session(&session), Ok(checkpoints));
+
+        let last = session.events.len() - 1;
+        if let TimelineEvent::Checkpoint { hash, .. } = &mut session.events[last] {
+            *hash = "00000000000000ab".to_string();
+        }
+        assert_eq!(
+            ConwaysGame::verify_session(&session),
+            Err(VerifyError::Diverged(Divergence {
+                generation: game.state.rounds,
+                reached: game.state.rounds,
+                expected: 0xab,
+                actual: Some(game.state_hash()),
+            }))
+        );
+        session.start = None;
+        assert_eq!(
+            ConwaysGame::verify_session(&session),
+            Err(VerifyError::NoStart)
+        );
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ghost_is_drawn_behind_the_board() {
+        let path = std::env::temp_dir().join(format!(
+            "conway_game_ghost_test_{}.json",
+            std::process::id()
+        ));
+        let mut recorded = ConwaysGame::headless(8, 8, 55);
+        recorded.start();
+        recorded.fast_forward(3);
+        recorded.write_timeline(&path).unwrap();
+
+        let mut game = ConwaysGame::headless(8, 8, 7);
+        let loaded = game.load_file(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(loaded, Ok(()));
+        assert_eq!(
+            game.state.rounds, 0,
+            "The run starts over from its first board"
+        );
+        game.print(super::PrintMode::PRETTY);
+        let alive = game.current.clone();
+        let dead_colors = game.settings.theme.colors(false);
+        for (y, row) in alive.iter().enumerate() {
+            for (x, is_alive) in row.iter().enumerate() {
+                let expected = match is_alive {
+                    true => (super::GHOST_COLOR, dead_colors.1),
+                    false => dead_colors,
+                };
+                assert_eq!(game.cell_colors(x, y, false), expected, "({}, {})", x, y);
+            }
+        }
+
+        game.set_ghost(None);
+        let (y, x) = alive
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| Some((y, row.iter().position(|is_alive| *is_alive)?)))
+            .unwrap();
+        assert_eq!(game.cell_colors(x, y, false), dead_colors);
+    }
+    #[cfg(feature = "net")]
+    #[test]
+    fn steps_are_broadcast_to_spectators() {
+        use crate::net::spectator::{SpectatorClient, SpectatorServer};
+
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        game.set_spectators(Some(server));
+        let spectator = std::thread::spawn(move || {
+            let mut client = SpectatorClient::connect(address).unwrap();
+            client.receive()
+        });
+        while !spectator.is_finished() {
+            game.step();
+            std::thread::yield_now();
+        }
+        let board = spectator.join().unwrap().expect("A board should be sent");
+        assert!(board.rounds > 0);
+        assert_eq!(board.seed, 55);
+    }
+    #[test]
+    fn session_summary() {
+        use crate::utils::clock::VirtualClock;
+
+        let (sender, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_secs(1))
+            .receiver(rec)
+            .handle(memory_handle())
+            .build()
+            .unwrap();
+        let clock = VirtualClock::new();
+        game.set_clock(Box::new(clock.clone()));
+        // A glider, the population is 5 for every generation
+        game.set_cells(vec![vec![false; 8]; 8]);
+        game.place_pattern(&"glider@2,2".parse().unwrap());
+        game.start();
+        for key in ['m', 'm', 'x'] {
+            sender.send(KeyEvent::key_down(key)).unwrap();
+        }
+        for _ in 0..4 {
+            clock.advance(Duration::from_secs(1));
+            game.tick();
+        }
+        game.fast_forward(4);
+        game.finish();
+
+        let summary = game.summary();
+        assert_eq!(summary.seed, 55);
+        assert_eq!(summary.generations, 8);
+        assert_eq!(summary.elapsed, Duration::from_secs(4));
+        assert_eq!(summary.generations_per_second(), 2.0);
+        assert_eq!(summary.peak_population, 5);
+        assert_eq!(summary.commands, 3);
+    }
+    #[test]
+    fn catches_up_when_behind() {
+        use crate::utils::{clock::VirtualClock, metrics::RENDER_TIME};
+
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_sen, rec) = mpsc::channel();
+        let mut game = ConwaysGame::builder(8, 8)
+            .seed(55)
+            .render_mode(super::PrintMode::PRETTY)
+            .speed(Duration::from_millis(100))
+            .receiver(rec)
+            .handle(Box::new(SharedHandle::init(buffer.clone())))
+            .build()
+            .unwrap();
+        let clock = VirtualClock::new();
+        game.set_clock(Box::new(clock.clone()));
+        game.start();
+        let status = |buffer: &Arc<Mutex<MemoryHandle>>| {
+            String::from_utf8_lossy(&buffer.lock().unwrap().get_buffer_content())
+                .contains("Running behind real-time")
+        };
+        // Eight rounds are due, a tick steps four of them and says it is behind
+        let test_cases = [
+            ("behind", 800, 4, 1, true),
+            ("render skipped", 100, 8, 1, true),
+            ("caught up", 100, 10, 2, false),
+        ];
+        for (test_case, millis, rounds, renders, is_behind) in test_cases {
+            clock.advance(Duration::from_millis(millis));
+            game.tick();
+            assert_eq!(game.state.rounds, rounds, "{}", test_case);
+            assert_eq!(
+                game.metrics.samples(RENDER_TIME).len(),
+                renders,
+                "{}",
+                test_case
+            );
+            assert_eq!(status(&buffer), is_behind, "{}", test_case);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 pub fn conways_law(is_alive: bool, live_siblings: u8) -> bool {
     assert!(live_siblings < 9);
     if is_alive {
-        if live_siblings < 2 || live_siblings > 3 {
+        if !(2..=3).contains(&live_siblings) {
             return false;
         }
         return true;
@@ -9,7 +9,7 @@ pub fn conways_law(is_alive: bool, live_siblings: u8) -> bool {
     if live_siblings == 3 {
         return true;
     }
-    return false;
+    false
 }
 
 #[cfg(test)]
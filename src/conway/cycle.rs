@@ -0,0 +1,221 @@
+use std::{collections::VecDeque, fmt::Display};
+
+/// A repeating sequence of generations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of generations before the shape repeats, 1 for a still life
+    pub period: usize,
+    /// How far the shape moved in one period, `(0, 0)` for oscillators and still lifes
+    pub displacement: (i64, i64),
+}
+impl Cycle {
+    pub fn is_still(&self) -> bool {
+        self.period == 1 && self.displacement == (0, 0)
+    }
+    pub fn is_moving(&self) -> bool {
+        self.displacement != (0, 0)
+    }
+}
+impl Display for Cycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_still() {
+            write!(f, "still life")
+        } else if self.is_moving() {
+            write!(
+                f,
+                "spaceship with period {} moving {}:{}",
+                self.period, self.displacement.0, self.displacement.1
+            )
+        } else {
+            write!(f, "oscillator with period {}", self.period)
+        }
+    }
+}
+
+/// The live cells of a generation, moved so that the top left of their bounding box is at 0:0
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Shape {
+    /// Where the top left of the bounding box was on the grid
+    origin: (usize, usize),
+    /// The live cells relative to the origin, ordered by row and then column
+    cells: Vec<(usize, usize)>,
+}
+impl Shape {
+    fn new(cells: &[Vec<bool>]) -> Option<Self> {
+        let mut live = Vec::new();
+        for (y, row) in cells.iter().enumerate() {
+            for (x, is_alive) in row.iter().enumerate() {
+                if *is_alive {
+                    live.push((x, y));
+                }
+            }
+        }
+        let min_x = live.iter().map(|(x, _)| *x).min()?;
+        let min_y = live.iter().map(|(_, y)| *y).min()?;
+        Some(Shape {
+            origin: (min_x, min_y),
+            cells: live
+                .into_iter()
+                .map(|(x, y)| (x - min_x, y - min_y))
+                .collect(),
+        })
+    }
+}
+
+/// Finds the period of a pattern by remembering its latest generations and looking for one with
+/// the same shape, anywhere on the grid
+///
+/// A shape that comes back in the same place is a still life or an oscillator, a shape that comes
+/// back somewhere else is a spaceship. Shapes that wrap around the edges of the grid are not
+/// recognized while they wrap
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{conway::cycle::CycleDetector, ConwaysGame};
+/// # let mut game = ConwaysGame::headless(16, 16, 42);
+/// let mut detector = CycleDetector::new(30);
+/// for _ in 0..1000 {
+///     game.next();
+///     if let Some(cycle) = detector.record(game.cells()) {
+///         break;
+///     }
+/// }
+/// ```
+pub struct CycleDetector {
+    max_period: usize,
+    history: VecDeque<Shape>,
+}
+impl CycleDetector {
+    /// Create a detector
+    ///
+    /// # Arguments
+    ///
+    /// * `max_period` - the longest period that is recognized
+    pub fn new(max_period: usize) -> Self {
+        assert!(max_period > 0);
+        CycleDetector {
+            max_period,
+            history: VecDeque::with_capacity(max_period),
+        }
+    }
+    /// Forget the remembered generations, e.g. when the board was replaced
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+    /// Add the next generation
+    ///
+    /// # Returns
+    ///
+    /// The shortest cycle that ends with this generation, `None` if the shape is new or there are
+    /// no live cells
+    pub fn record(&mut self, cells: &[Vec<bool>]) -> Option<Cycle> {
+        let shape = match Shape::new(cells) {
+            Some(shape) => shape,
+            None => {
+                self.history.clear();
+                return None;
+            }
+        };
+        let cycle = self
+            .history
+            .iter()
+            .rev()
+            .enumerate()
+            .find(|(_, earlier)| earlier.cells == shape.cells)
+            .map(|(index, earlier)| Cycle {
+                period: index + 1,
+                displacement: (
+                    shape.origin.0 as i64 - earlier.origin.0 as i64,
+                    shape.origin.1 as i64 - earlier.origin.1 as i64,
+                ),
+            });
+        if self.history.len() == self.max_period {
+            self.history.pop_front();
+        }
+        self.history.push_back(shape);
+        cycle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::{conways_game::ConwaysGame, patterns::PatternPlacement};
+
+    use super::{Cycle, CycleDetector};
+
+    fn find_cycle(pattern: &str, generations: usize) -> Option<Cycle> {
+        let mut game = ConwaysGame::headless(20, 20, 0);
+        game.set_cells(vec![vec![false; 20]; 20]);
+        game.place_pattern(&pattern.parse::<PatternPlacement>().unwrap());
+        let mut detector = CycleDetector::new(10);
+        detector.record(game.cells());
+        for _ in 0..generations {
+            game.next();
+            if let Some(cycle) = detector.record(game.cells()) {
+                return Some(cycle);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn detect_cycles() {
+        assert_eq!(
+            find_cycle("glider@2,2", 10),
+            Some(Cycle {
+                period: 4,
+                displacement: (1, 1)
+            })
+        );
+        let pulsar = find_cycle("pulsar@3,3", 10).expect("A pulsar oscillates");
+        assert_eq!(pulsar.period, 3);
+        assert!(!pulsar.is_moving());
+        assert!(!pulsar.is_still());
+        assert_eq!(
+            find_cycle("r-pentomino", 10),
+            None,
+            "The r-pentomino keeps changing for a long time"
+        );
+    }
+
+    #[test]
+    fn still_life_and_empty() {
+        let mut detector = CycleDetector::new(4);
+        let block = vec![
+            vec![false, false, false],
+            vec![false, true, true],
+            vec![false, true, true],
+        ];
+        assert_eq!(detector.record(&block), None);
+        let cycle = detector
+            .record(&block)
+            .expect("A block repeats every generation");
+        assert!(cycle.is_still());
+
+        let empty = vec![vec![false; 3]; 3];
+        assert_eq!(detector.record(&empty), None);
+        assert_eq!(detector.record(&empty), None, "An empty grid has no cycle");
+    }
+
+    #[test]
+    fn describe() {
+        let test_cases = [
+            ("still", 1, (0, 0), "still life"),
+            ("oscillator", 3, (0, 0), "oscillator with period 3"),
+            (
+                "spaceship",
+                4,
+                (1, -1),
+                "spaceship with period 4 moving 1:-1",
+            ),
+        ];
+        for (test_case, period, displacement, expected) in test_cases {
+            let cycle = Cycle {
+                period,
+                displacement,
+            };
+            assert_eq!(cycle.to_string(), expected, "{}", test_case);
+        }
+    }
+}
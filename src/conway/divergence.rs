@@ -0,0 +1,112 @@
+use std::fmt::Display;
+
+/// Hash the cells of a board with 64 bit FNV-1a, which gives the same hash on every platform and
+/// version, so that the generations of two runs can be compared
+pub fn board_hash(cells: &[Vec<bool>]) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    let mut hash = OFFSET;
+    for row in cells {
+        for byte in row.iter().map(|is_alive| *is_alive as u8).chain([b'\n']) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    hash
+}
+
+/// A generation of a replay that is not the generation that was recorded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The generation that was recorded
+    pub generation: u64,
+    /// The generation the replay is at
+    pub reached: u64,
+    pub expected: u64,
+    /// The hash of the generation in the replay, `None` if the replay never had the generation
+    pub actual: Option<u64>,
+}
+impl Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.actual {
+            Some(actual) => write!(
+                f,
+                "Generation {} has the hash {:016x} instead of {:016x}",
+                self.generation, actual, self.expected
+            ),
+            None => write!(
+                f,
+                "The replay reached generation {} instead of generation {}",
+                self.reached, self.generation
+            ),
+        }
+    }
+}
+
+/// Why a recorded session could not be verified
+#[derive(Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The session does not have the board it started with
+    NoStart,
+    /// A checkpoint of the session has a hash that can not be read
+    BadHash(String),
+    /// The replay went apart from the recording
+    Diverged(Divergence),
+}
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyError::NoStart => write!(f, "The session does not have its first board"),
+            VerifyError::BadHash(hash) => write!(f, "`{}` is not a hash", hash),
+            VerifyError::Diverged(divergence) => divergence.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{board_hash, Divergence};
+
+    #[test]
+    fn hashes() {
+        assert_eq!(board_hash(&[]), 0xcbf29ce484222325, "The offset of FNV-1a");
+        let board = [vec![true, false]];
+        let test_cases = [
+            ("another cell", vec![vec![false, true]]),
+            ("other rows", vec![vec![true], vec![false]]),
+            ("a longer row", vec![vec![true, false, false]]),
+        ];
+        for (test_case, cells) in test_cases {
+            assert_ne!(board_hash(&cells), board_hash(&board), "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn divergences() {
+        let test_cases = [
+            (
+                "another board",
+                Divergence {
+                    generation: 4,
+                    reached: 4,
+                    expected: 0xab,
+                    actual: Some(0x1),
+                },
+                "Generation 4 has the hash 0000000000000001 instead of 00000000000000ab",
+            ),
+            (
+                "another generation",
+                Divergence {
+                    generation: 4,
+                    reached: 9,
+                    expected: 0xab,
+                    actual: None,
+                },
+                "The replay reached generation 9 instead of generation 4",
+            ),
+        ];
+        for (test_case, divergence, expected) in test_cases {
+            assert_eq!(divergence.to_string(), expected, "{}", test_case);
+        }
+    }
+}
@@ -0,0 +1,543 @@
+use crate::{
+    console::input_record::Key,
+    shared::{
+        square::Square,
+        usize2d::{Coord, Usize2d},
+    },
+};
+
+use super::{
+    clipboard::Clipboard,
+    patterns::{Pattern, PATTERNS},
+};
+
+/// The number of edits that can be undone
+pub const UNDO_LIMIT: usize = 100;
+/// The keys that undo and redo an edit, Ctrl+Z and Ctrl+Y
+pub const UNDO_KEY: char = Key::Ctrl('z').to_char();
+pub const REDO_KEY: char = Key::Ctrl('y').to_char();
+/// The keys that copy, cut and paste the selection, Ctrl+C, Ctrl+X and Ctrl+V
+pub const COPY_KEY: char = Key::Ctrl('c').to_char();
+pub const CUT_KEY: char = Key::Ctrl('x').to_char();
+pub const PASTE_KEY: char = Key::Ctrl('v').to_char();
+/// The key that writes the clipboard to an RLE file, Ctrl+E. Ctrl+S is left to save the game
+pub const EXPORT_KEY: char = Key::Ctrl('e').to_char();
+
+/// A pattern from the library that can be stamped onto the board, turned in steps of 90 degrees
+#[derive(Clone, Copy, Debug)]
+pub struct Brush {
+    pub pattern: &'static Pattern,
+    /// The number of clockwise quarter turns
+    pub rotation: u8,
+}
+impl Brush {
+    pub fn new(pattern: &'static Pattern) -> Self {
+        Brush {
+            pattern,
+            rotation: 0,
+        }
+    }
+    /// Turn the brush 90 degrees clockwise
+    pub fn rotate(&mut self) {
+        self.rotation = (self.rotation + 1) % 4;
+    }
+    /// Get the width and height of the brush after the rotation
+    pub fn size(&self) -> Usize2d {
+        let (width, height) = (self.pattern.width(), self.pattern.height());
+        if self.rotation.is_multiple_of(2) {
+            Usize2d::new(width, height)
+        } else {
+            Usize2d::new(height, width)
+        }
+    }
+    /// Get the offsets of the live cells from the top left corner of the rotated brush
+    pub fn live_cells(&self) -> Vec<Usize2d> {
+        let mut cells = self.pattern.live_cells();
+        let mut height = self.pattern.height();
+        let mut width = self.pattern.width();
+        for _ in 0..self.rotation {
+            for cell in cells.iter_mut() {
+                *cell = Usize2d::new(height - 1 - cell.y, cell.x);
+            }
+            std::mem::swap(&mut width, &mut height);
+        }
+        cells
+    }
+}
+
+/// A cell that was changed by an edit
+#[derive(Clone, Copy, Debug)]
+pub struct CellChange {
+    pub cell: Coord,
+    pub was_alive: bool,
+    pub is_alive: bool,
+}
+
+/// The cells changed by a single toggle or stamp, so that it can be undone as a whole
+#[derive(Clone, Debug, Default)]
+pub struct Edit {
+    pub changes: Vec<CellChange>,
+}
+impl Edit {
+    /// Get the edit that reverts this one
+    pub fn inverse(&self) -> Edit {
+        Edit {
+            changes: self
+                .changes
+                .iter()
+                .rev()
+                .map(|change| CellChange {
+                    cell: change.cell,
+                    was_alive: change.is_alive,
+                    is_alive: change.was_alive,
+                })
+                .collect(),
+        }
+    }
+    /// Set the cells of a board to their state after the edit
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the board, indexed as `cells[y][x]`
+    pub fn apply(&self, cells: &mut [Vec<bool>]) {
+        for change in self.changes.iter() {
+            cells[change.cell.y][change.cell.x] = change.is_alive;
+        }
+    }
+}
+
+/// A press of the mouse that toggles the cell under it, followed by a drag that paints the cells
+/// it passes over alive. The whole stroke is undone as a single edit
+#[derive(Clone, Debug)]
+pub struct Stroke {
+    last: Coord,
+    pub edit: Edit,
+}
+impl Stroke {
+    /// Start a stroke by toggling a cell
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the board, indexed as `cells[y][x]`
+    /// * `cell` - the cell that was clicked
+    pub fn start(cells: &mut [Vec<bool>], cell: Coord) -> Self {
+        let was_alive = cells[cell.y][cell.x];
+        let edit = Edit {
+            changes: vec![CellChange {
+                cell,
+                was_alive,
+                is_alive: !was_alive,
+            }],
+        };
+        edit.apply(cells);
+        Stroke { last: cell, edit }
+    }
+    /// Paint the cells on the line from the last painted cell to a cell alive, so that a fast
+    /// drag leaves no gaps
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the board, indexed as `cells[y][x]`
+    /// * `cell` - the cell the mouse was dragged to
+    pub fn paint_to(&mut self, cells: &mut [Vec<bool>], cell: Coord) {
+        let (x_delta, y_delta) = (
+            cell.x as f64 - self.last.x as f64,
+            cell.y as f64 - self.last.y as f64,
+        );
+        let steps = x_delta.abs().max(y_delta.abs()) as usize;
+        for step in 1..=steps {
+            let progress = step as f64 / steps as f64;
+            let x = (self.last.x as f64 + x_delta * progress).round() as usize;
+            let y = (self.last.y as f64 + y_delta * progress).round() as usize;
+            if !cells[y][x] {
+                cells[y][x] = true;
+                self.edit.changes.push(CellChange {
+                    cell: Coord::new(x, y),
+                    was_alive: false,
+                    is_alive: true,
+                });
+            }
+        }
+        self.last = cell;
+    }
+}
+
+/// The state of the editor that is available while the game is paused
+///
+/// The editor has a cursor that is moved over the board and an optional brush. Without a brush
+/// single cells are toggled, with a brush the pattern is stamped with its top left corner at the
+/// cursor. Every toggle and stamp is kept on an undo stack until the editor is closed
+///
+/// A selection is the rectangle between the cursor and the cell it was started at, it does not
+/// wrap around the edges of the grid
+#[derive(Debug, Default)]
+pub struct Editor {
+    pub cursor: Coord,
+    brush_index: Option<usize>,
+    pub brush: Option<Brush>,
+    undo_stack: Vec<Edit>,
+    redo_stack: Vec<Edit>,
+    selection_anchor: Option<Coord>,
+}
+impl Editor {
+    /// Move the cursor, wrapping around the edges of the grid
+    ///
+    /// # Arguments
+    ///
+    /// * `x_delta` - the columns to move, negative to move left
+    /// * `y_delta` - the rows to move, negative to move up
+    /// * `grid` - the number of columns and rows of the grid
+    pub fn move_cursor(&mut self, x_delta: i64, y_delta: i64, grid: Usize2d) {
+        self.cursor = Coord::new(
+            (self.cursor.x as i64 + x_delta).rem_euclid(grid.x as i64) as usize,
+            (self.cursor.y as i64 + y_delta).rem_euclid(grid.y as i64) as usize,
+        );
+    }
+    /// Select the next pattern of the library as the brush, after the last pattern the brush is
+    /// removed again
+    pub fn next_brush(&mut self) {
+        self.brush_index = match self.brush_index {
+            None => Some(0),
+            Some(index) if index + 1 < PATTERNS.len() => Some(index + 1),
+            Some(_) => None,
+        };
+        self.brush = self.brush_index.map(|index| Brush::new(PATTERNS[index]));
+    }
+    /// Select a pattern of the library as the brush
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - the index of the pattern in `PATTERNS`
+    pub fn select_brush(&mut self, index: usize) {
+        self.brush_index = Some(index);
+        self.brush = Some(Brush::new(PATTERNS[index]));
+    }
+    /// Get the cells that the editor changes when it is applied at the cursor, wrapped onto the
+    /// grid. Without a brush this is only the cell under the cursor
+    pub fn target_cells(&self, grid: Usize2d) -> Vec<Coord> {
+        let offsets = match &self.brush {
+            Some(brush) => brush.live_cells(),
+            None => vec![Usize2d::new(0, 0)],
+        };
+        offsets
+            .into_iter()
+            .map(|offset| {
+                Coord::new(
+                    (self.cursor.x + offset.x) % grid.x,
+                    (self.cursor.y + offset.y) % grid.y,
+                )
+            })
+            .collect()
+    }
+    /// Change the cells the editor targets at the cursor
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the board, indexed as `cells[y][x]`
+    /// * `is_stamp` - set the target cells alive instead of toggling them
+    ///
+    /// # Returns
+    ///
+    /// The edit, which is also kept to be undone
+    pub fn apply(&mut self, cells: &mut [Vec<bool>], is_stamp: bool) -> Edit {
+        let grid = Usize2d::new(cells[0].len(), cells.len());
+        let targets = self
+            .target_cells(grid)
+            .into_iter()
+            .map(|cell| (cell, is_stamp || !cells[cell.y][cell.x]))
+            .collect();
+        self.set_cells(cells, targets)
+    }
+    /// Start a selection at the cursor, or end the selection that was started
+    pub fn toggle_selection(&mut self) {
+        self.selection_anchor = match self.selection_anchor {
+            Some(_) => None,
+            None => Some(self.cursor),
+        };
+    }
+    /// Get the selected rectangle, `None` if no selection was started
+    pub fn selection(&self) -> Option<Square> {
+        let anchor = self.selection_anchor?;
+        Some(Square::new(
+            Usize2d::new(anchor.x.min(self.cursor.x), anchor.y.min(self.cursor.y)),
+            Usize2d::new(anchor.x.max(self.cursor.x), anchor.y.max(self.cursor.y)),
+        ))
+    }
+    /// Kill the selected cells and end the selection
+    ///
+    /// # Returns
+    ///
+    /// The edit, which is also kept to be undone, `None` if nothing is selected
+    pub fn clear_selection(&mut self, cells: &mut [Vec<bool>]) -> Option<Edit> {
+        let (top_left, bottom_right) = self.selection()?.get_boundary();
+        self.selection_anchor = None;
+        let targets = (top_left.y..=bottom_right.y)
+            .flat_map(|y| (top_left.x..=bottom_right.x).map(move |x| (Coord::new(x, y), false)))
+            .collect();
+        Some(self.set_cells(cells, targets))
+    }
+    /// Copy the clipboard onto the board with its top left corner at the cursor, wrapping around
+    /// the edges of the grid. Dead cells of the clipboard replace live cells of the board
+    ///
+    /// # Returns
+    ///
+    /// The edit, which is also kept to be undone
+    pub fn paste(&mut self, cells: &mut [Vec<bool>], clipboard: &Clipboard) -> Edit {
+        let grid = Usize2d::new(cells[0].len(), cells.len());
+        let cursor = self.cursor;
+        let targets = clipboard
+            .cells
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter().enumerate().map(move |(x, is_alive)| {
+                    let cell = Coord::new((cursor.x + x) % grid.x, (cursor.y + y) % grid.y);
+                    (cell, *is_alive)
+                })
+            })
+            .collect();
+        self.set_cells(cells, targets)
+    }
+    /// Set cells of the board and keep the change to be undone
+    fn set_cells(&mut self, cells: &mut [Vec<bool>], targets: Vec<(Coord, bool)>) -> Edit {
+        let changes = targets
+            .into_iter()
+            .map(|(cell, is_alive)| CellChange {
+                cell,
+                was_alive: cells[cell.y][cell.x],
+                is_alive,
+            })
+            .collect();
+        let edit = Edit { changes };
+        edit.apply(cells);
+        self.record(edit.clone());
+        edit
+    }
+    /// Keep an edit to be undone. A new edit can not be redone after the edits that were undone
+    pub fn record(&mut self, edit: Edit) {
+        if self.undo_stack.len() == UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(edit);
+        self.redo_stack.clear();
+    }
+    /// Forget the edits and end the selection, e.g. once the grid changed size and the cells
+    /// they refer to moved
+    pub fn clear_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.selection_anchor = None;
+    }
+    /// Revert the latest edit
+    ///
+    /// # Returns
+    ///
+    /// The edit that reverts it, `None` if there is nothing to undo
+    pub fn undo(&mut self) -> Option<Edit> {
+        let edit = self.undo_stack.pop()?;
+        let inverse = edit.inverse();
+        self.redo_stack.push(edit);
+        Some(inverse)
+    }
+    /// Apply the latest undone edit again
+    ///
+    /// # Returns
+    ///
+    /// The edit, `None` if there is nothing to redo
+    pub fn redo(&mut self) -> Option<Edit> {
+        let edit = self.redo_stack.pop()?;
+        self.undo_stack.push(edit.clone());
+        Some(edit)
+    }
+    /// Describe the state of the editor in a single line
+    pub fn status(&self) -> String {
+        let brush = match &self.brush {
+            Some(brush) => format!("{} {}", brush.pattern.name, brush.rotation as usize * 90),
+            None => "cell".to_string(),
+        };
+        let selection = match self.selection() {
+            Some(selection) => format!(" | selection {}x{}", selection.width(), selection.height()),
+            None => String::new(),
+        };
+        format!(
+            "Edit {} | brush: {}{} | undo {} redo {} | ijkl x b r m h v t enter ^z ^y ^c ^x ^v ^s e",
+            self.cursor,
+            brush,
+            selection,
+            self.undo_stack.len(),
+            self.redo_stack.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        conway::{clipboard::Clipboard, patterns::GLIDER},
+        shared::usize2d::Usize2d,
+    };
+
+    use super::{Brush, Editor, Stroke, UNDO_LIMIT};
+
+    fn to_tuples(cells: Vec<Usize2d>) -> Vec<(usize, usize)> {
+        let mut cells: Vec<(usize, usize)> = cells.into_iter().map(|c| (c.x, c.y)).collect();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn rotate_brush() {
+        // .#.      #..      ###      .##
+        // ..#  ->  #.#  ->  #..  ->  #.#
+        // ###      ##.      .#.      ..#
+        let test_cases = vec![
+            (0, vec![(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)]),
+            (1, vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 1)]),
+            (2, vec![(0, 0), (0, 1), (1, 0), (1, 2), (2, 0)]),
+            (3, vec![(0, 1), (1, 0), (2, 0), (2, 1), (2, 2)]),
+        ];
+        let mut brush = Brush::new(&GLIDER);
+        for (rotation, expected) in test_cases {
+            assert_eq!(
+                to_tuples(brush.live_cells()),
+                expected,
+                "Test case {}: The cells after {} quarter turns are wrong",
+                rotation,
+                rotation
+            );
+            brush.rotate();
+        }
+        assert_eq!(brush.rotation, 0, "Four quarter turns is a full turn");
+    }
+
+    #[test]
+    fn cursor_and_targets() {
+        let grid = Usize2d::new(5, 4);
+        let mut editor = Editor::default();
+        editor.move_cursor(-1, -1, grid);
+        assert_eq!((editor.cursor.x, editor.cursor.y), (4, 3));
+        assert_eq!(to_tuples(editor.target_cells(grid)), vec![(4, 3)]);
+
+        editor.next_brush();
+        assert_eq!(editor.brush.map(|brush| brush.pattern.name), Some("glider"));
+        assert_eq!(
+            to_tuples(editor.target_cells(grid)),
+            vec![(0, 1), (0, 3), (1, 0), (1, 1), (4, 1)],
+            "The brush should wrap around the edges of the grid"
+        );
+        for _ in 0..5 {
+            editor.next_brush();
+        }
+        assert!(
+            editor.brush.is_none(),
+            "The brushes should cycle back to a single cell"
+        );
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut cells = vec![vec![false; 5]; 5];
+        let mut editor = Editor::default();
+        editor.apply(&mut cells, false);
+        editor.next_brush();
+        editor.move_cursor(1, 1, Usize2d::new(5, 5));
+        editor.apply(&mut cells, true);
+        let stamped = cells.clone();
+        let live = |cells: &Vec<Vec<bool>>| cells.iter().flatten().filter(|cell| **cell).count();
+        assert_eq!(live(&cells), 6);
+
+        let test_cases = [
+            ("undo the stamp", true, 1),
+            ("undo the toggle", true, 0),
+            ("nothing to undo", true, 0),
+            ("redo the toggle", false, 1),
+            ("redo the stamp", false, 6),
+            ("nothing to redo", false, 6),
+        ];
+        for (test_case, is_undo, expected) in test_cases {
+            let edit = match is_undo {
+                true => editor.undo(),
+                false => editor.redo(),
+            };
+            if let Some(edit) = edit {
+                edit.apply(&mut cells);
+            }
+            assert_eq!(live(&cells), expected, "{}", test_case);
+        }
+        assert_eq!(cells, stamped);
+
+        editor.undo().unwrap().apply(&mut cells);
+        editor.apply(&mut cells, false);
+        assert!(editor.redo().is_none(), "A new edit clears the redo stack");
+
+        for _ in 0..UNDO_LIMIT + 5 {
+            editor.apply(&mut cells, false);
+        }
+        let mut undone = 0;
+        while editor.undo().is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, UNDO_LIMIT);
+    }
+
+    #[test]
+    fn select_copy_paste() {
+        let grid = Usize2d::new(5, 5);
+        let mut cells = vec![vec![true; 5]; 5];
+        let mut editor = Editor::default();
+        editor.move_cursor(3, 1, grid);
+        editor.toggle_selection();
+        editor.move_cursor(-2, 1, grid);
+        let selection = editor.selection().expect("A selection was started");
+        let (top_left, bottom_right) = selection.get_boundary();
+        assert_eq!(
+            (top_left.x, top_left.y, bottom_right.x, bottom_right.y),
+            (1, 1, 3, 2),
+            "The selection spans from the anchor to the cursor in any direction"
+        );
+        assert!(editor.status().contains("selection 3x2"));
+
+        let clipboard = Clipboard::copy(&cells, &selection);
+        editor.clear_selection(&mut cells);
+        assert!(editor.selection().is_none());
+        let live = |cells: &Vec<Vec<bool>>| cells.iter().flatten().filter(|cell| **cell).count();
+        assert_eq!(live(&cells), 19);
+
+        editor.move_cursor(3, 3, grid);
+        let edit = editor.paste(&mut cells, &clipboard);
+        assert_eq!(edit.changes.len(), 6);
+        assert_eq!(
+            live(&cells),
+            20,
+            "The paste at 4:0 wraps around the edges and brings back the cleared cell 1:1"
+        );
+        editor.undo().unwrap().apply(&mut cells);
+        assert_eq!(live(&cells), 19);
+    }
+
+    #[test]
+    fn stroke() {
+        let board = |rows: &[&str]| -> Vec<Vec<bool>> {
+            rows.iter()
+                .map(|row| row.chars().map(|cell| cell == '#').collect())
+                .collect()
+        };
+        let mut cells = board(&["#....", ".....", "....."]);
+        let mut stroke = Stroke::start(&mut cells, Usize2d::new(0, 0));
+        assert_eq!(cells, board(&["....."; 3]), "The press toggles the cell");
+
+        let test_cases = [
+            ("next cell", (1, 0), vec![".#...", ".....", "....."]),
+            ("skipped cells", (4, 2), vec![".#...", "..##.", "....#"]),
+            ("painted cells", (3, 1), vec![".#...", "..##.", "....#"]),
+            ("back to the start", (0, 0), vec!["##...", "..##.", "....#"]),
+        ];
+        for (test_case, (x, y), expected) in test_cases {
+            stroke.paint_to(&mut cells, Usize2d::new(x, y));
+            assert_eq!(cells, board(&expected), "{}", test_case);
+        }
+        assert_eq!(stroke.edit.changes.len(), 6);
+        stroke.edit.inverse().apply(&mut cells);
+        assert_eq!(cells, board(&["#....", ".....", "....."]));
+    }
+}
@@ -0,0 +1,164 @@
+use rand::Rng;
+
+use crate::utils::rng::{RngService, GRID_STREAM};
+
+use super::automaton::Automaton;
+
+/// A one dimensional elementary cellular automaton, numbered by Wolfram's scheme from rule 0 to
+/// rule 255. A cell of the next generation is the bit of the rule number at the position made
+/// of the left neighbor, the cell and the right neighbor. The row wraps around its ends
+///
+/// The automaton keeps the latest generations as the rows of its grid, the newest row at the
+/// bottom. Once the grid is full the oldest row is dropped, so that the generations scroll
+/// upward
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::conway::{automaton::Automaton, elementary::Elementary};
+/// let mut rule_30 = Elementary::new(80, 24, 30);
+/// rule_30.next();
+/// println!("{}", rule_30.text());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Elementary {
+    rows: Vec<Vec<bool>>,
+    rule: u8,
+    height: usize,
+    generation: u64,
+}
+impl Elementary {
+    /// Start with a single live cell in the middle of the row
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the number of cells of a generation
+    /// * `height` - the number of generations that are kept
+    /// * `rule` - the Wolfram number of the rule
+    pub fn new(width: usize, height: usize, rule: u8) -> Self {
+        let mut first = vec![false; width];
+        first[width / 2] = true;
+        Elementary::from_row(first, height, rule)
+    }
+    /// Start with a row where half of the cells are alive
+    pub fn random(width: usize, height: usize, rule: u8, seed: u64) -> Self {
+        let mut rng = RngService::new(seed).stream(GRID_STREAM);
+        let first = (0..width).map(|_| rng.gen()).collect();
+        Elementary::from_row(first, height, rule)
+    }
+    /// Start with a row of cells
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - the first generation, with at least one cell
+    /// * `height` - the number of generations that are kept, at least one
+    /// * `rule` - the Wolfram number of the rule
+    pub fn from_row(first: Vec<bool>, height: usize, rule: u8) -> Self {
+        assert!(
+            !first.is_empty() && height > 0,
+            "The automaton needs at least one cell and one row"
+        );
+        Elementary {
+            rows: vec![first],
+            rule,
+            height,
+            generation: 0,
+        }
+    }
+    pub fn rule(&self) -> u8 {
+        self.rule
+    }
+    /// Get the number of the newest generation, the first is 0
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    /// Draw the kept generations as lines of text, a live cell is a block
+    pub fn text(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|is_alive| if *is_alive { '█' } else { ' ' })
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect()
+    }
+}
+impl Automaton for Elementary {
+    type Cell = bool;
+    fn next(&mut self) {
+        let last = self.rows.last().expect("There is always a row");
+        let width = last.len();
+        let next = (0..width)
+            .map(|x| {
+                let left = last[(x + width - 1) % width] as u8;
+                let right = last[(x + 1) % width] as u8;
+                let pattern = left << 2 | (last[x] as u8) << 1 | right;
+                self.rule >> pattern & 1 == 1
+            })
+            .collect();
+        if self.rows.len() == self.height {
+            self.rows.remove(0);
+        }
+        self.rows.push(next);
+        self.generation += 1;
+    }
+    fn cells(&self) -> &Vec<Vec<bool>> {
+        &self.rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::automaton::Automaton;
+
+    use super::Elementary;
+
+    #[test]
+    fn rules() {
+        let test_cases = [
+            (
+                "rule 30",
+                30,
+                vec!["   █   ", "  ███  ", " ██  █ ", "██ ████"],
+            ),
+            (
+                "rule 90",
+                90,
+                vec!["   █   ", "  █ █  ", " █   █ ", "█ █ █ █"],
+            ),
+            (
+                "rule 110",
+                110,
+                vec!["   █   ", "  ██   ", " ███   ", "██ █   "],
+            ),
+        ];
+        for (test_case, rule, expected) in test_cases {
+            let mut automaton = Elementary::new(7, 4, rule);
+            for _ in 0..3 {
+                automaton.next();
+            }
+            let expected: String = expected.iter().map(|row| format!("{}\n", row)).collect();
+            assert_eq!(automaton.text(), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn scrolls_upward() {
+        let mut automaton = Elementary::new(5, 2, 30);
+        let test_cases = [(1, 2), (2, 2), (3, 2)];
+        for (generation, rows) in test_cases {
+            let newest = automaton.cells().last().unwrap().clone();
+            automaton.next();
+            assert_eq!(automaton.generation(), generation);
+            assert_eq!(automaton.cells().len(), rows, "generation {}", generation);
+            assert_eq!(
+                automaton.cells()[0],
+                newest,
+                "The newest row moves up at generation {}",
+                generation
+            );
+        }
+    }
+}
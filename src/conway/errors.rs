@@ -0,0 +1,26 @@
+use std::fmt::Display;
+
+/// Why a `ConwaysGame` could not be built or resized
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConwayError {
+    /// The board needs at least one row and one column
+    EmptyBoard,
+    /// There is no source for the key inputs
+    MissingInput,
+    /// There is no handle to draw the game to
+    MissingHandle,
+    /// The saved game to resume is not valid
+    #[cfg(feature = "serde")]
+    InvalidSave,
+}
+impl Display for ConwayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyBoard => write!(f, "The board needs at least one row and one column"),
+            Self::MissingInput => write!(f, "The game needs a source for its key inputs"),
+            Self::MissingHandle => write!(f, "The game needs a handle to draw to"),
+            #[cfg(feature = "serde")]
+            Self::InvalidSave => write!(f, "The saved game can not be resumed"),
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use std::{fmt::Display, str::FromStr};
+
+use rand::Rng;
+
+use crate::{
+    rendering::colors::{TerminalColors, DECAY_PALETTE},
+    utils::rng::{RngService, GRID_STREAM},
+};
+
+use super::{
+    automaton::{moore_neighbors, Automaton},
+    rule::{Rule, RuleError},
+};
+
+/// The state of a dead cell
+pub const DEAD: u8 = 0;
+/// The state of a live cell, the states after it are the decay states
+pub const ALIVE: u8 = 1;
+
+/// A rule of the Generations family, where a live cell that does not survive passes through
+/// decay states before it is dead. Only live cells count as neighbors and a decaying cell can
+/// not be born again until it is dead
+///
+/// The rules are written as survival counts, birth counts and the number of states, separated
+/// by slashes. The number of states includes the dead and the live state
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::conway::{generations::GenerationsRule, rule::RuleError};
+/// # fn main() -> Result<(), RuleError> {
+/// let star_wars: GenerationsRule = "345/2/4".parse()?;
+/// assert_eq!(star_wars.states(), 4);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GenerationsRule {
+    rule: Rule,
+    states: u8,
+}
+impl GenerationsRule {
+    /// Star Wars, `345/2/4`, which grows into busy ships that leave trails
+    pub fn star_wars() -> Self {
+        GenerationsRule {
+            rule: Rule::new(&[2], &[3, 4, 5]),
+            states: 4,
+        }
+    }
+    /// Get the number of states, including the dead and the live state
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+    /// Get the state of a cell in the next generation
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - the state of the cell in the current generation
+    /// * `live_siblings` - the number of live neighbors of the cell
+    pub fn next_state(&self, state: u8, live_siblings: u8) -> u8 {
+        match state {
+            DEAD if self.rule.is_alive(false, live_siblings) => ALIVE,
+            DEAD => DEAD,
+            ALIVE if self.rule.is_alive(true, live_siblings) => ALIVE,
+            state if state + 1 < self.states => state + 1,
+            _ => DEAD,
+        }
+    }
+    /// Get the color a state is drawn in, the decay states fade through the `DECAY_PALETTE`
+    ///
+    /// # Returns
+    ///
+    /// The color, or `None` for a dead cell
+    pub fn color(&self, state: u8) -> Option<TerminalColors> {
+        match state {
+            DEAD => None,
+            ALIVE => Some(TerminalColors::White),
+            state => {
+                // The first decay state takes the first color and the last the last color
+                let decay_states = (self.states - 2).max(2) as usize - 1;
+                let index = (state as usize - 2) * (DECAY_PALETTE.len() - 1) / decay_states;
+                Some(DECAY_PALETTE[index.min(DECAY_PALETTE.len() - 1)])
+            }
+        }
+    }
+}
+impl FromStr for GenerationsRule {
+    type Err = RuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        let survival = parts.next().ok_or(RuleError::MissingSurvival)?;
+        let birth = parts.next().ok_or(RuleError::MissingBirth)?;
+        let states = parts.next().ok_or(RuleError::MissingStateCount)?;
+        if parts.next().is_some() {
+            return Err(RuleError::BadStateCount);
+        }
+        let states: u8 = states.parse().map_err(|_| RuleError::BadStateCount)?;
+        if states < 2 {
+            return Err(RuleError::BadStateCount);
+        }
+        Ok(GenerationsRule {
+            rule: format!("B{}/S{}", birth, survival).parse()?,
+            states,
+        })
+    }
+}
+impl Display for GenerationsRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Reuse the counts of the B/S notation, `B2/S345` becomes `345/2`
+        let rule = self.rule.to_string();
+        let (birth, survival) = rule.split_once('/').unwrap_or_default();
+        write!(
+            f,
+            "{}/{}/{}",
+            survival.trim_start_matches('S'),
+            birth.trim_start_matches('B'),
+            self.states
+        )
+    }
+}
+
+/// A cellular automaton with a rule of the Generations family, the cells are the states of the
+/// rule. The grid wraps around its edges
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::conway::{automaton::Automaton, generations::{Generations, GenerationsRule}};
+/// # let seed = 42;
+/// let mut generations = Generations::random(40, 20, seed, GenerationsRule::star_wars());
+/// generations.next();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Generations {
+    cells: Vec<Vec<u8>>,
+    rule: GenerationsRule,
+    rounds: u64,
+}
+impl Generations {
+    /// Create a grid of states
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the grid, indexed as `cells[y][x]`, with at least one row and column
+    /// * `rule` - the rule the cells change by
+    pub fn new(cells: Vec<Vec<u8>>, rule: GenerationsRule) -> Self {
+        assert!(
+            !cells.is_empty() && !cells[0].is_empty(),
+            "The grid needs at least one row and one column"
+        );
+        Generations {
+            cells,
+            rule,
+            rounds: 0,
+        }
+    }
+    /// Create a grid where half of the cells are alive and the others are dead
+    pub fn random(x_len: usize, y_len: usize, seed: u64, rule: GenerationsRule) -> Self {
+        let mut rng = RngService::new(seed).stream(GRID_STREAM);
+        let cells = (0..y_len)
+            .map(|_| {
+                (0..x_len)
+                    .map(|_| match rng.gen::<bool>() {
+                        true => ALIVE,
+                        false => DEAD,
+                    })
+                    .collect()
+            })
+            .collect();
+        Generations::new(cells, rule)
+    }
+    pub fn rule(&self) -> &GenerationsRule {
+        &self.rule
+    }
+    /// Count the live cells
+    pub fn population(&self) -> usize {
+        self.count(|state| state == ALIVE)
+    }
+    /// Count the cells in one of the decay states
+    pub fn decaying(&self) -> usize {
+        self.count(|state| state > ALIVE)
+    }
+    /// Get the number of generations since the grid was created
+    pub fn rounds(&self) -> u64 {
+        self.rounds
+    }
+    fn count(&self, is_counted: impl Fn(u8) -> bool) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|state| is_counted(**state))
+            .count()
+    }
+}
+impl Automaton for Generations {
+    type Cell = u8;
+    fn next(&mut self) {
+        let next = self
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, state)| {
+                        let live_siblings = moore_neighbors(&self.cells, x, y)
+                            .into_iter()
+                            .filter(|neighbor| *neighbor == ALIVE)
+                            .count();
+                        self.rule.next_state(*state, live_siblings as u8)
+                    })
+                    .collect()
+            })
+            .collect();
+        self.cells = next;
+        self.rounds += 1;
+    }
+    fn cells(&self) -> &Vec<Vec<u8>> {
+        &self.cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        conway::{automaton::Automaton, rule::RuleError},
+        rendering::colors::TerminalColors,
+    };
+
+    use super::{Generations, GenerationsRule};
+
+    #[test]
+    fn parse() {
+        let test_cases = [
+            ("star wars", "345/2/4", Ok(GenerationsRule::star_wars())),
+            ("missing states", "345/2", Err(RuleError::MissingStateCount)),
+            ("missing birth", "345", Err(RuleError::MissingBirth)),
+            ("one state", "345/2/1", Err(RuleError::BadStateCount)),
+            ("extra part", "345/2/4/1", Err(RuleError::BadStateCount)),
+            ("bad count", "349/2/4", Err(RuleError::BadNeighborCount)),
+        ];
+        for (test_case, rulestring, expected) in test_cases {
+            assert_eq!(
+                rulestring.parse::<GenerationsRule>(),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+        let brians_brain: GenerationsRule = "/2/3".parse().unwrap();
+        assert_eq!(brians_brain.to_string(), "/2/3");
+        assert_eq!(GenerationsRule::star_wars().to_string(), "345/2/4");
+    }
+
+    #[test]
+    fn decay() {
+        let rule = GenerationsRule::star_wars();
+        let test_cases = [
+            ("birth", 0, 2, 1),
+            ("no birth", 0, 3, 0),
+            ("survival", 1, 4, 1),
+            ("starts to decay", 1, 2, 2),
+            ("decays", 2, 3, 3),
+            ("dies", 3, 2, 0),
+        ];
+        for (test_case, state, live_siblings, expected) in test_cases {
+            assert_eq!(
+                rule.next_state(state, live_siblings),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+        let colors: Vec<Option<TerminalColors>> = (0..4).map(|state| rule.color(state)).collect();
+        assert_eq!(
+            colors,
+            [
+                None,
+                Some(TerminalColors::White),
+                Some(TerminalColors::Yellow),
+                Some(TerminalColors::DarkGrey)
+            ]
+        );
+    }
+
+    #[test]
+    fn trails() {
+        // In Brian's Brain a domino gives birth on both sides while it decays
+        let rule: GenerationsRule = "/2/3".parse().unwrap();
+        let mut generations = Generations::new(
+            vec![
+                vec![0, 0, 0, 0],
+                vec![0, 1, 1, 0],
+                vec![0, 0, 0, 0],
+                vec![0, 0, 0, 0],
+            ],
+            rule,
+        );
+        generations.next();
+        assert_eq!(
+            generations.cells(),
+            &vec![
+                vec![0, 1, 1, 0],
+                vec![0, 2, 2, 0],
+                vec![0, 1, 1, 0],
+                vec![0, 0, 0, 0],
+            ]
+        );
+        assert_eq!((generations.population(), generations.decaying()), (4, 2));
+        generations.next();
+        assert_eq!(generations.cells()[1][1..3], [0, 0], "The trail is gone");
+        assert_eq!(generations.rounds(), 2);
+    }
+}
@@ -0,0 +1,137 @@
+use super::{
+    conways_game::ConwaysGame,
+    divergence::VerifyError,
+    timeline::{RecordedSession, TimelineEvent},
+};
+
+/// A recorded run that is shown dimmed behind the live board at the same generation, to compare
+/// how an edit changed the evolution of a pattern
+///
+/// The run is replayed from the board it started with, with the commands in the generations
+/// their keys arrived in, the way `ConwaysGame::verify_session` replays it. The replay only steps
+/// forward, going back to an earlier generation starts it over
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::path::Path;
+/// # use tic_tac_toe2::{conway::{divergence::VerifyError, ghost::Ghost, timeline::RecordedSession}, ConwaysGame};
+/// # fn main() -> Result<(), VerifyError> {
+/// # let mut game = ConwaysGame::headless(16, 16, 42);
+/// let session = RecordedSession::load(Path::new("conway_55_timeline.json"))
+///     .expect("the timeline was written by the game");
+/// game.set_ghost(Some(Ghost::new(session)?));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Ghost {
+    session: RecordedSession,
+    replay: Box<ConwaysGame>,
+    /// The index of the first event that was not replayed
+    next_event: usize,
+    /// The generation the recording ends with
+    last_generation: u64,
+    /// The generation that was sought last
+    sought: u64,
+}
+impl Ghost {
+    /// Prepare the replay of a recorded session at the board it started with
+    pub fn new(session: RecordedSession) -> Result<Self, VerifyError> {
+        let replay = ConwaysGame::replay_of(&session)?;
+        let last_generation = session
+            .events
+            .iter()
+            .map(TimelineEvent::generation)
+            .max()
+            .unwrap_or_else(|| replay.rounds());
+        Ok(Ghost {
+            sought: replay.rounds(),
+            session,
+            replay: Box::new(replay),
+            next_event: 0,
+            last_generation,
+        })
+    }
+    /// Replay the run up to a generation, before the commands that arrived in it
+    pub fn seek(&mut self, generation: u64) {
+        if generation < self.sought {
+            if let Ok(replay) = ConwaysGame::replay_of(&self.session) {
+                *self.replay = replay;
+                self.next_event = 0;
+            }
+        }
+        self.sought = generation;
+        for event in &self.session.events[self.next_event..] {
+            if event.generation() >= generation {
+                break;
+            }
+            self.replay.replay_command(event);
+            self.next_event += 1;
+        }
+        if generation <= self.last_generation {
+            self.replay.advance_to(generation);
+        }
+    }
+    /// Get the cells of the run at the generation that was sought last, `None` if the run did
+    /// not have the generation
+    pub fn cells(&self) -> Option<&Vec<Vec<bool>>> {
+        match self.replay.rounds() == self.sought {
+            true => Some(self.replay.cells()),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::{
+        conways_game::ConwaysGame, divergence::VerifyError, timeline::RecordedSession,
+    };
+
+    use super::Ghost;
+
+    fn session() -> RecordedSession {
+        let mut game = ConwaysGame::headless(20, 20, 3);
+        game.start();
+        let dir = std::env::temp_dir().join(format!("conway_ghost_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("timeline.json");
+        game.fast_forward(12);
+        assert_eq!(game.rounds(), 12, "The board is not stable before the end");
+        game.write_timeline(&path).unwrap();
+        let session = RecordedSession::load(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        session
+    }
+
+    #[test]
+    fn replays_the_recording() {
+        let session = session();
+        let mut ghost = Ghost::new(session.clone()).unwrap();
+        let test_cases = [
+            ("forward", 5, true),
+            ("the end", 12, true),
+            ("back", 2, true),
+            ("beyond the end", 13, false),
+        ];
+        for (test_case, generation, is_shown) in test_cases {
+            ghost.seek(generation);
+            let mut expected = ConwaysGame::headless(20, 20, 3);
+            expected.restore(session.start.as_ref().unwrap()).unwrap();
+            for _ in 0..generation {
+                expected.next();
+            }
+            let cells = ghost.cells();
+            assert_eq!(cells.is_some(), is_shown, "{}", test_case);
+            if let Some(cells) = cells {
+                assert_eq!(cells, expected.cells(), "{}", test_case);
+            }
+        }
+
+        let no_start = RecordedSession {
+            start: None,
+            ..session
+        };
+        assert_eq!(Ghost::new(no_start).err(), Some(VerifyError::NoStart));
+    }
+}
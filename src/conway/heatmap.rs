@@ -0,0 +1,129 @@
+use std::collections::VecDeque;
+
+use crate::rendering::colors::{gradient, TerminalColors, HEAT_GRADIENT};
+
+/// How often each cell was alive over the latest generations
+///
+/// The counts are kept up to date as generations are recorded, the oldest generation is
+/// forgotten once the window is full
+pub struct Heatmap {
+    window: usize,
+    generations: VecDeque<Vec<Vec<bool>>>,
+    counts: Vec<Vec<usize>>,
+}
+impl Heatmap {
+    /// Create an empty heatmap
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - the number of generations that are remembered
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0);
+        Heatmap {
+            window,
+            generations: VecDeque::with_capacity(window),
+            counts: Vec::new(),
+        }
+    }
+    /// Add a generation, forgetting the oldest one if the window is full
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the cells of the generation, indexed as `cells[y][x]`
+    pub fn record(&mut self, cells: &[Vec<bool>]) {
+        let is_same_size = self.counts.len() == cells.len()
+            && self
+                .counts
+                .iter()
+                .zip(cells.iter())
+                .all(|(counts, row)| counts.len() == row.len());
+        if !is_same_size {
+            self.clear();
+            self.counts = cells.iter().map(|row| vec![0; row.len()]).collect();
+        }
+        if self.generations.len() == self.window {
+            if let Some(oldest) = self.generations.pop_front() {
+                Self::apply(&mut self.counts, &oldest, |count| count - 1);
+            }
+        }
+        Self::apply(&mut self.counts, cells, |count| count + 1);
+        self.generations.push_back(cells.to_vec());
+    }
+    /// Forget all generations
+    pub fn clear(&mut self) {
+        self.generations.clear();
+        self.counts.clear();
+    }
+    /// The number of recorded generations
+    pub fn len(&self) -> usize {
+        self.generations.len()
+    }
+    /// Check if no generations are recorded
+    pub fn is_empty(&self) -> bool {
+        self.generations.is_empty()
+    }
+    /// The part of the recorded generations in which a cell was alive
+    pub fn activity(&self, x: usize, y: usize) -> f32 {
+        match self.counts.get(y).and_then(|row| row.get(x)) {
+            Some(count) if !self.generations.is_empty() => {
+                *count as f32 / self.generations.len() as f32
+            }
+            _ => 0.0,
+        }
+    }
+    /// Get the color of a cell. Cells that were never alive are black, the others go from cold to
+    /// hot the more often they were alive
+    pub fn color(&self, x: usize, y: usize) -> TerminalColors {
+        let activity = self.activity(x, y);
+        if activity == 0.0 {
+            return HEAT_GRADIENT[0];
+        }
+        gradient(&HEAT_GRADIENT[1..], activity)
+    }
+    fn apply(counts: &mut [Vec<usize>], cells: &[Vec<bool>], change: fn(usize) -> usize) {
+        for (counts, row) in counts.iter_mut().zip(cells.iter()) {
+            for (count, is_alive) in counts.iter_mut().zip(row.iter()) {
+                if *is_alive {
+                    *count = change(*count);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rendering::colors::TerminalColors;
+
+    use super::Heatmap;
+
+    #[test]
+    fn activity_over_window() {
+        let mut heatmap = Heatmap::new(4);
+        // The left cell is always alive, the right cell blinks
+        for generation in 0..6 {
+            heatmap.record(&[vec![true, generation % 2 == 0, false]]);
+        }
+        assert_eq!(heatmap.len(), 4, "Only the window should be remembered");
+        assert_eq!(heatmap.activity(0, 0), 1.0);
+        assert_eq!(heatmap.activity(1, 0), 0.5);
+        assert_eq!(heatmap.activity(2, 0), 0.0);
+        assert_eq!(
+            heatmap.activity(9, 9),
+            0.0,
+            "Cells off the grid are inactive"
+        );
+
+        assert_eq!(heatmap.color(0, 0), TerminalColors::Red);
+        assert_eq!(heatmap.color(2, 0), TerminalColors::Black);
+    }
+
+    #[test]
+    fn resized_grid_starts_over() {
+        let mut heatmap = Heatmap::new(4);
+        heatmap.record(&[vec![true]]);
+        heatmap.record(&[vec![false, true]]);
+        assert_eq!(heatmap.len(), 1);
+        assert_eq!(heatmap.activity(1, 0), 1.0);
+    }
+}
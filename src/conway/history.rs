@@ -0,0 +1,247 @@
+use std::collections::VecDeque;
+
+use crate::rendering::hud::KeyHints;
+
+/// The width of the timeline slider in characters
+const SLIDER_WIDTH: usize = 32;
+
+/// A board as it was at the end of a round
+#[derive(Clone, Debug, PartialEq)]
+pub struct Generation {
+    pub round: u64,
+    pub cells: Vec<Vec<bool>>,
+    pub population: usize,
+}
+
+/// The latest generations of a game, the oldest generation is forgotten once it is full
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{conway::history::GenerationHistory, ConwaysGame};
+/// # let game = ConwaysGame::headless(16, 16, 42);
+/// let mut history = GenerationHistory::new(64);
+/// history.record(game.rounds(), game.cells());
+/// ```
+#[derive(Debug)]
+pub struct GenerationHistory {
+    capacity: usize,
+    generations: VecDeque<Generation>,
+}
+impl GenerationHistory {
+    /// Create an empty history
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - the number of generations that are remembered
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+        GenerationHistory {
+            capacity,
+            generations: VecDeque::with_capacity(capacity),
+        }
+    }
+    /// Add a generation, forgetting the oldest one if the history is full
+    pub fn record(&mut self, round: u64, cells: &[Vec<bool>]) {
+        if self.generations.len() == self.capacity {
+            self.generations.pop_front();
+        }
+        let population = cells
+            .iter()
+            .map(|row| row.iter().filter(|cell| **cell).count())
+            .sum();
+        self.generations.push_back(Generation {
+            round,
+            cells: cells.to_vec(),
+            population,
+        });
+    }
+    /// Get a generation by its position, 0 is the oldest generation that is remembered
+    pub fn get(&self, index: usize) -> Option<&Generation> {
+        self.generations.get(index)
+    }
+    /// Find the latest generation in which a cell was born or died
+    ///
+    /// # Returns
+    ///
+    /// The round of the generation, or `None` if the cell did not change in the remembered
+    /// generations
+    pub fn last_change(&self, x: usize, y: usize) -> Option<u64> {
+        self.generations
+            .iter()
+            .zip(self.generations.iter().skip(1))
+            .rev()
+            .find(|(before, after)| before.cells[y][x] != after.cells[y][x])
+            .map(|(_, after)| after.round)
+    }
+    /// Forget the generations after a position, to continue from that generation
+    pub fn truncate(&mut self, index: usize) {
+        self.generations.truncate(index + 1);
+    }
+    /// Forget all generations
+    pub fn clear(&mut self) {
+        self.generations.clear();
+    }
+    /// The number of remembered generations
+    pub fn len(&self) -> usize {
+        self.generations.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.generations.is_empty()
+    }
+}
+
+/// Scrubs the board through the history while the game is paused
+///
+/// The board of the running game is kept, so that closing the debugger without picking a
+/// generation returns to it
+#[derive(Debug)]
+pub struct TimeTravel {
+    index: usize,
+    pub live_cells: Vec<Vec<bool>>,
+    pub live_previous: Vec<Vec<bool>>,
+}
+impl TimeTravel {
+    /// Start at the latest generation of the history
+    pub fn new(
+        history: &GenerationHistory,
+        live_cells: Vec<Vec<bool>>,
+        live_previous: Vec<Vec<bool>>,
+    ) -> Self {
+        TimeTravel {
+            index: history.len().saturating_sub(1),
+            live_cells,
+            live_previous,
+        }
+    }
+    /// Get the position in the history of the generation that is shown
+    pub fn index(&self) -> usize {
+        self.index
+    }
+    /// Move through the history, stopping at the oldest and the latest generation
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - the number of generations to move, negative moves back in time
+    pub fn scrub(&mut self, history: &GenerationHistory, offset: i64) {
+        let last = history.len().saturating_sub(1) as i64;
+        self.index = (self.index as i64 + offset).clamp(0, last) as usize;
+    }
+    /// Describe the debugger on a line for the timeline, a line for the shown generation and a
+    /// line with the keys
+    pub fn lines(&self, history: &GenerationHistory) -> Vec<String> {
+        let last = history.len().saturating_sub(1);
+        let marker = match last {
+            0 => SLIDER_WIDTH - 1,
+            _ => self.index * (SLIDER_WIDTH - 1) / last,
+        };
+        let slider: String = (0..SLIDER_WIDTH)
+            .map(|position| if position == marker { '|' } else { '-' })
+            .collect();
+        let status = match history.get(self.index) {
+            Some(generation) => {
+                let before = match self.index {
+                    0 => generation.population,
+                    _ => history
+                        .get(self.index - 1)
+                        .map_or(generation.population, |before| before.population),
+                };
+                format!(
+                    "Generation {} ({}/{}) population {} ({:+})",
+                    generation.round,
+                    self.index + 1,
+                    history.len(),
+                    generation.population,
+                    generation.population as i64 - before as i64
+                )
+            }
+            None => "No generations recorded".to_string(),
+        };
+        vec![
+            format!("Time travel [{}]", slider),
+            status,
+            KeyHints::new(&[
+                ("a/d", "scrub"),
+                ("enter", "continue from here"),
+                ("esc", "back"),
+            ])
+            .line(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GenerationHistory, TimeTravel};
+
+    fn board(population: usize) -> Vec<Vec<bool>> {
+        vec![(0..4).map(|x| x < population).collect()]
+    }
+
+    #[test]
+    fn record() {
+        let mut history = GenerationHistory::new(3);
+        for round in 0..5 {
+            history.record(round, &board(round as usize));
+        }
+        assert_eq!(history.len(), 3);
+        let rounds: Vec<u64> = (0..3)
+            .map(|index| history.get(index).unwrap().round)
+            .collect();
+        assert_eq!(
+            rounds,
+            vec![2, 3, 4],
+            "The oldest generations are forgotten"
+        );
+        assert_eq!(history.get(0).unwrap().population, 2);
+        let test_cases = [
+            ("changed last", 3, Some(4)),
+            ("changed before", 2, Some(3)),
+            ("forgotten change", 1, None),
+            ("changed long ago", 0, None),
+        ];
+        for (test_case, x, expected) in test_cases {
+            assert_eq!(history.last_change(x, 0), expected, "{}", test_case);
+        }
+        history.truncate(1);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.get(2), None);
+    }
+
+    #[test]
+    fn scrub() {
+        let mut history = GenerationHistory::new(10);
+        for round in 0..4 {
+            history.record(round, &board(round as usize));
+        }
+        let test_cases = [
+            ("latest", 0, 3, "Generation 3 (4/4) population 3 (+1)"),
+            ("back", -2, 1, "Generation 1 (2/4) population 1 (+1)"),
+            (
+                "stops at the oldest",
+                -9,
+                0,
+                "Generation 0 (1/4) population 0 (+0)",
+            ),
+            (
+                "stops at the latest",
+                9,
+                3,
+                "Generation 3 (4/4) population 3 (+1)",
+            ),
+        ];
+        for (test_case, offset, index, status) in test_cases {
+            let mut time_travel = TimeTravel::new(&history, board(0), board(0));
+            time_travel.scrub(&history, offset);
+            assert_eq!(time_travel.index(), index, "{}", test_case);
+            assert_eq!(time_travel.lines(&history)[1], status, "{}", test_case);
+        }
+
+        let mut time_travel = TimeTravel::new(&history, board(0), board(0));
+        time_travel.scrub(&history, -3);
+        assert_eq!(
+            time_travel.lines(&history)[0],
+            format!("Time travel [|{}]", "-".repeat(31))
+        );
+    }
+}
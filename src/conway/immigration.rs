@@ -0,0 +1,225 @@
+use core::fmt::Display;
+
+use rand::Rng;
+
+use crate::{
+    rendering::colors::TerminalColors,
+    utils::rng::{RngService, GRID_STREAM},
+};
+
+use super::{
+    automaton::{moore_neighbors, Automaton},
+    rule::Rule,
+};
+
+/// The two species of the Immigration variant of the Game of Life
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Species {
+    Red,
+    Blue,
+}
+impl Species {
+    /// Get the color the cells of the species are drawn in
+    pub fn color(&self) -> TerminalColors {
+        match self {
+            Species::Red => TerminalColors::Red,
+            Species::Blue => TerminalColors::Blue,
+        }
+    }
+}
+impl Display for Species {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Species::Red => write!(f, "red"),
+            Species::Blue => write!(f, "blue"),
+        }
+    }
+}
+
+/// A cell of the Immigration grid, `None` is a dead cell
+pub type SpeciesCell = Option<Species>;
+
+/// The Game of Life with two competing species
+///
+/// Cells live and die by the rule like in the Game of Life, without looking at the species of
+/// their neighbors. A cell that is born takes the species of the majority of its live
+/// neighbors, a tie goes to red. A cell that survives keeps its species. The grid wraps around
+/// its edges
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{conway::{automaton::Automaton, immigration::{Immigration, Species}}, Rule};
+/// # let seed = 42;
+/// let mut immigration = Immigration::random(40, 20, seed, Rule::conway());
+/// immigration.next();
+/// println!("{} red cells", immigration.population(Species::Red));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Immigration {
+    cells: Vec<Vec<SpeciesCell>>,
+    rule: Rule,
+    rounds: u64,
+}
+impl Immigration {
+    /// Create a grid with the cells of both species
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the grid, indexed as `cells[y][x]`, with at least one row and column
+    /// * `rule` - the rule the cells live and die by
+    pub fn new(cells: Vec<Vec<SpeciesCell>>, rule: Rule) -> Self {
+        assert!(
+            !cells.is_empty() && !cells[0].is_empty(),
+            "The grid needs at least one row and one column"
+        );
+        Immigration {
+            cells,
+            rule,
+            rounds: 0,
+        }
+    }
+    /// Create a grid where half of the cells are alive, split evenly between the species
+    ///
+    /// # Arguments
+    ///
+    /// * `x_len` - the number of columns
+    /// * `y_len` - the number of rows
+    /// * `seed` - the seed of the cells, the same seed always gives the same grid
+    /// * `rule` - the rule the cells live and die by
+    pub fn random(x_len: usize, y_len: usize, seed: u64, rule: Rule) -> Self {
+        let mut rng = RngService::new(seed).stream(GRID_STREAM);
+        let cells = (0..y_len)
+            .map(|_| {
+                (0..x_len)
+                    .map(|_| match rng.gen::<bool>() {
+                        true => Some(match rng.gen::<bool>() {
+                            true => Species::Red,
+                            false => Species::Blue,
+                        }),
+                        false => None,
+                    })
+                    .collect()
+            })
+            .collect();
+        Immigration::new(cells, rule)
+    }
+    /// Count the live cells of a species
+    pub fn population(&self, species: Species) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|cell| **cell == Some(species))
+            .count()
+    }
+    /// Get the number of generations since the grid was created
+    pub fn rounds(&self) -> u64 {
+        self.rounds
+    }
+}
+impl Automaton for Immigration {
+    type Cell = SpeciesCell;
+    fn next(&mut self) {
+        let next = self
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, cell)| {
+                        let neighbors = moore_neighbors(&self.cells, x, y);
+                        let red = neighbors
+                            .iter()
+                            .filter(|cell| **cell == Some(Species::Red))
+                            .count();
+                        let blue = neighbors
+                            .iter()
+                            .filter(|cell| **cell == Some(Species::Blue))
+                            .count();
+                        if !self.rule.is_alive(cell.is_some(), (red + blue) as u8) {
+                            return None;
+                        }
+                        match cell {
+                            Some(species) => Some(*species),
+                            None if blue > red => Some(Species::Blue),
+                            None => Some(Species::Red),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        self.cells = next;
+        self.rounds += 1;
+    }
+    fn cells(&self) -> &Vec<Vec<SpeciesCell>> {
+        &self.cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::{automaton::Automaton, rule::Rule};
+
+    use super::{Immigration, Species, SpeciesCell};
+
+    fn grid(rows: &[&str]) -> Vec<Vec<SpeciesCell>> {
+        rows.iter()
+            .map(|row| {
+                row.chars()
+                    .map(|cell| match cell {
+                        'r' => Some(Species::Red),
+                        'b' => Some(Species::Blue),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn births_take_the_majority() {
+        let test_cases = [
+            (
+                "red blinker",
+                vec![".....", ".....", ".rrr.", ".....", "....."],
+                vec![".....", "..r..", "..r..", "..r..", "....."],
+            ),
+            (
+                "mixed blinker",
+                vec![".....", ".....", ".rbb.", ".....", "....."],
+                vec![".....", "..b..", "..b..", "..b..", "....."],
+            ),
+            (
+                "survivors keep their species",
+                vec![".....", ".rb..", ".bb..", ".....", "....."],
+                vec![".....", ".rb..", ".bb..", ".....", "....."],
+            ),
+        ];
+        for (test_case, cells, expected) in test_cases {
+            let mut immigration = Immigration::new(grid(&cells), Rule::conway());
+            immigration.next();
+            assert_eq!(immigration.cells(), &grid(&expected), "{}", test_case);
+            assert_eq!(immigration.rounds(), 1, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn populations() {
+        let immigration = Immigration::random(20, 10, 3, Rule::conway());
+        let (red, blue) = (
+            immigration.population(Species::Red),
+            immigration.population(Species::Blue),
+        );
+        let live = immigration.cells().iter().flatten().flatten().count();
+        assert_eq!(red + blue, live);
+        assert!(red > 0 && blue > 0, "red {}, blue {}", red, blue);
+        assert_eq!(
+            Immigration::random(20, 10, 3, Rule::conway()).cells(),
+            immigration.cells(),
+            "The same seed gives the same grid"
+        );
+        let size = immigration.size();
+        assert_eq!((size.x, size.y), (20, 10));
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+
+use crate::console::{input_record::KeyEvent, input_source::ReplayInput};
+
+use super::command::{CommandSource, GameCommand};
+
+/// The latest commands of a game with the inputs that ran them, to find out which command a key
+/// ended up running, to replay the keys and to undo the latest command
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{console::input_record::KeyEvent, conway::{command::{Command, CommandSource, GameCommand}, input_log::InputLog}, utils::clock::{Clock, SystemClock}};
+/// # let (event, clock) = (KeyEvent::key_down('q'), SystemClock);
+/// let mut log = InputLog::new(8);
+/// log.record(GameCommand::new(Command::QUIT, CommandSource::Key(event), clock.now()));
+/// ```
+pub struct InputLog {
+    capacity: usize,
+    entries: VecDeque<GameCommand>,
+}
+impl InputLog {
+    /// Create an empty log
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - the number of commands that are remembered
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+        InputLog {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+    /// Add a command, forgetting the oldest one if the log is full
+    pub fn record(&mut self, command: GameCommand) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(command);
+    }
+    /// Get the commands from the oldest to the newest
+    pub fn entries(&self) -> impl Iterator<Item = &GameCommand> {
+        self.entries.iter()
+    }
+    /// Take the newest command that can still be reverted, it can not be reverted again
+    ///
+    /// # Returns
+    ///
+    /// The command, with the inverse that reverts it
+    pub fn take_undo(&mut self) -> Option<GameCommand> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .rev()
+            .find(|entry| entry.inverse.is_some())?;
+        let undone = *entry;
+        entry.inverse = None;
+        Some(undone)
+    }
+    /// Write the pressed keys in the format that `ReplayInput` reads, played back a key per poll
+    /// with a key for every repeat
+    pub fn replay(&self) -> String {
+        let keys: Vec<(u64, KeyEvent)> = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry.source {
+                CommandSource::Key(event) if event.is_down => Some((event, entry.repeats())),
+                _ => None,
+            })
+            .flat_map(|(event, repeats)| {
+                std::iter::repeat_n(KeyEvent::key_down(event.command), repeats)
+            })
+            .enumerate()
+            .map(|(poll, event)| (poll as u64, event))
+            .collect();
+        ReplayInput::format(&keys)
+    }
+    /// Describe the commands from the newest to the oldest, a line per command with the input,
+    /// if it was pressed or released, the repeat count and the command
+    pub fn lines(&self) -> Vec<String> {
+        if self.entries.is_empty() {
+            return vec!["No input yet".to_string()];
+        }
+        self.entries
+            .iter()
+            .rev()
+            .map(|entry| {
+                let is_down = entry.is_down();
+                let command = match is_down {
+                    true => entry.command.to_string(),
+                    false => "-".to_string(),
+                };
+                format!(
+                    "{:<6} {:<4} x{:<3} {}",
+                    entry.input(),
+                    if is_down { "down" } else { "up" },
+                    entry.repeats(),
+                    command
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use crate::{
+        console::input_record::KeyEvent,
+        conway::command::{Command, CommandSource, GameCommand},
+    };
+
+    use super::InputLog;
+
+    fn key(event: KeyEvent, command: Command) -> GameCommand {
+        GameCommand::new(command, CommandSource::Key(event), Instant::now())
+    }
+
+    #[test]
+    fn lines() {
+        let mut log = InputLog::new(3);
+        assert_eq!(log.lines(), vec!["No input yet"]);
+
+        let released = KeyEvent {
+            command: 'q',
+            repreat_count: 2,
+            is_down: false,
+        };
+        log.record(key(KeyEvent::key_down('m'), Command::TOGGLEMODE));
+        log.record(key(KeyEvent::key_down(' '), Command::PAUSEPLAY));
+        log.record(key(KeyEvent::key_down('z'), Command::NOMAPPING));
+        log.record(key(released, Command::QUIT));
+        assert_eq!(log.entries().count(), 3, "The oldest event is forgotten");
+        let test_cases = [
+            ("released", "q      up   x2   -"),
+            ("not mapped", "z      down x1   Key not mapped"),
+            ("named key", "space  down x1   Toggle pause"),
+        ];
+        for ((test_case, expected), line) in test_cases.iter().zip(log.lines()) {
+            assert_eq!(&line, expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn undo_and_replay() {
+        let mut log = InputLog::new(8);
+        let held = KeyEvent {
+            command: 'd',
+            repreat_count: 3,
+            is_down: true,
+        };
+        log.record(key(KeyEvent::key_down(' '), Command::PAUSEPLAY));
+        log.record(key(held, Command::MOVERIGHT));
+        log.record(key(KeyEvent::key_down('z'), Command::NOMAPPING));
+        let test_cases = [
+            ("newest that can be undone", Some((Command::MOVELEFT, 3))),
+            ("undone already", Some((Command::PAUSEPLAY, 1))),
+            ("nothing left", None),
+        ];
+        for (test_case, expected) in test_cases {
+            let undone = log
+                .take_undo()
+                .map(|undone| (undone.inverse.unwrap(), undone.repeats()));
+            assert!(undone == expected, "{}", test_case);
+        }
+        assert_eq!(log.replay(), "0 space\n1 d\n2 d\n3 d\n4 z\n");
+    }
+}
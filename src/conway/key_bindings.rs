@@ -0,0 +1,172 @@
+use crate::{
+    console::input_record::{Key, DUMP_FRAME_KEY, QUIT_KEY, SAVE_KEY},
+    conway::{
+        command::Command,
+        editor::UNDO_KEY,
+        macros::{PLAY_MACRO_KEY, RECORD_MACRO_KEY},
+    },
+};
+
+/// The keys of the game and the commands they run
+///
+/// The bindings are kept in the order they were added, which is the order they are listed in
+/// the help. Letters are matched without case
+pub struct KeyBindings {
+    bindings: Vec<(char, Command)>,
+}
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let mut bindings = KeyBindings::empty();
+        bindings.bind('q', Command::QUIT);
+        bindings.bind(QUIT_KEY, Command::QUIT);
+        bindings.bind(SAVE_KEY, Command::SAVE);
+        bindings.bind('r', Command::RESET);
+        bindings.bind('b', Command::RESEED);
+        bindings.bind(' ', Command::PAUSEPLAY);
+        bindings.bind('m', Command::TOGGLEMODE);
+        bindings.bind('o', Command::TOGGLEDEBUGPANEL);
+        bindings.bind('n', Command::TOGGLEINPUTLOG);
+        bindings.bind('f', Command::TOGGLEFPS);
+        bindings.bind('x', Command::TOGGLEHEATMAP);
+        bindings.bind('g', Command::FASTFORWARD);
+        bindings.bind('w', Command::MOVEUP);
+        bindings.bind('a', Command::MOVELEFT);
+        bindings.bind('s', Command::MOVEDOWN);
+        bindings.bind('d', Command::MOVERIGHT);
+        bindings.bind('p', Command::PICKPATTERN);
+        bindings.bind('e', Command::OPENEDITOR);
+        bindings.bind('t', Command::TIMETRAVEL);
+        bindings.bind('+', Command::GROWGRID);
+        bindings.bind('-', Command::SHRINKGRID);
+        #[cfg(feature = "export-image")]
+        {
+            bindings.bind('c', Command::SCREENSHOT);
+            bindings.bind('v', Command::RECORD);
+        }
+        bindings.bind(DUMP_FRAME_KEY, Command::DUMPFRAME);
+        // Not `k`, the debug inspector moves down with it
+        bindings.bind('u', Command::SAVESEED);
+        bindings.bind(UNDO_KEY, Command::UNDO);
+        bindings.bind(RECORD_MACRO_KEY, Command::RECORDMACRO);
+        bindings.bind(PLAY_MACRO_KEY, Command::PLAYMACRO);
+        bindings.bind('h', Command::TOGGLEHELP);
+        bindings.bind('?', Command::TOGGLEHELP);
+        bindings.bind('\x1b', Command::OPENMENU);
+        bindings
+    }
+}
+impl KeyBindings {
+    /// Create bindings without any keys
+    pub fn empty() -> Self {
+        KeyBindings {
+            bindings: Vec::new(),
+        }
+    }
+    /// Bind a key to a command, replacing the command the key was bound to before
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::conway::{command::Command, key_bindings::KeyBindings};
+    /// # let mut bindings = KeyBindings::default();
+    /// bindings.bind('x', Command::QUIT);
+    /// ```
+    pub fn bind(&mut self, key: char, command: Command) {
+        let key = key.to_ascii_lowercase();
+        match self.bindings.iter_mut().find(|(bound, _)| *bound == key) {
+            Some(binding) => binding.1 = command,
+            None => self.bindings.push((key, command)),
+        }
+    }
+    /// Get the command of a key
+    ///
+    /// # Returns
+    ///
+    /// `Command::NOMAPPING` if the key is not bound
+    pub fn command(&self, key: char) -> Command {
+        let key = key.to_ascii_lowercase();
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == key)
+            .map(|(_, command)| *command)
+            .unwrap_or(Command::NOMAPPING)
+    }
+    /// The keys and their commands in the order they were bound
+    pub fn iter(&self) -> impl Iterator<Item = &(char, Command)> {
+        self.bindings.iter()
+    }
+    /// Describe every binding on a line of its own, as `<key>  <command>`
+    pub fn help_lines(&self) -> Vec<String> {
+        self.bindings
+            .iter()
+            .map(|(key, command)| format!("{:<6} {}", key_name(*key), command))
+            .collect()
+    }
+}
+
+/// Get the name of a key the way it is shown to players
+pub(crate) fn key_name(key: char) -> String {
+    Key::decode(key).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::command::Command;
+
+    use super::{key_name, KeyBindings};
+
+    #[test]
+    fn lookup() {
+        let mut bindings = KeyBindings::default();
+        bindings.bind('Z', Command::QUIT);
+        bindings.bind('f', Command::TOGGLEHEATMAP);
+        let test_cases = [
+            ("bound key", 'q', Command::QUIT),
+            ("upper case letter", 'Q', Command::QUIT),
+            ("added key", 'z', Command::QUIT),
+            ("rebound key", 'F', Command::TOGGLEHEATMAP),
+            ("help", 'h', Command::TOGGLEHELP),
+            ("symbol", '?', Command::TOGGLEHELP),
+            ("quit chord", '\x11', Command::QUIT),
+            ("save chord", '\x13', Command::SAVE),
+            ("unbound key", 'y', Command::NOMAPPING),
+        ];
+        for (test_case, key, expected) in test_cases {
+            assert!(
+                bindings.command(key) == expected,
+                "{}: '{}' should run {}",
+                test_case,
+                key,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn names() {
+        let test_cases = [
+            ("letter", 'q', "q"),
+            ("space", ' ', "space"),
+            ("enter", '\r', "enter"),
+            ("escape", '\x1b', "esc"),
+            ("control", '\x1a', "ctrl+z"),
+            ("control a", '\x01', "ctrl+a"),
+        ];
+        for (test_case, key, expected) in test_cases {
+            assert_eq!(key_name(key), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn help_lines() {
+        let mut bindings = KeyBindings::empty();
+        bindings.bind(' ', Command::PAUSEPLAY);
+        bindings.bind('Q', Command::QUIT);
+        bindings.bind(' ', Command::RESET);
+        assert_eq!(
+            bindings.help_lines(),
+            vec!["space  Reset".to_string(), "q      Quit".to_string()]
+        );
+        assert_eq!(bindings.iter().count(), 2);
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use crate::console::input_record::{Key, KeyEvent};
+
+/// The chord that starts and stops the recording of a macro, Ctrl+R
+pub const RECORD_MACRO_KEY: char = Key::Ctrl('r').to_char();
+/// The chord that plays a macro, Ctrl+P
+pub const PLAY_MACRO_KEY: char = Key::Ctrl('p').to_char();
+/// The most keys a macro keeps, the keys after them are not recorded
+pub const MACRO_LIMIT: usize = 1000;
+
+/// Key events that were recorded under a name, to play them again later, like the keys that
+/// draw the same pattern in the editor
+///
+/// A single macro is recorded at a time. Recording a name that is taken replaces the macro
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{console::input_record::KeyEvent, conway::macros::Macros, ConwaysGame};
+/// # let mut game = ConwaysGame::headless(16, 16, 42);
+/// # let event = KeyEvent::key_down('a');
+/// let mut macros = Macros::default();
+/// macros.start("glider");
+/// macros.record(event);
+/// macros.stop();
+/// game.handle_keys(macros.play("glider").unwrap_or_default());
+/// ```
+#[derive(Debug, Default)]
+pub struct Macros {
+    macros: BTreeMap<String, Vec<KeyEvent>>,
+    /// The name and the keys of the macro that is being recorded
+    recording: Option<(String, Vec<KeyEvent>)>,
+    /// The name of the macro that was recorded or played last
+    latest: Option<String>,
+}
+impl Macros {
+    /// Start recording a macro, dropping the one that was being recorded
+    pub fn start(&mut self, name: &str) {
+        self.recording = Some((name.to_string(), Vec::new()));
+    }
+    /// Stop recording and keep the macro, a macro without keys is dropped
+    ///
+    /// # Returns
+    ///
+    /// The name of the macro and the number of keys it has, `None` if nothing was being recorded
+    pub fn stop(&mut self) -> Option<(String, usize)> {
+        let (name, events) = self.recording.take()?;
+        let count = events.len();
+        if count > 0 {
+            self.macros.insert(name.clone(), events);
+            self.latest = Some(name.clone());
+        }
+        Some((name, count))
+    }
+    /// Add a key to the macro that is being recorded, released keys are left out
+    pub fn record(&mut self, event: KeyEvent) {
+        if let Some((_, events)) = self.recording.as_mut() {
+            if event.is_down && events.len() < MACRO_LIMIT {
+                events.push(event);
+            }
+        }
+    }
+    /// Get the name of the macro that is being recorded
+    pub fn recording(&self) -> Option<&str> {
+        self.recording.as_ref().map(|(name, _)| name.as_str())
+    }
+    /// Get the keys of a macro to play them, and remember it as the latest macro
+    pub fn play(&mut self, name: &str) -> Option<Vec<KeyEvent>> {
+        let events = self.macros.get(name)?.clone();
+        self.latest = Some(name.to_string());
+        Some(events)
+    }
+    /// Get the name of the macro that was recorded or played last
+    pub fn latest(&self) -> Option<&str> {
+        self.latest.as_deref()
+    }
+    /// Get the names of the macros in alphabetical order
+    pub fn names(&self) -> Vec<String> {
+        self.macros.keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::console::input_record::KeyEvent;
+
+    use super::{Macros, MACRO_LIMIT};
+
+    fn keys(events: &[KeyEvent]) -> String {
+        events.iter().map(|event| event.command).collect()
+    }
+
+    #[test]
+    fn record_and_play() {
+        let mut macros = Macros::default();
+        macros.record(KeyEvent::key_down('x'));
+        assert_eq!(macros.stop(), None, "Nothing is being recorded");
+
+        let released = KeyEvent {
+            is_down: false,
+            ..KeyEvent::key_down('a')
+        };
+        let test_cases = [
+            ("keys", "one", "ab", Some(("one", 2)), "one"),
+            ("another macro", "two", "e", Some(("two", 1)), "two"),
+            ("replace a macro", "one", "iij", Some(("one", 3)), "one"),
+            ("no keys", "three", "", Some(("three", 0)), "one"),
+        ];
+        for (test_case, name, typed, expected, latest) in test_cases {
+            macros.start(name);
+            assert_eq!(macros.recording(), Some(name), "{}", test_case);
+            for key in typed.chars() {
+                macros.record(KeyEvent::key_down(key));
+                macros.record(released);
+            }
+            let stopped = macros.stop();
+            assert_eq!(
+                stopped
+                    .as_ref()
+                    .map(|(name, count)| (name.as_str(), *count)),
+                expected,
+                "{}",
+                test_case
+            );
+            assert_eq!(macros.latest(), Some(latest), "{}", test_case);
+            assert_eq!(macros.recording(), None, "{}", test_case);
+        }
+        assert_eq!(macros.names(), vec!["one", "two"]);
+        assert_eq!(
+            macros.play("two").map(|events| keys(&events)),
+            Some("e".into())
+        );
+        assert_eq!(macros.latest(), Some("two"));
+        assert_eq!(macros.play("three"), None, "An empty macro is dropped");
+
+        macros.start("long");
+        for _ in 0..MACRO_LIMIT + 5 {
+            macros.record(KeyEvent::key_down('d'));
+        }
+        assert_eq!(macros.stop(), Some(("long".to_string(), MACRO_LIMIT)));
+    }
+}
@@ -0,0 +1,280 @@
+use super::{rule::Rule, settings::Boundary};
+
+/// The offsets of the eight neighbors of a cell
+const NEIGHBOR_OFFSETS: [(i64, i64); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Steps a board by keeping the number of living neighbors of every cell up to date, instead of
+/// counting them again for every cell of every generation
+///
+/// A cell can only change if it or one of its neighbors changed in the generation before, every
+/// other cell sees the same neighborhood as last time. Only the cells around the changes are
+/// evaluated, so the stable regions of a board cost nothing. The counts are changed by one for
+/// the neighbors of every cell that was born or died
+///
+/// The counter does not keep a copy of the board, the owner steps it with the board it produced
+/// last. A board that was changed in any other way, like by the editor, needs a new counter
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{conway::{neighbor_counts::NeighborCounts, settings::Boundary}, ConwaysGame, Rule};
+/// # let mut cells = ConwaysGame::headless(16, 16, 42).cells().clone();
+/// let mut counts = NeighborCounts::new(&cells, Rule::conway(), Boundary::Wrap);
+/// let mut next = cells.clone();
+/// let population = counts.step(&cells, &mut next);
+/// std::mem::swap(&mut cells, &mut next);
+/// ```
+#[derive(Clone, Debug)]
+pub struct NeighborCounts {
+    counts: Vec<Vec<u8>>,
+    rule: Rule,
+    boundary: Boundary,
+    /// The cells that were born or died in the last generation
+    changed: Vec<(usize, usize)>,
+    /// Every cell is evaluated on the first step, since nothing is known about the changes yet
+    is_fresh: bool,
+    /// Marks the cells that are evaluated in a step, so that a cell is evaluated once
+    is_queued: Vec<Vec<bool>>,
+    population: u64,
+}
+impl NeighborCounts {
+    /// Count the neighbors of every cell of a board
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the board, it has at least one row
+    /// * `rule` - decides which cells are born and which survive
+    /// * `boundary` - what is beyond the edges of the board
+    pub fn new(cells: &[Vec<bool>], rule: Rule, boundary: Boundary) -> Self {
+        let (x_len, y_len) = (cells[0].len(), cells.len());
+        let mut counts = NeighborCounts {
+            counts: vec![vec![0; x_len]; y_len],
+            rule,
+            boundary,
+            changed: Vec::new(),
+            is_fresh: true,
+            is_queued: vec![vec![false; x_len]; y_len],
+            population: 0,
+        };
+        for (y, row) in cells.iter().enumerate() {
+            for (x, is_alive) in row.iter().enumerate() {
+                if *is_alive {
+                    counts.population += 1;
+                    counts.add_to_neighbors(x, y, 1);
+                }
+            }
+        }
+        counts
+    }
+    /// Check if the counter steps with a rule and a boundary. The board is not checked, a counter
+    /// has to be dropped once its board is changed other than by `step`
+    pub fn matches(&self, rule: Rule, boundary: Boundary) -> bool {
+        self.rule == rule && self.boundary == boundary
+    }
+    /// Get the cells that were born or died in the last step, as `(x, y)`
+    pub fn changed(&self) -> &[(usize, usize)] {
+        &self.changed
+    }
+    /// Get the number of living neighbors of a cell
+    pub fn count(&self, x: usize, y: usize) -> u8 {
+        self.counts[y][x]
+    }
+    /// Calculate the next generation
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the board the counter was created with, or the board its last step produced
+    /// * `next` - gets the next generation. On the first step it is any board of the size of
+    ///   `cells`, after that it holds the generation before `cells`. Only the cells that changed
+    ///   in the last step and in this one are written
+    ///
+    /// # Returns
+    ///
+    /// The number of living cells of the next generation
+    pub fn step(&mut self, cells: &[Vec<bool>], next: &mut [Vec<bool>]) -> u64 {
+        let mut changed = Vec::new();
+        if self.is_fresh {
+            self.is_fresh = false;
+            for (row, cells) in next.iter_mut().zip(cells.iter()) {
+                row.copy_from_slice(cells);
+            }
+            for y in 0..cells.len() {
+                for x in 0..cells[y].len() {
+                    self.evaluate(x, y, cells, next, &mut changed);
+                }
+            }
+        } else {
+            let mut queued = Vec::new();
+            for &(x, y) in &self.changed {
+                // The generation before differs from `cells` by the cells of the last step
+                next[y][x] = cells[y][x];
+                let neighbors = NEIGHBOR_OFFSETS.map(|offset| self.neighbor(x, y, offset));
+                for (x, y) in std::iter::once((x, y)).chain(neighbors.into_iter().flatten()) {
+                    if !self.is_queued[y][x] {
+                        self.is_queued[y][x] = true;
+                        queued.push((x, y));
+                    }
+                }
+            }
+            for &(x, y) in &queued {
+                self.is_queued[y][x] = false;
+                self.evaluate(x, y, cells, next, &mut changed);
+            }
+        }
+        for &(x, y) in &changed {
+            match next[y][x] {
+                true => {
+                    self.population += 1;
+                    self.add_to_neighbors(x, y, 1);
+                }
+                false => {
+                    self.population -= 1;
+                    self.add_to_neighbors(x, y, -1);
+                }
+            }
+        }
+        self.changed = changed;
+        self.population
+    }
+    fn evaluate(
+        &self,
+        x: usize,
+        y: usize,
+        cells: &[Vec<bool>],
+        next: &mut [Vec<bool>],
+        changed: &mut Vec<(usize, usize)>,
+    ) {
+        let is_alive = self.rule.is_alive(cells[y][x], self.counts[y][x]);
+        next[y][x] = is_alive;
+        if is_alive != cells[y][x] {
+            changed.push((x, y));
+        }
+    }
+    /// Change the count of every neighbor of a cell. A neighbor that is reached through more than
+    /// one offset on a tiny wrapping board is changed once per offset, like it is counted
+    fn add_to_neighbors(&mut self, x: usize, y: usize, delta: i8) {
+        for offset in NEIGHBOR_OFFSETS {
+            if let Some((x, y)) = self.neighbor(x, y, offset) {
+                self.counts[y][x] = self.counts[y][x].wrapping_add_signed(delta);
+            }
+        }
+    }
+    /// Get the neighbor of a cell in a direction, `None` if it is beyond a dead edge
+    fn neighbor(&self, x: usize, y: usize, (dx, dy): (i64, i64)) -> Option<(usize, usize)> {
+        let (x_len, y_len) = (self.counts[0].len() as i64, self.counts.len() as i64);
+        let (x, y) = (x as i64 + dx, y as i64 + dy);
+        let is_outside = x < 0 || y < 0 || x >= x_len || y >= y_len;
+        if is_outside && self.boundary == Boundary::Dead {
+            return None;
+        }
+        Some((x.rem_euclid(x_len) as usize, y.rem_euclid(y_len) as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::channel;
+
+    use crate::{
+        conway::{conways_game::ConwaysGame, rule::Rule, settings::Boundary},
+        handler::memory_handle::MemoryHandle,
+    };
+
+    use super::NeighborCounts;
+
+    /// Count the neighbors of a cell of a new board
+    fn count(cells: &[Vec<bool>], x: usize, y: usize, boundary: Boundary) -> u8 {
+        let counts = NeighborCounts::new(cells, Rule::conway(), boundary);
+        counts.count(x, y)
+    }
+
+    #[test]
+    fn counts() {
+        // . # .
+        // # # .
+        // . . .
+        let cells = vec![
+            vec![false, true, false],
+            vec![true, true, false],
+            vec![false, false, false],
+        ];
+        let test_cases = [
+            ("centre wrap", (1, 1), Boundary::Wrap, 2),
+            ("centre dead", (1, 1), Boundary::Dead, 2),
+            ("corner wrap", (2, 2), Boundary::Wrap, 3),
+            ("corner dead", (2, 2), Boundary::Dead, 1),
+            ("top left dead", (0, 0), Boundary::Dead, 3),
+        ];
+        for (test_case, (x, y), boundary, expected) in test_cases {
+            assert_eq!(count(&cells, x, y, boundary), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn matches_the_full_scan() {
+        let test_cases = [
+            ("wrap", Boundary::Wrap, Rule::conway()),
+            ("dead", Boundary::Dead, Rule::conway()),
+            ("highlife", Boundary::Wrap, "B36/S23".parse().unwrap()),
+        ];
+        for (test_case, boundary, rule) in test_cases {
+            let (_, receiver) = channel();
+            let mut game = ConwaysGame::builder(17, 11)
+                .seed(8)
+                .rule(rule)
+                .boundary(boundary)
+                .receiver(receiver)
+                .handle(Box::new(MemoryHandle::new()))
+                .build()
+                .unwrap();
+            let mut counts = NeighborCounts::new(game.cells(), rule, boundary);
+            let mut cells = game.cells().clone();
+            let mut next = vec![vec![false; 17]; 11];
+            for round in 0..60 {
+                let population = counts.step(&cells, &mut next);
+                std::mem::swap(&mut cells, &mut next);
+                game.next_full_scan();
+                assert_eq!(&cells, game.cells(), "{} round {}", test_case, round);
+                let alive = cells.iter().flatten().filter(|is_alive| **is_alive).count();
+                assert_eq!(population, alive as u64, "{} round {}", test_case, round);
+            }
+        }
+    }
+
+    #[test]
+    fn tiny_wrapping_boards() {
+        for (x_len, y_len) in [(1, 1), (2, 1), (2, 2), (3, 1)] {
+            let mut cells = vec![vec![false; x_len]; y_len];
+            cells[0][0] = true;
+            let mut counts = NeighborCounts::new(&cells, Rule::conway(), Boundary::Wrap);
+            let mut next = cells.clone();
+            for _ in 0..4 {
+                counts.step(&cells, &mut next);
+                std::mem::swap(&mut cells, &mut next);
+                let fresh = NeighborCounts::new(&cells, Rule::conway(), Boundary::Wrap);
+                for y in 0..y_len {
+                    for x in 0..x_len {
+                        assert_eq!(
+                            counts.count(x, y),
+                            fresh.count(x, y),
+                            "{}x{} ({}, {})",
+                            x_len,
+                            y_len,
+                            x,
+                            y
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::{fmt::Display, time::Duration};
+#[cfg(feature = "serde")]
+use std::{fs, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use super::save::SaveError;
+use super::{
+    print_mode::PrintMode,
+    settings::{Boundary, BOUNDARIES},
+};
+use crate::rendering::{
+    hud::Menu,
+    theme::{Theme, THEMES},
+};
+
+/// The round durations the speed option steps through, from fast to slow
+pub const SPEEDS: [Duration; 6] = [
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_millis(1000),
+    Duration::from_millis(2000),
+];
+
+/// Move a round duration along `SPEEDS`, stopping at the fastest and the slowest speed
+///
+/// # Arguments
+///
+/// * `round_duration` - the current duration, a duration between two speeds counts as the slower
+///   one
+/// * `step` - the number of speeds to move, positive is faster
+pub fn step_speed(round_duration: Duration, step: i64) -> Duration {
+    let current = SPEEDS
+        .iter()
+        .position(|speed| *speed >= round_duration)
+        .unwrap_or(SPEEDS.len() - 1) as i64;
+    // Moving forward makes the game faster, so it picks a shorter duration
+    let index = (current - step).clamp(0, SPEEDS.len() as i64 - 1);
+    SPEEDS[index as usize]
+}
+
+/// The settings that can be changed on the options screen while the game runs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GameOptions {
+    pub round_duration: Duration,
+    pub boundary: Boundary,
+    pub theme: Theme,
+    pub print_mode: PrintMode,
+}
+
+/// The options as they are written to the options file. The values are stored by their names so
+/// that the file can be edited by hand
+#[cfg(feature = "serde")]
+#[derive(Debug, Serialize, Deserialize)]
+struct OptionsFile {
+    round_millis: u64,
+    boundary: String,
+    theme: String,
+    print_mode: String,
+}
+
+#[cfg(feature = "serde")]
+impl GameOptions {
+    /// Write the options to a file as JSON, through a temporary file like `SavedGame::save`
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        let file = OptionsFile {
+            round_millis: self.round_duration.as_millis() as u64,
+            boundary: self.boundary.to_string(),
+            theme: self.theme.to_string(),
+            print_mode: self.print_mode.to_string(),
+        };
+        let json = serde_json::to_string(&file).map_err(|_| SaveError::BadFormat)?;
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+    /// Read options that were written with `save`
+    pub fn load(path: &Path) -> Result<Self, SaveError> {
+        let json = fs::read_to_string(path).map_err(|_| SaveError::ReadFailed)?;
+        let file: OptionsFile = serde_json::from_str(&json).map_err(|_| SaveError::BadFormat)?;
+        if file.round_millis == 0 {
+            return Err(SaveError::BadFormat);
+        }
+        Ok(GameOptions {
+            round_duration: Duration::from_millis(file.round_millis),
+            boundary: file.boundary.parse().map_err(|_| SaveError::BadFormat)?,
+            theme: file.theme.parse().map_err(|_| SaveError::BadFormat)?,
+            print_mode: file.print_mode.parse().map_err(|_| SaveError::BadFormat)?,
+        })
+    }
+}
+
+/// The rows of the options screen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OptionItem {
+    Speed,
+    Boundary,
+    Theme,
+    RenderMode,
+}
+impl Display for OptionItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptionItem::Speed => write!(f, "Speed"),
+            OptionItem::Boundary => write!(f, "Boundary"),
+            OptionItem::Theme => write!(f, "Theme"),
+            OptionItem::RenderMode => write!(f, "Render"),
+        }
+    }
+}
+
+/// The rows of the options screen in the order they are listed
+pub const OPTION_ITEMS: [OptionItem; 4] = [
+    OptionItem::Speed,
+    OptionItem::Boundary,
+    OptionItem::Theme,
+    OptionItem::RenderMode,
+];
+
+/// The options screen, with the options that are being changed and the selected row
+#[derive(Debug)]
+pub struct OptionsScreen {
+    pub menu: Menu<OptionItem>,
+    pub options: GameOptions,
+}
+impl OptionsScreen {
+    pub fn new(options: GameOptions) -> Self {
+        OptionsScreen {
+            menu: Menu::new(&OPTION_ITEMS),
+            options,
+        }
+    }
+    /// Change the value of the selected row. The speed stops at the fastest and the slowest
+    /// speed, the other values wrap around
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - the number of values to move, negative moves back
+    pub fn change(&mut self, step: i64) {
+        let item = self.menu.selected();
+        let options = &mut self.options;
+        match item {
+            OptionItem::Speed => {
+                options.round_duration = step_speed(options.round_duration, step);
+            }
+            OptionItem::Boundary => {
+                options.boundary = cycle(&BOUNDARIES, options.boundary, step);
+            }
+            OptionItem::Theme => options.theme = cycle(&THEMES, options.theme, step),
+            OptionItem::RenderMode => {
+                let modes = [PrintMode::PRETTY, PrintMode::DEBUG];
+                options.print_mode = cycle(&modes, options.print_mode, step);
+            }
+        }
+    }
+    /// Describe the screen on a line per row, with an arrow in front of the selected row
+    pub fn lines(&self) -> Vec<String> {
+        self.menu.lines_with(|item| {
+            let value = match item {
+                OptionItem::Speed => format!("{} ms", self.options.round_duration.as_millis()),
+                OptionItem::Boundary => self.options.boundary.to_string(),
+                OptionItem::Theme => self.options.theme.to_string(),
+                OptionItem::RenderMode => self.options.print_mode.to_string(),
+            };
+            format!("{:<8} < {} >", item.to_string(), value)
+        })
+    }
+}
+
+/// Step through a list of values, wrapping around its ends
+fn cycle<T: PartialEq + Copy>(values: &[T], current: T, step: i64) -> T {
+    let index = values
+        .iter()
+        .position(|value| *value == current)
+        .unwrap_or(0) as i64;
+    values[(index + step).rem_euclid(values.len() as i64) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        conway::{print_mode::PrintMode, settings::Boundary},
+        rendering::theme::Theme,
+    };
+
+    use super::{GameOptions, OptionItem, OptionsScreen};
+
+    fn options() -> GameOptions {
+        GameOptions {
+            round_duration: Duration::from_millis(1000),
+            boundary: Boundary::Wrap,
+            theme: Theme::Classic,
+            print_mode: PrintMode::PRETTY,
+        }
+    }
+
+    #[test]
+    fn change() {
+        let test_cases = [
+            ("faster", 0, vec![1], "Speed", "500 ms"),
+            ("slower", 0, vec![-1], "Speed", "2000 ms"),
+            ("stops at the slowest", 0, vec![-1, -1], "Speed", "2000 ms"),
+            ("stops at the fastest", 0, vec![9], "Speed", "50 ms"),
+            ("boundary", 1, vec![1], "Boundary", "Dead"),
+            ("boundary wraps", 1, vec![1, 1], "Boundary", "Wrap"),
+            ("theme", 2, vec![-1], "Theme", "Ocean"),
+            ("render mode", 3, vec![1], "Render", "Debug"),
+            ("selection wraps", -1, vec![1], "Render", "Debug"),
+        ];
+        for (test_case, selection, steps, item, value) in test_cases {
+            let mut screen = OptionsScreen::new(options());
+            screen.menu.move_selection(selection);
+            for step in steps {
+                screen.change(step);
+            }
+            let line = &screen.lines()[screen.menu.selected() as usize];
+            assert_eq!(
+                line,
+                &format!("> {:<8} < {} >", item, value),
+                "{}",
+                test_case
+            );
+        }
+    }
+
+    #[test]
+    fn speed_between_steps() {
+        let mut screen = OptionsScreen::new(GameOptions {
+            round_duration: Duration::from_millis(300),
+            ..options()
+        });
+        assert_eq!(screen.menu.selected(), OptionItem::Speed);
+        screen.change(1);
+        assert_eq!(screen.options.round_duration, Duration::from_millis(250));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn save_and_load() {
+        use std::{env, fs, process};
+
+        use crate::conway::save::SaveError;
+
+        let saved = GameOptions {
+            round_duration: Duration::from_millis(250),
+            boundary: Boundary::Dead,
+            theme: Theme::Mono,
+            print_mode: PrintMode::DEBUG,
+        };
+        let path = env::temp_dir().join(format!("conway_options_test_{}.json", process::id()));
+        assert_eq!(saved.save(&path), Ok(()));
+        let loaded = GameOptions::load(&path);
+        fs::write(
+            &path,
+            r#"{"round_millis":0,"boundary":"Wrap","theme":"Mono","print_mode":"pretty"}"#,
+        )
+        .unwrap();
+        let zero_speed = GameOptions::load(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded, Ok(saved));
+        assert_eq!(zero_speed, Err(SaveError::BadFormat));
+        assert_eq!(GameOptions::load(&path), Err(SaveError::ReadFailed));
+    }
+}
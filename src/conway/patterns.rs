@@ -0,0 +1,185 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::shared::usize2d::Usize2d;
+
+/// A named pattern of live cells, drawn as rows of `#` (alive) and `.` (dead)
+#[derive(Debug, PartialEq)]
+pub struct Pattern {
+    pub name: &'static str,
+    pub rows: &'static [&'static str],
+}
+impl Pattern {
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+    /// Get the offsets of the live cells from the top left corner of the pattern
+    pub fn live_cells(&self) -> Vec<Usize2d> {
+        let mut cells = Vec::new();
+        for (y, row) in self.rows.iter().enumerate() {
+            for (x, cell) in row.bytes().enumerate() {
+                if cell == b'#' {
+                    cells.push(Usize2d::new(x, y));
+                }
+            }
+        }
+        cells
+    }
+}
+
+pub const GLIDER: Pattern = Pattern {
+    name: "glider",
+    rows: &[".#.", "..#", "###"],
+};
+pub const LWSS: Pattern = Pattern {
+    name: "lwss",
+    rows: &[".#..#", "#....", "#...#", "####."],
+};
+pub const PULSAR: Pattern = Pattern {
+    name: "pulsar",
+    rows: &[
+        "..###...###..",
+        ".............",
+        "#....#.#....#",
+        "#....#.#....#",
+        "#....#.#....#",
+        "..###...###..",
+        ".............",
+        "..###...###..",
+        "#....#.#....#",
+        "#....#.#....#",
+        "#....#.#....#",
+        ".............",
+        "..###...###..",
+    ],
+};
+pub const GOSPER_GLIDER_GUN: Pattern = Pattern {
+    name: "gosper-glider-gun",
+    rows: &[
+        "........................#...........",
+        "......................#.#...........",
+        "............##......##............##",
+        "...........#...#....##............##",
+        "##........#.....#...##..............",
+        "##........#...#.##....#.#...........",
+        "..........#.....#.......#...........",
+        "...........#...#....................",
+        "............##......................",
+    ],
+};
+pub const R_PENTOMINO: Pattern = Pattern {
+    name: "r-pentomino",
+    rows: &[".##", "##.", ".#."],
+};
+
+/// All patterns that are bundled with the game
+pub const PATTERNS: [&Pattern; 5] = [&GLIDER, &LWSS, &PULSAR, &GOSPER_GLIDER_GUN, &R_PENTOMINO];
+
+/// Find a bundled pattern by its name, ignoring case
+pub fn find_pattern(name: &str) -> Option<&'static Pattern> {
+    PATTERNS
+        .into_iter()
+        .find(|pattern| pattern.name.eq_ignore_ascii_case(name))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PatternErr {
+    UnknownPattern,
+    BadLocation,
+}
+impl Display for PatternErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownPattern => write!(f, "Unknown pattern"),
+            Self::BadLocation => write!(f, "The location should look like 'x,y'"),
+        }
+    }
+}
+
+/// A pattern and where to place it, parsed from arguments like `glider@10,10`
+///
+/// Without a location the pattern is placed in the center of the grid
+#[derive(Clone, Copy, Debug)]
+pub struct PatternPlacement {
+    pub pattern: &'static Pattern,
+    pub origin: Option<Usize2d>,
+}
+impl FromStr for PatternPlacement {
+    type Err = PatternErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, location) = match s.split_once('@') {
+            Some((name, location)) => (name, Some(location)),
+            None => (s, None),
+        };
+        let pattern = find_pattern(name.trim()).ok_or(PatternErr::UnknownPattern)?;
+        let origin = match location {
+            Some(location) => {
+                let (x, y) = location.split_once(',').ok_or(PatternErr::BadLocation)?;
+                let x = x.trim().parse().map_err(|_| PatternErr::BadLocation)?;
+                let y = y.trim().parse().map_err(|_| PatternErr::BadLocation)?;
+                Some(Usize2d::new(x, y))
+            }
+            None => None,
+        };
+        Ok(PatternPlacement { pattern, origin })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_pattern, PatternErr, PatternPlacement, GLIDER, PATTERNS, PULSAR};
+
+    #[test]
+    fn patterns_are_rectangular() {
+        for pattern in PATTERNS {
+            assert!(
+                pattern.rows.iter().all(|row| row.len() == pattern.width()),
+                "All rows of {} should have the same width",
+                pattern.name
+            );
+            assert!(
+                pattern
+                    .rows
+                    .iter()
+                    .all(|row| row.bytes().all(|c| c == b'#' || c == b'.')),
+                "{} should only contain '#' and '.'",
+                pattern.name
+            );
+        }
+        assert_eq!(GLIDER.live_cells().len(), 5);
+        assert_eq!(PULSAR.live_cells().len(), 48);
+        assert_eq!((PULSAR.width(), PULSAR.height()), (13, 13));
+    }
+
+    #[test]
+    fn parse_placement() {
+        let test_cases = vec![
+            (1, "glider@10,10", Ok(("glider", Some((10, 10))))),
+            (2, "LWSS@ 3, 4", Ok(("lwss", Some((3, 4))))),
+            (3, "pulsar", Ok(("pulsar", None))),
+            (4, "blinker@1,1", Err(PatternErr::UnknownPattern)),
+            (5, "glider@10", Err(PatternErr::BadLocation)),
+            (6, "glider@x,1", Err(PatternErr::BadLocation)),
+        ];
+        for (test_case, arg, expected) in test_cases {
+            let result = arg.parse::<PatternPlacement>().map(|placement| {
+                (
+                    placement.pattern.name,
+                    placement.origin.map(|origin| (origin.x, origin.y)),
+                )
+            });
+            assert_eq!(
+                result, expected,
+                "Test case {}: Parsing '{}' gave the wrong result",
+                test_case, arg
+            );
+        }
+        assert_eq!(
+            find_pattern("R-Pentomino").map(|p| p.name),
+            Some("r-pentomino")
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt::Display;
+
+use crate::rendering::hud::Menu;
+
+/// The choices of the pause menu
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PauseMenuItem {
+    Resume,
+    Save,
+    Load,
+    Options,
+    Scores,
+    Achievements,
+    Seed,
+    Quit,
+}
+impl Display for PauseMenuItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PauseMenuItem::Resume => write!(f, "Resume"),
+            PauseMenuItem::Save => write!(f, "Save"),
+            PauseMenuItem::Load => write!(f, "Load"),
+            PauseMenuItem::Options => write!(f, "Options"),
+            PauseMenuItem::Scores => write!(f, "High scores"),
+            PauseMenuItem::Achievements => write!(f, "Achievements"),
+            PauseMenuItem::Seed => write!(f, "New seed"),
+            PauseMenuItem::Quit => write!(f, "Quit"),
+        }
+    }
+}
+
+/// The items of the pause menu in the order they are listed
+pub const PAUSE_MENU_ITEMS: [PauseMenuItem; 8] = [
+    PauseMenuItem::Resume,
+    PauseMenuItem::Save,
+    PauseMenuItem::Load,
+    PauseMenuItem::Options,
+    PauseMenuItem::Scores,
+    PauseMenuItem::Achievements,
+    PauseMenuItem::Seed,
+    PauseMenuItem::Quit,
+];
+
+/// What the text that is typed into a text prompt is for
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextPrompt {
+    /// The seed the game starts over with
+    Seed,
+    /// The file the game is saved to
+    SavePath,
+    /// The name of the macro that is recorded
+    MacroName,
+    /// The name of the macro that is played
+    PlayMacro,
+}
+
+/// The menu that is shown over the paused game
+///
+/// Saving and the high scores need the serde feature, their items are disabled without it
+#[derive(Debug)]
+pub struct PauseMenu {
+    pub menu: Menu<PauseMenuItem>,
+    /// The result of the latest choice that kept the menu open, like a save
+    pub status: String,
+}
+impl Default for PauseMenu {
+    fn default() -> Self {
+        #[allow(unused_mut)]
+        let mut menu = Menu::new(&PAUSE_MENU_ITEMS);
+        #[cfg(not(feature = "serde"))]
+        {
+            menu.set_enabled(PauseMenuItem::Save, false);
+            menu.set_enabled(PauseMenuItem::Scores, false);
+        }
+        PauseMenu {
+            menu,
+            status: String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rendering::hud::Widget;
+
+    use super::{PauseMenu, PauseMenuItem};
+
+    #[test]
+    fn items() {
+        let mut menu = PauseMenu::default();
+        menu.menu.select(PauseMenuItem::Options);
+        let (save, scores) = match cfg!(feature = "serde") {
+            true => ("  Save", "  High scores"),
+            false => ("  (Save)", "  (High scores)"),
+        };
+        assert_eq!(
+            menu.menu.rows(),
+            vec![
+                "  Resume",
+                save,
+                "  Load",
+                "> Options",
+                scores,
+                "  Achievements",
+                "  New seed",
+                "  Quit"
+            ]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use core::fmt::Display;
 use core::str::FromStr;
 
-#[derive(PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum PrintMode {
     PRETTY,
     DEBUG,
@@ -18,8 +18,8 @@ impl FromStr for PrintMode {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "pretty" | "PRETTY" => Ok(PrintMode::PRETTY),
-            "debug" | "DEBUG" => Ok(PrintMode::DEBUG),
+            "pretty" | "Pretty" | "PRETTY" => Ok(PrintMode::PRETTY),
+            "debug" | "Debug" | "DEBUG" => Ok(PrintMode::DEBUG),
             _ => Err(format!("`{}` is not a valid mode", s)),
         }
     }
@@ -0,0 +1,155 @@
+//! Property based tests for the life engine, run against random boards generated by proptest
+use std::{collections::HashSet, sync::mpsc, time::Duration};
+
+use proptest::prelude::*;
+
+use crate::handler::memory_handle::MemoryHandle;
+
+use super::{conways_game::ConwaysGame, print_mode::PrintMode, rule::Rule};
+
+fn game(cells: Vec<Vec<bool>>) -> ConwaysGame {
+    let (_, receiver) = mpsc::channel();
+    let mut game = ConwaysGame::builder(cells[0].len(), cells.len())
+        .seed(0)
+        .render_mode(PrintMode::PRETTY)
+        .speed(Duration::from_secs(1))
+        .receiver(receiver)
+        .handle(Box::new(MemoryHandle::new()))
+        .build()
+        .unwrap();
+    game.set_cells(cells);
+    game
+}
+
+fn board() -> impl Strategy<Value = Vec<Vec<bool>>> {
+    (1usize..24, 1usize..24).prop_flat_map(|(x_len, y_len)| {
+        prop::collection::vec(prop::collection::vec(any::<bool>(), x_len), y_len)
+    })
+}
+
+fn rule() -> impl Strategy<Value = Rule> {
+    rule_with_birth_from(0)
+}
+
+/// Rules with `B0` fill every empty region at once, which a sparse set of live cells can not
+/// represent
+fn rule_with_birth_from(min_birth: u8) -> impl Strategy<Value = Rule> {
+    (
+        prop::collection::vec(min_birth..9, 0..9),
+        prop::collection::vec(0u8..9, 0..9),
+    )
+        .prop_map(|(birth, survival)| Rule::new(&birth, &survival))
+}
+
+/// Step a set of live cells on a wrapping grid, the sparse counterpart of `ConwaysGame::next`
+fn sparse_next(
+    live: &HashSet<(usize, usize)>,
+    x_len: usize,
+    y_len: usize,
+    rule: &Rule,
+) -> HashSet<(usize, usize)> {
+    let mut counts: std::collections::HashMap<(usize, usize), u8> = Default::default();
+    for (x, y) in live {
+        for x_delta in -1i64..=1 {
+            for y_delta in -1i64..=1 {
+                if x_delta == 0 && y_delta == 0 {
+                    continue;
+                }
+                let x_sibling = (*x as i64 + x_delta).rem_euclid(x_len as i64) as usize;
+                let y_sibling = (*y as i64 + y_delta).rem_euclid(y_len as i64) as usize;
+                *counts.entry((x_sibling, y_sibling)).or_default() += 1;
+            }
+        }
+    }
+    let mut next: HashSet<(usize, usize)> = counts
+        .iter()
+        .filter(|(cell, count)| rule.is_alive(live.contains(cell), **count))
+        .map(|(cell, _)| *cell)
+        .collect();
+    if rule.is_alive(true, 0) {
+        next.extend(live.iter().filter(|cell| !counts.contains_key(cell)));
+    }
+    next
+}
+
+fn to_sparse(cells: &[Vec<bool>]) -> HashSet<(usize, usize)> {
+    let mut live = HashSet::new();
+    for (y, row) in cells.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            if *cell {
+                live.insert((x, y));
+            }
+        }
+    }
+    live
+}
+
+/// Place a pattern, given as rows of `#` and `.`, on an empty grid at an offset
+fn place(pattern: &[&str], x_len: usize, y_len: usize, x: usize, y: usize) -> Vec<Vec<bool>> {
+    let mut cells = vec![vec![false; x_len]; y_len];
+    for (y_delta, row) in pattern.iter().enumerate() {
+        for (x_delta, cell) in row.bytes().enumerate() {
+            cells[y + y_delta][x + x_delta] = cell == b'#';
+        }
+    }
+    cells
+}
+
+const BLOCK: [&str; 2] = ["##", "##"];
+const BEEHIVE: [&str; 3] = [".##.", "#..#", ".##."];
+
+proptest! {
+    #[test]
+    fn population_never_exceeds_grid(cells in board(), rule in rule(), steps in 1usize..8) {
+        let size = cells.len() * cells[0].len();
+        let mut game = game(cells);
+        game.set_rule(rule);
+        for _ in 0..steps {
+            game.next();
+            prop_assert!(game.population() <= size);
+            prop_assert_eq!(game.cells().len() * game.cells()[0].len(), size);
+        }
+    }
+
+    #[test]
+    fn still_lifes_never_change(
+        (pattern, x_len, y_len, x, y) in prop_oneof![Just(&BLOCK[..]), Just(&BEEHIVE[..])]
+            .prop_flat_map(|pattern| {
+                let width = pattern[0].len();
+                let height = pattern.len();
+                (width + 2..20, height + 2..20).prop_flat_map(move |(x_len, y_len)| {
+                    (Just(pattern), Just(x_len), Just(y_len), 0..=x_len - width - 2, 0..=y_len - height - 2)
+                })
+            }),
+        steps in 1usize..6,
+    ) {
+        // Offset by one so that the margin of empty cells is on every side after wrapping
+        let cells = place(pattern, x_len, y_len, x + 1, y + 1);
+        let mut game = game(cells.clone());
+        for _ in 0..steps {
+            game.next();
+            prop_assert_eq!(game.cells(), &cells);
+        }
+    }
+
+    #[test]
+    fn rulestring_round_trips(rule in rule()) {
+        let rulestring = rule.to_string();
+        prop_assert_eq!(rulestring.parse::<Rule>(), Ok(rule));
+        prop_assert_eq!(rulestring.parse::<Rule>().map(|rule| rule.to_string()), Ok(rulestring));
+    }
+
+    #[test]
+    fn sparse_and_dense_agree(cells in board(), rule in rule_with_birth_from(1), steps in 1usize..6) {
+        let x_len = cells[0].len();
+        let y_len = cells.len();
+        let mut live = to_sparse(&cells);
+        let mut game = game(cells);
+        game.set_rule(rule);
+        for step in 0..steps {
+            game.next();
+            live = sparse_next(&live, x_len, y_len, &rule);
+            prop_assert_eq!(to_sparse(game.cells()), live.clone(), "step {}", step);
+        }
+    }
+}
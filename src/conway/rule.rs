@@ -0,0 +1,158 @@
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RuleError {
+    MissingBirth,
+    MissingSurvival,
+    BadNeighborCount,
+    /// A Generations rule needs the number of states after the birth counts
+    MissingStateCount,
+    /// A Generations rule needs at least the dead and the live state
+    BadStateCount,
+}
+
+/// A life-like rule that decides if a cell lives on by the number of its live neighbors
+///
+/// Rules are written as rulestrings in the B/S notation, e.g. `B3/S23` for Conway's game of life
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{conway::rule::RuleError, Rule};
+/// # fn main() -> Result<(), RuleError> {
+/// let high_life: Rule = "B36/S23".parse()?;
+/// assert!(high_life.is_alive(false, 6));
+/// assert_eq!(high_life.to_string(), "B36/S23");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::conway()
+    }
+}
+impl Rule {
+    /// The rule of Conway's game of life, `B3/S23`
+    pub fn conway() -> Self {
+        Rule::new(&[3], &[2, 3])
+    }
+    /// Create a rule from the neighbor counts that cause a birth and the counts that a live cell
+    /// survives with
+    pub fn new(birth: &[u8], survival: &[u8]) -> Self {
+        let mut rule = Rule {
+            birth: [false; 9],
+            survival: [false; 9],
+        };
+        for count in birth {
+            rule.birth[*count as usize] = true;
+        }
+        for count in survival {
+            rule.survival[*count as usize] = true;
+        }
+        rule
+    }
+    /// Decide if a cell is alive in the next generation
+    ///
+    /// # Arguments
+    ///
+    /// * `is_alive` - if the cell is alive in the current generation
+    /// * `live_siblings` - the number of live neighbors of the cell
+    pub fn is_alive(&self, is_alive: bool, live_siblings: u8) -> bool {
+        assert!(live_siblings < 9);
+        if is_alive {
+            return self.survival[live_siblings as usize];
+        }
+        self.birth[live_siblings as usize]
+    }
+}
+impl FromStr for Rule {
+    type Err = RuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (birth, survival) = s.split_once('/').ok_or(RuleError::MissingSurvival)?;
+        let birth = birth
+            .strip_prefix(['B', 'b'])
+            .ok_or(RuleError::MissingBirth)?;
+        let survival = survival
+            .strip_prefix(['S', 's'])
+            .ok_or(RuleError::MissingSurvival)?;
+        Ok(Rule::new(&parse_counts(birth)?, &parse_counts(survival)?))
+    }
+}
+fn parse_counts(counts: &str) -> Result<Vec<u8>, RuleError> {
+    counts
+        .chars()
+        .map(|count| match count.to_digit(10) {
+            Some(count) if count < 9 => Ok(count as u8),
+            _ => Err(RuleError::BadNeighborCount),
+        })
+        .collect()
+}
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format_counts = |counts: &[bool; 9]| -> String {
+            (0..9)
+                .filter(|count| counts[*count])
+                .map(|count| count.to_string())
+                .collect()
+        };
+        write!(
+            f,
+            "B{}/S{}",
+            format_counts(&self.birth),
+            format_counts(&self.survival)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::conways_law::conways_law;
+
+    use super::{Rule, RuleError};
+
+    #[test]
+    fn conway_matches_law() {
+        let rule = Rule::conway();
+        for is_alive in [true, false] {
+            for live_siblings in 0..9 {
+                assert_eq!(
+                    rule.is_alive(is_alive, live_siblings),
+                    conways_law(is_alive, live_siblings),
+                    "alive: {}, neighbors: {}",
+                    is_alive,
+                    live_siblings
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let test_cases = vec![
+            (1, "B3/S23", Ok(Rule::conway())),
+            (2, "b3/s32", Ok(Rule::conway())),
+            (3, "B36/S23", Ok(Rule::new(&[3, 6], &[2, 3]))),
+            (4, "B/S", Ok(Rule::new(&[], &[]))),
+            (5, "B3S23", Err(RuleError::MissingSurvival)),
+            (6, "3/S23", Err(RuleError::MissingBirth)),
+            (7, "B3/23", Err(RuleError::MissingSurvival)),
+            (8, "B39/S23", Err(RuleError::BadNeighborCount)),
+            (9, "B3/S2x", Err(RuleError::BadNeighborCount)),
+        ];
+        for (test_case, rulestring, expected) in test_cases {
+            assert_eq!(
+                rulestring.parse::<Rule>(),
+                expected,
+                "Test case {}: Parsing '{}' gave the wrong result",
+                test_case,
+                rulestring
+            );
+        }
+    }
+}
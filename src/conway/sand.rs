@@ -0,0 +1,250 @@
+use core::fmt::Display;
+
+use crate::rendering::colors::TerminalColors;
+
+use super::automaton::Automaton;
+
+/// The materials of the falling sand toy
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Material {
+    #[default]
+    Empty,
+    /// Falls straight down or slides off to the side, and sinks through water
+    Sand,
+    /// Falls like sand and spreads sideways when it can not fall
+    Water,
+    /// Never moves
+    Wall,
+}
+impl Material {
+    /// The materials in the order of their number keys, from 1
+    pub const ALL: [Material; 4] = [
+        Material::Sand,
+        Material::Water,
+        Material::Wall,
+        Material::Empty,
+    ];
+    /// Get the color the material is drawn in, `None` for empty cells
+    pub fn color(&self) -> Option<TerminalColors> {
+        match self {
+            Material::Empty => None,
+            Material::Sand => Some(TerminalColors::Yellow),
+            Material::Water => Some(TerminalColors::Blue),
+            Material::Wall => Some(TerminalColors::Grey),
+        }
+    }
+    /// Check if a grain of this material can move into a cell of another material
+    fn displaces(&self, other: Material) -> bool {
+        match self {
+            Material::Sand => matches!(other, Material::Empty | Material::Water),
+            Material::Water => other == Material::Empty,
+            Material::Empty | Material::Wall => false,
+        }
+    }
+}
+impl Display for Material {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Material::Empty => write!(f, "empty"),
+            Material::Sand => write!(f, "sand"),
+            Material::Water => write!(f, "water"),
+            Material::Wall => write!(f, "wall"),
+        }
+    }
+}
+
+/// A falling sand simulation, where every cell holds a material
+///
+/// Grains move at most one cell per generation. The grid does not wrap around its edges, the
+/// bottom row is the floor. The side a grain tries first alternates between the generations so
+/// that piles grow evenly
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::conway::{automaton::Automaton, sand::{Material, Sandbox}};
+/// let mut sandbox = Sandbox::new(40, 20);
+/// sandbox.paint(20, 0, Material::Sand);
+/// sandbox.next();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sandbox {
+    cells: Vec<Vec<Material>>,
+    rounds: u64,
+}
+impl Sandbox {
+    /// Create an empty sandbox
+    pub fn new(x_len: usize, y_len: usize) -> Self {
+        Sandbox::from_cells(vec![vec![Material::Empty; x_len]; y_len])
+    }
+    /// Create a sandbox from a grid
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the grid, indexed as `cells[y][x]`, with at least one row and column
+    pub fn from_cells(cells: Vec<Vec<Material>>) -> Self {
+        assert!(
+            !cells.is_empty() && !cells[0].is_empty(),
+            "The grid needs at least one row and one column"
+        );
+        Sandbox { cells, rounds: 0 }
+    }
+    /// Put a material in a cell, cells outside of the grid are ignored
+    pub fn paint(&mut self, x: usize, y: usize, material: Material) {
+        if let Some(cell) = self.cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell = material;
+        }
+    }
+    /// Empty every cell
+    pub fn clear(&mut self) {
+        let size = self.size();
+        self.cells = vec![vec![Material::Empty; size.x]; size.y];
+    }
+    /// Count the cells of a material
+    pub fn count(&self, material: Material) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|cell| **cell == material)
+            .count()
+    }
+    /// Get the number of generations since the sandbox was created
+    pub fn rounds(&self) -> u64 {
+        self.rounds
+    }
+    /// Find where a grain moves to, trying straight down first, then diagonally down and then,
+    /// for water, sideways
+    fn target(&self, x: usize, y: usize, sides: [isize; 2]) -> Option<(usize, usize)> {
+        let grain = self.cells[y][x];
+        let (x_len, y_len) = (self.cells[0].len(), self.cells.len());
+        let side = |side: isize| x.checked_add_signed(side).filter(|x| *x < x_len);
+        let mut candidates = vec![];
+        if y + 1 < y_len {
+            candidates.push(Some((x, y + 1)));
+            candidates.extend(sides.map(|dx| side(dx).map(|x| (x, y + 1))));
+        }
+        if grain == Material::Water {
+            candidates.extend(sides.map(|dx| side(dx).map(|x| (x, y))));
+        }
+        candidates
+            .into_iter()
+            .flatten()
+            .find(|(x, y)| grain.displaces(self.cells[*y][*x]))
+    }
+}
+impl Automaton for Sandbox {
+    type Cell = Material;
+    fn next(&mut self) {
+        let sides = match self.rounds % 2 {
+            0 => [-1, 1],
+            _ => [1, -1],
+        };
+        let (x_len, y_len) = (self.cells[0].len(), self.cells.len());
+        let mut has_moved = vec![vec![false; x_len]; y_len];
+        // Start at the floor so that a column of grains falls together
+        for y in (0..y_len).rev() {
+            for x in 0..x_len {
+                if has_moved[y][x] {
+                    continue;
+                }
+                let Some((to_x, to_y)) = self.target(x, y, sides) else {
+                    continue;
+                };
+                let displaced = self.cells[to_y][to_x];
+                self.cells[to_y][to_x] = self.cells[y][x];
+                self.cells[y][x] = displaced;
+                has_moved[to_y][to_x] = true;
+                has_moved[y][x] = displaced != Material::Empty;
+            }
+        }
+        self.rounds += 1;
+    }
+    fn cells(&self) -> &Vec<Vec<Material>> {
+        &self.cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::automaton::Automaton;
+
+    use super::{Material, Sandbox};
+
+    fn grid(rows: &[&str]) -> Vec<Vec<Material>> {
+        rows.iter()
+            .map(|row| {
+                row.chars()
+                    .map(|cell| match cell {
+                        's' => Material::Sand,
+                        'w' => Material::Water,
+                        '#' => Material::Wall,
+                        _ => Material::Empty,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn materials_move() {
+        let test_cases = [
+            (
+                "sand falls",
+                vec!["...", ".s.", "..."],
+                vec!["...", "...", ".s."],
+            ),
+            (
+                "sand slides left first",
+                vec!["...", ".s.", ".#."],
+                vec!["...", "...", "s#."],
+            ),
+            (
+                "sand rests on a pile",
+                vec!["...", ".s.", "###"],
+                vec!["...", ".s.", "###"],
+            ),
+            (
+                "sand sinks through water",
+                vec!["...", ".s.", "#w#"],
+                vec!["...", ".w.", "#s#"],
+            ),
+            (
+                "water spreads",
+                vec!["...", ".w.", "###"],
+                vec!["...", "w..", "###"],
+            ),
+            (
+                "walls stay",
+                vec![".#.", "...", "..."],
+                vec![".#.", "...", "..."],
+            ),
+            (
+                "a column falls together",
+                vec![".s.", ".s.", "..."],
+                vec!["...", ".s.", ".s."],
+            ),
+        ];
+        for (test_case, cells, expected) in test_cases {
+            let mut sandbox = Sandbox::from_cells(grid(&cells));
+            sandbox.next();
+            assert_eq!(sandbox.cells(), &grid(&expected), "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn piles_grow_evenly() {
+        let mut sandbox = Sandbox::new(5, 3);
+        for _ in 0..3 {
+            sandbox.paint(2, 0, Material::Sand);
+            sandbox.next();
+            sandbox.next();
+        }
+        sandbox.next();
+        assert_eq!(sandbox.cells(), &grid(&[".....", ".....", ".sss."]));
+        assert_eq!(sandbox.count(Material::Sand), 3);
+        sandbox.paint(9, 9, Material::Wall);
+        sandbox.clear();
+        assert_eq!(sandbox.count(Material::Empty), 15);
+        assert_eq!(sandbox.rounds(), 7);
+    }
+}
@@ -0,0 +1,151 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::rule::Rule;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SaveError {
+    ReadFailed,
+    WriteFailed,
+    BadFormat,
+    BadRule,
+    BadGrid,
+}
+impl From<std::io::Error> for SaveError {
+    fn from(_value: std::io::Error) -> Self {
+        Self::WriteFailed
+    }
+}
+
+/// The state of a game that can be written to a file and picked up again later
+///
+/// The cells are stored as one string per row with `#` for a live cell and `.` for a dead cell,
+/// so that saved games can be read and edited by hand
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub seed: u64,
+    pub rule: String,
+    pub rounds: u64,
+    pub cells: Vec<String>,
+}
+impl SavedGame {
+    /// Capture a generation of a game
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the cells of the generation, indexed as `cells[y][x]`
+    /// * `seed` - the seed the game was started with
+    /// * `rule` - the rule the game is stepped with
+    /// * `rounds` - the number of rounds that were played
+    pub fn new(cells: &[Vec<bool>], seed: u64, rule: Rule, rounds: u64) -> Self {
+        SavedGame {
+            seed,
+            rule: rule.to_string(),
+            rounds,
+            cells: cells
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|is_alive| if *is_alive { '#' } else { '.' })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+    /// Get the cells, indexed as `cells[y][x]`
+    ///
+    /// # Returns
+    ///
+    /// `SaveError::BadGrid` if the grid is empty, the rows differ in length or have other
+    /// characters than `#` and `.`
+    pub fn grid(&self) -> Result<Vec<Vec<bool>>, SaveError> {
+        let width = self.cells.first().map(|row| row.len()).unwrap_or(0);
+        if width == 0 {
+            return Err(SaveError::BadGrid);
+        }
+        self.cells
+            .iter()
+            .map(|row| {
+                if row.len() != width {
+                    return Err(SaveError::BadGrid);
+                }
+                row.chars()
+                    .map(|cell| match cell {
+                        '#' => Ok(true),
+                        '.' => Ok(false),
+                        _ => Err(SaveError::BadGrid),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+    /// Get the rule the game is stepped with
+    pub fn rule(&self) -> Result<Rule, SaveError> {
+        self.rule.parse().map_err(|_| SaveError::BadRule)
+    }
+    /// Write the game to a file as JSON. The game is written to a temporary file next to `path`
+    /// first, so that a crash while writing never leaves a half written save behind
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        let json = serde_json::to_string(self).map_err(|_| SaveError::BadFormat)?;
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+    /// Read a game that was written with `save`
+    pub fn load(path: &Path) -> Result<Self, SaveError> {
+        let json = fs::read_to_string(path).map_err(|_| SaveError::ReadFailed)?;
+        let saved: SavedGame = serde_json::from_str(&json).map_err(|_| SaveError::BadFormat)?;
+        saved.grid()?;
+        saved.rule()?;
+        Ok(saved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::conway::rule::Rule;
+
+    use super::{SaveError, SavedGame};
+
+    #[test]
+    fn save_and_load() {
+        let cells = vec![vec![true, false, false], vec![false, true, true]];
+        let saved = SavedGame::new(&cells, 42, "B36/S23".parse().unwrap(), 7);
+        assert_eq!(saved.cells, vec!["#..", ".##"]);
+
+        let path = env::temp_dir().join(format!("conway_save_test_{}.json", process::id()));
+        assert_eq!(saved.save(&path), Ok(()));
+        let loaded = SavedGame::load(&path);
+        let _ = fs::remove_file(&path);
+
+        let loaded = loaded.expect("The saved game should load");
+        assert_eq!(loaded, saved);
+        assert_eq!(loaded.grid(), Ok(cells));
+        assert_eq!(loaded.rule(), Ok(Rule::new(&[3, 6], &[2, 3])));
+    }
+
+    #[test]
+    fn bad_saves() {
+        let saved = |rule: &str, cells: Vec<&str>| SavedGame {
+            seed: 0,
+            rule: rule.to_string(),
+            rounds: 0,
+            cells: cells.into_iter().map(String::from).collect(),
+        };
+        assert_eq!(saved("B3/S23", vec![]).grid(), Err(SaveError::BadGrid));
+        assert_eq!(
+            saved("B3/S23", vec!["#.", "#"]).grid(),
+            Err(SaveError::BadGrid)
+        );
+        assert_eq!(saved("B3/S23", vec!["#x"]).grid(), Err(SaveError::BadGrid));
+        assert_eq!(saved("3/23", vec!["#"]).rule(), Err(SaveError::BadRule));
+        assert_eq!(
+            SavedGame::load(&env::temp_dir().join("conway_save_test_missing.json")),
+            Err(SaveError::ReadFailed)
+        );
+    }
+}
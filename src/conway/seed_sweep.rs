@@ -0,0 +1,275 @@
+use std::{
+    cmp::Reverse,
+    fmt::Display,
+    io::{self, Write},
+    ops::Range,
+    str::FromStr,
+    time::Instant,
+};
+
+use crate::{
+    rendering::hud::{Column, Table, Widget},
+    utils::progress::Progress,
+};
+
+use super::conways_game::ConwaysGame;
+
+/// The number of seeds that are listed for each ranking in the report
+pub const REPORT_LENGTH: usize = 5;
+
+#[derive(Debug, PartialEq)]
+pub enum SeedSweepErr {
+    BadRange,
+    EmptyRange,
+}
+impl Display for SeedSweepErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BadRange => write!(f, "The seeds should look like 'start..end'"),
+            Self::EmptyRange => write!(f, "The end seed should be after the start seed"),
+        }
+    }
+}
+
+/// The seeds of a sweep, parsed from arguments like `0..100`. The end is not included
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeedRange(pub Range<u64>);
+impl FromStr for SeedRange {
+    type Err = SeedSweepErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once("..").ok_or(SeedSweepErr::BadRange)?;
+        let start: u64 = start.trim().parse().map_err(|_| SeedSweepErr::BadRange)?;
+        let end: u64 = end.trim().parse().map_err(|_| SeedSweepErr::BadRange)?;
+        if end <= start {
+            return Err(SeedSweepErr::EmptyRange);
+        }
+        Ok(SeedRange(start..end))
+    }
+}
+
+/// How a board that was filled from one seed played out
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SeedResult {
+    pub seed: u64,
+    /// The number of generations until the board was stable, or the cap
+    pub generations: u64,
+    pub peak_population: u64,
+    pub final_population: u64,
+    pub is_stable: bool,
+}
+
+/// Runs a headless game for every seed in a range and collects how each of them played out
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::fs::File;
+/// # use tic_tac_toe2::conway::seed_sweep::{SeedRange, SeedSweep};
+/// # fn main() -> Result<(), std::io::Error> {
+/// let sweep = SeedSweep::new(32, 32, 1000);
+/// let results = sweep.run(SeedRange(0..100));
+/// sweep.write_csv(&results, &mut File::create("sweep.csv")?)?;
+/// println!("{}", SeedSweep::report(&results));
+/// # Ok(())
+/// # }
+/// ```
+pub struct SeedSweep {
+    pub x_len: usize,
+    pub y_len: usize,
+    /// The number of generations after which a board that is not stable yet is stopped
+    pub generation_cap: u64,
+}
+impl SeedSweep {
+    pub fn new(x_len: usize, y_len: usize, generation_cap: u64) -> Self {
+        SeedSweep {
+            x_len,
+            y_len,
+            generation_cap,
+        }
+    }
+    /// Run the board of a single seed until it is stable or the cap is reached
+    pub fn run_seed(&self, seed: u64) -> SeedResult {
+        self.play(seed).0
+    }
+    /// Run the board of a single seed like `run_seed`, keeping the game to look at the final
+    /// generation
+    pub fn play(&self, seed: u64) -> (SeedResult, ConwaysGame) {
+        let mut game = ConwaysGame::headless(self.x_len, self.y_len, seed);
+        let initial_population = game.population() as u64;
+        game.fast_forward(self.generation_cap);
+        let summary = game.summary();
+        let result = SeedResult {
+            seed,
+            generations: summary.generations,
+            peak_population: summary.peak_population.max(initial_population),
+            final_population: game.population() as u64,
+            is_stable: game.is_stable(),
+        };
+        (result, game)
+    }
+    /// Run the boards of all seeds in a range, in order
+    pub fn run(&self, seeds: SeedRange) -> Vec<SeedResult> {
+        let progress = Progress::new(seeds.0.end.saturating_sub(seeds.0.start), Instant::now());
+        self.run_with_progress(seeds, &progress)
+    }
+    /// Run the boards of all seeds in a range like `run`, counting every seed that is done, so
+    /// that another thread can show how far the sweep got
+    pub fn run_with_progress(&self, seeds: SeedRange, progress: &Progress) -> Vec<SeedResult> {
+        seeds
+            .0
+            .map(|seed| {
+                let result = self.run_seed(seed);
+                progress.advance(1);
+                result
+            })
+            .collect()
+    }
+    /// Write the results as CSV with a header row
+    pub fn write_csv(&self, results: &[SeedResult], writer: &mut dyn Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            "seed,x_len,y_len,generations,peak_population,final_population,is_stable"
+        )?;
+        for result in results {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{}",
+                result.seed,
+                self.x_len,
+                self.y_len,
+                result.generations,
+                result.peak_population,
+                result.final_population,
+                result.is_stable
+            )?;
+        }
+        Ok(())
+    }
+    /// List the seeds that lived the longest and that reached the largest populations, as
+    /// tables of the best `REPORT_LENGTH` seeds
+    pub fn report(results: &[SeedResult]) -> String {
+        let mut lines = vec![format!("Ran {} seeds", results.len())];
+        let mut ranked = results.to_vec();
+        // Ties are listed with the lowest seed first
+        ranked.sort_by_key(|result| (Reverse(result.generations), result.seed));
+        let longest: Vec<Vec<String>> = ranked
+            .iter()
+            .take(REPORT_LENGTH)
+            .map(|result| {
+                let state = if result.is_stable {
+                    "stable"
+                } else {
+                    "still active"
+                };
+                vec![
+                    result.seed.to_string(),
+                    result.generations.to_string(),
+                    state.to_string(),
+                ]
+            })
+            .collect();
+        let columns = vec![
+            Column::right("Seed"),
+            Column::right("Generations"),
+            Column::left("State"),
+        ];
+        lines.push("Longest lived:".to_string());
+        lines.extend(Table::new(columns, || longest.clone()).rows());
+
+        ranked.sort_by_key(|result| (Reverse(result.peak_population), result.seed));
+        let largest: Vec<Vec<String>> = ranked
+            .iter()
+            .take(REPORT_LENGTH)
+            .map(|result| vec![result.seed.to_string(), result.peak_population.to_string()])
+            .collect();
+        let columns = vec![Column::right("Seed"), Column::right("Cells")];
+        lines.push("Largest populations:".to_string());
+        lines.extend(Table::new(columns, || largest.clone()).rows());
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use crate::utils::progress::Progress;
+
+    use super::{SeedRange, SeedResult, SeedSweep, SeedSweepErr};
+
+    #[test]
+    fn parse_range() {
+        let test_cases = vec![
+            (1, "0..10", Ok(SeedRange(0..10))),
+            (2, " 5 .. 7 ", Ok(SeedRange(5..7))),
+            (3, "7..7", Err(SeedSweepErr::EmptyRange)),
+            (4, "10", Err(SeedSweepErr::BadRange)),
+            (5, "a..b", Err(SeedSweepErr::BadRange)),
+        ];
+        for (test_case, input, expected) in test_cases {
+            assert_eq!(
+                input.parse::<SeedRange>(),
+                expected,
+                "Test case {}: '{}' was parsed wrong",
+                test_case,
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn run_seeds() {
+        let sweep = SeedSweep::new(8, 8, 50);
+        let results = sweep.run("3..6".parse().unwrap());
+        assert_eq!(
+            results.iter().map(|result| result.seed).collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        for result in results.iter() {
+            assert!(result.generations <= 50, "The cap should be respected");
+            assert!(result.is_stable || result.generations == 50);
+            assert!(result.peak_population >= result.final_population);
+        }
+        assert_eq!(
+            sweep.run_seed(4),
+            results[1],
+            "The same seed should play out the same way"
+        );
+
+        let progress = Progress::new(3, Instant::now());
+        let tracked = sweep.run_with_progress("3..6".parse().unwrap(), &progress);
+        assert_eq!(tracked, results);
+        assert_eq!(progress.get(), (3, 3), "Every seed is counted");
+    }
+
+    #[test]
+    fn csv_and_report() {
+        let result = |seed, generations, peak_population, is_stable| SeedResult {
+            seed,
+            generations,
+            peak_population,
+            final_population: 0,
+            is_stable,
+        };
+        let results = vec![result(0, 12, 30, true), result(1, 50, 20, false)];
+        let sweep = SeedSweep::new(8, 6, 50);
+
+        let mut csv = Vec::new();
+        sweep.write_csv(&results, &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "seed,x_len,y_len,generations,peak_population,final_population,is_stable\n\
+             0,8,6,12,30,0,true\n\
+             1,8,6,50,20,0,false\n"
+        );
+        assert_eq!(
+            SeedSweep::report(&results),
+            "Ran 2 seeds\n\
+             Longest lived:\n\
+             Seed Generations State\n   1          50 still active\n   0          12 stable\n\
+             Largest populations:\n\
+             Seed Cells\n   0    30\n   1    20"
+        );
+    }
+}
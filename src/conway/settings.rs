@@ -0,0 +1,144 @@
+use std::{fmt::Display, path::PathBuf, str::FromStr, time::Duration};
+
+#[cfg(feature = "export-image")]
+use crate::export::image::ImageOptions;
+use crate::{
+    panel::layout::PanelLayout,
+    rendering::theme::Theme,
+    shared::usize2d::{Coord, Usize2d},
+};
+
+use super::rule::Rule;
+
+/// What is beyond the edges of the grid when the neighbors of a cell are counted
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Boundary {
+    /// The edges wrap around to the other side of the grid
+    #[default]
+    Wrap,
+    /// The cells beyond the edges are always dead
+    Dead,
+}
+/// All boundaries in the order they are cycled through
+pub const BOUNDARIES: [Boundary; 2] = [Boundary::Wrap, Boundary::Dead];
+impl Display for Boundary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Boundary::Wrap => write!(f, "Wrap"),
+            Boundary::Dead => write!(f, "Dead"),
+        }
+    }
+}
+impl FromStr for Boundary {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wrap" | "Wrap" => Ok(Boundary::Wrap),
+            "dead" | "Dead" => Ok(Boundary::Dead),
+            _ => Err(format!("`{}` is not a valid boundary", s)),
+        }
+    }
+}
+
+pub struct ConwaysSettings {
+    pub x_len: usize,
+    pub y_len: usize,
+    pub cell_view_width: usize,
+    pub cell_view_height: usize,
+    pub round_duration: Duration,
+    /// The offset of the board from where the layout places it
+    pub origin: Coord,
+    /// The padding around the board and where a board that is smaller than the screen is placed
+    pub layout: PanelLayout,
+    /// The first visible cell when the board does not fit on the screen
+    pub scroll: Coord,
+    /// The size of the screen in characters, `None` draws the whole board
+    pub screen_size: Option<Usize2d>,
+    pub seed: u64,
+    pub rule: Rule,
+    /// The number of generations the heatmap is colored by
+    pub heatmap_window: usize,
+    /// The number of generations that can be scrubbed through with the time travel
+    pub history_length: usize,
+    /// The number of key events that are listed in the input log
+    pub input_log_length: usize,
+    /// The number of generations between the checkpoints of the session timeline, 0 for none
+    pub timeline_checkpoint_interval: u64,
+    /// Pause once the board is stable or repeats itself instead of stopping the game
+    pub auto_pause: bool,
+    /// The longest period of an oscillator or spaceship that pauses the game
+    pub cycle_max_period: usize,
+    /// The number of generations that are skipped by a fast forward
+    pub skip_generations: u64,
+    /// The number of generations that are fast forwarded when the game starts
+    pub start_skip: u64,
+    /// The time between writes of the autosave
+    pub autosave_interval: Duration,
+    /// The file the game is written to when it is saved from the pause menu
+    pub save_path: PathBuf,
+    /// The file the clipboard of the editor is written to as RLE
+    pub clipboard_path: PathBuf,
+    /// The file the choices of the options screen are written to
+    pub options_path: PathBuf,
+    /// The file the high scores are kept in
+    pub scores_path: PathBuf,
+    /// The file the achievements are kept in
+    pub achievements_path: PathBuf,
+    /// The directory screenshots, frame dumps and recordings are written to
+    pub export_dir: PathBuf,
+    /// The number of generations a recording holds
+    #[cfg(feature = "export-image")]
+    pub gif_generations: usize,
+    /// The size in pixels of a character in the frames of a recording
+    #[cfg(feature = "export-image")]
+    pub gif_options: ImageOptions,
+    /// How long a generation is shown in a recording
+    #[cfg(feature = "export-image")]
+    pub gif_frame_delay: Duration,
+    pub boundary: Boundary,
+    pub theme: Theme,
+}
+impl ConwaysSettings {
+    pub fn init(x_len: usize, y_len: usize, duration: Duration, seed: u64) -> Self {
+        ConwaysSettings {
+            x_len,
+            y_len,
+            cell_view_width: 3,
+            cell_view_height: 2,
+            round_duration: duration,
+            origin: Coord::default(),
+            layout: PanelLayout::centered(0),
+            scroll: Coord::default(),
+            screen_size: None,
+            seed,
+            rule: Rule::conway(),
+            heatmap_window: 32,
+            history_length: 256,
+            input_log_length: 8,
+            timeline_checkpoint_interval: 100,
+            auto_pause: false,
+            cycle_max_period: 30,
+            skip_generations: 100,
+            start_skip: 0,
+            autosave_interval: Duration::from_secs(30),
+            save_path: PathBuf::from("conways_game.json"),
+            clipboard_path: PathBuf::from("clipboard.rle"),
+            options_path: PathBuf::from("conways_options.json"),
+            scores_path: PathBuf::from("scores.json"),
+            achievements_path: PathBuf::from("achievements.json"),
+            export_dir: PathBuf::from("."),
+            #[cfg(feature = "export-image")]
+            gif_generations: 100,
+            #[cfg(feature = "export-image")]
+            gif_options: ImageOptions {
+                cell_width: 4,
+                cell_height: 8,
+                draw_glyphs: false,
+            },
+            #[cfg(feature = "export-image")]
+            gif_frame_delay: Duration::from_millis(100),
+            boundary: Boundary::Wrap,
+            theme: Theme::Classic,
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use std::{
+    io::{Read, Write},
+    process::{Child, Command, Stdio},
+    sync::{mpsc, Arc, Mutex, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::{clipboard::Clipboard, rule::Rule};
+
+/// How long a clipboard command may run before it is stopped, the game waits for it
+const COMMAND_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Why the system clipboard could not be used
+#[derive(Debug, PartialEq, Eq)]
+pub enum SystemClipboardError {
+    /// The clipboard command could not be started, like when it is not installed
+    Unavailable,
+    /// The clipboard command failed
+    Failed,
+}
+
+/// The clipboard of the desktop, used through the commands the platform has for it
+///
+/// # Example
+///
+/// ```no_run
+/// # use tic_tac_toe2::conway::shared_clipboard::{SystemClipboard, SystemClipboardError};
+/// # fn main() -> Result<(), SystemClipboardError> {
+/// if let Some(system) = SystemClipboard::detect() {
+///     system.write("x = 3, y = 1\n3o!\n")?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemClipboard {
+    /// The program and the arguments that read the text to copy from their input
+    copy: Vec<String>,
+    /// The program and the arguments that write the copied text to their output
+    paste: Vec<String>,
+}
+impl SystemClipboard {
+    /// Use a clipboard through commands
+    ///
+    /// # Arguments
+    ///
+    /// * `copy` - the program and the arguments that read the text to copy from their input
+    /// * `paste` - the program and the arguments that write the copied text to their output
+    pub fn new(copy: &[&str], paste: &[&str]) -> Self {
+        let command = |parts: &[&str]| parts.iter().map(|part| part.to_string()).collect();
+        SystemClipboard {
+            copy: command(copy),
+            paste: command(paste),
+        }
+    }
+    /// Find the clipboard commands of the platform: `pbcopy` on macOS, `clip` and PowerShell on
+    /// Windows, `wl-copy` under Wayland and `xclip` under X11. `None` without a desktop, like
+    /// over SSH
+    pub fn detect() -> Option<Self> {
+        SystemClipboard::detect_with(std::env::consts::OS, |name| {
+            std::env::var_os(name).is_some_and(|value| !value.is_empty())
+        })
+    }
+    /// Put text on the clipboard
+    ///
+    /// # Returns
+    ///
+    /// `SystemClipboardError::Failed` when the command fails or does not finish in time
+    pub fn write(&self, text: &str) -> Result<(), SystemClipboardError> {
+        // The output is not piped: `xclip` keeps running in the background to serve the
+        // selection and would hold it open
+        let mut child = SystemClipboard::command(&self.copy)?
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|_| SystemClipboardError::Unavailable)?;
+        let stdin = child.stdin.take();
+        let text = text.to_string();
+        SystemClipboard::finish(child, move || {
+            stdin
+                .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok())
+                .then_some(String::new())
+        })
+        .map(|_| ())
+    }
+    /// Get the text on the clipboard
+    ///
+    /// # Returns
+    ///
+    /// `SystemClipboardError::Failed` when the command fails or does not finish in time
+    pub fn read(&self) -> Result<String, SystemClipboardError> {
+        let mut child = SystemClipboard::command(&self.paste)?
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|_| SystemClipboardError::Unavailable)?;
+        let stdout = child.stdout.take();
+        SystemClipboard::finish(child, move || {
+            let mut output = Vec::new();
+            stdout
+                .is_some_and(|mut stdout| stdout.read_to_end(&mut output).is_ok())
+                .then(|| String::from_utf8_lossy(&output).into_owned())
+        })
+    }
+    /// Wait for a clipboard command, stopping it once `COMMAND_TIMEOUT` has passed
+    ///
+    /// # Arguments
+    ///
+    /// * `child` - the started command
+    /// * `pipe` - writes the input or reads the output of the command on a worker thread, so that
+    ///   a command that does not read or write can not block the game
+    fn finish(
+        mut child: Child,
+        pipe: impl FnOnce() -> Option<String> + Send + 'static,
+    ) -> Result<String, SystemClipboardError> {
+        let deadline = Instant::now() + COMMAND_TIMEOUT;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || sender.send(pipe()));
+        let piped = receiver
+            .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            .ok()
+            .flatten();
+        let status = loop {
+            match child.try_wait() {
+                Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(5)),
+                Ok(Some(status)) => break Some(status),
+                _ => break None,
+            }
+        };
+        if status.is_none() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        match (piped, status.is_some_and(|status| status.success())) {
+            (Some(text), true) => Ok(text),
+            _ => Err(SystemClipboardError::Failed),
+        }
+    }
+    /// Find the clipboard commands of an operating system, with the environment variables that
+    /// are set
+    fn detect_with(os: &str, is_set: impl Fn(&str) -> bool) -> Option<Self> {
+        match os {
+            "macos" => Some(SystemClipboard::new(&["pbcopy"], &["pbpaste"])),
+            "windows" => Some(SystemClipboard::new(
+                &["clip"],
+                &["powershell", "-NoProfile", "-Command", "Get-Clipboard"],
+            )),
+            _ if is_set("WAYLAND_DISPLAY") => {
+                Some(SystemClipboard::new(&["wl-copy"], &["wl-paste", "-n"]))
+            }
+            _ if is_set("DISPLAY") => Some(SystemClipboard::new(
+                &["xclip", "-selection", "clipboard"],
+                &["xclip", "-selection", "clipboard", "-o"],
+            )),
+            _ => None,
+        }
+    }
+    fn command(parts: &[String]) -> Result<Command, SystemClipboardError> {
+        let (program, args) = parts
+            .split_first()
+            .ok_or(SystemClipboardError::Unavailable)?;
+        let mut command = Command::new(program);
+        command.args(args);
+        Ok(command)
+    }
+}
+
+/// The clipboard that the editor and the scenes share, so that a region copied in one of them
+/// can be pasted in the others
+///
+/// Clones share the copied pattern. With a system clipboard the pattern is also copied there as
+/// RLE, and a pattern on the system clipboard is pasted before the copied one, so that patterns
+/// can be pasted from websites and other programs
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::sync::mpsc;
+/// # use tic_tac_toe2::{
+/// #     conway::{
+/// #         conways_game::ConwaysGame,
+/// #         rule::Rule,
+/// #         settings::Boundary,
+/// #         shared_clipboard::{SharedClipboard, SystemClipboard},
+/// #     },
+/// #     scene::race_mode::{RaceMode, Racer},
+/// #     shared::usize2d::Usize2d,
+/// #     SharedHandle,
+/// # };
+/// # let (_sender, receiver) = mpsc::channel();
+/// # let racer = Racer::new(Rule::conway(), Boundary::Wrap);
+/// let clipboard = SharedClipboard::new(SystemClipboard::detect());
+/// let game = ConwaysGame::builder(40, 20)
+///     .clipboard(clipboard.clone())
+///     .receiver(receiver)
+///     .handle(Box::new(SharedHandle::init_std_out()))
+///     .build();
+/// let mut race = RaceMode::new(Usize2d::new(40, 20), 7, 5, [racer, racer]);
+/// race.set_clipboard(clipboard);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SharedClipboard {
+    pattern: Arc<Mutex<Option<Clipboard>>>,
+    system: Option<SystemClipboard>,
+}
+impl SharedClipboard {
+    /// Create an empty clipboard
+    ///
+    /// # Arguments
+    ///
+    /// * `system` - the system clipboard to copy to and paste from, `None` to keep the patterns
+    ///   in memory only
+    pub fn new(system: Option<SystemClipboard>) -> Self {
+        SharedClipboard {
+            pattern: Arc::new(Mutex::new(None)),
+            system,
+        }
+    }
+    /// Copy a pattern for all clones of the clipboard, and to the system clipboard as RLE
+    ///
+    /// # Returns
+    ///
+    /// An error if the system clipboard failed, the pattern is copied for the clones anyway
+    pub fn set(&self, pattern: Clipboard, rule: &Rule) -> Result<(), SystemClipboardError> {
+        let rle = self.system.as_ref().map(|_| pattern.to_rle(rule));
+        *self.pattern.lock().unwrap_or_else(PoisonError::into_inner) = Some(pattern);
+        match (&self.system, rle) {
+            (Some(system), Some(rle)) => system.write(&rle),
+            _ => Ok(()),
+        }
+    }
+    /// Get the pattern to paste: the pattern on the system clipboard if it holds one, otherwise
+    /// the pattern that was copied last
+    pub fn get(&self) -> Option<Clipboard> {
+        let pasted = self
+            .system
+            .as_ref()
+            .and_then(|system| system.read().ok())
+            .and_then(|text| Clipboard::from_text(&text).ok());
+        pasted.or_else(|| {
+            self.pattern
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::{clipboard::Clipboard, rule::Rule};
+
+    use super::{SharedClipboard, SystemClipboard, SystemClipboardError};
+
+    fn glider() -> Clipboard {
+        Clipboard::from_text(".O\n..O\nOOO").unwrap()
+    }
+
+    #[test]
+    fn detect() {
+        let xclip = SystemClipboard::new(
+            &["xclip", "-selection", "clipboard"],
+            &["xclip", "-selection", "clipboard", "-o"],
+        );
+        let test_cases = [
+            ("macos", "macos", vec![], Some("pbcopy")),
+            ("windows", "windows", vec![], Some("clip")),
+            (
+                "wayland before x11",
+                "linux",
+                vec!["WAYLAND_DISPLAY", "DISPLAY"],
+                Some("wl-copy"),
+            ),
+            ("x11", "linux", vec!["DISPLAY"], Some("xclip")),
+            ("no desktop", "linux", vec![], None),
+        ];
+        for (test_case, os, variables, expected) in test_cases {
+            let system = SystemClipboard::detect_with(os, |name| variables.contains(&name));
+            assert_eq!(
+                system.as_ref().map(|system| system.copy[0].as_str()),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+        assert_eq!(
+            SystemClipboard::detect_with("linux", |name| name == "DISPLAY"),
+            Some(xclip)
+        );
+    }
+
+    #[test]
+    fn shared_between_clones() {
+        let clipboard = SharedClipboard::default();
+        let scene = clipboard.clone();
+        assert_eq!(scene.get(), None);
+        assert_eq!(clipboard.set(glider(), &Rule::conway()), Ok(()));
+        assert_eq!(scene.get(), Some(glider()));
+
+        let missing = SharedClipboard::new(Some(SystemClipboard::new(
+            &["conway_missing_clipboard_command"],
+            &["conway_missing_clipboard_command"],
+        )));
+        assert_eq!(
+            missing.set(glider(), &Rule::conway()),
+            Err(SystemClipboardError::Unavailable)
+        );
+        assert_eq!(
+            missing.get(),
+            Some(glider()),
+            "The copied pattern is kept when the system clipboard fails"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn system_clipboard() {
+        let path =
+            std::env::temp_dir().join(format!("conway_system_clipboard_{}", std::process::id()));
+        let path = path.display().to_string();
+        let copy = format!("cat > '{}'", path);
+        let paste = format!("cat '{}'", path);
+        let system = SystemClipboard::new(&["sh", "-c", &copy], &["sh", "-c", &paste]);
+        let clipboard = SharedClipboard::new(Some(system.clone()));
+
+        clipboard.set(glider(), &Rule::conway()).unwrap();
+        assert_eq!(
+            system.read(),
+            Ok("x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n".to_string()),
+            "Copied as RLE"
+        );
+
+        let test_cases = [
+            (
+                "a pattern from a website",
+                "#N Blinker\nx = 3, y = 1, rule = B3/S23\n3o!",
+                vec![vec![true, true, true]],
+            ),
+            ("other text", "hello", glider().cells),
+        ];
+        for (test_case, text, expected) in test_cases {
+            system.write(text).unwrap();
+            assert_eq!(
+                clipboard.get().map(|pattern| pattern.cells),
+                Some(expected),
+                "{}",
+                test_case
+            );
+        }
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(system.read(), Err(SystemClipboardError::Failed));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hanging_command() {
+        let hanging = SystemClipboard::new(&["sh", "-c", "sleep 5"], &["sh", "-c", "sleep 5"]);
+        let started = std::time::Instant::now();
+        assert_eq!(hanging.read(), Err(SystemClipboardError::Failed));
+        assert_eq!(hanging.write("3o!"), Err(SystemClipboardError::Failed));
+        assert!(
+            started.elapsed() < std::time::Duration::from_secs(2),
+            "The commands are stopped before they finish"
+        );
+    }
+}
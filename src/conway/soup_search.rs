@@ -0,0 +1,313 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+use super::{
+    conways_game::ConwaysGame,
+    cycle::{Cycle, CycleDetector},
+    seed_sweep::{SeedRange, SeedSweep},
+};
+
+/// The longest period of an oscillator or spaceship that is recognized
+pub const MAX_PERIOD: usize = 15;
+/// The empty cells around an object while it is classified, enough for a spaceship to move for
+/// two periods without wrapping around the grid
+const PADDING: usize = MAX_PERIOD + 2;
+/// Live cells this close to each other belong to the same object, further apart they can not
+/// influence each other in the next generation
+const OBJECT_DISTANCE: usize = 2;
+
+/// The kinds of objects a soup search records. Still lifes are too common to record
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FindKind {
+    Glider,
+    Spaceship,
+    Oscillator,
+}
+impl Display for FindKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Glider => write!(f, "glider"),
+            Self::Spaceship => write!(f, "spaceship"),
+            Self::Oscillator => write!(f, "oscillator"),
+        }
+    }
+}
+
+/// An object that was left behind by the soup of a seed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Find {
+    pub seed: u64,
+    pub kind: FindKind,
+    pub period: usize,
+    /// The left of the bounding box of the object in the final generation
+    pub x: usize,
+    /// The top of the bounding box of the object in the final generation
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Runs the seeds of a sweep and looks for oscillators and spaceships in the ash they leave behind
+///
+/// The final generation of each seed is split into objects that are too far apart to influence
+/// each other. Each object is then run on its own until the cycle detector recognizes it
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::fs::File;
+/// # use tic_tac_toe2::conway::{seed_sweep::{SeedRange, SeedSweep}, soup_search::SoupSearch};
+/// # fn main() -> Result<(), std::io::Error> {
+/// let search = SoupSearch::new(SeedSweep::new(32, 32, 1000));
+/// let finds = search.run(SeedRange(0..100));
+/// search.write_results(&finds, &mut File::create("finds.csv")?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SoupSearch {
+    pub sweep: SeedSweep,
+}
+impl SoupSearch {
+    pub fn new(sweep: SeedSweep) -> Self {
+        SoupSearch { sweep }
+    }
+    /// Search the final generations of all seeds in a range, in order
+    pub fn run(&self, seeds: SeedRange) -> Vec<Find> {
+        seeds.0.flat_map(|seed| self.search_seed(seed)).collect()
+    }
+    /// Search the final generation of a single seed
+    pub fn search_seed(&self, seed: u64) -> Vec<Find> {
+        let (_, game) = self.sweep.play(seed);
+        find_objects(game.cells())
+            .into_iter()
+            .filter_map(|object| {
+                let (kind, period) = classify(&object)?;
+                Some(Find {
+                    seed,
+                    kind,
+                    period,
+                    x: object.x,
+                    y: object.y,
+                    width: object.width,
+                    height: object.height,
+                })
+            })
+            .collect()
+    }
+    /// Write the finds as CSV with a header row
+    pub fn write_results(&self, finds: &[Find], writer: &mut dyn Write) -> io::Result<()> {
+        writeln!(writer, "seed,kind,period,x,y,width,height")?;
+        for find in finds {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{}",
+                find.seed, find.kind, find.period, find.x, find.y, find.width, find.height
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A group of live cells and its bounding box on the grid
+#[derive(Clone, Debug, PartialEq)]
+struct Object {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    /// The live cells relative to the top left of the bounding box
+    cells: Vec<(usize, usize)>,
+}
+
+/// Split the live cells of a grid into objects that can not influence each other
+fn find_objects(cells: &[Vec<bool>]) -> Vec<Object> {
+    let mut live = Vec::new();
+    for (y, row) in cells.iter().enumerate() {
+        for (x, is_alive) in row.iter().enumerate() {
+            if *is_alive {
+                live.push((x, y));
+            }
+        }
+    }
+    let mut is_grouped = vec![false; live.len()];
+    let mut objects = Vec::new();
+    for start in 0..live.len() {
+        if is_grouped[start] {
+            continue;
+        }
+        is_grouped[start] = true;
+        let mut group = vec![live[start]];
+        let mut next = 0;
+        while next < group.len() {
+            let (x, y) = group[next];
+            next += 1;
+            for other in 0..live.len() {
+                let (other_x, other_y) = live[other];
+                if !is_grouped[other]
+                    && x.abs_diff(other_x) <= OBJECT_DISTANCE
+                    && y.abs_diff(other_y) <= OBJECT_DISTANCE
+                {
+                    is_grouped[other] = true;
+                    group.push(live[other]);
+                }
+            }
+        }
+        objects.push(Object::new(group));
+    }
+    objects
+}
+impl Object {
+    fn new(cells: Vec<(usize, usize)>) -> Self {
+        let x = cells.iter().map(|(x, _)| *x).min().unwrap_or(0);
+        let y = cells.iter().map(|(_, y)| *y).min().unwrap_or(0);
+        let right = cells.iter().map(|(x, _)| *x).max().unwrap_or(0);
+        let bottom = cells.iter().map(|(_, y)| *y).max().unwrap_or(0);
+        Object {
+            x,
+            y,
+            width: right - x + 1,
+            height: bottom - y + 1,
+            cells: cells
+                .iter()
+                .map(|(cell_x, cell_y)| (cell_x - x, cell_y - y))
+                .collect(),
+        }
+    }
+}
+
+/// Run an object on its own until it repeats
+///
+/// # Returns
+///
+/// The kind and period of oscillators and spaceships, `None` for still lifes and objects that
+/// do not repeat within `MAX_PERIOD` generations
+fn classify(object: &Object) -> Option<(FindKind, usize)> {
+    let x_len = object.width + PADDING * 2;
+    let y_len = object.height + PADDING * 2;
+    let mut cells = vec![vec![false; x_len]; y_len];
+    for (x, y) in object.cells.iter() {
+        cells[y + PADDING][x + PADDING] = true;
+    }
+    let mut game = ConwaysGame::headless(x_len, y_len, 0);
+    game.set_cells(cells);
+    let mut detector = CycleDetector::new(MAX_PERIOD);
+    detector.record(game.cells());
+    for _ in 0..MAX_PERIOD * 2 {
+        game.next();
+        if let Some(cycle) = detector.record(game.cells()) {
+            return kind(&cycle, object.cells.len()).map(|kind| (kind, cycle.period));
+        }
+    }
+    None
+}
+fn kind(cycle: &Cycle, population: usize) -> Option<FindKind> {
+    if cycle.is_still() {
+        return None;
+    }
+    if !cycle.is_moving() {
+        return Some(FindKind::Oscillator);
+    }
+    let is_glider = population == 5
+        && cycle.period == 4
+        && cycle.displacement.0.abs() == 1
+        && cycle.displacement.1.abs() == 1;
+    if is_glider {
+        Some(FindKind::Glider)
+    } else {
+        Some(FindKind::Spaceship)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::{patterns::PatternPlacement, seed_sweep::SeedSweep};
+
+    use super::{classify, find_objects, Find, FindKind, SoupSearch};
+
+    fn grid(placements: &[&str]) -> Vec<Vec<bool>> {
+        let mut cells = vec![vec![false; 30]; 30];
+        for placement in placements {
+            let placement: PatternPlacement = placement.parse().unwrap();
+            let origin = placement.origin.unwrap();
+            for cell in placement.pattern.live_cells() {
+                cells[origin.y + cell.y][origin.x + cell.x] = true;
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn classify_objects() {
+        let cells = grid(&["glider@1,1", "pulsar@10,10", "lwss@1,20"]);
+        let mut block = cells.clone();
+        block[25][25] = true;
+        block[25][26] = true;
+        block[26][25] = true;
+        block[26][26] = true;
+
+        let objects = find_objects(&block);
+        assert_eq!(objects.len(), 4, "The objects are far enough apart");
+        let kinds: Vec<Option<(FindKind, usize)>> = objects.iter().map(classify).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Some((FindKind::Glider, 4)),
+                Some((FindKind::Oscillator, 3)),
+                Some((FindKind::Spaceship, 4)),
+                None,
+            ]
+        );
+        assert_eq!(
+            (
+                objects[1].x,
+                objects[1].y,
+                objects[1].width,
+                objects[1].height
+            ),
+            (10, 10, 13, 13),
+            "The bounding box of the pulsar is wrong"
+        );
+    }
+
+    #[test]
+    fn close_cells_are_one_object() {
+        let mut cells = vec![vec![false; 10]; 10];
+        cells[1][1] = true;
+        cells[3][3] = true;
+        cells[6][6] = true;
+        let objects = find_objects(&cells);
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].cells, vec![(0, 0), (2, 2)]);
+    }
+
+    #[test]
+    fn results_file() {
+        let search = SoupSearch::new(SeedSweep::new(16, 16, 200));
+        let finds = search.run("0..3".parse().unwrap());
+        assert_eq!(
+            finds,
+            [0, 1, 2]
+                .into_iter()
+                .flat_map(|seed| search.search_seed(seed))
+                .collect::<Vec<_>>()
+        );
+
+        let find = Find {
+            seed: 7,
+            kind: FindKind::Glider,
+            period: 4,
+            x: 3,
+            y: 4,
+            width: 3,
+            height: 3,
+        };
+        let mut csv = Vec::new();
+        search.write_results(&[find], &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "seed,kind,period,x,y,width,height\n7,glider,4,3,4,3,3\n"
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use std::{collections::VecDeque, fs, path::Path, time::Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::metrics::SessionSummary;
+
+use super::{
+    command::GameCommand,
+    divergence::board_hash,
+    save::{SaveError, SavedGame},
+    settings::ConwaysSettings,
+};
+
+/// The most events a timeline keeps, the oldest events are dropped after that
+pub const TIMELINE_LIMIT: usize = 100_000;
+
+/// Something that happened in a session, with the milliseconds since the session started
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TimelineEvent {
+    /// A command that an input ran, in the generation the input arrived in
+    Command {
+        at_ms: u64,
+        generation: u64,
+        input: String,
+        is_down: bool,
+        repeats: usize,
+        command: String,
+    },
+    /// The state of the board at a generation, to find where two runs of a seed went apart
+    Checkpoint {
+        at_ms: u64,
+        generation: u64,
+        population: usize,
+        hash: String,
+    },
+}
+impl TimelineEvent {
+    /// Get the generation the event happened in
+    pub fn generation(&self) -> u64 {
+        match self {
+            TimelineEvent::Command { generation, .. }
+            | TimelineEvent::Checkpoint { generation, .. } => *generation,
+        }
+    }
+}
+
+/// A session as it is written to JSON, see `Timeline`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedSession {
+    pub seed: u64,
+    pub rule: String,
+    /// The board the session started with, to replay the session from
+    pub start: Option<SavedGame>,
+    /// The settings that change how the board is stepped, for the replay
+    pub boundary: String,
+    pub auto_pause: bool,
+    pub cycle_max_period: usize,
+    pub skip_generations: u64,
+    pub generations: u64,
+    pub elapsed_ms: u64,
+    pub peak_population: u64,
+    pub commands: u64,
+    /// The number of the oldest events that were dropped because the timeline was full
+    pub dropped_events: u64,
+    pub events: Vec<TimelineEvent>,
+}
+impl RecordedSession {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        fs::write(path, self.to_json())?;
+        Ok(())
+    }
+    /// Read a session that was written with `save`
+    pub fn load(path: &Path) -> Result<Self, SaveError> {
+        let json = fs::read_to_string(path).map_err(|_| SaveError::ReadFailed)?;
+        serde_json::from_str(&json).map_err(|_| SaveError::BadFormat)
+    }
+}
+
+/// The commands and the generation checkpoints of a session, written to JSON when the session
+/// ends so that a run can be analyzed, attached to a bug report or verified by replaying it
+///
+/// Unlike the input log, which only keeps the latest commands to show them, a timeline keeps up
+/// to `TIMELINE_LIMIT` events with their timing
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::{path::Path, time::{Duration, Instant}};
+/// # use tic_tac_toe2::{console::input_record::KeyEvent, conway::{command::{Command, CommandSource, GameCommand}, save::SaveError, timeline::Timeline}, utils::metrics::Metrics, ConwaysSettings};
+/// # fn main() -> Result<(), SaveError> {
+/// # let started = Instant::now();
+/// # let metrics = Metrics::new(started);
+/// # let settings = ConwaysSettings::init(3, 1, Duration::from_secs(1), 42);
+/// # let command = GameCommand::new(Command::FASTFORWARD, CommandSource::Key(KeyEvent::key_down('f')), started);
+/// # let (rounds, seed, cells) = (3, 42, vec![vec![true, true, false]]);
+/// let mut timeline = Timeline::new(started);
+/// timeline.record_command(&command, rounds);
+/// timeline.checkpoint(Instant::now(), rounds, &cells);
+/// let session = timeline.session(&settings, &metrics.summary(seed, Instant::now()));
+/// session.save(Path::new("timeline.json"))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Timeline {
+    started: Instant,
+    start: Option<SavedGame>,
+    events: VecDeque<TimelineEvent>,
+    dropped: u64,
+    /// Where the command of the input that is being handled goes and its generation, see `mark`
+    pending: Option<(usize, u64)>,
+}
+impl Timeline {
+    pub fn new(started: Instant) -> Self {
+        Timeline {
+            started,
+            start: None,
+            events: VecDeque::new(),
+            dropped: 0,
+            pending: None,
+        }
+    }
+    /// Forget all events and start a new session
+    pub fn reset(&mut self, started: Instant) {
+        *self = Timeline::new(started);
+    }
+    /// Keep the board the session starts with
+    pub fn set_start(&mut self, board: SavedGame) {
+        self.start = Some(board);
+    }
+    /// Remember where an input arrived before it is handled. The command it runs is recorded
+    /// there, before the checkpoints the command caused, like those of a fast forward
+    pub fn mark(&mut self, generation: u64) {
+        self.pending = Some((self.events.len(), generation));
+    }
+    /// Add a command that ran, where the latest `mark` was or else at the end
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - the command
+    /// * `generation` - the generation it ran in, if there is no mark
+    pub fn record_command(&mut self, command: &GameCommand, generation: u64) {
+        self.make_room();
+        let (index, generation) = self
+            .pending
+            .take()
+            .unwrap_or((self.events.len(), generation));
+        let event = TimelineEvent::Command {
+            at_ms: self.elapsed_ms(command.at),
+            generation,
+            input: command.input(),
+            is_down: command.is_down(),
+            repeats: command.repeats(),
+            command: command.command.to_string(),
+        };
+        self.events.insert(index, event);
+    }
+    /// Add the state of the board at a generation
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - when the generation was reached
+    /// * `generation` - the number of rounds that were played
+    /// * `cells` - the cells of the generation, indexed as `cells[y][x]`
+    pub fn checkpoint(&mut self, at: Instant, generation: u64, cells: &[Vec<bool>]) {
+        let event = TimelineEvent::Checkpoint {
+            at_ms: self.elapsed_ms(at),
+            generation,
+            population: cells.iter().flatten().filter(|is_alive| **is_alive).count(),
+            hash: format!("{:016x}", board_hash(cells)),
+        };
+        self.make_room();
+        self.events.push_back(event);
+    }
+    /// Get the events from the oldest to the newest
+    pub fn events(&self) -> impl Iterator<Item = &TimelineEvent> {
+        self.events.iter()
+    }
+    /// Get the hash of the latest checkpoint of a generation
+    pub fn checkpoint_hash(&self, generation: u64) -> Option<u64> {
+        self.events.iter().rev().find_map(|event| match event {
+            TimelineEvent::Checkpoint {
+                generation: checkpoint,
+                hash,
+                ..
+            } if *checkpoint == generation => u64::from_str_radix(hash, 16).ok(),
+            _ => None,
+        })
+    }
+    /// Get the session with the settings of the game and the numbers of its summary
+    pub fn session(&self, settings: &ConwaysSettings, summary: &SessionSummary) -> RecordedSession {
+        RecordedSession {
+            seed: settings.seed,
+            rule: settings.rule.to_string(),
+            start: self.start.clone(),
+            boundary: settings.boundary.to_string(),
+            auto_pause: settings.auto_pause,
+            cycle_max_period: settings.cycle_max_period,
+            skip_generations: settings.skip_generations,
+            generations: summary.generations,
+            elapsed_ms: summary.elapsed.as_millis() as u64,
+            peak_population: summary.peak_population,
+            commands: summary.commands,
+            dropped_events: self.dropped,
+            events: self.events.iter().cloned().collect(),
+        }
+    }
+    /// Drop the oldest event if the timeline is full, moving the mark with the other events
+    fn make_room(&mut self) {
+        if self.events.len() < TIMELINE_LIMIT {
+            return;
+        }
+        self.events.pop_front();
+        self.dropped += 1;
+        if let Some((index, _)) = self.pending.as_mut() {
+            *index = index.saturating_sub(1);
+        }
+    }
+    fn elapsed_ms(&self, at: Instant) -> u64 {
+        at.saturating_duration_since(self.started).as_millis() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::{
+        console::input_record::KeyEvent,
+        conway::{
+            command::{Command, CommandSource, GameCommand},
+            divergence::board_hash,
+            rule::Rule,
+            settings::ConwaysSettings,
+        },
+        utils::metrics::SessionSummary,
+    };
+
+    use super::{Timeline, TimelineEvent};
+
+    fn command(at: Instant) -> GameCommand {
+        GameCommand::new(
+            Command::FASTFORWARD,
+            CommandSource::Key(KeyEvent::key_down('f')),
+            at,
+        )
+    }
+
+    #[test]
+    fn export() {
+        let started = Instant::now();
+        let mut timeline = Timeline::new(started);
+        let at = started + Duration::from_millis(250);
+        let cells = [vec![true, true, false]];
+        timeline.record_command(&command(at), 3);
+        timeline.mark(4);
+        timeline.checkpoint(at + Duration::from_secs(1), 100, &cells);
+        timeline.record_command(&command(at), 100);
+        let events: Vec<&TimelineEvent> = timeline.events().collect();
+        let fast_forward = |generation| TimelineEvent::Command {
+            at_ms: 250,
+            generation,
+            input: "f".to_string(),
+            is_down: true,
+            repeats: 1,
+            command: Command::FASTFORWARD.to_string(),
+        };
+        assert_eq!(
+            events,
+            vec![
+                &fast_forward(3),
+                &fast_forward(4),
+                &TimelineEvent::Checkpoint {
+                    at_ms: 1250,
+                    generation: 100,
+                    population: 2,
+                    hash: format!("{:016x}", board_hash(&cells)),
+                },
+            ],
+            "A marked command goes before the checkpoints it caused"
+        );
+
+        let summary = SessionSummary {
+            seed: 42,
+            generations: 100,
+            elapsed: Duration::from_millis(1500),
+            peak_population: 7,
+            commands: 2,
+        };
+        let settings = ConwaysSettings::init(3, 1, Duration::from_secs(1), 42);
+        let session = timeline.session(&settings, &summary);
+        let json: serde_json::Value = serde_json::from_str(&session.to_json()).unwrap();
+        let test_cases = [
+            ("seed", "/seed", serde_json::json!(42)),
+            (
+                "rule",
+                "/rule",
+                serde_json::json!(Rule::conway().to_string()),
+            ),
+            ("summary", "/elapsed_ms", serde_json::json!(1500)),
+            ("command", "/events/0/type", serde_json::json!("command")),
+            ("checkpoint", "/events/2/generation", serde_json::json!(100)),
+            ("nothing dropped", "/dropped_events", serde_json::json!(0)),
+            ("no start", "/start", serde_json::Value::Null),
+            ("settings", "/boundary", serde_json::json!("Wrap")),
+        ];
+        for (test_case, pointer, expected) in test_cases {
+            assert_eq!(json.pointer(pointer), Some(&expected), "{}", test_case);
+        }
+        assert_eq!(serde_json::from_str(&session.to_json()).ok(), Some(session));
+
+        assert_eq!(timeline.checkpoint_hash(100), Some(board_hash(&cells)));
+        assert_eq!(
+            timeline.checkpoint_hash(3),
+            None,
+            "Commands are not checkpoints"
+        );
+
+        timeline.reset(at);
+        assert_eq!(timeline.events().count(), 0);
+    }
+}
@@ -0,0 +1,127 @@
+use core::fmt::Display;
+
+use crate::shared::usize2d::{Coord, Usize2d};
+
+/// A change of the whole board that keeps every cell but moves it, e.g. to build a symmetric
+/// starting pattern from one half of it
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{conway::transform::BoardTransform, ConwaysGame};
+/// # let game = ConwaysGame::headless(16, 16, 42);
+/// let mirrored = BoardTransform::MirrorHorizontally.apply(game.cells());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardTransform {
+    /// Swap the left and the right side
+    MirrorHorizontally,
+    /// Swap the top and the bottom
+    MirrorVertically,
+    /// Turn the board a quarter clockwise, the columns become rows
+    RotateClockwise,
+}
+impl BoardTransform {
+    /// Get the number of columns and rows of a grid after the transform
+    pub fn size(&self, grid: Usize2d) -> Usize2d {
+        match self {
+            BoardTransform::MirrorHorizontally | BoardTransform::MirrorVertically => grid,
+            BoardTransform::RotateClockwise => Usize2d::new(grid.y, grid.x),
+        }
+    }
+    /// Get the location of a cell after the transform
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` - the cell before the transform
+    /// * `grid` - the number of columns and rows before the transform
+    pub fn map(&self, cell: Coord, grid: Usize2d) -> Coord {
+        match self {
+            BoardTransform::MirrorHorizontally => Coord::new(grid.x - 1 - cell.x, cell.y),
+            BoardTransform::MirrorVertically => Coord::new(cell.x, grid.y - 1 - cell.y),
+            BoardTransform::RotateClockwise => Coord::new(grid.y - 1 - cell.y, cell.x),
+        }
+    }
+    /// Move every cell of a board
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the board, indexed as `cells[y][x]`
+    ///
+    /// # Returns
+    ///
+    /// A new board, with the rows and columns swapped by a rotation
+    pub fn apply(&self, cells: &[Vec<bool>]) -> Vec<Vec<bool>> {
+        let grid = Usize2d::new(cells[0].len(), cells.len());
+        let size = self.size(grid);
+        let mut transformed = vec![vec![false; size.x]; size.y];
+        for (y, row) in cells.iter().enumerate() {
+            for (x, is_alive) in row.iter().enumerate() {
+                let cell = self.map(Coord::new(x, y), grid);
+                transformed[cell.y][cell.x] = *is_alive;
+            }
+        }
+        transformed
+    }
+}
+impl Display for BoardTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardTransform::MirrorHorizontally => write!(f, "mirrored horizontally"),
+            BoardTransform::MirrorVertically => write!(f, "mirrored vertically"),
+            BoardTransform::RotateClockwise => write!(f, "rotated clockwise"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shared::usize2d::Usize2d;
+
+    use super::BoardTransform;
+
+    fn board(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|cell| cell == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn transforms() {
+        // ##.
+        // ..#
+        let cells = board(&["##.", "..#"]);
+        let test_cases = [
+            (
+                "mirror horizontally",
+                BoardTransform::MirrorHorizontally,
+                vec![".##", "#.."],
+            ),
+            (
+                "mirror vertically",
+                BoardTransform::MirrorVertically,
+                vec!["..#", "##."],
+            ),
+            (
+                "rotate clockwise",
+                BoardTransform::RotateClockwise,
+                vec![".#", ".#", "#."],
+            ),
+        ];
+        for (test_case, transform, expected) in test_cases {
+            assert_eq!(transform.apply(&cells), board(&expected), "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn four_rotations_are_a_full_turn() {
+        let cells = board(&["#...", ".##.", "...#"]);
+        let mut rotated = cells.clone();
+        for _ in 0..4 {
+            rotated = BoardTransform::RotateClockwise.apply(&rotated);
+        }
+        assert_eq!(rotated, cells);
+        let size = BoardTransform::RotateClockwise.size(Usize2d::new(4, 3));
+        assert_eq!((size.x, size.y), (3, 4));
+    }
+}
@@ -0,0 +1,191 @@
+use crate::shared::usize2d::{Coord, Usize2d};
+
+/// The part of the grid that is drawn when the whole board does not fit on the screen
+#[derive(Clone, Copy, Debug)]
+pub struct Viewport {
+    /// The first visible cell
+    pub first: Coord,
+    /// The number of visible columns and rows
+    pub size: Usize2d,
+}
+impl Viewport {
+    /// A viewport that shows the whole grid
+    pub fn full(grid: Usize2d) -> Self {
+        Viewport {
+            first: Coord::default(),
+            size: grid,
+        }
+    }
+    /// Fit as many cells as possible in the space that is available for the board
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - the number of columns and rows of the grid
+    /// * `space` - the characters that are available for the board
+    /// * `cell_size` - the characters a cell takes up, including the gap to the next cell
+    /// * `scroll` - the wanted first visible cell, moved back so that the viewport stays on the
+    ///   grid
+    pub fn fit(grid: Usize2d, space: Usize2d, cell_size: Usize2d, scroll: Coord) -> Self {
+        // The board starts with a gap, the left and top edge indicators are drawn in it
+        let columns = (space.x.saturating_sub(1) / cell_size.x).clamp(1, grid.x);
+        let rows = (space.y.saturating_sub(1) / cell_size.y).clamp(1, grid.y);
+        Viewport {
+            first: Coord::new(scroll.x.min(grid.x - columns), scroll.y.min(grid.y - rows)),
+            size: Usize2d::new(columns, rows),
+        }
+    }
+    /// Check if only a part of the grid is visible
+    pub fn is_partial(&self, grid: Usize2d) -> bool {
+        self.size.x < grid.x || self.size.y < grid.y
+    }
+    /// Get the first visible cell after scrolling the least distance needed to show a cell
+    pub fn follow(&self, cell: Coord) -> Coord {
+        let follow_axis = |first: usize, size: usize, cell: usize| -> usize {
+            if cell < first {
+                cell
+            } else if cell >= first + size {
+                cell + 1 - size
+            } else {
+                first
+            }
+        };
+        Coord::new(
+            follow_axis(self.first.x, self.size.x, cell.x),
+            follow_axis(self.first.y, self.size.y, cell.y),
+        )
+    }
+}
+
+/// The live cells beyond each edge of a viewport
+///
+/// The left and right edges are counted per visible row and the top and bottom edges per visible
+/// column. Cells beyond a corner count for the nearest row and column of both edges
+#[derive(Debug, Default, PartialEq)]
+pub struct EdgeActivity {
+    pub left: Vec<usize>,
+    pub right: Vec<usize>,
+    pub top: Vec<usize>,
+    pub bottom: Vec<usize>,
+}
+impl EdgeActivity {
+    /// Count the live cells outside of a viewport
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - the cells of the grid, indexed as `cells[y][x]`
+    /// * `viewport` - the visible part of the grid
+    pub fn count(cells: &[Vec<bool>], viewport: &Viewport) -> Self {
+        let first = viewport.first;
+        let end = first + viewport.size;
+        let mut activity = EdgeActivity {
+            left: vec![0; viewport.size.y],
+            right: vec![0; viewport.size.y],
+            top: vec![0; viewport.size.x],
+            bottom: vec![0; viewport.size.x],
+        };
+        for (y, row) in cells.iter().enumerate() {
+            let visible_row = y.clamp(first.y, end.y - 1) - first.y;
+            for (x, is_alive) in row.iter().enumerate() {
+                if !is_alive {
+                    continue;
+                }
+                let visible_column = x.clamp(first.x, end.x - 1) - first.x;
+                if x < first.x {
+                    activity.left[visible_row] += 1;
+                } else if x >= end.x {
+                    activity.right[visible_row] += 1;
+                }
+                if y < first.y {
+                    activity.top[visible_column] += 1;
+                } else if y >= end.y {
+                    activity.bottom[visible_column] += 1;
+                }
+            }
+        }
+        activity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shared::usize2d::{Coord, Usize2d};
+
+    use super::{EdgeActivity, Viewport};
+
+    #[test]
+    fn fit() {
+        let grid = Usize2d::new(20, 10);
+        let cell_size = Usize2d::new(4, 3);
+        let test_cases = vec![
+            (1, Usize2d::new(81, 31), Coord::new(5, 5), (0, 0), (20, 10)),
+            (2, Usize2d::new(30, 20), Coord::new(0, 0), (0, 0), (7, 6)),
+            (3, Usize2d::new(30, 20), Coord::new(3, 2), (3, 2), (7, 6)),
+            (4, Usize2d::new(30, 20), Coord::new(50, 50), (13, 4), (7, 6)),
+            (5, Usize2d::new(2, 2), Coord::new(0, 0), (0, 0), (1, 1)),
+        ];
+        for (test_case, space, scroll, expected_first, expected_size) in test_cases {
+            let viewport = Viewport::fit(grid, space, cell_size, scroll);
+            assert_eq!(
+                (viewport.first.x, viewport.first.y),
+                expected_first,
+                "Test case {}: The first visible cell is wrong",
+                test_case
+            );
+            assert_eq!(
+                (viewport.size.x, viewport.size.y),
+                expected_size,
+                "Test case {}: The number of visible cells is wrong",
+                test_case
+            );
+        }
+    }
+
+    #[test]
+    fn follow() {
+        let viewport = Viewport {
+            first: Coord::new(4, 4),
+            size: Usize2d::new(3, 3),
+        };
+        let test_cases = vec![
+            (1, Coord::new(5, 5), (4, 4)),
+            (2, Coord::new(1, 5), (1, 4)),
+            (3, Coord::new(9, 7), (7, 5)),
+        ];
+        for (test_case, cell, expected) in test_cases {
+            let first = viewport.follow(cell);
+            assert_eq!(
+                (first.x, first.y),
+                expected,
+                "Test case {}: The viewport should scroll just far enough to show {}",
+                test_case,
+                cell
+            );
+        }
+    }
+
+    #[test]
+    fn count_edge_activity() {
+        // #....
+        // ....#
+        // ..#..
+        // ...#.
+        // with the 2x2 cells starting at 1:1 visible
+        let mut cells = vec![vec![false; 5]; 4];
+        for (x, y) in [(0, 0), (4, 1), (2, 2), (3, 3)] {
+            cells[y][x] = true;
+        }
+        let viewport = Viewport {
+            first: Coord::new(1, 1),
+            size: Usize2d::new(2, 2),
+        };
+        assert_eq!(
+            EdgeActivity::count(&cells, &viewport),
+            EdgeActivity {
+                left: vec![1, 0],
+                right: vec![1, 1],
+                top: vec![1, 0],
+                bottom: vec![0, 1],
+            }
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    handler::{cursor_style::CursorStyle, handle::Handle, handle_error::HandleError},
+    rendering::colors::TerminalColors,
+    shared::usize2d::{Coord, Usize2d},
+    utils::clock::Clock,
+};
+
+/// The state the scheduler shares with its handles
+#[derive(Debug)]
+struct Schedule {
+    terminal: Box<dyn Handle>,
+    clock: Box<dyn Clock>,
+    interval: Duration,
+    last_flush: Option<Instant>,
+    /// Something asked for a flush that did not happen yet
+    is_pending: bool,
+    flushes: u64,
+}
+impl Schedule {
+    fn flush_due(&mut self) -> bool {
+        let now = self.clock.now();
+        let is_due = self
+            .last_flush
+            .is_none_or(|last_flush| now.saturating_duration_since(last_flush) >= self.interval);
+        if !self.is_pending || !is_due {
+            return false;
+        }
+        self.flush(now);
+        true
+    }
+    fn flush(&mut self, now: Instant) {
+        let _ = self.terminal.flush();
+        self.last_flush = Some(now);
+        self.is_pending = false;
+        self.flushes += 1;
+    }
+}
+
+/// Flushes a terminal that several panels draw to at most once per frame interval
+///
+/// The panels write through the handles of the scheduler, see `handle`. Their writes go to the
+/// terminal right away, but a flush only asks for one: the terminal is flushed at once if a frame
+/// interval passed since the last flush, otherwise the flush waits for `flush_due`. The output of
+/// all the panels between two flushes reaches the screen in a single write, since `StdIOHandle`
+/// collects everything until it is flushed
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use tic_tac_toe2::{coordination::flush_scheduler::FlushScheduler, utils::clock::SystemClock, StdIOHandle};
+/// # let interval = Duration::from_millis(16);
+/// let scheduler = FlushScheduler::new(Box::new(StdIOHandle::new()), interval, Box::new(SystemClock));
+/// let left = Box::new(scheduler.handle());
+/// let right = Box::new(scheduler.handle());
+/// // ... the panels draw and flush as often as they like
+/// scheduler.flush_due();
+/// ```
+#[derive(Clone, Debug)]
+pub struct FlushScheduler {
+    schedule: Arc<Mutex<Schedule>>,
+}
+impl FlushScheduler {
+    /// Create a scheduler for a terminal
+    ///
+    /// # Arguments
+    ///
+    /// * `terminal` - the handle the panels draw to, it is only flushed by the scheduler
+    /// * `interval` - the least time between two flushes
+    /// * `clock` - where the time is read from
+    pub fn new(terminal: Box<dyn Handle>, interval: Duration, clock: Box<dyn Clock>) -> Self {
+        FlushScheduler {
+            schedule: Arc::new(Mutex::new(Schedule {
+                terminal,
+                clock,
+                interval,
+                last_flush: None,
+                is_pending: false,
+                flushes: 0,
+            })),
+        }
+    }
+    /// Get a handle that draws to the terminal and leaves the flushes to the scheduler
+    pub fn handle(&self) -> ScheduledHandle {
+        ScheduledHandle {
+            schedule: self.schedule.clone(),
+        }
+    }
+    /// Flush the terminal if a flush was asked for and a frame interval passed since the last one
+    ///
+    /// # Returns
+    ///
+    /// `true` if the terminal was flushed
+    pub fn flush_due(&self) -> bool {
+        lock(&self.schedule).flush_due()
+    }
+    /// Flush the terminal if a flush was asked for, however soon after the last one
+    pub fn flush_pending(&self) {
+        let mut schedule = lock(&self.schedule);
+        if schedule.is_pending {
+            let now = schedule.clock.now();
+            schedule.flush(now);
+        }
+    }
+    /// Get the number of times the terminal was flushed
+    pub fn flushes(&self) -> u64 {
+        lock(&self.schedule).flushes
+    }
+}
+
+fn lock(schedule: &Mutex<Schedule>) -> MutexGuard<'_, Schedule> {
+    schedule
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Draws to the terminal of a `FlushScheduler`, a flush only asks the scheduler for one
+#[derive(Debug)]
+pub struct ScheduledHandle {
+    schedule: Arc<Mutex<Schedule>>,
+}
+impl ScheduledHandle {
+    fn terminal(&self) -> MutexGuard<'_, Schedule> {
+        lock(&self.schedule)
+    }
+}
+impl Write for ScheduledHandle {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.terminal().terminal.write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        let mut schedule = self.terminal();
+        schedule.is_pending = true;
+        schedule.flush_due();
+        Ok(())
+    }
+}
+impl Handle for ScheduledHandle {
+    fn set_cursor_location(&mut self, coord: Usize2d) -> Result<(), HandleError> {
+        self.terminal().terminal.set_cursor_location(coord)
+    }
+    fn set_foreground_color(&mut self, color: TerminalColors) -> Result<(), HandleError> {
+        self.terminal().terminal.set_foreground_color(color)
+    }
+    fn set_background_color(&mut self, color: TerminalColors) -> Result<(), HandleError> {
+        self.terminal().terminal.set_background_color(color)
+    }
+    fn write_to_location(&mut self, buf: &[u8], coord: Coord) -> Result<usize, HandleError> {
+        self.terminal().terminal.write_to_location(buf, coord)
+    }
+    fn clear_screen(&mut self) -> Result<(), HandleError> {
+        self.terminal().terminal.clear_screen()
+    }
+    fn set_cursor_visible(&mut self, is_visible: bool) -> Result<(), HandleError> {
+        self.terminal().terminal.set_cursor_visible(is_visible)
+    }
+    fn set_cursor_style(&mut self, style: CursorStyle) -> Result<(), HandleError> {
+        self.terminal().terminal.set_cursor_style(style)
+    }
+    fn set_alternate_screen(&mut self, is_active: bool) -> Result<(), HandleError> {
+        self.terminal().terminal.set_alternate_screen(is_active)
+    }
+    fn set_title(&mut self, title: &str) -> Result<(), HandleError> {
+        self.terminal().terminal.set_title(title)
+    }
+    fn bell(&mut self) -> Result<(), HandleError> {
+        self.terminal().terminal.bell()
+    }
+    fn reset_colors(&mut self) -> Result<(), HandleError> {
+        self.terminal().terminal.reset_colors()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use crate::{
+        handler::{handle::Handle, memory_handle::MemoryHandle, shared_handle::SharedHandle},
+        shared::usize2d::Usize2d,
+        utils::clock::VirtualClock,
+    };
+
+    use super::FlushScheduler;
+
+    #[test]
+    fn flushes_once_per_interval() {
+        let terminal = Arc::new(Mutex::new(MemoryHandle::new()));
+        let clock = VirtualClock::new();
+        let scheduler = FlushScheduler::new(
+            Box::new(SharedHandle::init(terminal.clone())),
+            Duration::from_millis(16),
+            Box::new(clock.clone()),
+        );
+        let mut handles = [scheduler.handle(), scheduler.handle()];
+        let test_cases = [
+            ("first frame", 0, 0, 1),
+            ("left panel", 0, 5, 1),
+            ("right panel", 1, 5, 1),
+            ("next frame", 0, 6, 2),
+            ("held back", 1, 1, 2),
+        ];
+        for (x, (test_case, panel, millis, expected)) in test_cases.into_iter().enumerate() {
+            clock.advance(Duration::from_millis(millis));
+            let _ = handles[panel].write_to_location(b"#", Usize2d::new(x, 0));
+            let _ = handles[panel].flush();
+            assert_eq!(scheduler.flushes(), expected, "{}", test_case);
+        }
+        assert!(!scheduler.flush_due(), "A flush waits for the interval");
+        clock.advance(Duration::from_millis(16));
+        assert!(scheduler.flush_due());
+        assert!(!scheduler.flush_due(), "Nothing is pending");
+        assert_eq!(
+            String::from_utf8_lossy(&terminal.lock().unwrap().get_buffer_content()),
+            "#####"
+        );
+    }
+}
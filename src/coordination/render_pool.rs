@@ -0,0 +1,306 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread::{spawn, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    coordination::flush_scheduler::FlushScheduler,
+    panel::{
+        frame_channel::{frame_channel, FrameReceiver, FrameSender, FRAME_QUEUE_CAPACITY},
+        panel::Panel,
+    },
+    rendering::render_object::RenderObjects,
+};
+
+/// The number of worker threads a pool starts at most by default
+pub const DEFAULT_WORKERS: usize = 2;
+/// The time between two ticks of the same panel by default, about 60 frames a second
+pub const DEFAULT_FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// A panel waiting in the run queue of the pool
+struct Slot {
+    panel: Panel,
+    /// The earliest time the panel is ticked again
+    due: Instant,
+}
+
+/// The state the pool shares with its workers
+#[derive(Default)]
+struct Queue {
+    slots: VecDeque<Slot>,
+    /// The panels that are ticked by a worker at the moment
+    running: usize,
+    is_stopped: bool,
+    /// Flushes the terminal the panels draw to after their ticks
+    flush_scheduler: Option<FlushScheduler>,
+}
+
+/// Ticks the panels of a session on a few shared worker threads instead of a hot looping thread
+/// per panel
+///
+/// The panels wait in one run queue. A worker takes the panel at the front, ticks it once and
+/// puts it at the back, so every panel gets a turn before any panel gets a second one. A panel
+/// is ticked at most once per frame interval and the workers sleep while no panel is due. A
+/// panel that is killed or fails leaves the pool
+///
+/// Frames reach the panels through bounded channels made with `frame_channel`. A panel that
+/// falls behind drops its oldest frames, so it can not pile up stale ones
+///
+/// Panels that draw through the handles of a `FlushScheduler` set with `set_flush_scheduler`
+/// leave the flushes to the pool, which flushes the terminal after a tick once a frame interval
+/// passed since the last flush
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::mpsc::channel;
+/// # use tic_tac_toe2::{coordination::render_pool::{RenderPool, DEFAULT_FRAME_INTERVAL, DEFAULT_WORKERS}, panel::{command_enum::PanelCommandEnum, errors::PanelError}, shared::{square::Square, usize2d::Usize2d}, utils::clock::SystemClock, MemoryHandle, Panel};
+/// # fn main() -> Result<(), PanelError> {
+/// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+/// # let (handle, clock) = (Box::new(MemoryHandle::new()), Box::new(SystemClock));
+/// let mut pool = RenderPool::new(DEFAULT_WORKERS, DEFAULT_FRAME_INTERVAL);
+/// let (_frame_sender, frame_receiver) = RenderPool::frame_channel();
+/// let (command_sender, command_receiver) = channel();
+/// pool.add(Panel::init_with_clock(area, frame_receiver, command_receiver, handle, clock)?);
+/// // ...
+/// let _ = command_sender.send(PanelCommandEnum::KillProcess);
+/// pool.stop();
+/// # Ok(())
+/// # }
+/// ```
+pub struct RenderPool {
+    shared: Arc<(Mutex<Queue>, Condvar)>,
+    workers: Vec<JoinHandle<()>>,
+    max_workers: usize,
+    frame_interval: Duration,
+}
+impl RenderPool {
+    /// Create a pool without panels. The workers are started as panels are added, never more
+    /// workers than panels
+    ///
+    /// # Arguments
+    ///
+    /// * `max_workers` - the largest number of worker threads, at least one
+    /// * `frame_interval` - the least time between two ticks of the same panel
+    pub fn new(max_workers: usize, frame_interval: Duration) -> Self {
+        assert!(max_workers > 0, "The pool needs at least one worker");
+        RenderPool {
+            shared: Arc::new((Mutex::new(Queue::default()), Condvar::new())),
+            workers: Vec::new(),
+            max_workers,
+            frame_interval,
+        }
+    }
+    /// Create the bounded channel that frames are sent to a panel of the pool through
+    pub fn frame_channel() -> (FrameSender<RenderObjects>, FrameReceiver<RenderObjects>) {
+        frame_channel(FRAME_QUEUE_CAPACITY)
+    }
+    /// Add a panel to the back of the run queue, it is ticked right away
+    pub fn add(&mut self, panel: Panel) {
+        let (queue, available) = &*self.shared;
+        let mut queue = lock(queue);
+        queue.slots.push_back(Slot {
+            panel,
+            due: Instant::now(),
+        });
+        let panels = queue.slots.len() + queue.running;
+        drop(queue);
+        if self.workers.len() < self.max_workers.min(panels) {
+            let shared = self.shared.clone();
+            let frame_interval = self.frame_interval;
+            self.workers
+                .push(spawn(move || work(shared, frame_interval)));
+        }
+        available.notify_one();
+    }
+    /// Flush the terminal of a scheduler after the ticks of the panels, see `FlushScheduler`
+    pub fn set_flush_scheduler(&mut self, flush_scheduler: FlushScheduler) {
+        lock(&self.shared.0).flush_scheduler = Some(flush_scheduler);
+    }
+    /// Get the number of panels that are still running
+    pub fn len(&self) -> usize {
+        let queue = lock(&self.shared.0);
+        queue.slots.len() + queue.running
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Get the number of worker threads that were started
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+    /// Stop the workers once they finish the tick they are in, flush what the panels drew last
+    /// and drop the panels that are left
+    pub fn stop(&mut self) {
+        let (queue, available) = &*self.shared;
+        lock(queue).is_stopped = true;
+        available.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+        let mut queue = lock(queue);
+        queue.slots.clear();
+        if let Some(flush_scheduler) = queue.flush_scheduler.take() {
+            flush_scheduler.flush_pending();
+        }
+    }
+}
+impl Drop for RenderPool {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Lock the queue, a worker that panicked while holding it does not stop the other workers
+fn lock(queue: &Mutex<Queue>) -> MutexGuard<'_, Queue> {
+    queue
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The loop of a worker thread: take the panel at the front once it is due, tick it and put it
+/// at the back
+fn work(shared: Arc<(Mutex<Queue>, Condvar)>, frame_interval: Duration) {
+    let (queue, available) = &*shared;
+    loop {
+        let mut slot = {
+            let mut guard = lock(queue);
+            loop {
+                if guard.is_stopped {
+                    return;
+                }
+                let now = Instant::now();
+                let wait = match guard.slots.front() {
+                    Some(slot) if slot.due <= now => break,
+                    Some(slot) => Some(slot.due - now),
+                    None => None,
+                };
+                guard = match wait {
+                    Some(wait) => {
+                        available
+                            .wait_timeout(guard, wait)
+                            .unwrap_or_else(|poisoned| poisoned.into_inner())
+                            .0
+                    }
+                    None => available
+                        .wait(guard)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner()),
+                };
+            }
+            guard.running += 1;
+            guard
+                .slots
+                .pop_front()
+                .expect("The front slot was just checked")
+        };
+        let is_running = matches!(slot.panel.tick(), Ok(true));
+        let mut guard = lock(queue);
+        guard.running -= 1;
+        let flush_scheduler = guard.flush_scheduler.clone();
+        if is_running {
+            slot.due = Instant::now() + frame_interval;
+            guard.slots.push_back(slot);
+            drop(guard);
+            available.notify_one();
+        } else {
+            drop(guard);
+        }
+        if let Some(flush_scheduler) = flush_scheduler {
+            flush_scheduler.flush_due();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            mpsc::{channel, Sender},
+            Arc, Mutex,
+        },
+        thread::sleep,
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        handler::{memory_handle::MemoryHandle, shared_handle::SharedHandle},
+        panel::{command_enum::PanelCommandEnum, panel::Panel},
+        shared::{square::Square, usize2d::Usize2d},
+        utils::clock::SystemClock,
+    };
+
+    use super::RenderPool;
+
+    /// Wait up to a second for a condition that is met by the workers
+    fn eventually(condition: impl Fn() -> bool) -> bool {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(1) {
+            if condition() {
+                return true;
+            }
+            sleep(Duration::from_millis(5));
+        }
+        false
+    }
+
+    fn add_panel(
+        pool: &mut RenderPool,
+        row: usize,
+        handle: Arc<Mutex<MemoryHandle>>,
+    ) -> Sender<PanelCommandEnum> {
+        let area = Square::new(Usize2d::new(0, row), Usize2d::new(5, row));
+        let (_, frame_receiver) = RenderPool::frame_channel();
+        let (command_sender, command_receiver) = channel();
+        let panel = Panel::init_with_clock(
+            area,
+            frame_receiver,
+            command_receiver,
+            Box::new(SharedHandle::init(handle)),
+            Box::new(SystemClock),
+        )
+        .expect("The panel should be created");
+        pool.add(panel);
+        command_sender
+    }
+
+    #[test]
+    fn few_workers_run_many_panels() {
+        let mut pool = RenderPool::new(2, Duration::from_millis(1));
+        let handle = Arc::new(Mutex::new(MemoryHandle::new()));
+        let senders: Vec<Sender<PanelCommandEnum>> = (0..6)
+            .map(|row| add_panel(&mut pool, row, handle.clone()))
+            .collect();
+        assert_eq!(pool.worker_count(), 2, "The workers are shared");
+        for (index, sender) in senders.iter().enumerate() {
+            sender
+                .send(PanelCommandEnum::SetTitle(index.to_string()))
+                .unwrap();
+        }
+        let expected = " 0    \n 1    \n 2    \n 3    \n 4    \n 5    ";
+        let content =
+            || String::from_utf8_lossy(&handle.lock().unwrap().get_buffer_content()).to_string();
+        assert!(eventually(|| content() == expected), "{:?}", content());
+
+        for sender in &senders[..4] {
+            sender.send(PanelCommandEnum::KillProcess).unwrap();
+        }
+        assert!(
+            eventually(|| pool.len() == 2),
+            "Killed panels leave the pool"
+        );
+        pool.stop();
+        assert!(pool.is_empty());
+        assert_eq!(pool.worker_count(), 0);
+    }
+
+    #[test]
+    fn one_worker_per_panel_at_most() {
+        let mut pool = RenderPool::new(4, Duration::from_millis(1));
+        let handle = Arc::new(Mutex::new(MemoryHandle::new()));
+        let _sender = add_panel(&mut pool, 0, handle);
+        assert_eq!(pool.worker_count(), 1);
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -1,19 +1,79 @@
+use super::flush_scheduler::FlushScheduler;
+use super::render_pool::{RenderPool, DEFAULT_FRAME_INTERVAL, DEFAULT_WORKERS};
 use crate::{
-    panel::panel::Panel,
-    shared::{frame::Frame, usize2d::Usize2d},
+    console::terminal_size::terminal_size,
+    handler::{handle::Handle, handle_error::HandleError},
+    panel::{
+        command_enum::PanelCommandEnum, errors::PanelError, frame_channel::FrameSender,
+        layout::LayoutNode, panel::Panel,
+    },
+    rendering::render_object::RenderObjects,
+    shared::{frame::SharedFrame, square::Square, usize2d::Usize2d},
+    utils::{
+        clock::SystemClock,
+        metrics::{Metrics, DROPPED_FRAMES},
+        timer::{TimerId, Timers},
+    },
 };
-use std::sync::mpsc::{channel, Receiver, Sender};
+use std::{
+    sync::mpsc::{channel, Receiver, Sender},
+    time::{Duration, Instant},
+};
+
+/// A command that is sent to a registered panel once its timer is due
+#[derive(Clone, Debug)]
+pub struct ScheduledCommand {
+    pub panel: usize,
+    pub command: PanelCommandEnum,
+}
+
+/// A panel that floats above the layout, like the help or a dialog
+#[derive(Clone, Debug)]
+struct Overlay {
+    panel: usize,
+    area: Square,
+    is_visible: bool,
+}
+
+/// Reports the size of the terminal, `None` if it is unknown
+pub type TerminalSizeSource = Box<dyn Fn() -> Option<Usize2d> + Send>;
+/// Runs once when the session shuts down cleanly, e.g. to remove the autosave
+pub type ShutdownHook = Box<dyn FnOnce() + Send>;
 
 pub struct CoordinatorService {
     state: bool,
-    panels: Vec<Panel>,
+    /// Ticks the panels that were added with `add_panel`
+    render_pool: RenderPool,
+    /// A copy of the frame sender of every panel of the render pool, to count the dropped frames
+    frame_senders: Vec<FrameSender<RenderObjects>>,
+    panel_senders: Vec<Sender<PanelCommandEnum>>,
+    timers: Timers<ScheduledCommand>,
+    terminal_size_source: TerminalSizeSource,
+    shutdown_hooks: Vec<ShutdownHook>,
+    /// Where the registered panels are placed on the screen
+    layout: Option<LayoutNode>,
+    /// The terminal size the panels were last placed for
+    laid_out_for: Option<Usize2d>,
+    /// The area every panel of the layout was last placed in, by panel
+    layout_areas: Vec<(usize, Square)>,
+    /// The floating panels, the last one is drawn on top
+    overlays: Vec<Overlay>,
 }
 
 impl CoordinatorService {
     pub fn init() -> Self {
         CoordinatorService {
             state: true,
-            panels: Vec::new(),
+            render_pool: RenderPool::new(DEFAULT_WORKERS, DEFAULT_FRAME_INTERVAL),
+            frame_senders: Vec::new(),
+            panel_senders: Vec::new(),
+            timers: Timers::default(),
+            terminal_size_source: Box::new(terminal_size),
+            shutdown_hooks: Vec::new(),
+            layout: None,
+            laid_out_for: None,
+            layout_areas: Vec::new(),
+            overlays: Vec::new(),
         }
     }
     pub fn new_sender_receiver<T>() -> (Sender<T>, Receiver<T>) {
@@ -21,19 +81,791 @@ impl CoordinatorService {
         (sender, receiver)
     }
 
-    pub fn new_window(_frame_receiver: Receiver<Frame>, _resize_receiver: Receiver<Usize2d>) {
+    pub fn new_window(_frame_receiver: Receiver<SharedFrame>, _resize_receiver: Receiver<Usize2d>) {
         //-> Result<Window, WindowExeption>
     }
+
+    /// Register the command sender of a running panel so that timers can send commands to it
+    ///
+    /// # Returns
+    ///
+    /// The index of the panel that is used when scheduling commands
+    pub fn register_panel(&mut self, command_sender: Sender<PanelCommandEnum>) -> usize {
+        self.panel_senders.push(command_sender);
+        self.panel_senders.len() - 1
+    }
+    /// Create a panel that is ticked by the render pool of the coordinator and register it
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - the area of the screen the panel draws in
+    /// * `handle` - where the panel writes to
+    ///
+    /// # Returns
+    ///
+    /// The index of the panel that is used when scheduling commands and the sender its frames
+    /// are sent through, which drops the oldest frame once the panel falls behind
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use tic_tac_toe2::{coordination::service::CoordinatorService, panel::errors::PanelError, rendering::render_object::RenderObjects, shared::{square::Square, usize2d::Usize2d}, SharedHandle};
+    /// # fn main() -> Result<(), PanelError> {
+    /// # let mut service = CoordinatorService::init();
+    /// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+    /// # let render_objects: RenderObjects = Arc::new([]);
+    /// let (panel, frame_sender) = service.add_panel(area, Box::new(SharedHandle::init_std_out()))?;
+    /// let _ = frame_sender.send(render_objects);
+    /// # service.shutdown();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_panel(
+        &mut self,
+        area: Square,
+        handle: Box<dyn Handle>,
+    ) -> Result<(usize, FrameSender<RenderObjects>), PanelError> {
+        let (frame_sender, frame_receiver) = RenderPool::frame_channel();
+        let (command_sender, command_receiver) = channel();
+        let panel = Panel::init_with_clock(
+            area,
+            frame_receiver,
+            command_receiver,
+            handle,
+            Box::new(SystemClock),
+        )?;
+        self.render_pool.add(panel);
+        self.frame_senders.push(frame_sender.clone());
+        Ok((self.register_panel(command_sender), frame_sender))
+    }
+    /// Let the render pool flush a terminal that several panels draw to, at most once per frame
+    ///
+    /// # Arguments
+    ///
+    /// * `terminal` - the handle the panels draw to
+    ///
+    /// # Returns
+    ///
+    /// The scheduler that hands out the handles of the panels
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{coordination::service::CoordinatorService, panel::errors::PanelError, shared::{square::Square, usize2d::Usize2d}, StdIOHandle};
+    /// # fn main() -> Result<(), PanelError> {
+    /// # let mut service = CoordinatorService::init();
+    /// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+    /// let scheduler = service.schedule_flushes(Box::new(StdIOHandle::new()));
+    /// let (panel, frame_sender) = service.add_panel(area, Box::new(scheduler.handle()))?;
+    /// # service.shutdown();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn schedule_flushes(&mut self, terminal: Box<dyn Handle>) -> FlushScheduler {
+        let scheduler =
+            FlushScheduler::new(terminal, DEFAULT_FRAME_INTERVAL, Box::new(SystemClock));
+        self.render_pool.set_flush_scheduler(scheduler.clone());
+        scheduler
+    }
+    /// Get the number of panels of the render pool that are still running
+    pub fn running_panels(&self) -> usize {
+        self.render_pool.len()
+    }
+    /// Add the frames that the panels of the render pool dropped since the last call to the
+    /// `DROPPED_FRAMES` counter of the metrics
+    ///
+    /// # Returns
+    ///
+    /// The number of frames that were added
+    pub fn record_dropped_frames(&self, metrics: &mut Metrics) -> u64 {
+        let dropped = self
+            .frame_senders
+            .iter()
+            .map(|sender| sender.take_dropped())
+            .sum();
+        metrics.add(DROPPED_FRAMES, dropped);
+        dropped
+    }
+    /// Send a command to a panel once after a delay
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::{sync::mpsc::channel, time::{Duration, Instant}};
+    /// # use tic_tac_toe2::{coordination::service::CoordinatorService, panel::command_enum::PanelCommandEnum};
+    /// # let mut service = CoordinatorService::init();
+    /// # let (command_sender, _commands) = channel();
+    /// let panel = service.register_panel(command_sender);
+    /// service.schedule_once(
+    ///     panel,
+    ///     Duration::from_secs(60),
+    ///     PanelCommandEnum::PauseProcess,
+    ///     Instant::now(),
+    /// );
+    /// ```
+    pub fn schedule_once(
+        &mut self,
+        panel: usize,
+        delay: Duration,
+        command: PanelCommandEnum,
+        now: Instant,
+    ) -> TimerId {
+        self.timers
+            .once(delay, ScheduledCommand { panel, command }, now)
+    }
+    /// Send a command to a panel every `interval`
+    pub fn schedule_repeating(
+        &mut self,
+        panel: usize,
+        interval: Duration,
+        command: PanelCommandEnum,
+        now: Instant,
+    ) -> TimerId {
+        self.timers
+            .repeating(interval, ScheduledCommand { panel, command }, now)
+    }
+    /// Restart the countdown of a scheduled command, e.g. an idle timeout after input
+    pub fn restart_timer(&mut self, id: TimerId, now: Instant) -> bool {
+        self.timers.restart(id, now)
+    }
+    pub fn cancel_timer(&mut self, id: TimerId) -> bool {
+        self.timers.cancel(id)
+    }
+    /// Replace the way the terminal size is detected, e.g. with a fixed size in tests
+    pub fn set_terminal_size_source(&mut self, source: TerminalSizeSource) {
+        self.terminal_size_source = source;
+    }
+    /// Get the number of columns and rows of the terminal
+    pub fn terminal_size(&self) -> Option<Usize2d> {
+        (self.terminal_size_source)()
+    }
+    /// Test if the terminal can fit a layout. If the size of the terminal is unknown it is assumed
+    /// to fit
+    pub fn fits_terminal(&self, required: Usize2d) -> bool {
+        match self.terminal_size() {
+            Some(size) => size.x >= required.x && size.y >= required.y,
+            None => true,
+        }
+    }
+    /// Check that the terminal can fit a layout before rendering it. If it can not, the screen is
+    /// cleared and a centered "terminal too small" message is written instead
+    ///
+    /// # Arguments
+    ///
+    /// * `required` - the number of columns and rows the layout needs
+    /// * `handle` - the handle the layout is rendered to
+    ///
+    /// # Returns
+    ///
+    /// `true` if the layout can be rendered
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{coordination::service::CoordinatorService, handler::handle_error::HandleError, shared::usize2d::Usize2d, ConwaysGame, MemoryHandle};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let service = CoordinatorService::init();
+    /// # let (mut game, mut handle) = (ConwaysGame::headless(16, 16, 42), MemoryHandle::new());
+    /// if service.guard_terminal_size(Usize2d::new(80, 24), &mut handle)? {
+    ///     game.render();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn guard_terminal_size(
+        &self,
+        required: Usize2d,
+        handle: &mut dyn Handle,
+    ) -> Result<bool, HandleError> {
+        guard_terminal_size(self.terminal_size(), required, handle)
+    }
+    /// Place the registered panels with a layout. The panels are moved on the next tick and again
+    /// every time the terminal is resized
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::mpsc::channel;
+    /// # use tic_tac_toe2::{coordination::service::CoordinatorService, panel::layout::{Constraint, Direction, LayoutNode}};
+    /// # let mut service = CoordinatorService::init();
+    /// # let (command_sender, _commands) = channel();
+    /// # let status = service.register_panel(channel().0);
+    /// let main = service.register_panel(command_sender);
+    /// service.set_layout(LayoutNode::Split(
+    ///     Direction::Vertical,
+    ///     vec![
+    ///         (Constraint::Fill, LayoutNode::Panel(main)),
+    ///         (Constraint::Fixed(1), LayoutNode::Panel(status)),
+    ///     ],
+    /// ));
+    /// ```
+    pub fn set_layout(&mut self, layout: LayoutNode) {
+        self.layout = Some(layout);
+        self.laid_out_for = None;
+    }
+    /// Move the panels to their area of the layout if the terminal size changed since they were
+    /// last placed. Nothing is moved while the size of the terminal is unknown
+    ///
+    /// # Returns
+    ///
+    /// The number of panels that were sent their new area
+    pub fn apply_layout(&mut self) -> usize {
+        let (layout, size) = match (&self.layout, self.terminal_size()) {
+            (Some(layout), Some(size)) if size.x > 0 && size.y > 0 => (layout, size),
+            _ => return 0,
+        };
+        if self
+            .laid_out_for
+            .is_some_and(|laid_out| laid_out.x == size.x && laid_out.y == size.y)
+        {
+            return 0;
+        }
+        let screen = Square::new(Usize2d::default(), Usize2d::new(size.x - 1, size.y - 1));
+        let areas = layout.areas(&screen);
+        let mut moved = 0;
+        for (panel, area) in areas.iter() {
+            if self.send(*panel, PanelCommandEnum::MoveTo(area.clone())) {
+                moved += 1;
+            }
+        }
+        self.layout_areas = areas;
+        self.laid_out_for = Some(size);
+        // The panels of the layout draw over the overlays when they move
+        for overlay in self.overlays.clone() {
+            if overlay.is_visible {
+                self.send(overlay.panel, PanelCommandEnum::ForceRedraw);
+            }
+        }
+        moved
+    }
+    /// Register a running panel that floats above the layout. The overlay starts hidden
+    ///
+    /// # Arguments
+    ///
+    /// * `command_sender` - the command sender of the panel
+    /// * `area` - the area of the screen the overlay covers while it is shown
+    ///
+    /// # Returns
+    ///
+    /// The index of the panel, to show and hide it
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::mpsc::channel;
+    /// # use tic_tac_toe2::{coordination::service::CoordinatorService, shared::{square::Square, usize2d::Usize2d}};
+    /// # let mut service = CoordinatorService::init();
+    /// # let (command_sender, _commands) = channel();
+    /// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+    /// let help = service.register_overlay(command_sender, area);
+    /// service.show_overlay(help);
+    /// ```
+    pub fn register_overlay(
+        &mut self,
+        command_sender: Sender<PanelCommandEnum>,
+        area: Square,
+    ) -> usize {
+        let panel = self.register_panel(command_sender);
+        self.send(panel, PanelCommandEnum::Hide);
+        self.send(panel, PanelCommandEnum::MoveTo(area.clone()));
+        self.overlays.push(Overlay {
+            panel,
+            area,
+            is_visible: false,
+        });
+        panel
+    }
+    /// Show an overlay on top of the other overlays
+    ///
+    /// # Returns
+    ///
+    /// `false` if the panel is not a registered overlay
+    pub fn show_overlay(&mut self, panel: usize) -> bool {
+        let index = match self
+            .overlays
+            .iter()
+            .position(|overlay| overlay.panel == panel)
+        {
+            Some(index) => index,
+            None => return false,
+        };
+        let mut overlay = self.overlays.remove(index);
+        overlay.is_visible = true;
+        self.overlays.push(overlay);
+        self.send(panel, PanelCommandEnum::Show)
+    }
+    /// Hide an overlay and redraw the panels it covered, the panels of the layout first and then
+    /// the overlays that are still shown
+    ///
+    /// # Returns
+    ///
+    /// `false` if the panel is not a registered overlay
+    pub fn hide_overlay(&mut self, panel: usize) -> bool {
+        let hidden = match self
+            .overlays
+            .iter_mut()
+            .find(|overlay| overlay.panel == panel)
+        {
+            Some(overlay) => {
+                overlay.is_visible = false;
+                overlay.area.clone()
+            }
+            None => return false,
+        };
+        self.send(panel, PanelCommandEnum::Hide);
+        let covered: Vec<usize> = self
+            .layout_areas
+            .iter()
+            .map(|(panel, area)| (*panel, area))
+            .chain(
+                self.overlays
+                    .iter()
+                    .filter(|overlay| overlay.is_visible)
+                    .map(|overlay| (overlay.panel, &overlay.area)),
+            )
+            .filter(|(_, area)| area.overlaps_with(&hidden))
+            .map(|(panel, _)| panel)
+            .collect();
+        for panel in covered {
+            self.send(panel, PanelCommandEnum::ForceRedraw);
+        }
+        true
+    }
+    /// Send a command to a registered panel
+    ///
+    /// # Returns
+    ///
+    /// `false` if the panel is not registered or already stopped
+    fn send(&self, panel: usize, command: PanelCommandEnum) -> bool {
+        match self.panel_senders.get(panel) {
+            Some(sender) => sender.send(command).is_ok(),
+            None => false,
+        }
+    }
+    /// Register work that has to happen when the session shuts down cleanly
+    pub fn on_shutdown(&mut self, hook: ShutdownHook) {
+        self.shutdown_hooks.push(hook);
+    }
+    /// Shut the session down cleanly: cancel the timers, tell the registered panels to stop, stop
+    /// the render pool and run the shutdown hooks in the order they were registered
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::coordination::service::CoordinatorService;
+    /// # let mut service = CoordinatorService::init();
+    /// service.on_shutdown(Box::new(move || println!("Bye")));
+    /// service.shutdown();
+    /// ```
+    pub fn shutdown(&mut self) {
+        self.state = false;
+        self.timers = Timers::default();
+        for sender in self.panel_senders.drain(..) {
+            let _ = sender.send(PanelCommandEnum::KillProcess);
+        }
+        self.render_pool.stop();
+        for hook in self.shutdown_hooks.drain(..) {
+            hook();
+        }
+    }
+    /// Place the panels again if the terminal was resized and dispatch the commands of all
+    /// timers that are due to their panels
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - the current time
+    ///
+    /// # Returns
+    ///
+    /// The number of timer commands that were delivered. Commands for panels that are not
+    /// registered or that already stopped are dropped
+    pub fn tick(&mut self, now: Instant) -> usize {
+        self.apply_layout();
+        let mut delivered = 0;
+        for scheduled in self.timers.tick(now) {
+            if self.send(scheduled.panel, scheduled.command) {
+                delivered += 1;
+            }
+        }
+        delivered
+    }
+}
+
+/// Check that a terminal of a size can fit a layout before rendering it, like
+/// `CoordinatorService::guard_terminal_size`, for a size that was read earlier. A terminal of an
+/// unknown size is assumed to fit
+pub fn guard_terminal_size(
+    size: Option<Usize2d>,
+    required: Usize2d,
+    handle: &mut dyn Handle,
+) -> Result<bool, HandleError> {
+    let size = match size {
+        Some(size) if size.x < required.x || size.y < required.y => size,
+        _ => return Ok(true),
+    };
+    let message = format!("terminal too small (need {}x{})", required.x, required.y);
+    let message = &message.as_bytes()[..message.len().min(size.x)];
+    // Terminal locations are 1 indexed
+    let origin = Usize2d::new((size.x - message.len()) / 2 + 1, size.y.div_ceil(2));
+    handle.clear_screen()?;
+    handle.reset_colors()?;
+    handle.write_to_location(message, origin)?;
+    handle.flush().map_err(|_| HandleError::WriteFailed)?;
+    Ok(false)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        sync::mpsc::channel,
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        handler::memory_handle::MemoryHandle,
+        panel::{
+            command_enum::PanelCommandEnum,
+            frame_channel::{frame_channel, FRAME_QUEUE_CAPACITY},
+        },
+    };
+
     use super::*;
 
     #[test]
     fn init() {
         let service = CoordinatorService::init();
-        assert_eq!(service.state, true, "After initialization, the service should have a property called state that is set to 'true'");
-        assert_eq!(service.panels.len(), 0, "After initialization, the service should have a property called windowa that is an empty Vec of Window");
+        assert!(service.state, "After initialization, the service should have a property called state that is set to 'true'");
+        assert_eq!(
+            service.render_pool.len(),
+            0,
+            "After initialization, the service should have a render pool without panels"
+        );
+    }
+
+    #[test]
+    fn tick_dispatches_scheduled_commands() {
+        let start = Instant::now();
+        let mut service = CoordinatorService::init();
+        let (command_sender, command_receiver) = channel();
+        let panel = service.register_panel(command_sender);
+
+        let idle = service.schedule_once(
+            panel,
+            Duration::from_secs(60),
+            PanelCommandEnum::PauseProcess,
+            start,
+        );
+        service.schedule_repeating(
+            panel,
+            Duration::from_secs(2),
+            PanelCommandEnum::ForceRedraw,
+            start,
+        );
+        service.schedule_once(
+            panel + 1,
+            Duration::from_secs(1),
+            PanelCommandEnum::KillProcess,
+            start,
+        );
+
+        assert_eq!(
+            service.tick(start + Duration::from_secs(2)),
+            1,
+            "Only the redraw should be delivered, the panel of the kill command does not exist"
+        );
+        assert!(matches!(
+            command_receiver.try_recv(),
+            Ok(PanelCommandEnum::ForceRedraw)
+        ));
+
+        assert!(service.restart_timer(idle, start + Duration::from_secs(30)));
+        assert_eq!(service.tick(start + Duration::from_secs(61)), 1);
+        assert!(matches!(
+            command_receiver.try_recv(),
+            Ok(PanelCommandEnum::ForceRedraw)
+        ));
+        assert_eq!(service.tick(start + Duration::from_secs(90)), 2);
+        assert!(matches!(
+            command_receiver.try_recv(),
+            Ok(PanelCommandEnum::ForceRedraw)
+        ));
+        assert!(matches!(
+            command_receiver.try_recv(),
+            Ok(PanelCommandEnum::PauseProcess)
+        ));
+    }
+
+    #[test]
+    fn guard_terminal_size() {
+        let test_cases = vec![
+            (1, None, true, ""),
+            (2, Some(Usize2d::new(40, 10)), true, ""),
+            (3, Some(Usize2d::new(80, 30)), true, ""),
+            (
+                4,
+                Some(Usize2d::new(39, 6)),
+                false,
+                "\n\n\n     terminal too small (need 40x10)",
+            ),
+            (5, Some(Usize2d::new(12, 1)), false, "\n terminal too"),
+        ];
+        for (test_case, size, expected_fits, expected_text) in test_cases {
+            let mut service = CoordinatorService::init();
+            service.set_terminal_size_source(Box::new(move || size));
+            let mut handle = MemoryHandle::new();
+
+            let fits = service.guard_terminal_size(Usize2d::new(40, 10), &mut handle);
+            assert_eq!(
+                fits,
+                Ok(expected_fits),
+                "Test case {}: The size check is wrong",
+                test_case
+            );
+            assert_eq!(service.fits_terminal(Usize2d::new(40, 10)), expected_fits);
+            assert_eq!(
+                String::from_utf8(handle.get_buffer_content()).unwrap(),
+                expected_text,
+                "Test case {}: The guard message is wrong",
+                test_case
+            );
+        }
+    }
+
+    #[test]
+    fn layout_follows_resizes() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::panel::layout::{Constraint, Direction, LayoutNode};
+
+        let size = Arc::new(Mutex::new(None));
+        let mut service = CoordinatorService::init();
+        let source = size.clone();
+        service.set_terminal_size_source(Box::new(move || *source.lock().unwrap()));
+        let (main_sender, main_receiver) = channel();
+        let (status_sender, status_receiver) = channel();
+        let main = service.register_panel(main_sender);
+        let status = service.register_panel(status_sender);
+        service.set_layout(LayoutNode::Split(
+            Direction::Vertical,
+            vec![
+                (Constraint::Fill, LayoutNode::Panel(main)),
+                (Constraint::Fixed(1), LayoutNode::Panel(status)),
+            ],
+        ));
+        let last_area = |receiver: &Receiver<PanelCommandEnum>| {
+            receiver
+                .try_iter()
+                .last()
+                .and_then(|command| match command {
+                    PanelCommandEnum::MoveTo(area) => {
+                        let (top_left, bottom_right) = area.get_boundary();
+                        Some((top_left.x, top_left.y, bottom_right.x, bottom_right.y))
+                    }
+                    _ => None,
+                })
+        };
+
+        let test_cases = [
+            ("unknown size", None, 0, None, None),
+            (
+                "first size",
+                Some(Usize2d::new(80, 24)),
+                2,
+                Some((0, 0, 79, 22)),
+                Some((0, 23, 79, 23)),
+            ),
+            ("same size", Some(Usize2d::new(80, 24)), 0, None, None),
+            (
+                "resized",
+                Some(Usize2d::new(100, 30)),
+                2,
+                Some((0, 0, 99, 28)),
+                Some((0, 29, 99, 29)),
+            ),
+        ];
+        for (test_case, terminal, moved, main_area, status_area) in test_cases {
+            *size.lock().unwrap() = terminal;
+            assert_eq!(service.apply_layout(), moved, "{}", test_case);
+            assert_eq!(last_area(&main_receiver), main_area, "{}", test_case);
+            assert_eq!(last_area(&status_receiver), status_area, "{}", test_case);
+        }
+
+        *size.lock().unwrap() = Some(Usize2d::new(60, 20));
+        service.tick(Instant::now());
+        assert_eq!(
+            last_area(&main_receiver),
+            Some((0, 0, 59, 18)),
+            "A tick places the panels after a resize"
+        );
+    }
+
+    #[test]
+    fn overlays() {
+        use crate::panel::layout::{Constraint, Direction, LayoutNode};
+
+        let mut service = CoordinatorService::init();
+        service.set_terminal_size_source(Box::new(|| Some(Usize2d::new(80, 24))));
+        let mut receivers = Vec::new();
+        let mut register = |service: &mut CoordinatorService, area: Option<Square>| {
+            let (sender, receiver) = channel();
+            receivers.push(receiver);
+            match area {
+                Some(area) => service.register_overlay(sender, area),
+                None => service.register_panel(sender),
+            }
+        };
+        let sidebar = register(&mut service, None);
+        let main = register(&mut service, None);
+        let help = register(
+            &mut service,
+            Some(Square::new(Usize2d::new(30, 5), Usize2d::new(60, 15))),
+        );
+        let dialog = register(
+            &mut service,
+            Some(Square::new(Usize2d::new(40, 10), Usize2d::new(50, 12))),
+        );
+        service.set_layout(LayoutNode::Split(
+            Direction::Horizontal,
+            vec![
+                (Constraint::Fixed(20), LayoutNode::Panel(sidebar)),
+                (Constraint::Fill, LayoutNode::Panel(main)),
+            ],
+        ));
+        service.apply_layout();
+        let commands = |receivers: &Vec<Receiver<PanelCommandEnum>>| -> Vec<String> {
+            receivers
+                .iter()
+                .map(|receiver| {
+                    receiver
+                        .try_iter()
+                        .map(|command| match command {
+                            PanelCommandEnum::Show => "show",
+                            PanelCommandEnum::Hide => "hide",
+                            PanelCommandEnum::MoveTo(_) => "move",
+                            PanelCommandEnum::ForceRedraw => "redraw",
+                            _ => "other",
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect()
+        };
+        assert_eq!(
+            commands(&receivers),
+            ["move", "move", "hide move", "hide move"],
+            "Overlays start hidden"
+        );
+
+        assert!(service.show_overlay(help));
+        assert!(service.show_overlay(dialog));
+        assert!(
+            !service.show_overlay(main),
+            "The main panel is not an overlay"
+        );
+        assert_eq!(commands(&receivers), ["", "", "show", "show"]);
+
+        assert!(service.hide_overlay(help));
+        assert_eq!(
+            commands(&receivers),
+            ["", "redraw", "hide", "redraw"],
+            "Only the panels below the help are redrawn"
+        );
+    }
+
+    #[test]
+    fn dropped_frames() {
+        let mut service = CoordinatorService::init();
+        let (frame_sender, _frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        service.frame_senders.push(frame_sender.clone());
+        for _ in 0..5 {
+            frame_sender.send(Vec::new().into()).unwrap();
+        }
+        let mut metrics = Metrics::new(Instant::now());
+        let test_cases = [("dropped", 3), ("taken once", 0)];
+        for (test_case, expected) in test_cases {
+            assert_eq!(
+                service.record_dropped_frames(&mut metrics),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+        assert_eq!(metrics.count(DROPPED_FRAMES), 3);
+    }
+
+    #[test]
+    fn scheduled_flushes() {
+        let terminal = std::sync::Arc::new(std::sync::Mutex::new(MemoryHandle::new()));
+        let mut service = CoordinatorService::init();
+        let scheduler = service.schedule_flushes(Box::new(
+            crate::handler::shared_handle::SharedHandle::init(terminal.clone()),
+        ));
+        for (row, title) in ["left", "right"].into_iter().enumerate() {
+            let area = Square::new(Usize2d::new(0, row), Usize2d::new(6, row));
+            let (panel, _) = service
+                .add_panel(area, Box::new(scheduler.handle()))
+                .unwrap();
+            service.send(panel, PanelCommandEnum::SetTitle(title.to_string()));
+        }
+        let screen =
+            || String::from_utf8_lossy(&terminal.lock().unwrap().get_buffer_content()).to_string();
+        let start = Instant::now();
+        while screen() != " left  \n right " && start.elapsed() < Duration::from_secs(1) {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(screen(), " left  \n right ");
+        service.shutdown();
+        assert!(
+            scheduler.flushes() > 0,
+            "The render pool flushes the terminal"
+        );
+    }
+    #[test]
+    fn shutdown() {
+        let start = Instant::now();
+        let mut service = CoordinatorService::init();
+        let (command_sender, command_receiver) = channel();
+        let panel = service.register_panel(command_sender);
+        service.schedule_repeating(
+            panel,
+            Duration::from_secs(1),
+            PanelCommandEnum::ForceRedraw,
+            start,
+        );
+        let handle = std::sync::Arc::new(std::sync::Mutex::new(MemoryHandle::new()));
+        let (pooled, _frame_sender) = service
+            .add_panel(
+                Square::new(Usize2d::new(0, 0), Usize2d::new(3, 1)),
+                Box::new(crate::handler::shared_handle::SharedHandle::init(handle)),
+            )
+            .unwrap();
+        assert_eq!((pooled, service.running_panels()), (1, 1));
+        let (hook_sender, hook_receiver) = channel();
+        for name in ["first", "second"] {
+            let hook_sender = hook_sender.clone();
+            service.on_shutdown(Box::new(move || hook_sender.send(name).unwrap()));
+        }
+
+        service.shutdown();
+        assert!(!service.state);
+        assert_eq!(
+            service.running_panels(),
+            0,
+            "The render pool should be stopped"
+        );
+        assert!(matches!(
+            command_receiver.try_recv(),
+            Ok(PanelCommandEnum::KillProcess)
+        ));
+        assert_eq!(
+            hook_receiver.try_iter().collect::<Vec<_>>(),
+            ["first", "second"]
+        );
+        assert_eq!(
+            service.tick(start + Duration::from_secs(5)),
+            0,
+            "The timers should be cancelled"
+        );
     }
 }
@@ -0,0 +1,126 @@
+use std::{fmt::Display, sync::Arc};
+
+use crate::{
+    rendering::{render_object::RenderObject, sprite::Sprite},
+    shared::float2d::Float2d,
+};
+
+/// A unique identifier handed out by the `EntityLayer`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EntityId(pub usize);
+impl Display for EntityId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// Something in a game that has a position, moves with a velocity and is drawn with a sprite
+///
+/// The sprite is shared so that many entities can be drawn with the same sprite without copying it
+#[derive(Debug)]
+pub struct Entity {
+    id: EntityId,
+    pub position: Float2d,
+    pub velocity: Float2d,
+    pub sprite: Arc<Sprite>,
+}
+impl Entity {
+    /// Create a new entity
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the entity
+    /// * `sprite` - the sprite the entity is drawn with
+    /// * `position` - the position of the top left corner of the sprite
+    /// * `velocity` - the distance moved per tick
+    pub fn new(id: EntityId, sprite: Arc<Sprite>, position: Float2d, velocity: Float2d) -> Self {
+        Entity {
+            id,
+            position,
+            velocity,
+            sprite,
+        }
+    }
+    pub fn id(&self) -> EntityId {
+        self.id
+    }
+    /// Move the entity by its velocity for a number of ticks
+    ///
+    /// # Arguments
+    ///
+    /// * `ticks` - the number of ticks that passed, fractions are allowed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use tic_tac_toe2::{entity::entity::{Entity, EntityId}, rendering::sprite::Sprite, shared::float2d::Float2d};
+    /// let mut entity = Entity::new(
+    ///     EntityId(0),
+    ///     Arc::new(Sprite::default()),
+    ///     Float2d::new(1.0, 1.0),
+    ///     Float2d::new(0.5, 0.0),
+    /// );
+    /// entity.integrate(2.0);
+    /// assert_eq!(entity.position, Float2d::new(2.0, 1.0));
+    /// ```
+    pub fn integrate(&mut self, ticks: f32) {
+        self.position = self.position + self.velocity * ticks;
+    }
+    /// Get the object that should be sent to a panel to draw the entity
+    ///
+    /// # Returns
+    ///
+    /// `Some(RenderObject)` at the current cell, or `None` if the entity is off the top or left
+    /// of the screen
+    pub fn to_render_object(&self) -> Option<RenderObject> {
+        self.position
+            .to_coord()
+            .map(|coord| RenderObject::new(self.sprite.clone(), coord))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{rendering::sprite::Sprite, shared::float2d::Float2d};
+
+    use super::{Entity, EntityId};
+
+    #[test]
+    fn integrate() {
+        let test_cases = vec![
+            (1, (1.0, 1.0), (0.5, 0.0), 2.0, (2.0, 1.0), Some((2, 1))),
+            (2, (1.0, 1.0), (0.0, 0.25), 1.0, (1.0, 1.25), Some((1, 1))),
+            (3, (0.5, 0.0), (-1.0, 0.0), 1.0, (-0.5, 0.0), None),
+            (4, (3.0, 4.0), (1.0, 1.0), 0.0, (3.0, 4.0), Some((3, 4))),
+        ];
+        for (test_case, (x, y), (dx, dy), ticks, (expected_x, expected_y), expected_coord) in
+            test_cases
+        {
+            let mut entity = Entity::new(
+                EntityId(test_case),
+                Arc::new(Sprite::default()),
+                Float2d::new(x, y),
+                Float2d::new(dx, dy),
+            );
+            entity.integrate(ticks);
+            assert_eq!(
+                entity.position,
+                Float2d::new(expected_x, expected_y),
+                "Test case {}: The position after integrating is wrong",
+                test_case
+            );
+            let actual_coord = entity.to_render_object().map(|object| {
+                let location = object.get_location();
+                (location.x, location.y)
+            });
+            assert_eq!(
+                actual_coord, expected_coord,
+                "Test case {}: The render object location is wrong",
+                test_case
+            );
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use crate::{
+    rendering::{render_object::RenderObject, sprite::Sprite},
+    shared::float2d::Float2d,
+};
+
+use super::entity::{Entity, EntityId};
+
+/// Owns the entities of a game and moves them every tick
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use tic_tac_toe2::{entity::entity_layer::EntityLayer, rendering::sprite::Sprite, shared::float2d::Float2d};
+/// # let (frame_sender, _frames) = std::sync::mpsc::channel();
+/// let mut layer = EntityLayer::default();
+/// let ball = layer.spawn(
+///     Arc::new(Sprite::default()),
+///     Float2d::new(1.0, 1.0),
+///     Float2d::new(1.0, 0.0),
+/// );
+/// let render_objects = layer.update();
+/// let _ = frame_sender.send(render_objects);
+/// ```
+#[derive(Debug, Default)]
+pub struct EntityLayer {
+    entities: Vec<Entity>,
+    next_id: usize,
+}
+impl EntityLayer {
+    /// Add a new entity to the layer
+    ///
+    /// # Arguments
+    ///
+    /// * `sprite` - the sprite the entity is drawn with
+    /// * `position` - the starting position of the entity
+    /// * `velocity` - the distance the entity moves per tick
+    ///
+    /// # Returns
+    ///
+    /// The `EntityId` that can be used to look the entity up again
+    pub fn spawn(&mut self, sprite: Arc<Sprite>, position: Float2d, velocity: Float2d) -> EntityId {
+        let id = EntityId(self.next_id);
+        self.next_id += 1;
+        self.entities
+            .push(Entity::new(id, sprite, position, velocity));
+        id
+    }
+    /// Remove an entity from the layer
+    ///
+    /// # Returns
+    ///
+    /// The removed `Entity`, or `None` if there is no entity with the id
+    pub fn remove(&mut self, id: EntityId) -> Option<Entity> {
+        let index = self.entities.iter().position(|entity| entity.id() == id)?;
+        Some(self.entities.remove(index))
+    }
+    pub fn get(&self, id: EntityId) -> Option<&Entity> {
+        self.entities.iter().find(|entity| entity.id() == id)
+    }
+    pub fn get_mut(&mut self, id: EntityId) -> Option<&mut Entity> {
+        self.entities.iter_mut().find(|entity| entity.id() == id)
+    }
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+    /// Move every entity by one tick
+    ///
+    /// # Returns
+    ///
+    /// The `RenderObject`s of the entities after moving, in the order they were spawned
+    pub fn update(&mut self) -> Vec<RenderObject> {
+        for entity in self.entities.iter_mut() {
+            entity.integrate(1.0);
+        }
+        self.render_objects()
+    }
+    /// Get the `RenderObject`s for every entity that is on screen
+    pub fn render_objects(&self) -> Vec<RenderObject> {
+        self.entities
+            .iter()
+            .filter_map(|entity| entity.to_render_object())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{rendering::sprite::Sprite, shared::float2d::Float2d};
+
+    use super::EntityLayer;
+
+    #[test]
+    fn spawn_update_and_remove() {
+        let sprite = Arc::new(Sprite::default());
+        let mut layer = EntityLayer::default();
+        let ball = layer.spawn(
+            sprite.clone(),
+            Float2d::new(1.0, 1.0),
+            Float2d::new(1.0, 0.5),
+        );
+        let wall = layer.spawn(sprite.clone(), Float2d::new(0.0, 0.0), Float2d::default());
+        assert_ne!(ball, wall, "Every entity should get a unique id");
+        assert_eq!(layer.len(), 2);
+
+        let render_objects = layer.update();
+        assert_eq!(render_objects.len(), 2);
+        let location = render_objects[0].get_location();
+        assert_eq!((location.x, location.y), (2, 1));
+        assert_eq!(layer.get(ball).unwrap().position, Float2d::new(2.0, 1.5));
+
+        layer.get_mut(wall).unwrap().velocity = Float2d::new(-1.0, 0.0);
+        let render_objects = layer.update();
+        assert_eq!(
+            render_objects.len(),
+            1,
+            "Entities that moved off screen should not be rendered"
+        );
+
+        assert!(layer.remove(wall).is_some());
+        assert!(layer.remove(wall).is_none());
+        assert!(layer.get(wall).is_none());
+        let new_id = layer.spawn(sprite, Float2d::default(), Float2d::default());
+        assert_ne!(new_id, wall, "Ids should not be reused");
+    }
+}
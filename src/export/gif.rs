@@ -0,0 +1,466 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use super::image::{Image, ImageOptions, Rgb};
+use crate::{shared::frame::Frame, utils::progress::Progress};
+
+/// The most colors a GIF palette can hold
+const MAX_COLORS: usize = 256;
+/// The most codes an LZW table can hold before it has to be cleared
+const MAX_CODES: u16 = 4096;
+/// The widest LZW code in bits
+const MAX_CODE_SIZE: u8 = 12;
+/// The largest data sub-block
+const MAX_SUB_BLOCK: usize = 255;
+
+/// Collects the frames of a number of generations and encodes them as an animated GIF
+///
+/// The frames are drawn with `Image::from_frame`, like the PNG screenshots, so the recording
+/// looks the same as a screenshot of every generation
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{path::Path, time::Duration};
+/// # use tic_tac_toe2::{export::{gif::GifRecorder, image::ImageOptions}, ConwaysGame};
+/// # fn main() -> Result<(), std::io::Error> {
+/// # let mut game = ConwaysGame::headless(16, 16, 42);
+/// let mut recorder = GifRecorder::new(100, ImageOptions::default(), Duration::from_millis(100));
+/// while recorder.push(&game.frame()) {
+///     game.step();
+/// }
+/// recorder.save(Path::new("run.gif"))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct GifRecorder {
+    images: Vec<Image>,
+    generations: usize,
+    pub options: ImageOptions,
+    pub frame_delay: Duration,
+}
+impl GifRecorder {
+    /// Create a recorder without frames
+    ///
+    /// # Arguments
+    ///
+    /// * `generations` - the number of frames to record
+    /// * `options` - the size of a character of a frame in pixels
+    /// * `frame_delay` - how long every frame is shown, GIFs count in hundredths of a second
+    pub fn new(generations: usize, options: ImageOptions, frame_delay: Duration) -> Self {
+        GifRecorder {
+            images: Vec::with_capacity(generations),
+            generations,
+            options,
+            frame_delay,
+        }
+    }
+    /// Add a frame to the recording, frames after the last generation are ignored
+    ///
+    /// # Returns
+    ///
+    /// `true` if the recording still has room for more frames
+    pub fn push(&mut self, frame: &Frame) -> bool {
+        if !self.is_full() {
+            self.images.push(Image::from_frame(frame, &self.options));
+        }
+        !self.is_full()
+    }
+    /// Check if every generation was recorded
+    pub fn is_full(&self) -> bool {
+        self.images.len() >= self.generations
+    }
+    /// Get the number of recorded frames
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+    /// Check if nothing was recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+    /// Encode the recorded frames as a GIF that loops forever
+    pub fn encode(&self) -> Vec<u8> {
+        encode(&self.images, self.frame_delay)
+    }
+    /// Encode the recorded frames and write them to a file
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.encode())
+    }
+    /// Encode the recorded frames and write them to a file on a worker thread, so that a game
+    /// keeps running while a long recording is saved
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the file to write
+    /// * `now` - the current time, the time that is left is estimated from it
+    pub fn save_in_background(self, path: PathBuf, now: Instant) -> GifExport {
+        let progress = Progress::new(self.images.len() as u64, now);
+        let worker_progress = progress.clone();
+        let worker_path = path.clone();
+        let worker = thread::spawn(move || {
+            let gif = encode_with_progress(&self.images, self.frame_delay, &worker_progress);
+            fs::write(worker_path, gif)
+        });
+        GifExport {
+            worker,
+            progress,
+            path,
+        }
+    }
+}
+
+/// A recording that is saved on a worker thread, see `GifRecorder::save_in_background`
+#[derive(Debug)]
+pub struct GifExport {
+    worker: JoinHandle<io::Result<()>>,
+    progress: Progress,
+    pub path: PathBuf,
+}
+impl GifExport {
+    /// Get the frames that are encoded so far
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+    /// Check if the file is written, or writing it failed
+    pub fn is_finished(&self) -> bool {
+        self.worker.is_finished()
+    }
+    /// Wait until the file is written
+    pub fn join(self) -> io::Result<PathBuf> {
+        match self.worker.join() {
+            Ok(result) => result.map(|_| self.path),
+            Err(_) => Err(io::Error::other("The GIF export panicked")),
+        }
+    }
+}
+
+/// Encode images as the frames of an animated GIF that loops forever
+///
+/// All frames share one palette with the colors of the images. Frames are drawn with at most 256
+/// colors, further colors are replaced by the closest color in the palette
+pub fn encode(images: &[Image], frame_delay: Duration) -> Vec<u8> {
+    encode_with_progress(
+        images,
+        frame_delay,
+        &Progress::new(images.len() as u64, Instant::now()),
+    )
+}
+
+/// Encode images like `encode`, counting every frame that is encoded
+pub fn encode_with_progress(
+    images: &[Image],
+    frame_delay: Duration,
+    progress: &Progress,
+) -> Vec<u8> {
+    let width = images.iter().map(|image| image.width).max().unwrap_or(0);
+    let height = images.iter().map(|image| image.height).max().unwrap_or(0);
+    let palette = palette(images);
+    // The palette size is a power of two of at least 2 colors
+    let color_bits = (palette.len().max(2) as u32)
+        .next_power_of_two()
+        .trailing_zeros() as u8;
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend((width as u16).to_le_bytes());
+    gif.extend((height as u16).to_le_bytes());
+    // A global palette with 8 bit colors
+    gif.extend([0xf0 | (color_bits - 1), 0, 0]);
+    for index in 0..1usize << color_bits {
+        gif.extend(palette.get(index).unwrap_or(&[0, 0, 0]));
+    }
+    // The application extension that makes the animation loop forever
+    gif.extend([0x21, 0xff, 0x0b]);
+    gif.extend(b"NETSCAPE2.0");
+    gif.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    let delay = (frame_delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+    let mut lookup: HashMap<Rgb, u8> = HashMap::new();
+    for image in images {
+        // The graphic control extension with the delay, every frame replaces the one before
+        gif.extend([0x21, 0xf9, 0x04, 0x04]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0, 0]);
+
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend((image.width as u16).to_le_bytes());
+        gif.extend((image.height as u16).to_le_bytes());
+        gif.push(0);
+
+        let indices: Vec<u8> = image
+            .pixels
+            .iter()
+            .map(|color| {
+                *lookup
+                    .entry(*color)
+                    .or_insert_with(|| closest_color(&palette, *color))
+            })
+            .collect();
+        let min_code_size = color_bits.max(2);
+        gif.push(min_code_size);
+        for block in lzw(&indices, min_code_size).chunks(MAX_SUB_BLOCK) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+        progress.advance(1);
+    }
+    gif.push(0x3b);
+    gif
+}
+
+/// Collect the first 256 colors the images use, in the order they appear
+fn palette(images: &[Image]) -> Vec<Rgb> {
+    let mut palette = Vec::new();
+    for color in images.iter().flat_map(|image| image.pixels.iter()) {
+        if palette.len() == MAX_COLORS {
+            break;
+        }
+        if !palette.contains(color) {
+            palette.push(*color);
+        }
+    }
+    palette
+}
+
+/// Find the palette index of the color that is the closest to a color
+fn closest_color(palette: &[Rgb], color: Rgb) -> u8 {
+    let distance = |other: &Rgb| -> i32 {
+        (0..3)
+            .map(|channel| (other[channel] as i32 - color[channel] as i32).pow(2))
+            .sum()
+    };
+    (0..palette.len())
+        .min_by_key(|index| distance(&palette[*index]))
+        .unwrap_or(0) as u8
+}
+
+/// Compress palette indices with the variable width LZW that GIF uses
+///
+/// # Arguments
+///
+/// * `indices` - the palette index of every pixel
+/// * `min_code_size` - the number of bits of a palette index, at least 2
+pub(crate) fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut writer = BitWriter::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut code_size = min_code_size + 1;
+    writer.write(clear, code_size);
+
+    let mut iter = indices.iter();
+    let mut prefix = match iter.next() {
+        Some(index) => *index as u16,
+        None => {
+            writer.write(end, code_size);
+            return writer.finish();
+        }
+    };
+    for index in iter {
+        if let Some(code) = table.get(&(prefix, *index)) {
+            prefix = *code;
+            continue;
+        }
+        writer.write(prefix, code_size);
+        table.insert((prefix, *index), next);
+        next += 1;
+        // The decoder adds its codes one code later, so it widens the codes one code later too
+        if next > 1 << code_size && code_size < MAX_CODE_SIZE {
+            code_size += 1;
+        }
+        if next == MAX_CODES {
+            writer.write(clear, code_size);
+            table.clear();
+            next = end + 1;
+            code_size = min_code_size + 1;
+        }
+        prefix = *index as u16;
+    }
+    writer.write(prefix, code_size);
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+/// Packs codes into bytes starting at the lowest bit
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs, process,
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        export::image::{Image, ImageOptions},
+        rendering::colors::TerminalColors,
+        shared::frame::Pixel,
+    };
+
+    use super::{closest_color, encode, lzw, GifRecorder};
+
+    /// Decode an LZW stream the way a GIF viewer does
+    fn decode(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<usize> = None;
+        let mut output = Vec::new();
+        let (mut position, total) = (0usize, bytes.len() * 8);
+        while position + code_size as usize <= total {
+            let mut code = 0usize;
+            for bit in 0..code_size as usize {
+                let at = position + bit;
+                code |= ((bytes[at / 8] >> (at % 8)) as usize & 1) << bit;
+            }
+            position += code_size as usize;
+            if code == clear {
+                table = (0..clear).map(|index| vec![index as u8]).collect();
+                table.extend([vec![], vec![]]);
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                break;
+            }
+            let entry = match (table.get(code), previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => {
+                    let mut entry = table[previous].clone();
+                    entry.push(table[previous][0]);
+                    entry
+                }
+                (None, None) => panic!("Unknown first code {}", code),
+            };
+            if let Some(previous) = previous {
+                if table.len() < 4096 {
+                    let mut added = table[previous].clone();
+                    added.push(entry[0]);
+                    table.push(added);
+                    if table.len() == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                }
+            }
+            output.extend(&entry);
+            previous = Some(code);
+        }
+        output
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let noise: Vec<u8> = (0..20_000u32)
+            .map(|index| (index.wrapping_mul(2_654_435_761) >> 13) as u8 % 7)
+            .collect();
+        let test_cases = [
+            ("empty", vec![], 2),
+            ("one pixel", vec![3], 2),
+            ("repeated", vec![1; 5_000], 2),
+            ("table is cleared", noise, 3),
+            ("wide palette", (0..=255).cycle().take(3_000).collect(), 8),
+        ];
+        for (test_case, indices, min_code_size) in test_cases {
+            let decoded = decode(&lzw(&indices, min_code_size), min_code_size);
+            assert_eq!(decoded, indices, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn closest() {
+        let palette = [[0, 0, 0], [250, 10, 10], [255, 255, 255]];
+        let test_cases = [
+            ("exact", [250, 10, 10], 1),
+            ("near white", [200, 220, 210], 2),
+            ("dark", [20, 0, 30], 0),
+        ];
+        for (test_case, color, expected) in test_cases {
+            assert_eq!(closest_color(&palette, color), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn gif_layout() {
+        let first = Image::new(3, 2, [1, 2, 3]);
+        let mut second = first.clone();
+        second.fill(0, 0, 1, 1, [4, 5, 6]);
+        let gif = encode(&[first, second], Duration::from_millis(250));
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(&gif[6..10], &[3, 0, 2, 0]);
+        // Two colors in a palette of two
+        assert_eq!(gif[10], 0xf0);
+        assert_eq!(&gif[13..19], &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(&gif[22..33], b"NETSCAPE2.0");
+        assert_eq!(&gif[38..44], &[0x21, 0xf9, 0x04, 0x04, 25, 0]);
+        assert_eq!(gif.iter().filter(|byte| **byte == 0x2c).count(), 2);
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+
+    #[test]
+    fn recorder() {
+        let frame = vec![vec![Pixel::new(
+            ' ',
+            TerminalColors::Red,
+            TerminalColors::Default,
+        )]];
+        let options = ImageOptions {
+            cell_width: 4,
+            cell_height: 4,
+            draw_glyphs: false,
+        };
+        let mut recorder = GifRecorder::new(2, options, Duration::from_millis(100));
+        let test_cases = [
+            ("first frame", true, 1),
+            ("last frame", false, 2),
+            ("full", false, 2),
+        ];
+        for (test_case, has_room, frames) in test_cases {
+            assert_eq!(recorder.push(&frame), has_room, "{}", test_case);
+            assert_eq!(recorder.len(), frames, "{}", test_case);
+        }
+        assert!(recorder.is_full());
+        assert_eq!(&recorder.encode()[6..10], &[4, 0, 4, 0]);
+
+        let expected = recorder.encode();
+        let path = env::temp_dir().join(format!("gif_export_test_{}.gif", process::id()));
+        let export = recorder.save_in_background(path.clone(), Instant::now());
+        let progress = export.progress().clone();
+        assert_eq!(export.join().ok(), Some(path.clone()));
+        assert_eq!(progress.get(), (2, 2), "Every frame is counted");
+        let written = fs::read(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(written.ok(), Some(expected));
+    }
+}
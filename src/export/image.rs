@@ -0,0 +1,230 @@
+use crate::{
+    rendering::{
+        colors::TerminalColors,
+        fonts::{block_font::BlockFont, standard_font::STANDARD_FONT},
+    },
+    shared::frame::Frame,
+};
+
+/// The color of an image pixel as red, green and blue
+pub type Rgb = [u8; 3];
+
+/// The color that is used for `TerminalColors::Default` backgrounds
+pub const DEFAULT_BACKGROUND: Rgb = [0, 0, 0];
+/// The color that is used for `TerminalColors::Default` foregrounds
+pub const DEFAULT_FOREGROUND: Rgb = [229, 229, 229];
+
+/// Convert a 256 color terminal code to the color xterm shows for it
+///
+/// # Arguments
+///
+/// * `color` - the terminal color
+/// * `default` - the color used for `TerminalColors::Default`, which depends on the terminal
+pub fn terminal_rgb(color: TerminalColors, default: Rgb) -> Rgb {
+    const STANDARD: [Rgb; 16] = [
+        [0, 0, 0],
+        [205, 0, 0],
+        [0, 205, 0],
+        [205, 205, 0],
+        [0, 0, 238],
+        [205, 0, 205],
+        [0, 205, 205],
+        [229, 229, 229],
+        [127, 127, 127],
+        [255, 0, 0],
+        [0, 255, 0],
+        [255, 255, 0],
+        [92, 92, 255],
+        [255, 0, 255],
+        [0, 255, 255],
+        [255, 255, 255],
+    ];
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let code = color as i32;
+    match code {
+        0..=15 => STANDARD[code as usize],
+        16..=231 => {
+            let index = code as usize - 16;
+            [
+                CUBE_LEVELS[index / 36],
+                CUBE_LEVELS[(index / 6) % 6],
+                CUBE_LEVELS[index % 6],
+            ]
+        }
+        232..=255 => {
+            let level = 8 + 10 * (code as u8 - 232);
+            [level, level, level]
+        }
+        _ => default,
+    }
+}
+
+/// How a frame is turned into an image
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageOptions {
+    /// The width in pixels of a character of the frame
+    pub cell_width: usize,
+    /// The height in pixels of a character of the frame
+    pub cell_height: usize,
+    /// Draw the characters in the foreground color, otherwise only the backgrounds are drawn
+    pub draw_glyphs: bool,
+}
+impl Default for ImageOptions {
+    fn default() -> Self {
+        ImageOptions {
+            cell_width: 8,
+            cell_height: 16,
+            draw_glyphs: true,
+        }
+    }
+}
+
+/// An image with a color per pixel, stored row by row from the top left
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+impl Image {
+    /// Create an image filled with one color
+    pub fn new(width: usize, height: usize, color: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+    /// Get the color of a pixel
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+    /// Fill a rectangle with a color, the parts outside of the image are skipped
+    pub fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgb) {
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                self.pixels[row * self.width + column] = color;
+            }
+        }
+    }
+    /// Draw a frame as an image where every character is a colored rectangle
+    ///
+    /// Full blocks are filled with the foreground color. Letters and digits are drawn with the
+    /// standard block font scaled to the rectangle, other characters only show their background,
+    /// so the image looks the same whatever font the terminal uses
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{export::image::{Image, ImageOptions}, ConwaysGame};
+    /// # let frame = ConwaysGame::headless(16, 16, 42).frame();
+    /// let image = Image::from_frame(&frame, &ImageOptions::default());
+    /// ```
+    pub fn from_frame(frame: &Frame, options: &ImageOptions) -> Self {
+        let columns = frame.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut image = Image::new(
+            columns * options.cell_width,
+            frame.len() * options.cell_height,
+            DEFAULT_BACKGROUND,
+        );
+        for (row, pixels) in frame.iter().enumerate() {
+            for (column, pixel) in pixels.iter().enumerate() {
+                let x = column * options.cell_width;
+                let y = row * options.cell_height;
+                let background = terminal_rgb(pixel.background_color(), DEFAULT_BACKGROUND);
+                image.fill(x, y, options.cell_width, options.cell_height, background);
+                if options.draw_glyphs {
+                    let foreground = terminal_rgb(pixel.foreground_color(), DEFAULT_FOREGROUND);
+                    image.draw_glyph(pixel.char(), x, y, options, foreground);
+                }
+            }
+        }
+        image
+    }
+    /// Draw a character over the rectangle of a cell
+    fn draw_glyph(&mut self, ch: char, x: usize, y: usize, options: &ImageOptions, color: Rgb) {
+        if ch == '█' {
+            self.fill(x, y, options.cell_width, options.cell_height, color);
+            return;
+        }
+        let glyph = match STANDARD_FONT.glyph(ch.to_ascii_lowercase()) {
+            Some(glyph) => glyph,
+            None => return,
+        };
+        let (font_width, font_height) = (STANDARD_FONT.width(), STANDARD_FONT.height());
+        for row in 0..options.cell_height {
+            let line: Vec<char> = glyph[row * font_height / options.cell_height]
+                .chars()
+                .collect();
+            for column in 0..options.cell_width {
+                if line[column * font_width / options.cell_width] != ' ' {
+                    self.fill(x + column, y + row, 1, 1, color);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rendering::colors::TerminalColors, shared::frame::Pixel};
+
+    use super::{terminal_rgb, Image, ImageOptions, DEFAULT_BACKGROUND, DEFAULT_FOREGROUND};
+
+    #[test]
+    fn colors() {
+        let test_cases = [
+            ("color cube", TerminalColors::LightGreen, [135, 255, 135]),
+            ("first cube color", TerminalColors::DarkBlue, [0, 0, 135]),
+            ("grey ramp", TerminalColors::Black, [8, 8, 8]),
+            ("last cube color", TerminalColors::White, [255, 255, 255]),
+            ("terminal default", TerminalColors::Default, [1, 2, 3]),
+        ];
+        for (test_case, color, expected) in test_cases {
+            assert_eq!(terminal_rgb(color, [1, 2, 3]), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn from_frame() {
+        let frame = vec![
+            vec![
+                Pixel::new(' ', TerminalColors::Red, TerminalColors::White),
+                Pixel::new('█', TerminalColors::Red, TerminalColors::White),
+            ],
+            vec![Pixel::new(
+                'i',
+                TerminalColors::Default,
+                TerminalColors::Default,
+            )],
+        ];
+        let options = ImageOptions {
+            cell_width: 7,
+            cell_height: 5,
+            draw_glyphs: true,
+        };
+        let image = Image::from_frame(&frame, &options);
+        assert_eq!((image.width, image.height), (14, 10));
+        let red = terminal_rgb(TerminalColors::Red, DEFAULT_BACKGROUND);
+        let white = terminal_rgb(TerminalColors::White, DEFAULT_FOREGROUND);
+        let test_cases = [
+            ("background", (3, 2), red),
+            ("full block", (10, 2), white),
+            ("short row", (10, 7), DEFAULT_BACKGROUND),
+            ("glyph", (3, 6), DEFAULT_FOREGROUND),
+            ("around the glyph", (0, 6), DEFAULT_BACKGROUND),
+        ];
+        for (test_case, (x, y), expected) in test_cases {
+            assert_eq!(image.pixel(x, y), expected, "{}", test_case);
+        }
+
+        let without_glyphs = Image::from_frame(
+            &frame,
+            &ImageOptions {
+                draw_glyphs: false,
+                ..options
+            },
+        );
+        assert_eq!(without_glyphs.pixel(10, 2), red);
+    }
+}
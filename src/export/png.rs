@@ -0,0 +1,153 @@
+use std::{fs, io, path::Path};
+
+use super::image::Image;
+
+/// The bytes every PNG file starts with
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+/// The largest block deflate can store without compression
+const MAX_STORED_BLOCK: usize = 65_535;
+
+/// Encode an image as an 8 bit RGB PNG
+///
+/// The image data is stored without compression, which keeps the encoder small. Screenshots of
+/// the board are mostly a handful of colors, so they are still small enough to share
+///
+/// # Examples
+///
+/// ```
+/// # use tic_tac_toe2::{export::{image::{Image, ImageOptions}, png::encode}, ConwaysGame};
+/// # let frame = ConwaysGame::headless(16, 16, 42).frame();
+/// let bytes = encode(&Image::from_frame(&frame, &ImageOptions::default()));
+/// ```
+pub fn encode(image: &Image) -> Vec<u8> {
+    let mut header = Vec::with_capacity(13);
+    header.extend((image.width as u32).to_be_bytes());
+    header.extend((image.height as u32).to_be_bytes());
+    // 8 bits per channel, truecolor, deflate, adaptive filtering, no interlacing
+    header.extend([8, 2, 0, 0, 0]);
+
+    // Every row starts with its filter type, 0 leaves the row as it is
+    let mut rows = Vec::with_capacity(image.height * (1 + image.width * 3));
+    for row in image.pixels.chunks(image.width.max(1)).take(image.height) {
+        rows.push(0);
+        for pixel in row {
+            rows.extend(pixel);
+        }
+    }
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&rows));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Encode an image as a PNG and write it to a file
+pub fn write_png(image: &Image, path: &Path) -> io::Result<()> {
+    fs::write(path, encode(image))
+}
+
+/// Append a chunk with its length and checksum
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wrap data in a zlib stream of uncompressed deflate blocks
+pub(crate) fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // A 32K window with deflate and the check bits that make the header a multiple of 31
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_last = blocks.peek().is_none();
+        stream.push(is_last as u8);
+        let length = block.len() as u16;
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+/// The CRC-32 checksum that PNG uses for its chunks
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// The Adler-32 checksum that ends a zlib stream
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65_521;
+        b = (b + a) % 65_521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export::image::Image;
+
+    use super::{adler32, crc32, encode, zlib_stored, SIGNATURE};
+
+    #[test]
+    fn checksums() {
+        let test_cases = [
+            ("empty", "", 0, 1),
+            ("text", "123456789", 0xcbf4_3926, 0x091e_01de),
+            ("chunk type", "IEND", 0xae42_6082, 0x02d7_0121),
+        ];
+        for (test_case, data, crc, adler) in test_cases {
+            assert_eq!(crc32(data.as_bytes()), crc, "crc of {}", test_case);
+            assert_eq!(adler32(data.as_bytes()), adler, "adler of {}", test_case);
+        }
+    }
+
+    #[test]
+    fn stored_blocks() {
+        let data = vec![7u8; 70_000];
+        let stream = zlib_stored(&data);
+        // Two blocks with a five byte header each, the zlib header and the checksum
+        assert_eq!(stream.len(), 70_000 + 2 * 5 + 2 + 4);
+        assert_eq!(&stream[..5], &[0x78, 0x01, 0, 0xff, 0xff]);
+        assert_eq!(stream[2 + 5 + 65_535], 1, "The second block is the last");
+        assert_eq!(
+            zlib_stored(&[]),
+            vec![0x78, 0x01, 1, 0, 0, 0xff, 0xff, 0, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn png_layout() {
+        let image = Image::new(2, 3, [10, 20, 30]);
+        let png = encode(&image);
+        assert_eq!(&png[..8], &SIGNATURE);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 2, 0, 0, 0, 3]);
+        assert_eq!(
+            &png[png.len() - 12..],
+            &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+        // A row is the filter byte and three bytes per pixel
+        let data_length = u32::from_be_bytes([png[33], png[34], png[35], png[36]]);
+        assert_eq!(&png[37..41], b"IDAT");
+        assert_eq!(data_length as usize, 2 + 5 + 3 * (1 + 2 * 3) + 4);
+        assert_eq!(&png[41 + 7..41 + 11], &[0, 10, 20, 30]);
+    }
+}
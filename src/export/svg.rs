@@ -0,0 +1,298 @@
+use std::fmt::Write;
+
+use super::image::{terminal_rgb, ImageOptions, Rgb, DEFAULT_BACKGROUND, DEFAULT_FOREGROUND};
+use crate::{
+    shared::frame::Frame,
+    tic_tac_toe::board::{Board, Mark},
+};
+
+/// The color of the lines between the cells of a tic-tac-toe board
+const GRID_COLOR: Rgb = [127, 127, 127];
+/// The colors the marks of a tic-tac-toe board are drawn in
+const X_COLOR: Rgb = [205, 0, 0];
+const O_COLOR: Rgb = [92, 92, 255];
+
+/// Draw a frame as an SVG where every character is a colored rectangle
+///
+/// Neighbouring characters with the same color are merged into one rectangle, which keeps the
+/// file small for boards with large areas of one color. Full blocks are filled with the
+/// foreground color, other characters are written as monospace text
+///
+/// # Arguments
+///
+/// * `frame` - the characters and colors to draw
+/// * `options` - the size of a character, `draw_glyphs` turns the text on or off
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs;
+/// # use tic_tac_toe2::{export::{image::ImageOptions, svg::frame_svg}, ConwaysGame};
+/// # fn main() -> Result<(), std::io::Error> {
+/// # let game = ConwaysGame::headless(16, 16, 42);
+/// fs::write("board.svg", frame_svg(&game.frame(), &ImageOptions::default()))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn frame_svg(frame: &Frame, options: &ImageOptions) -> String {
+    let columns = frame.iter().map(|row| row.len()).max().unwrap_or(0);
+    let (width, height) = (
+        columns * options.cell_width,
+        frame.len() * options.cell_height,
+    );
+    let mut svg = header(width, height, DEFAULT_BACKGROUND);
+    let mut text = String::new();
+    for (row, pixels) in frame.iter().enumerate() {
+        let fills: Vec<Rgb> = pixels
+            .iter()
+            .map(|pixel| match pixel.char() {
+                '█' => terminal_rgb(pixel.foreground_color(), DEFAULT_FOREGROUND),
+                _ => terminal_rgb(pixel.background_color(), DEFAULT_BACKGROUND),
+            })
+            .collect();
+        let mut column = 0;
+        while column < fills.len() {
+            let run = fills[column..]
+                .iter()
+                .take_while(|fill| **fill == fills[column])
+                .count();
+            if fills[column] != DEFAULT_BACKGROUND {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    column * options.cell_width,
+                    row * options.cell_height,
+                    run * options.cell_width,
+                    options.cell_height,
+                    hex(fills[column])
+                );
+            }
+            column += run;
+        }
+        if !options.draw_glyphs {
+            continue;
+        }
+        for (column, pixel) in pixels.iter().enumerate() {
+            if pixel.char().is_whitespace() || pixel.char() == '█' {
+                continue;
+            }
+            let _ = writeln!(
+                text,
+                r#"<text x="{}" y="{}" fill="{}">{}</text>"#,
+                column * options.cell_width,
+                (row + 1) * options.cell_height,
+                hex(terminal_rgb(pixel.foreground_color(), DEFAULT_FOREGROUND)),
+                escape(pixel.char())
+            );
+        }
+    }
+    if !text.is_empty() {
+        let _ = writeln!(
+            svg,
+            r#"<g font-family="monospace" font-size="{}">"#,
+            options.cell_height
+        );
+        svg.push_str(&text);
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Draw a tic-tac-toe board as an SVG with a grid and the marks as lines and circles
+///
+/// # Arguments
+///
+/// * `board` - the board to draw
+/// * `cell_size` - the width and height of a cell
+pub fn board_svg(board: &Board, cell_size: usize) -> String {
+    let size = 3 * cell_size;
+    let mut svg = header(size, size, [255, 255, 255]);
+    let stroke = (cell_size / 12).max(1);
+    let _ = writeln!(
+        svg,
+        r#"<g stroke="{}" stroke-width="{}" stroke-linecap="round" fill="none">"#,
+        hex(GRID_COLOR),
+        stroke
+    );
+    for line in 1..3 {
+        let at = line * cell_size;
+        let _ = writeln!(svg, r#"<line x1="{at}" y1="0" x2="{at}" y2="{size}"/>"#);
+        let _ = writeln!(svg, r#"<line x1="0" y1="{at}" x2="{size}" y2="{at}"/>"#);
+    }
+    let margin = cell_size / 5;
+    for index in 0..9 {
+        let (left, top) = ((index % 3) * cell_size, (index / 3) * cell_size);
+        match board.cell(index) {
+            Some(Mark::X) => {
+                let (near, far) = (margin, cell_size - margin);
+                let color = hex(X_COLOR);
+                let _ = writeln!(
+                    svg,
+                    r#"<path d="M{} {}L{} {}M{} {}L{} {}" stroke="{}"/>"#,
+                    left + near,
+                    top + near,
+                    left + far,
+                    top + far,
+                    left + far,
+                    top + near,
+                    left + near,
+                    top + far,
+                    color
+                );
+            }
+            Some(Mark::O) => {
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{}" cy="{}" r="{}" stroke="{}"/>"#,
+                    left + cell_size / 2,
+                    top + cell_size / 2,
+                    cell_size / 2 - margin,
+                    hex(O_COLOR)
+                );
+            }
+            None => (),
+        }
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// Start an SVG document with a background that covers the whole image
+fn header(width: usize, height: usize, background: Rgb) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        hex(background)
+    )
+}
+
+/// Write a color the way SVG and CSS expect it
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+/// Escape the characters that have a meaning in XML
+fn escape(ch: char) -> String {
+    match ch {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        '"' => "&quot;".to_string(),
+        _ => ch.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        export::image::ImageOptions,
+        rendering::colors::TerminalColors,
+        shared::frame::Pixel,
+        tic_tac_toe::board::{Board, Mark},
+    };
+
+    use super::{board_svg, escape, frame_svg, hex};
+
+    #[test]
+    fn frame() {
+        let red = Pixel::new(' ', TerminalColors::Red, TerminalColors::White);
+        let frame = vec![
+            vec![red.clone(), red.clone(), Pixel::default(), red],
+            vec![Pixel::new(
+                '<',
+                TerminalColors::Default,
+                TerminalColors::Default,
+            )],
+        ];
+        let options = ImageOptions {
+            cell_width: 5,
+            cell_height: 10,
+            draw_glyphs: true,
+        };
+        let svg = frame_svg(&frame, &options);
+        let test_cases = [
+            ("size", r#"width="20" height="20""#, 1),
+            (
+                "merged run",
+                r##"<rect x="0" y="0" width="10" height="10" fill="#d70000"/>"##,
+                1,
+            ),
+            (
+                "single cell",
+                r##"<rect x="15" y="0" width="5" height="10" fill="#d70000"/>"##,
+                1,
+            ),
+            (
+                "text",
+                r##"<text x="0" y="20" fill="#e5e5e5">&lt;</text>"##,
+                1,
+            ),
+            ("default background is not drawn", "<rect ", 3),
+        ];
+        for (test_case, expected, count) in test_cases {
+            assert_eq!(
+                svg.matches(expected).count(),
+                count,
+                "{}\n{}",
+                test_case,
+                svg
+            );
+        }
+        assert!(svg.ends_with("</svg>\n"));
+
+        let without_glyphs = frame_svg(
+            &frame,
+            &ImageOptions {
+                draw_glyphs: false,
+                ..options
+            },
+        );
+        assert!(!without_glyphs.contains("<text"), "{}", without_glyphs);
+    }
+
+    #[test]
+    fn board() {
+        let mut board = Board::default();
+        board.play(Mark::X, 4).unwrap();
+        board.play(Mark::O, 0).unwrap();
+        let svg = board_svg(&board, 60);
+        let test_cases = [
+            ("size", r#"width="180" height="180""#, 1),
+            ("grid", "<line ", 4),
+            (
+                "x",
+                r##"<path d="M72 72L108 108M108 72L72 108" stroke="#cd0000"/>"##,
+                1,
+            ),
+            (
+                "o",
+                r##"<circle cx="30" cy="30" r="18" stroke="#5c5cff"/>"##,
+                1,
+            ),
+        ];
+        for (test_case, expected, count) in test_cases {
+            assert_eq!(
+                svg.matches(expected).count(),
+                count,
+                "{}\n{}",
+                test_case,
+                svg
+            );
+        }
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(hex([255, 8, 171]), "#ff08ab");
+        let test_cases = [
+            ("plain", 'a', "a"),
+            ("tag", '<', "&lt;"),
+            ("amp", '&', "&amp;"),
+        ];
+        for (test_case, ch, expected) in test_cases {
+            assert_eq!(escape(ch), expected, "{}", test_case);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    fmt::Debug,
+    io::{self, Write},
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, SetCursorStyle, Show},
+    style::{Color, ResetColor, SetBackgroundColor, SetForegroundColor},
+    terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, SetTitle},
+    Command, QueueableCommand,
+};
+
+use crate::{rendering::colors::TerminalColors, shared::usize2d::Usize2d};
+
+use super::{
+    cursor_style::{CursorShape, CursorStyle},
+    handle::Handle,
+    handle_error::HandleError,
+};
+
+/// A handle that draws to the terminal through crossterm, with the `crossterm-backend` feature
+///
+/// The commands are queued and written at once on every flush, like the frames of
+/// `StdIOHandle`. It is a second implementation of the terminal output to compare `StdIOHandle`
+/// against, the two draw the same screen for the same calls
+pub struct CrosstermHandle {
+    handle: Box<dyn Write + Send>,
+}
+
+impl Default for CrosstermHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrosstermHandle {
+    pub fn new() -> Self {
+        CrosstermHandle::with_writer(Box::new(io::stdout()))
+    }
+    /// Create a handle that queues its commands on another writer
+    pub fn with_writer(writer: Box<dyn Write + Send>) -> Self {
+        CrosstermHandle { handle: writer }
+    }
+    fn queue(&mut self, command: impl Command, error: HandleError) -> Result<(), HandleError> {
+        self.handle.queue(command).map(|_| ()).map_err(|_| error)
+    }
+}
+
+/// Get the crossterm color of a terminal color, the default color resets to the color of the
+/// terminal
+fn color(color: TerminalColors) -> Color {
+    match color {
+        TerminalColors::Default => Color::Reset,
+        color => Color::AnsiValue(color as u8),
+    }
+}
+
+impl Debug for CrosstermHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[CrosstermHandle]")
+    }
+}
+impl Write for CrosstermHandle {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.handle.write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.handle.flush()
+    }
+}
+
+impl Handle for CrosstermHandle {
+    fn set_cursor_location(&mut self, coordinate: Usize2d) -> Result<(), HandleError> {
+        // Crossterm counts from 0 where the escape codes count from 1, and a 0 in an escape code
+        // is taken as 1
+        let x = coordinate.x.saturating_sub(1).min(u16::MAX as usize) as u16;
+        let y = coordinate.y.saturating_sub(1).min(u16::MAX as usize) as u16;
+        self.queue(MoveTo(x, y), HandleError::SetCursorLocationFailed)
+    }
+    fn set_foreground_color(&mut self, color: TerminalColors) -> Result<(), HandleError> {
+        self.queue(
+            SetForegroundColor(self::color(color)),
+            HandleError::SetForegroundFailed,
+        )
+    }
+    fn set_background_color(&mut self, color: TerminalColors) -> Result<(), HandleError> {
+        self.queue(
+            SetBackgroundColor(self::color(color)),
+            HandleError::SetBackgroundFailed,
+        )
+    }
+    fn write_to_location(
+        &mut self,
+        buf: &[u8],
+        coord: crate::shared::usize2d::Coord,
+    ) -> Result<usize, HandleError> {
+        self.set_cursor_location(coord)?;
+        self.write(buf).map_err(|_| HandleError::WriteFailed)
+    }
+    fn clear_screen(&mut self) -> Result<(), HandleError> {
+        self.reset_colors()?;
+        self.queue(Clear(ClearType::All), HandleError::WriteFailed)
+    }
+    fn set_cursor_visible(&mut self, is_visible: bool) -> Result<(), HandleError> {
+        match is_visible {
+            true => self.queue(Show, HandleError::WriteFailed),
+            false => self.queue(Hide, HandleError::WriteFailed),
+        }
+    }
+    fn set_cursor_style(&mut self, style: CursorStyle) -> Result<(), HandleError> {
+        let style = match (style.shape, style.is_blinking) {
+            (CursorShape::Default, _) => SetCursorStyle::DefaultUserShape,
+            (CursorShape::Block, true) => SetCursorStyle::BlinkingBlock,
+            (CursorShape::Block, false) => SetCursorStyle::SteadyBlock,
+            (CursorShape::Underline, true) => SetCursorStyle::BlinkingUnderScore,
+            (CursorShape::Underline, false) => SetCursorStyle::SteadyUnderScore,
+            (CursorShape::Bar, true) => SetCursorStyle::BlinkingBar,
+            (CursorShape::Bar, false) => SetCursorStyle::SteadyBar,
+        };
+        self.queue(style, HandleError::WriteFailed)
+    }
+    fn set_alternate_screen(&mut self, is_active: bool) -> Result<(), HandleError> {
+        match is_active {
+            true => self.queue(EnterAlternateScreen, HandleError::WriteFailed),
+            false => self.queue(LeaveAlternateScreen, HandleError::WriteFailed),
+        }
+    }
+    fn set_title(&mut self, title: &str) -> Result<(), HandleError> {
+        // Control characters would end the sequence early
+        let title: String = title.chars().filter(|c| !c.is_control()).collect();
+        self.queue(SetTitle(title), HandleError::WriteFailed)
+    }
+    fn bell(&mut self) -> Result<(), HandleError> {
+        // Crossterm has no command for the bell
+        self.handle
+            .write_all(b"\x07")
+            .map_err(|_| HandleError::WriteFailed)
+    }
+    fn reset_colors(&mut self) -> Result<(), HandleError> {
+        self.queue(ResetColor, HandleError::WriteFailed)
+    }
+}
@@ -0,0 +1,77 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CursorShape {
+    /// The shape the user configured for their terminal
+    Default,
+    Block,
+    Underline,
+    Bar,
+}
+
+/// The shape of the cursor and if it blinks, e.g. a bar while editing cells
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{handler::{cursor_style::{CursorShape, CursorStyle}, handle_error::HandleError}, Handle, MemoryHandle};
+/// # fn main() -> Result<(), HandleError> {
+/// # let mut handle = MemoryHandle::new();
+/// handle.set_cursor_style(CursorStyle::new(CursorShape::Bar, true))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CursorStyle {
+    pub shape: CursorShape,
+    pub is_blinking: bool,
+}
+impl Default for CursorStyle {
+    fn default() -> Self {
+        CursorStyle {
+            shape: CursorShape::Default,
+            is_blinking: true,
+        }
+    }
+}
+impl CursorStyle {
+    pub fn new(shape: CursorShape, is_blinking: bool) -> Self {
+        CursorStyle { shape, is_blinking }
+    }
+    /// Get the parameter of the DECSCUSR escape code (`CSI n SP q`) for the style
+    pub fn code(&self) -> u8 {
+        let steady = if self.is_blinking { 0 } else { 1 };
+        match self.shape {
+            CursorShape::Default => 0,
+            CursorShape::Block => 1 + steady,
+            CursorShape::Underline => 3 + steady,
+            CursorShape::Bar => 5 + steady,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CursorShape, CursorStyle};
+
+    #[test]
+    fn code() {
+        let test_cases = vec![
+            (1, CursorStyle::default(), 0),
+            (2, CursorStyle::new(CursorShape::Default, false), 0),
+            (3, CursorStyle::new(CursorShape::Block, true), 1),
+            (4, CursorStyle::new(CursorShape::Block, false), 2),
+            (5, CursorStyle::new(CursorShape::Underline, true), 3),
+            (6, CursorStyle::new(CursorShape::Underline, false), 4),
+            (7, CursorStyle::new(CursorShape::Bar, true), 5),
+            (8, CursorStyle::new(CursorShape::Bar, false), 6),
+        ];
+        for (test_case, style, expected) in test_cases {
+            assert_eq!(
+                style.code(),
+                expected,
+                "Test case {}: The code for {:?} is wrong",
+                test_case,
+                style
+            );
+        }
+    }
+}
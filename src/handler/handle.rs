@@ -1,11 +1,11 @@
-use std::{fmt::Debug, io::Write, usize};
+use std::{fmt::Debug, io::Write};
 
 use crate::{
     rendering::colors::TerminalColors,
     shared::usize2d::{Coord, Usize2d},
 };
 
-use super::handle_error::HandleError;
+use super::{cursor_style::CursorStyle, handle_error::HandleError};
 
 pub trait Handle: Write + Send + Debug {
     /// Set the locaction of the cursor on the buffer
@@ -21,7 +21,12 @@ pub trait Handle: Write + Send + Debug {
     /// # Example
     ///
     /// ```
-    /// my_handle.set_cursor_location(Usize2d::default());
+    /// # use tic_tac_toe2::{handler::handle_error::HandleError, shared::usize2d::{Coord, Usize2d}, Handle, MemoryHandle, TerminalColors};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.set_cursor_location(Usize2d::default())?;
+    /// # Ok(())
+    /// # }
     /// ```
     fn set_cursor_location(&mut self, coord: Usize2d) -> Result<(), HandleError>;
     /// Set the foreground color at the current coordinate on the buffer for this handle
@@ -37,7 +42,12 @@ pub trait Handle: Write + Send + Debug {
     /// # Example
     ///
     /// ```
-    /// my_handle.set_foreground_color(TerminalColors::Red);
+    /// # use tic_tac_toe2::{handler::handle_error::HandleError, shared::usize2d::{Coord, Usize2d}, Handle, MemoryHandle, TerminalColors};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.set_foreground_color(TerminalColors::Red)?;
+    /// # Ok(())
+    /// # }
     /// ```
     fn set_foreground_color(&mut self, color: TerminalColors) -> Result<(), HandleError>;
     /// Set the background color at the current coordinate on the buffer for this handle
@@ -53,7 +63,12 @@ pub trait Handle: Write + Send + Debug {
     /// # Example
     ///
     /// ```
-    /// my_handle.set_background_color(TerminalColors::Blue);
+    /// # use tic_tac_toe2::{handler::handle_error::HandleError, shared::usize2d::{Coord, Usize2d}, Handle, MemoryHandle, TerminalColors};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.set_background_color(TerminalColors::Blue)?;
+    /// # Ok(())
+    /// # }
     /// ```
     fn set_background_color(&mut self, color: TerminalColors) -> Result<(), HandleError>;
     /// Write to a specific location
@@ -70,7 +85,147 @@ pub trait Handle: Write + Send + Debug {
     /// # Example
     ///
     /// ```
-    /// my_handle.write_to_location(b"test", Coord::new(10,10));
+    /// # use tic_tac_toe2::{handler::handle_error::HandleError, shared::usize2d::{Coord, Usize2d}, Handle, MemoryHandle, TerminalColors};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.write_to_location(b"test", Coord::new(10,10))?;
+    /// # Ok(())
+    /// # }
     /// ```
     fn write_to_location(&mut self, buf: &[u8], coord: Coord) -> Result<usize, HandleError>;
+    /// Clear everything that was written to the buffer
+    ///
+    /// Handles that do not draw to a terminal can ignore this
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{handler::handle_error::HandleError, shared::usize2d::{Coord, Usize2d}, Handle, MemoryHandle, TerminalColors};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.clear_screen()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn clear_screen(&mut self) -> Result<(), HandleError> {
+        Ok(())
+    }
+    /// Show or hide the cursor
+    ///
+    /// Handles that do not draw to a terminal can ignore this
+    ///
+    /// # Arguments
+    ///
+    /// * `is_visible` - `true` to show the cursor
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{handler::handle_error::HandleError, shared::usize2d::{Coord, Usize2d}, Handle, MemoryHandle, TerminalColors};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.set_cursor_visible(false)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn set_cursor_visible(&mut self, _is_visible: bool) -> Result<(), HandleError> {
+        Ok(())
+    }
+    /// Set the shape of the cursor and if it blinks
+    ///
+    /// Handles that do not draw to a terminal can ignore this
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - the new style, `CursorStyle::default()` restores the style of the terminal
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{handler::{cursor_style::{CursorShape, CursorStyle}, handle_error::HandleError}, Handle, MemoryHandle};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.set_cursor_style(CursorStyle::new(CursorShape::Underline, false))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn set_cursor_style(&mut self, _style: CursorStyle) -> Result<(), HandleError> {
+        Ok(())
+    }
+    /// Switch to or back from the alternate screen buffer. Whatever was on the main screen,
+    /// including the scrollback, is shown again once the alternate screen is left
+    ///
+    /// Handles that do not draw to a terminal can ignore this
+    ///
+    /// # Arguments
+    ///
+    /// * `is_active` - `true` to switch to the alternate screen
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{handler::handle_error::HandleError, shared::usize2d::{Coord, Usize2d}, Handle, MemoryHandle, TerminalColors};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.set_alternate_screen(true)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn set_alternate_screen(&mut self, _is_active: bool) -> Result<(), HandleError> {
+        Ok(())
+    }
+    /// Set the title of the terminal window or tab
+    ///
+    /// Handles that do not draw to a terminal can ignore this
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - the new title
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{handler::handle_error::HandleError, shared::usize2d::{Coord, Usize2d}, Handle, MemoryHandle, TerminalColors};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.set_title("Conway - round 12")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn set_title(&mut self, _title: &str) -> Result<(), HandleError> {
+        Ok(())
+    }
+    /// Ring the terminal bell, e.g. to signal that a game is over
+    ///
+    /// Handles that do not draw to a terminal can ignore this
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{handler::handle_error::HandleError, shared::usize2d::{Coord, Usize2d}, Handle, MemoryHandle, TerminalColors};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.bell()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn bell(&mut self) -> Result<(), HandleError> {
+        Ok(())
+    }
+    /// Reset the colors and styles back to the terminal defaults
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{handler::handle_error::HandleError, shared::usize2d::{Coord, Usize2d}, Handle, MemoryHandle, TerminalColors};
+    /// # fn main() -> Result<(), HandleError> {
+    /// # let mut my_handle = MemoryHandle::new();
+    /// my_handle.reset_colors()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn reset_colors(&mut self) -> Result<(), HandleError> {
+        self.set_background_color(TerminalColors::Default)?;
+        self.set_foreground_color(TerminalColors::Default)
+    }
 }
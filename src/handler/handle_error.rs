@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum HandleError {
     WriteFailed,
     SetCursorLocationFailed,
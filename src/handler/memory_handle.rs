@@ -1,7 +1,6 @@
 use std::{
     fmt::Debug,
     io::{self, Write},
-    usize,
 };
 
 use crate::{
@@ -10,7 +9,12 @@ use crate::{
     utils::vec_t_writer::{write_t_to_vec, write_vec_to_vec},
 };
 
-use super::{handle::Handle, handle_error::HandleError};
+use super::{
+    cursor_style::CursorStyle,
+    handle::Handle,
+    handle_error::HandleError,
+    terminal_state::{Escape, TerminalState},
+};
 
 /// The behavior of `MemoryHandle` shoudld be as similar to the `StdOut` behaviour as possible.
 /// It is used for unit/simulation testing
@@ -24,6 +28,27 @@ pub struct MemoryHandle {
     current_cursor_location: Usize2d,
     current_background_color: TerminalColors,
     current_foreground_color: TerminalColors,
+    main_screen: Option<SavedScreen>,
+    pub title: Option<String>,
+    pub bell_count: usize,
+    pub cursor_style: CursorStyle,
+    /// Leaves out the escape sequences `StdIOHandle` would leave out
+    state: TerminalState,
+    /// The escape sequences `StdIOHandle` would write, once `record_escapes` was called
+    escapes: Option<Vec<Escape>>,
+}
+
+/// The content of the main screen while the alternate screen is active
+struct SavedScreen {
+    buffer: Vec<Vec<u8>>,
+    foreground_color_buffer: Vec<Vec<TerminalColors>>,
+    background_color_buffer: Vec<Vec<TerminalColors>>,
+}
+
+impl Default for MemoryHandle {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MemoryHandle {
@@ -38,15 +63,21 @@ impl MemoryHandle {
             current_cursor_location: Usize2d::default(),
             current_background_color: TerminalColors::default(),
             current_foreground_color: TerminalColors::default(),
+            main_screen: None,
+            title: None,
+            bell_count: 0,
+            cursor_style: CursorStyle::default(),
+            state: TerminalState::default(),
+            escapes: None,
         }
     }
 }
 impl MemoryHandle {
     pub fn get_buffer_content(&self) -> Vec<u8> {
-        if self.buffer.len() == 0 {
+        if self.buffer.is_empty() {
             return Vec::new();
         }
-        assert!(self.buffer.len() > 0);
+        assert!(!self.buffer.is_empty());
         let mut result: Vec<u8> = Vec::with_capacity(self.buffer_temp.len() * 2 - 1);
 
         for index in 0..self.buffer_temp.len() {
@@ -67,6 +98,25 @@ impl MemoryHandle {
     pub fn need_to_flush(self) -> bool {
         self.buffer_temp != self.buffer
     }
+    /// Start recording the escape sequences a terminal handle would write, leaving out the ones
+    /// that would not change anything
+    pub fn record_escapes(&mut self) {
+        self.escapes = Some(Vec::new());
+    }
+    /// Take the escape sequences recorded since the last take
+    pub fn take_escapes(&mut self) -> Vec<Escape> {
+        self.escapes
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+    fn record(&mut self, escape: Escape) {
+        if self.state.needs(escape) {
+            if let Some(escapes) = &mut self.escapes {
+                escapes.push(escape);
+            }
+        }
+    }
 }
 
 impl Debug for MemoryHandle {
@@ -85,6 +135,7 @@ impl Write for MemoryHandle {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         let vec_to_push = buf.to_vec();
         let len_to_push = vec_to_push.len();
+        self.state.advance(buf);
 
         let required_len = self.current_cursor_location.y + 1;
         if self.buffer_temp.len() < required_len {
@@ -104,22 +155,24 @@ impl Write for MemoryHandle {
         self.background_color_buffer_temp[self.current_cursor_location.y] = write_t_to_vec(
             self.background_color_buffer_temp[self.current_cursor_location.y].clone(),
             self.current_background_color,
-            len_to_push,
             self.current_cursor_location.x,
+            len_to_push,
             TerminalColors::default(),
         );
 
         self.foreground_color_buffer_temp[self.current_cursor_location.y] = write_t_to_vec(
             self.foreground_color_buffer_temp[self.current_cursor_location.y].clone(),
             self.current_foreground_color,
-            len_to_push,
             self.current_cursor_location.x,
+            len_to_push,
             TerminalColors::default(),
         );
+        self.current_cursor_location.x += len_to_push;
 
         Ok(buf.len())
     }
     fn flush(&mut self) -> io::Result<()> {
+        self.state.forget();
         self.buffer = self.buffer_temp.clone();
         self.foreground_color_buffer = self.foreground_color_buffer_temp.clone();
         self.background_color_buffer = self.background_color_buffer_temp.clone();
@@ -140,21 +193,60 @@ impl Write for MemoryHandle {
 
 impl Handle for MemoryHandle {
     fn set_cursor_location(&mut self, coordinate: Usize2d) -> Result<(), HandleError> {
+        self.record(Escape::CursorLocation(coordinate.x, coordinate.y));
         self.current_cursor_location = coordinate;
         Ok(())
     }
     fn set_foreground_color(&mut self, color: TerminalColors) -> Result<(), HandleError> {
+        self.record(Escape::Foreground(color));
         self.current_foreground_color = color;
         Ok(())
     }
     fn set_background_color(&mut self, color: TerminalColors) -> Result<(), HandleError> {
+        self.record(Escape::Background(color));
         self.current_background_color = color;
         Ok(())
     }
     fn write_to_location(&mut self, buf: &[u8], coord: Coord) -> Result<usize, HandleError> {
-        let _ = self.set_cursor_location(coord)?;
+        self.set_cursor_location(coord)?;
         self.write(buf).map_err(|_| HandleError::WriteFailed)
     }
+    fn clear_screen(&mut self) -> Result<(), HandleError> {
+        self.state.forget_colors();
+        self.buffer_temp.clear();
+        self.foreground_color_buffer_temp.clear();
+        self.background_color_buffer_temp.clear();
+        Ok(())
+    }
+    fn set_cursor_style(&mut self, style: CursorStyle) -> Result<(), HandleError> {
+        self.cursor_style = style;
+        Ok(())
+    }
+    fn set_title(&mut self, title: &str) -> Result<(), HandleError> {
+        self.title = Some(title.to_string());
+        Ok(())
+    }
+    fn bell(&mut self) -> Result<(), HandleError> {
+        self.bell_count += 1;
+        Ok(())
+    }
+    fn set_alternate_screen(&mut self, is_active: bool) -> Result<(), HandleError> {
+        self.state.forget();
+        if is_active && self.main_screen.is_none() {
+            self.main_screen = Some(SavedScreen {
+                buffer: std::mem::take(&mut self.buffer_temp),
+                foreground_color_buffer: std::mem::take(&mut self.foreground_color_buffer_temp),
+                background_color_buffer: std::mem::take(&mut self.background_color_buffer_temp),
+            });
+        } else if !is_active {
+            if let Some(saved) = self.main_screen.take() {
+                self.buffer_temp = saved.buffer;
+                self.foreground_color_buffer_temp = saved.foreground_color_buffer;
+                self.background_color_buffer_temp = saved.background_color_buffer;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -163,7 +255,7 @@ mod tests {
 
     use crate::rendering::colors::TerminalColors as TC;
     use crate::{
-        handler::{handle::Handle, memory_handle::MemoryHandle},
+        handler::{handle::Handle, memory_handle::MemoryHandle, terminal_state::Escape},
         shared::usize2d::Usize2d,
         vec_vec_enum_to_string,
     };
@@ -291,12 +383,6 @@ mod tests {
             vec![], //
             vec![], //
             vec![
-                TC::Default,
-                TC::Default,
-                TC::Default,
-                TC::Default,
-                TC::Default,
-                TC::Default,
                 TC::Default,
                 TC::Default,
                 TC::Default,
@@ -307,6 +393,12 @@ mod tests {
                 TC::Black,
                 TC::Black,
                 TC::Black,
+                TC::Black,
+                TC::Black,
+                TC::Black,
+                TC::Black,
+                TC::Black,
+                TC::Black,
             ], //
         ];
 
@@ -327,12 +419,12 @@ mod tests {
                 TC::Default,
                 TC::Default,
                 TC::Default,
-                TC::Default,
-                TC::Default,
-                TC::Default,
-                TC::Default,
-                TC::Default,
-                TC::Default,
+                TC::Red,
+                TC::Red,
+                TC::Red,
+                TC::Red,
+                TC::Red,
+                TC::Red,
                 TC::Red,
                 TC::Red,
                 TC::Red,
@@ -349,4 +441,117 @@ mod tests {
             vec_vec_enum_to_string!(expected)
         );
     }
+    #[test]
+    fn consecutive_writes() {
+        let mut handle = MemoryHandle::new();
+        let _ = handle.set_cursor_location(Usize2d::new(2, 0));
+        let _ = handle.set_background_color(TC::Red);
+        let _ = handle.write(b"ab");
+        let _ = handle.set_background_color(TC::LightGreen);
+        let _ = handle.write(b"cd");
+        let _ = handle.flush();
+
+        assert_eq!(
+            String::from_utf8_lossy(&handle.get_buffer_content()),
+            "  abcd",
+            "A write should continue where the previous one stopped, like a terminal"
+        );
+        assert_eq!(
+            handle.get_background_colors(),
+            vec![vec![
+                TC::Default,
+                TC::Default,
+                TC::Red,
+                TC::Red,
+                TC::LightGreen,
+                TC::LightGreen
+            ]],
+            "The colors should be written under the characters they were set for"
+        );
+    }
+    #[test]
+    fn clear_screen() {
+        let mut handle = MemoryHandle::new();
+        let _ = handle.write_to_location(b"Hello world", Usize2d::new(2, 1));
+        let _ = handle.flush();
+        assert!(!handle.get_buffer_content().is_empty());
+
+        let result = handle.clear_screen();
+        assert!(result.is_ok(), "Clear should not fail");
+        let _ = handle.write_to_location(b"Hi", Usize2d::new(0, 0));
+        let _ = handle.flush();
+        assert_eq!(
+            String::from_utf8_lossy(&handle.get_buffer_content()),
+            "Hi",
+            "Clearing the screen should remove the previous content"
+        );
+    }
+    #[test]
+    fn alternate_screen() {
+        let mut handle = MemoryHandle::new();
+        let _ = handle.write_to_location(b"scrollback", Usize2d::new(0, 0));
+        let _ = handle.flush();
+
+        let result = handle.set_alternate_screen(true);
+        assert!(
+            result.is_ok(),
+            "Entering the alternate screen should not fail"
+        );
+        let _ = handle.write_to_location(b"game", Usize2d::new(1, 1));
+        let _ = handle.flush();
+        assert_eq!(
+            String::from_utf8_lossy(&handle.get_buffer_content()),
+            "\n game",
+            "The alternate screen should start empty"
+        );
+
+        let result = handle.set_alternate_screen(false);
+        assert!(
+            result.is_ok(),
+            "Leaving the alternate screen should not fail"
+        );
+        let _ = handle.flush();
+        assert_eq!(
+            String::from_utf8_lossy(&handle.get_buffer_content()),
+            "scrollback",
+            "The main screen should be restored"
+        );
+    }
+    #[test]
+    fn escape_stream() {
+        let mut handle = MemoryHandle::new();
+        handle.record_escapes();
+        // A row of cells, drawn the way the renderers draw them
+        let draw_row = |handle: &mut MemoryHandle, y| {
+            for x in 0..3 {
+                let color = if x == 2 { TC::Red } else { TC::Black };
+                let _ = handle.set_background_color(color);
+                let _ = handle.set_foreground_color(TC::White);
+                let _ = handle.write_to_location(b"  ", Usize2d::new(x * 2, y));
+            }
+        };
+        draw_row(&mut handle, 1);
+        draw_row(&mut handle, 2);
+        assert_eq!(
+            handle.take_escapes(),
+            vec![
+                Escape::Background(TC::Black),
+                Escape::Foreground(TC::White),
+                Escape::CursorLocation(0, 1),
+                Escape::Background(TC::Red),
+                Escape::Background(TC::Black),
+                Escape::CursorLocation(0, 2),
+                Escape::Background(TC::Red),
+            ],
+            "Only the escapes that change the colors or move the cursor are written"
+        );
+
+        let _ = handle.flush();
+        let _ = handle.set_foreground_color(TC::White);
+        assert_eq!(
+            handle.take_escapes(),
+            vec![Escape::Foreground(TC::White)],
+            "Every frame starts without knowing the state of the terminal"
+        );
+    }
 }
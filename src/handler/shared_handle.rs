@@ -5,7 +5,10 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use super::{handle::Handle, handle_error::HandleError, std_io_handle::StdIOHandle};
+use super::{
+    cursor_style::CursorStyle, handle::Handle, handle_error::HandleError,
+    std_io_handle::StdIOHandle,
+};
 
 #[derive(Debug)]
 pub struct SharedHandle {
@@ -107,9 +110,58 @@ impl Handle for SharedHandle {
         coord: crate::shared::usize2d::Coord,
     ) -> Result<usize, HandleError> {
         //TODO: What even
-        let _ = self.set_cursor_location(coord)?;
+        self.set_cursor_location(coord)?;
         self.write(buf).map_err(|_| HandleError::WriteFailed)
     }
+    fn clear_screen(&mut self) -> Result<(), HandleError> {
+        let mut locked_writer = self
+            .handle
+            .lock()
+            .map_err(|_| SharedWriterErr::FailedToLock)?;
+        locked_writer.clear_screen()
+    }
+    fn set_cursor_visible(&mut self, is_visible: bool) -> Result<(), HandleError> {
+        let mut locked_writer = self
+            .handle
+            .lock()
+            .map_err(|_| SharedWriterErr::FailedToLock)?;
+        locked_writer.set_cursor_visible(is_visible)
+    }
+    fn set_cursor_style(&mut self, style: CursorStyle) -> Result<(), HandleError> {
+        let mut locked_writer = self
+            .handle
+            .lock()
+            .map_err(|_| SharedWriterErr::FailedToLock)?;
+        locked_writer.set_cursor_style(style)
+    }
+    fn set_alternate_screen(&mut self, is_active: bool) -> Result<(), HandleError> {
+        let mut locked_writer = self
+            .handle
+            .lock()
+            .map_err(|_| SharedWriterErr::FailedToLock)?;
+        locked_writer.set_alternate_screen(is_active)
+    }
+    fn set_title(&mut self, title: &str) -> Result<(), HandleError> {
+        let mut locked_writer = self
+            .handle
+            .lock()
+            .map_err(|_| SharedWriterErr::FailedToLock)?;
+        locked_writer.set_title(title)
+    }
+    fn bell(&mut self) -> Result<(), HandleError> {
+        let mut locked_writer = self
+            .handle
+            .lock()
+            .map_err(|_| SharedWriterErr::FailedToLock)?;
+        locked_writer.bell()
+    }
+    fn reset_colors(&mut self) -> Result<(), HandleError> {
+        let mut locked_writer = self
+            .handle
+            .lock()
+            .map_err(|_| SharedWriterErr::FailedToLock)?;
+        locked_writer.reset_colors()
+    }
 }
 
 #[derive(Debug)]
@@ -119,7 +171,7 @@ pub enum SharedWriterErr {
 impl Error for SharedWriterErr {}
 impl From<SharedWriterErr> for io::Error {
     fn from(err: SharedWriterErr) -> io::Error {
-        io::Error::new(io::ErrorKind::Other, err)
+        io::Error::other(err)
     }
 }
 impl From<SharedWriterErr> for HandleError {
@@ -150,9 +202,9 @@ mod tests {
         let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
         let writer = SharedHandle::init(buffer.clone());
         let test_str = "Hello world";
-        let _ = match writer.write(format_args!("{}", test_str)) {
+        match writer.write(format_args!("{}", test_str)) {
             Ok(_) => (),
-            Err(_) => assert!(false, "This should never happen"),
+            Err(_) => panic!("This should never happen"),
         };
         let result = writer.flush();
         assert!(result.is_ok());
@@ -1,22 +1,104 @@
-use std::{
-    fmt::Debug,
-    io::{Stdout, Write},
-};
+use std::{env, fmt::Debug, io::Write};
 
 use crate::{rendering::colors::TerminalColors, shared::usize2d::Usize2d};
 
-use super::{handle::Handle, handle_error::HandleError};
+use super::{
+    cursor_style::CursorStyle,
+    handle::Handle,
+    handle_error::HandleError,
+    terminal_state::{Escape, TerminalState},
+};
+
+const BEGIN_SYNCHRONIZED_UPDATE: &[u8] = b"\x1b[?2026h";
+const END_SYNCHRONIZED_UPDATE: &[u8] = b"\x1b[?2026l";
 
+/// A handle that writes ANSI escape codes to standard out, or any other writer
+///
+/// On terminals that support synchronized output (DEC mode 2026) everything written between two
+/// flushes is wrapped in a synchronized update, so the terminal shows each frame at once instead
+/// of drawing a half finished redraw
+///
+/// Everything written between two flushes is collected and written to the writer at once. Within
+/// a frame the color and cursor sequences that would not change anything are left out, the state
+/// of the terminal is forgotten on every flush since other handles may write to it in between
 pub struct StdIOHandle {
-    handle: Stdout,
+    handle: Box<dyn Write + Send>,
+    /// The output of the current frame
+    frame: Vec<u8>,
+    state: TerminalState,
+    is_synchronized: bool,
+    is_update_open: bool,
+}
+
+impl Default for StdIOHandle {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl StdIOHandle {
     pub fn new() -> Self {
+        StdIOHandle::with_writer(
+            Box::new(std::io::stdout()),
+            supports_synchronized_output(|name| env::var(name).ok()),
+        )
+    }
+    /// Create a handle that writes its escape codes to another writer
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - where the output is written to
+    /// * `is_synchronized` - if frames should be wrapped in synchronized updates
+    pub fn with_writer(writer: Box<dyn Write + Send>, is_synchronized: bool) -> Self {
         StdIOHandle {
-            handle: std::io::stdout(),
+            handle: writer,
+            frame: Vec::new(),
+            state: TerminalState::default(),
+            is_synchronized,
+            is_update_open: false,
         }
     }
+    /// Get the writer for the current frame, opening a synchronized update on the first write
+    /// after a flush
+    fn frame_writer(&mut self) -> &mut Vec<u8> {
+        if self.is_synchronized && !self.is_update_open {
+            self.frame.extend_from_slice(BEGIN_SYNCHRONIZED_UPDATE);
+            self.is_update_open = true;
+        }
+        &mut self.frame
+    }
+    /// Get the number of escape sequences that were left out because they changed nothing
+    pub fn skipped_escapes(&self) -> u64 {
+        self.state.skipped()
+    }
+}
+
+/// Detect if the terminal supports synchronized output from its environment variables
+///
+/// Querying the terminal would mean reading its reply from the input, which is shared with the
+/// games, so the terminals that are known to support it are recognized by name instead
+///
+/// # Arguments
+///
+/// * `var` - looks up an environment variable
+pub fn supports_synchronized_output(var: impl Fn(&str) -> Option<String>) -> bool {
+    if var("WT_SESSION").is_some() {
+        return true;
+    }
+    let term_program = var("TERM_PROGRAM").unwrap_or_default();
+    let term = var("TERM").unwrap_or_default();
+    [
+        "WezTerm",
+        "iTerm.app",
+        "vscode",
+        "ghostty",
+        "contour",
+        "rio",
+    ]
+    .contains(&term_program.as_str())
+        || ["kitty", "alacritty", "foot", "contour"]
+            .iter()
+            .any(|name| term.contains(name))
 }
 
 impl Debug for StdIOHandle {
@@ -26,30 +108,59 @@ impl Debug for StdIOHandle {
 }
 impl Write for StdIOHandle {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.handle.write(buf)
+        self.state.advance(buf);
+        self.frame_writer().write(buf)
     }
     fn flush(&mut self) -> std::io::Result<()> {
+        if self.is_update_open {
+            self.is_update_open = false;
+            self.frame.extend_from_slice(END_SYNCHRONIZED_UPDATE);
+        }
+        self.state.forget();
+        let frame = std::mem::take(&mut self.frame);
+        self.handle.write_all(&frame)?;
         self.handle.flush()
     }
 }
+impl Drop for StdIOHandle {
+    fn drop(&mut self) {
+        if !self.frame.is_empty() {
+            let _ = self.flush();
+        }
+    }
+}
 
 impl Handle for StdIOHandle {
     fn set_cursor_location(&mut self, coordinate: Usize2d) -> Result<(), HandleError> {
-        match write!(self.handle, "\x1b[{};{}H", coordinate.y, coordinate.x) {
+        if !self.state.needs_cursor_location(coordinate) {
+            return Ok(());
+        }
+        match write!(
+            self.frame_writer(),
+            "\x1b[{};{}H",
+            coordinate.y,
+            coordinate.x
+        ) {
             Ok(_) => Ok(()),
             Err(_) => Err(HandleError::SetCursorLocationFailed),
         }
     }
     fn set_foreground_color(&mut self, color: TerminalColors) -> Result<(), HandleError> {
-        match write!(self.handle, "\x1b[38;5;{}m", color as u32) {
+        if !self.state.needs(Escape::Foreground(color)) {
+            return Ok(());
+        }
+        match write!(self.frame_writer(), "\x1b[38;5;{}m", color as u32) {
             Ok(_) => Ok(()),
             Err(_) => Err(HandleError::SetForegroundFailed),
         }
     }
     fn set_background_color(&mut self, color: TerminalColors) -> Result<(), HandleError> {
-        match write!(self.handle, "\x1b[38;5;{}m", color as u32) {
+        if !self.state.needs(Escape::Background(color)) {
+            return Ok(());
+        }
+        match write!(self.frame_writer(), "\x1b[48;5;{}m", color as u32) {
             Ok(_) => Ok(()),
-            Err(_) => Err(HandleError::SetForegroundFailed),
+            Err(_) => Err(HandleError::SetBackgroundFailed),
         }
     }
     fn write_to_location(
@@ -57,7 +168,153 @@ impl Handle for StdIOHandle {
         buf: &[u8],
         coord: crate::shared::usize2d::Coord,
     ) -> Result<usize, HandleError> {
-        let _ = self.set_cursor_location(coord)?;
+        self.set_cursor_location(coord)?;
         self.write(buf).map_err(|_| HandleError::WriteFailed)
     }
+    fn clear_screen(&mut self) -> Result<(), HandleError> {
+        self.reset_colors()?;
+        write!(self.frame_writer(), "\x1b[2J").map_err(|_| HandleError::WriteFailed)
+    }
+    fn set_cursor_visible(&mut self, is_visible: bool) -> Result<(), HandleError> {
+        let code = if is_visible { 'h' } else { 'l' };
+        write!(self.frame_writer(), "\x1b[?25{}", code).map_err(|_| HandleError::WriteFailed)
+    }
+    fn set_cursor_style(&mut self, style: CursorStyle) -> Result<(), HandleError> {
+        write!(self.frame_writer(), "\x1b[{} q", style.code()).map_err(|_| HandleError::WriteFailed)
+    }
+    fn set_alternate_screen(&mut self, is_active: bool) -> Result<(), HandleError> {
+        self.state.forget();
+        let code = if is_active { 'h' } else { 'l' };
+        write!(self.frame_writer(), "\x1b[?1049{}", code).map_err(|_| HandleError::WriteFailed)
+    }
+    fn set_title(&mut self, title: &str) -> Result<(), HandleError> {
+        // Control characters would end the sequence early
+        let title: String = title.chars().filter(|c| !c.is_control()).collect();
+        write!(self.frame_writer(), "\x1b]0;{}\x07", title).map_err(|_| HandleError::WriteFailed)
+    }
+    fn bell(&mut self) -> Result<(), HandleError> {
+        write!(self.frame_writer(), "\x07").map_err(|_| HandleError::WriteFailed)
+    }
+    fn reset_colors(&mut self) -> Result<(), HandleError> {
+        self.state.forget_colors();
+        write!(self.frame_writer(), "\x1b[0m").map_err(|_| HandleError::WriteFailed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        handler::handle::Handle, rendering::colors::TerminalColors as TC, shared::usize2d::Usize2d,
+    };
+
+    use super::{supports_synchronized_output, StdIOHandle};
+
+    /// A writer that keeps everything written to it
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    impl Output {
+        fn text(&self) -> String {
+            String::from_utf8_lossy(&self.0.lock().unwrap()).to_string()
+        }
+    }
+
+    #[test]
+    fn synchronized_frames() {
+        let test_cases = vec![
+            (
+                1,
+                true,
+                "\x1b[?2026h\x1b[2;1Hab\x1b[?2026l\x1b[?2026hc\x1b[?2026l",
+            ),
+            (2, false, "\x1b[2;1Habc"),
+        ];
+        for (test_case, is_synchronized, expected) in test_cases {
+            let output = Output::default();
+            let mut handle = StdIOHandle::with_writer(Box::new(output.clone()), is_synchronized);
+            let _ = handle.write_to_location(b"ab", Usize2d::new(1, 2));
+            let _ = handle.flush();
+            let _ = handle.flush();
+            let _ = handle.write(b"c");
+            let _ = handle.flush();
+            assert_eq!(
+                output.text(),
+                expected,
+                "Test case {}: The frames are not wrapped correctly",
+                test_case
+            );
+        }
+    }
+
+    #[test]
+    fn skips_redundant_escapes() {
+        let output = Output::default();
+        let mut handle = StdIOHandle::with_writer(Box::new(output.clone()), false);
+        for x in 0..3 {
+            let _ = handle.set_foreground_color(TC::White);
+            let _ = handle.write_to_location(b"ab", Usize2d::new(1 + x * 2, 2));
+        }
+        assert_eq!(output.text(), "", "A frame is written at once on the flush");
+        let _ = handle.flush();
+        let _ = handle.set_foreground_color(TC::White);
+        let _ = handle.flush();
+        assert_eq!(
+            output.text(),
+            "\x1b[38;5;231m\x1b[2;1Hababab\x1b[38;5;231m",
+            "The colors and the cursor are only set when they change"
+        );
+        assert_eq!(handle.skipped_escapes(), 4);
+    }
+
+    #[test]
+    fn background_color() {
+        let output = Output::default();
+        let mut handle = StdIOHandle::with_writer(Box::new(output.clone()), false);
+        let _ = handle.set_background_color(TC::Black);
+        let _ = handle.set_foreground_color(TC::Black);
+        let _ = handle.flush();
+        assert_eq!(
+            output.text(),
+            "\x1b[48;5;232m\x1b[38;5;232m",
+            "The background is set with 48, not with the foreground code 38"
+        );
+    }
+
+    #[test]
+    fn detect_synchronized_output() {
+        let test_cases = vec![
+            (1, vec![], false),
+            (2, vec![("TERM", "xterm-256color")], false),
+            (3, vec![("TERM", "xterm-kitty")], true),
+            (4, vec![("TERM_PROGRAM", "WezTerm")], true),
+            (5, vec![("TERM_PROGRAM", "Apple_Terminal")], false),
+            (6, vec![("WT_SESSION", "b7a3")], true),
+        ];
+        for (test_case, vars, expected) in test_cases {
+            let lookup = |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            };
+            assert_eq!(
+                supports_synchronized_output(lookup),
+                expected,
+                "Test case {}: The detection is wrong",
+                test_case
+            );
+        }
+    }
 }
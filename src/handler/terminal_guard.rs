@@ -0,0 +1,43 @@
+use std::{panic, sync::Once};
+
+use super::{cursor_style::CursorStyle, handle::Handle, std_io_handle::StdIOHandle};
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Install a panic hook that leaves the alternate screen, restores the cursor and resets the colors
+/// before the panic message is printed, so that the message ends up on the main screen instead of
+/// being thrown away with the alternate screen
+///
+/// Installing the hook more than once has no effect
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::sync::mpsc::channel;
+/// # use tic_tac_toe2::{handler::terminal_guard::restore_terminal_on_panic, ConwayError, ConwaysGame};
+/// # fn main() -> Result<(), ConwayError> {
+/// # let (_keys, receiver) = channel();
+/// # let mut game = ConwaysGame::builder(40, 20).receiver(receiver).build()?;
+/// restore_terminal_on_panic();
+/// game.run();
+/// # Ok(())
+/// # }
+/// ```
+pub fn restore_terminal_on_panic() {
+    INSTALL_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore_terminal(&mut StdIOHandle::new());
+            previous_hook(info);
+        }));
+    });
+}
+
+/// Bring a terminal back into the state it was in before a game started
+pub fn restore_terminal(handle: &mut dyn Handle) {
+    let _ = handle.reset_colors();
+    let _ = handle.set_cursor_style(CursorStyle::default());
+    let _ = handle.set_cursor_visible(true);
+    let _ = handle.set_alternate_screen(false);
+    let _ = handle.flush();
+}
@@ -0,0 +1,171 @@
+use crate::{rendering::colors::TerminalColors, shared::usize2d::Usize2d};
+
+/// The characters that may be drawn two columns wide or none at all, the cursor is not followed
+/// past text with such characters. Emoji, CJK, Hangul, full width forms and zero width characters
+const UNSURE_WIDTHS: [(char, char); 10] = [
+    ('\u{1100}', '\u{115f}'),
+    ('\u{200b}', '\u{200f}'),
+    ('\u{2600}', '\u{27bf}'),
+    ('\u{2e80}', '\u{a4cf}'),
+    ('\u{ac00}', '\u{d7a3}'),
+    ('\u{f900}', '\u{faff}'),
+    ('\u{fe00}', '\u{fe4f}'),
+    ('\u{ff00}', '\u{ff60}'),
+    ('\u{ffe0}', '\u{ffe6}'),
+    ('\u{1f000}', char::MAX),
+];
+
+/// An escape sequence a handle writes to the terminal
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Escape {
+    CursorLocation(usize, usize),
+    Foreground(TerminalColors),
+    Background(TerminalColors),
+}
+
+/// Follows what the terminal was last told, so that a handle can leave out the escape sequences
+/// that would not change anything
+///
+/// The renderers set the colors before almost every cell and move the cursor before every write,
+/// even when the colors are the same and the cursor is already where the write goes. A handle
+/// asks `needs` before writing an escape sequence and skips it if the terminal is already in that
+/// state
+///
+/// Anything the state can not follow, like a reset of the colors or text with control characters,
+/// makes it forget that part, so the next sequence is always written
+///
+/// # Example
+///
+/// ```
+/// # use std::io::Write;
+/// # use tic_tac_toe2::{handler::terminal_state::{Escape, TerminalState}, TerminalColors};
+/// # fn main() -> std::io::Result<()> {
+/// # let mut state = TerminalState::default();
+/// # let mut handle: Vec<u8> = Vec::new();
+/// # let color = TerminalColors::Red;
+/// if state.needs(Escape::Foreground(color)) {
+///     write!(handle, "\x1b[38;5;{}m", color as u32)?;
+/// }
+/// assert!(!state.needs(Escape::Foreground(color)));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TerminalState {
+    cursor: Option<(usize, usize)>,
+    foreground: Option<TerminalColors>,
+    background: Option<TerminalColors>,
+    /// The escape sequences that were left out
+    skipped: u64,
+}
+impl TerminalState {
+    /// Check if an escape sequence changes the state of the terminal, and count the state as
+    /// changed
+    ///
+    /// # Returns
+    ///
+    /// `true` if the sequence has to be written, `false` if the terminal is already in that state
+    pub fn needs(&mut self, escape: Escape) -> bool {
+        let is_needed = match escape {
+            Escape::CursorLocation(x, y) => self.cursor.replace((x, y)) != Some((x, y)),
+            Escape::Foreground(color) => self.foreground.replace(color) != Some(color),
+            Escape::Background(color) => self.background.replace(color) != Some(color),
+        };
+        if !is_needed {
+            self.skipped += 1;
+        }
+        is_needed
+    }
+    /// Check if moving the cursor changes the state of the terminal, see `needs`
+    pub fn needs_cursor_location(&mut self, coord: Usize2d) -> bool {
+        self.needs(Escape::CursorLocation(coord.x, coord.y))
+    }
+    /// Move the cursor past text that was written at it
+    pub fn advance(&mut self, buf: &[u8]) {
+        let Some((x, y)) = self.cursor else {
+            return;
+        };
+        let columns = std::str::from_utf8(buf).ok().and_then(|text| {
+            text.chars().try_fold(0, |columns, c| {
+                let is_followed = !c.is_control()
+                    && !UNSURE_WIDTHS
+                        .iter()
+                        .any(|(first, last)| (*first..=*last).contains(&c));
+                is_followed.then_some(columns + 1)
+            })
+        });
+        self.cursor = columns.map(|columns| (x + columns, y));
+    }
+    /// Forget where the cursor is, like after clearing the screen
+    pub fn forget_cursor(&mut self) {
+        self.cursor = None;
+    }
+    /// Forget the colors, like after they were reset
+    pub fn forget_colors(&mut self) {
+        self.foreground = None;
+        self.background = None;
+    }
+    /// Forget everything, like after switching screens
+    pub fn forget(&mut self) {
+        self.forget_cursor();
+        self.forget_colors();
+    }
+    /// Get the number of escape sequences that were left out
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rendering::colors::TerminalColors as TC;
+
+    use super::{Escape, TerminalState};
+
+    #[test]
+    fn skips_repeats() {
+        let mut state = TerminalState::default();
+        let test_cases = [
+            ("first color", Escape::Foreground(TC::Red), true),
+            ("same color", Escape::Foreground(TC::Red), false),
+            ("background", Escape::Background(TC::Red), true),
+            ("new color", Escape::Foreground(TC::White), true),
+            ("first move", Escape::CursorLocation(2, 1), true),
+            ("same place", Escape::CursorLocation(2, 1), false),
+        ];
+        for (test_case, escape, expected) in test_cases {
+            assert_eq!(state.needs(escape), expected, "{}", test_case);
+        }
+        assert_eq!(state.skipped(), 2);
+    }
+
+    #[test]
+    fn follows_the_cursor() {
+        let test_cases = [
+            ("ascii", "ab".as_bytes(), false),
+            ("blocks", "██".as_bytes(), false),
+            ("new line", "a\n".as_bytes(), true),
+            ("wide", "🙂".as_bytes(), true),
+            ("invalid", &[0xff, 0xfe][..], true),
+        ];
+        for (test_case, buf, expected) in test_cases {
+            let mut state = TerminalState::default();
+            state.needs(Escape::CursorLocation(0, 3));
+            state.advance(buf);
+            assert_eq!(
+                state.needs(Escape::CursorLocation(2, 3)),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+
+        let mut state = TerminalState::default();
+        state.needs(Escape::Foreground(TC::Red));
+        state.forget();
+        assert!(
+            state.needs(Escape::Foreground(TC::Red)),
+            "A forgotten color is set again"
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Terminal games and the pieces they are built from
+//!
+//! The binary is a thin consumer of this library, everything it runs can be used from other
+//! crates as well:
+//!
+//! * [`conway`] - the Game of Life engine with its rules, patterns, saves and analysis tools
+//! * [`maze`] - maze generation and pathfinding that can be animated a step at a time
+//! * [`panel`] - panels, toasts and the state they share for drawing boxed text
+//! * [`handler`] - the handles that output is written to, like the terminal or memory
+//! * [`rendering`] - colors, fonts, themes, HUD widgets and the helpers that draw messages and
+//!   sprites
+//! * [`scene`] - screens that own the whole terminal, like the title screen and its demo
+//! * `export` - PNG, GIF and SVG exports of frames and boards, with the `export-image` feature
+//! * [`tic_tac_toe`] - the tic-tac-toe board, its rules and a computer player
+//!
+//! The types most programs need are re-exported from the root of the crate. The heavier parts
+//! are behind features that are all enabled by default:
+//!
+//! * `serde` - saves, the autosave, options, scores and achievements on disk
+//! * `net` - hosting, joining and spectating games over TCP
+//! * `web` - showing and controlling a simulation in a browser over a WebSocket, not enabled by
+//!   default
+//! * `export-image` - PNG, GIF and SVG exports
+//! * `windows-console` and `unix-console` - the console APIs of the platforms
+//! * `crossterm-backend` - the terminal output, keys and size through crossterm instead, not
+//!   enabled by default
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{sync::mpsc, time::Duration};
+//! use tic_tac_toe2::{ConwayError, ConwaysGame, MemoryHandle, PrintMode};
+//!
+//! # fn main() -> Result<(), ConwayError> {
+//! let (_keys, receiver) = mpsc::channel();
+//! let mut game = ConwaysGame::builder(20, 10)
+//!     .seed(42)
+//!     .render_mode(PrintMode::DEBUG)
+//!     .speed(Duration::from_millis(100))
+//!     .receiver(receiver)
+//!     .handle(Box::new(MemoryHandle::new()))
+//!     .build()?;
+//! game.step();
+//! game.render();
+//! # Ok(())
+//! # }
+//! ```
+
+pub use conway::{
+    conways_game::{ConwaysGame, ConwaysGameBuilder},
+    errors::ConwayError,
+    print_mode::PrintMode,
+    rule::Rule,
+    settings::ConwaysSettings,
+};
+pub use handler::{
+    handle::Handle, memory_handle::MemoryHandle, shared_handle::SharedHandle,
+    std_io_handle::StdIOHandle,
+};
+pub use panel::{layout::PanelLayout, panel::Panel};
+pub use rendering::{colors::TerminalColors, theme::Theme};
+pub use shared::frame::{Frame, Pixel};
+pub use tic_tac_toe::board::{Board, Mark};
+
+pub mod utils {
+    pub mod arg_helper;
+    pub mod buffer_pool;
+    pub mod clock;
+    pub mod helper_macros;
+    pub mod metrics;
+    pub mod progress;
+    pub mod rng;
+    pub mod step_scheduler;
+    pub mod timer;
+    pub mod vec_t_writer;
+}
+
+pub mod console {
+    #[cfg(all(windows, feature = "windows-console"))]
+    pub mod console_control;
+    #[cfg(feature = "crossterm-backend")]
+    pub mod crossterm_input;
+    pub mod errors;
+    pub mod input_record;
+    pub mod input_source;
+    #[cfg(all(windows, feature = "windows-console"))]
+    pub mod mode;
+    #[cfg(all(windows, feature = "windows-console"))]
+    pub mod notify_inputs;
+    pub mod terminal_size;
+}
+pub mod conway {
+    pub mod automaton;
+    #[cfg(feature = "serde")]
+    pub mod autosave;
+    pub mod bit_grid;
+    pub mod checkpoint;
+    pub mod clipboard;
+    pub mod command;
+    pub mod conways_game;
+    pub mod conways_law;
+    pub mod cycle;
+    pub mod divergence;
+    pub mod editor;
+    pub mod elementary;
+    pub mod errors;
+    pub mod generations;
+    #[cfg(feature = "serde")]
+    pub mod ghost;
+    pub mod heatmap;
+    pub mod history;
+    pub mod immigration;
+    pub mod input_log;
+    pub mod key_bindings;
+    pub mod macros;
+    pub mod neighbor_counts;
+    pub mod options;
+    pub mod patterns;
+    pub mod pause_menu;
+    pub mod print_mode;
+    #[cfg(test)]
+    mod properties;
+    pub mod rule;
+    pub mod sand;
+    #[cfg(feature = "serde")]
+    pub mod save;
+    pub mod seed_sweep;
+    pub mod settings;
+    pub mod shared_clipboard;
+    pub mod soup_search;
+    #[cfg(feature = "serde")]
+    pub mod timeline;
+    pub mod transform;
+    pub mod viewport;
+}
+pub mod coordination {
+    pub mod flush_scheduler;
+    pub mod render_pool;
+    pub mod service;
+}
+#[cfg(feature = "net")]
+pub mod net {
+    pub mod protocol;
+    pub mod session;
+    pub mod spectator;
+    #[cfg(feature = "web")]
+    pub mod web_bridge;
+    #[cfg(feature = "web")]
+    pub mod websocket;
+}
+#[cfg(feature = "export-image")]
+pub mod export {
+    pub mod gif;
+    pub mod image;
+    pub mod png;
+    pub mod svg;
+}
+pub mod entity {
+    #[allow(clippy::module_inception)]
+    pub mod entity;
+    pub mod entity_layer;
+}
+pub mod maze {
+    pub mod generator;
+    #[allow(clippy::module_inception)]
+    pub mod maze;
+    pub mod solver;
+}
+pub mod panel {
+    pub mod background;
+    pub mod command_enum;
+    pub mod errors;
+    pub mod file_picker;
+    pub mod frame_channel;
+    pub mod frame_history;
+    pub mod layout;
+    #[allow(clippy::module_inception)]
+    pub mod panel;
+    pub mod renderer;
+    pub mod state;
+    pub mod toast;
+}
+pub mod rendering {
+    pub mod fonts {
+        pub mod block_font;
+        pub mod small_font;
+        pub mod standard_font;
+    }
+    pub mod colors;
+    pub mod hud;
+    pub mod marquee;
+    pub mod message_helper;
+    pub mod message_style;
+    pub mod particles;
+    pub mod render_object;
+    pub mod sparkline;
+    pub mod sprite;
+    pub mod theme;
+}
+
+pub mod scene {
+    pub mod attract_mode;
+    pub mod elementary_mode;
+    pub mod generations_mode;
+    pub mod immigration_mode;
+    pub mod maze_mode;
+    pub mod race_mode;
+    pub mod sand_mode;
+    #[allow(clippy::module_inception)]
+    pub mod scene;
+    pub mod title_screen;
+}
+
+pub mod scores {
+    pub mod achievements;
+    #[cfg(feature = "serde")]
+    pub mod score_board;
+}
+
+pub mod tic_tac_toe {
+    pub mod ai;
+    pub mod board;
+}
+
+pub mod shared {
+    pub mod float2d;
+    pub mod frame;
+    pub mod frame_dump;
+    pub mod shared_errors;
+    pub mod square;
+    pub mod usize2d;
+}
+#[cfg(test)]
+pub mod testing {
+    pub mod conformance;
+    pub mod driver;
+    pub mod snapshot;
+}
+pub mod handler {
+    #[cfg(feature = "crossterm-backend")]
+    pub mod crossterm_handle;
+    pub mod cursor_style;
+    pub mod handle;
+    pub mod handle_error;
+    pub mod memory_handle;
+    pub mod shared_handle;
+    pub mod std_io_handle;
+    pub mod terminal_guard;
+    pub mod terminal_state;
+}
@@ -1,58 +1,513 @@
-use coordination::service::CoordinatorService;
-use std::env;
-use utils::arg_helper::read_config;
-
-pub mod utils {
-    pub mod arg_helper;
-    pub mod helper_macros;
-    pub mod vec_t_writer;
+#[cfg(not(any(
+    all(windows, feature = "windows-console"),
+    feature = "crossterm-backend"
+)))]
+use std::io::{self, BufReader};
+#[cfg(feature = "serde")]
+use std::path::Path;
+use std::{
+    env,
+    fs::File,
+    thread,
+    time::{Duration, Instant},
+};
+#[cfg(not(any(
+    all(windows, feature = "windows-console"),
+    feature = "crossterm-backend"
+)))]
+use tic_tac_toe2::console::input_source::listen_and_notify_line_inputs;
+#[cfg(all(
+    windows,
+    feature = "windows-console",
+    not(feature = "crossterm-backend")
+))]
+use tic_tac_toe2::console::notify_inputs::listen_and_notify_key_inputs;
+#[cfg(feature = "serde")]
+use tic_tac_toe2::conway::{autosave::Autosave, save::SavedGame, timeline::RecordedSession};
+#[cfg(not(feature = "crossterm-backend"))]
+use tic_tac_toe2::SharedHandle;
+#[cfg(feature = "crossterm-backend")]
+use tic_tac_toe2::{
+    console::crossterm_input::CrosstermInput, handler::crossterm_handle::CrosstermHandle,
+};
+use tic_tac_toe2::{
+    console::{input_source::InputSource, terminal_size::terminal_size},
+    conway::{
+        conways_game::ConwaysGame,
+        generations::GenerationsRule,
+        options::SPEEDS,
+        patterns::PatternPlacement,
+        rule::Rule,
+        seed_sweep::{SeedRange, SeedSweep},
+        settings::Boundary,
+        shared_clipboard::{SharedClipboard, SystemClipboard},
+        soup_search::SoupSearch,
+    },
+    coordination::service::CoordinatorService,
+    handler::handle::Handle,
+    maze::{generator::GenerationAlgorithm, solver::SearchAlgorithm},
+    rendering::hud::{ProgressBar, Widget},
+    scene::{
+        attract_mode::AttractMode,
+        elementary_mode::ElementaryMode,
+        generations_mode::GenerationsMode,
+        immigration_mode::ImmigrationMode,
+        maze_mode::MazeMode,
+        race_mode::{RaceMode, Racer},
+        sand_mode::SandMode,
+        scene::{run_scene, Scene, SCENE_TICK},
+        title_screen::{TitleMenuItem, TitleScreen},
+    },
+    shared::usize2d::Usize2d,
+    utils::{
+        arg_helper::{read_config, read_optional_config},
+        clock::SystemClock,
+        progress::Progress,
+        rng::SeedArg,
+    },
+};
+#[cfg(feature = "net")]
+use tic_tac_toe2::{
+    net::{
+        session::{NetSession, DEFAULT_PORT},
+        spectator::{SpectatorClient, SpectatorServer, DEFAULT_SPECTATOR_PORT},
+    },
+    scene::scene::is_leave_key,
+    shared::{square::Square, usize2d::Coord},
+};
+#[cfg(feature = "web")]
+use tic_tac_toe2::{
+    net::{
+        spectator::board_render_object,
+        web_bridge::{WebBridge, DEFAULT_WEB_PORT},
+    },
+    panel::renderer::render,
+    MemoryHandle,
+};
+
+/// The number of ticks of the title screen before its background advances a round
+const TITLE_TICKS_PER_GENERATION: usize = 10;
+/// The number of ticks of the attract mode before a move or a generation
+const ATTRACT_TICKS_PER_STEP: usize = 5;
+/// The number of moves or generations of a demo of the attract mode
+const ATTRACT_STEPS_PER_DEMO: usize = 60;
+/// The number of ticks of a mode that was chosen with a flag before its next generation
+const MODE_TICKS_PER_GENERATION: usize = 2;
+
+fn main() -> Result<(), SystemException> {
+    let args: Vec<String> = env::args().collect();
+
+    #[cfg(all(windows, feature = "windows-console"))]
+    let console = tic_tac_toe2::console::console_control::ConsoleControl::init()
+        .map_err(|_| SystemException::_Windows)?;
+
+    let x_len: usize = read_config(&args, "--x-len".to_string(), 10);
+    let y_len: usize = read_config(&args, "--y-len".to_string(), 10);
+    let pattern: Option<PatternPlacement> = read_optional_config(&args, "--pattern".to_string());
+    let skip: u64 = read_config(&args, "--skip".to_string(), 0);
+    let seed: SeedArg = read_config(&args, "--seed".to_string(), SeedArg::Random);
+    let rng = seed.service();
+    eprintln!("{}", rng);
+
+    let seed_sweep: Option<SeedRange> = read_optional_config(&args, "--seed-sweep".to_string());
+    if let Some(seeds) = seed_sweep {
+        let generation_cap: u64 = read_config(&args, "--generation-cap".to_string(), 1000);
+        let csv_path: String = read_optional_config(&args, "--csv".to_string())
+            .unwrap_or("seed_sweep.csv".to_string());
+        let sweep = SeedSweep::new(x_len, y_len, generation_cap);
+        let soup_search_path: Option<String> =
+            read_optional_config(&args, "--soup-search".to_string());
+        // The sweep runs on a worker thread while the progress is drawn on this one
+        let progress = Progress::new(seeds.0.end - seeds.0.start, Instant::now());
+        let results = thread::scope(|scope| {
+            let worker = scope.spawn(|| sweep.run_with_progress(seeds.clone(), &progress));
+            let bar = ProgressBar::tracking("Sweep", 20, &progress, &SystemClock);
+            while !worker.is_finished() {
+                eprint!("\r{}", bar.rows()[0]);
+                thread::sleep(Duration::from_millis(100));
+            }
+            eprintln!("\r{}", bar.rows()[0]);
+            worker.join()
+        })
+        .map_err(|_| SystemException::Sweep)?;
+        let mut csv = File::create(&csv_path).map_err(|_| SystemException::Sweep)?;
+        sweep
+            .write_csv(&results, &mut csv)
+            .map_err(|_| SystemException::Sweep)?;
+        println!("{}", SeedSweep::report(&results));
+        println!("Results written to {}", csv_path);
+        if let Some(path) = soup_search_path {
+            let search = SoupSearch::new(sweep);
+            let finds = search.run(seeds);
+            let mut file = File::create(&path).map_err(|_| SystemException::Sweep)?;
+            search
+                .write_results(&finds, &mut file)
+                .map_err(|_| SystemException::Sweep)?;
+            println!(
+                "{} oscillators and spaceships written to {}",
+                finds.len(),
+                path
+            );
+        }
+        return Ok(());
+    }
+    #[cfg(feature = "serde")]
+    {
+        let verify_path: Option<String> = read_optional_config(&args, "--verify".to_string());
+        if let Some(path) = verify_path {
+            let session =
+                RecordedSession::load(Path::new(&path)).map_err(|_| SystemException::Verify)?;
+            return match ConwaysGame::verify_session(&session) {
+                Ok(checked) => {
+                    println!("All {} checkpoints of {} match", checked, path);
+                    Ok(())
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    Err(SystemException::Verify)
+                }
+            };
+        }
+    }
+    if let Some(mut mode) = chosen_mode(&args, rng.master_seed()) {
+        let result = show_mode(&mut *mode);
+        #[cfg(all(windows, feature = "windows-console"))]
+        let _ = console.set_mode(tic_tac_toe2::console::mode::ConsoleMode::Cooked);
+        return result;
+    }
+    #[cfg(feature = "net")]
+    {
+        let host = args.iter().any(|arg| arg == "--host");
+        let connect: Option<String> = read_optional_config(&args, "--connect".to_string());
+        if host || connect.is_some() {
+            let session = match connect {
+                Some(address) => NetSession::connect(address),
+                None => {
+                    println!("Waiting for a player to connect on port {}", DEFAULT_PORT);
+                    NetSession::host(("0.0.0.0", DEFAULT_PORT))
+                }
+            };
+            return session
+                .and_then(|mut session| {
+                    session.play_in_terminal(&mut std::io::stdin().lock(), &mut std::io::stdout())
+                })
+                .map(|_| ())
+                .map_err(|e| {
+                    eprintln!("{}", e);
+                    SystemException::Net
+                });
+        }
+        #[cfg(feature = "web")]
+        if args.iter().any(|arg| arg == "--web") {
+            return serve_web(x_len, y_len, rng.master_seed());
+        }
+        let watch: Option<String> = read_optional_config(&args, "--watch".to_string());
+        if let Some(address) = watch {
+            let result = watch_game(address);
+            #[cfg(all(windows, feature = "windows-console"))]
+            let _ = console.set_mode(tic_tac_toe2::console::mode::ConsoleMode::Cooked);
+            return result;
+        }
+    }
+    // Bind before the terminal is taken over, so that a port that is in use can be reported
+    #[cfg(feature = "net")]
+    let spectators = match args.iter().any(|arg| arg == "--serve") {
+        true => {
+            let server =
+                SpectatorServer::bind(("0.0.0.0", DEFAULT_SPECTATOR_PORT)).map_err(|e| {
+                    eprintln!("{}", e);
+                    SystemException::Net
+                })?;
+            println!("Spectators can watch on port {}", DEFAULT_SPECTATOR_PORT);
+            Some(server)
+        }
+        false => None,
+    };
+    let mut service = CoordinatorService::init();
+
+    // Offer to resume before the terminal is switched to raw mode. The autosave is kept until
+    // the game shuts down cleanly, so that a resumed game that crashes can be resumed again
+    #[cfg(feature = "serde")]
+    let resumed: Option<SavedGame> =
+        Autosave::default().offer_resume(&mut std::io::stdin().lock(), &mut std::io::stdout());
+
+    let mut input = game_input()?;
+    let mut handle = game_handle();
+    if !show_title(&mut *input, &mut *handle, rng.master_seed())? {
+        leave_screen(&mut *handle);
+        service.shutdown();
+        #[cfg(all(windows, feature = "windows-console"))]
+        let _ = console.set_mode(tic_tac_toe2::console::mode::ConsoleMode::Cooked);
+        return Ok(());
+    }
+
+    let mut builder = ConwaysGame::builder(x_len, y_len)
+        .seed(rng.master_seed())
+        .skip(skip)
+        .clipboard(SharedClipboard::new(SystemClipboard::detect()))
+        .input(input)
+        .handle(handle);
+    if let Some(placement) = pattern {
+        builder = builder.pattern(placement);
+    }
+    #[cfg(feature = "serde")]
+    if let Some(saved) = resumed {
+        builder = builder.resume(saved);
+    }
+    #[cfg(feature = "net")]
+    if let Some(server) = spectators {
+        builder = builder.spectators(server);
+    }
+    let game = ConwaysGame::run_async(builder);
+    let result = game.join().map_err(|_| SystemException::Game);
+    service.shutdown();
+    // The input thread waits for a key before it sees that the game stopped
+    #[cfg(all(windows, feature = "windows-console"))]
+    let _ = console.set_mode(tic_tac_toe2::console::mode::ConsoleMode::Cooked);
+    result
+}
+
+/// Show the title screen until a game is chosen. The demo of the title menu, or a title screen
+/// that nobody touched for a while, runs the attract mode, which returns to the title screen on
+/// any key
+///
+/// # Returns
+///
+/// `true` to play, `false` to quit
+fn show_title(
+    input: &mut dyn InputSource,
+    handle: &mut dyn Handle,
+    seed: u64,
+) -> Result<bool, SystemException> {
+    let size = screen_size();
+    let mut title = TitleScreen::new(size, seed, TITLE_TICKS_PER_GENERATION)
+        .map_err(|_| SystemException::Renderer)?;
+    let _ = handle.set_alternate_screen(true);
+    let _ = handle.set_cursor_visible(false);
+    loop {
+        let _ = handle.clear_screen();
+        run_scene(&mut title, input, handle, SCENE_TICK).map_err(|_| SystemException::Renderer)?;
+        match title.choice() {
+            Some(TitleMenuItem::Play) => return Ok(true),
+            Some(TitleMenuItem::Demo) => {
+                let mut attract =
+                    AttractMode::new(size, seed, ATTRACT_TICKS_PER_STEP, ATTRACT_STEPS_PER_DEMO);
+                let _ = handle.clear_screen();
+                run_scene(&mut attract, input, handle, SCENE_TICK)
+                    .map_err(|_| SystemException::Renderer)?;
+                title.wake();
+            }
+            Some(TitleMenuItem::Quit) | None => return Ok(false),
+        }
+    }
 }
 
-pub mod coordination {
-    pub mod service;
+/// Get the mode that was chosen with a flag instead of the Game of Life:
+///
+/// * `--rule N` - the elementary automaton with the Wolfram number N
+/// * `--sand` - the falling sand sandbox
+/// * `--maze` - a maze that is carved and then solved
+/// * `--race` - the Game of Life with a wrapping boundary next to one with a dead boundary, or
+///   next to a wrapping board under the rule of `--race-rule`, like `B36/S23`
+/// * `--immigration` - the two species variant of the Game of Life
+/// * `--generations RULE` - a Generations rule like `345/2/4`, Star Wars without a rule
+fn chosen_mode(args: &[String], seed: u64) -> Option<Box<dyn Scene>> {
+    let size = screen_size();
+    let elementary_rule: Option<u8> = read_optional_config(args, "--rule".to_string());
+    if let Some(rule) = elementary_rule {
+        return Some(Box::new(ElementaryMode::new(
+            size,
+            rule,
+            seed,
+            MODE_TICKS_PER_GENERATION,
+        )));
+    }
+    if args.iter().any(|arg| arg == "--sand") {
+        return Some(Box::new(SandMode::new(size, MODE_TICKS_PER_GENERATION)));
+    }
+    if args.iter().any(|arg| arg == "--race") {
+        let left = Racer::new(Rule::conway(), Boundary::Wrap);
+        let race_rule: Option<Rule> = read_optional_config(args, "--race-rule".to_string());
+        let right = match race_rule {
+            Some(rule) => Racer::new(rule, Boundary::Wrap),
+            None => Racer::new(Rule::conway(), Boundary::Dead),
+        };
+        let mut race = RaceMode::new(size, seed, MODE_TICKS_PER_GENERATION, [left, right]);
+        race.set_clipboard(SharedClipboard::new(SystemClipboard::detect()));
+        return Some(Box::new(race));
+    }
+    if args.iter().any(|arg| arg == "--immigration") {
+        return Some(Box::new(ImmigrationMode::new(
+            size,
+            seed,
+            MODE_TICKS_PER_GENERATION,
+        )));
+    }
+    if args.iter().any(|arg| arg == "--generations") {
+        let rule: Option<GenerationsRule> = read_optional_config(args, "--generations".to_string());
+        return Some(Box::new(GenerationsMode::new(
+            size,
+            seed,
+            MODE_TICKS_PER_GENERATION,
+            rule.unwrap_or_else(GenerationsRule::star_wars),
+        )));
+    }
+    if args.iter().any(|arg| arg == "--maze") {
+        return Some(Box::new(MazeMode::new(
+            size,
+            seed,
+            GenerationAlgorithm::Backtracker,
+            SearchAlgorithm::AStar,
+            SPEEDS[0],
+            Box::new(SystemClock),
+        )));
+    }
+    None
 }
-pub mod panel {
-    pub mod command_enum;
-    pub mod errors;
-    pub mod panel;
-    pub mod state;
+
+/// Show a mode that was chosen with a flag, like the elementary automaton of `--rule`, until it
+/// is left
+fn show_mode(scene: &mut dyn Scene) -> Result<(), SystemException> {
+    let mut input = game_input()?;
+    let mut handle = game_handle();
+    let _ = handle.set_alternate_screen(true);
+    let _ = handle.set_cursor_visible(false);
+    let _ = handle.clear_screen();
+    let result = run_scene(scene, &mut *input, &mut *handle, SCENE_TICK)
+        .map_err(|_| SystemException::Renderer);
+    leave_screen(&mut *handle);
+    result
 }
-pub mod rendering {
-    pub mod colors;
-    pub mod render_object;
-    pub mod sprite;
+
+/// Watch a game that is served with `--serve` in a panel that fills the terminal, until the game
+/// stops or a key that leaves a scene is pressed
+#[cfg(feature = "net")]
+fn watch_game(address: String) -> Result<(), SystemException> {
+    let mut client = SpectatorClient::connect(address).map_err(|e| {
+        eprintln!("{}", e);
+        SystemException::Net
+    })?;
+    let mut input = game_input()?;
+    let mut handle = game_handle();
+    let _ = handle.set_alternate_screen(true);
+    let _ = handle.set_cursor_visible(false);
+    let _ = handle.clear_screen();
+    let mut service = CoordinatorService::init();
+    let area = Square::new(Usize2d::new(1, 1), screen_size());
+    let (_, frame_sender) = service
+        .add_panel(area, handle)
+        .map_err(|_| SystemException::Renderer)?;
+    let watcher = thread::spawn(move || client.watch(&frame_sender, Coord::new(1, 1)));
+    'watching: while !watcher.is_finished() {
+        while let Some(event) = input.poll() {
+            if event.is_down && is_leave_key(event.command) {
+                break 'watching;
+            }
+        }
+        thread::sleep(SCENE_TICK);
+    }
+    service.shutdown();
+    leave_screen(&mut *game_handle());
+    Ok(())
 }
 
-pub mod shared {
-    pub mod frame;
-    pub mod shared_errors;
-    pub mod square;
-    pub mod usize2d;
+/// Play a game in the browsers that open the page of the web bridge instead of the terminal,
+/// until a browser quits the game. The board is sent whenever it changes or a browser connects
+#[cfg(feature = "web")]
+fn serve_web(x_len: usize, y_len: usize, seed: u64) -> Result<(), SystemException> {
+    let area = Square::new(
+        Usize2d::new(0, 0),
+        Usize2d::new(x_len.saturating_sub(1), y_len.saturating_sub(1)),
+    );
+    let (mut bridge, keys) = WebBridge::bind(("0.0.0.0", DEFAULT_WEB_PORT), area).map_err(|e| {
+        eprintln!("{}", e);
+        SystemException::Net
+    })?;
+    let mut game = ConwaysGame::builder(x_len, y_len)
+        .seed(seed)
+        .receiver(keys)
+        .handle(Box::new(MemoryHandle::new()))
+        .build()
+        .map_err(|_| SystemException::Game)?;
+    println!(
+        "Open http://localhost:{} in a browser to play",
+        DEFAULT_WEB_PORT
+    );
+    game.start();
+    let mut shown: Option<(SavedGame, usize)> = None;
+    loop {
+        bridge.poll_keys();
+        if !game.tick() {
+            break;
+        }
+        let board = game.save();
+        let browsers = bridge.client_count();
+        let is_shown = matches!(&shown, Some((shown_board, shown_browsers))
+            if *shown_board == board && *shown_browsers == browsers);
+        if !is_shown {
+            let objects: Vec<_> = board_render_object(&board, Coord::new(0, 0))
+                .into_iter()
+                .collect();
+            render(&mut bridge, &objects).map_err(|_| SystemException::Renderer)?;
+            shown = Some((board, browsers));
+        }
+        thread::sleep(SCENE_TICK);
+    }
+    game.finish();
+    Ok(())
 }
-pub mod handler {
-    pub mod handle;
-    pub mod handle_error;
-    pub mod memory_handle;
-    pub mod shared_handle;
-    pub mod std_io_handle;
+
+/// Give the terminal back the way it was before the scenes were shown
+fn leave_screen(handle: &mut dyn Handle) {
+    let _ = handle.set_cursor_visible(true);
+    let _ = handle.set_alternate_screen(false);
 }
 
-fn main() -> Result<(), SystemException> {
-    let args: Vec<String> = env::args().collect();
+/// Get the size of the terminal the scenes are drawn on, 80 by 24 if it can not be read
+fn screen_size() -> Usize2d {
+    terminal_size().unwrap_or(Usize2d::new(80, 24))
+}
 
-    let _x_len: usize = read_config(&args, "--x-len".to_string(), 10);
-    let _service = CoordinatorService::init();
+/// Get the terminal the title screen and the game are drawn on: through crossterm with the
+/// `crossterm-backend` feature, otherwise standard out
+fn game_handle() -> Box<dyn Handle> {
+    #[cfg(feature = "crossterm-backend")]
+    return Box::new(CrosstermHandle::new());
+    #[cfg(not(feature = "crossterm-backend"))]
+    Box::new(SharedHandle::init_std_out())
+}
 
-    return Ok(());
+/// Start taking the keys of the game: through crossterm with the `crossterm-backend` feature,
+/// from the raw console input on Windows, or from the lines typed on other terminals
+fn game_input() -> Result<Box<dyn InputSource>, SystemException> {
+    #[cfg(feature = "crossterm-backend")]
+    return match CrosstermInput::new() {
+        Ok(input) => Ok(Box::new(input)),
+        Err(_) => Err(SystemException::_InputReader),
+    };
+    #[cfg(not(feature = "crossterm-backend"))]
+    {
+        let (sender, receiver) = CoordinatorService::new_sender_receiver();
+        #[cfg(all(windows, feature = "windows-console"))]
+        listen_and_notify_key_inputs(sender);
+        #[cfg(not(all(windows, feature = "windows-console")))]
+        listen_and_notify_line_inputs(BufReader::new(io::stdin()), sender);
+        Ok(Box::new(receiver))
+    }
 }
 
 #[derive(Debug)]
 enum SystemException {
-    _GameException,
-    _InputReaderException,
-    _CoordinatorException,
-    _RedererException,
-    _WindowsException,
+    Game,
+    _InputReader,
+    _Coordinator,
+    Renderer,
+    _Windows,
+    Sweep,
+    #[cfg(feature = "serde")]
+    Verify,
+    #[cfg(feature = "net")]
+    Net,
 }
 
 /// Placeholder
@@ -0,0 +1,196 @@
+use std::fmt::Display;
+
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::utils::rng::{RngService, GRID_STREAM};
+
+use super::maze::{Maze, MazeCell};
+
+/// The ways a maze can be carved
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenerationAlgorithm {
+    /// Walks to a random unvisited neighbor and backs up at dead ends, which gives long winding
+    /// corridors
+    Backtracker,
+    /// Grows the maze through a random wall around the part that is carved, which gives many
+    /// short dead ends
+    Prim,
+}
+impl Display for GenerationAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerationAlgorithm::Backtracker => write!(f, "recursive backtracker"),
+            GenerationAlgorithm::Prim => write!(f, "Prim's"),
+        }
+    }
+}
+
+/// Carves a perfect maze, where every cell can be reached by exactly one path, a passage at a
+/// time so that the carving can be animated
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::maze::{generator::{GenerationAlgorithm, MazeGenerator}, maze::Maze};
+/// # fn draw(_: &Maze) {}
+/// # let seed = 42;
+/// let mut generator = MazeGenerator::new(20, 10, GenerationAlgorithm::Prim, seed);
+/// while generator.step() {
+///     draw(generator.maze());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MazeGenerator {
+    maze: Maze,
+    algorithm: GenerationAlgorithm,
+    rng: ChaCha8Rng,
+    visited: Vec<Vec<bool>>,
+    /// The path of the backtracker, from the first cell to the cell it is at
+    stack: Vec<MazeCell>,
+    /// The walls of Prim's algorithm between a visited cell and a cell that might not be
+    frontier: Vec<(MazeCell, MazeCell)>,
+    passages: usize,
+    current: MazeCell,
+}
+impl MazeGenerator {
+    /// Start carving from the top left cell
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - the number of cells in a row
+    /// * `rows` - the number of cells in a column
+    /// * `algorithm` - how the maze is carved
+    /// * `seed` - the seed of the maze, the same seed always gives the same maze
+    pub fn new(columns: usize, rows: usize, algorithm: GenerationAlgorithm, seed: u64) -> Self {
+        let mut generator = MazeGenerator {
+            maze: Maze::new(columns, rows),
+            algorithm,
+            rng: RngService::new(seed).stream(GRID_STREAM),
+            visited: vec![vec![false; columns]; rows],
+            stack: vec![],
+            frontier: vec![],
+            passages: 0,
+            current: (0, 0),
+        };
+        generator.visit((0, 0));
+        generator
+    }
+    pub fn maze(&self) -> &Maze {
+        &self.maze
+    }
+    pub fn algorithm(&self) -> GenerationAlgorithm {
+        self.algorithm
+    }
+    /// Get the cell that was carved last
+    pub fn current(&self) -> MazeCell {
+        self.current
+    }
+    /// Check if every cell is connected, a perfect maze has one passage less than it has cells
+    pub fn is_done(&self) -> bool {
+        self.passages + 1 == self.maze.columns() * self.maze.rows()
+    }
+    /// Carve the next passage
+    ///
+    /// # Returns
+    ///
+    /// `true` if a passage was carved, `false` once the maze is done
+    pub fn step(&mut self) -> bool {
+        if self.is_done() {
+            return false;
+        }
+        let (from, to) = match self.algorithm {
+            GenerationAlgorithm::Backtracker => loop {
+                let Some(&cell) = self.stack.last() else {
+                    return false;
+                };
+                let unvisited = self.unvisited_neighbors(cell);
+                if unvisited.is_empty() {
+                    self.stack.pop();
+                    continue;
+                }
+                break (cell, unvisited[self.rng.gen_range(0..unvisited.len())]);
+            },
+            GenerationAlgorithm::Prim => loop {
+                if self.frontier.is_empty() {
+                    return false;
+                }
+                let index = self.rng.gen_range(0..self.frontier.len());
+                let (from, to) = self.frontier.swap_remove(index);
+                if !self.visited[to.1][to.0] {
+                    break (from, to);
+                }
+            },
+        };
+        self.maze.open(from, to);
+        self.passages += 1;
+        self.visit(to);
+        true
+    }
+    fn unvisited_neighbors(&self, cell: MazeCell) -> Vec<MazeCell> {
+        self.maze
+            .neighbors(cell)
+            .into_iter()
+            .filter(|(x, y)| !self.visited[*y][*x])
+            .collect()
+    }
+    /// Mark a cell as part of the maze and remember where to continue from it
+    fn visit(&mut self, cell: MazeCell) {
+        self.visited[cell.1][cell.0] = true;
+        self.current = cell;
+        match self.algorithm {
+            GenerationAlgorithm::Backtracker => self.stack.push(cell),
+            GenerationAlgorithm::Prim => {
+                let walls = self
+                    .unvisited_neighbors(cell)
+                    .into_iter()
+                    .map(|to| (cell, to));
+                self.frontier.extend(walls);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::{GenerationAlgorithm, MazeGenerator};
+
+    #[test]
+    fn perfect_mazes() {
+        let test_cases = [
+            ("backtracker", GenerationAlgorithm::Backtracker),
+            ("prim", GenerationAlgorithm::Prim),
+        ];
+        for (test_case, algorithm) in test_cases {
+            let mut generator = MazeGenerator::new(8, 5, algorithm, 7);
+            let mut steps = 0;
+            while generator.step() {
+                steps += 1;
+            }
+            assert_eq!(steps, 8 * 5 - 1, "{}", test_case);
+            assert!(generator.is_done(), "{}", test_case);
+            // Every cell is reached from the first one without a loop
+            let maze = generator.maze();
+            let mut seen = vec![vec![false; 8]; 5];
+            let mut queue = VecDeque::from([((0, 0), None)]);
+            let mut reached = 0;
+            while let Some((cell, from)) = queue.pop_front() {
+                let (x, y): (usize, usize) = cell;
+                assert!(!seen[y][x], "{} has a loop at {:?}", test_case, cell);
+                seen[y][x] = true;
+                reached += 1;
+                for next in maze.passages(cell) {
+                    if Some(next) != from {
+                        queue.push_back((next, Some(cell)));
+                    }
+                }
+            }
+            assert_eq!(reached, 8 * 5, "{}", test_case);
+            let mut again = MazeGenerator::new(8, 5, algorithm, 7);
+            while again.step() {}
+            assert_eq!(again.maze(), maze, "{} is the same for a seed", test_case);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+/// A cell of a maze as `(x, y)`, counted in cells and not in characters
+pub type MazeCell = (usize, usize);
+
+/// A rectangular maze of cells with walls between them
+///
+/// The maze is kept as a grid of characters where every cell and every wall takes one
+/// character, so that it can be drawn as it is. The cell `(x, y)` is at `(2x + 1, 2y + 1)` and
+/// the wall between two cells is the character between them. The border is always a wall
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::maze::maze::Maze;
+/// let mut maze = Maze::new(10, 5);
+/// maze.open((0, 0), (1, 0));
+/// assert_eq!(maze.passages((0, 0)), vec![(1, 0)]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Maze {
+    columns: usize,
+    rows: usize,
+    /// `true` for a wall, indexed as `walls[y][x]`
+    walls: Vec<Vec<bool>>,
+}
+impl Maze {
+    /// Create a maze where every cell is closed off
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - the number of cells in a row, at least one
+    /// * `rows` - the number of cells in a column, at least one
+    pub fn new(columns: usize, rows: usize) -> Self {
+        assert!(
+            columns > 0 && rows > 0,
+            "The maze needs at least one row and one column"
+        );
+        Maze {
+            columns,
+            rows,
+            walls: vec![vec![true; 2 * columns + 1]; 2 * rows + 1],
+        }
+    }
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    /// Get the grid of characters, `true` for a wall
+    pub fn walls(&self) -> &Vec<Vec<bool>> {
+        &self.walls
+    }
+    /// Get the character of a cell in the grid
+    pub fn position(cell: MazeCell) -> (usize, usize) {
+        (2 * cell.0 + 1, 2 * cell.1 + 1)
+    }
+    /// Get the character of the wall between two neighboring cells
+    pub fn wall_between(from: MazeCell, to: MazeCell) -> (usize, usize) {
+        (from.0 + to.0 + 1, from.1 + to.1 + 1)
+    }
+    /// Carve a passage between two neighboring cells, opening both cells and the wall between
+    /// them
+    pub fn open(&mut self, from: MazeCell, to: MazeCell) {
+        for (x, y) in [
+            Maze::position(from),
+            Maze::wall_between(from, to),
+            Maze::position(to),
+        ] {
+            self.walls[y][x] = false;
+        }
+    }
+    /// Get the cells next to a cell, above, left, right and below it, whether there is a wall
+    /// between them or not
+    pub fn neighbors(&self, cell: MazeCell) -> Vec<MazeCell> {
+        let (x, y) = cell;
+        let mut neighbors = vec![];
+        if y > 0 {
+            neighbors.push((x, y - 1));
+        }
+        if x > 0 {
+            neighbors.push((x - 1, y));
+        }
+        if x + 1 < self.columns {
+            neighbors.push((x + 1, y));
+        }
+        if y + 1 < self.rows {
+            neighbors.push((x, y + 1));
+        }
+        neighbors
+    }
+    /// Get the neighbors of a cell that can be reached from it without passing a wall
+    pub fn passages(&self, cell: MazeCell) -> Vec<MazeCell> {
+        self.neighbors(cell)
+            .into_iter()
+            .filter(|neighbor| {
+                let (x, y) = Maze::wall_between(cell, *neighbor);
+                !self.walls[y][x]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Maze;
+
+    #[test]
+    fn passages() {
+        let mut maze = Maze::new(3, 2);
+        assert_eq!(maze.walls().len(), 5);
+        assert_eq!(maze.walls()[0].len(), 7);
+        maze.open((0, 0), (1, 0));
+        maze.open((1, 0), (1, 1));
+        let test_cases = [
+            ("corner", (0, 0), vec![(1, 0)]),
+            ("junction", (1, 0), vec![(0, 0), (1, 1)]),
+            ("dead end", (1, 1), vec![(1, 0)]),
+            ("closed", (2, 1), vec![]),
+        ];
+        for (test_case, cell, expected) in test_cases {
+            assert_eq!(maze.passages(cell), expected, "{}", test_case);
+        }
+        assert_eq!(maze.neighbors((2, 1)), vec![(2, 0), (1, 1)]);
+        let row: String = maze.walls()[1]
+            .iter()
+            .map(|is_wall| if *is_wall { '#' } else { '.' })
+            .collect();
+        assert_eq!(row, "#...###");
+    }
+}
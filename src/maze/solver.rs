@@ -0,0 +1,236 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Display};
+
+use super::maze::{Maze, MazeCell};
+
+/// The ways a path through a maze can be searched
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchAlgorithm {
+    /// Explores the cells in the order of their distance from the start
+    BreadthFirst,
+    /// Explores the cells that look closest to the goal first, by their distance from the start
+    /// plus their Manhattan distance to the goal
+    AStar,
+}
+impl Display for SearchAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchAlgorithm::BreadthFirst => write!(f, "BFS"),
+            SearchAlgorithm::AStar => write!(f, "A*"),
+        }
+    }
+}
+
+/// Searches the shortest path between two cells of a maze, a cell at a time so that the search
+/// can be animated
+///
+/// Both algorithms keep their frontier in one priority queue. Breadth first search orders it
+/// by the distance from the start, A* adds the distance to the goal. Ties go to the cell that
+/// was found first
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::maze::{generator::{GenerationAlgorithm, MazeGenerator}, maze::Maze, solver::{MazeSolver, SearchAlgorithm}};
+/// # fn draw(_: &Maze, _: &MazeSolver) {}
+/// # let mut generator = MazeGenerator::new(20, 10, GenerationAlgorithm::Prim, 42);
+/// # while generator.step() {}
+/// # let maze = generator.maze().clone();
+/// let mut solver = MazeSolver::new(&maze, (0, 0), (19, 9), SearchAlgorithm::AStar);
+/// while solver.step(&maze) {
+///     draw(&maze, &solver);
+/// }
+/// println!("{:?}", solver.path());
+/// ```
+#[derive(Clone, Debug)]
+pub struct MazeSolver {
+    algorithm: SearchAlgorithm,
+    goal: MazeCell,
+    /// The cells to explore by `(priority, distance to the goal, order found, cell)`
+    frontier: BinaryHeap<Reverse<(usize, usize, usize, MazeCell)>>,
+    found: usize,
+    distances: Vec<Vec<Option<usize>>>,
+    parents: Vec<Vec<Option<MazeCell>>>,
+    explored: Vec<Vec<bool>>,
+    explored_count: usize,
+    path: Option<Vec<MazeCell>>,
+}
+impl MazeSolver {
+    /// Start a search
+    ///
+    /// # Arguments
+    ///
+    /// * `maze` - the maze that is searched
+    /// * `start` - the cell the path starts at
+    /// * `goal` - the cell the path ends at
+    /// * `algorithm` - the order the cells are explored in
+    pub fn new(maze: &Maze, start: MazeCell, goal: MazeCell, algorithm: SearchAlgorithm) -> Self {
+        let (columns, rows) = (maze.columns(), maze.rows());
+        let mut solver = MazeSolver {
+            algorithm,
+            goal,
+            frontier: BinaryHeap::new(),
+            found: 0,
+            distances: vec![vec![None; columns]; rows],
+            parents: vec![vec![None; columns]; rows],
+            explored: vec![vec![false; columns]; rows],
+            explored_count: 0,
+            path: None,
+        };
+        solver.distances[start.1][start.0] = Some(0);
+        solver.push(start, 0);
+        solver
+    }
+    pub fn algorithm(&self) -> SearchAlgorithm {
+        self.algorithm
+    }
+    /// Check if a cell was explored
+    pub fn is_explored(&self, cell: MazeCell) -> bool {
+        self.explored[cell.1][cell.0]
+    }
+    /// Get the number of cells that were explored
+    pub fn explored_count(&self) -> usize {
+        self.explored_count
+    }
+    /// Get the path from the start to the goal, once the goal was reached
+    pub fn path(&self) -> Option<&[MazeCell]> {
+        self.path.as_deref()
+    }
+    /// Check if the search is over, either with a path or because there is none
+    pub fn is_done(&self) -> bool {
+        self.path.is_some() || self.frontier.is_empty()
+    }
+    /// Explore the next cell
+    ///
+    /// # Arguments
+    ///
+    /// * `maze` - the maze that is searched, the same one the search started in
+    ///
+    /// # Returns
+    ///
+    /// `true` if a cell was explored, `false` once the search is over
+    pub fn step(&mut self, maze: &Maze) -> bool {
+        if self.path.is_some() {
+            return false;
+        }
+        let cell = loop {
+            let Some(Reverse((_, _, _, cell))) = self.frontier.pop() else {
+                return false;
+            };
+            if !self.is_explored(cell) {
+                break cell;
+            }
+        };
+        self.explored[cell.1][cell.0] = true;
+        self.explored_count += 1;
+        if cell == self.goal {
+            self.path = Some(self.trace(cell));
+            return true;
+        }
+        let distance = self.distances[cell.1][cell.0].unwrap_or_default() + 1;
+        for next in maze.passages(cell) {
+            let (x, y) = next;
+            if self.distances[y][x].is_some_and(|known| known <= distance) {
+                continue;
+            }
+            self.distances[y][x] = Some(distance);
+            self.parents[y][x] = Some(cell);
+            self.push(next, distance);
+        }
+        true
+    }
+    fn push(&mut self, cell: MazeCell, distance: usize) {
+        let (priority, remaining) = match self.algorithm {
+            SearchAlgorithm::BreadthFirst => (distance, 0),
+            SearchAlgorithm::AStar => {
+                let remaining = cell.0.abs_diff(self.goal.0) + cell.1.abs_diff(self.goal.1);
+                (distance + remaining, remaining)
+            }
+        };
+        self.frontier
+            .push(Reverse((priority, remaining, self.found, cell)));
+        self.found += 1;
+    }
+    /// Follow the parents back from a cell to the start
+    fn trace(&self, mut cell: MazeCell) -> Vec<MazeCell> {
+        let mut path = vec![cell];
+        while let Some(parent) = self.parents[cell.1][cell.0] {
+            path.push(parent);
+            cell = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::{
+        generator::{GenerationAlgorithm, MazeGenerator},
+        maze::Maze,
+    };
+
+    use super::{MazeSolver, SearchAlgorithm};
+
+    #[test]
+    fn shortest_path() {
+        // A corridor along the top and the right that ends in a dead end, and one along the
+        // left and the bottom that leads to the goal
+        // . . . .
+        // .     .
+        // . . . .
+        let mut maze = Maze::new(4, 3);
+        for (from, to) in [
+            ((0, 0), (1, 0)),
+            ((1, 0), (2, 0)),
+            ((2, 0), (3, 0)),
+            ((3, 0), (3, 1)),
+            ((3, 1), (3, 2)),
+            ((0, 0), (0, 1)),
+            ((0, 1), (0, 2)),
+            ((0, 2), (1, 2)),
+            ((1, 2), (2, 2)),
+        ] {
+            maze.open(from, to);
+        }
+        let test_cases = [
+            ("bfs", SearchAlgorithm::BreadthFirst, 9),
+            ("a star", SearchAlgorithm::AStar, 7),
+        ];
+        for (test_case, algorithm, explored) in test_cases {
+            let mut solver = MazeSolver::new(&maze, (0, 0), (2, 2), algorithm);
+            while solver.step(&maze) {}
+            assert!(solver.is_done(), "{}", test_case);
+            assert_eq!(
+                solver.path(),
+                Some(&[(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)][..]),
+                "{}",
+                test_case
+            );
+            assert_eq!(solver.explored_count(), explored, "{}", test_case);
+        }
+        let mut unreachable = MazeSolver::new(&maze, (0, 0), (1, 1), SearchAlgorithm::AStar);
+        while unreachable.step(&maze) {}
+        assert!(unreachable.is_done());
+        assert_eq!(unreachable.path(), None);
+    }
+
+    #[test]
+    fn a_star_explores_less() {
+        let mut generator = MazeGenerator::new(30, 15, GenerationAlgorithm::Prim, 3);
+        while generator.step() {}
+        let maze = generator.maze();
+        let explored: Vec<(usize, usize)> = [SearchAlgorithm::BreadthFirst, SearchAlgorithm::AStar]
+            .into_iter()
+            .map(|algorithm| {
+                let mut solver = MazeSolver::new(maze, (0, 0), (29, 14), algorithm);
+                while solver.step(maze) {}
+                (
+                    solver.explored_count(),
+                    solver.path().map_or(0, |path| path.len()),
+                )
+            })
+            .collect();
+        assert_eq!(explored[0].1, explored[1].1, "Both find the shortest path");
+        assert!(explored[1].0 <= explored[0].0, "{:?}", explored);
+    }
+}
@@ -0,0 +1,215 @@
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::conway::save::SavedGame;
+
+/// The version of the protocol, both sides have to use the same version
+pub const PROTOCOL_VERSION: u32 = 1;
+/// The largest message that is accepted, anything longer is treated as a broken connection
+pub const MAX_MESSAGE_LEN: usize = 64 * 1024;
+/// The largest frame that is sent to a browser, which fits a terminal of 400 by 150 characters
+pub const MAX_FRAME_MESSAGE_LEN: usize = 1024 * 1024;
+/// The largest board that is accepted. A board is sent with a byte per cell, so it gets a limit
+/// of its own that fits a board of 4000 by 4000 cells
+pub const MAX_BOARD_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum NetError {
+    ConnectFailed,
+    Disconnected,
+    WriteFailed,
+    BadMessage,
+    MessageTooLong,
+    VersionMismatch,
+    InvalidMove,
+}
+impl std::fmt::Display for NetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ConnectFailed => write!(f, "Could not connect to the other player"),
+            Self::Disconnected => write!(f, "The other player disconnected"),
+            Self::WriteFailed => write!(f, "Could not send to the other player"),
+            Self::BadMessage => write!(f, "The other player sent a message that is not understood"),
+            Self::MessageTooLong => write!(f, "The other player sent a message that is too long"),
+            Self::VersionMismatch => write!(f, "The other player uses a different version"),
+            Self::InvalidMove => write!(f, "The other player made a move that is not allowed"),
+        }
+    }
+}
+
+/// The messages that are sent between two instances of the game
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// The first message of both sides after connecting
+    Hello { version: u32 },
+    /// Place the mark of the sender in a cell
+    Move { index: usize },
+    /// The sender leaves the game
+    Bye,
+    /// The latest generation of a Conway board, sent to spectators
+    Board(SavedGame),
+    /// A drawn frame for a browser, the characters of every row with the colors of the pixels
+    /// as a string per row. Every pixel takes two hexadecimal digits for the index of its color
+    /// in the 256 color palette, or `--` for the color of the browser
+    Frame {
+        rows: Vec<String>,
+        background: Vec<String>,
+        foreground: Vec<String>,
+    },
+    /// A key that was pressed in a browser, named the way `Key` names keys, like `ctrl+s`
+    Key { key: String },
+}
+
+impl Message {
+    /// Get the longest the message may be, boards and frames grow with the size of the board
+    /// and the terminal
+    pub fn max_len(&self) -> usize {
+        match self {
+            Message::Board(_) => MAX_BOARD_MESSAGE_LEN,
+            Message::Frame { .. } => MAX_FRAME_MESSAGE_LEN,
+            _ => MAX_MESSAGE_LEN,
+        }
+    }
+}
+
+/// Encode a message as a 4 byte big endian length followed by the message as JSON, so that a
+/// message that goes to several connections is only encoded once
+///
+/// # Returns
+///
+/// The bytes to write, or `NetError::MessageTooLong` if the message is longer than its limit
+pub fn encode_message(message: &Message) -> Result<Vec<u8>, NetError> {
+    let json = serde_json::to_vec(message).map_err(|_| NetError::BadMessage)?;
+    if json.len() > message.max_len() {
+        return Err(NetError::MessageTooLong);
+    }
+    let mut bytes = Vec::with_capacity(json.len() + 4);
+    bytes.extend_from_slice(&(json.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(&json);
+    Ok(bytes)
+}
+
+/// Write a message as a 4 byte big endian length followed by the message as JSON
+///
+/// # Examples
+///
+/// ```
+/// # use tic_tac_toe2::net::protocol::{write_message, Message, NetError};
+/// # fn main() -> Result<(), NetError> {
+/// # let mut stream = Vec::new();
+/// write_message(&mut stream, &Message::Move { index: 4 })?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_message(writer: &mut dyn Write, message: &Message) -> Result<(), NetError> {
+    let bytes = encode_message(message)?;
+    writer
+        .write_all(&bytes)
+        .and_then(|_| writer.flush())
+        .map_err(|_| NetError::WriteFailed)
+}
+
+/// Read a message that was written with `write_message`, blocking until it is complete
+///
+/// # Returns
+///
+/// `NetError::Disconnected` if the connection closes before a whole message is read
+pub fn read_message(reader: &mut dyn Read) -> Result<Message, NetError> {
+    read_message_up_to(reader, MAX_MESSAGE_LEN)
+}
+
+/// Read a message like `read_message` on a connection that takes longer messages, like the
+/// boards a spectator receives
+///
+/// # Arguments
+///
+/// * `reader` - the connection
+/// * `max_len` - the longest message that is accepted, e.g. `MAX_BOARD_MESSAGE_LEN`
+pub fn read_message_up_to(reader: &mut dyn Read, max_len: usize) -> Result<Message, NetError> {
+    let mut len = [0u8; 4];
+    reader
+        .read_exact(&mut len)
+        .map_err(|_| NetError::Disconnected)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > max_len {
+        return Err(NetError::MessageTooLong);
+    }
+    let mut json = vec![0u8; len];
+    reader
+        .read_exact(&mut json)
+        .map_err(|_| NetError::Disconnected)?;
+    serde_json::from_slice(&json).map_err(|_| NetError::BadMessage)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conway::{rule::Rule, save::SavedGame};
+
+    use super::{
+        read_message, read_message_up_to, write_message, Message, NetError, MAX_BOARD_MESSAGE_LEN,
+        MAX_MESSAGE_LEN,
+    };
+
+    #[test]
+    fn round_trip() {
+        let messages = vec![
+            Message::Hello { version: 1 },
+            Message::Move { index: 4 },
+            Message::Bye,
+        ];
+        let mut buffer = Vec::new();
+        for message in messages.iter() {
+            write_message(&mut buffer, message).unwrap();
+        }
+        assert_eq!(&buffer[..4], &[0, 0, 0, 28]);
+        assert_eq!(&buffer[4..32], br#"{"type":"hello","version":1}"#);
+
+        let mut reader = buffer.as_slice();
+        for message in messages {
+            assert_eq!(read_message(&mut reader), Ok(message));
+        }
+        assert_eq!(read_message(&mut reader), Err(NetError::Disconnected));
+    }
+
+    #[test]
+    fn bad_messages() {
+        let mut truncated: &[u8] = &[0, 0, 0, 10, b'{'];
+        assert_eq!(read_message(&mut truncated), Err(NetError::Disconnected));
+
+        let mut unknown: &[u8] = b"\x00\x00\x00\x02{}";
+        assert_eq!(read_message(&mut unknown), Err(NetError::BadMessage));
+
+        let too_long = (MAX_MESSAGE_LEN as u32 + 1).to_be_bytes();
+        assert_eq!(
+            read_message(&mut too_long.as_slice()),
+            Err(NetError::MessageTooLong)
+        );
+    }
+
+    #[test]
+    fn large_boards() {
+        let cells = vec![vec![true; 400]; 400];
+        let board = Message::Board(SavedGame::new(&cells, 1, Rule::conway(), 0));
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &board).unwrap();
+        assert!(buffer.len() > MAX_MESSAGE_LEN);
+        assert_eq!(
+            read_message(&mut buffer.as_slice()),
+            Err(NetError::MessageTooLong),
+            "Other messages keep their limit"
+        );
+        assert_eq!(
+            read_message_up_to(&mut buffer.as_slice(), MAX_BOARD_MESSAGE_LEN),
+            Ok(board)
+        );
+
+        let cells = vec![vec![false; 5000]; 5000];
+        let too_large = Message::Board(SavedGame::new(&cells, 1, Rule::conway(), 0));
+        assert_eq!(
+            write_message(&mut Vec::new(), &too_large),
+            Err(NetError::MessageTooLong)
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use std::{
+    io::{BufRead, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use crate::tic_tac_toe::board::{Board, Mark, Outcome};
+
+use super::protocol::{read_message, write_message, Message, NetError, PROTOCOL_VERSION};
+
+/// The port that is used when hosting without an address
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// What happened on the other side of the connection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetEvent {
+    /// The other player placed their mark
+    Moved { index: usize, outcome: Outcome },
+    /// The other player left the game
+    Left,
+}
+
+/// A game of tic-tac-toe between two instances of the game over TCP
+///
+/// The host plays X and starts, the player that connects plays O. Both sides keep their own
+/// board and validate every move, their own before it is sent and the other player's when it is
+/// received. A move that is not allowed ends the session
+///
+/// Losing the connection is reported as `NetError::Disconnected`, so that it can be shown to the
+/// player instead of ending the game silently
+///
+/// # Example
+///
+/// ```no_run
+/// # use tic_tac_toe2::net::{protocol::NetError, session::{NetEvent, NetSession, DEFAULT_PORT}};
+/// # fn main() -> Result<(), NetError> {
+/// let mut session = NetSession::host(("0.0.0.0", DEFAULT_PORT))?;
+/// session.play(4)?;
+/// match session.receive() {
+///     Ok(NetEvent::Moved { index, outcome }) => (),
+///     Ok(NetEvent::Left) | Err(NetError::Disconnected) => (),
+///     Err(e) => (),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct NetSession {
+    stream: TcpStream,
+    board: Board,
+    mark: Mark,
+}
+impl NetSession {
+    /// Wait for another player to connect and play X
+    pub fn host(address: impl ToSocketAddrs) -> Result<Self, NetError> {
+        let listener = TcpListener::bind(address).map_err(|_| NetError::ConnectFailed)?;
+        Self::accept(&listener)
+    }
+    /// Wait for another player to connect to a listener that is already bound, and play X
+    pub fn accept(listener: &TcpListener) -> Result<Self, NetError> {
+        let (stream, _) = listener.accept().map_err(|_| NetError::ConnectFailed)?;
+        Self::start(stream, Mark::X)
+    }
+    /// Connect to a player that is hosting and play O
+    pub fn connect(address: impl ToSocketAddrs) -> Result<Self, NetError> {
+        let stream = TcpStream::connect(address).map_err(|_| NetError::ConnectFailed)?;
+        Self::start(stream, Mark::O)
+    }
+    fn start(mut stream: TcpStream, mark: Mark) -> Result<Self, NetError> {
+        let _ = stream.set_nodelay(true);
+        write_message(
+            &mut stream,
+            &Message::Hello {
+                version: PROTOCOL_VERSION,
+            },
+        )?;
+        match read_message(&mut stream)? {
+            Message::Hello { version } if version == PROTOCOL_VERSION => (),
+            Message::Hello { .. } => return Err(NetError::VersionMismatch),
+            _ => return Err(NetError::BadMessage),
+        }
+        Ok(NetSession {
+            stream,
+            board: Board::default(),
+            mark,
+        })
+    }
+    /// The mark of this side
+    pub fn mark(&self) -> Mark {
+        self.mark
+    }
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+    /// Check if this side plays next
+    pub fn is_my_turn(&self) -> bool {
+        self.board.outcome() == Outcome::InProgress && self.board.turn() == self.mark
+    }
+    /// Place the mark of this side and send the move to the other player
+    ///
+    /// # Returns
+    ///
+    /// The state of the game after the move. Moves that are not allowed are not sent
+    pub fn play(&mut self, index: usize) -> Result<Outcome, NetError> {
+        let outcome = self
+            .board
+            .play(self.mark, index)
+            .map_err(|_| NetError::InvalidMove)?;
+        write_message(&mut self.stream, &Message::Move { index })?;
+        Ok(outcome)
+    }
+    /// Wait for the next message of the other player and apply it to the board
+    ///
+    /// # Returns
+    ///
+    /// `NetError::InvalidMove` if the other player made a move that is not allowed, the session
+    /// is closed in that case
+    pub fn receive(&mut self) -> Result<NetEvent, NetError> {
+        match read_message(&mut self.stream)? {
+            Message::Move { index } => match self.board.play(self.mark.other(), index) {
+                Ok(outcome) => Ok(NetEvent::Moved { index, outcome }),
+                Err(_) => {
+                    self.leave();
+                    Err(NetError::InvalidMove)
+                }
+            },
+            Message::Bye => Ok(NetEvent::Left),
+            Message::Hello { .. }
+            | Message::Board(_)
+            | Message::Frame { .. }
+            | Message::Key { .. } => Err(NetError::BadMessage),
+        }
+    }
+    /// Tell the other player that this side leaves the game
+    pub fn leave(&mut self) {
+        let _ = write_message(&mut self.stream, &Message::Bye);
+        let _ = self.stream.shutdown(std::net::Shutdown::Both);
+    }
+    /// Play the session in a terminal until it is over. The board is written before every move
+    /// and the moves of this side are read as the number of a cell, 1 to 9 from the top left
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - the moves of the player are read from here, `q` leaves the game
+    /// * `output` - the board and the messages for the player are written here
+    ///
+    /// # Returns
+    ///
+    /// The outcome of the game, `Outcome::InProgress` if one of the players left
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// # use tic_tac_toe2::net::{protocol::NetError, session::{NetSession, DEFAULT_PORT}};
+    /// # fn main() -> Result<(), NetError> {
+    /// # let address = ("127.0.0.1", DEFAULT_PORT);
+    /// let mut session = NetSession::connect(address)?;
+    /// session.play_in_terminal(&mut io::stdin().lock(), &mut io::stdout())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn play_in_terminal(
+        &mut self,
+        input: &mut dyn BufRead,
+        output: &mut dyn Write,
+    ) -> Result<Outcome, NetError> {
+        let _ = writeln!(output, "You play {}", self.mark);
+        loop {
+            let _ = write!(output, "{}", board_text(&self.board));
+            match self.board.outcome() {
+                Outcome::InProgress => (),
+                Outcome::Won(mark) => {
+                    let _ = writeln!(output, "{} won", mark);
+                    return Ok(self.board.outcome());
+                }
+                Outcome::Draw => {
+                    let _ = writeln!(output, "It is a draw");
+                    return Ok(Outcome::Draw);
+                }
+            }
+            if !self.is_my_turn() {
+                let _ = writeln!(output, "Waiting for {}", self.mark.other());
+                let _ = output.flush();
+                if self.receive()? == NetEvent::Left {
+                    let _ = writeln!(output, "The other player left the game");
+                    return Ok(Outcome::InProgress);
+                }
+                continue;
+            }
+            let _ = write!(output, "Your move (1-9, q to leave): ");
+            let _ = output.flush();
+            let mut line = String::new();
+            let line = match input.read_line(&mut line) {
+                Ok(0) | Err(_) => "q",
+                Ok(_) => line.trim(),
+            };
+            if line == "q" {
+                self.leave();
+                return Ok(Outcome::InProgress);
+            }
+            let index = line
+                .parse::<usize>()
+                .ok()
+                .and_then(|cell| cell.checked_sub(1));
+            match index.map(|index| self.play(index)) {
+                Some(Ok(_)) => (),
+                Some(Err(NetError::InvalidMove)) | None => {
+                    let _ = writeln!(output, "`{}` is not a free cell", line);
+                }
+                Some(Err(e)) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Write a board with the marks and the numbers of the free cells
+fn board_text(board: &Board) -> String {
+    let cell = |index: usize| match board.cell(index) {
+        Some(mark) => mark.to_string(),
+        None => (index + 1).to_string(),
+    };
+    let rows: Vec<String> = (0..3)
+        .map(|row| {
+            format!(
+                " {} | {} | {}\n",
+                cell(row * 3),
+                cell(row * 3 + 1),
+                cell(row * 3 + 2)
+            )
+        })
+        .collect();
+    rows.join("---+---+---\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Cursor, Write},
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    use crate::{
+        net::protocol::{read_message, write_message, Message, NetError, PROTOCOL_VERSION},
+        tic_tac_toe::board::{Mark, Outcome},
+    };
+
+    use super::{board_text, NetEvent, NetSession};
+
+    fn listener() -> TcpListener {
+        TcpListener::bind("127.0.0.1:0").expect("A local port should be free")
+    }
+
+    #[test]
+    fn play_a_game() {
+        let listener = listener();
+        let address = listener.local_addr().unwrap();
+        let guest = thread::spawn(move || {
+            let mut guest = NetSession::connect(address).unwrap();
+            assert_eq!(guest.mark(), Mark::O);
+            assert_eq!(
+                guest.receive(),
+                Ok(NetEvent::Moved {
+                    index: 0,
+                    outcome: Outcome::InProgress
+                })
+            );
+            assert_eq!(
+                guest.play(0),
+                Err(NetError::InvalidMove),
+                "The cell is taken"
+            );
+            guest.play(3).unwrap();
+            guest.receive().unwrap();
+            guest.play(4).unwrap();
+            guest.receive()
+        });
+
+        let mut host = NetSession::accept(&listener).unwrap();
+        assert_eq!(host.mark(), Mark::X);
+        assert!(host.is_my_turn());
+        host.play(0).unwrap();
+        assert!(!host.is_my_turn());
+        host.receive().unwrap();
+        host.play(1).unwrap();
+        host.receive().unwrap();
+        assert_eq!(host.play(2), Ok(Outcome::Won(Mark::X)));
+
+        assert_eq!(
+            guest.join().unwrap(),
+            Ok(NetEvent::Moved {
+                index: 2,
+                outcome: Outcome::Won(Mark::X)
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_move_from_the_other_side() {
+        let listener = listener();
+        let address = listener.local_addr().unwrap();
+        let cheater = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write_message(
+                &mut stream,
+                &Message::Hello {
+                    version: PROTOCOL_VERSION,
+                },
+            )
+            .unwrap();
+            read_message(&mut stream).unwrap();
+            // O moves before X
+            write_message(&mut stream, &Message::Move { index: 4 }).unwrap();
+            read_message(&mut stream)
+        });
+
+        let mut host = NetSession::accept(&listener).unwrap();
+        assert_eq!(host.receive(), Err(NetError::InvalidMove));
+        assert_eq!(
+            cheater.join().unwrap(),
+            Ok(Message::Bye),
+            "The other side should be told that the session ended"
+        );
+    }
+
+    #[test]
+    fn disconnect() {
+        let listener = listener();
+        let address = listener.local_addr().unwrap();
+        let guest = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write_message(
+                &mut stream,
+                &Message::Hello {
+                    version: PROTOCOL_VERSION,
+                },
+            )
+            .unwrap();
+            read_message(&mut stream).unwrap();
+            // Half a message, then the connection is dropped
+            stream.write_all(&[0, 0]).unwrap();
+        });
+
+        let mut host = NetSession::accept(&listener).unwrap();
+        guest.join().unwrap();
+        assert_eq!(host.receive(), Err(NetError::Disconnected));
+    }
+
+    #[test]
+    fn version_mismatch() {
+        let listener = listener();
+        let address = listener.local_addr().unwrap();
+        let guest = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write_message(&mut stream, &Message::Hello { version: 0 }).unwrap();
+            read_message(&mut stream)
+        });
+        assert_eq!(
+            NetSession::accept(&listener).err(),
+            Some(NetError::VersionMismatch)
+        );
+        guest.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn play_in_terminal() {
+        let test_cases = [
+            (
+                "won",
+                "x\n1\n2\n3\n",
+                "4\n1\n5\n",
+                Outcome::Won(Mark::X),
+                "X won",
+            ),
+            (
+                "the other player left",
+                "5\n",
+                "q\n",
+                Outcome::InProgress,
+                "The other player left the game",
+            ),
+        ];
+        for (test_case, host_moves, guest_moves, expected, message) in test_cases {
+            let listener = listener();
+            let address = listener.local_addr().unwrap();
+            let guest = thread::spawn(move || {
+                let mut guest = NetSession::connect(address).unwrap();
+                let mut output = Vec::new();
+                let outcome = guest.play_in_terminal(&mut Cursor::new(guest_moves), &mut output);
+                (outcome, String::from_utf8(output).unwrap())
+            });
+            let mut host = NetSession::accept(&listener).unwrap();
+            let mut output = Vec::new();
+            let outcome = host.play_in_terminal(&mut Cursor::new(host_moves), &mut output);
+            let output = String::from_utf8(output).unwrap();
+            let (guest_outcome, guest_output) = guest.join().unwrap();
+
+            assert_eq!(outcome, Ok(expected), "{}", test_case);
+            assert!(output.contains(message), "{}: {}", test_case, output);
+            assert_eq!(guest_outcome, Ok(expected), "{}", test_case);
+            assert!(guest_output.starts_with("You play O"), "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn board_text_marks_and_free_cells() {
+        let mut board = crate::tic_tac_toe::board::Board::default();
+        board.play(Mark::X, 4).unwrap();
+        board.play(Mark::O, 0).unwrap();
+        assert_eq!(
+            board_text(&board),
+            " O | 2 | 3\n---+---+---\n 4 | X | 6\n---+---+---\n 7 | 8 | 9\n"
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    io::{ErrorKind, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{sync_channel, SyncSender, TrySendError},
+        Arc,
+    },
+    thread,
+};
+
+use crate::{
+    conway::save::SavedGame,
+    panel::frame_channel::FrameSender,
+    rendering::{
+        colors::TerminalColors,
+        render_object::{RenderObject, RenderObjects},
+        sprite::Sprite,
+    },
+    shared::usize2d::Coord,
+};
+
+use super::protocol::{
+    encode_message, read_message, read_message_up_to, write_message, Message, NetError,
+    MAX_BOARD_MESSAGE_LEN, PROTOCOL_VERSION,
+};
+
+/// The port spectators connect to unless another one is given
+pub const DEFAULT_SPECTATOR_PORT: u16 = 7879;
+/// The number of generations that wait to be written to a spectator. A spectator that falls
+/// further behind is dropped
+const SPECTATOR_QUEUE_LEN: usize = 32;
+
+/// A connected spectator, written to by a thread of its own so that a slow connection does not
+/// hold up the simulation
+struct Spectator {
+    queue: SyncSender<Arc<Vec<u8>>>,
+    /// A second handle to the connection, to close it when the spectator is dropped
+    stream: TcpStream,
+}
+impl Spectator {
+    /// Start the thread that writes the queued messages to a connection
+    fn spawn(stream: TcpStream) -> Option<Self> {
+        let mut writer = stream.try_clone().ok()?;
+        let (queue, messages) = sync_channel::<Arc<Vec<u8>>>(SPECTATOR_QUEUE_LEN);
+        thread::spawn(move || {
+            for message in messages {
+                if writer
+                    .write_all(&message)
+                    .and_then(|_| writer.flush())
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        });
+        Some(Spectator { queue, stream })
+    }
+}
+impl Drop for Spectator {
+    fn drop(&mut self) {
+        // Wakes the writer if it is stuck on a spectator that stopped reading
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// Sends the generations of a running simulation to read-only spectators
+///
+/// Spectators can join at any time, they receive every generation that is broadcast after they
+/// joined. Every spectator is written to from a thread of its own, a broadcast only queues the
+/// generation. Spectators that can not keep up or disconnect are dropped
+///
+/// # Example
+///
+/// ```no_run
+/// # use tic_tac_toe2::{net::{protocol::NetError, spectator::{SpectatorServer, DEFAULT_SPECTATOR_PORT}}, ConwaysGame};
+/// # fn main() -> Result<(), NetError> {
+/// # let mut game = ConwaysGame::headless(16, 16, 42);
+/// let mut server = SpectatorServer::bind(("0.0.0.0", DEFAULT_SPECTATOR_PORT))?;
+/// loop {
+///     game.step();
+///     server.broadcast(&game.save());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct SpectatorServer {
+    listener: TcpListener,
+    spectators: Vec<Spectator>,
+}
+impl SpectatorServer {
+    /// Listen for spectators without blocking the simulation
+    pub fn bind(address: impl ToSocketAddrs) -> Result<Self, NetError> {
+        let listener = TcpListener::bind(address).map_err(|_| NetError::ConnectFailed)?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|_| NetError::ConnectFailed)?;
+        Ok(SpectatorServer {
+            listener,
+            spectators: Vec::new(),
+        })
+    }
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr().ok()
+    }
+    /// The number of spectators that are connected
+    pub fn spectator_count(&self) -> usize {
+        self.spectators.len()
+    }
+    /// Greet the spectators that are waiting to join
+    pub fn accept_pending(&mut self) {
+        loop {
+            match self.listener.accept() {
+                Ok((mut stream, _)) => {
+                    // Some platforms hand out connections that do not block like the listener
+                    let _ = stream.set_nonblocking(false);
+                    let _ = stream.set_nodelay(true);
+                    let hello = Message::Hello {
+                        version: PROTOCOL_VERSION,
+                    };
+                    if write_message(&mut stream, &hello).is_err() {
+                        continue;
+                    }
+                    if let Some(spectator) = Spectator::spawn(stream) {
+                        self.spectators.push(spectator);
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(_) => return,
+            }
+        }
+    }
+    /// Queue a generation for all spectators, after letting new spectators join. The board is
+    /// encoded once and written by the threads of the spectators
+    ///
+    /// # Returns
+    ///
+    /// The number of spectators the generation was queued for
+    pub fn broadcast(&mut self, board: &SavedGame) -> usize {
+        self.accept_pending();
+        let Ok(message) = encode_message(&Message::Board(board.clone())) else {
+            return 0;
+        };
+        let message = Arc::new(message);
+        self.spectators.retain(
+            |spectator| match spectator.queue.try_send(message.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => false,
+            },
+        );
+        self.spectators.len()
+    }
+}
+
+/// Watches a simulation that is broadcast by a `SpectatorServer`
+pub struct SpectatorClient {
+    stream: TcpStream,
+}
+impl SpectatorClient {
+    pub fn connect(address: impl ToSocketAddrs) -> Result<Self, NetError> {
+        let mut stream = TcpStream::connect(address).map_err(|_| NetError::ConnectFailed)?;
+        match read_message(&mut stream)? {
+            Message::Hello { version } if version == PROTOCOL_VERSION => (),
+            Message::Hello { .. } => return Err(NetError::VersionMismatch),
+            _ => return Err(NetError::BadMessage),
+        }
+        Ok(SpectatorClient { stream })
+    }
+    /// Wait for the next generation
+    pub fn receive(&mut self) -> Result<SavedGame, NetError> {
+        match read_message_up_to(&mut self.stream, MAX_BOARD_MESSAGE_LEN)? {
+            Message::Board(board) => Ok(board),
+            Message::Bye => Err(NetError::Disconnected),
+            _ => Err(NetError::BadMessage),
+        }
+    }
+    /// Send every generation to a panel until the simulation stops or the panel is gone
+    ///
+    /// # Arguments
+    ///
+    /// * `frame_sender` - sends the frames to the panel the board is drawn in
+    /// * `location` - the top left corner of the board in the panel
+    ///
+    /// # Returns
+    ///
+    /// Why the spectator stopped, `NetError::Disconnected` once the simulation is gone
+    pub fn watch(
+        &mut self,
+        frame_sender: &FrameSender<RenderObjects>,
+        location: Coord,
+    ) -> NetError {
+        loop {
+            let board = match self.receive() {
+                Ok(board) => board,
+                Err(e) => return e,
+            };
+            let frame = board_render_object(&board, location).into_iter().collect();
+            if frame_sender.send(frame).is_err() {
+                return NetError::Disconnected;
+            }
+        }
+    }
+}
+
+/// Turn a generation into a render object with a character for every cell, `#` for live cells
+/// and `.` for dead cells
+///
+/// # Returns
+///
+/// `None` if the board has no cells
+pub fn board_render_object(board: &SavedGame, location: Coord) -> Option<RenderObject> {
+    let cells = board.grid().ok()?;
+    let chars: Vec<Vec<u8>> = cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|is_alive| if *is_alive { b'#' } else { b'.' })
+                .collect()
+        })
+        .collect();
+    let color = |is_alive: &bool| {
+        if *is_alive {
+            TerminalColors::LightGreen
+        } else {
+            TerminalColors::Red
+        }
+    };
+    let backgrounds = cells
+        .iter()
+        .map(|row| row.iter().map(color).collect())
+        .collect();
+    let foregrounds = vec![vec![TerminalColors::Black; cells[0].len()]; cells.len()];
+    let sprite = Sprite::new(
+        format!("round {}", board.rounds),
+        cells[0].len(),
+        cells.len(),
+        chars,
+        backgrounds,
+        foregrounds,
+    );
+    Some(RenderObject::new(sprite, location))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpStream, thread, time::Duration};
+
+    use crate::{
+        conway::{rule::Rule, save::SavedGame},
+        net::protocol::NetError,
+        panel::frame_channel::{frame_channel, FRAME_QUEUE_CAPACITY},
+        shared::{
+            square::Square,
+            usize2d::{Coord, Usize2d},
+        },
+    };
+
+    use super::{SpectatorClient, SpectatorServer};
+
+    fn board(rounds: u64) -> SavedGame {
+        SavedGame::new(
+            &[vec![true, false], vec![false, true]],
+            1,
+            Rule::conway(),
+            rounds,
+        )
+    }
+
+    #[test]
+    fn broadcast_to_spectators() {
+        let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        assert_eq!(server.broadcast(&board(0)), 0, "Nobody is watching yet");
+
+        let spectators: Vec<_> = (0..2)
+            .map(|_| {
+                thread::spawn(move || {
+                    let mut client = SpectatorClient::connect(address).unwrap();
+                    client.receive()
+                })
+            })
+            .collect();
+        while server.spectator_count() < 2 {
+            server.accept_pending();
+            thread::yield_now();
+        }
+        assert_eq!(server.broadcast(&board(1)), 2);
+        for spectator in spectators {
+            assert_eq!(spectator.join().unwrap(), Ok(board(1)));
+        }
+
+        // Writes to a closed connection only fail once the other side reset it
+        for rounds in 2..100 {
+            if server.broadcast(&board(rounds)) == 0 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(server.spectator_count(), 0, "Closed spectators are dropped");
+    }
+
+    #[test]
+    fn large_boards_and_slow_spectators() {
+        let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        let large = SavedGame::new(&vec![vec![true; 300]; 300], 1, Rule::conway(), 0);
+        let expected = large.clone();
+        let spectator = thread::spawn(move || {
+            let mut client = SpectatorClient::connect(address).unwrap();
+            client.receive()
+        });
+        while server.spectator_count() == 0 {
+            server.accept_pending();
+            thread::yield_now();
+        }
+        assert_eq!(server.broadcast(&large), 1);
+        assert_eq!(
+            spectator.join().unwrap(),
+            Ok(expected),
+            "A board of more than 64k cells"
+        );
+
+        // Connects but never reads, so its queue fills up once the connection is full. It joins
+        // with the first broadcast
+        let _stalled = TcpStream::connect(address).unwrap();
+        let mut broadcasts = 0;
+        while server.broadcast(&large) > 0 {
+            broadcasts += 1;
+            assert!(
+                broadcasts < 10_000,
+                "The stalled spectator should be dropped"
+            );
+        }
+    }
+
+    #[test]
+    fn watch_sends_frames_to_the_panel() {
+        let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        let (frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let spectator = thread::spawn(move || {
+            let mut client = SpectatorClient::connect(address).unwrap();
+            client.watch(&frame_sender, Coord::new(2, 3))
+        });
+        while server.spectator_count() == 0 {
+            server.accept_pending();
+            thread::yield_now();
+        }
+        server.broadcast(&board(4));
+        let frame = loop {
+            match frame_receiver.try_recv() {
+                Ok(frame) => break frame,
+                Err(_) => thread::yield_now(),
+            }
+        };
+        assert_eq!(frame.len(), 1);
+        assert_eq!(
+            frame[0].get_area().to_string(),
+            Square::new(Usize2d::new(2, 3), Usize2d::new(3, 4)).to_string(),
+            "The 2x2 board should be drawn at the location"
+        );
+
+        drop(server);
+        assert_eq!(spectator.join().unwrap(), NetError::Disconnected);
+    }
+}
@@ -0,0 +1,363 @@
+use std::{
+    io::ErrorKind,
+    net::{SocketAddr, TcpListener, ToSocketAddrs},
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+use crate::{
+    console::input_record::{Key, KeyEvent},
+    panel::{
+        errors::PanelError,
+        renderer::{FrameRenderer, Renderer},
+    },
+    rendering::{colors::TerminalColors, render_object::RenderObject},
+    shared::{
+        frame::{Frame, Pixel},
+        square::Square,
+    },
+};
+
+use super::{
+    protocol::{Message, NetError},
+    websocket::{read_request, write_page, Request, WebSocket},
+};
+
+/// The port the binary serves the page on with `--web`
+pub const DEFAULT_WEB_PORT: u16 = 7880;
+/// The page that shows the frames in a browser and sends the keys back
+const PAGE: &str = include_str!("web_bridge.html");
+
+/// Serves the frames of a simulation to browsers over a WebSocket and takes their keys back, so
+/// that a browser can show and control the simulation
+///
+/// Opening the address in a browser loads a page that connects to the WebSocket. Every finished
+/// frame is sent to the browsers as a `Message::Frame` in JSON, and the keys pressed in them come
+/// back as `Message::Key`, which are sent to the receiver that `bind` returns. Browsers that can
+/// not keep up or disconnect are dropped
+///
+/// # Example
+///
+/// ```no_run
+/// # use tic_tac_toe2::{net::{protocol::NetError, web_bridge::{WebBridge, DEFAULT_WEB_PORT}}, panel::renderer::render, rendering::render_object::RenderObject, shared::{square::Square, usize2d::Usize2d}, ConwaysGame};
+/// # fn main() -> Result<(), NetError> {
+/// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+/// # let objects: Vec<RenderObject> = Vec::new();
+/// let (mut bridge, keys) = WebBridge::bind(("0.0.0.0", DEFAULT_WEB_PORT), area)?;
+/// let mut game = ConwaysGame::builder(20, 10).receiver(keys).build().expect("the board is not empty");
+/// loop {
+///     bridge.poll_keys();
+///     render(&mut bridge, &objects).expect("the frame is drawn");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct WebBridge {
+    listener: TcpListener,
+    clients: Vec<WebSocket>,
+    renderer: FrameRenderer,
+    key_sender: Sender<KeyEvent>,
+}
+impl WebBridge {
+    /// Listen for browsers without blocking the simulation
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - the address the page and the WebSocket are served on
+    /// * `area` - the area of the screen that is sent to the browsers
+    ///
+    /// # Returns
+    ///
+    /// The bridge and the receiver of the keys pressed in the browsers
+    pub fn bind(
+        address: impl ToSocketAddrs,
+        area: Square,
+    ) -> Result<(Self, Receiver<KeyEvent>), NetError> {
+        let listener = TcpListener::bind(address).map_err(|_| NetError::ConnectFailed)?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|_| NetError::ConnectFailed)?;
+        let (key_sender, key_receiver) = channel();
+        let bridge = WebBridge {
+            listener,
+            clients: Vec::new(),
+            renderer: FrameRenderer::new(area, Pixel::default()),
+            key_sender,
+        };
+        Ok((bridge, key_receiver))
+    }
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr().ok()
+    }
+    /// The number of browsers that are connected
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+    /// Serve the page or open the WebSocket of the browsers that connected since the last call
+    ///
+    /// A browser that is slow to send its request holds up the caller for up to half a second
+    pub fn accept_pending(&mut self) {
+        loop {
+            let mut stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(_) => return,
+            };
+            // The connections of a nonblocking listener do not block on every platform
+            if stream.set_nonblocking(false).is_err() {
+                continue;
+            }
+            match read_request(&mut stream) {
+                Ok(Request::Upgrade(key)) => {
+                    if let Ok(client) = WebSocket::accept(stream, &key) {
+                        self.clients.push(client);
+                    }
+                }
+                Ok(Request::Page(_)) => {
+                    let _ = write_page(stream, "text/html; charset=utf-8", PAGE);
+                }
+                Err(_) => (),
+            }
+        }
+    }
+    /// Send the keys the browsers pressed since the last call to the receiver, after letting new
+    /// browsers connect. Keys with a name that is not known are skipped
+    ///
+    /// # Returns
+    ///
+    /// The number of keys that were sent
+    pub fn poll_keys(&mut self) -> usize {
+        self.accept_pending();
+        let mut keys = Vec::new();
+        self.clients
+            .retain_mut(|client| match client.receive_texts() {
+                Ok(texts) => {
+                    keys.extend(texts.iter().filter_map(|text| parse_key_message(text)));
+                    true
+                }
+                Err(_) => false,
+            });
+        keys.into_iter()
+            .take_while(|key| self.key_sender.send(KeyEvent::key_down(*key)).is_ok())
+            .count()
+    }
+}
+impl Renderer for WebBridge {
+    fn begin_frame(&mut self) -> Result<(), PanelError> {
+        self.renderer.begin_frame()
+    }
+    fn draw(&mut self, object: &RenderObject) -> Result<(), PanelError> {
+        self.renderer.draw(object)
+    }
+    /// Send the frame to the browsers, after letting new browsers connect
+    fn end_frame(&mut self) -> Result<(), PanelError> {
+        self.renderer.end_frame()?;
+        self.accept_pending();
+        if self.clients.is_empty() {
+            return Ok(());
+        }
+        let Some(frame) = self.renderer.frame() else {
+            return Ok(());
+        };
+        let message = frame_message(frame);
+        let Ok(json) = serde_json::to_string(&message) else {
+            return Ok(());
+        };
+        if json.len() > message.max_len() {
+            return Ok(());
+        }
+        self.clients
+            .retain_mut(|client| client.send_text(&json).is_ok());
+        Ok(())
+    }
+}
+
+/// Turn a frame into the message the browsers draw, with the characters of every row and the
+/// indexes of their colors in the 256 color palette as two hexadecimal digits per pixel, `--`
+/// for the color of the browser
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{net::{protocol::Message, web_bridge::frame_message}, Pixel, TerminalColors};
+/// let frame = vec![vec![Pixel::new('#', TerminalColors::Default, TerminalColors::Red)]];
+/// let Message::Frame { foreground, .. } = frame_message(&frame) else {
+///     unreachable!();
+/// };
+/// assert_eq!(foreground, ["a0"]);
+/// ```
+pub fn frame_message(frame: &Frame) -> Message {
+    let colors = |color: fn(&Pixel) -> TerminalColors| -> Vec<String> {
+        frame
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|pixel| match color(pixel) as i32 {
+                        index @ 0..=255 => format!("{:02x}", index),
+                        _ => "--".to_string(),
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+    Message::Frame {
+        rows: frame
+            .iter()
+            .map(|row| row.iter().map(Pixel::char).collect())
+            .collect(),
+        background: colors(Pixel::background_color),
+        foreground: colors(Pixel::foreground_color),
+    }
+}
+
+/// Get the character of the key in a `Message::Key` that a browser sent
+fn parse_key_message(text: &str) -> Option<char> {
+    match serde_json::from_str(text).ok()? {
+        Message::Key { key } => Key::parse(&key).map(Key::to_char),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+        thread,
+        time::Duration,
+    };
+
+    use crate::{
+        conway::{rule::Rule, save::SavedGame},
+        net::{
+            protocol::Message,
+            spectator::board_render_object,
+            websocket::{accept_key, decode_frame, encode_frame},
+        },
+        panel::renderer::render,
+        rendering::colors::TerminalColors,
+        shared::{
+            frame::Pixel,
+            square::Square,
+            usize2d::{Coord, Usize2d},
+        },
+    };
+
+    use super::{frame_message, parse_key_message, WebBridge};
+
+    /// Read from a connection until some bytes end the way that is expected
+    fn read_until(stream: &mut TcpStream, is_done: impl Fn(&[u8]) -> bool) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut chunk = [0u8; 1024];
+        while !is_done(&bytes) {
+            let read = stream.read(&mut chunk).unwrap();
+            assert!(read > 0, "The connection closed early");
+            bytes.extend_from_slice(&chunk[..read]);
+        }
+        bytes
+    }
+
+    #[test]
+    fn keys() {
+        let test_cases = [
+            ("char", r#"{"type":"key","key":"a"}"#, Some('a')),
+            ("chord", r#"{"type":"key","key":"ctrl+s"}"#, Some('\x13')),
+            ("named", r#"{"type":"key","key":"space"}"#, Some(' ')),
+            ("unknown name", r#"{"type":"key","key":"shift"}"#, None),
+            ("another message", r#"{"type":"bye"}"#, None),
+            ("not json", "a", None),
+        ];
+        for (test_case, text, expected) in test_cases {
+            assert_eq!(parse_key_message(text), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn browser_session() {
+        let area = Square::new(Usize2d::new(0, 0), Usize2d::new(2, 0));
+        let (mut bridge, keys) = WebBridge::bind("127.0.0.1:0", area).unwrap();
+        let address = bridge.local_addr().unwrap();
+
+        let page = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream
+                .write_all(b"GET / HTTP/1.1\r\nHost: x\r\n\r\n")
+                .unwrap();
+            let mut page = String::new();
+            stream.read_to_string(&mut page).unwrap();
+            page
+        });
+        let browser = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            let request = "GET /ws HTTP/1.1\r\nHost: x\r\nUpgrade: websocket\r\n\
+                           Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                           Sec-WebSocket-Version: 13\r\n\r\n";
+            stream.write_all(request.as_bytes()).unwrap();
+            let response = read_until(&mut stream, |bytes| bytes.ends_with(b"\r\n\r\n"));
+            let key = r#"{"type":"key","key":"ctrl+s"}"#;
+            stream
+                .write_all(&encode_frame(0x1, key.as_bytes(), Some([1, 2, 3, 4])))
+                .unwrap();
+            let frame = read_until(&mut stream, |bytes| {
+                matches!(decode_frame(bytes), Ok(Some(_)))
+            });
+            let (frame, _) = decode_frame(&frame).unwrap().unwrap();
+            (String::from_utf8(response).unwrap(), frame.payload)
+        });
+
+        while bridge.client_count() == 0 {
+            bridge.poll_keys();
+            thread::yield_now();
+        }
+        let page = page.join().unwrap();
+        assert!(page.starts_with("HTTP/1.1 200 OK\r\n"), "{}", page);
+        assert!(page.contains("new WebSocket("), "The page connects back");
+
+        let mut sent = 0;
+        while sent == 0 {
+            sent = bridge.poll_keys();
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(keys.try_recv().map(|event| event.command), Ok('\x13'));
+
+        let board = SavedGame::new(&[vec![true, false, true]], 1, Rule::conway(), 3);
+        let objects: Vec<_> = board_render_object(&board, Coord::new(0, 0))
+            .into_iter()
+            .collect();
+        render(&mut bridge, &objects).unwrap();
+        let (response, payload) = browser.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 101 Switching Protocols\r\n"));
+        let accept = format!(
+            "Sec-WebSocket-Accept: {}\r\n",
+            accept_key("dGhlIHNhbXBsZSBub25jZQ==")
+        );
+        assert!(response.contains(&accept), "{}", response);
+        let message: Message = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(
+            message,
+            Message::Frame {
+                rows: vec!["#.#".to_string()],
+                background: vec!["78a078".to_string()],
+                foreground: vec!["e8e8e8".to_string()],
+            },
+            "The frame is clipped to the area and keeps its colors"
+        );
+    }
+
+    #[test]
+    fn large_frames() {
+        let test_cases = [
+            ("a wide terminal", 200, 60),
+            ("the largest terminal", 400, 150),
+        ];
+        for (test_case, x_len, y_len) in test_cases {
+            let pixel = Pixel::new('#', TerminalColors::Red, TerminalColors::Default);
+            let message = frame_message(&vec![vec![pixel; x_len]; y_len]);
+            let json = serde_json::to_string(&message).unwrap();
+            assert!(json.len() <= message.max_len(), "{}", test_case);
+            let Message::Frame { background, .. } = message else {
+                panic!("{}: not a frame", test_case);
+            };
+            assert_eq!(background[0].len(), 2 * x_len, "{}", test_case);
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use std::{
+    io::{ErrorKind, Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use super::protocol::{NetError, MAX_MESSAGE_LEN};
+
+/// Appended to the key of a handshake before it is hashed, see RFC 6455
+const HANDSHAKE_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// How long the request of a connection that was just accepted may take
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_millis(500);
+/// The longest request that is read before the connection is given up
+const MAX_REQUEST_LEN: usize = 8 * 1024;
+
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+/// A frame of the WebSocket protocol, only unfragmented frames are supported
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WsFrame {
+    pub opcode: u8,
+    pub payload: Vec<u8>,
+}
+
+/// What the request of a connection asked for
+#[derive(Debug, PartialEq, Eq)]
+pub enum Request {
+    /// A WebSocket with the key of the handshake
+    Upgrade(String),
+    /// A plain page, with the path of the request
+    Page(String),
+}
+
+/// A WebSocket connection on the server side, the messages are text
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::net::TcpListener;
+/// # use tic_tac_toe2::net::{protocol::NetError, websocket::{read_request, Request, WebSocket}};
+/// # fn main() -> Result<(), NetError> {
+/// # let listener = TcpListener::bind(("127.0.0.1", 7880)).map_err(|_| NetError::ConnectFailed)?;
+/// let (mut stream, _) = listener.accept().map_err(|_| NetError::ConnectFailed)?;
+/// if let Ok(Request::Upgrade(key)) = read_request(&mut stream) {
+///     let mut socket = WebSocket::accept(stream, &key)?;
+///     socket.send_text("{}")?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WebSocket {
+    stream: TcpStream,
+    /// The bytes that were read but are not a whole frame yet
+    buffer: Vec<u8>,
+}
+impl WebSocket {
+    /// Finish the handshake of a request to upgrade, after which the socket does not block
+    pub fn accept(mut stream: TcpStream, key: &str) -> Result<Self, NetError> {
+        let response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(key)
+        );
+        stream
+            .write_all(response.as_bytes())
+            .map_err(|_| NetError::WriteFailed)?;
+        stream
+            .set_nonblocking(true)
+            .map_err(|_| NetError::ConnectFailed)?;
+        let _ = stream.set_nodelay(true);
+        Ok(WebSocket {
+            stream,
+            buffer: Vec::new(),
+        })
+    }
+    /// Send a text message
+    pub fn send_text(&mut self, text: &str) -> Result<(), NetError> {
+        self.send(OPCODE_TEXT, text.as_bytes())
+    }
+    fn send(&mut self, opcode: u8, payload: &[u8]) -> Result<(), NetError> {
+        let frame = encode_frame(opcode, payload, None);
+        // A client that can not take a whole frame at once can not keep up
+        self.stream
+            .write_all(&frame)
+            .map_err(|_| NetError::WriteFailed)
+    }
+    /// Take the text messages that arrived without waiting for more, answering the pings
+    ///
+    /// # Returns
+    ///
+    /// `NetError::Disconnected` once the client closed the connection
+    pub fn receive_texts(&mut self) -> Result<Vec<String>, NetError> {
+        let mut chunk = [0u8; 1024];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(NetError::Disconnected),
+                Ok(read) => self.buffer.extend_from_slice(&chunk[..read]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => return Err(NetError::Disconnected),
+            }
+        }
+        let mut texts = Vec::new();
+        while let Some((frame, len)) = decode_frame(&self.buffer)? {
+            self.buffer.drain(..len);
+            match frame.opcode {
+                OPCODE_TEXT => {
+                    let text =
+                        String::from_utf8(frame.payload).map_err(|_| NetError::BadMessage)?;
+                    texts.push(text);
+                }
+                OPCODE_PING => self.send(OPCODE_PONG, &frame.payload)?,
+                OPCODE_PONG => (),
+                OPCODE_CLOSE => {
+                    let _ = self.send(OPCODE_CLOSE, &[]);
+                    return Err(NetError::Disconnected);
+                }
+                _ => return Err(NetError::BadMessage),
+            }
+        }
+        Ok(texts)
+    }
+}
+
+/// Read the request of a connection that was just accepted
+pub fn read_request(stream: &mut TcpStream) -> Result<Request, NetError> {
+    stream
+        .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+        .map_err(|_| NetError::ConnectFailed)?;
+    let mut request = Vec::new();
+    let mut chunk = [0u8; 512];
+    while !request.ends_with(b"\r\n\r\n") {
+        let read = stream
+            .read(&mut chunk)
+            .map_err(|_| NetError::Disconnected)?;
+        if read == 0 {
+            return Err(NetError::Disconnected);
+        }
+        request.extend_from_slice(&chunk[..read]);
+        if request.len() > MAX_REQUEST_LEN {
+            return Err(NetError::MessageTooLong);
+        }
+    }
+    parse_request(&String::from_utf8_lossy(&request))
+}
+
+/// Find out what a request asks for, from its request line and its headers
+fn parse_request(request: &str) -> Result<Request, NetError> {
+    let mut lines = request.lines();
+    let path = match lines
+        .next()
+        .map(|line| line.split(' ').collect::<Vec<&str>>())
+    {
+        Some(parts) if parts.len() == 3 && parts[0] == "GET" => parts[1].to_string(),
+        _ => return Err(NetError::BadMessage),
+    };
+    let key = lines.find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("sec-websocket-key")
+            .then(|| value.trim().to_string())
+    });
+    Ok(match key {
+        Some(key) => Request::Upgrade(key),
+        None => Request::Page(path),
+    })
+}
+
+/// Write a plain page as the response to a request and close the connection
+pub fn write_page(mut stream: TcpStream, content_type: &str, body: &str) -> Result<(), NetError> {
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        content_type,
+        body.len(),
+        body
+    );
+    stream
+        .write_all(response.as_bytes())
+        .map_err(|_| NetError::WriteFailed)?;
+    let _ = stream.shutdown(std::net::Shutdown::Both);
+    Ok(())
+}
+
+/// Encode a frame, the frames of a client are masked and the frames of a server are not
+pub fn encode_frame(opcode: u8, payload: &[u8], mask: Option<[u8; 4]>) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    let mask_bit = if mask.is_some() { 0x80 } else { 0 };
+    match payload.len() {
+        len @ 0..=125 => frame.push(mask_bit | len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(mask_bit | 126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(mask_bit | 127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    match mask {
+        Some(mask) => {
+            frame.extend_from_slice(&mask);
+            frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        }
+        None => frame.extend_from_slice(payload),
+    }
+    frame
+}
+
+/// Decode the first frame of some bytes
+///
+/// # Returns
+///
+/// The frame and the number of bytes it took, `None` if the frame is not complete yet
+pub fn decode_frame(bytes: &[u8]) -> Result<Option<(WsFrame, usize)>, NetError> {
+    let [first, second, ..] = bytes else {
+        return Ok(None);
+    };
+    if first & 0x80 == 0 {
+        return Err(NetError::BadMessage);
+    }
+    let opcode = first & 0x0F;
+    let is_masked = second & 0x80 != 0;
+    let (len, mut offset) = match second & 0x7F {
+        126 if bytes.len() >= 4 => (u16::from_be_bytes([bytes[2], bytes[3]]) as usize, 4),
+        127 if bytes.len() >= 10 => {
+            let mut len = [0u8; 8];
+            len.copy_from_slice(&bytes[2..10]);
+            (u64::from_be_bytes(len) as usize, 10)
+        }
+        126 | 127 => return Ok(None),
+        len => (len as usize, 2),
+    };
+    if len > MAX_MESSAGE_LEN {
+        return Err(NetError::MessageTooLong);
+    }
+    let mut mask = None;
+    if is_masked {
+        if bytes.len() < offset + 4 {
+            return Ok(None);
+        }
+        mask = Some([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ]);
+        offset += 4;
+    }
+    if bytes.len() < offset + len {
+        return Ok(None);
+    }
+    let payload = bytes[offset..offset + len]
+        .iter()
+        .enumerate()
+        .map(|(i, b)| match mask {
+            Some(mask) => b ^ mask[i % 4],
+            None => *b,
+        })
+        .collect();
+    Ok(Some((WsFrame { opcode, payload }, offset + len)))
+}
+
+/// Get the `Sec-WebSocket-Accept` of the key of a handshake
+pub fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{}{}", key, HANDSHAKE_GUID).as_bytes()))
+}
+
+/// Hash bytes with SHA-1, which the handshake needs. It is not used for anything else
+fn sha1(bytes: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut message = bytes.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((bytes.len() as u64) * 8).to_be_bytes());
+    for block in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (h, value) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(value);
+        }
+    }
+    let mut digest = [0u8; 20];
+    for (chunk, word) in digest.chunks_mut(4).zip(h) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// Encode bytes as base64 with padding
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let triple = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |triple, (i, b)| triple | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => encoded.push(ALPHABET[(triple >> (18 - 6 * i) & 0x3F) as usize] as char),
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        accept_key, base64, decode_frame, encode_frame, parse_request, sha1, Request, WsFrame,
+        OPCODE_TEXT,
+    };
+    use crate::net::protocol::{NetError, MAX_MESSAGE_LEN};
+
+    #[test]
+    fn handshake() {
+        let hex: String = sha1(b"abc").iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(hex, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        let test_cases = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v")];
+        for (text, expected) in test_cases {
+            assert_eq!(base64(text.as_bytes()), expected, "'{}'", text);
+        }
+        // The example of RFC 6455
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+
+        let test_cases = [
+            (
+                "upgrade",
+                "GET /ws HTTP/1.1\r\nHost: x\r\nsec-websocket-key: abc== \r\n\r\n",
+                Ok(Request::Upgrade("abc==".to_string())),
+            ),
+            (
+                "page",
+                "GET / HTTP/1.1\r\nHost: x\r\n\r\n",
+                Ok(Request::Page("/".to_string())),
+            ),
+            ("post", "POST / HTTP/1.1\r\n\r\n", Err(NetError::BadMessage)),
+        ];
+        for (test_case, request, expected) in test_cases {
+            assert_eq!(parse_request(request), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn frames() {
+        let long = vec![b'x'; 300];
+        let test_cases = [
+            ("short", b"hello".to_vec(), None),
+            ("masked", b"hello".to_vec(), Some([1, 2, 3, 4])),
+            ("long", long.clone(), Some([9, 8, 7, 6])),
+            ("empty", Vec::new(), None),
+        ];
+        for (test_case, payload, mask) in test_cases {
+            let mut bytes = encode_frame(OPCODE_TEXT, &payload, mask);
+            let len = bytes.len();
+            assert_eq!(decode_frame(&bytes[..len - 1]), Ok(None), "{}", test_case);
+            bytes.extend_from_slice(b"next");
+            let expected = WsFrame {
+                opcode: OPCODE_TEXT,
+                payload,
+            };
+            assert_eq!(
+                decode_frame(&bytes),
+                Ok(Some((expected, len))),
+                "{}",
+                test_case
+            );
+        }
+        assert_eq!(encode_frame(OPCODE_TEXT, b"hi", None), b"\x81\x02hi");
+        assert_eq!(
+            &encode_frame(OPCODE_TEXT, &long, None)[..4],
+            &[0x81, 126, 1, 44]
+        );
+        assert_eq!(decode_frame(b"\x01\x02hi"), Err(NetError::BadMessage));
+        let too_long = encode_frame(OPCODE_TEXT, &vec![0; MAX_MESSAGE_LEN + 1], None);
+        assert_eq!(decode_frame(&too_long), Err(NetError::MessageTooLong));
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    conway::conways_game::ConwaysGame,
+    rendering::colors::TerminalColors,
+    shared::{
+        frame::{Frame, Pixel},
+        usize2d::Usize2d,
+    },
+};
+
+/// The color the living cells of a `ConwayBackground` are dimmed to by default
+pub const DIMMED_COLOR: TerminalColors = TerminalColors::DarkGrey;
+
+/// Something that animates beneath the content of a panel, like a dimmed Game of Life behind a
+/// menu
+///
+/// The panel draws the background wherever its own content is its background pixel
+pub trait BackgroundProvider: Send + Debug {
+    /// Advance the background by one iteration of the panel loop
+    ///
+    /// # Returns
+    ///
+    /// `true` if the background changed and should be drawn again
+    fn tick(&mut self) -> bool;
+    /// Draw the background for an area
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the area, the frame has exactly this size
+    fn frame(&self, size: Usize2d) -> Frame;
+}
+
+/// A background that can be handed to a panel with `PanelCommandEnum::SetBackgroundProvider`.
+/// Panels that get clones of the same provider share one animation, which every one of them
+/// advances on its own ticks
+pub type SharedBackground = Arc<Mutex<dyn BackgroundProvider>>;
+
+/// A Game of Life with a cell per character, drawn in one dim color
+///
+/// The game starts over with the next seed once it is stable, so the background never freezes.
+/// An area larger than the board repeats it
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::{mpsc::{channel, SendError}, Arc, Mutex};
+/// # use tic_tac_toe2::{panel::{background::{ConwayBackground, SharedBackground}, command_enum::PanelCommandEnum}, shared::usize2d::Usize2d};
+/// # fn main() -> Result<(), SendError<PanelCommandEnum>> {
+/// # let (size, seed) = (Usize2d::new(80, 24), 42);
+/// # let (command_sender, _commands) = channel();
+/// let background: SharedBackground = Arc::new(Mutex::new(ConwayBackground::new(size, seed, 10)));
+/// command_sender.send(PanelCommandEnum::SetBackgroundProvider(Some(background)))?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ConwayBackground {
+    game: ConwaysGame,
+    size: Usize2d,
+    seed: u64,
+    rounds: u64,
+    color: TerminalColors,
+    ticks_per_generation: usize,
+    tick_count: usize,
+}
+impl ConwayBackground {
+    /// Create a background with `DIMMED_COLOR` cells
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the board
+    /// * `seed` - the seed of the first board
+    /// * `ticks_per_generation` - the number of ticks before the board advances a round
+    pub fn new(size: Usize2d, seed: u64, ticks_per_generation: usize) -> Self {
+        assert!(
+            ticks_per_generation > 0,
+            "The background needs at least one tick per generation"
+        );
+        let size = Usize2d::new(size.x.max(1), size.y.max(1));
+        ConwayBackground {
+            game: ConwaysGame::headless(size.x, size.y, seed),
+            size,
+            seed,
+            rounds: 0,
+            color: DIMMED_COLOR,
+            ticks_per_generation,
+            tick_count: 0,
+        }
+    }
+    /// Draw the living cells in another color
+    pub fn with_color(mut self, color: TerminalColors) -> Self {
+        self.color = color;
+        self
+    }
+    /// Get the round of the board, which starts at 0 for every new seed
+    pub fn rounds(&self) -> u64 {
+        self.rounds
+    }
+    /// Get the seed of the board that is shown
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+impl BackgroundProvider for ConwayBackground {
+    fn tick(&mut self) -> bool {
+        self.tick_count += 1;
+        if self.tick_count < self.ticks_per_generation {
+            return false;
+        }
+        self.tick_count = 0;
+        if self.game.is_stable() {
+            self.seed = self.seed.wrapping_add(1);
+            self.game = ConwaysGame::headless(self.size.x, self.size.y, self.seed);
+            self.rounds = 0;
+        } else {
+            self.game.step();
+            self.rounds += 1;
+        }
+        true
+    }
+    fn frame(&self, size: Usize2d) -> Frame {
+        let cells = self.game.cells();
+        (0..size.y)
+            .map(|y| {
+                (0..size.x)
+                    .map(|x| match cells[y % self.size.y][x % self.size.x] {
+                        true => Pixel::new('█', TerminalColors::Default, self.color),
+                        false => Pixel::default(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+impl Debug for ConwayBackground {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConwayBackground")
+            .field("size", &self.size)
+            .field("seed", &self.seed)
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rendering::colors::TerminalColors, shared::usize2d::Usize2d};
+
+    use super::{BackgroundProvider, ConwayBackground};
+
+    #[test]
+    fn restarts_when_stable() {
+        let mut background = ConwayBackground::new(Usize2d::new(6, 6), 9, 2);
+        let test_cases = [("first tick", false), ("second tick", true)];
+        for (test_case, changed) in test_cases {
+            assert_eq!(background.tick(), changed, "{}", test_case);
+        }
+        assert_eq!(background.rounds(), 1);
+        let mut restarted = false;
+        for _ in 0..2000 {
+            background.tick();
+            if background.seed() != 9 {
+                restarted = true;
+                break;
+            }
+        }
+        assert!(restarted, "A small board settles and starts over");
+        assert_eq!(background.rounds(), 0);
+    }
+
+    #[test]
+    fn tiles_the_area() {
+        let background =
+            ConwayBackground::new(Usize2d::new(4, 3), 2, 1).with_color(TerminalColors::Blue);
+        let frame = background.frame(Usize2d::new(9, 7));
+        assert_eq!((frame.len(), frame[0].len()), (7, 9));
+        for (y, row) in frame.iter().enumerate() {
+            for (x, pixel) in row.iter().enumerate() {
+                assert_eq!(pixel, &frame[y % 3][x % 4], "({}, {})", x, y);
+                if pixel.char() == '█' {
+                    assert_eq!(pixel.foreground_color(), TerminalColors::Blue);
+                }
+            }
+        }
+        assert!(frame.iter().flatten().any(|pixel| pixel.char() == '█'));
+    }
+}
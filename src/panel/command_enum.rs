@@ -1,6 +1,31 @@
+use std::time::Duration;
+
+use crate::shared::{frame::Pixel, square::Square};
+
+use super::{background::SharedBackground, layout::PanelLayout};
+
+#[derive(Clone, Debug)]
 pub enum PanelCommandEnum {
     KillProcess,
     PauseProcess,
+    ResumeProcess,
     ClearBuffer,
     ResizeBuffer,
+    ForceRedraw,
+    SetTitle(String),
+    MoveTo(Square),
+    SetBackground(Pixel),
+    /// Animate something beneath the content of the panel, `None` goes back to the plain
+    /// background
+    SetBackgroundProvider(Option<SharedBackground>),
+    SetLayout(PanelLayout),
+    Show,
+    Hide,
+    ShowToast(String, Duration),
+    /// Keep up to a number of the latest frames to review them, `None` forgets the frames
+    RecordFrames(Option<usize>),
+    /// Start or stop showing the recorded frames instead of the content, only while paused
+    ReviewFrames,
+    /// Show a newer recorded frame in the review, or an older one for a negative number
+    ScrubFrames(isize),
 }
@@ -0,0 +1,266 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::rendering::hud::{Column, Table, Widget};
+
+/// What a key did to a `FilePicker`
+#[derive(Clone, Debug, PartialEq)]
+pub enum PickerAction {
+    /// The picker does not use the key
+    Ignored,
+    /// The selection moved to another entry
+    Moved,
+    /// Another directory is listed
+    Opened,
+    /// A file was chosen with enter
+    Chosen(PathBuf),
+    /// Escape was pressed
+    Cancelled,
+}
+
+/// A file or a directory in the listed directory
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    name: String,
+    is_dir: bool,
+}
+
+/// A dialog that lists the files of a directory with some extensions, so that a scene can ask
+/// for a file to load, like a pattern or a saved game
+///
+/// The directories are listed first, with `..` for the parent directory, and then the files.
+/// Hidden entries are left out. The keys `w` and `s` move the selection, enter opens the
+/// selected directory or chooses the selected file, backspace opens the parent directory and
+/// escape cancels
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::path::Path;
+/// # use tic_tac_toe2::{console::input_record::KeyEvent, conway::clipboard::Clipboard, panel::file_picker::{FilePicker, PickerAction}};
+/// # fn main() -> Result<(), std::io::Error> {
+/// # let event = KeyEvent::key_down('\r');
+/// let mut picker = FilePicker::open(Path::new("."), &["rle", "cells"], 8)?;
+/// if let PickerAction::Chosen(path) = picker.handle_key(event.command) {
+///     let pattern = Clipboard::load(&path);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FilePicker {
+    dir: PathBuf,
+    /// The extensions of the files that are listed, without the dot and in lower case
+    extensions: Vec<String>,
+    entries: Vec<Entry>,
+    selected: usize,
+    visible_rows: usize,
+    /// Why the latest directory could not be opened
+    error: Option<String>,
+}
+impl FilePicker {
+    /// List a directory
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - the directory that is listed first
+    /// * `extensions` - the extensions of the files that can be chosen, like `rle`
+    /// * `visible_rows` - the most entries that are shown at once
+    pub fn open(dir: &Path, extensions: &[&str], visible_rows: usize) -> io::Result<Self> {
+        let dir = fs::canonicalize(dir)?;
+        let extensions: Vec<String> = extensions
+            .iter()
+            .map(|extension| extension.to_ascii_lowercase())
+            .collect();
+        let entries = list(&dir, &extensions)?;
+        Ok(FilePicker {
+            dir,
+            extensions,
+            entries,
+            selected: 0,
+            visible_rows,
+            error: None,
+        })
+    }
+    /// Get the directory that is listed
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+    /// Get the names of the entries, with a `/` after the directories
+    pub fn entries(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|entry| match entry.is_dir {
+                true => format!("{}/", entry.name),
+                false => entry.name.clone(),
+            })
+            .collect()
+    }
+    /// Get the path of the selected entry, `None` if the directory has no entries
+    pub fn selected(&self) -> Option<PathBuf> {
+        let entry = self.entries.get(self.selected)?;
+        match entry.name.as_str() {
+            ".." => self.dir.parent().map(Path::to_path_buf),
+            name => Some(self.dir.join(name)),
+        }
+    }
+    /// Apply a key to the picker
+    pub fn handle_key(&mut self, key: char) -> PickerAction {
+        let count = self.entries.len() as i64;
+        match key {
+            'w' | 'W' | 's' | 'S' if count > 0 => {
+                let offset = if key.eq_ignore_ascii_case(&'w') {
+                    -1
+                } else {
+                    1
+                };
+                self.selected = (self.selected as i64 + offset).rem_euclid(count) as usize;
+                PickerAction::Moved
+            }
+            '\r' | '\n' => {
+                let Some(path) = self.selected() else {
+                    return PickerAction::Ignored;
+                };
+                match self.entries[self.selected].is_dir {
+                    true => self.change_dir(path),
+                    false => PickerAction::Chosen(path),
+                }
+            }
+            '\x08' | '\x7f' => match self.dir.parent() {
+                Some(parent) => self.change_dir(parent.to_path_buf()),
+                None => PickerAction::Ignored,
+            },
+            '\x1b' => PickerAction::Cancelled,
+            _ => PickerAction::Ignored,
+        }
+    }
+    /// List another directory, or keep the listed one and remember why the other one could not
+    /// be listed
+    fn change_dir(&mut self, dir: PathBuf) -> PickerAction {
+        match list(&dir, &self.extensions) {
+            Ok(entries) => {
+                self.dir = dir;
+                self.entries = entries;
+                self.selected = 0;
+                self.error = None;
+                PickerAction::Opened
+            }
+            Err(e) => {
+                self.error = Some(format!("Can not open {}: {}", dir.display(), e.kind()));
+                PickerAction::Ignored
+            }
+        }
+    }
+}
+impl Widget for FilePicker {
+    fn rows(&self) -> Vec<String> {
+        let names: Vec<Vec<String>> = self.entries().into_iter().map(|name| vec![name]).collect();
+        let mut table = Table::new(vec![Column::left("Name")], || names.clone())
+            .visible_rows(self.visible_rows);
+        table.highlight(Some(self.selected));
+        let mut rows = vec![self.dir.display().to_string()];
+        match self.entries.is_empty() {
+            true => rows.push("No files".to_string()),
+            false => rows.extend(table.rows()),
+        }
+        rows.extend(self.error.clone());
+        rows
+    }
+}
+
+/// List the directories and the files with some extensions in a directory, sorted by name
+fn list(dir: &Path, extensions: &[String]) -> io::Result<Vec<Entry>> {
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            dirs.push(name);
+        } else if path.extension().is_some_and(|extension| {
+            extensions.contains(&extension.to_string_lossy().to_ascii_lowercase())
+        }) {
+            files.push(name);
+        }
+    }
+    dirs.sort();
+    files.sort();
+    let parent = dir.parent().map(|_| "..".to_string());
+    Ok(parent
+        .into_iter()
+        .chain(dirs)
+        .map(|name| Entry { name, is_dir: true })
+        .chain(files.into_iter().map(|name| Entry {
+            name,
+            is_dir: false,
+        }))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::rendering::hud::Widget;
+
+    use super::{FilePicker, PickerAction};
+
+    #[test]
+    fn browse() {
+        let root = env::temp_dir().join(format!("file_picker_test_{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("patterns")).unwrap();
+        for name in [
+            "b.json",
+            "a.RLE",
+            "notes.txt",
+            ".hidden.rle",
+            "patterns/glider.cells",
+        ] {
+            fs::write(root.join(name), "").unwrap();
+        }
+
+        let dir = fs::canonicalize(&root).unwrap();
+        let mut picker = FilePicker::open(&root, &["rle", "cells", "json"], 3).unwrap();
+        assert_eq!(
+            picker.entries(),
+            vec!["../", "patterns/", "a.RLE", "b.json"],
+            "The directories first, then the files with the extensions"
+        );
+        let test_cases = [
+            ("up wraps around", 'w', PickerAction::Moved, "b.json"),
+            ("down wraps around", 's', PickerAction::Moved, "../"),
+            ("other keys", 'x', PickerAction::Ignored, "../"),
+            ("down", 'S', PickerAction::Moved, "patterns/"),
+            ("open a directory", '\r', PickerAction::Opened, "../"),
+            ("back up", '\x7f', PickerAction::Opened, "../"),
+        ];
+        for (test_case, key, expected, selected) in test_cases {
+            assert_eq!(picker.handle_key(key), expected, "{}", test_case);
+            assert_eq!(picker.entries()[picker.selected], selected, "{}", test_case);
+        }
+        assert_eq!(picker.dir(), dir);
+
+        for key in ['w', 'w'] {
+            picker.handle_key(key);
+        }
+        assert_eq!(
+            picker.rows()[1..],
+            ["  Name", "  ../", "  patterns/", "> a.RLE", "1-3 of 4"]
+        );
+        let chosen = picker.handle_key('\n');
+        assert_eq!(picker.handle_key('\x1b'), PickerAction::Cancelled);
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(chosen, PickerAction::Chosen(dir.join("a.RLE")));
+    }
+}
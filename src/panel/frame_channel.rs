@@ -0,0 +1,220 @@
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    sync::{
+        mpsc::{SendError, TryRecvError},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+/// The number of frames that wait for a panel before the oldest one is dropped
+pub const FRAME_QUEUE_CAPACITY: usize = 2;
+
+#[derive(Debug)]
+struct Queue<T> {
+    frames: VecDeque<T>,
+    capacity: usize,
+    /// The frames that were dropped since the drops were last taken
+    dropped: u64,
+    senders: usize,
+    is_receiver_alive: bool,
+}
+
+/// Create a channel for the frames of a panel that holds at most `capacity` frames
+///
+/// A renderer that falls behind only ever sees the latest frames. Sending to a full channel
+/// drops the oldest frame instead of waiting, and a receiver that takes a frame with
+/// `try_recv_latest` skips the frames that are older than the newest. Both count as dropped
+/// frames, which can be recorded in the metrics with `FrameSender::take_dropped`
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::{mpsc::SendError, Arc};
+/// # use tic_tac_toe2::{panel::frame_channel::{frame_channel, FRAME_QUEUE_CAPACITY}, rendering::render_object::{RenderObject, RenderObjects}, utils::metrics::{Metrics, DROPPED_FRAMES}};
+/// # fn main() -> Result<(), SendError<RenderObjects>> {
+/// # let mut metrics = Metrics::new(std::time::Instant::now());
+/// # let render_objects: RenderObjects = Arc::new([]);
+/// # let (frame_sender, _frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+/// frame_sender.send(render_objects)?;
+/// metrics.add(DROPPED_FRAMES, frame_sender.take_dropped());
+/// # Ok(())
+/// # }
+/// ```
+pub fn frame_channel<T>(capacity: usize) -> (FrameSender<T>, FrameReceiver<T>) {
+    assert!(
+        capacity > 0,
+        "The channel needs room for at least one frame"
+    );
+    let queue = Arc::new(Mutex::new(Queue {
+        frames: VecDeque::with_capacity(capacity),
+        capacity,
+        dropped: 0,
+        senders: 1,
+        is_receiver_alive: true,
+    }));
+    (
+        FrameSender {
+            queue: queue.clone(),
+        },
+        FrameReceiver { queue },
+    )
+}
+
+fn lock<T>(queue: &Mutex<Queue<T>>) -> MutexGuard<'_, Queue<T>> {
+    queue
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Sends frames into a channel made by `frame_channel`, it never waits for the receiver
+pub struct FrameSender<T> {
+    queue: Arc<Mutex<Queue<T>>>,
+}
+impl<T> FrameSender<T> {
+    /// Queue a frame, dropping the oldest queued frame if the channel is full
+    ///
+    /// # Returns
+    ///
+    /// The frame back as an error if the receiver is gone
+    pub fn send(&self, frame: T) -> Result<(), SendError<T>> {
+        let mut queue = lock(&self.queue);
+        if !queue.is_receiver_alive {
+            return Err(SendError(frame));
+        }
+        if queue.frames.len() == queue.capacity {
+            queue.frames.pop_front();
+            queue.dropped += 1;
+        }
+        queue.frames.push_back(frame);
+        Ok(())
+    }
+    /// Get the number of frames that were dropped since the drops were last taken and start
+    /// counting from 0 again
+    pub fn take_dropped(&self) -> u64 {
+        std::mem::take(&mut lock(&self.queue).dropped)
+    }
+}
+impl<T> Clone for FrameSender<T> {
+    fn clone(&self) -> Self {
+        lock(&self.queue).senders += 1;
+        FrameSender {
+            queue: self.queue.clone(),
+        }
+    }
+}
+impl<T> Drop for FrameSender<T> {
+    fn drop(&mut self) {
+        lock(&self.queue).senders -= 1;
+    }
+}
+impl<T> Debug for FrameSender<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameSender").finish_non_exhaustive()
+    }
+}
+
+/// Receives the frames of a channel made by `frame_channel`
+pub struct FrameReceiver<T> {
+    queue: Arc<Mutex<Queue<T>>>,
+}
+impl<T> FrameReceiver<T> {
+    /// Take the oldest queued frame
+    ///
+    /// # Returns
+    ///
+    /// `TryRecvError::Empty` if no frame is queued and `TryRecvError::Disconnected` once all
+    /// senders are gone as well
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut queue = lock(&self.queue);
+        match queue.frames.pop_front() {
+            Some(frame) => Ok(frame),
+            None if queue.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+    /// Take the newest queued frame and drop the older ones
+    ///
+    /// # Returns
+    ///
+    /// The same errors as `try_recv`
+    pub fn try_recv_latest(&self) -> Result<T, TryRecvError> {
+        let mut queue = lock(&self.queue);
+        let skipped = queue.frames.len().saturating_sub(1);
+        queue.dropped += skipped as u64;
+        queue.frames.drain(..skipped);
+        match queue.frames.pop_front() {
+            Some(frame) => Ok(frame),
+            None if queue.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+}
+impl<T> Drop for FrameReceiver<T> {
+    fn drop(&mut self) {
+        let mut queue = lock(&self.queue);
+        queue.is_receiver_alive = false;
+        queue.frames.clear();
+    }
+}
+impl<T> Debug for FrameReceiver<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let queue = lock(&self.queue);
+        f.debug_struct("FrameReceiver")
+            .field("queued", &queue.frames.len())
+            .field("capacity", &queue.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{SendError, TryRecvError};
+
+    use super::frame_channel;
+
+    #[test]
+    fn drops_the_oldest() {
+        let (sender, receiver) = frame_channel(2);
+        for frame in 1..=5 {
+            sender.send(frame).unwrap();
+        }
+        assert_eq!(sender.take_dropped(), 3);
+        assert_eq!(sender.take_dropped(), 0, "The drops are taken once");
+        let test_cases = [
+            ("oldest kept", Ok(4)),
+            ("newest", Ok(5)),
+            ("empty", Err(TryRecvError::Empty)),
+        ];
+        for (test_case, expected) in test_cases {
+            assert_eq!(receiver.try_recv(), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn keeps_the_latest() {
+        let (sender, receiver) = frame_channel(3);
+        for frame in 1..=3 {
+            sender.send(frame).unwrap();
+        }
+        assert_eq!(receiver.try_recv_latest(), Ok(3));
+        assert_eq!(sender.take_dropped(), 2, "Skipped frames are dropped");
+        assert_eq!(receiver.try_recv_latest(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn disconnects() {
+        let (sender, receiver) = frame_channel(1);
+        let copy = sender.clone();
+        sender.send(1).unwrap();
+        drop(sender);
+        assert_eq!(receiver.try_recv(), Ok(1));
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+        drop(receiver);
+        assert_eq!(copy.send(2), Err(SendError(2)));
+
+        let (sender, receiver) = frame_channel::<u8>(1);
+        drop(sender);
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Disconnected));
+    }
+}
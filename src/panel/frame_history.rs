@@ -0,0 +1,197 @@
+use std::collections::VecDeque;
+
+use crate::shared::frame::SharedFrame;
+
+/// The latest frames a panel flushed, to scrub through them while the panel is paused and catch
+/// glitches that are only on the screen for a frame
+///
+/// Recording a frame drops the oldest one once `capacity` frames are kept. While the frames are
+/// reviewed, the frame at the cursor is shown instead of the content of the panel
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use tic_tac_toe2::{handler::handle_error::HandleError, panel::{frame_history::FrameHistory, renderer::{render, FrameRenderer}}, scene::scene::write_frame_at, shared::{square::Square, usize2d::{Coord, Usize2d}}, MemoryHandle, Pixel};
+/// # fn main() -> Result<(), HandleError> {
+/// # let mut renderer = FrameRenderer::new(Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10)), Pixel::default());
+/// # render(&mut renderer, &[]).unwrap();
+/// # let (origin, handle) = (Coord::new(1, 1), &mut MemoryHandle::new());
+/// let mut history = FrameHistory::new(120);
+/// history.record(Arc::clone(renderer.frame().unwrap()));
+/// history.toggle_review();
+/// history.scrub(-10);
+/// write_frame_at(history.shown().unwrap(), origin, handle)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FrameHistory {
+    /// The frames share their pixels with the renderer that drew them
+    frames: VecDeque<SharedFrame>,
+    capacity: usize,
+    /// The index of the frame that is reviewed, `None` outside of the review
+    cursor: Option<usize>,
+    /// The frame that is shown changed since `take_changed`
+    is_changed: bool,
+}
+impl FrameHistory {
+    /// Create an empty history
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - the most frames that are kept, at least one
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "The history needs room for at least one frame"
+        );
+        FrameHistory {
+            frames: VecDeque::with_capacity(capacity),
+            capacity,
+            cursor: None,
+            is_changed: false,
+        }
+    }
+    /// Keep a frame as the newest one, dropping the oldest frame if the history is full
+    pub fn record(&mut self, frame: SharedFrame) {
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+            if let Some(cursor) = self.cursor.as_mut() {
+                *cursor = cursor.saturating_sub(1);
+            }
+        }
+        self.frames.push_back(frame);
+    }
+    /// Forget the frames and stop the review
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.cursor = None;
+    }
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+    pub fn is_reviewing(&self) -> bool {
+        self.cursor.is_some()
+    }
+    /// Start reviewing the frames at the newest one, or stop reviewing them. There is nothing to
+    /// review before the first frame
+    pub fn toggle_review(&mut self) {
+        match self.cursor {
+            Some(_) => self.end_review(),
+            None if !self.frames.is_empty() => {
+                self.cursor = Some(self.frames.len() - 1);
+                self.is_changed = true;
+            }
+            None => {}
+        }
+    }
+    /// Stop reviewing the frames, the newest frame is shown again
+    pub fn end_review(&mut self) {
+        if self.cursor.take().is_some() {
+            self.is_changed = true;
+        }
+    }
+    /// Move the cursor of the review, stopping at the oldest and the newest frame
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - the number of frames to move, negative moves to older frames
+    pub fn scrub(&mut self, frames: isize) {
+        let Some(cursor) = self.cursor else {
+            return;
+        };
+        let moved = cursor
+            .saturating_add_signed(frames)
+            .min(self.frames.len() - 1);
+        if moved != cursor {
+            self.cursor = Some(moved);
+            self.is_changed = true;
+        }
+    }
+    /// Get the number of the reviewed frame from the oldest one, starting at 1, and the number of
+    /// frames. `None` outside of the review
+    pub fn position(&self) -> Option<(usize, usize)> {
+        self.cursor.map(|cursor| (cursor + 1, self.frames.len()))
+    }
+    /// Get the frame at the cursor while reviewing, otherwise the newest frame
+    pub fn shown(&self) -> Option<&SharedFrame> {
+        match self.cursor {
+            Some(cursor) => self.frames.get(cursor),
+            None => self.frames.back(),
+        }
+    }
+    /// Check if the shown frame changed since the last call
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.is_changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::shared::frame::{Pixel, SharedFrame};
+
+    use super::FrameHistory;
+
+    fn frame(ch: char) -> SharedFrame {
+        Arc::new(vec![vec![Pixel::new(
+            ch,
+            Default::default(),
+            Default::default(),
+        )]])
+    }
+
+    fn shown(history: &FrameHistory) -> Option<char> {
+        history.shown().map(|frame| frame[0][0].char())
+    }
+
+    #[test]
+    fn scrub() {
+        let mut history = FrameHistory::new(3);
+        history.toggle_review();
+        assert!(!history.is_reviewing(), "There is nothing to review yet");
+        for ch in ['a', 'b', 'c', 'd'] {
+            history.record(frame(ch));
+        }
+        assert_eq!(history.len(), 3, "The oldest frame is dropped");
+        assert_eq!(shown(&history), Some('d'));
+        assert!(!history.take_changed());
+
+        history.toggle_review();
+        let test_cases = [
+            ("start at the newest", 0, Some((3, 3)), 'd', true),
+            ("older", -1, Some((2, 3)), 'c', true),
+            ("stop at the oldest", -5, Some((1, 3)), 'b', true),
+            ("already the oldest", -1, Some((1, 3)), 'b', false),
+            ("stop at the newest", 9, Some((3, 3)), 'd', true),
+        ];
+        for (test_case, frames, position, expected, is_changed) in test_cases {
+            history.scrub(frames);
+            assert_eq!(history.position(), position, "{}", test_case);
+            assert_eq!(shown(&history), Some(expected), "{}", test_case);
+            assert_eq!(history.take_changed(), is_changed, "{}", test_case);
+        }
+
+        history.scrub(-1);
+        history.record(frame('e'));
+        assert_eq!(
+            shown(&history),
+            Some('c'),
+            "The cursor stays on its frame when the oldest is dropped"
+        );
+        history.toggle_review();
+        assert_eq!(history.position(), None);
+        assert_eq!(shown(&history), Some('e'));
+        assert!(history.take_changed(), "The newest frame is shown again");
+
+        history.toggle_review();
+        history.clear();
+        assert!(history.is_empty());
+        assert!(!history.is_reviewing());
+    }
+}
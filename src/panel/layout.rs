@@ -0,0 +1,402 @@
+use crate::shared::{
+    square::Square,
+    usize2d::{Coord, Usize2d},
+};
+
+/// Where content that is narrower than its area is placed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HorizontalAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Where content that is lower than its area is placed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VerticalAlign {
+    #[default]
+    Top,
+    Center,
+    Bottom,
+}
+
+/// How content is placed in an area that is larger than the content
+///
+/// The padding is kept clear on every side of the area. The content is aligned in what is left,
+/// content that does not fit starts at the top left of it. The default layout has no padding and
+/// places the content in the top left corner
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{shared::{square::Square, usize2d::Usize2d}, PanelLayout};
+/// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+/// let layout = PanelLayout::centered(1);
+/// let origin = layout.content_origin(&area, Usize2d::new(9, 5));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PanelLayout {
+    pub padding: usize,
+    pub horizontal: HorizontalAlign,
+    pub vertical: VerticalAlign,
+}
+impl PanelLayout {
+    /// Center the content both ways
+    pub fn centered(padding: usize) -> Self {
+        PanelLayout {
+            padding,
+            horizontal: HorizontalAlign::Center,
+            vertical: VerticalAlign::Center,
+        }
+    }
+    /// Get the space that is left for the content of an area once the padding is taken off
+    pub fn inner_size(&self, area: &Square) -> Usize2d {
+        Usize2d::new(
+            area.width().saturating_sub(2 * self.padding),
+            area.height().saturating_sub(2 * self.padding),
+        )
+    }
+    /// Get the top left corner of content that is placed in an area
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - the area the content is placed in
+    /// * `content` - the number of columns and rows of the content
+    pub fn content_origin(&self, area: &Square, content: Usize2d) -> Coord {
+        let (top_left, _) = area.get_boundary();
+        let inner = self.inner_size(area);
+        let (free_x, free_y) = (
+            inner.x.saturating_sub(content.x),
+            inner.y.saturating_sub(content.y),
+        );
+        let x = match self.horizontal {
+            HorizontalAlign::Left => 0,
+            HorizontalAlign::Center => free_x / 2,
+            HorizontalAlign::Right => free_x,
+        };
+        let y = match self.vertical {
+            VerticalAlign::Top => 0,
+            VerticalAlign::Center => free_y / 2,
+            VerticalAlign::Bottom => free_y,
+        };
+        Coord::new(top_left.x + self.padding + x, top_left.y + self.padding + y)
+    }
+}
+
+/// How much of the length of a split a part takes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// A number of columns or rows
+    Fixed(usize),
+    /// A share of the length of the split, from 0 to 100
+    Percent(usize),
+    /// An equal share of what the fixed and percentage parts leave
+    Fill,
+    /// Like `Fill`, but never less than a number of columns or rows
+    Min(usize),
+    /// Like `Fill`, but never more than a number of columns or rows
+    Max(usize),
+}
+
+/// The way a split divides its area
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Side by side, the constraints are in columns
+    Horizontal,
+    /// Above each other, the constraints are in rows
+    Vertical,
+}
+
+/// Divide an area into parts next to or above each other
+///
+/// Fixed and percentage parts are sized first, the flexible parts share what is left. Once the
+/// parts need more than the area, the last parts are cut short
+///
+/// # Arguments
+///
+/// * `area` - the area to divide
+/// * `direction` - if the parts are side by side or above each other
+/// * `constraints` - the size of every part, in order
+///
+/// # Returns
+///
+/// The area of every part, `None` for a part that got no space
+///
+/// # Examples
+///
+/// ```
+/// # use tic_tac_toe2::{panel::layout::{split, Constraint, Direction}, shared::{square::Square, usize2d::Usize2d}};
+/// # let screen = Square::new(Usize2d::new(1, 1), Usize2d::new(80, 24));
+/// let parts = split(&screen, Direction::Horizontal, &[Constraint::Fixed(20), Constraint::Fill]);
+/// ```
+pub fn split(
+    area: &Square,
+    direction: Direction,
+    constraints: &[Constraint],
+) -> Vec<Option<Square>> {
+    let (top_left, bottom_right) = area.get_boundary();
+    let total = match direction {
+        Direction::Horizontal => area.width(),
+        Direction::Vertical => area.height(),
+    };
+    let mut lengths = vec![None; constraints.len()];
+    for (length, constraint) in lengths.iter_mut().zip(constraints) {
+        *length = match constraint {
+            Constraint::Fixed(cells) => Some(*cells),
+            Constraint::Percent(percent) => Some(total * percent / 100),
+            _ => None,
+        };
+    }
+    // Share what is left between the flexible parts, a part that hits its minimum or maximum
+    // keeps that length and the others share again
+    loop {
+        let used: usize = lengths.iter().flatten().sum();
+        let flexible: Vec<usize> = (0..lengths.len())
+            .filter(|index| lengths[*index].is_none())
+            .collect();
+        if flexible.is_empty() {
+            break;
+        }
+        let share = total.saturating_sub(used) / flexible.len();
+        let mut is_settled = true;
+        for index in flexible.iter() {
+            match constraints[*index] {
+                Constraint::Min(cells) if cells > share => lengths[*index] = Some(cells),
+                Constraint::Max(cells) if cells < share => lengths[*index] = Some(cells),
+                _ => continue,
+            }
+            is_settled = false;
+        }
+        if is_settled {
+            // The first parts get the columns or rows that do not divide evenly
+            let extra = total.saturating_sub(used) % flexible.len();
+            for (position, index) in flexible.into_iter().enumerate() {
+                lengths[index] = Some(share + usize::from(position < extra));
+            }
+            break;
+        }
+    }
+    let mut start = 0;
+    lengths
+        .into_iter()
+        .map(|length| {
+            let length = length.unwrap_or(0).min(total - start);
+            let part = (length > 0).then(|| match direction {
+                Direction::Horizontal => Square::new(
+                    Usize2d::new(top_left.x + start, top_left.y),
+                    Usize2d::new(top_left.x + start + length - 1, bottom_right.y),
+                ),
+                Direction::Vertical => Square::new(
+                    Usize2d::new(top_left.x, top_left.y + start),
+                    Usize2d::new(bottom_right.x, top_left.y + start + length - 1),
+                ),
+            });
+            start += length;
+            part
+        })
+        .collect()
+}
+
+/// The panels of a screen as a tree of splits
+///
+/// # Example
+///
+/// A sidebar of 20 columns, a main panel that fills the rest and a status bar of 1 row
+///
+/// ```
+/// # use tic_tac_toe2::panel::layout::{Constraint, Direction, LayoutNode};
+/// # let (sidebar, main, status) = (0, 1, 2);
+/// let layout = LayoutNode::Split(
+///     Direction::Vertical,
+///     vec![
+///         (
+///             Constraint::Fill,
+///             LayoutNode::Split(
+///                 Direction::Horizontal,
+///                 vec![
+///                     (Constraint::Fixed(20), LayoutNode::Panel(sidebar)),
+///                     (Constraint::Fill, LayoutNode::Panel(main)),
+///                 ],
+///             ),
+///         ),
+///         (Constraint::Fixed(1), LayoutNode::Panel(status)),
+///     ],
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutNode {
+    /// A panel by the index it was registered with
+    Panel(usize),
+    /// An area that is divided between its children
+    Split(Direction, Vec<(Constraint, LayoutNode)>),
+}
+impl LayoutNode {
+    /// Get the area of every panel of the tree when the tree fills an area. Panels that got no
+    /// space are left out
+    pub fn areas(&self, area: &Square) -> Vec<(usize, Square)> {
+        match self {
+            LayoutNode::Panel(panel) => vec![(*panel, area.clone())],
+            LayoutNode::Split(direction, children) => {
+                let constraints: Vec<Constraint> =
+                    children.iter().map(|(constraint, _)| *constraint).collect();
+                split(area, *direction, &constraints)
+                    .into_iter()
+                    .zip(children)
+                    .filter_map(|(part, (_, child))| part.map(|part| child.areas(&part)))
+                    .flatten()
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shared::{square::Square, usize2d::Usize2d};
+
+    use super::{
+        split, Constraint, Direction, HorizontalAlign, LayoutNode, PanelLayout, VerticalAlign,
+    };
+
+    /// The left, top, right and bottom of an area
+    type Bounds = (usize, usize, usize, usize);
+    /// The first and last column of an area
+    type Span = Option<(usize, usize)>;
+
+    fn bounds(part: &Option<Square>) -> Option<Bounds> {
+        part.as_ref().map(|part| {
+            let (top_left, bottom_right) = part.get_boundary();
+            (top_left.x, top_left.y, bottom_right.x, bottom_right.y)
+        })
+    }
+
+    #[test]
+    fn content_origin() {
+        // 20 columns and 10 rows
+        let area = Square::new(Usize2d::new(5, 2), Usize2d::new(24, 11));
+        let content = Usize2d::new(6, 4);
+        let test_cases = [
+            ("default", PanelLayout::default(), content, (5, 2)),
+            ("centered", PanelLayout::centered(0), content, (12, 5)),
+            (
+                "centered with padding",
+                PanelLayout::centered(2),
+                content,
+                (12, 5),
+            ),
+            (
+                "bottom right with padding",
+                PanelLayout {
+                    padding: 1,
+                    horizontal: HorizontalAlign::Right,
+                    vertical: VerticalAlign::Bottom,
+                },
+                content,
+                (18, 7),
+            ),
+            (
+                "too large",
+                PanelLayout::centered(1),
+                Usize2d::new(30, 30),
+                (6, 3),
+            ),
+        ];
+        for (test_case, layout, content, (x, y)) in test_cases {
+            let origin = layout.content_origin(&area, content);
+            assert_eq!((origin.x, origin.y), (x, y), "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn split_constraints() {
+        // 100 columns and 10 rows
+        let area = Square::new(Usize2d::new(0, 0), Usize2d::new(99, 9));
+        let test_cases: [(&str, Vec<Constraint>, Vec<Span>); 6] = [
+            (
+                "fixed and fill",
+                vec![Constraint::Fixed(20), Constraint::Fill],
+                vec![Some((0, 19)), Some((20, 99))],
+            ),
+            (
+                "percentages",
+                vec![Constraint::Percent(25), Constraint::Percent(75)],
+                vec![Some((0, 24)), Some((25, 99))],
+            ),
+            (
+                "uneven fill",
+                vec![Constraint::Fill, Constraint::Fill, Constraint::Fill],
+                vec![Some((0, 33)), Some((34, 66)), Some((67, 99))],
+            ),
+            (
+                "maximum gives the rest to the others",
+                vec![Constraint::Max(10), Constraint::Fill],
+                vec![Some((0, 9)), Some((10, 99))],
+            ),
+            (
+                "minimum takes from the others",
+                vec![
+                    Constraint::Percent(50),
+                    Constraint::Min(40),
+                    Constraint::Fill,
+                ],
+                vec![Some((0, 49)), Some((50, 89)), Some((90, 99))],
+            ),
+            (
+                "too large",
+                vec![
+                    Constraint::Fixed(90),
+                    Constraint::Fixed(20),
+                    Constraint::Fill,
+                ],
+                vec![Some((0, 89)), Some((90, 99)), None],
+            ),
+        ];
+        for (test_case, constraints, expected) in test_cases {
+            let parts = split(&area, Direction::Horizontal, &constraints);
+            let actual: Vec<Span> = parts
+                .iter()
+                .map(|part| bounds(part).map(|(left, _, right, _)| (left, right)))
+                .collect();
+            assert_eq!(actual, expected, "{}", test_case);
+            for part in parts.iter().flatten() {
+                assert_eq!(part.height(), 10, "{}", test_case);
+            }
+        }
+    }
+
+    #[test]
+    fn layout_tree() {
+        let (sidebar, main, status) = (0, 1, 2);
+        let layout = LayoutNode::Split(
+            Direction::Vertical,
+            vec![
+                (
+                    Constraint::Fill,
+                    LayoutNode::Split(
+                        Direction::Horizontal,
+                        vec![
+                            (Constraint::Fixed(20), LayoutNode::Panel(sidebar)),
+                            (Constraint::Fill, LayoutNode::Panel(main)),
+                        ],
+                    ),
+                ),
+                (Constraint::Fixed(1), LayoutNode::Panel(status)),
+            ],
+        );
+        let screen = Square::new(Usize2d::new(0, 0), Usize2d::new(79, 23));
+        let areas: Vec<(usize, Option<Bounds>)> = layout
+            .areas(&screen)
+            .into_iter()
+            .map(|(panel, area)| (panel, bounds(&Some(area))))
+            .collect();
+        assert_eq!(
+            areas,
+            vec![
+                (sidebar, Some((0, 0, 19, 22))),
+                (main, Some((20, 0, 79, 22))),
+                (status, Some((0, 23, 79, 23))),
+            ]
+        );
+    }
+}
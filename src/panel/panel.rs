@@ -1,15 +1,33 @@
 use std::{
-    sync::mpsc::Receiver,
+    sync::{mpsc::Receiver, Arc},
     thread::{spawn, JoinHandle},
 };
 
 use crate::{
     handler::handle::Handle,
-    rendering::render_object::RenderObject,
-    shared::{frame::Pixel, square::Square, usize2d::Usize2d},
+    rendering::{
+        colors::TerminalColors,
+        render_object::{RenderObject, RenderObjects},
+    },
+    scene::scene::{write_frame_at, write_pixels},
+    shared::{
+        frame::{Pixel, SharedFrame},
+        square::Square,
+        usize2d::{Coord, Usize2d},
+    },
+    utils::{
+        buffer_pool::FramePool,
+        clock::{Clock, SystemClock},
+    },
 };
 
-use super::{command_enum::PanelCommandEnum, errors::PanelError, state::PanelState};
+use super::{
+    command_enum::PanelCommandEnum,
+    errors::PanelError,
+    frame_channel::FrameReceiver,
+    renderer::{render, FrameRenderer, Renderer},
+    state::PanelState,
+};
 
 /// # Description
 ///
@@ -19,16 +37,24 @@ use super::{command_enum::PanelCommandEnum, errors::PanelError, state::PanelStat
 /// coordinate called `bottom_right`
 ///
 /// All data processed by this panel should render inside this area. Data is sent to the panel
-/// through a sender in the form of list of renderable sprites `Vec<RenderObject>`
+/// through a sender in the form of a shared list of renderable sprites `RenderObjects`
 #[derive(Debug)]
 pub struct Panel {
-    _previous_frame: Vec<Vec<Pixel>>,
-    _next_frame: Vec<Vec<Pixel>>,
+    /// The frames share one allocation after a reset
+    _previous_frame: SharedFrame,
+    _next_frame: SharedFrame,
     area: Square,
-    frame_receiver: Receiver<Vec<RenderObject>>,
+    frame_receiver: FrameReceiver<RenderObjects>,
     command_receiver: Receiver<PanelCommandEnum>,
     state: PanelState,
     handle: Box<dyn Handle>,
+    clock: Box<dyn Clock>,
+    /// Recycles the frames the background is composited in
+    frame_pool: FramePool,
+    /// The areas the objects of the last frame were written to, cleared before the next frame
+    drawn_areas: Vec<Square>,
+    /// Draws the frames in memory for the frame history, while the frames are recorded
+    recorder: FrameRenderer,
 }
 impl Panel {
     /// Initialize an instance of Window
@@ -38,7 +64,8 @@ impl Panel {
     /// * `size` - number of rows and columns in the grid as a usize
     /// * `top_left` - the top left most coordinate for the frame
     /// * `bottom_right` - the bottom right most coordinate for the frame
-    /// * `frame_receiver` - receives the next frame to be printed
+    /// * `frame_receiver` - receives the next frame to be printed, older frames that are still
+    ///   queued are skipped
     /// * `command_receiver` - receives the commands for the window e.g. kill_process, resize_window
     ///
     /// #Returns
@@ -47,31 +74,87 @@ impl Panel {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let size = Usize2d::new(10, 69);
     /// let top_left= Usize2d::new(0, 0);
     /// let bottom_right= Usize2d::new(10, 69);
     /// let bottom_right= Usize2d::new(10, 69);
-    /// let (_, frame_receiver) = channel();
+    /// let (_, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
     /// let (_, command_receiver) = channel();
     ///
     /// let window= Window::init(size, top_left, bottom_right, frame_receiver, command_receiver);
     /// ```
     fn init(
         area: Square,
-        frame_receiver: Receiver<Vec<RenderObject>>,
+        frame_receiver: FrameReceiver<RenderObjects>,
         command_receiver: Receiver<PanelCommandEnum>,
         handle: Box<dyn Handle>,
     ) -> Result<Self, PanelError> {
-        let new_state = vec![vec![Pixel::default(); area.width()]; area.height()];
+        Self::init_with_clock(
+            area,
+            frame_receiver,
+            command_receiver,
+            handle,
+            Box::new(SystemClock),
+        )
+    }
+    /// Initialize a panel that reads the time from a specific clock
+    ///
+    /// This is used by tests to control how time passes for toasts and other timed content. See
+    /// `init` for the other arguments
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - the clock the panel reads the current time from
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::mpsc::channel;
+    /// # use tic_tac_toe2::{panel::{errors::PanelError, frame_channel::{frame_channel, FRAME_QUEUE_CAPACITY}}, shared::{square::Square, usize2d::Usize2d}, utils::clock::VirtualClock, MemoryHandle, Panel};
+    /// # fn main() -> Result<(), PanelError> {
+    /// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+    /// # let (_frames, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+    /// # let (_commands, command_receiver) = channel();
+    /// # let handle = Box::new(MemoryHandle::new());
+    /// let clock = VirtualClock::new();
+    /// let panel = Panel::init_with_clock(
+    ///     area,
+    ///     frame_receiver,
+    ///     command_receiver,
+    ///     handle,
+    ///     Box::new(clock.clone()),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn init_with_clock(
+        area: Square,
+        frame_receiver: FrameReceiver<RenderObjects>,
+        command_receiver: Receiver<PanelCommandEnum>,
+        handle: Box<dyn Handle>,
+        clock: Box<dyn Clock>,
+    ) -> Result<Self, PanelError> {
+        let state = PanelState {
+            is_redraw_requested: true,
+            ..PanelState::default()
+        };
+        let new_state = Arc::new(vec![
+            vec![state.background.clone(); area.width()];
+            area.height()
+        ]);
         Ok(Panel {
             _previous_frame: new_state.clone(),
             _next_frame: new_state.clone(),
+            recorder: FrameRenderer::new(area.clone(), state.background.clone()),
             area,
             frame_receiver,
             command_receiver,
-            state: PanelState::default(),
+            state,
             handle,
+            clock,
+            frame_pool: FramePool::default(),
+            drawn_areas: Vec::new(),
         })
     }
 
@@ -80,56 +163,328 @@ impl Panel {
     /// # Examples
     ///
     /// ```
-    /// let window= Window::init(...);
-    /// window.run();
+    /// # use std::sync::mpsc::channel;
+    /// # use tic_tac_toe2::{panel::{command_enum::PanelCommandEnum, errors::PanelError, frame_channel::{frame_channel, FRAME_QUEUE_CAPACITY}}, shared::{square::Square, usize2d::Usize2d}, utils::clock::SystemClock, MemoryHandle, Panel};
+    /// # fn main() -> Result<(), PanelError> {
+    /// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+    /// # let (_frames, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+    /// # let (command_sender, command_receiver) = channel();
+    /// # let mut panel = Panel::init_with_clock(area, frame_receiver, command_receiver, Box::new(MemoryHandle::new()), Box::new(SystemClock))?;
+    /// # command_sender.send(PanelCommandEnum::KillProcess).unwrap();
+    /// panel.run()?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn run(&mut self) -> Result<(), PanelError> {
-        loop {
-            match self.command_receiver.try_recv() {
-                Ok(cmd) => self.state.process_command(cmd),
-                Err(_) => (),
-            };
-
-            if self.state.is_killed {
-                break;
-            }
+        while self.tick()? {}
+        Ok(())
+    }
+
+    /// Run a single iteration of the panel loop
+    ///
+    /// # Returns
+    ///
+    /// `Ok(false)` once the panel was killed, `Ok(true)` if the loop should continue
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::mpsc::channel;
+    /// # use tic_tac_toe2::{panel::{command_enum::PanelCommandEnum, errors::PanelError, frame_channel::{frame_channel, FRAME_QUEUE_CAPACITY}}, shared::{square::Square, usize2d::Usize2d}, utils::clock::SystemClock, MemoryHandle, Panel};
+    /// # fn main() -> Result<(), PanelError> {
+    /// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+    /// # let (_frames, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+    /// # let (command_sender, command_receiver) = channel();
+    /// # let mut panel = Panel::init_with_clock(area, frame_receiver, command_receiver, Box::new(MemoryHandle::new()), Box::new(SystemClock))?;
+    /// # command_sender.send(PanelCommandEnum::KillProcess).unwrap();
+    /// while panel.tick()? {}
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tick(&mut self) -> Result<bool, PanelError> {
+        if let Ok(cmd) = self.command_receiver.try_recv() {
+            self.state.process_command(cmd, self.clock.now());
+        }
 
-            match self.frame_receiver.try_recv() {
-                Ok(render_objects) => match self.process_frame(render_objects) {
-                    Ok(_) => {}
-                    Err(e) => return Err(e),
-                },
-                Err(_) => {}
-            };
-            // TODO: self.render_frame();
-            // TODO: self.push_frame();
+        if self.state.is_killed {
+            return Ok(false);
+        }
+        if let Some(area) = self.state.new_area.take() {
+            self.move_to(area)?;
+        }
+        if self.state.is_hidden {
+            // The panels below draw over the area of a hidden panel
+            return Ok(true);
+        }
+        let is_redrawn = self.state.is_redraw_requested;
+        if is_redrawn {
+            self.redraw()?;
+        }
+        self.write_background(is_redrawn)?;
+        self.write_history(is_redrawn)?;
+        self.write_toasts()?;
+        if self.state.is_paused {
+            return Ok(true);
+        }
+
+        if let Ok(render_objects) = self.frame_receiver.try_recv_latest() {
+            self.process_frame(render_objects)?;
+        }
+        // TODO: self.render_frame();
+        // TODO: self.push_frame();
+        Ok(true)
+    }
+
+    /// Move the panel to a new area of the screen, clearing the area it used to occupy unless the
+    /// panel is hidden
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - the new area of the panel
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let window= Window::init(...);
+    /// window.move_to(Square::new(Usize2d::new(10, 0), Usize2d::new(20, 10)));
+    /// ```
+    fn move_to(&mut self, area: Square) -> Result<(), PanelError> {
+        let old_area = self.area.clone();
+        if !self.state.is_hidden {
+            self.clear_area(&old_area, &Pixel::default())?;
+        }
+        self.area = area;
+        self.reset_frames();
+        // The recorded frames do not fit the new area
+        if let Some(history) = self.state.history.as_mut() {
+            history.clear();
         }
         Ok(())
     }
 
-    /// Process received frame data
+    /// Get the top left corner of content of a size in this panel, using the padding and the
+    /// alignment of the panel layout
     ///
     /// # Examples
     ///
     /// ```
+    /// # use std::sync::mpsc::channel;
+    /// # use tic_tac_toe2::{panel::{command_enum::PanelCommandEnum, errors::PanelError, frame_channel::{frame_channel, FRAME_QUEUE_CAPACITY}}, shared::{square::Square, usize2d::Usize2d}, utils::clock::SystemClock, MemoryHandle, Panel};
+    /// # fn main() -> Result<(), PanelError> {
+    /// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+    /// # let (_frames, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+    /// # let (command_sender, command_receiver) = channel();
+    /// # let mut panel = Panel::init_with_clock(area, frame_receiver, command_receiver, Box::new(MemoryHandle::new()), Box::new(SystemClock))?;
+    /// let origin = panel.content_origin(Usize2d::new(9, 5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn content_origin(&self, content: Usize2d) -> Coord {
+        self.state.layout.content_origin(&self.area, content)
+    }
+
+    /// Fill the previous and next frames with the background of the panel
+    fn reset_frames(&mut self) {
+        let new_state = Arc::new(vec![
+            vec![self.state.background.clone(); self.area.width()];
+            self.area.height()
+        ]);
+        self._previous_frame = new_state.clone();
+        self._next_frame = new_state;
+        self.drawn_areas.clear();
+        self.recorder = FrameRenderer::new(self.area.clone(), self.state.background.clone());
+    }
+
+    /// Clear the panel to its background and draw the title and toasts again
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
     /// let window= Window::init(...);
-    /// let render_objects = vec![
-    ///     RenderObject {
-    ///         coordinate: Usize2d::default(),
-    ///         sprite: vec![
-    ///             "a".to_string(),
-    ///             "b".to_string(),
-    ///             "c".to_string(),
-    ///         ]
-    ///     }
-    /// ];
-    /// window.process_frame(render_objects);
+    /// window.redraw();
     /// ```
-    pub fn process_frame(&mut self, _render_objects: Vec<RenderObject>) -> Result<(), PanelError> {
-        // TODO: write the objects to the panel
+    fn redraw(&mut self) -> Result<(), PanelError> {
+        let area = self.area.clone();
+        let background = self.state.background.clone();
+        self.clear_area(&area, &background)?;
+        self.reset_frames();
+        self.write_title()?;
+        self.state.toasts.invalidate();
+        self.state.is_redraw_requested = false;
+        let _ = self.handle.flush();
+        Ok(())
+    }
+
+    /// Write the title over the first row of the panel, if it has one
+    fn write_title(&mut self) -> Result<(), PanelError> {
+        let Some(title) = &self.state.title else {
+            return Ok(());
+        };
+        let (top_left, _) = self.area.get_boundary();
+        let text: String = format!(" {} ", title)
+            .chars()
+            .take(self.area.width())
+            .collect();
+        self.handle
+            .set_background_color(TerminalColors::White)
+            .map_err(|_| PanelError::WriteFailed)?;
+        self.handle
+            .set_foreground_color(TerminalColors::Black)
+            .map_err(|_| PanelError::WriteFailed)?;
+        self.handle
+            .write_to_location(text.as_bytes(), top_left)
+            .map_err(|_| PanelError::WriteLocationFailed)?;
         Ok(())
     }
 
+    /// Advance the background provider and write it beneath the content of the panel, with the
+    /// title and the toasts on top. The provider does not move while the panel is paused
+    ///
+    /// # Arguments
+    ///
+    /// * `is_redrawn` - if the panel was just cleared, which writes the background even if it did
+    ///   not change
+    fn write_background(&mut self, is_redrawn: bool) -> Result<(), PanelError> {
+        let Some(provider) = self.state.background_provider.clone() else {
+            return Ok(());
+        };
+        let size = Usize2d::new(self.area.width(), self.area.height());
+        let background = {
+            let mut provider = provider.lock().map_err(|_| PanelError::WriteFailed)?;
+            let is_changed = !self.state.is_paused && provider.tick();
+            if !is_changed && !is_redrawn {
+                return Ok(());
+            }
+            provider.frame(size)
+        };
+        // The content shows through wherever it is not the plain background
+        let mut frame = self.frame_pool.take(size.x, size.y, Pixel::default());
+        for ((pixels, content), background) in frame
+            .iter_mut()
+            .zip(self._next_frame.iter())
+            .zip(background)
+        {
+            for ((pixel, content), background) in pixels.iter_mut().zip(content).zip(background) {
+                *pixel = match *content == self.state.background {
+                    true => background,
+                    false => content.clone(),
+                };
+            }
+        }
+        let (top_left, _) = self.area.get_boundary();
+        write_frame_at(&frame, top_left, self.handle.as_mut())
+            .map_err(|_| PanelError::WriteFailed)?;
+        self.frame_pool.give(frame);
+        self.write_title()?;
+        self.state.toasts.invalidate();
+        Ok(())
+    }
+
+    /// Write the recorded frame that is reviewed over the content, with its number on the last
+    /// row. Once the review ends the newest frame is written back
+    ///
+    /// # Arguments
+    ///
+    /// * `is_redrawn` - if the panel was just cleared, which writes the reviewed frame again
+    fn write_history(&mut self, is_redrawn: bool) -> Result<(), PanelError> {
+        let Some(history) = self.state.history.as_mut() else {
+            return Ok(());
+        };
+        let is_shown_again = is_redrawn && history.is_reviewing();
+        if !history.take_changed() && !is_shown_again {
+            return Ok(());
+        }
+        let Some(frame) = history.shown() else {
+            return Ok(());
+        };
+        let mut frame = Arc::clone(frame);
+        if let Some((number, count)) = history.position() {
+            let label: Vec<Pixel> = format!(" Frame {}/{} ", number, count)
+                .chars()
+                .map(|ch| Pixel::new(ch, TerminalColors::White, TerminalColors::Black))
+                .collect();
+            let last_row = frame.len().saturating_sub(1);
+            // The label is written on a copy, the recorded frame is left as it is
+            write_pixels(Arc::make_mut(&mut frame), Usize2d::new(0, last_row), &label);
+        }
+        let (top_left, _) = self.area.get_boundary();
+        write_frame_at(&frame, top_left, self.handle.as_mut())
+            .map_err(|_| PanelError::WriteFailed)?;
+        self.state.toasts.invalidate();
+        let _ = self.handle.flush();
+        Ok(())
+    }
+
+    /// Overwrite an area of the screen with a pixel
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - the area to clear
+    /// * `pixel` - the character and colors to fill the area with
+    fn clear_area(&mut self, area: &Square, pixel: &Pixel) -> Result<(), PanelError> {
+        let (top_left, _) = area.get_boundary();
+        let empty = pixel.char().to_string().repeat(area.width());
+        self.handle
+            .set_background_color(pixel.background_color())
+            .map_err(|_| PanelError::WriteFailed)?;
+        self.handle
+            .set_foreground_color(pixel.foreground_color())
+            .map_err(|_| PanelError::WriteFailed)?;
+        for row in 0..area.height() {
+            self.handle
+                .write_to_location(empty.as_bytes(), top_left + Usize2d::new(0, row))
+                .map_err(|_| PanelError::WriteLocationFailed)?;
+        }
+        Ok(())
+    }
+
+    /// Write the toasts on top of the panel content if any toast was added or expired
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let window= Window::init(...);
+    /// window.write_toasts();
+    /// ```
+    fn write_toasts(&mut self) -> Result<(), PanelError> {
+        self.state.toasts.dismiss_expired(self.clock.now());
+        if !self.state.toasts.is_dirty() {
+            return Ok(());
+        }
+        self.state
+            .toasts
+            .write(self.handle.as_mut(), &self.area)
+            .map_err(|_| PanelError::WriteFailed)
+    }
+
+    /// Write the objects of a frame, after clearing the objects of the last frame to the
+    /// background. Objects that are outside of the panel are skipped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::mpsc::channel;
+    /// # use std::sync::Arc;
+    /// # use tic_tac_toe2::rendering::{render_object::{RenderObject, RenderObjects}, sprite::Sprite};
+    /// # use tic_tac_toe2::shared::usize2d::Coord;
+    /// # use tic_tac_toe2::{panel::{command_enum::PanelCommandEnum, errors::PanelError, frame_channel::{frame_channel, FRAME_QUEUE_CAPACITY}}, shared::{square::Square, usize2d::Usize2d}, utils::clock::SystemClock, MemoryHandle, Panel};
+    /// # fn main() -> Result<(), PanelError> {
+    /// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+    /// # let (_frames, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+    /// # let (command_sender, command_receiver) = channel();
+    /// # let mut panel = Panel::init_with_clock(area, frame_receiver, command_receiver, Box::new(MemoryHandle::new()), Box::new(SystemClock))?;
+    /// let render_objects: RenderObjects = Arc::new([
+    ///     RenderObject::new(Sprite::default(), Coord::new(1, 1)),
+    ///     RenderObject::new(Sprite::default(), Coord::new(5, 1)),
+    /// ]);
+    /// panel.process_frame(render_objects)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn process_frame(&mut self, render_objects: RenderObjects) -> Result<(), PanelError> {
+        render(self, &render_objects)
+    }
+
     /// Initialize and run on a new thread
     ///
     /// # Arguments
@@ -148,19 +503,23 @@ impl Panel {
     /// # Examples
     ///
     /// ```
-    /// let size = Usize2d::new(10, 69);
-    /// let top_left= Usize2d::new(0, 0);
-    /// let bottom_right= Usize2d::new(10, 69);
-    /// let bottom_right= Usize2d::new(10, 69);
-    /// let (_, frame_receiver) = channel();
-    /// let (_, command_receiver) = channel();
+    /// # use std::sync::mpsc::channel;
+    /// # use tic_tac_toe2::{panel::{command_enum::PanelCommandEnum, errors::PanelError, frame_channel::{frame_channel, FRAME_QUEUE_CAPACITY}}, shared::{square::Square, usize2d::Usize2d}, MemoryHandle, Panel};
+    /// # fn main() -> Result<(), PanelError> {
+    /// let area = Square::new(Usize2d::new(1, 1), Usize2d::new(10, 69));
+    /// let (_, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+    /// let (command_sender, command_receiver) = channel();
     ///
-    /// let handle= Window::init_run_async(size, top_left, bottom_right, frame_receiver, command_receiver);
-    /// handle.join().unwrap();
+    /// let handle = Box::new(MemoryHandle::new());
+    /// let panel = Panel::init_run_async(area, frame_receiver, command_receiver, handle)?;
+    /// command_sender.send(PanelCommandEnum::KillProcess).unwrap();
+    /// panel.join().unwrap();
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn init_run_async(
         area: Square,
-        frame_receiver: Receiver<Vec<RenderObject>>,
+        frame_receiver: FrameReceiver<RenderObjects>,
         command_receiver: Receiver<PanelCommandEnum>,
         handle: Box<dyn Handle>,
     ) -> Result<JoinHandle<()>, PanelError> {
@@ -178,24 +537,19 @@ impl Panel {
     ///
     /// # Returns
     ///
-    /// A result object indicating success
+    /// `Ok(true)` once the object was written, `Ok(false)` if its sprite has nothing to write in
+    /// the panel and `PanelError::OutOfBounds` if the object is outside of the panel
     ///
     /// # Example
-    /// ```
-    /// let panel = Panel::init();
-    /// let render_object = RenderObject::default();
-    /// let result = panel.write_object(render_object);
+    /// ```ignore
+    /// let mut panel = Panel::init();
+    /// let render_object = RenderObject::new(Sprite::default(), Coord::new(1, 1));
+    /// let result = panel.write_object(&render_object);
     /// assert!(result.is_ok());
     ///
     /// ```
-    fn write_object(mut self, render_object: RenderObject) -> Result<bool, PanelError> {
+    fn write_object(&mut self, render_object: &RenderObject) -> Result<bool, PanelError> {
         if !self.area.overlaps_with(&render_object.get_area()) {
-            println!(
-                "No overlap for object with coord: {}",
-                render_object.get_location()
-            );
-            println!("Panel area: {}", self.area);
-            println!("Object area: {}", render_object.get_area());
             return Err(PanelError::OutOfBounds);
         }
         let to_write: Vec<Vec<u8>> = match render_object.get_content_to_write(self.area.clone()) {
@@ -203,25 +557,55 @@ impl Panel {
             Err(_) => return Ok(false),
         };
 
-        for index in 0..to_write.len() {
-            let _ = self
-                .handle
-                .set_cursor_location(render_object.get_location() + Usize2d::new(0, index))
-                .map_err(|_| PanelError::WriteLocationFailed)?;
-
+        for (index, row) in to_write.iter().enumerate() {
             // TODO: Switch colors
-            println!("Coordinate: {}", render_object.get_location());
-            let _ = self
-                .handle
-                .write(&to_write[index])
+            self.handle
+                .write_to_location(row, render_object.get_location() + Usize2d::new(0, index))
                 .map_err(|_| PanelError::WriteFailed)?;
         }
-        let _ = self.handle.flush();
-
         Ok(true)
     }
 }
 
+/// The panels are the renderer of the terminal. A frame clears the objects of the last frame to
+/// the background and is flushed once it is finished
+impl Renderer for Panel {
+    fn begin_frame(&mut self) -> Result<(), PanelError> {
+        let background = self.state.background.clone();
+        for area in std::mem::take(&mut self.drawn_areas) {
+            if let Some(area) = area.intersection(&self.area) {
+                self.clear_area(&area, &background)?;
+            }
+        }
+        if self.state.history.is_some() {
+            self.recorder.begin_frame()?;
+        }
+        Ok(())
+    }
+    fn draw(&mut self, object: &RenderObject) -> Result<(), PanelError> {
+        match self.write_object(object) {
+            Ok(true) => self.drawn_areas.push(object.get_area()),
+            Ok(false) | Err(PanelError::OutOfBounds) => {}
+            Err(e) => return Err(e),
+        }
+        if self.state.history.is_some() {
+            self.recorder.draw(object)?;
+        }
+        Ok(())
+    }
+    /// Flush the frame, and keep it in the frame history while the frames are recorded
+    fn end_frame(&mut self) -> Result<(), PanelError> {
+        let _ = self.handle.flush();
+        if let Some(history) = self.state.history.as_mut() {
+            self.recorder.end_frame()?;
+            if let Some(frame) = self.recorder.frame() {
+                history.record(Arc::clone(frame));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -232,13 +616,19 @@ mod tests {
 
     use crate::{
         handler::{memory_handle::MemoryHandle, shared_handle::SharedHandle},
-        panel::command_enum::PanelCommandEnum,
-        rendering::{render_object::RenderObject, sprite::Sprite},
+        panel::{
+            background::{BackgroundProvider, SharedBackground},
+            command_enum::PanelCommandEnum,
+            frame_channel::{frame_channel, FRAME_QUEUE_CAPACITY},
+            layout::PanelLayout,
+        },
+        rendering::{colors::TerminalColors, render_object::RenderObject, sprite::Sprite},
         shared::{
-            frame::Pixel,
+            frame::{Frame, Pixel},
             square::Square,
             usize2d::{Coord, Usize2d},
         },
+        testing::driver::TestDriver,
     };
 
     use super::Panel;
@@ -249,7 +639,7 @@ mod tests {
         let bottom_right = Usize2d::new(10, 20);
         let square = Square::new(top_left, bottom_right);
         let handle = Box::new(MemoryHandle::new());
-        let (_, frame_receiver) = channel();
+        let (_, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
         let (_, command_receiver) = channel();
 
         let window = Panel::init(square, frame_receiver, command_receiver, handle);
@@ -258,19 +648,23 @@ mod tests {
         let expected = vec![vec![Pixel::default(); 11]; 21];
 
         assert_eq!(
-            window._previous_frame,
+            *window._previous_frame,
             expected,
             "Default initialization previous frame is wrong. Expected lenth: {}, Actual length: {}",
             expected.len(),
             window._previous_frame.len()
         );
         assert_eq!(
-            window._next_frame,
+            *window._next_frame,
             expected,
             "Default initialization for next frame is wrong. Expected lenth: {}, Actual length: {}",
             expected.len(),
             window._next_frame.len()
         );
+        assert!(
+            Arc::ptr_eq(&window._previous_frame, &window._next_frame),
+            "The frames share their pixels until one is changed"
+        );
     }
 
     #[test]
@@ -279,19 +673,15 @@ mod tests {
         let bottom_right = Usize2d::new(10, 20);
         let square = Square::new(top_left, bottom_right);
 
-        let handle = Box::new(MemoryHandle::new());
-        let (_, frame_receiver) = channel();
-        let (command_sender, command_receiver) = channel();
-
-        let handle = Panel::init_run_async(square, frame_receiver, command_receiver, handle);
-        let result = command_sender.send(PanelCommandEnum::KillProcess);
+        let (mut driver, _frame_sender) = TestDriver::panel(square, Duration::from_millis(16));
+        driver.advance(3);
         assert!(
-            !result.is_err(),
-            "There should be no bugs when sending the kill command"
+            !driver.is_finished(),
+            "The panel should run until it is killed"
         );
-        sleep(Duration::from_millis(100));
+        driver.press(PanelCommandEnum::KillProcess);
         assert!(
-            handle.unwrap().is_finished(),
+            driver.is_finished(),
             "The process should be completed due to the kill command"
         );
     }
@@ -303,7 +693,7 @@ mod tests {
         let square = Square::new(top_left, bottom_right);
 
         let handle = Box::new(MemoryHandle::new());
-        let (_frame_sender, frame_receiver) = channel();
+        let (_frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
         let (command_sender, command_receiver) = channel();
 
         let handle = Panel::init_run_async(square, frame_receiver, command_receiver, handle);
@@ -312,7 +702,7 @@ mod tests {
 
         let result = command_sender.send(PanelCommandEnum::KillProcess);
         assert!(
-            !result.is_err(),
+            result.is_ok(),
             "There should be no bugs when sending the kill command"
         );
         sleep(Duration::from_millis(100));
@@ -322,6 +712,33 @@ mod tests {
         );
     }
     #[test]
+    fn content_origin() {
+        let square = Square::new(Usize2d::new(2, 1), Usize2d::new(21, 10));
+        let (_, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let (command_sender, command_receiver) = channel();
+        let mut panel = Panel::init(
+            square,
+            frame_receiver,
+            command_receiver,
+            Box::new(MemoryHandle::new()),
+        )
+        .unwrap();
+        let content = Usize2d::new(4, 2);
+        let origin = panel.content_origin(content);
+        assert_eq!(
+            (origin.x, origin.y),
+            (2, 1),
+            "Content starts at the top left"
+        );
+
+        command_sender
+            .send(PanelCommandEnum::SetLayout(PanelLayout::centered(1)))
+            .unwrap();
+        assert_eq!(panel.tick(), Ok(true));
+        let origin = panel.content_origin(content);
+        assert_eq!((origin.x, origin.y), (10, 5), "Content is centered");
+    }
+    #[test]
     fn write_object() {
         let test_cases = vec![
             (
@@ -345,16 +762,17 @@ mod tests {
             let mem_handle = Arc::new(Mutex::new(MemoryHandle::new()));
 
             let handle = SharedHandle::init(mem_handle.clone());
-            let (_frame_sender, frame_receiver) = channel();
+            let (_frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
             let (_, command_receiver) = channel();
 
-            let panel = Panel::init(square, frame_receiver, command_receiver, Box::new(handle))
+            let mut panel = Panel::init(square, frame_receiver, command_receiver, Box::new(handle))
                 .expect("Failed to init the panel");
 
             let obj = RenderObject::new(Sprite::default(), object_coordinate);
             let _ = panel
-                .write_object(obj)
-                .expect(&format!("Test case {} failed to write object to handle", i)[..]);
+                .write_object(&obj)
+                .unwrap_or_else(|_| panic!("Test case {} failed to write object to handle", i));
+            let _ = panel.handle.flush();
 
             let actual_string = get_shared_mem_handle_content(mem_handle.clone());
 
@@ -365,6 +783,344 @@ mod tests {
             )
         }
     }
+    #[test]
+    fn writes_objects_across_frames() {
+        let square = Square::new(Usize2d::new(0, 0), Usize2d::new(9, 3));
+        let mem_handle = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let (_command_sender, command_receiver) = channel();
+        let mut panel = Panel::init(
+            square,
+            frame_receiver,
+            command_receiver,
+            Box::new(SharedHandle::init(mem_handle.clone())),
+        )
+        .expect("Failed to init the panel");
+
+        let test_cases = [
+            (
+                "two objects",
+                vec![Coord::new(0, 0), Coord::new(5, 0)],
+                "X X  X X\n X    X\nX X  X X",
+            ),
+            ("moved", vec![Coord::new(2, 1)], "\n  X X\n   X\n  X X"),
+            (
+                "cut off",
+                vec![Coord::new(8, 2)],
+                "\n\n        X\n         X",
+            ),
+            ("outside", vec![Coord::new(20, 20)], ""),
+        ];
+        for (test_case, locations, expected) in test_cases {
+            let objects: Vec<RenderObject> = locations
+                .into_iter()
+                .map(|location| RenderObject::new(Sprite::default(), location))
+                .collect();
+            frame_sender.send(objects.into()).unwrap();
+            assert_eq!(panel.tick(), Ok(true), "{}", test_case);
+            let content = get_shared_mem_handle_content(mem_handle.clone());
+            let content: Vec<&str> = content.lines().map(|line| line.trim_end()).collect();
+            assert_eq!(content.join("\n").trim_end(), expected, "{}", test_case);
+        }
+    }
+    #[test]
+    fn run_and_toast() {
+        let top_left = Usize2d::new(0, 0);
+        let bottom_right = Usize2d::new(19, 4);
+        let square = Square::new(top_left, bottom_right);
+        let mem_handle = Arc::new(Mutex::new(MemoryHandle::new()));
+
+        let handle = Box::new(SharedHandle::init(mem_handle.clone()));
+        let (_frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let (command_sender, command_receiver) = channel();
+
+        let handle = Panel::init_run_async(square, frame_receiver, command_receiver, handle);
+        let result = command_sender.send(PanelCommandEnum::ShowToast(
+            "saved".to_string(),
+            Duration::from_millis(200),
+        ));
+        assert!(result.is_ok());
+        sleep(Duration::from_millis(100));
+        let empty_row = " ".repeat(20);
+        assert_eq!(
+            get_shared_mem_handle_content(mem_handle.clone()),
+            format!(
+                "              saved {}",
+                format!("\n{}", empty_row).repeat(4)
+            )
+        );
+
+        sleep(Duration::from_millis(200));
+        assert_eq!(
+            get_shared_mem_handle_content(mem_handle.clone()),
+            format!("{}{}", empty_row, format!("\n{}", empty_row).repeat(4)),
+            "The toast should be cleared once it expired"
+        );
+
+        let result = command_sender.send(PanelCommandEnum::KillProcess);
+        assert!(result.is_ok());
+        sleep(Duration::from_millis(100));
+        assert!(handle.unwrap().is_finished());
+    }
+    #[test]
+    fn run_set_title_and_move() {
+        let square = Square::new(Usize2d::new(0, 0), Usize2d::new(9, 1));
+        let mem_handle = Arc::new(Mutex::new(MemoryHandle::new()));
+
+        let handle = Box::new(SharedHandle::init(mem_handle.clone()));
+        let (_frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let (command_sender, command_receiver) = channel();
+
+        let handle = Panel::init_run_async(square, frame_receiver, command_receiver, handle);
+        let result = command_sender.send(PanelCommandEnum::SetTitle("debug".to_string()));
+        assert!(result.is_ok());
+        sleep(Duration::from_millis(100));
+        assert_eq!(
+            get_shared_mem_handle_content(mem_handle.clone()),
+            " debug    \n          ",
+            "The title should be drawn in the first row of the panel"
+        );
+
+        let new_area = Square::new(Usize2d::new(2, 2), Usize2d::new(11, 3));
+        let result = command_sender.send(PanelCommandEnum::MoveTo(new_area));
+        assert!(result.is_ok());
+        sleep(Duration::from_millis(100));
+        assert_eq!(
+            get_shared_mem_handle_content(mem_handle.clone()),
+            "          \n          \n   debug    \n            ",
+            "The old area should be cleared and the title drawn in the new area"
+        );
+
+        let result = command_sender.send(PanelCommandEnum::KillProcess);
+        assert!(result.is_ok());
+        sleep(Duration::from_millis(100));
+        assert!(handle.unwrap().is_finished());
+    }
+    #[test]
+    fn run_with_background() {
+        let square = Square::new(Usize2d::new(1, 0), Usize2d::new(3, 1));
+        let mem_handle = Arc::new(Mutex::new(MemoryHandle::new()));
+
+        let handle = Box::new(SharedHandle::init(mem_handle.clone()));
+        let (_frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let (command_sender, command_receiver) = channel();
+
+        let handle = Panel::init_run_async(square, frame_receiver, command_receiver, handle);
+        sleep(Duration::from_millis(100));
+        assert_eq!(
+            get_shared_mem_handle_content(mem_handle.clone()),
+            "    \n    ",
+            "The panel should clear its area when it starts"
+        );
+
+        let background = Pixel::new('.', TerminalColors::Black, TerminalColors::White);
+        let result = command_sender.send(PanelCommandEnum::SetBackground(background));
+        assert!(result.is_ok());
+        sleep(Duration::from_millis(100));
+        assert_eq!(
+            get_shared_mem_handle_content(mem_handle.clone()),
+            " ...\n ...",
+            "The panel should be filled with its background"
+        );
+        let background_colors = mem_handle.lock().unwrap().get_background_colors();
+        assert_eq!(background_colors[0][0], TerminalColors::Default);
+        assert_eq!(background_colors[0][1], TerminalColors::Black);
+
+        let result = command_sender.send(PanelCommandEnum::KillProcess);
+        assert!(result.is_ok());
+        sleep(Duration::from_millis(100));
+        assert!(handle.unwrap().is_finished());
+    }
+    #[test]
+    fn pause_and_resume() {
+        let square = Square::new(Usize2d::new(0, 0), Usize2d::new(10, 10));
+        let (frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let (command_sender, command_receiver) = channel();
+        let mut panel = Panel::init(
+            square,
+            frame_receiver,
+            command_receiver,
+            Box::new(MemoryHandle::new()),
+        )
+        .expect("Failed to init the panel");
+
+        let _ = command_sender.send(PanelCommandEnum::PauseProcess);
+        let _ = frame_sender.send(Arc::new([RenderObject::new(
+            Sprite::default(),
+            Coord::new(1, 1),
+        )]));
+        let _ = command_sender.send(PanelCommandEnum::KillProcess);
+        let result = panel.run();
+        assert!(result.is_ok());
+        assert!(
+            panel.frame_receiver.try_recv().is_ok(),
+            "A paused panel should not process frames"
+        );
+    }
+    #[test]
+    fn skips_stale_frames() {
+        let square = Square::new(Usize2d::new(0, 0), Usize2d::new(4, 1));
+        let (frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let (_command_sender, command_receiver) = channel();
+        let mut panel = Panel::init(
+            square,
+            frame_receiver,
+            command_receiver,
+            Box::new(MemoryHandle::new()),
+        )
+        .expect("Failed to init the panel");
+
+        for x in 0..3 {
+            let _ = frame_sender.send(Arc::new([RenderObject::new(
+                Sprite::default(),
+                Coord::new(x, 0),
+            )]));
+        }
+        assert_eq!(panel.tick(), Ok(true));
+        assert!(
+            panel.frame_receiver.try_recv().is_err(),
+            "Only the latest frame is processed"
+        );
+        assert_eq!(
+            frame_sender.take_dropped(),
+            2,
+            "One frame is dropped when sending to the full channel and one is skipped"
+        );
+    }
+    #[test]
+    fn review_recorded_frames() {
+        let square = Square::new(Usize2d::new(0, 0), Usize2d::new(9, 3));
+        let mem_handle = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let (command_sender, command_receiver) = channel();
+        let mut panel = Panel::init(
+            square,
+            frame_receiver,
+            command_receiver,
+            Box::new(SharedHandle::init(mem_handle.clone())),
+        )
+        .expect("Failed to init the panel");
+        command_sender
+            .send(PanelCommandEnum::RecordFrames(Some(5)))
+            .unwrap();
+        for x in [0, 5] {
+            frame_sender
+                .send(Arc::new([RenderObject::new(
+                    Sprite::default(),
+                    Coord::new(x, 0),
+                )]))
+                .unwrap();
+            assert_eq!(panel.tick(), Ok(true));
+        }
+
+        let newest = "     X X\n      X\n     X X";
+        let test_cases = [
+            (
+                "paused",
+                PanelCommandEnum::PauseProcess,
+                newest.to_string(),
+                TerminalColors::Default,
+            ),
+            (
+                "review",
+                PanelCommandEnum::ReviewFrames,
+                format!("{}\n Frame 2/2", newest),
+                TerminalColors::White,
+            ),
+            (
+                "scrub",
+                PanelCommandEnum::ScrubFrames(-1),
+                "X X\n X\nX X\n Frame 1/2".to_string(),
+                TerminalColors::White,
+            ),
+            (
+                "resume",
+                PanelCommandEnum::ResumeProcess,
+                newest.to_string(),
+                TerminalColors::Default,
+            ),
+        ];
+        for (test_case, command, expected, label_background) in test_cases {
+            command_sender.send(command).unwrap();
+            assert_eq!(panel.tick(), Ok(true), "{}", test_case);
+            let content = get_shared_mem_handle_content(mem_handle.clone());
+            let content: Vec<&str> = content.lines().map(|line| line.trim_end()).collect();
+            assert_eq!(content.join("\n").trim_end(), expected, "{}", test_case);
+            let background_colors = mem_handle.lock().unwrap().get_background_colors();
+            assert_eq!(background_colors[3][1], label_background, "{}", test_case);
+        }
+    }
+    #[test]
+    fn background_provider() {
+        /// Fills the area with the number of ticks it has seen
+        #[derive(Debug, Default)]
+        struct Counter {
+            ticks: u32,
+        }
+        impl BackgroundProvider for Counter {
+            fn tick(&mut self) -> bool {
+                self.ticks += 1;
+                true
+            }
+            fn frame(&self, size: Usize2d) -> Frame {
+                let digit = char::from_digit(self.ticks % 10, 10).unwrap();
+                vec![
+                    vec![Pixel::new(digit, TerminalColors::Default, TerminalColors::Grey); size.x];
+                    size.y
+                ]
+            }
+        }
+
+        let square = Square::new(Usize2d::new(0, 0), Usize2d::new(9, 1));
+        let mem_handle = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (_frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let (command_sender, command_receiver) = channel();
+        let mut panel = Panel::init(
+            square,
+            frame_receiver,
+            command_receiver,
+            Box::new(SharedHandle::init(mem_handle.clone())),
+        )
+        .expect("Failed to init the panel");
+        let provider: SharedBackground = Arc::new(Mutex::new(Counter::default()));
+        let test_cases = [
+            (
+                "provider",
+                Some(PanelCommandEnum::SetBackgroundProvider(Some(provider))),
+                "1111111111\n1111111111",
+            ),
+            (
+                "title on top",
+                Some(PanelCommandEnum::SetTitle("menu".to_string())),
+                " menu 2222\n2222222222",
+            ),
+            ("animates", None, " menu 3333\n3333333333"),
+            (
+                "paused",
+                Some(PanelCommandEnum::PauseProcess),
+                " menu 3333\n3333333333",
+            ),
+            (
+                "removed",
+                Some(PanelCommandEnum::SetBackgroundProvider(None)),
+                " menu     \n          ",
+            ),
+        ];
+        for (test_case, command, expected) in test_cases {
+            if let Some(command) = command {
+                command_sender.send(command).unwrap();
+            }
+            assert_eq!(panel.tick(), Ok(true), "{}", test_case);
+            assert_eq!(
+                get_shared_mem_handle_content(mem_handle.clone()),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+        let foreground_colors = mem_handle.lock().unwrap().get_foreground_colors();
+        assert_eq!(foreground_colors[1][0], TerminalColors::Default);
+    }
     fn get_shared_mem_handle_content(handle: Arc<Mutex<MemoryHandle>>) -> String {
         let locked_writer_result = handle.lock();
         let guard = locked_writer_result.unwrap();
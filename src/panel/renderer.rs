@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use crate::{
+    rendering::render_object::RenderObject,
+    shared::{
+        frame::{Pixel, SharedFrame},
+        square::Square,
+    },
+};
+
+use super::errors::PanelError;
+
+/// A front-end that draws the objects of the frames of a game, like a panel of the terminal, an
+/// image export, a broadcast to spectators or a test
+///
+/// A frame is drawn by `begin_frame`, a `draw` for every object from the bottom to the top and
+/// `end_frame`, see `render`. `Panel` draws to the terminal
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{panel::{errors::PanelError, renderer::{render, FrameRenderer}}, rendering::render_object::RenderObject, shared::{square::Square, usize2d::Usize2d}, Pixel};
+/// # fn main() -> Result<(), PanelError> {
+/// # let area = Square::new(Usize2d::new(1, 1), Usize2d::new(20, 10));
+/// # let objects: Vec<RenderObject> = Vec::new();
+/// let mut renderer = FrameRenderer::new(area.clone(), Pixel::default());
+/// render(&mut renderer, &objects)?;
+/// let frame = renderer.frame().unwrap();
+/// assert_eq!(frame.len(), area.height());
+/// # Ok(())
+/// # }
+/// ```
+pub trait Renderer {
+    /// Start a frame, the objects of the last frame are forgotten
+    fn begin_frame(&mut self) -> Result<(), PanelError>;
+    /// Draw an object over the objects that were drawn before it in this frame. An object that
+    /// does not fit is clipped, an object outside of the renderer is skipped
+    fn draw(&mut self, object: &RenderObject) -> Result<(), PanelError>;
+    /// Finish a frame, it is shown or kept once it is finished
+    fn end_frame(&mut self) -> Result<(), PanelError>;
+}
+
+/// Draw a frame with a renderer, from the first object to the last
+pub fn render(renderer: &mut dyn Renderer, objects: &[RenderObject]) -> Result<(), PanelError> {
+    renderer.begin_frame()?;
+    for object in objects {
+        renderer.draw(object)?;
+    }
+    renderer.end_frame()
+}
+
+/// Draws the frames in memory with their colors, for the image exports and for tests
+#[derive(Debug)]
+pub struct FrameRenderer {
+    /// The area of the screen that is drawn, the objects are placed in screen coordinates
+    area: Square,
+    background: Pixel,
+    /// The frame that is being drawn
+    next: SharedFrame,
+    /// The latest frame that was finished
+    finished: Option<SharedFrame>,
+    frames: u64,
+}
+impl FrameRenderer {
+    /// Create a renderer for an area of the screen
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - the area, like the area of a panel
+    /// * `background` - the pixel where no object is drawn
+    pub fn new(area: Square, background: Pixel) -> Self {
+        FrameRenderer {
+            next: Arc::new(vec![vec![background.clone(); area.width()]; area.height()]),
+            area,
+            background,
+            finished: None,
+            frames: 0,
+        }
+    }
+    /// Get the latest frame that was finished, `None` before the first one. Cloning it does not
+    /// copy the pixels
+    pub fn frame(&self) -> Option<&SharedFrame> {
+        self.finished.as_ref()
+    }
+    /// Get the number of frames that were finished
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+}
+impl Renderer for FrameRenderer {
+    fn begin_frame(&mut self) -> Result<(), PanelError> {
+        for row in Arc::make_mut(&mut self.next).iter_mut() {
+            row.fill(self.background.clone());
+        }
+        Ok(())
+    }
+    fn draw(&mut self, object: &RenderObject) -> Result<(), PanelError> {
+        let Some(visible) = self.area.intersection(&object.get_area()) else {
+            return Ok(());
+        };
+        let (top_left, bottom_right) = visible.get_boundary();
+        let (area_top_left, _) = self.area.get_boundary();
+        let location = object.get_location();
+        let next = Arc::make_mut(&mut self.next);
+        for y in top_left.y..=bottom_right.y {
+            for x in top_left.x..=bottom_right.x {
+                if let Some(pixel) = object.sprite().pixel(x - location.x, y - location.y) {
+                    next[y - area_top_left.y][x - area_top_left.x] = pixel;
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Keep the frame without copying it. The next frame is drawn over the frame before it,
+    /// unless that frame is still held, like by a frame history
+    fn end_frame(&mut self) -> Result<(), PanelError> {
+        let drawn = std::mem::replace(&mut self.next, Arc::new(Vec::new()));
+        self.next = match self.finished.replace(drawn) {
+            Some(previous) if Arc::strong_count(&previous) == 1 => previous,
+            _ => Arc::new(vec![
+                vec![self.background.clone(); self.area.width()];
+                self.area.height()
+            ]),
+        };
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        panel::errors::PanelError,
+        rendering::{colors::TerminalColors as TC, render_object::RenderObject, sprite::Sprite},
+        shared::{
+            frame::Pixel,
+            square::Square,
+            usize2d::{Coord, Usize2d},
+        },
+    };
+
+    use super::{render, FrameRenderer, Renderer};
+
+    /// Remembers the calls it got, to check the order `render` calls them in
+    #[derive(Default)]
+    struct CallLog(Vec<String>);
+    impl Renderer for CallLog {
+        fn begin_frame(&mut self) -> Result<(), PanelError> {
+            self.0.push("begin".to_string());
+            Ok(())
+        }
+        fn draw(&mut self, object: &RenderObject) -> Result<(), PanelError> {
+            let location = object.get_location();
+            self.0.push(format!("draw {},{}", location.x, location.y));
+            Ok(())
+        }
+        fn end_frame(&mut self) -> Result<(), PanelError> {
+            self.0.push("end".to_string());
+            Ok(())
+        }
+    }
+
+    fn chars(renderer: &FrameRenderer) -> Vec<String> {
+        renderer
+            .frame()
+            .unwrap()
+            .iter()
+            .map(|row| row.iter().map(Pixel::char).collect())
+            .collect()
+    }
+
+    #[test]
+    fn renders_frames() {
+        let mut log = CallLog::default();
+        let objects = [
+            RenderObject::new(Sprite::default(), Coord::new(1, 1)),
+            RenderObject::new(Sprite::default(), Coord::new(3, 2)),
+        ];
+        render(&mut log, &objects).unwrap();
+        assert_eq!(log.0, ["begin", "draw 1,1", "draw 3,2", "end"]);
+
+        let area = Square::new(Usize2d::new(1, 1), Usize2d::new(5, 3));
+        let mut renderer = FrameRenderer::new(area, Pixel::new('.', TC::Black, TC::White));
+        assert_eq!(renderer.frame(), None);
+        let test_cases = [
+            (
+                "the later object on top, clipped to the area",
+                vec![Coord::new(1, 1), Coord::new(3, 2), Coord::new(9, 9)],
+                ["X.X..", ".XX.X", "X.XX."],
+            ),
+            (
+                "the last frame is cleared",
+                vec![Coord::new(4, 0)],
+                ["....X", "...X.", "....."],
+            ),
+        ];
+        for (frames, (test_case, locations, expected)) in test_cases.into_iter().enumerate() {
+            let objects: Vec<RenderObject> = locations
+                .into_iter()
+                .map(|location| RenderObject::new(Sprite::default(), location))
+                .collect();
+            render(&mut renderer, &objects).unwrap();
+            assert_eq!(chars(&renderer), expected, "{}", test_case);
+            assert_eq!(renderer.frames(), frames as u64 + 1, "{}", test_case);
+        }
+        let frame = renderer.frame().unwrap();
+        assert_eq!(frame[0][4].background_color(), TC::Red, "The sprite colors");
+        assert_eq!(frame[0][0].background_color(), TC::Black, "The background");
+    }
+
+    #[test]
+    fn keeps_held_frames() {
+        let area = Square::new(Usize2d::new(0, 0), Usize2d::new(2, 0));
+        let mut renderer = FrameRenderer::new(area, Pixel::new('.', TC::Black, TC::White));
+        let sprite = |location| [RenderObject::new(Sprite::default(), location)];
+        render(&mut renderer, &sprite(Coord::new(0, 0))).unwrap();
+        let held = Arc::clone(renderer.frame().unwrap());
+        for location in [Coord::new(1, 0), Coord::new(2, 0)] {
+            render(&mut renderer, &sprite(location)).unwrap();
+        }
+        assert_eq!(chars(&renderer), ["..X"]);
+        let row: String = held[0].iter().map(Pixel::char).collect();
+        assert_eq!(row, "X.X", "A frame that is held is not drawn over");
+        assert!(!Arc::ptr_eq(&held, renderer.frame().unwrap()));
+    }
+}
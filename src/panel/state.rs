@@ -1,31 +1,218 @@
-use crate::shared::usize2d::Usize2d;
+use std::time::Instant;
 
-use super::command_enum::PanelCommandEnum;
+use crate::shared::{frame::Pixel, square::Square, usize2d::Usize2d};
 
-#[derive(Debug)]
+use super::{
+    background::SharedBackground, command_enum::PanelCommandEnum, frame_history::FrameHistory,
+    layout::PanelLayout, toast::ToastLayer,
+};
+
+#[derive(Debug, Default)]
 pub struct PanelState {
     pub is_paused: bool,
     pub is_killed: bool,
     pub is_cleared: bool,
+    pub is_redraw_requested: bool,
+    /// A hidden panel does not draw anything until it is shown again
+    pub is_hidden: bool,
     pub new_buffer_size: Usize2d,
-}
-impl Default for PanelState {
-    fn default() -> Self {
-        PanelState {
-            is_paused: false,
-            is_killed: false,
-            is_cleared: false,
-            new_buffer_size: Usize2d::default(),
-        }
-    }
+    pub new_area: Option<Square>,
+    pub title: Option<String>,
+    pub background: Pixel,
+    /// The animation drawn wherever the content is the background pixel
+    pub background_provider: Option<SharedBackground>,
+    /// The padding and alignment of content that is smaller than the panel
+    pub layout: PanelLayout,
+    pub toasts: ToastLayer,
+    /// The latest frames, while they are recorded
+    pub history: Option<FrameHistory>,
 }
 impl PanelState {
-    pub fn process_command(&mut self, command: PanelCommandEnum) {
+    /// Update the state with a command
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - the command received by the panel
+    /// * `now` - the current time of the panel clock, used to time toasts
+    pub fn process_command(&mut self, command: PanelCommandEnum, now: Instant) {
         match command {
             PanelCommandEnum::KillProcess => self.is_killed = true,
             PanelCommandEnum::PauseProcess => self.is_paused = true,
+            PanelCommandEnum::ResumeProcess => {
+                self.is_paused = false;
+                if let Some(history) = self.history.as_mut() {
+                    history.end_review();
+                }
+            }
             PanelCommandEnum::ClearBuffer => self.is_cleared = true,
             PanelCommandEnum::ResizeBuffer => self.is_cleared = true,
+            PanelCommandEnum::ForceRedraw => self.is_redraw_requested = true,
+            PanelCommandEnum::SetTitle(title) => {
+                self.title = Some(title);
+                self.is_redraw_requested = true;
+            }
+            PanelCommandEnum::MoveTo(area) => {
+                self.new_area = Some(area);
+                self.is_redraw_requested = true;
+            }
+            PanelCommandEnum::SetBackground(background) => {
+                self.background = background;
+                self.is_redraw_requested = true;
+            }
+            PanelCommandEnum::SetBackgroundProvider(provider) => {
+                self.background_provider = provider;
+                self.is_redraw_requested = true;
+            }
+            PanelCommandEnum::SetLayout(layout) => {
+                self.layout = layout;
+                self.is_redraw_requested = true;
+            }
+            PanelCommandEnum::Show => {
+                self.is_hidden = false;
+                self.is_redraw_requested = true;
+            }
+            PanelCommandEnum::Hide => self.is_hidden = true,
+            PanelCommandEnum::ShowToast(message, duration) => {
+                self.toasts.push(message, duration, now)
+            }
+            PanelCommandEnum::RecordFrames(capacity) => {
+                // The content below a review that is cut short is drawn again
+                if self
+                    .history
+                    .as_ref()
+                    .is_some_and(FrameHistory::is_reviewing)
+                {
+                    self.is_redraw_requested = true;
+                }
+                self.history = capacity.map(FrameHistory::new);
+            }
+            PanelCommandEnum::ReviewFrames => {
+                if let (true, Some(history)) = (self.is_paused, self.history.as_mut()) {
+                    history.toggle_review();
+                }
+            }
+            PanelCommandEnum::ScrubFrames(frames) => {
+                if let Some(history) = self.history.as_mut() {
+                    history.scrub(frames);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use crate::{
+        panel::{command_enum::PanelCommandEnum, layout::PanelLayout},
+        rendering::colors::TerminalColors,
+        shared::{frame::Pixel, square::Square, usize2d::Usize2d},
+    };
+
+    use super::PanelState;
+
+    #[test]
+    fn pause_and_resume() {
+        let mut state = PanelState::default();
+        state.process_command(PanelCommandEnum::PauseProcess, Instant::now());
+        assert!(state.is_paused);
+        state.process_command(PanelCommandEnum::ResumeProcess, Instant::now());
+        assert!(!state.is_paused);
+    }
+
+    #[test]
+    fn show_and_hide() {
+        let mut state = PanelState::default();
+        state.process_command(PanelCommandEnum::Hide, Instant::now());
+        assert!(state.is_hidden);
+        assert!(!state.is_redraw_requested);
+        state.process_command(PanelCommandEnum::Show, Instant::now());
+        assert!(!state.is_hidden);
+        assert!(
+            state.is_redraw_requested,
+            "A panel that is shown again should draw itself"
+        );
+    }
+
+    #[test]
+    fn redraw_commands() {
+        let mut state = PanelState::default();
+        state.process_command(PanelCommandEnum::ForceRedraw, Instant::now());
+        assert!(state.is_redraw_requested);
+
+        let mut state = PanelState::default();
+        state.process_command(
+            PanelCommandEnum::SetTitle("debug".to_string()),
+            Instant::now(),
+        );
+        assert_eq!(state.title, Some("debug".to_string()));
+        assert!(
+            state.is_redraw_requested,
+            "A new title should trigger a redraw"
+        );
+
+        let mut state = PanelState::default();
+        let area = Square::new(Usize2d::new(1, 2), Usize2d::new(3, 4));
+        state.process_command(PanelCommandEnum::MoveTo(area), Instant::now());
+        let (top_left, bottom_right) = state.new_area.unwrap().get_boundary();
+        assert_eq!((top_left.x, top_left.y), (1, 2));
+        assert_eq!((bottom_right.x, bottom_right.y), (3, 4));
+        assert!(
+            state.is_redraw_requested,
+            "Moving the panel should trigger a redraw"
+        );
+
+        let mut state = PanelState::default();
+        let background = Pixel::new('.', TerminalColors::Black, TerminalColors::White);
+        state.process_command(
+            PanelCommandEnum::SetBackground(background.clone()),
+            Instant::now(),
+        );
+        assert_eq!(state.background, background);
+        assert!(
+            state.is_redraw_requested,
+            "A new background should trigger a redraw"
+        );
+
+        let mut state = PanelState::default();
+        let layout = PanelLayout::centered(1);
+        state.process_command(PanelCommandEnum::SetLayout(layout), Instant::now());
+        assert_eq!(state.layout, layout);
+        assert!(
+            state.is_redraw_requested,
+            "A new layout should trigger a redraw"
+        );
+    }
+
+    #[test]
+    fn review_frames() {
+        let mut state = PanelState::default();
+        let now = Instant::now();
+        state.process_command(PanelCommandEnum::RecordFrames(Some(4)), now);
+        let history = state.history.as_mut().unwrap();
+        history.record(vec![vec![Pixel::default()]].into());
+        history.record(vec![vec![Pixel::default()]].into());
+        let test_cases = [
+            ("running", PanelCommandEnum::ReviewFrames, None),
+            ("paused", PanelCommandEnum::PauseProcess, None),
+            ("review", PanelCommandEnum::ReviewFrames, Some((2, 2))),
+            ("scrub", PanelCommandEnum::ScrubFrames(-1), Some((1, 2))),
+            ("resume", PanelCommandEnum::ResumeProcess, None),
+        ];
+        for (test_case, command, position) in test_cases {
+            state.process_command(command, now);
+            let history = state.history.as_ref().unwrap();
+            assert_eq!(history.position(), position, "{}", test_case);
         }
+
+        state.process_command(PanelCommandEnum::PauseProcess, now);
+        state.process_command(PanelCommandEnum::ReviewFrames, now);
+        state.process_command(PanelCommandEnum::RecordFrames(None), now);
+        assert!(state.history.is_none());
+        assert!(
+            state.is_redraw_requested,
+            "The content below the review should be drawn again"
+        );
     }
 }
@@ -0,0 +1,329 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    handler::{handle::Handle, handle_error::HandleError},
+    rendering::colors::TerminalColors,
+    shared::{square::Square, usize2d::Coord},
+};
+
+/// A short message that is shown on top of the panel content until it expires
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub message: String,
+    expires_at: Instant,
+}
+
+/// The corner of the panel where toasts are stacked
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// The layer that holds all active toasts of a panel
+///
+/// Toasts are drawn after everything else in the panel so they always sit above the game content.
+/// The newest toast is drawn closest to the corner and older toasts are pushed further away from
+/// it. Each toast takes up one row of the panel
+#[derive(Debug)]
+pub struct ToastLayer {
+    toasts: VecDeque<Toast>,
+    corner: ToastCorner,
+    max_visible: usize,
+    foreground: TerminalColors,
+    background: TerminalColors,
+    drawn: Vec<(Coord, usize)>,
+    is_dirty: bool,
+}
+impl Default for ToastLayer {
+    fn default() -> Self {
+        ToastLayer::new(ToastCorner::TopRight)
+    }
+}
+impl ToastLayer {
+    /// Create a new layer without any toasts
+    ///
+    /// # Arguments
+    ///
+    /// * `corner` - the corner of the panel where the toasts are stacked
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::panel::toast::{ToastCorner, ToastLayer};
+    /// let layer = ToastLayer::new(ToastCorner::BottomRight);
+    /// ```
+    pub fn new(corner: ToastCorner) -> Self {
+        ToastLayer {
+            toasts: VecDeque::new(),
+            corner,
+            max_visible: 5,
+            foreground: TerminalColors::Black,
+            background: TerminalColors::White,
+            drawn: Vec::new(),
+            is_dirty: false,
+        }
+    }
+
+    /// Add a toast to the layer. If there are more toasts than can be shown the oldest toast is
+    /// dismissed
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - the text of the toast
+    /// * `duration` - how long the toast should be shown for
+    /// * `now` - the current time
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use tic_tac_toe2::panel::toast::{ToastCorner, ToastLayer};
+    /// # let mut layer = ToastLayer::new(ToastCorner::BottomRight);
+    /// layer.push("Saved to conway.cells".to_string(), Duration::from_secs(2), Instant::now());
+    /// ```
+    pub fn push(&mut self, message: String, duration: Duration, now: Instant) {
+        self.toasts.push_front(Toast {
+            message,
+            expires_at: now + duration,
+        });
+        self.toasts.truncate(self.max_visible);
+        self.is_dirty = true;
+    }
+
+    /// Remove all toasts that have expired
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - the current time
+    ///
+    /// # Returns
+    ///
+    /// `true` if any toast was removed
+    pub fn dismiss_expired(&mut self, now: Instant) -> bool {
+        let count = self.toasts.len();
+        self.toasts.retain(|toast| toast.expires_at > now);
+        let dismissed = self.toasts.len() != count;
+        self.is_dirty |= dismissed;
+        dismissed
+    }
+
+    /// Check if the layer changed since it was last written
+    pub fn is_dirty(&self) -> bool {
+        self.is_dirty
+    }
+
+    /// Forget what was drawn previously and draw the toasts again on the next write. Used when the
+    /// content below the toasts has been cleared
+    pub fn invalidate(&mut self) {
+        self.drawn.clear();
+        self.is_dirty = true;
+    }
+
+    /// Get the active toasts with the newest toast first
+    pub fn toasts(&self) -> &VecDeque<Toast> {
+        &self.toasts
+    }
+
+    /// Calculate where each toast should be drawn inside an area
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - the area of the panel
+    ///
+    /// # Returns
+    ///
+    /// The location and (clipped) text of each toast that fits inside the area
+    pub fn placements(&self, area: &Square) -> Vec<(Coord, String)> {
+        let (top_left, bottom_right) = area.get_boundary();
+        self.toasts
+            .iter()
+            .take(area.height())
+            .enumerate()
+            .map(|(index, toast)| {
+                let text: String = format!(" {} ", toast.message)
+                    .chars()
+                    .take(area.width())
+                    .collect();
+                let width = text.chars().count();
+                let x = match self.corner {
+                    ToastCorner::TopLeft | ToastCorner::BottomLeft => top_left.x,
+                    ToastCorner::TopRight | ToastCorner::BottomRight => bottom_right.x + 1 - width,
+                };
+                let y = match self.corner {
+                    ToastCorner::TopLeft | ToastCorner::TopRight => top_left.y + index,
+                    ToastCorner::BottomLeft | ToastCorner::BottomRight => bottom_right.y - index,
+                };
+                (Coord::new(x, y), text)
+            })
+            .collect()
+    }
+
+    /// Write the toasts to a handle, clearing the cells of toasts that were drawn previously
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - the handle to write to
+    /// * `area` - the area of the panel
+    ///
+    /// # Returns
+    ///
+    /// A result indicating if the write succeeded
+    pub fn write(&mut self, handle: &mut dyn Handle, area: &Square) -> Result<(), HandleError> {
+        handle.set_background_color(TerminalColors::Default)?;
+        handle.set_foreground_color(TerminalColors::Default)?;
+        for (coord, width) in self.drawn.drain(..) {
+            handle.write_to_location(" ".repeat(width).as_bytes(), coord)?;
+        }
+
+        handle.set_background_color(self.background)?;
+        handle.set_foreground_color(self.foreground)?;
+        for (coord, text) in self.placements(area) {
+            handle.write_to_location(text.as_bytes(), coord)?;
+            self.drawn.push((coord, text.chars().count()));
+        }
+        self.is_dirty = false;
+        handle.flush().map_err(|_| HandleError::WriteFailed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        handler::{memory_handle::MemoryHandle, shared_handle::SharedHandle},
+        shared::{
+            square::Square,
+            usize2d::{Coord, Usize2d},
+        },
+    };
+
+    use super::{ToastCorner, ToastLayer};
+
+    #[test]
+    fn expire() {
+        let now = Instant::now();
+        let mut layer = ToastLayer::default();
+        layer.push("first".to_string(), Duration::from_secs(1), now);
+        layer.push("second".to_string(), Duration::from_secs(3), now);
+        assert!(layer.is_dirty());
+
+        assert!(!layer.dismiss_expired(now + Duration::from_millis(500)));
+        assert_eq!(layer.toasts().len(), 2);
+
+        assert!(layer.dismiss_expired(now + Duration::from_secs(2)));
+        assert_eq!(layer.toasts().len(), 1);
+        assert_eq!(layer.toasts()[0].message, "second");
+
+        assert!(layer.dismiss_expired(now + Duration::from_secs(3)));
+        assert!(layer.toasts().is_empty());
+    }
+
+    #[test]
+    fn stack_limit() {
+        let now = Instant::now();
+        let mut layer = ToastLayer::default();
+        for i in 0..7 {
+            layer.push(format!("toast {}", i), Duration::from_secs(1), now);
+        }
+        assert_eq!(layer.toasts().len(), 5);
+        assert_eq!(
+            layer.toasts()[0].message,
+            "toast 6",
+            "The newest toast comes first"
+        );
+        assert_eq!(
+            layer.toasts()[4].message,
+            "toast 2",
+            "The oldest toasts are dropped"
+        );
+    }
+
+    #[test]
+    fn placements() {
+        let now = Instant::now();
+        let area = Square::new(Usize2d::new(2, 3), Usize2d::new(21, 12));
+        let test_cases = vec![
+            (ToastCorner::TopLeft, Coord::new(2, 3), Coord::new(2, 4)),
+            (ToastCorner::TopRight, Coord::new(17, 3), Coord::new(15, 4)),
+            (
+                ToastCorner::BottomLeft,
+                Coord::new(2, 12),
+                Coord::new(2, 11),
+            ),
+            (
+                ToastCorner::BottomRight,
+                Coord::new(17, 12),
+                Coord::new(15, 11),
+            ),
+        ];
+
+        for (i, (corner, newest, oldest)) in test_cases.into_iter().enumerate() {
+            let mut layer = ToastLayer::new(corner);
+            layer.push("first".to_string(), Duration::from_secs(1), now);
+            layer.push("new".to_string(), Duration::from_secs(1), now);
+            let placements = layer.placements(&area);
+
+            assert_eq!(placements[0].1, " new ", "Test case {}", i);
+            assert_eq!(
+                (placements[0].0.x, placements[0].0.y),
+                (newest.x, newest.y),
+                "Test case {}: the newest toast is in the wrong place",
+                i
+            );
+            assert_eq!(
+                (placements[1].0.x, placements[1].0.y),
+                (oldest.x, oldest.y),
+                "Test case {}: the oldest toast is in the wrong place",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn placements_clip_to_area() {
+        let area = Square::new(Usize2d::new(0, 0), Usize2d::new(4, 0));
+        let mut layer = ToastLayer::new(ToastCorner::TopLeft);
+        layer.push("first".to_string(), Duration::from_secs(1), Instant::now());
+        layer.push("second".to_string(), Duration::from_secs(1), Instant::now());
+
+        let placements = layer.placements(&area);
+        assert_eq!(placements.len(), 1, "Only one toast fits in a single row");
+        assert_eq!(placements[0].1, " seco");
+    }
+
+    #[test]
+    fn write_and_clear() {
+        let now = Instant::now();
+        let area = Square::new(Usize2d::new(0, 0), Usize2d::new(9, 2));
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let mut handle = SharedHandle::init(buffer.clone());
+        let mut layer = ToastLayer::new(ToastCorner::TopLeft);
+
+        layer.push("saved".to_string(), Duration::from_secs(1), now);
+        let result = layer.write(&mut handle, &area);
+        assert!(result.is_ok());
+        assert!(!layer.is_dirty());
+        let content = buffer.lock().unwrap().get_buffer_content();
+        assert_eq!(String::from_utf8_lossy(&content), " saved ");
+
+        layer.dismiss_expired(now + Duration::from_secs(1));
+        let result = layer.write(&mut handle, &area);
+        assert!(result.is_ok());
+        let content = buffer.lock().unwrap().get_buffer_content();
+        assert_eq!(
+            String::from_utf8_lossy(&content),
+            "       ",
+            "The cells of the expired toast should be cleared"
+        );
+    }
+}
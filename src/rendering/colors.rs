@@ -1,21 +1,95 @@
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
 pub enum TerminalColors {
     LightGreen = 120,
     Red = 160,
     White = 231,
     Black = 232,
     HotPink = 200,
+    Yellow = 226,
+    DarkBlue = 18,
+    Blue = 27,
+    Cyan = 45,
+    Orange = 208,
+    DarkGrey = 236,
+    Grey = 242,
+    #[default]
     Default = -1,
 }
-impl Default for TerminalColors {
-    fn default() -> Self {
-        TerminalColors::Default
-    }
-}
 impl Display for TerminalColors {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
         write!(f, "{:?}", self)
     }
 }
+
+/// The colors of the decay states of a Generations rule, from just dying to almost dead
+pub const DECAY_PALETTE: [TerminalColors; 5] = [
+    TerminalColors::Yellow,
+    TerminalColors::Orange,
+    TerminalColors::Red,
+    TerminalColors::Grey,
+    TerminalColors::DarkGrey,
+];
+
+/// The colors from cold to hot that are used for heatmaps
+pub const HEAT_GRADIENT: [TerminalColors; 8] = [
+    TerminalColors::Black,
+    TerminalColors::DarkBlue,
+    TerminalColors::Blue,
+    TerminalColors::Cyan,
+    TerminalColors::LightGreen,
+    TerminalColors::Yellow,
+    TerminalColors::Orange,
+    TerminalColors::Red,
+];
+
+/// Pick the color at a point along a gradient
+///
+/// # Arguments
+///
+/// * `colors` - the colors of the gradient, from the start to the end
+/// * `fraction` - the point along the gradient, `0.0` is the first color and `1.0` the last
+///
+/// # Examples
+///
+/// ```
+/// # use tic_tac_toe2::rendering::colors::{gradient, HEAT_GRADIENT};
+/// let color = gradient(&HEAT_GRADIENT, 0.5);
+/// ```
+pub fn gradient(colors: &[TerminalColors], fraction: f32) -> TerminalColors {
+    assert!(!colors.is_empty());
+    let last = colors.len() - 1;
+    let index = (fraction.clamp(0.0, 1.0) * last as f32).round() as usize;
+    colors[index.min(last)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gradient, TerminalColors as TC, HEAT_GRADIENT};
+
+    #[test]
+    fn gradient_picks_nearest_color() {
+        let colors = [TC::Black, TC::Blue, TC::White];
+        let test_cases = vec![
+            (1, 0.0, TC::Black),
+            (2, 0.2, TC::Black),
+            (3, 0.3, TC::Blue),
+            (4, 0.5, TC::Blue),
+            (5, 0.8, TC::White),
+            (6, 1.0, TC::White),
+            (7, -1.0, TC::Black),
+            (8, 2.0, TC::White),
+        ];
+        for (test_case, fraction, expected) in test_cases {
+            assert_eq!(
+                gradient(&colors, fraction),
+                expected,
+                "Test case {}: The color at {} is wrong",
+                test_case,
+                fraction
+            );
+        }
+        assert_eq!(gradient(&HEAT_GRADIENT, 1.0), TC::Red);
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt::Debug;
+
+/// A font made out of block characters where every glyph is a fixed number of terminal rows
+/// high and terminal columns wide
+///
+/// Glyphs are looked up by their lowercase character. Every row of a glyph should contain exactly
+/// `width` characters so that messages line up
+pub trait BlockFont: Debug + Sync {
+    /// The name of the font
+    fn name(&self) -> &'static str;
+    /// The number of terminal rows every glyph takes up
+    fn height(&self) -> usize;
+    /// The number of terminal columns every glyph takes up, including the spacing between glyphs
+    fn width(&self) -> usize;
+    /// Get the rows that make up a character
+    ///
+    /// # Arguments
+    ///
+    /// * `ch` - the lowercase character to look up
+    ///
+    /// # Returns
+    ///
+    /// The rows of the glyph or `None` if the font does not support the character
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::rendering::fonts::{block_font::BlockFont, standard_font::STANDARD_FONT};
+    /// let rows = STANDARD_FONT.glyph('a').unwrap();
+    /// assert_eq!(rows.len(), STANDARD_FONT.height());
+    /// ```
+    fn glyph(&self, ch: char) -> Option<&'static [&'static str]>;
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::BlockFont;
+
+    /// Check that every supported glyph of a font has the dimensions the font claims to have
+    pub fn assert_font_dimensions(font: &dyn BlockFont) {
+        let supported = " abcdefghijklmnopqrstuvwxyz0123456789";
+        for ch in supported.chars() {
+            let glyph = font.glyph(ch);
+            assert!(
+                glyph.is_some(),
+                "The font {} should support the character '{}'",
+                font.name(),
+                ch
+            );
+            let glyph = glyph.unwrap();
+            assert_eq!(
+                glyph.len(),
+                font.height(),
+                "The glyph '{}' in the font {} has the wrong height",
+                ch,
+                font.name()
+            );
+            for (i, row) in glyph.iter().enumerate() {
+                assert_eq!(
+                    row.chars().count(),
+                    font.width(),
+                    "Row {} of the glyph '{}' in the font {} has the wrong width",
+                    i,
+                    ch,
+                    font.name()
+                );
+            }
+        }
+        assert!(font.glyph('?').is_none());
+    }
+}
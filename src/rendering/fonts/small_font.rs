@@ -0,0 +1,264 @@
+use super::block_font::BlockFont;
+
+/// A compact 3 row font for status text, built from half block characters
+#[derive(Debug)]
+pub struct SmallFont;
+pub static SMALL_FONT: SmallFont = SmallFont;
+
+impl BlockFont for SmallFont {
+    fn name(&self) -> &'static str {
+        "small"
+    }
+    fn height(&self) -> usize {
+        3
+    }
+    fn width(&self) -> usize {
+        4
+    }
+    fn glyph(&self, ch: char) -> Option<&'static [&'static str]> {
+        let glyph: &'static [&'static str] = match ch {
+            ' ' => &SPACE,
+            'a' => &A,
+            'b' => &B,
+            'c' => &C,
+            'd' => &D,
+            'e' => &E,
+            'f' => &F,
+            'g' => &G,
+            'h' => &H,
+            'i' => &I,
+            'j' => &J,
+            'k' => &K,
+            'l' => &L,
+            'm' => &M,
+            'n' => &N,
+            'o' => &O,
+            'p' => &P,
+            'q' => &Q,
+            'r' => &R,
+            's' => &S,
+            't' => &T,
+            'u' => &U,
+            'v' => &V,
+            'w' => &W,
+            'x' => &X,
+            'y' => &Y,
+            'z' => &Z,
+            '0' => &NUM_0,
+            '1' => &NUM_1,
+            '2' => &NUM_2,
+            '3' => &NUM_3,
+            '4' => &NUM_4,
+            '5' => &NUM_5,
+            '6' => &NUM_6,
+            '7' => &NUM_7,
+            '8' => &NUM_8,
+            '9' => &NUM_9,
+            _ => return None,
+        };
+        Some(glyph)
+    }
+}
+const A: [&str; 3] = [
+    "▄▀▄ ", //
+    "█▀█ ", //
+    "▀ ▀ ", //
+];
+const B: [&str; 3] = [
+    "█▀▄ ", //
+    "█▀▄ ", //
+    "▀▀  ", //
+];
+const C: [&str; 3] = [
+    "▄▀▀ ", //
+    "█   ", //
+    " ▀▀ ", //
+];
+const D: [&str; 3] = [
+    "█▀▄ ", //
+    "█ █ ", //
+    "▀▀  ", //
+];
+const E: [&str; 3] = [
+    "█▀▀ ", //
+    "█▀  ", //
+    "▀▀▀ ", //
+];
+const F: [&str; 3] = [
+    "█▀▀ ", //
+    "█▀  ", //
+    "▀   ", //
+];
+const G: [&str; 3] = [
+    "▄▀▀ ", //
+    "█ ▄ ", //
+    " ▀▀ ", //
+];
+const H: [&str; 3] = [
+    "█ █ ", //
+    "█▀█ ", //
+    "▀ ▀ ", //
+];
+const I: [&str; 3] = [
+    "▀█▀ ", //
+    " █  ", //
+    "▀▀▀ ", //
+];
+const J: [&str; 3] = [
+    "  █ ", //
+    "▄ █ ", //
+    " ▀  ", //
+];
+const K: [&str; 3] = [
+    "█ ▄ ", //
+    "█▀▄ ", //
+    "▀ ▀ ", //
+];
+const L: [&str; 3] = [
+    "█   ", //
+    "█   ", //
+    "▀▀▀ ", //
+];
+const M: [&str; 3] = [
+    "█▄█ ", //
+    "█ █ ", //
+    "▀ ▀ ", //
+];
+const N: [&str; 3] = [
+    "█▀▄ ", //
+    "█ █ ", //
+    "▀ ▀ ", //
+];
+const O: [&str; 3] = [
+    "▄▀▄ ", //
+    "█ █ ", //
+    " ▀  ", //
+];
+const P: [&str; 3] = [
+    "█▀▄ ", //
+    "█▀  ", //
+    "▀   ", //
+];
+const Q: [&str; 3] = [
+    "▄▀▄ ", //
+    "█ █ ", //
+    " ▀▄ ", //
+];
+const R: [&str; 3] = [
+    "█▀▄ ", //
+    "█▀▄ ", //
+    "▀ ▀ ", //
+];
+const S: [&str; 3] = [
+    "▄▀▀ ", //
+    " ▀▄ ", //
+    "▀▀  ", //
+];
+const T: [&str; 3] = [
+    "▀█▀ ", //
+    " █  ", //
+    " ▀  ", //
+];
+const U: [&str; 3] = [
+    "█ █ ", //
+    "█ █ ", //
+    "▀▀▀ ", //
+];
+const V: [&str; 3] = [
+    "█ █ ", //
+    "█ █ ", //
+    " ▀  ", //
+];
+const W: [&str; 3] = [
+    "█ █ ", //
+    "█▄█ ", //
+    "▀ ▀ ", //
+];
+const X: [&str; 3] = [
+    "▀▄▀ ", //
+    "▄▀▄ ", //
+    "▀ ▀ ", //
+];
+const Y: [&str; 3] = [
+    "█ █ ", //
+    "▀█▀ ", //
+    " ▀  ", //
+];
+const Z: [&str; 3] = [
+    "▀▀█ ", //
+    "▄▀  ", //
+    "▀▀▀ ", //
+];
+const NUM_0: [&str; 3] = [
+    "█▀█ ", //
+    "█ █ ", //
+    "▀▀▀ ", //
+];
+const NUM_1: [&str; 3] = [
+    "▄█  ", //
+    " █  ", //
+    "▀▀▀ ", //
+];
+const NUM_2: [&str; 3] = [
+    "▀▀█ ", //
+    "█▀▀ ", //
+    "▀▀▀ ", //
+];
+const NUM_3: [&str; 3] = [
+    "▀▀█ ", //
+    " ▀█ ", //
+    "▀▀▀ ", //
+];
+const NUM_4: [&str; 3] = [
+    "█ █ ", //
+    "▀▀█ ", //
+    "  ▀ ", //
+];
+const NUM_5: [&str; 3] = [
+    "█▀▀ ", //
+    "▀▀█ ", //
+    "▀▀▀ ", //
+];
+const NUM_6: [&str; 3] = [
+    "█▀▀ ", //
+    "█▀█ ", //
+    "▀▀▀ ", //
+];
+const NUM_7: [&str; 3] = [
+    "▀▀█ ", //
+    "  █ ", //
+    "  ▀ ", //
+];
+const NUM_8: [&str; 3] = [
+    "█▀█ ", //
+    "█▀█ ", //
+    "▀▀▀ ", //
+];
+const NUM_9: [&str; 3] = [
+    "█▀█ ", //
+    "▀▀█ ", //
+    "▀▀▀ ", //
+];
+const SPACE: [&str; 3] = [
+    "    ", //
+    "    ", //
+    "    ", //
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::rendering::fonts::block_font::{test::assert_font_dimensions, BlockFont};
+
+    use super::SMALL_FONT;
+
+    #[test]
+    fn dimensions() {
+        assert_font_dimensions(&SMALL_FONT);
+    }
+
+    #[test]
+    fn glyph_is_case_sensitive() {
+        assert!(SMALL_FONT.glyph('a').is_some());
+        assert!(SMALL_FONT.glyph('A').is_none());
+    }
+}
@@ -0,0 +1,333 @@
+use super::block_font::BlockFont;
+
+/// The original 5 row font used for banners like "paused"
+#[derive(Debug)]
+pub struct StandardFont;
+pub static STANDARD_FONT: StandardFont = StandardFont;
+
+impl BlockFont for StandardFont {
+    fn name(&self) -> &'static str {
+        "standard"
+    }
+    fn height(&self) -> usize {
+        5
+    }
+    fn width(&self) -> usize {
+        7
+    }
+    fn glyph(&self, ch: char) -> Option<&'static [&'static str]> {
+        let glyph: &'static [&'static str] = match ch {
+            ' ' => &SPACE,
+            'a' => &A,
+            'b' => &B,
+            'c' => &C,
+            'd' => &D,
+            'e' => &E,
+            'f' => &F,
+            'g' => &G,
+            'h' => &H,
+            'i' => &I,
+            'j' => &J,
+            'k' => &K,
+            'l' => &L,
+            'm' => &M,
+            'n' => &N,
+            'o' => &O,
+            'p' => &P,
+            'q' => &Q,
+            'r' => &R,
+            's' => &S,
+            't' => &T,
+            'u' => &U,
+            'v' => &V,
+            'w' => &W,
+            'x' => &X,
+            'y' => &Y,
+            'z' => &Z,
+            '0' => &NUM_0,
+            '1' => &NUM_1,
+            '2' => &NUM_2,
+            '3' => &NUM_3,
+            '4' => &NUM_4,
+            '5' => &NUM_5,
+            '6' => &NUM_6,
+            '7' => &NUM_7,
+            '8' => &NUM_8,
+            '9' => &NUM_9,
+            _ => return None,
+        };
+        Some(glyph)
+    }
+}
+
+const A: [&str; 5] = [
+    "  ███  ", //
+    " █   █ ", //
+    " █████ ", //
+    " █   █ ", //
+    " █   █ ", //
+];
+const B: [&str; 5] = [
+    " ████  ", //
+    " █   █ ", //
+    " ████  ", //
+    " █   █ ", //
+    " ████  ", //
+];
+const C: [&str; 5] = [
+    "  ███  ", //
+    " █   █ ", //
+    " █     ", //
+    " █   █ ", //
+    "  ███  ", //
+];
+const D: [&str; 5] = [
+    " ███   ", //
+    " █  █  ", //
+    " █   █ ", //
+    " █  █  ", //
+    " ███   ", //
+];
+const E: [&str; 5] = [
+    " █████ ", //
+    " █     ", //
+    " ████  ", //
+    " █     ", //
+    " █████ ", //
+];
+const F: [&str; 5] = [
+    " █████ ", //
+    " █     ", //
+    " ████  ", //
+    " █     ", //
+    " █     ", //
+];
+const G: [&str; 5] = [
+    "  ████ ", //
+    " █     ", //
+    " █  ██ ", //
+    " █   █ ", //
+    "  ███  ", //
+];
+const H: [&str; 5] = [
+    " █   █ ", //
+    " █   █ ", //
+    " █████ ", //
+    " █   █ ", //
+    " █   █ ", //
+];
+const I: [&str; 5] = [
+    " █████ ", //
+    "   █   ", //
+    "   █   ", //
+    "   █   ", //
+    " █████ ", //
+];
+const J: [&str; 5] = [
+    " █████ ", //
+    "     █ ", //
+    "     █ ", //
+    " █   █ ", //
+    "  ███  ", //
+];
+const K: [&str; 5] = [
+    " █   █ ", //
+    " █  █  ", //
+    " ███   ", //
+    " █  █  ", //
+    " █   █ ", //
+];
+const L: [&str; 5] = [
+    " █     ", //
+    " █     ", //
+    " █     ", //
+    " █     ", //
+    " █████ ", //
+];
+const M: [&str; 5] = [
+    " █   █ ", //
+    " ██ ██ ", //
+    " █ █ █ ", //
+    " █   █ ", //
+    " █   █ ", //
+];
+const N: [&str; 5] = [
+    " █   █ ", //
+    " ██  █ ", //
+    " █ █ █ ", //
+    " █  ██ ", //
+    " █   █ ", //
+];
+const O: [&str; 5] = [
+    "  ███  ", //
+    " █   █ ", //
+    " █   █ ", //
+    " █   █ ", //
+    "  ███  ", //
+];
+const P: [&str; 5] = [
+    " ████  ", //
+    " █   █ ", //
+    " ████  ", //
+    " █     ", //
+    " █     ", //
+];
+const Q: [&str; 5] = [
+    "  ███  ", //
+    " █   █ ", //
+    " █   █ ", //
+    " █  ██ ", //
+    "  ████ ", //
+];
+const R: [&str; 5] = [
+    " ████  ", //
+    " █   █ ", //
+    " ████  ", //
+    " █  █  ", //
+    " █   █ ", //
+];
+const S: [&str; 5] = [
+    "  ████ ", //
+    " █     ", //
+    "  ███  ", //
+    "     █ ", //
+    " ████  ", //
+];
+const T: [&str; 5] = [
+    " █████ ", //
+    "   █   ", //
+    "   █   ", //
+    "   █   ", //
+    "   █   ", //
+];
+const U: [&str; 5] = [
+    " █   █ ", //
+    " █   █ ", //
+    " █   █ ", //
+    " █   █ ", //
+    "  ███  ", //
+];
+const V: [&str; 5] = [
+    " █   █ ", //
+    " █   █ ", //
+    " █   █ ", //
+    "  █ █  ", //
+    "   █   ", //
+];
+const W: [&str; 5] = [
+    " █   █ ", //
+    " █   █ ", //
+    " █ █ █ ", //
+    " ██ ██ ", //
+    " █   █ ", //
+];
+const X: [&str; 5] = [
+    " █   █ ", //
+    "  █ █  ", //
+    "   █   ", //
+    "  █ █  ", //
+    " █   █ ", //
+];
+const Y: [&str; 5] = [
+    " █   █ ", //
+    "  █ █  ", //
+    "   █   ", //
+    "   █   ", //
+    "   █   ", //
+];
+const Z: [&str; 5] = [
+    " █████ ", //
+    "     █ ", //
+    "   █   ", //
+    " █     ", //
+    " █████ ", //
+];
+const NUM_0: [&str; 5] = [
+    "  ███  ", //
+    " █   █ ", //
+    " █   █ ", //
+    " █   █ ", //
+    "  ███  ", //
+];
+const NUM_1: [&str; 5] = [
+    "   █   ", //
+    "  ██   ", //
+    "   █   ", //
+    "   █   ", //
+    "  ███  ", //
+];
+const NUM_2: [&str; 5] = [
+    "  ███  ", //
+    " █   █ ", //
+    "    █  ", //
+    "   █   ", //
+    " █████ ", //
+];
+const NUM_3: [&str; 5] = [
+    "  ███  ", //
+    " █   █ ", //
+    "    ██ ", //
+    " █   █ ", //
+    "  ███  ", //
+];
+const NUM_4: [&str; 5] = [
+    "    ██ ", //
+    "   █ █ ", //
+    "  █  █ ", //
+    " █████ ", //
+    "     █ ", //
+];
+const NUM_5: [&str; 5] = [
+    " █████ ", //
+    " █     ", //
+    " ████  ", //
+    "     █ ", //
+    " ████  ", //
+];
+const NUM_6: [&str; 5] = [
+    "  ███  ", //
+    " █     ", //
+    " ████  ", //
+    " █   █ ", //
+    "  ███  ", //
+];
+const NUM_7: [&str; 5] = [
+    " █████ ", //
+    "     █ ", //
+    "    █  ", //
+    "   █   ", //
+    "  █    ", //
+];
+const NUM_8: [&str; 5] = [
+    "  ███  ", //
+    " █   █ ", //
+    "  ███  ", //
+    " █   █ ", //
+    "  ███  ", //
+];
+const NUM_9: [&str; 5] = [
+    "  ███  ", //
+    " █   █ ", //
+    "  ████ ", //
+    "     █ ", //
+    "  ███  ", //
+];
+const SPACE: [&str; 5] = [
+    "       ", //
+    "       ", //
+    "       ", //
+    "       ", //
+    "       ", //
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::rendering::fonts::block_font::test::assert_font_dimensions;
+
+    use super::STANDARD_FONT;
+
+    #[test]
+    fn dimensions() {
+        assert_font_dimensions(&STANDARD_FONT);
+    }
+}
@@ -0,0 +1,1055 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::{
+    shared::usize2d::Coord,
+    utils::{clock::Clock, progress::Progress},
+};
+
+use super::{
+    colors::TerminalColors, render_object::RenderObject, sparkline::sparkline, sprite::Sprite,
+};
+
+/// Reads the value a widget shows every time the widget is drawn
+pub type Binding<'a, T> = Box<dyn Fn() -> T + 'a>;
+
+/// A part of a heads-up display, like the debug info of a game or its score
+///
+/// A widget is drawn as rows of text. The text can be written to a handle as it is, or turned
+/// into a `RenderObject` for a panel
+pub trait Widget {
+    /// Get the rows of the widget, from the top down
+    fn rows(&self) -> Vec<String>;
+    /// Draw the widget as an object with its top left corner at a location
+    ///
+    /// A sprite holds a byte per column, so the characters beyond ASCII are drawn as the ASCII
+    /// character that looks the most like them, see `ascii_glyph`
+    fn render_object(
+        &self,
+        location: Coord,
+        background: TerminalColors,
+        foreground: TerminalColors,
+    ) -> RenderObject {
+        let rows = self.rows();
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let height = rows.len().max(1);
+        let mut chars: Vec<Vec<u8>> = rows
+            .iter()
+            .map(|row| {
+                let mut bytes: Vec<u8> = row.chars().map(ascii_glyph).collect();
+                bytes.resize(width, b' ');
+                bytes
+            })
+            .collect();
+        chars.resize(height, vec![b' '; width]);
+        let sprite = Sprite::new(
+            "HUD widget".to_string(),
+            width,
+            height,
+            chars,
+            vec![vec![background; width]; height],
+            vec![vec![foreground; width]; height],
+        );
+        RenderObject::new(sprite, location)
+    }
+}
+
+/// A line of text
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{rendering::hud::Label, ConwaysGame};
+/// # let game = ConwaysGame::headless(16, 16, 42);
+/// let label = Label::new(|| format!("Round {}", game.rounds()));
+/// ```
+pub struct Label<'a> {
+    text: Binding<'a, String>,
+}
+impl<'a> Label<'a> {
+    pub fn new(text: impl Fn() -> String + 'a) -> Self {
+        Label {
+            text: Box::new(text),
+        }
+    }
+}
+impl Widget for Label<'_> {
+    fn rows(&self) -> Vec<String> {
+        vec![(self.text)()]
+    }
+}
+
+/// A named value, drawn as `name: value`
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::rendering::hud::{Readout, Widget};
+/// # let frames_per_second = 30;
+/// let fps = Readout::new("FPS", || frames_per_second);
+/// assert_eq!(fps.rows(), vec!["FPS: 30"]);
+/// ```
+pub struct Readout<'a> {
+    name: String,
+    value: Binding<'a, String>,
+}
+impl<'a> Readout<'a> {
+    pub fn new<T: Display>(name: &str, value: impl Fn() -> T + 'a) -> Self {
+        Readout {
+            name: name.to_string(),
+            value: Box::new(move || value().to_string()),
+        }
+    }
+}
+impl Widget for Readout<'_> {
+    fn rows(&self) -> Vec<String> {
+        vec![format!("{}: {}", self.name, (self.value)())]
+    }
+}
+
+/// How far something got, drawn as a bar with the count and the percentage, like
+/// `Loading [###   ] 3/6 50%`. With an estimate of the time that is left the bar ends with it,
+/// like `ETA 12s`
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{rendering::hud::ProgressBar, utils::{clock::{Clock, SystemClock}, progress::Progress}};
+/// # let (loaded, total) = (3, 10);
+/// let loading = ProgressBar::new("Loading", 6, || (loaded, total));
+/// let progress = Progress::new(100, SystemClock.now());
+/// let sweep = ProgressBar::tracking("Sweep", 20, &progress, &SystemClock);
+/// ```
+pub struct ProgressBar<'a> {
+    name: String,
+    width: usize,
+    /// The part that is done and the whole
+    progress: Binding<'a, (u64, u64)>,
+    eta: Option<Binding<'a, Option<Duration>>>,
+}
+impl<'a> ProgressBar<'a> {
+    /// Create a bar
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the text before the bar
+    /// * `width` - the number of characters between the brackets of the bar
+    /// * `progress` - the part that is done and the whole
+    pub fn new(name: &str, width: usize, progress: impl Fn() -> (u64, u64) + 'a) -> Self {
+        ProgressBar {
+            name: name.to_string(),
+            width,
+            progress: Box::new(progress),
+            eta: None,
+        }
+    }
+    /// Create a bar that shows the progress of an operation, which may run on another thread,
+    /// with the time it has left
+    pub fn tracking(
+        name: &str,
+        width: usize,
+        progress: &'a Progress,
+        clock: &'a dyn Clock,
+    ) -> Self {
+        ProgressBar::new(name, width, || progress.get()).eta(|| progress.eta(clock.now()))
+    }
+    /// Show the estimated time that is left, `None` while there is no estimate
+    pub fn eta(mut self, eta: impl Fn() -> Option<Duration> + 'a) -> Self {
+        self.eta = Some(Box::new(eta));
+        self
+    }
+}
+impl Widget for ProgressBar<'_> {
+    fn rows(&self) -> Vec<String> {
+        let (done, total) = (self.progress)();
+        let (filled, percent) = match total {
+            0 => (self.width, 100),
+            _ => {
+                let done = done.min(total);
+                (
+                    (done as usize * self.width) / total as usize,
+                    done * 100 / total,
+                )
+            }
+        };
+        let mut row = format!(
+            "{} [{}{}] {}/{} {}%",
+            self.name,
+            "#".repeat(filled),
+            " ".repeat(self.width - filled),
+            done,
+            total,
+            percent
+        );
+        if let Some(eta) = self.eta.as_ref().and_then(|eta| eta()) {
+            row.push_str(&format!(" ETA {}", format_eta(eta)));
+        }
+        vec![row]
+    }
+}
+
+/// Write the time that is left in whole seconds, with the minutes once it is a minute or longer
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs_f64().ceil() as u64;
+    match secs {
+        0..60 => format!("{}s", secs),
+        _ => format!("{}m {:02}s", secs / 60, secs % 60),
+    }
+}
+
+/// A series of values drawn as bars after a name, see `sparkline`. The bars are right aligned
+/// in a fixed width so that the newest bar stays in place while the series fills up
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Instant;
+/// # use tic_tac_toe2::{rendering::hud::SparklineWidget, utils::metrics::{Metrics, UPDATE_TIME}};
+/// # let metrics = Metrics::new(Instant::now());
+/// let graph = SparklineWidget::new("Update", 40, 3, || {
+///     let samples = metrics.samples(UPDATE_TIME);
+///     samples.iter().map(|sample| sample.as_micros() as u64).collect()
+/// });
+/// ```
+pub struct SparklineWidget<'a> {
+    name: String,
+    width: usize,
+    values_per_bar: usize,
+    values: Binding<'a, Vec<u64>>,
+}
+impl<'a> SparklineWidget<'a> {
+    /// Create a graph
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the text before the bars
+    /// * `width` - the width the bars are aligned in
+    /// * `values_per_bar` - the number of values in a bar, a bar shows the highest of them
+    /// * `values` - the values from the oldest to the newest
+    pub fn new(
+        name: &str,
+        width: usize,
+        values_per_bar: usize,
+        values: impl Fn() -> Vec<u64> + 'a,
+    ) -> Self {
+        SparklineWidget {
+            name: name.to_string(),
+            width,
+            values_per_bar,
+            values: Box::new(values),
+        }
+    }
+}
+impl Widget for SparklineWidget<'_> {
+    fn rows(&self) -> Vec<String> {
+        let values = (self.values)();
+        vec![format!(
+            "{} {:>width$}",
+            self.name,
+            sparkline(&values, self.values_per_bar),
+            width = self.width
+        )]
+    }
+}
+
+/// The keys that can be pressed and what they do, like `p: next, esc: close`
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::rendering::hud::{KeyHints, Widget};
+/// let hints = KeyHints::new(&[("w/s", "move"), ("enter", "select")]);
+/// assert_eq!(hints.rows(), vec!["w/s: move, enter: select"]);
+/// ```
+pub struct KeyHints {
+    hints: Vec<(String, String)>,
+}
+impl KeyHints {
+    pub fn new(hints: &[(&str, &str)]) -> Self {
+        KeyHints {
+            hints: hints
+                .iter()
+                .map(|(key, action)| (key.to_string(), action.to_string()))
+                .collect(),
+        }
+    }
+    /// Get the hints as a single line
+    pub fn line(&self) -> String {
+        self.hints
+            .iter()
+            .map(|(key, action)| format!("{}: {}", key, action))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+impl Widget for KeyHints {
+    fn rows(&self) -> Vec<String> {
+        vec![self.line()]
+    }
+}
+
+/// Which side of a column the text of a cell is drawn on
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// A column of a `Table`
+#[derive(Clone, Debug)]
+pub struct Column {
+    pub header: String,
+    /// The number of characters of the column, `None` to fit the widest cell
+    pub width: Option<usize>,
+    pub align: Align,
+}
+impl Column {
+    /// Create a column of text that fits the widest cell
+    pub fn left(header: &str) -> Self {
+        Column {
+            header: header.to_string(),
+            width: None,
+            align: Align::Left,
+        }
+    }
+    /// Create a column of numbers that fits the widest cell
+    pub fn right(header: &str) -> Self {
+        Column {
+            align: Align::Right,
+            ..Column::left(header)
+        }
+    }
+    /// Give the column a fixed width, longer cells are cut off
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+}
+
+/// Rows of cells in columns under a line of headers, like a list of scores
+///
+/// A table can show a part of its rows, see `visible_rows`, and scrolls to keep the highlighted
+/// row in view. The highlighted row is marked with `>` and a line below the rows tells which rows
+/// are shown while some are not
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::rendering::hud::{Column, Table};
+/// # fn scores() -> Vec<Vec<String>> {
+/// #     vec![vec!["snake".to_string(), "12".to_string()]]
+/// # }
+/// let mut table = Table::new(vec![Column::left("Name"), Column::right("Score")], || scores())
+///     .visible_rows(5);
+/// table.highlight(Some(7));
+/// ```
+pub struct Table<'a> {
+    columns: Vec<Column>,
+    rows: Binding<'a, Vec<Vec<String>>>,
+    /// The most rows that are shown at once, `None` to show them all
+    visible_rows: Option<usize>,
+    highlighted: Option<usize>,
+    /// The first row that is shown
+    scroll: usize,
+}
+impl<'a> Table<'a> {
+    pub fn new(columns: Vec<Column>, rows: impl Fn() -> Vec<Vec<String>> + 'a) -> Self {
+        Table {
+            columns,
+            rows: Box::new(rows),
+            visible_rows: None,
+            highlighted: None,
+            scroll: 0,
+        }
+    }
+    /// Show at most a number of rows at once
+    pub fn visible_rows(mut self, rows: usize) -> Self {
+        self.visible_rows = Some(rows.max(1));
+        self
+    }
+    /// Mark a row, and scroll as little as needed to show it
+    pub fn highlight(&mut self, row: Option<usize>) {
+        self.highlighted = row;
+        if let Some(row) = row {
+            self.scroll_to(row);
+        }
+    }
+    /// Scroll as little as needed to show a row
+    pub fn scroll_to(&mut self, row: usize) {
+        let Some(visible) = self.visible_rows else {
+            return;
+        };
+        if row < self.scroll {
+            self.scroll = row;
+        } else if row >= self.scroll + visible {
+            self.scroll = row + 1 - visible;
+        }
+    }
+    /// Scroll by a number of rows, up if it is negative
+    pub fn scroll_by(&mut self, rows: isize) {
+        self.scroll = self.scroll.saturating_add_signed(rows);
+    }
+    /// Get the first row that is shown
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+    /// Pad or cut a cell to the width of its column
+    fn cell(text: &str, width: usize, align: Align) -> String {
+        let text: String = text.chars().take(width).collect();
+        match align {
+            Align::Left => format!("{:<width$}", text),
+            Align::Right => format!("{:>width$}", text),
+        }
+    }
+}
+impl Widget for Table<'_> {
+    fn rows(&self) -> Vec<String> {
+        let rows = (self.rows)();
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                column.width.unwrap_or_else(|| {
+                    rows.iter()
+                        .filter_map(|row| row.get(index))
+                        .chain([&column.header])
+                        .map(|text| text.chars().count())
+                        .max()
+                        .unwrap_or(0)
+                })
+            })
+            .collect();
+        let has_marker = self.highlighted.is_some();
+        let line = |cells: Vec<&str>, marker: char| {
+            let cells: Vec<String> = self
+                .columns
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(index, (column, width))| {
+                    Table::cell(cells.get(index).unwrap_or(&""), *width, column.align)
+                })
+                .collect();
+            let line = cells.join(" ");
+            match has_marker {
+                true => format!("{} {}", marker, line).trim_end().to_string(),
+                false => line.trim_end().to_string(),
+            }
+        };
+
+        let headers = self
+            .columns
+            .iter()
+            .map(|column| column.header.as_str())
+            .collect();
+        let mut lines = vec![line(headers, ' ')];
+        let visible = self.visible_rows.unwrap_or(rows.len());
+        let first = self.scroll.min(rows.len().saturating_sub(visible));
+        for (index, row) in rows.iter().enumerate().skip(first).take(visible) {
+            let marker = match self.highlighted == Some(index) {
+                true => '>',
+                false => ' ',
+            };
+            lines.push(line(row.iter().map(String::as_str).collect(), marker));
+        }
+        if rows.len() > visible {
+            let last = (first + visible).min(rows.len());
+            lines.push(format!("{}-{} of {}", first + 1, last, rows.len()));
+        }
+        lines
+    }
+}
+
+/// What a key did to a `Menu`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MenuAction<T> {
+    /// The menu does not use the key
+    Ignored,
+    /// The selection moved to another item
+    Moved,
+    /// The selected item was chosen with enter
+    Confirmed(T),
+}
+
+/// A list of items with one of them selected, like the pause menu or the rows of the options
+/// screen
+///
+/// The keys `w` and `s` move the selection up and down, wrapping around the ends of the list and
+/// skipping the items that are disabled, and enter chooses the selected item. The selected item
+/// is marked with `>` and a disabled item is drawn in parentheses
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{console::input_record::KeyEvent, rendering::hud::{Menu, MenuAction}, scene::title_screen::TitleMenuItem};
+/// # fn start(_: TitleMenuItem) {}
+/// # let event = KeyEvent::key_down('\r');
+/// let mut menu = Menu::new(&[TitleMenuItem::Play, TitleMenuItem::Quit]);
+/// if let MenuAction::Confirmed(item) = menu.handle_key(event.command) {
+///     start(item);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Menu<T> {
+    items: Vec<T>,
+    is_disabled: Vec<bool>,
+    selected: usize,
+}
+impl<T: Copy + PartialEq + Display> Menu<T> {
+    /// Create a menu with the first item selected
+    pub fn new(items: &[T]) -> Self {
+        Menu {
+            items: items.to_vec(),
+            is_disabled: vec![false; items.len()],
+            selected: 0,
+        }
+    }
+    /// Enable or disable an item, the selection moves on to the next item if its item is
+    /// disabled
+    pub fn set_enabled(&mut self, item: T, is_enabled: bool) {
+        if let Some(index) = self.index(item) {
+            self.is_disabled[index] = !is_enabled;
+        }
+        if self.is_disabled.get(self.selected) == Some(&true) {
+            self.move_selection(1);
+        }
+    }
+    /// Check if an item can be selected
+    pub fn is_enabled(&self, item: T) -> bool {
+        self.index(item)
+            .is_some_and(|index| !self.is_disabled[index])
+    }
+    /// Move the selection up or down, wrapping around the ends of the menu and skipping the
+    /// disabled items. The selection stays if every item is disabled
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - the number of enabled items to move, negative moves up
+    pub fn move_selection(&mut self, offset: i64) {
+        let count = self.items.len() as i64;
+        let step = offset.signum();
+        for _ in 0..offset.unsigned_abs() {
+            let mut index = self.selected as i64;
+            for _ in 0..count {
+                index = (index + step).rem_euclid(count);
+                if !self.is_disabled[index as usize] {
+                    self.selected = index as usize;
+                    break;
+                }
+            }
+        }
+    }
+    /// Select an item, if it is enabled
+    pub fn select(&mut self, item: T) {
+        if let Some(index) = self.index(item).filter(|index| !self.is_disabled[*index]) {
+            self.selected = index;
+        }
+    }
+    /// Get the item that is selected
+    pub fn selected(&self) -> T {
+        self.items[self.selected]
+    }
+    /// Apply a key to the menu
+    pub fn handle_key(&mut self, key: char) -> MenuAction<T> {
+        match key {
+            'w' | 'W' => self.move_selection(-1),
+            's' | 'S' => self.move_selection(1),
+            '\r' | '\n' => return MenuAction::Confirmed(self.selected()),
+            _ => return MenuAction::Ignored,
+        }
+        MenuAction::Moved
+    }
+    /// Describe the menu on a line per item with a label of its own, like an item with its value
+    pub fn lines_with(&self, label: impl Fn(T) -> String) -> Vec<String> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let marker = if index == self.selected { '>' } else { ' ' };
+                match self.is_disabled[index] {
+                    true => format!("{} ({})", marker, label(*item)),
+                    false => format!("{} {}", marker, label(*item)),
+                }
+            })
+            .collect()
+    }
+    fn index(&self, item: T) -> Option<usize> {
+        self.items.iter().position(|other| *other == item)
+    }
+}
+impl<T: Copy + PartialEq + Display> Widget for Menu<T> {
+    fn rows(&self) -> Vec<String> {
+        self.lines_with(|item| item.to_string())
+    }
+}
+
+/// What a key did to a `TextInput`
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputAction {
+    /// The field does not use the key
+    Ignored,
+    /// The text or the cursor changed
+    Edited,
+    /// Enter was pressed while the text is valid
+    Submitted(String),
+    /// Enter was pressed while the text is not valid, see `TextInput::error`
+    Rejected,
+    /// Escape was pressed
+    Cancelled,
+}
+
+/// Checks the text of a `TextInput`, the error is the reason the text can not be used. It is
+/// `Send` so that a game with an open field can run on a thread of its own
+pub type Validator<'a> = Box<dyn Fn(&str) -> Result<(), String> + Send + 'a>;
+
+/// A line of text that is typed in, like a seed or the name of a file
+///
+/// The keys are the characters the terminal sends:
+/// * a printable character is inserted at the cursor while the text is shorter than the limit
+/// * backspace deletes the character before the cursor and Ctrl+D the one after it
+/// * Ctrl+B and Ctrl+F move the cursor back and forward, Ctrl+A and Ctrl+E to the start and end
+/// * enter submits the text if it is valid and escape cancels
+///
+/// The field is drawn as its label and text, with a `^` below the character at the cursor and
+/// the reason the text is not valid below that
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{console::input_record::KeyEvent, rendering::hud::{InputAction, TextInput}};
+/// # fn reseed(_: u64) {}
+/// # let event = KeyEvent::key_down('\r');
+/// let mut seed = TextInput::new("Seed", 20)
+///     .validate(|text| text.parse::<u64>().map(|_| ()).map_err(|e| e.to_string()));
+/// if let InputAction::Submitted(text) = seed.handle_key(event.command) {
+///     reseed(text.parse().unwrap());
+/// }
+/// ```
+pub struct TextInput<'a> {
+    label: String,
+    text: Vec<char>,
+    /// The index of the character the next one is inserted before
+    cursor: usize,
+    max_len: usize,
+    validator: Option<Validator<'a>>,
+}
+impl<'a> TextInput<'a> {
+    /// Create an empty field
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - the name that is drawn in front of the text
+    /// * `max_len` - the most characters the text can have
+    pub fn new(label: &str, max_len: usize) -> Self {
+        TextInput {
+            label: label.to_string(),
+            text: Vec::new(),
+            cursor: 0,
+            max_len,
+            validator: None,
+        }
+    }
+    /// Check the text every time it changes and only submit it while it is valid
+    pub fn validate(mut self, validator: impl Fn(&str) -> Result<(), String> + Send + 'a) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+    /// Start with some text, cut to the limit, and the cursor after it
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = text.chars().take(self.max_len).collect();
+        self.cursor = self.text.len();
+        self
+    }
+    /// Get the text that was typed
+    pub fn text(&self) -> String {
+        self.text.iter().collect()
+    }
+    /// Get the number of characters before the cursor
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+    /// Get the reason the text is not valid, `None` if it is
+    pub fn error(&self) -> Option<String> {
+        let validator = self.validator.as_ref()?;
+        validator(&self.text()).err()
+    }
+    /// Apply a key to the field
+    pub fn handle_key(&mut self, key: char) -> InputAction {
+        match key {
+            '\r' | '\n' => {
+                return match self.error() {
+                    Some(_) => InputAction::Rejected,
+                    None => InputAction::Submitted(self.text()),
+                };
+            }
+            '\x1b' => return InputAction::Cancelled,
+            '\x08' | '\x7f' if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.text.remove(self.cursor);
+            }
+            '\x04' if self.cursor < self.text.len() => {
+                self.text.remove(self.cursor);
+            }
+            '\x02' if self.cursor > 0 => self.cursor -= 1,
+            '\x06' if self.cursor < self.text.len() => self.cursor += 1,
+            '\x01' => self.cursor = 0,
+            '\x05' => self.cursor = self.text.len(),
+            _ if !key.is_control() && self.text.len() < self.max_len => {
+                self.text.insert(self.cursor, key);
+                self.cursor += 1;
+            }
+            _ => return InputAction::Ignored,
+        }
+        InputAction::Edited
+    }
+}
+impl Widget for TextInput<'_> {
+    fn rows(&self) -> Vec<String> {
+        let prefix = format!("{}: ", self.label);
+        let mut rows = vec![
+            format!("{}{}", prefix, self.text()),
+            format!("{}^", " ".repeat(prefix.chars().count() + self.cursor)),
+        ];
+        rows.extend(self.error());
+        rows
+    }
+}
+
+/// Widgets stacked from the top down
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{rendering::hud::{Hud, KeyHints, Readout}, shared::usize2d::Coord, TerminalColors};
+/// # let score = 12;
+/// let hud = Hud::new()
+///     .with(Readout::new("Score", || score))
+///     .with(KeyHints::new(&[("q", "quit")]));
+/// let objects = hud.render_objects(Coord::new(0, 20), TerminalColors::Black, TerminalColors::White);
+/// ```
+#[derive(Default)]
+pub struct Hud<'a> {
+    widgets: Vec<Box<dyn Widget + 'a>>,
+}
+impl<'a> Hud<'a> {
+    pub fn new() -> Self {
+        Hud::default()
+    }
+    /// Add a widget below the widgets that were added before
+    pub fn with(mut self, widget: impl Widget + 'a) -> Self {
+        self.widgets.push(Box::new(widget));
+        self
+    }
+    /// Draw every widget as an object, a widget starts on the row below the previous one
+    pub fn render_objects(
+        &self,
+        location: Coord,
+        background: TerminalColors,
+        foreground: TerminalColors,
+    ) -> Vec<RenderObject> {
+        let mut y = location.y;
+        self.widgets
+            .iter()
+            .map(|widget| {
+                let object =
+                    widget.render_object(Coord::new(location.x, y), background, foreground);
+                y += object.get_area().height();
+                object
+            })
+            .collect()
+    }
+}
+impl Widget for Hud<'_> {
+    fn rows(&self) -> Vec<String> {
+        self.widgets
+            .iter()
+            .flat_map(|widget| widget.rows())
+            .collect()
+    }
+}
+
+/// Get the ASCII character that looks the most like a character, `?` if none does
+pub fn ascii_glyph(c: char) -> u8 {
+    match c {
+        _ if c.is_ascii() => c as u8,
+        '▁' | '▂' => b'_',
+        '▃' | '▄' => b'.',
+        '▅' | '▆' => b'-',
+        '▇' => b'=',
+        '█' => b'#',
+        '─' | '━' => b'-',
+        '│' | '┃' => b'|',
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' => b'+',
+        _ => b'?',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, time::Duration};
+
+    use crate::{
+        rendering::colors::TerminalColors as TC,
+        shared::{square::Square, usize2d::Coord},
+        utils::{
+            clock::{Clock, VirtualClock},
+            progress::Progress,
+        },
+    };
+
+    use super::{
+        Column, Hud, InputAction, KeyHints, Label, Menu, MenuAction, ProgressBar, Readout,
+        SparklineWidget, Table, TextInput, Widget,
+    };
+
+    #[test]
+    fn rows() {
+        let test_cases: [(&str, Box<dyn Widget>, &str); 6] = [
+            (
+                "label",
+                Box::new(Label::new(|| "Round 3".to_string())),
+                "Round 3",
+            ),
+            ("readout", Box::new(Readout::new("FPS", || 30)), "FPS: 30"),
+            (
+                "progress",
+                Box::new(ProgressBar::new("Loading", 4, || (3, 6))),
+                "Loading [##  ] 3/6 50%",
+            ),
+            (
+                "nothing to do",
+                Box::new(ProgressBar::new("Loading", 2, || (0, 0))),
+                "Loading [##] 0/0 100%",
+            ),
+            (
+                "sparkline",
+                Box::new(SparklineWidget::new("Update", 4, 2, || {
+                    vec![1, 7, 0, 0, 3, 2]
+                })),
+                "Update  █▁▄",
+            ),
+            (
+                "key hints",
+                Box::new(KeyHints::new(&[("w/s", "move"), ("esc", "resume")])),
+                "w/s: move, esc: resume",
+            ),
+        ];
+        for (test_case, widget, expected) in test_cases {
+            assert_eq!(widget.rows(), vec![expected], "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn progress_with_eta() {
+        let clock = VirtualClock::new();
+        let progress = Progress::new(8, clock.now());
+        let bar = ProgressBar::tracking("Sweep", 4, &progress, &clock);
+        let test_cases = [
+            ("no estimate yet", 0, 0, "Sweep [    ] 0/8 0%"),
+            ("a quarter", 2, 10, "Sweep [#   ] 2/8 25% ETA 30s"),
+            ("minutes", 3, 60, "Sweep [#   ] 3/8 37% ETA 1m 57s"),
+            ("done", 8, 30, "Sweep [####] 8/8 100% ETA 0s"),
+        ];
+        for (test_case, done, secs, expected) in test_cases {
+            progress.set(done);
+            clock.advance(Duration::from_secs(secs));
+            assert_eq!(bar.rows(), vec![expected], "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn table() {
+        let rows = || {
+            (1..=5)
+                .map(|seed| vec![format!("seed {}", seed), (seed * 10).to_string()])
+                .collect()
+        };
+        let columns = vec![Column::left("Seed"), Column::right("Cells").width(3)];
+        let mut table = Table::new(columns.clone(), rows);
+        assert_eq!(
+            table.rows(),
+            vec![
+                "Seed   Cel",
+                "seed 1  10",
+                "seed 2  20",
+                "seed 3  30",
+                "seed 4  40",
+                "seed 5  50"
+            ],
+            "Every row without scrolling"
+        );
+
+        table = Table::new(columns, rows).visible_rows(2);
+        let test_cases = [
+            (
+                "first rows",
+                Some(1),
+                vec!["  Seed   Cel", "  seed 1  10", "> seed 2  20", "1-2 of 5"],
+            ),
+            (
+                "scrolled down",
+                Some(3),
+                vec!["  Seed   Cel", "  seed 3  30", "> seed 4  40", "3-4 of 5"],
+            ),
+            (
+                "scrolled up",
+                Some(2),
+                vec!["  Seed   Cel", "> seed 3  30", "  seed 4  40", "3-4 of 5"],
+            ),
+            (
+                "not marked",
+                None,
+                vec!["Seed   Cel", "seed 3  30", "seed 4  40", "3-4 of 5"],
+            ),
+        ];
+        for (test_case, highlighted, expected) in test_cases {
+            table.highlight(highlighted);
+            assert_eq!(table.rows(), expected, "{}", test_case);
+        }
+        table.scroll_by(10);
+        assert_eq!(
+            table.rows()[1],
+            "seed 4  40",
+            "Scrolling stops at the last row"
+        );
+    }
+
+    #[test]
+    fn menu() {
+        let items = ["Play", "Save", "Options", "Quit"];
+        let test_cases = [
+            ("no movement", "", "Play"),
+            ("down", "s", "Save"),
+            ("down and up", "ssw", "Save"),
+            ("wraps above the first item", "w", "Quit"),
+            ("wraps below the last item", "ssss", "Play"),
+            ("other keys", "xS", "Save"),
+        ];
+        for (test_case, keys, expected) in test_cases {
+            let mut menu = Menu::new(&items);
+            for key in keys.chars() {
+                menu.handle_key(key);
+            }
+            assert_eq!(menu.selected(), expected, "{}", test_case);
+        }
+
+        let mut menu = Menu::new(&items);
+        menu.set_enabled("Save", false);
+        let test_cases = [
+            ("skips the disabled item", 's', MenuAction::Moved, "Options"),
+            ("skips it going up", 'w', MenuAction::Moved, "Play"),
+            ("ignored", 'h', MenuAction::Ignored, "Play"),
+            ("confirmed", '\r', MenuAction::Confirmed("Play"), "Play"),
+        ];
+        for (test_case, key, action, expected) in test_cases {
+            assert_eq!(menu.handle_key(key), action, "{}", test_case);
+            assert_eq!(menu.selected(), expected, "{}", test_case);
+        }
+        assert_eq!(
+            menu.rows(),
+            vec!["> Play", "  (Save)", "  Options", "  Quit"]
+        );
+        menu.select("Save");
+        assert_eq!(menu.selected(), "Play", "A disabled item is not selected");
+        menu.select("Quit");
+        menu.set_enabled("Quit", false);
+        assert_eq!(
+            menu.selected(),
+            "Play",
+            "The selection leaves a disabled item"
+        );
+        assert!(!menu.is_enabled("Quit"));
+    }
+
+    #[test]
+    fn text_input() {
+        let mut input = TextInput::new("Seed", 4).validate(|text| {
+            text.parse::<u64>()
+                .map(|_| ())
+                .map_err(|_| "not a number".to_string())
+        });
+        let test_cases = [
+            ("typed", "12", InputAction::Edited, "12", 2),
+            ("back", "\x02", InputAction::Edited, "12", 1),
+            ("inserted", "3", InputAction::Edited, "132", 2),
+            ("start", "\x01", InputAction::Edited, "132", 0),
+            (
+                "nothing before the cursor",
+                "\x08",
+                InputAction::Ignored,
+                "132",
+                0,
+            ),
+            ("deleted", "\x04", InputAction::Edited, "32", 0),
+            ("end", "\x05", InputAction::Edited, "32", 2),
+            ("backspace", "\x7f", InputAction::Edited, "3", 1),
+            ("full", "4567", InputAction::Ignored, "3456", 4),
+            ("control keys", "\t", InputAction::Ignored, "3456", 4),
+            (
+                "submitted",
+                "\r",
+                InputAction::Submitted("3456".to_string()),
+                "3456",
+                4,
+            ),
+            ("invalid", "\x08x", InputAction::Edited, "345x", 4),
+            ("rejected", "\n", InputAction::Rejected, "345x", 4),
+            ("cancelled", "\x1b", InputAction::Cancelled, "345x", 4),
+        ];
+        for (test_case, keys, expected, text, cursor) in test_cases {
+            let actions: Vec<InputAction> = keys.chars().map(|key| input.handle_key(key)).collect();
+            assert_eq!(actions.last(), Some(&expected), "{}", test_case);
+            assert_eq!(input.text(), text, "{}", test_case);
+            assert_eq!(input.cursor(), cursor, "{}", test_case);
+        }
+        assert_eq!(
+            input.rows(),
+            vec!["Seed: 345x", "          ^", "not a number"]
+        );
+
+        let input = TextInput::new("File", 5).with_text("saves/game.json");
+        assert_eq!(input.rows(), vec!["File: saves", "           ^"]);
+        assert_eq!(input.error(), None, "Any text is valid without a validator");
+    }
+
+    #[test]
+    fn bindings_are_read_when_drawn() {
+        let score = Cell::new(1);
+        let readout = Readout::new("Score", || score.get());
+        assert_eq!(readout.rows(), vec!["Score: 1"]);
+        score.set(12);
+        assert_eq!(readout.rows(), vec!["Score: 12"]);
+    }
+
+    #[test]
+    fn render_objects() {
+        let hud = Hud::new()
+            .with(Readout::new("Turn", || 'X'))
+            .with(SparklineWidget::new("Score", 3, 1, || vec![0, 4, 8]));
+        assert_eq!(hud.rows(), vec!["Turn: X", "Score ▁▄█"]);
+
+        let objects = hud.render_objects(Coord::new(2, 5), TC::Black, TC::White);
+        let test_cases = [("first row", 5, "Turn: X"), ("second row", 6, "Score _.#")];
+        for ((test_case, y, expected), object) in test_cases.into_iter().zip(&objects) {
+            assert_eq!(
+                (object.get_location().x, object.get_location().y),
+                (2, y),
+                "{}",
+                test_case
+            );
+            let area = Square::new(Coord::new(0, 0), Coord::new(20, 20));
+            let content = object.get_content_to_write(area).unwrap();
+            assert_eq!(
+                String::from_utf8_lossy(&content[0]),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+    }
+}
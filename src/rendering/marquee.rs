@@ -0,0 +1,225 @@
+use crate::{
+    handler::{handle::Handle, handle_error::HandleError},
+    shared::{square::Square, usize2d::Usize2d},
+};
+
+use super::{
+    message_helper::{MessageHelper, MessageHelperErr},
+    message_style::MessageStyle,
+};
+
+/// A message in block letters that scrolls from right to left through an area of the screen
+///
+/// The marquee does not keep time itself. Call `tick` once per panel tick and it moves the text
+/// one column every `ticks_per_step` ticks. The text enters on the right hand side of the area,
+/// scrolls out on the left and then starts over
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{handler::handle_error::HandleError, rendering::{marquee::Marquee, message_style::MessageStyle}, shared::{square::Square, usize2d::Usize2d}, MemoryHandle};
+/// # fn main() -> Result<(), HandleError> {
+/// # let mut handle = MemoryHandle::new();
+/// let area = Square::new(Usize2d::new(1, 1), Usize2d::new(40, 5));
+/// let mut marquee = Marquee::new("game over", area, MessageStyle::default(), 2).unwrap();
+/// for _ in 0..100 {
+///     if marquee.tick() {
+///         marquee.write(&mut handle)?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Marquee {
+    rows: Vec<Vec<char>>,
+    text_width: usize,
+    area: Square,
+    style: MessageStyle,
+    ticks_per_step: usize,
+    tick_count: usize,
+    offset: usize,
+}
+impl Marquee {
+    /// Create a new marquee
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - the text to scroll
+    /// * `area` - the clipping area the text scrolls through
+    /// * `style` - the colors and font of the text. Borders and shadows are not drawn
+    /// * `ticks_per_step` - the number of ticks before the text moves one column
+    ///
+    /// # Returns
+    ///
+    /// The marquee or a `MessageHelperErr` if the message can not be rendered in the font
+    pub fn new(
+        message: &str,
+        area: Square,
+        style: MessageStyle,
+        ticks_per_step: usize,
+    ) -> Result<Self, MessageHelperErr> {
+        assert!(
+            ticks_per_step > 0,
+            "A marquee needs at least one tick per step"
+        );
+        let rows: Vec<Vec<char>> = MessageHelper::render_rows(message, &style)?
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        let text_width = rows.first().map(|row| row.len()).unwrap_or(0);
+        Ok(Marquee {
+            rows,
+            text_width,
+            area,
+            style,
+            ticks_per_step,
+            tick_count: 0,
+            offset: 0,
+        })
+    }
+
+    /// Advance the marquee by one tick
+    ///
+    /// # Returns
+    ///
+    /// `true` if the text moved and the marquee should be redrawn
+    pub fn tick(&mut self) -> bool {
+        self.tick_count += 1;
+        if self.tick_count < self.ticks_per_step {
+            return false;
+        }
+        self.tick_count = 0;
+        self.offset = (self.offset + 1) % self.cycle_len();
+        true
+    }
+
+    /// Get the part of the text that is currently visible inside the area
+    ///
+    /// # Returns
+    ///
+    /// One `String` per visible row, each exactly as wide as the area
+    pub fn visible_rows(&self) -> Vec<String> {
+        let width = self.area.width();
+        let cycle_len = self.cycle_len();
+        self.rows
+            .iter()
+            .take(self.area.height())
+            .map(|row| {
+                (0..width)
+                    .map(|column| {
+                        let index = (self.offset + column) % cycle_len;
+                        if index < width {
+                            ' '
+                        } else {
+                            row[index - width]
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Write the visible part of the text to a handle
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - the handle to write to
+    ///
+    /// # Returns
+    ///
+    /// A result indicating if the write succeeded
+    pub fn write(&self, handle: &mut dyn Handle) -> Result<(), HandleError> {
+        let (top_left, _) = self.area.get_boundary();
+        handle.set_background_color(self.style.background)?;
+        handle.set_foreground_color(self.style.foreground)?;
+        for (index, row) in self.visible_rows().iter().enumerate() {
+            handle.write_to_location(row.as_bytes(), top_left + Usize2d::new(0, index))?;
+        }
+        handle.flush().map_err(|_| HandleError::WriteFailed)
+    }
+
+    /// The number of steps before the text is back where it started
+    fn cycle_len(&self) -> usize {
+        self.area.width() + self.text_width
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        handler::{memory_handle::MemoryHandle, shared_handle::SharedHandle},
+        rendering::{fonts::small_font::SMALL_FONT, message_style::MessageStyle},
+        shared::{square::Square, usize2d::Usize2d},
+    };
+
+    use super::Marquee;
+
+    fn small_marquee(ticks_per_step: usize) -> Marquee {
+        let area = Square::new(Usize2d::new(0, 0), Usize2d::new(3, 1));
+        let style = MessageStyle::builder().font(&SMALL_FONT).build();
+        Marquee::new("l", area, style, ticks_per_step).expect("The message should be supported")
+    }
+
+    #[test]
+    fn starts_empty() {
+        let marquee = small_marquee(1);
+        assert_eq!(
+            marquee.visible_rows(),
+            vec!["    ", "    "],
+            "The text should enter from the right so the area starts empty and is clipped to 2 rows"
+        );
+    }
+
+    #[test]
+    fn scrolls_through_area() {
+        let mut marquee = small_marquee(1);
+        let test_cases = [
+            (1, vec!["   █", "   █"]),
+            (2, vec!["  █ ", "  █ "]),
+            (4, vec!["█   ", "█   "]),
+            (5, vec!["    ", "    "]),
+            (7, vec!["    ", "    "]),
+            (8, vec!["    ", "    "]),
+            (9, vec!["   █", "   █"]),
+        ];
+        let mut ticks = 0;
+        for (i, (target, expected)) in test_cases.iter().enumerate() {
+            while ticks < *target {
+                assert!(marquee.tick(), "Every tick should move the text");
+                ticks += 1;
+            }
+            assert_eq!(
+                marquee.visible_rows(),
+                *expected,
+                "Test case {}: wrong rows after {} ticks",
+                i,
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn speed() {
+        let mut marquee = small_marquee(3);
+        assert!(!marquee.tick());
+        assert!(!marquee.tick());
+        assert!(marquee.tick(), "The text should move on every third tick");
+        assert_eq!(marquee.visible_rows(), vec!["   █", "   █"]);
+    }
+
+    #[test]
+    fn write() {
+        let mut marquee = small_marquee(1);
+        marquee.tick();
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let mut handle = SharedHandle::init(buffer.clone());
+
+        let result = marquee.write(&mut handle);
+        assert!(result.is_ok(), "Writing the marquee should not fail");
+
+        let content = buffer.lock().unwrap().get_buffer_content();
+        assert_eq!(String::from_utf8_lossy(&content), "   █\n   █");
+    }
+}
@@ -0,0 +1,469 @@
+use std::fmt::Display;
+
+use crate::{
+    assert_r,
+    handler::handle::Handle,
+    shared::usize2d::{Coord, Usize2d},
+};
+
+use super::{colors::TerminalColors, message_style::MessageStyle};
+
+pub struct MessageHelper {
+    pub handle: Box<dyn Handle>,
+}
+#[derive(Debug, PartialEq)]
+pub enum MessageHelperErr {
+    UnsupportedCharacters,
+    MessageOutOfBounds,
+    UnexpecterMessageLength,
+    WriteFailed,
+}
+impl Display for MessageHelperErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedCharacters => write!(f, "Unsupported character"),
+            Self::MessageOutOfBounds => write!(f, "Message out of bounds"),
+            Self::UnexpecterMessageLength => write!(f, "Unexpected message length"),
+            Self::WriteFailed => write!(f, "Failed to write message"),
+        }
+    }
+}
+impl MessageHelper {
+    /// Print a message so that it is centered around a coordinate
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - the message to be printed
+    /// * `center` - the coordinate the message should be centered around
+    /// * `style` - the colors, shadow and border of the message
+    ///
+    /// # Returns
+    ///
+    /// A result indicating if the message could be printed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{rendering::{message_helper::MessageHelper, message_style::MessageStyle}, shared::usize2d::Coord, MemoryHandle};
+    /// # let mut helper = MessageHelper::init(Box::new(MemoryHandle::new()));
+    /// let _ = helper.print_around_centerpoint(
+    ///     "paused".to_string(),
+    ///     Coord::new(40, 12),
+    ///     &MessageStyle::default(),
+    /// );
+    /// ```
+    pub fn print_around_centerpoint(
+        &mut self,
+        message: String,
+        center: Coord,
+        style: &MessageStyle,
+    ) -> Result<(), MessageHelperErr> {
+        let origin = self.calculate_origin(message.clone(), center, style);
+
+        assert_r!(
+            origin.x > 0 && origin.y > 0,
+            MessageHelperErr::MessageOutOfBounds
+        );
+
+        self.print(message, origin, style)
+    }
+
+    /// Calculate the top left coordinate of a message centered around a coordinate, including
+    /// the border of the style
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - the message to be printed
+    /// * `center` - the coordinate the message should be centered around
+    /// * `style` - the style of the message
+    ///
+    /// # Returns
+    ///
+    /// The top left coordinate of the message. Terminal locations are 1 indexed so the smallest
+    /// coordinate that can be returned is (1, 1)
+    pub fn calculate_origin(&self, message: String, center: Coord, style: &MessageStyle) -> Coord {
+        let (before, _) = style.padding();
+        let width = (message.chars().count() / 2) * style.font.width() + before;
+        let height = style.font.height() / 2 + before;
+
+        Coord::new(
+            center.x.saturating_sub(width).max(1),
+            center.y.saturating_sub(height).max(1),
+        )
+    }
+
+    /// Print a message in block letters with its top left corner at the origin
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - the message to be printed
+    /// * `origin` - the top left corner of the message (including its border)
+    /// * `style` - the colors, shadow and border of the message
+    ///
+    /// # Returns
+    ///
+    /// A result indicating if the message could be printed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{rendering::{message_helper::MessageHelper, message_style::MessageStyle}, shared::usize2d::Coord, MemoryHandle, TerminalColors};
+    /// # let mut helper = MessageHelper::init(Box::new(MemoryHandle::new()));
+    /// let style = MessageStyle::builder().shadow(TerminalColors::Black).build();
+    /// let _ = helper.print("hello".to_string(), Coord::new(1, 1), &style);
+    /// ```
+    pub fn print(
+        &mut self,
+        message: String,
+        origin: Coord,
+        style: &MessageStyle,
+    ) -> Result<(), MessageHelperErr> {
+        assert!(
+            origin.x > 0 && origin.y > 0,
+            "terminal locations are 1 indexed"
+        );
+        let glyphs = Self::get_glyphs(&message, style)?;
+        let text_width = glyphs.len() * style.font.width();
+        let (before, _) = style.padding();
+        let total_width = text_width + before * 2;
+        let total_height = style.font.height() + before * 2;
+
+        for line in 0..total_height {
+            self.handle
+                .set_cursor_location(origin + Usize2d::new(0, line))
+                .map_err(|_| MessageHelperErr::WriteFailed)?;
+
+            let is_border_line = before > 0 && (line == 0 || line == total_height - 1);
+            match style.border {
+                Some(border) if is_border_line => {
+                    let (left, right) = if line == 0 {
+                        ("┌", "┐")
+                    } else {
+                        ("└", "┘")
+                    };
+                    let edge = format!("{}{}{}", left, "─".repeat(text_width), right);
+                    self.write_segment(&edge, border, style.background)?;
+                }
+                Some(border) => {
+                    self.write_segment("│", border, style.background)?;
+                    self.write_glyph_row(&glyphs, line - before, style)?;
+                    self.write_segment("│", border, style.background)?;
+                }
+                None => self.write_glyph_row(&glyphs, line, style)?,
+            }
+
+            if let Some(shadow) = style.shadow {
+                if line > 0 {
+                    self.write_segment(" ", shadow, shadow)?;
+                }
+            }
+            self.flush()?;
+        }
+
+        if let Some(shadow) = style.shadow {
+            self.handle
+                .set_cursor_location(origin + Usize2d::new(1, total_height))
+                .map_err(|_| MessageHelperErr::WriteFailed)?;
+            self.write_segment(&" ".repeat(total_width), shadow, shadow)?;
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Look up the block letters for every character in a message
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - the message to be printed
+    /// * `style` - the style containing the font to use
+    ///
+    /// # Returns
+    ///
+    /// The glyphs of the message or `MessageHelperErr::UnsupportedCharacters` if any of the
+    /// characters in the message do not have a glyph in the font
+    fn get_glyphs(
+        message: &str,
+        style: &MessageStyle,
+    ) -> Result<Vec<&'static [&'static str]>, MessageHelperErr> {
+        message
+            .chars()
+            .map(|ch| {
+                let current = match ch.to_lowercase().next() {
+                    Some(lower) => lower,
+                    None => return Err(MessageHelperErr::UnexpecterMessageLength),
+                };
+                match style.font.glyph(current) {
+                    Some(printable) => Ok(printable),
+                    None => Err(MessageHelperErr::UnsupportedCharacters),
+                }
+            })
+            .collect()
+    }
+
+    /// Render a message into the rows of block letters without writing it anywhere
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - the message to be rendered
+    /// * `style` - the style containing the font to use
+    ///
+    /// # Returns
+    ///
+    /// One `String` per row of the font or `MessageHelperErr::UnsupportedCharacters` if any of the
+    /// characters in the message do not have a glyph in the font
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::rendering::{message_helper::MessageHelper, message_style::MessageStyle};
+    /// let rows = MessageHelper::render_rows("hi", &MessageStyle::default()).unwrap();
+    /// assert_eq!(rows.len(), 5);
+    /// ```
+    pub fn render_rows(
+        message: &str,
+        style: &MessageStyle,
+    ) -> Result<Vec<String>, MessageHelperErr> {
+        let glyphs = Self::get_glyphs(message, style)?;
+        Ok((0..style.font.height())
+            .map(|row| glyphs.iter().map(|glyph| glyph[row]).collect())
+            .collect())
+    }
+
+    fn write_glyph_row(
+        &mut self,
+        glyphs: &[&'static [&'static str]],
+        row: usize,
+        style: &MessageStyle,
+    ) -> Result<(), MessageHelperErr> {
+        let printable_row: String = glyphs.iter().map(|glyph| glyph[row]).collect();
+        self.write_segment(&printable_row, style.foreground, style.background)
+    }
+
+    fn write_segment(
+        &mut self,
+        text: &str,
+        foreground: TerminalColors,
+        background: TerminalColors,
+    ) -> Result<(), MessageHelperErr> {
+        self.handle
+            .set_background_color(background)
+            .map_err(|_| MessageHelperErr::WriteFailed)?;
+        self.handle
+            .set_foreground_color(foreground)
+            .map_err(|_| MessageHelperErr::WriteFailed)?;
+        self.handle
+            .write_all(text.as_bytes())
+            .map_err(|_| MessageHelperErr::WriteFailed)
+    }
+
+    fn flush(&mut self) -> Result<(), MessageHelperErr> {
+        self.handle
+            .flush()
+            .map_err(|_| MessageHelperErr::WriteFailed)
+    }
+
+    pub fn init(handle: Box<dyn Handle>) -> Self {
+        MessageHelper { handle }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        handler::{memory_handle::MemoryHandle, shared_handle::SharedHandle},
+        rendering::{
+            colors::TerminalColors as TC, fonts::small_font::SMALL_FONT,
+            message_style::MessageStyle,
+        },
+        shared::usize2d::Coord,
+    };
+
+    use super::{MessageHelper, MessageHelperErr};
+
+    fn init_helper() -> (MessageHelper, Arc<Mutex<MemoryHandle>>) {
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let handle = SharedHandle::init(buffer.clone());
+        (MessageHelper::init(Box::new(handle)), buffer)
+    }
+
+    fn get_content(buffer: Arc<Mutex<MemoryHandle>>) -> String {
+        let unwrapped = buffer.lock().unwrap().get_buffer_content();
+        String::from_utf8_lossy(&unwrapped).to_string()
+    }
+
+    #[test]
+    fn print_at_origin() {
+        let (mut helper, buffer) = init_helper();
+
+        match helper.print(
+            "hello".to_string(),
+            Coord::new(1, 1),
+            &MessageStyle::default(),
+        ) {
+            Ok(_) => (),
+            Err(_) => panic!("Should not have an error at this point"),
+        };
+
+        let result = get_content(buffer.clone());
+        let expected = "\n  █   █  █████  █      █       ███  \n  █   █  █      █      █      █   █ \n  █████  ████   █      █      █   █ \n  █   █  █      █      █      █   █ \n  █   █  █████  █████  █████   ███  ";
+        assert_eq!(
+            expected, result,
+            "The output written should look like a chunky version of the string \"hello\""
+        );
+
+        let background = buffer.lock().unwrap().get_background_colors();
+        let foreground = buffer.lock().unwrap().get_foreground_colors();
+        assert_eq!(background[1][0], TC::Default);
+        assert_eq!(background[1][1], TC::White);
+        assert_eq!(foreground[1][1], TC::Red);
+    }
+    #[test]
+    fn print_at_location() {
+        let (mut helper, buffer) = init_helper();
+
+        match helper.print(
+            "world".to_string(),
+            Coord::new(10, 18),
+            &MessageStyle::default(),
+        ) {
+            Ok(_) => (),
+            Err(_) => panic!("Should not have an error at this point"),
+        };
+
+        let result = get_content(buffer.clone());
+        let rows: Vec<&str> = result.split('\n').collect();
+        let expected = [
+            " █   █   ███   ████   █      ███   ",
+            " █   █  █   █  █   █  █      █  █  ",
+            " █ █ █  █   █  ████   █      █   █ ",
+            " ██ ██  █   █  █  █   █      █  █  ",
+            " █   █   ███   █   █  █████  ███   ",
+        ];
+        assert_eq!(rows.len(), 23, "The message should end on row 22");
+        for (index, row) in expected.iter().enumerate() {
+            assert_eq!(
+                rows[18 + index],
+                format!("{}{}", " ".repeat(10), row),
+                "The output written should look like a chunky version of the string \"world\" but shifted 17 positions down and 9 positions to the right"
+            );
+        }
+
+        let background = buffer.lock().unwrap().get_background_colors();
+        assert_eq!(background[18][9], TC::Default);
+        assert_eq!(background[18][10], TC::White);
+    }
+    #[test]
+    fn print_around_location() {
+        let (mut helper, buffer) = init_helper();
+
+        match helper.print_around_centerpoint(
+            "what even".to_string(),
+            Coord::new(10, 18),
+            &MessageStyle::default(),
+        ) {
+            Ok(_) => (),
+            Err(_) => panic!("Should not have an error at this point"),
+        };
+        let result = get_content(buffer);
+        let rows: Vec<&str> = result.split('\n').collect();
+        assert_eq!(rows.len(), 21, "The message should end on row 20");
+        assert_eq!(
+            rows[16], "  █   █  █   █   ███   █████         █████  █   █  █████  █   █ ",
+            "The message should be printed around the centerpoint x = 10 and y = 18"
+        );
+    }
+    #[test]
+    fn print_with_border() {
+        let (mut helper, buffer) = init_helper();
+        let style = MessageStyle::builder().border(TC::HotPink).build();
+
+        match helper.print("hi".to_string(), Coord::new(1, 1), &style) {
+            Ok(_) => (),
+            Err(_) => panic!("Should not have an error at this point"),
+        };
+
+        let result = get_content(buffer.clone());
+        let expected = "\n ┌──────────────┐\n │ █   █  █████ │\n │ █   █    █   │\n │ █████    █   │\n │ █   █    █   │\n │ █   █  █████ │\n └──────────────┘";
+        assert_eq!(
+            expected, result,
+            "The message should be surrounded by a border"
+        );
+
+        let foreground = buffer.lock().unwrap().get_foreground_colors();
+        assert_eq!(
+            foreground[2][1],
+            TC::HotPink,
+            "The border should use its own color"
+        );
+        assert_eq!(
+            foreground[2][4],
+            TC::Red,
+            "The glyphs should keep their color"
+        );
+    }
+    #[test]
+    fn print_with_shadow() {
+        let (mut helper, buffer) = init_helper();
+        let style = MessageStyle::builder().shadow(TC::Black).build();
+
+        match helper.print("i".to_string(), Coord::new(1, 1), &style) {
+            Ok(_) => (),
+            Err(_) => panic!("Should not have an error at this point"),
+        };
+
+        let result = get_content(buffer.clone());
+        let expected = "\n  █████ \n    █    \n    █    \n    █    \n  █████  \n         ";
+        assert_eq!(expected, result, "The message should have a drop shadow");
+
+        let background = buffer.lock().unwrap().get_background_colors();
+        assert_eq!(
+            background[1].last(),
+            Some(&TC::White),
+            "The first row should not have a shadow"
+        );
+        assert_eq!(
+            background[2].last(),
+            Some(&TC::Black),
+            "The shadow should be right of the message"
+        );
+        assert_eq!(
+            background[6][1],
+            TC::Default,
+            "The shadow should be offset by one cell"
+        );
+        assert_eq!(
+            background[6][2],
+            TC::Black,
+            "The shadow should be below the message"
+        );
+    }
+    #[test]
+    fn print_with_small_font() {
+        let (mut helper, buffer) = init_helper();
+        let style = MessageStyle::builder().font(&SMALL_FONT).build();
+
+        match helper.print("hi 5".to_string(), Coord::new(1, 1), &style) {
+            Ok(_) => (),
+            Err(_) => panic!("Should not have an error at this point"),
+        };
+
+        let result = get_content(buffer);
+        let expected = "\n █ █ ▀█▀     █▀▀ \n █▀█  █      ▀▀█ \n ▀ ▀ ▀▀▀     ▀▀▀ ";
+        assert_eq!(expected, result, "The message should only be 3 rows high");
+    }
+    #[test]
+    fn print_unsupported_characters() {
+        let (mut helper, _) = init_helper();
+
+        match helper.print_around_centerpoint(
+            "what even?".to_string(),
+            Coord::new(10, 18),
+            &MessageStyle::default(),
+        ) {
+            Ok(_) => panic!("Should have an error at this point"),
+            Err(e) => assert_eq!(e, MessageHelperErr::UnsupportedCharacters),
+        };
+    }
+}
@@ -0,0 +1,153 @@
+use super::{
+    colors::TerminalColors,
+    fonts::{block_font::BlockFont, standard_font::STANDARD_FONT},
+};
+
+/// Describes how a message printed by the `MessageHelper` should look
+///
+/// The default style matches the original banner look: red glyphs in the standard font on a white
+/// background without a shadow or a border
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::rendering::{colors::TerminalColors, fonts::small_font::SMALL_FONT, message_style::MessageStyle};
+/// let style = MessageStyle::builder()
+///     .foreground(TerminalColors::Black)
+///     .background(TerminalColors::LightGreen)
+///     .shadow(TerminalColors::Black)
+///     .font(&SMALL_FONT)
+///     .build();
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct MessageStyle {
+    pub foreground: TerminalColors,
+    pub background: TerminalColors,
+    pub shadow: Option<TerminalColors>,
+    pub border: Option<TerminalColors>,
+    pub font: &'static dyn BlockFont,
+}
+impl Default for MessageStyle {
+    fn default() -> Self {
+        MessageStyle {
+            foreground: TerminalColors::Red,
+            background: TerminalColors::White,
+            shadow: None,
+            border: None,
+            font: &STANDARD_FONT,
+        }
+    }
+}
+impl MessageStyle {
+    /// Get a builder that starts from the default style
+    ///
+    /// # Returns
+    ///
+    /// A `MessageStyleBuilder` that can be used to change the style one property at a time
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::rendering::{colors::TerminalColors, message_style::MessageStyle};
+    /// let style = MessageStyle::builder().border(TerminalColors::HotPink).build();
+    /// ```
+    pub fn builder() -> MessageStyleBuilder {
+        MessageStyleBuilder {
+            style: MessageStyle::default(),
+        }
+    }
+    /// The number of extra cells the border and shadow add to the width and height of a message
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the cells added before the message and the cells added after the message
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::rendering::{colors::TerminalColors, message_style::MessageStyle};
+    /// let style = MessageStyle::builder().border(TerminalColors::Red).build();
+    /// let (before, after) = style.padding();
+    /// assert_eq!((before, after), (1, 1));
+    /// ```
+    pub fn padding(&self) -> (usize, usize) {
+        let border = if self.border.is_some() { 1 } else { 0 };
+        let shadow = if self.shadow.is_some() { 1 } else { 0 };
+        (border, border + shadow)
+    }
+}
+
+/// Builder for a `MessageStyle`
+pub struct MessageStyleBuilder {
+    style: MessageStyle,
+}
+impl MessageStyleBuilder {
+    /// Set the color used to draw the glyphs
+    pub fn foreground(mut self, color: TerminalColors) -> Self {
+        self.style.foreground = color;
+        self
+    }
+    /// Set the color behind the glyphs
+    pub fn background(mut self, color: TerminalColors) -> Self {
+        self.style.background = color;
+        self
+    }
+    /// Add a drop shadow one cell to the right and one cell below the message
+    pub fn shadow(mut self, color: TerminalColors) -> Self {
+        self.style.shadow = Some(color);
+        self
+    }
+    /// Add a single line border around the message
+    pub fn border(mut self, color: TerminalColors) -> Self {
+        self.style.border = Some(color);
+        self
+    }
+    /// Set the font the glyphs are drawn with
+    pub fn font(mut self, font: &'static dyn BlockFont) -> Self {
+        self.style.font = font;
+        self
+    }
+    /// Finish building the style
+    ///
+    /// # Returns
+    ///
+    /// The configured `MessageStyle`
+    pub fn build(self) -> MessageStyle {
+        self.style
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rendering::{colors::TerminalColors as TC, fonts::small_font::SMALL_FONT};
+
+    use super::MessageStyle;
+
+    #[test]
+    fn default() {
+        let style = MessageStyle::default();
+        assert_eq!(style.foreground, TC::Red);
+        assert_eq!(style.background, TC::White);
+        assert_eq!(style.shadow, None);
+        assert_eq!(style.border, None);
+        assert_eq!(style.font.name(), "standard");
+        assert_eq!(style.padding(), (0, 0));
+    }
+
+    #[test]
+    fn builder() {
+        let style = MessageStyle::builder()
+            .foreground(TC::Black)
+            .background(TC::LightGreen)
+            .shadow(TC::Black)
+            .border(TC::HotPink)
+            .font(&SMALL_FONT)
+            .build();
+        assert_eq!(style.foreground, TC::Black);
+        assert_eq!(style.background, TC::LightGreen);
+        assert_eq!(style.shadow, Some(TC::Black));
+        assert_eq!(style.border, Some(TC::HotPink));
+        assert_eq!(style.font.name(), "small");
+        assert_eq!(style.padding(), (1, 2));
+    }
+}
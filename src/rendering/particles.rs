@@ -0,0 +1,259 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::shared::float2d::Float2d;
+
+use super::{colors::TerminalColors, render_object::RenderObject, sprite::Sprite};
+
+/// A single colored character that moves for a number of ticks before it disappears
+#[derive(Debug)]
+pub struct Particle {
+    pub position: Float2d,
+    pub velocity: Float2d,
+    remaining_ticks: usize,
+    sprite: Arc<Sprite>,
+}
+impl Particle {
+    /// Create a new particle
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - the character to draw
+    /// * `color` - the foreground color of the character
+    /// * `position` - the starting position
+    /// * `velocity` - the distance moved per tick
+    /// * `lifetime` - the number of ticks the particle stays alive for
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{rendering::particles::Particle, shared::float2d::Float2d, TerminalColors};
+    /// let spark = Particle::new(
+    ///     b'*',
+    ///     TerminalColors::Red,
+    ///     Float2d::new(5.0, 5.0),
+    ///     Float2d::new(0.0, -0.5),
+    ///     10,
+    /// );
+    /// ```
+    pub fn new(
+        char: u8,
+        color: TerminalColors,
+        position: Float2d,
+        velocity: Float2d,
+        lifetime: usize,
+    ) -> Self {
+        let sprite = Sprite::new(
+            "Particle".to_string(),
+            1,
+            1,
+            vec![vec![char]],
+            vec![vec![TerminalColors::Default]],
+            vec![vec![color]],
+        );
+        Particle {
+            position,
+            velocity,
+            remaining_ticks: lifetime,
+            sprite: Arc::new(sprite),
+        }
+    }
+    pub fn is_alive(&self) -> bool {
+        self.remaining_ticks > 0
+    }
+    pub fn remaining_ticks(&self) -> usize {
+        self.remaining_ticks
+    }
+}
+
+/// Spawns, moves and removes short lived particles
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{rendering::particles::ParticleSystem, shared::float2d::Float2d, TerminalColors};
+/// # let (frame_sender, _frames) = std::sync::mpsc::channel();
+/// let mut particles = ParticleSystem::default();
+/// particles.burst(
+///     Float2d::new(10.0, 5.0),
+///     20,
+///     &[b'*', b'+'],
+///     &[TerminalColors::Red, TerminalColors::LightGreen],
+///     8,
+///     &mut rand::thread_rng(),
+/// );
+/// for _ in 0..8 {
+///     let _ = frame_sender.send(particles.update());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    max_particles: usize,
+}
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        ParticleSystem {
+            particles: vec![],
+            max_particles: 500,
+        }
+    }
+}
+impl ParticleSystem {
+    /// Create a particle system that keeps at most `max_particles` alive at once
+    pub fn new(max_particles: usize) -> Self {
+        ParticleSystem {
+            particles: vec![],
+            max_particles,
+        }
+    }
+    /// Add a particle. The oldest particle is dropped if the system is full
+    pub fn spawn(&mut self, particle: Particle) {
+        if self.max_particles == 0 {
+            return;
+        }
+        if self.particles.len() >= self.max_particles {
+            self.particles.remove(0);
+        }
+        self.particles.push(particle);
+    }
+    /// Spawn a number of particles flying away from a point in random directions
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - the point the particles start at
+    /// * `count` - the number of particles to spawn
+    /// * `chars` - the characters to pick from
+    /// * `colors` - the colors to pick from
+    /// * `lifetime` - the maximum number of ticks a particle stays alive for
+    /// * `rng` - the random number generator used for the directions, characters and colors
+    pub fn burst<R: Rng>(
+        &mut self,
+        origin: Float2d,
+        count: usize,
+        chars: &[u8],
+        colors: &[TerminalColors],
+        lifetime: usize,
+        rng: &mut R,
+    ) {
+        if chars.is_empty() || colors.is_empty() || lifetime == 0 {
+            return;
+        }
+        for _ in 0..count {
+            let velocity = Float2d::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-0.5..=0.5));
+            let char = chars[rng.gen_range(0..chars.len())];
+            let color = colors[rng.gen_range(0..colors.len())];
+            let lifetime = rng.gen_range(1..=lifetime);
+            self.spawn(Particle::new(char, color, origin, velocity, lifetime));
+        }
+    }
+    /// Move every particle by one tick and remove the particles that expired
+    ///
+    /// # Returns
+    ///
+    /// The `RenderObject`s of the particles that are still alive and on screen
+    pub fn update(&mut self) -> Vec<RenderObject> {
+        for particle in self.particles.iter_mut() {
+            particle.position = particle.position + particle.velocity;
+            particle.remaining_ticks = particle.remaining_ticks.saturating_sub(1);
+        }
+        self.particles.retain(|particle| particle.is_alive());
+        self.render_objects()
+    }
+    /// Get the `RenderObject`s of the particles that are on screen
+    pub fn render_objects(&self) -> Vec<RenderObject> {
+        self.particles
+            .iter()
+            .filter_map(|particle| {
+                particle
+                    .position
+                    .to_coord()
+                    .map(|coord| RenderObject::new(particle.sprite.clone(), coord))
+            })
+            .collect()
+    }
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.particles.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{rendering::colors::TerminalColors, shared::float2d::Float2d};
+
+    use super::{Particle, ParticleSystem};
+
+    #[test]
+    fn update_and_cleanup() {
+        let mut particles = ParticleSystem::default();
+        particles.spawn(Particle::new(
+            b'*',
+            TerminalColors::Red,
+            Float2d::new(2.0, 2.0),
+            Float2d::new(1.0, 0.0),
+            2,
+        ));
+        particles.spawn(Particle::new(
+            b'.',
+            TerminalColors::Red,
+            Float2d::new(0.0, 0.0),
+            Float2d::new(-1.0, 0.0),
+            5,
+        ));
+
+        let render_objects = particles.update();
+        assert_eq!(particles.len(), 2);
+        assert_eq!(
+            render_objects.len(),
+            1,
+            "Particles that are off screen should not be rendered"
+        );
+        let location = render_objects[0].get_location();
+        assert_eq!((location.x, location.y), (3, 2));
+
+        let _ = particles.update();
+        assert_eq!(
+            particles.len(),
+            1,
+            "Expired particles should be removed automatically"
+        );
+        assert_eq!(particles.particles()[0].remaining_ticks(), 3);
+    }
+
+    #[test]
+    fn burst() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut particles = ParticleSystem::new(10);
+        let colors = [TerminalColors::Red, TerminalColors::LightGreen];
+        particles.burst(Float2d::new(5.0, 5.0), 15, b"*+", &colors, 4, &mut rng);
+        assert_eq!(
+            particles.len(),
+            10,
+            "The particle system should not grow past its limit"
+        );
+        for particle in particles.particles() {
+            assert!(particle.remaining_ticks() >= 1 && particle.remaining_ticks() <= 4);
+            assert!(particle.velocity.x.abs() <= 1.0 && particle.velocity.y.abs() <= 0.5);
+        }
+        for _ in 0..4 {
+            let _ = particles.update();
+        }
+        assert!(particles.is_empty(), "Every particle should have expired");
+
+        particles.burst(Float2d::default(), 5, &[], &colors, 4, &mut rng);
+        assert!(particles.is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use std::io::Error;
+use std::{io::Error, sync::Arc};
 
 use crate::{
     handler::handle::Handle,
@@ -11,14 +11,33 @@ use crate::{
 
 use super::sprite::Sprite;
 
+/// The objects of one frame of a panel, shared instead of copied on their way to the panel
+pub type RenderObjects = Arc<[RenderObject]>;
+
 pub struct RenderObject {
     coordinate: Usize2d,
-    sprite: Sprite,
+    sprite: Arc<Sprite>,
 }
 impl RenderObject {
-    pub fn new(sprite: Sprite, location: Coord) -> Self {
+    /// Create a new render object
+    ///
+    /// # Arguments
+    ///
+    /// * `sprite` - the sprite to draw, either owned or shared with other objects through an `Arc`
+    /// * `location` - the coordinate of the top left corner of the sprite
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use tic_tac_toe2::{rendering::{render_object::RenderObject, sprite::Sprite}, shared::usize2d::Coord};
+    /// let sprite = Arc::new(Sprite::default());
+    /// let first = RenderObject::new(sprite.clone(), Coord::new(1, 1));
+    /// let second = RenderObject::new(Sprite::default(), Coord::new(5, 1));
+    /// ```
+    pub fn new(sprite: impl Into<Arc<Sprite>>, location: Coord) -> Self {
         RenderObject {
-            sprite,
+            sprite: sprite.into(),
             coordinate: location,
         }
     }
@@ -27,13 +46,81 @@ impl RenderObject {
             Usize2d::new(self.coordinate.x, self.coordinate.y),
             Usize2d::new(
                 self.coordinate.x + self.sprite.width - 1,
-                self.coordinate.y + self.sprite.width - 1,
+                self.coordinate.y + self.sprite.height - 1,
             ),
         )
     }
     pub fn get_location(&self) -> Usize2d {
         self.coordinate
     }
+    pub fn sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+    /// Test if the areas of two objects overlap
+    ///
+    /// This is a cheap check that ignores the content of the sprites. Use `overlaps_pixels` to
+    /// confirm a hit
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the object to test against
+    ///
+    /// # Returns
+    ///
+    /// `true` if the areas of the objects share at least one cell
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{rendering::{render_object::RenderObject, sprite::Sprite}, shared::usize2d::Coord};
+    /// let player = RenderObject::new(Sprite::default(), Coord::new(1, 1));
+    /// let enemy = RenderObject::new(Sprite::default(), Coord::new(3, 3));
+    /// assert!(player.intersects(&enemy));
+    /// ```
+    pub fn intersects(&self, other: &RenderObject) -> bool {
+        self.get_area().overlaps_with(&other.get_area())
+    }
+    /// Test if two objects have a non-transparent cell on the same coordinate
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the object to test against
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least one cell is drawn by both objects
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::{rendering::{render_object::RenderObject, sprite::Sprite}, shared::usize2d::Coord};
+    /// let player = RenderObject::new(Sprite::default(), Coord::new(1, 1));
+    /// let enemy = RenderObject::new(Sprite::default(), Coord::new(2, 1));
+    /// assert!(!player.overlaps_pixels(&enemy));
+    /// ```
+    pub fn overlaps_pixels(&self, other: &RenderObject) -> bool {
+        let overlap = match self.get_area().intersection(&other.get_area()) {
+            Some(overlap) => overlap,
+            None => return false,
+        };
+        let (top_left, bottom_right) = overlap.get_boundary();
+        for y in top_left.y..=bottom_right.y {
+            for x in top_left.x..=bottom_right.x {
+                if !self.is_transparent_at(x, y) && !other.is_transparent_at(x, y) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+    /// Test if the object is transparent at a screen coordinate
+    fn is_transparent_at(&self, x: usize, y: usize) -> bool {
+        if x < self.coordinate.x || y < self.coordinate.y {
+            return true;
+        }
+        self.sprite
+            .is_transparent(x - self.coordinate.x, y - self.coordinate.y)
+    }
     pub fn write_clamped(
         &self,
         _handle: &mut dyn Handle,
@@ -42,7 +129,7 @@ impl RenderObject {
         // TODO:let to_write = self.sprite.get_content_for_area(clamp, self.coordinate);
 
         //handle.write(&to_write)
-        Err(Error::new(std::io::ErrorKind::Other, "TBI"))
+        Err(Error::other("TBI"))
     }
     /// Get the content that should be written to the screen given the limitations i.t.o
     /// coordinates and available screen space
@@ -58,10 +145,129 @@ impl RenderObject {
     /// # Example
     ///
     /// ```
-    /// let clamp = Square::default();
-    /// let content_to_write: Vec<Vec<u8>> = render_object.get_content_to_write(clamp);
+    /// # use tic_tac_toe2::{rendering::{render_object::RenderObject, sprite::Sprite}, shared::{shared_errors::SharedErrors, square::Square, usize2d::{Coord, Usize2d}}};
+    /// # fn main() -> Result<(), SharedErrors> {
+    /// # let render_object = RenderObject::new(Sprite::default(), Coord::new(1, 1));
+    /// let clamp = Square::new(Usize2d::new(1, 1), Usize2d::new(80, 24));
+    /// let content_to_write: Vec<Vec<u8>> = render_object.get_content_to_write(clamp)?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn get_content_to_write(&self, clamp: Square) -> Result<Vec<Vec<u8>>, SharedErrors> {
         self.sprite.get_content_for_area(self.coordinate, clamp)
     }
 }
+
+/// Find every pair of objects that collide
+///
+/// The objects are sorted by their left edge so that only objects whose columns overlap are
+/// compared, after which the areas and then the pixels are tested
+///
+/// # Arguments
+///
+/// * `objects` - the objects to test
+///
+/// # Returns
+///
+/// A `Vec<(usize, usize)>` with the indexes of the colliding objects. The smaller index is first
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{rendering::{render_object::{find_collisions, RenderObject}, sprite::Sprite}, shared::usize2d::Coord};
+/// let objects = vec![
+///     RenderObject::new(Sprite::default(), Coord::new(1, 1)),
+///     RenderObject::new(Sprite::default(), Coord::new(3, 3)),
+/// ];
+/// assert_eq!(find_collisions(&objects), vec![(0, 1)]);
+/// ```
+pub fn find_collisions(objects: &[RenderObject]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..objects.len()).collect();
+    order.sort_by_key(|index| objects[*index].coordinate.x);
+
+    let mut collisions = vec![];
+    for (position, first) in order.iter().enumerate() {
+        let (_, first_end) = objects[*first].get_area().get_boundary();
+        for second in order[position + 1..].iter() {
+            if objects[*second].coordinate.x > first_end.x {
+                break;
+            }
+            if objects[*first].intersects(&objects[*second])
+                && objects[*first].overlaps_pixels(&objects[*second])
+            {
+                collisions.push((*first.min(second), *first.max(second)));
+            }
+        }
+    }
+    collisions.sort();
+    collisions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rendering::sprite::Sprite, shared::usize2d::Coord};
+
+    use super::{find_collisions, RenderObject};
+
+    #[test]
+    fn get_area() {
+        let sprite = Sprite::new(
+            "Tall Sprite".to_string(),
+            2,
+            3,
+            vec![vec![b'x'; 2]; 3],
+            vec![vec![Default::default(); 2]; 3],
+            vec![vec![Default::default(); 2]; 3],
+        );
+        let object = RenderObject::new(sprite, Coord::new(1, 1));
+        let area = object.get_area();
+        assert_eq!(area.width(), 2);
+        assert_eq!(area.height(), 3);
+    }
+
+    #[test]
+    fn intersects() {
+        let test_cases = vec![
+            (1, Coord::new(1, 1), Coord::new(1, 1), true, true),
+            (2, Coord::new(1, 1), Coord::new(3, 3), true, true),
+            (3, Coord::new(1, 1), Coord::new(2, 1), true, false),
+            (4, Coord::new(1, 1), Coord::new(2, 3), true, false),
+            (5, Coord::new(1, 1), Coord::new(4, 1), false, false),
+        ];
+        for (test_case, first, second, expected_area, expected_pixels) in test_cases {
+            let first = RenderObject::new(Sprite::default(), first);
+            let second = RenderObject::new(Sprite::default(), second);
+            assert_eq!(
+                first.intersects(&second),
+                expected_area,
+                "Test case {}: The area intersection is wrong",
+                test_case
+            );
+            assert_eq!(
+                first.overlaps_pixels(&second),
+                expected_pixels,
+                "Test case {}: The pixel overlap is wrong",
+                test_case
+            );
+            assert_eq!(
+                second.overlaps_pixels(&first),
+                expected_pixels,
+                "Test case {}: The pixel overlap should be symmetric",
+                test_case
+            );
+        }
+    }
+
+    #[test]
+    fn collisions() {
+        let objects = vec![
+            RenderObject::new(Sprite::default(), Coord::new(10, 1)),
+            RenderObject::new(Sprite::default(), Coord::new(1, 1)),
+            RenderObject::new(Sprite::default(), Coord::new(12, 3)),
+            RenderObject::new(Sprite::default(), Coord::new(2, 1)),
+            RenderObject::new(Sprite::default(), Coord::new(3, 3)),
+        ];
+        assert_eq!(find_collisions(&objects), vec![(0, 2), (1, 4)]);
+        assert!(find_collisions(&[]).is_empty());
+    }
+}
@@ -0,0 +1,55 @@
+/// The bars of a sparkline from the lowest to the highest value
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Draw values as a line of bars that are scaled to the highest value
+///
+/// Every bar shows the highest of a group of values, so that a single spike is not averaged
+/// away. The groups are counted from the newest value, which is the last one, so the newest bar
+/// is always complete
+///
+/// # Arguments
+///
+/// * `values` - the values from the oldest to the newest
+/// * `values_per_bar` - the number of values in a bar
+///
+/// # Examples
+///
+/// ```
+/// # use tic_tac_toe2::rendering::sparkline::sparkline;
+/// assert_eq!(sparkline(&[0, 4, 8], 1), "▁▄█");
+/// ```
+pub fn sparkline(values: &[u64], values_per_bar: usize) -> String {
+    assert!(values_per_bar > 0, "A bar needs at least one value");
+    let highest = values.iter().copied().max().unwrap_or(0);
+    let mut bars: Vec<char> = values
+        .rchunks(values_per_bar)
+        .map(|group| {
+            let value = group.iter().copied().max().unwrap_or(0);
+            match highest {
+                0 => BARS[0],
+                _ => BARS[(value * (BARS.len() as u64 - 1) / highest) as usize],
+            }
+        })
+        .collect();
+    bars.reverse();
+    bars.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sparkline;
+
+    #[test]
+    fn bars() {
+        let test_cases: [(&str, &[u64], usize, &str); 5] = [
+            ("empty", &[], 1, ""),
+            ("all zero", &[0, 0, 0], 1, "▁▁▁"),
+            ("scaled", &[0, 1, 2, 3, 4, 5, 6, 7], 1, "▁▂▃▄▅▆▇█"),
+            ("grouped by highest", &[1, 7, 0, 0, 3, 2], 2, "█▁▄"),
+            ("partial oldest group", &[7, 0, 0, 7, 7], 2, "█▁█"),
+        ];
+        for (test_case, values, values_per_bar, expected) in test_cases {
+            assert_eq!(sparkline(values, values_per_bar), expected, "{}", test_case);
+        }
+    }
+}
@@ -1,12 +1,14 @@
-use std::usize;
-
 use crate::{
     assert_r,
-    shared::{shared_errors::SharedErrors, square::Square, usize2d::Coord},
+    shared::{frame::Pixel, shared_errors::SharedErrors, square::Square, usize2d::Coord},
 };
 
 use super::colors::TerminalColors as TC;
 
+/// The character used for cells of a sprite that should not be drawn or collide with anything
+pub const TRANSPARENT: u8 = b' ';
+
+#[derive(Debug)]
 pub struct Sprite {
     _name: String,
     pub width: usize,
@@ -49,9 +51,9 @@ impl Sprite {
     /// * `height` - The total number of characters that the sprite is tall
     /// * `chars` - The characters that makes up the sprite as a `Vec<Vec<u8>>`
     /// * `background_colors` - The colors that makes up the sprite's background as a
-    /// `Vec<Vec<TerminalColors>>`
+    ///   `Vec<Vec<TerminalColors>>`
     /// * `foreground_colors` - The colors that makes up the sprite's foreground as a
-    /// `Vec<Vec<TerminalColors>>`
+    ///   `Vec<Vec<TerminalColors>>`
     ///
     /// # Result
     ///
@@ -74,13 +76,13 @@ impl Sprite {
             chars.len(),
             height
         );
-        for index in 0..chars.len() {
+        for (index, row) in chars.iter().enumerate() {
             assert_eq!(
-                chars[index].len(),
+                row.len(),
                 width,
                 "The width of the sprite on row {} is wrong. Width read: {}, Width in metadata: {}",
                 index,
-                chars[index].len(),
+                row.len(),
                 width
             );
         }
@@ -93,6 +95,53 @@ impl Sprite {
             _foreground_colors: foreground_colors,
         }
     }
+    /// Test if a cell of the sprite is transparent
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - the column of the cell relative to the left of the sprite
+    /// * `y` - the row of the cell relative to the top of the sprite
+    ///
+    /// # Returns
+    ///
+    /// `true` if the cell holds the `TRANSPARENT` character or is outside of the sprite
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::rendering::sprite::Sprite;
+    /// let sprite = Sprite::default();
+    /// assert!(!sprite.is_transparent(0, 0));
+    /// assert!(sprite.is_transparent(1, 0));
+    /// ```
+    pub fn is_transparent(&self, x: usize, y: usize) -> bool {
+        match self.chars.get(y).and_then(|row| row.get(x)) {
+            Some(char) => *char == TRANSPARENT,
+            None => true,
+        }
+    }
+    /// Get a cell of the sprite with its colors
+    ///
+    /// # Returns
+    ///
+    /// `None` if the cell is transparent or outside of the sprite
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Pixel> {
+        if self.is_transparent(x, y) {
+            return None;
+        }
+        let color = |colors: &Vec<Vec<TC>>| {
+            colors
+                .get(y)
+                .and_then(|row| row.get(x))
+                .copied()
+                .unwrap_or_default()
+        };
+        Some(Pixel::new(
+            self.chars[y][x] as char,
+            color(&self._background_colors),
+            color(&self._foreground_colors),
+        ))
+    }
     ///
     /// Get the sprite content to be rendered given a coordinate and a space to write to
     ///
@@ -161,7 +210,7 @@ impl Sprite {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let index = get_index_in_range(4, 5,1,5);
     /// assert!(index == 3, "The index of pos 4 should be 3 in the range (1 to 5)");
     /// ```
@@ -172,19 +221,19 @@ impl Sprite {
         range_end: usize,
     ) -> Result<(usize, usize), SharedErrors> {
         assert!(
-            coord_start < coord_end,
-            "The starting coordinate ({}) may not be before the ending coordinate ({})",
+            coord_start <= coord_end,
+            "The starting coordinate ({}) may not be after the ending coordinate ({})",
             coord_start,
             coord_end
         );
         assert!(
-            range_start < range_end,
-            "The starting range coordinate ({}) may not be before the ending range coordinate ({})",
+            range_start <= range_end,
+            "The starting range coordinate ({}) may not be after the ending range coordinate ({})",
             range_start,
             range_end
         );
-        assert_r!(coord_start < range_end, SharedErrors::OutOfBounds);
-        assert_r!(coord_end > range_start, SharedErrors::OutOfBounds);
+        assert_r!(coord_start <= range_end, SharedErrors::OutOfBounds);
+        assert_r!(coord_end >= range_start, SharedErrors::OutOfBounds);
         let starting_index = match Self::get_index_in_range(range_start, coord_start, coord_end) {
             Ok(v) => v,
             Err(SharedErrors::BeforeStart) => 0,
@@ -199,7 +248,7 @@ impl Sprite {
         };
 
         assert!(
-            starting_index < ending_index,
+            starting_index <= ending_index,
             "Invalid indexes. The starting index ({}) is after the ending index ({}).",
             starting_index,
             ending_index
@@ -221,13 +270,13 @@ impl Sprite {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let index = get_index_in_range(4,1,5);
     /// assert!(index == 3, "The index of pos 4 should be 3 in the range (1 to 5)");
     /// ```
     fn get_index_in_range(coord: usize, start: usize, end: usize) -> Result<usize, SharedErrors> {
-        assert_r!(coord > start, SharedErrors::BeforeStart);
-        assert_r!(coord < end, SharedErrors::AfterEnd);
+        assert_r!(coord >= start, SharedErrors::BeforeStart);
+        assert_r!(coord <= end, SharedErrors::AfterEnd);
         Ok(coord - start)
     }
 }
@@ -296,10 +345,32 @@ pub mod test {
         );
     }
     #[test]
+    fn is_transparent() {
+        let sprite = Sprite::default();
+        let test_cases = vec![
+            (1, (0, 0), false),
+            (2, (1, 0), true),
+            (3, (1, 1), false),
+            (4, (3, 0), true),
+            (5, (0, 3), true),
+        ];
+        for (test_case, (x, y), expected) in test_cases {
+            assert_eq!(
+                sprite.is_transparent(x, y),
+                expected,
+                "Test case {}: The transparency of cell ({}, {}) is wrong",
+                test_case,
+                x,
+                y
+            );
+        }
+    }
+    #[test]
     fn get_index() {
         let test_cases = vec![
             (1, 0, 5, 1, true, SharedErrors::None),
             (3, 1, 5, 2, true, SharedErrors::None),
+            (5, 1, 5, 4, true, SharedErrors::None),
             (0, 1, 5, 0, false, SharedErrors::BeforeStart),
             (11, 1, 5, 0, false, SharedErrors::AfterEnd),
         ];
@@ -340,6 +411,8 @@ pub mod test {
             ((1, 8), (6, 12), (5, 7), true, SharedErrors::None), //leans left
             ((3, 8), (0, 12), (0, 5), true, SharedErrors::None), //inside
             ((3, 8), (4, 6), (1, 3), true, SharedErrors::None), //over
+            ((4, 4), (0, 10), (0, 0), true, SharedErrors::None), //single cell
+            ((1, 5), (5, 10), (4, 4), true, SharedErrors::None), //touching
             ((1, 3), (5, 10), (0, 0), false, SharedErrors::OutOfBounds), //oob
             ((11, 13), (5, 10), (0, 0), false, SharedErrors::OutOfBounds), //oob
         ];
@@ -0,0 +1,72 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::colors::TerminalColors;
+
+/// The colors the cells of the board are drawn in
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Theme {
+    /// Green live cells on red
+    #[default]
+    Classic,
+    /// White live cells on black
+    Mono,
+    /// Cyan live cells on dark blue
+    Ocean,
+}
+impl Theme {
+    /// Get the background and foreground colors of a cell
+    pub fn colors(&self, is_alive: bool) -> (TerminalColors, TerminalColors) {
+        match (self, is_alive) {
+            (Theme::Classic, true) => (TerminalColors::LightGreen, TerminalColors::Black),
+            (Theme::Classic, false) => (TerminalColors::Red, TerminalColors::White),
+            (Theme::Mono, true) => (TerminalColors::White, TerminalColors::Black),
+            (Theme::Mono, false) => (TerminalColors::Black, TerminalColors::White),
+            (Theme::Ocean, true) => (TerminalColors::Cyan, TerminalColors::Black),
+            (Theme::Ocean, false) => (TerminalColors::DarkBlue, TerminalColors::White),
+        }
+    }
+}
+/// All themes in the order they are cycled through
+pub const THEMES: [Theme; 3] = [Theme::Classic, Theme::Mono, Theme::Ocean];
+impl Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Theme::Classic => write!(f, "Classic"),
+            Theme::Mono => write!(f, "Mono"),
+            Theme::Ocean => write!(f, "Ocean"),
+        }
+    }
+}
+impl FromStr for Theme {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        THEMES
+            .into_iter()
+            .find(|theme| theme.to_string().eq_ignore_ascii_case(s))
+            .ok_or(format!("`{}` is not a valid theme", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Theme, THEMES};
+
+    #[test]
+    fn parse() {
+        let test_cases = [
+            ("name", "Ocean", Ok(Theme::Ocean)),
+            ("lower case", "mono", Ok(Theme::Mono)),
+            (
+                "unknown theme",
+                "neon",
+                Err("`neon` is not a valid theme".to_string()),
+            ),
+        ];
+        for (test_case, input, expected) in test_cases {
+            assert_eq!(input.parse::<Theme>(), expected, "{}", test_case);
+        }
+        for theme in THEMES {
+            assert_eq!(theme.to_string().parse::<Theme>(), Ok(theme));
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    console::input_record::KeyEvent,
+    conway::{
+        conways_game::ConwaysGame,
+        patterns::{Pattern, PatternPlacement, PATTERNS},
+    },
+    rendering::colors::TerminalColors,
+    shared::{
+        frame::{Frame, Pixel},
+        usize2d::Usize2d,
+    },
+    tic_tac_toe::{
+        ai::choose_move,
+        board::{Board, Mark, Outcome},
+    },
+    utils::rng::{RngService, AI_STREAM},
+};
+
+use super::scene::{write_pixels, Scene, SceneTransition};
+
+/// The color of the cells of a pattern demo
+const CELL_COLOR: TerminalColors = TerminalColors::LightGreen;
+/// The color of the lines of a tic-tac-toe board
+const GRID_COLOR: TerminalColors = TerminalColors::Grey;
+/// The colors of the marks of a tic-tac-toe board
+const X_COLOR: TerminalColors = TerminalColors::Red;
+const O_COLOR: TerminalColors = TerminalColors::Blue;
+/// The width and height of a drawn tic-tac-toe board
+const BOARD_WIDTH: usize = 11;
+const BOARD_HEIGHT: usize = 5;
+
+/// What the attract mode shows at the moment
+enum Demo {
+    /// A tic-tac-toe game between two computer players
+    TicTacToe(Board),
+    /// A preset pattern running on an empty board
+    Pattern(&'static Pattern, Box<ConwaysGame>),
+}
+
+/// The demo that runs when nobody touches the menu, cycling through a tic-tac-toe game between
+/// two computer players and every preset pattern
+///
+/// Every demo runs for the same number of steps, the tic-tac-toe board stays on the screen once
+/// the game is over. Any key leaves the attract mode
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{scene::{attract_mode::AttractMode, title_screen::TitleScreen}, shared::usize2d::Usize2d};
+/// # let (size, seed) = (Usize2d::new(80, 24), 42);
+/// # let mut title = TitleScreen::new(size, seed, 10).unwrap();
+/// if title.is_idle() {
+///     let mut attract = AttractMode::new(size, seed, 5, 60);
+///     // run the attract mode until it is left, then
+///     title.wake();
+/// }
+/// ```
+pub struct AttractMode {
+    size: Usize2d,
+    rng: ChaCha8Rng,
+    demo: Demo,
+    demo_index: usize,
+    ticks_per_step: usize,
+    tick_count: usize,
+    steps_per_demo: usize,
+    step_count: usize,
+}
+impl AttractMode {
+    /// Create the attract mode, starting with a tic-tac-toe game
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the terminal
+    /// * `seed` - the seed of the moves of the computer players
+    /// * `ticks_per_step` - the number of ticks before a move or a generation
+    /// * `steps_per_demo` - the number of steps before the next demo starts
+    pub fn new(size: Usize2d, seed: u64, ticks_per_step: usize, steps_per_demo: usize) -> Self {
+        assert!(
+            ticks_per_step > 0 && steps_per_demo > 0,
+            "The attract mode needs at least one tick per step and one step per demo"
+        );
+        AttractMode {
+            size,
+            rng: RngService::new(seed).stream(AI_STREAM),
+            demo: Demo::TicTacToe(Board::default()),
+            demo_index: 0,
+            ticks_per_step,
+            tick_count: 0,
+            steps_per_demo,
+            step_count: 0,
+        }
+    }
+    /// Get the name of the demo that is shown
+    pub fn demo_name(&self) -> &'static str {
+        match &self.demo {
+            Demo::TicTacToe(_) => "tic tac toe",
+            Demo::Pattern(pattern, _) => pattern.name,
+        }
+    }
+    /// Start the demo after the current one, the tic-tac-toe game follows the last pattern
+    fn next_demo(&mut self) {
+        self.demo_index = (self.demo_index + 1) % (PATTERNS.len() + 1);
+        self.step_count = 0;
+        self.demo = match self.demo_index {
+            0 => Demo::TicTacToe(Board::default()),
+            index => {
+                let pattern = PATTERNS[index - 1];
+                let (x_len, y_len) = (self.size.x.max(1), self.size.y.saturating_sub(1).max(1));
+                let mut game = ConwaysGame::headless(x_len, y_len, 0);
+                game.set_cells(vec![vec![false; x_len]; y_len]);
+                game.place_pattern(&PatternPlacement {
+                    pattern,
+                    origin: None,
+                });
+                Demo::Pattern(pattern, Box::new(game))
+            }
+        };
+    }
+    /// The line at the bottom of the screen
+    fn caption(&self) -> String {
+        let status = match &self.demo {
+            Demo::TicTacToe(board) => match board.outcome() {
+                Outcome::InProgress => format!("{} to play", board.turn()),
+                Outcome::Won(mark) => format!("{} wins", mark),
+                Outcome::Draw => "draw".to_string(),
+            },
+            Demo::Pattern(..) => "running".to_string(),
+        };
+        format!("Demo: {} - {} - press any key", self.demo_name(), status)
+    }
+}
+impl Scene for AttractMode {
+    fn tick(&mut self) -> bool {
+        self.tick_count += 1;
+        if self.tick_count < self.ticks_per_step {
+            return false;
+        }
+        self.tick_count = 0;
+        self.step_count += 1;
+        if self.step_count > self.steps_per_demo {
+            self.next_demo();
+            return true;
+        }
+        match &mut self.demo {
+            Demo::TicTacToe(board) => {
+                if let Some(cell) = choose_move(board, &mut self.rng) {
+                    let _ = board.play(board.turn(), cell);
+                }
+            }
+            Demo::Pattern(_, game) => game.step(),
+        }
+        true
+    }
+    fn handle_key(&mut self, event: KeyEvent) -> SceneTransition {
+        match event.is_down {
+            true => SceneTransition::Leave,
+            false => SceneTransition::Stay,
+        }
+    }
+    fn frame(&self) -> Frame {
+        let mut frame = vec![vec![Pixel::default(); self.size.x]; self.size.y];
+        match &self.demo {
+            Demo::TicTacToe(board) => {
+                let left = self.size.x.saturating_sub(BOARD_WIDTH) / 2;
+                let top = self.size.y.saturating_sub(BOARD_HEIGHT + 1) / 2;
+                for (row, line) in board_rows(board).iter().enumerate() {
+                    write_pixels(&mut frame, Usize2d::new(left, top + row), line);
+                }
+            }
+            Demo::Pattern(_, game) => {
+                for (y, row) in game.cells().iter().enumerate() {
+                    for (x, is_alive) in row.iter().enumerate() {
+                        if *is_alive {
+                            frame[y][x] = Pixel::new('█', TerminalColors::Default, CELL_COLOR);
+                        }
+                    }
+                }
+            }
+        }
+        let caption: Vec<Pixel> = self
+            .caption()
+            .chars()
+            .map(|ch| Pixel::new(ch, TerminalColors::Default, TerminalColors::White))
+            .collect();
+        let left = self.size.x.saturating_sub(caption.len()) / 2;
+        write_pixels(
+            &mut frame,
+            Usize2d::new(left, self.size.y.saturating_sub(1)),
+            &caption,
+        );
+        frame
+    }
+}
+
+/// Draw a tic-tac-toe board as rows of text with lines between the cells
+fn board_rows(board: &Board) -> Vec<Vec<Pixel>> {
+    let grid = |ch: char| Pixel::new(ch, TerminalColors::Default, GRID_COLOR);
+    let mut rows = Vec::with_capacity(BOARD_HEIGHT);
+    for row in 0..3 {
+        if row > 0 {
+            rows.push("---+---+---".chars().map(grid).collect());
+        }
+        let mut line = Vec::with_capacity(BOARD_WIDTH);
+        for column in 0..3 {
+            if column > 0 {
+                line.push(grid('|'));
+            }
+            let mark = match board.cell(row * 3 + column) {
+                Some(Mark::X) => Pixel::new('X', TerminalColors::Default, X_COLOR),
+                Some(Mark::O) => Pixel::new('O', TerminalColors::Default, O_COLOR),
+                None => Pixel::default(),
+            };
+            line.extend([Pixel::default(), mark, Pixel::default()]);
+        }
+        rows.push(line);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        console::input_record::KeyEvent,
+        conway::patterns::PATTERNS,
+        scene::scene::{Scene, SceneTransition},
+        shared::usize2d::Usize2d,
+    };
+
+    use super::AttractMode;
+
+    fn text(attract: &AttractMode) -> Vec<String> {
+        attract
+            .frame()
+            .iter()
+            .map(|row| row.iter().map(|pixel| pixel.char()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn cycles_through_demos() {
+        let mut attract = AttractMode::new(Usize2d::new(60, 30), 5, 2, 10);
+        let mut names = vec![attract.demo_name()];
+        for _ in 0..(PATTERNS.len() + 1) * 11 * 2 {
+            attract.tick();
+            if names.last() != Some(&attract.demo_name()) {
+                names.push(attract.demo_name());
+            }
+        }
+        let mut expected = vec!["tic tac toe"];
+        expected.extend(PATTERNS.iter().map(|pattern| pattern.name));
+        expected.push("tic tac toe");
+        assert_eq!(names, expected);
+    }
+
+    #[test]
+    fn frames() {
+        let mut attract = AttractMode::new(Usize2d::new(60, 20), 5, 1, 12);
+        for _ in 0..12 {
+            attract.tick();
+        }
+        let rows = text(&attract);
+        assert!(
+            rows[19].trim_start().starts_with("Demo: tic tac toe - "),
+            "{:?}",
+            rows[19]
+        );
+        assert!(
+            rows[19].contains(" wins - ") || rows[19].contains(" draw - "),
+            "The game should be over: {}",
+            rows[19]
+        );
+        assert_eq!(rows[8].trim(), "---+---+---");
+        let marks = rows
+            .iter()
+            .flat_map(|row| row.chars())
+            .filter(|ch| *ch == 'X' || *ch == 'O')
+            .count();
+        assert!(marks >= 5, "{:?}", rows);
+
+        attract.tick();
+        let cells = attract
+            .frame()
+            .iter()
+            .flatten()
+            .filter(|pixel| pixel.char() == '█')
+            .count();
+        assert_eq!(
+            cells,
+            PATTERNS[0].live_cells().len(),
+            "{}",
+            attract.demo_name()
+        );
+    }
+
+    #[test]
+    fn any_key_returns_to_the_menu() {
+        let mut attract = AttractMode::new(Usize2d::new(40, 20), 5, 1, 12);
+        let released = KeyEvent {
+            is_down: false,
+            ..KeyEvent::key_down('q')
+        };
+        let test_cases = [
+            ("press", KeyEvent::key_down('q'), SceneTransition::Leave),
+            ("release", released, SceneTransition::Stay),
+        ];
+        for (test_case, event, expected) in test_cases {
+            assert_eq!(attract.handle_key(event), expected, "{}", test_case);
+        }
+    }
+}
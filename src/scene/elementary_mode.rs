@@ -0,0 +1,208 @@
+use crate::{
+    console::input_record::KeyEvent,
+    conway::{automaton::Automaton, elementary::Elementary},
+    rendering::colors::TerminalColors,
+    shared::{
+        frame::{Frame, Pixel},
+        usize2d::Usize2d,
+    },
+};
+
+use super::scene::{is_leave_key, write_pixels, Scene, SceneTransition};
+
+/// The color of the live cells
+const CELL_COLOR: TerminalColors = TerminalColors::Cyan;
+/// The color of the text below the generations
+const CAPTION_COLOR: TerminalColors = TerminalColors::White;
+
+/// An elementary cellular automaton with a row per generation, the newest generation at the
+/// bottom and the older ones scrolling upward
+///
+/// Space pauses, + and - switch to the next and the previous rule, r starts over from a random
+/// row and q or escape leaves the mode. A new rule starts from a single live cell
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{scene::elementary_mode::ElementaryMode, shared::usize2d::Usize2d};
+/// # let size = Usize2d::new(80, 24);
+/// # let seed = 42;
+/// let mut rule_110 = ElementaryMode::new(size, 110, seed, 5);
+/// ```
+pub struct ElementaryMode {
+    size: Usize2d,
+    automaton: Elementary,
+    seed: u64,
+    is_paused: bool,
+    ticks_per_generation: usize,
+    tick_count: usize,
+}
+impl ElementaryMode {
+    /// Create the mode, starting with a single live cell in the middle of the first row
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the terminal
+    /// * `rule` - the Wolfram number of the rule
+    /// * `seed` - the seed of the random rows
+    /// * `ticks_per_generation` - the number of ticks before the next generation
+    pub fn new(size: Usize2d, rule: u8, seed: u64, ticks_per_generation: usize) -> Self {
+        assert!(
+            ticks_per_generation > 0,
+            "The automaton needs at least one tick per generation"
+        );
+        ElementaryMode {
+            size,
+            automaton: Elementary::new(size.x.max(1), ElementaryMode::height(size), rule),
+            seed,
+            is_paused: false,
+            ticks_per_generation,
+            tick_count: 0,
+        }
+    }
+    /// Get the automaton that is shown
+    pub fn automaton(&self) -> &Elementary {
+        &self.automaton
+    }
+    /// The number of generations that fit above the caption
+    fn height(size: Usize2d) -> usize {
+        size.y.saturating_sub(1).max(1)
+    }
+    /// The line at the bottom of the screen
+    fn caption(&self) -> String {
+        let status = match self.is_paused {
+            true => "paused",
+            false => "running",
+        };
+        format!(
+            "Rule {} - generation {} {} - space + - r q",
+            self.automaton.rule(),
+            self.automaton.generation(),
+            status
+        )
+    }
+}
+impl Scene for ElementaryMode {
+    fn tick(&mut self) -> bool {
+        if self.is_paused {
+            return false;
+        }
+        self.tick_count += 1;
+        if self.tick_count < self.ticks_per_generation {
+            return false;
+        }
+        self.tick_count = 0;
+        self.automaton.next();
+        true
+    }
+    fn handle_key(&mut self, event: KeyEvent) -> SceneTransition {
+        if !event.is_down {
+            return SceneTransition::Stay;
+        }
+        let (width, height) = (self.size.x.max(1), ElementaryMode::height(self.size));
+        let rule = self.automaton.rule();
+        match event.command {
+            key if is_leave_key(key) => return SceneTransition::Leave,
+            ' ' => self.is_paused = !self.is_paused,
+            '+' => self.automaton = Elementary::new(width, height, rule.wrapping_add(1)),
+            '-' => self.automaton = Elementary::new(width, height, rule.wrapping_sub(1)),
+            'r' | 'R' => {
+                self.seed = self.seed.wrapping_add(1);
+                self.automaton = Elementary::random(width, height, rule, self.seed);
+            }
+            _ => {}
+        }
+        SceneTransition::Stay
+    }
+    fn frame(&self) -> Frame {
+        let mut frame = vec![vec![Pixel::default(); self.size.x]; self.size.y];
+        // The newest generation is always drawn right above the caption
+        let top = ElementaryMode::height(self.size) - self.automaton.cells().len();
+        for (y, row) in self.automaton.cells().iter().enumerate() {
+            for (x, is_alive) in row.iter().enumerate() {
+                if *is_alive {
+                    let pixel = Pixel::new('█', TerminalColors::Default, CELL_COLOR);
+                    write_pixels(&mut frame, Usize2d::new(x, top + y), &[pixel]);
+                }
+            }
+        }
+        let caption: Vec<Pixel> = self
+            .caption()
+            .chars()
+            .map(|ch| Pixel::new(ch, TerminalColors::Default, CAPTION_COLOR))
+            .collect();
+        let left = self.size.x.saturating_sub(caption.len()) / 2;
+        write_pixels(
+            &mut frame,
+            Usize2d::new(left, self.size.y.saturating_sub(1)),
+            &caption,
+        );
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        console::input_record::KeyEvent,
+        scene::scene::{Scene, SceneTransition},
+        shared::usize2d::Usize2d,
+    };
+
+    use super::ElementaryMode;
+
+    fn text(mode: &ElementaryMode) -> Vec<String> {
+        mode.frame()
+            .iter()
+            .map(|row| row.iter().map(|pixel| pixel.char()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn generations_scroll_upward() {
+        let mut mode = ElementaryMode::new(Usize2d::new(51, 4), 30, 1, 1);
+        let rows = text(&mode);
+        assert_eq!(rows[2].trim(), "█", "The first generation is at the bottom");
+        assert!(rows[0].trim().is_empty());
+        mode.tick();
+        let rows = text(&mode);
+        assert_eq!((rows[1].trim(), rows[2].trim()), ("█", "███"));
+        mode.tick();
+        mode.tick();
+        let rows = text(&mode);
+        assert_eq!(rows[0].trim(), "███", "The first generation scrolled out");
+        assert_eq!(rows[2].trim(), "██ ████");
+        assert_eq!(
+            rows[3].trim(),
+            "Rule 30 - generation 3 running - space + - r q"
+        );
+    }
+
+    #[test]
+    fn keys() {
+        let mut mode = ElementaryMode::new(Usize2d::new(20, 6), 30, 1, 1);
+        let test_cases = [
+            ("next rule", '+', 31),
+            ("previous rule", '-', 30),
+            ("wraps", '-', 29),
+            ("random row", 'r', 29),
+        ];
+        for (test_case, key, rule) in test_cases {
+            mode.tick();
+            assert_eq!(
+                mode.handle_key(KeyEvent::key_down(key)),
+                SceneTransition::Stay,
+                "{}",
+                test_case
+            );
+            assert_eq!(mode.automaton().rule(), rule, "{}", test_case);
+            assert_eq!(mode.automaton().generation(), 0, "{}", test_case);
+        }
+        mode.handle_key(KeyEvent::key_down(' '));
+        assert!(!mode.tick(), "The automaton is paused");
+        assert_eq!(
+            mode.handle_key(KeyEvent::key_down('\x1b')),
+            SceneTransition::Leave
+        );
+    }
+}
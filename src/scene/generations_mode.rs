@@ -0,0 +1,205 @@
+use crate::{
+    console::input_record::KeyEvent,
+    conway::{
+        automaton::Automaton,
+        generations::{Generations, GenerationsRule},
+    },
+    rendering::colors::TerminalColors,
+    shared::{
+        frame::{Frame, Pixel},
+        usize2d::Usize2d,
+    },
+};
+
+use super::scene::{draw_cells, is_leave_key, write_pixels, Scene, SceneTransition};
+
+/// The color of the text below the board
+const CAPTION_COLOR: TerminalColors = TerminalColors::White;
+
+/// A Generations rule running with a cell per character, the decaying cells leave trails that
+/// fade through the decay palette
+///
+/// Space pauses the board, r starts over with the next seed and q or escape leaves the mode
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{conway::generations::GenerationsRule, scene::generations_mode::GenerationsMode, shared::usize2d::Usize2d};
+/// # let size = Usize2d::new(80, 24);
+/// # let seed = 42;
+/// let mut star_wars = GenerationsMode::new(size, seed, 5, GenerationsRule::star_wars());
+/// ```
+pub struct GenerationsMode {
+    size: Usize2d,
+    automaton: Generations,
+    seed: u64,
+    is_paused: bool,
+    ticks_per_generation: usize,
+    tick_count: usize,
+}
+impl GenerationsMode {
+    /// Create the mode with a random grid that fills the terminal above the caption
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the terminal
+    /// * `seed` - the seed of the first grid
+    /// * `ticks_per_generation` - the number of ticks before the next generation
+    /// * `rule` - the rule the cells change by
+    pub fn new(
+        size: Usize2d,
+        seed: u64,
+        ticks_per_generation: usize,
+        rule: GenerationsRule,
+    ) -> Self {
+        assert!(
+            ticks_per_generation > 0,
+            "The board needs at least one tick per generation"
+        );
+        GenerationsMode {
+            size,
+            automaton: GenerationsMode::grid(size, seed, rule),
+            seed,
+            is_paused: false,
+            ticks_per_generation,
+            tick_count: 0,
+        }
+    }
+    /// Get the automaton that is shown
+    pub fn automaton(&self) -> &Generations {
+        &self.automaton
+    }
+    /// Create a random grid for a terminal, leaving the bottom row for the caption
+    fn grid(size: Usize2d, seed: u64, rule: GenerationsRule) -> Generations {
+        let (x_len, y_len) = (size.x.max(1), size.y.saturating_sub(1).max(1));
+        Generations::random(x_len, y_len, seed, rule)
+    }
+    /// The line at the bottom of the screen
+    fn caption(&self) -> String {
+        let status = match self.is_paused {
+            true => "paused",
+            false => "running",
+        };
+        format!(
+            "Generations {} - alive {} decaying {} - round {} {} - space r q",
+            self.automaton.rule(),
+            self.automaton.population(),
+            self.automaton.decaying(),
+            self.automaton.rounds(),
+            status
+        )
+    }
+}
+impl Scene for GenerationsMode {
+    fn tick(&mut self) -> bool {
+        if self.is_paused {
+            return false;
+        }
+        self.tick_count += 1;
+        if self.tick_count < self.ticks_per_generation {
+            return false;
+        }
+        self.tick_count = 0;
+        self.automaton.next();
+        true
+    }
+    fn handle_key(&mut self, event: KeyEvent) -> SceneTransition {
+        if !event.is_down {
+            return SceneTransition::Stay;
+        }
+        match event.command {
+            key if is_leave_key(key) => return SceneTransition::Leave,
+            ' ' => self.is_paused = !self.is_paused,
+            'r' | 'R' => {
+                self.seed = self.seed.wrapping_add(1);
+                let rule = *self.automaton.rule();
+                self.automaton = GenerationsMode::grid(self.size, self.seed, rule);
+            }
+            _ => {}
+        }
+        SceneTransition::Stay
+    }
+    fn frame(&self) -> Frame {
+        let mut frame = vec![vec![Pixel::default(); self.size.x]; self.size.y];
+        let rule = self.automaton.rule();
+        draw_cells(&mut frame, self.automaton.cells(), |state| {
+            rule.color(*state)
+                .map(|color| Pixel::new('█', TerminalColors::Default, color))
+        });
+        let caption: Vec<Pixel> = self
+            .caption()
+            .chars()
+            .map(|ch| Pixel::new(ch, TerminalColors::Default, CAPTION_COLOR))
+            .collect();
+        let left = self.size.x.saturating_sub(caption.len()) / 2;
+        write_pixels(
+            &mut frame,
+            Usize2d::new(left, self.size.y.saturating_sub(1)),
+            &caption,
+        );
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        console::input_record::KeyEvent,
+        conway::generations::GenerationsRule,
+        rendering::colors::{TerminalColors, DECAY_PALETTE},
+        scene::scene::{Scene, SceneTransition},
+        shared::usize2d::Usize2d,
+    };
+
+    use super::GenerationsMode;
+
+    #[test]
+    fn trails_are_drawn() {
+        let mut mode =
+            GenerationsMode::new(Usize2d::new(80, 20), 2, 1, GenerationsRule::star_wars());
+        for _ in 0..3 {
+            mode.tick();
+        }
+        let frame = mode.frame();
+        let count = |color: TerminalColors| {
+            frame[..19]
+                .iter()
+                .flatten()
+                .filter(|pixel| pixel.char() == '█' && pixel.foreground_color() == color)
+                .count()
+        };
+        assert_eq!(count(TerminalColors::White), mode.automaton().population());
+        let decaying = count(DECAY_PALETTE[0]) + count(DECAY_PALETTE[4]);
+        assert_eq!(decaying, mode.automaton().decaying());
+        assert!(decaying > 0, "Star Wars leaves trails");
+
+        let caption: String = frame[19].iter().map(|pixel| pixel.char()).collect();
+        assert!(
+            caption.contains("Generations 345/2/4 - alive "),
+            "{:?}",
+            caption
+        );
+        assert!(caption.contains(" - round 3 running"), "{:?}", caption);
+    }
+
+    #[test]
+    fn keys() {
+        let mut mode =
+            GenerationsMode::new(Usize2d::new(30, 10), 2, 1, GenerationsRule::star_wars());
+        let test_cases = [
+            ("release", false, ' ', SceneTransition::Stay),
+            ("pause", true, ' ', SceneTransition::Stay),
+            ("leave", true, 'q', SceneTransition::Leave),
+        ];
+        for (test_case, is_down, key, expected) in test_cases {
+            let event = KeyEvent {
+                is_down,
+                ..KeyEvent::key_down(key)
+            };
+            assert_eq!(mode.handle_key(event), expected, "{}", test_case);
+        }
+        assert!(!mode.tick(), "The board is paused");
+        mode.handle_key(KeyEvent::key_down('r'));
+        assert_eq!(mode.automaton().rounds(), 0);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{
+    console::input_record::KeyEvent,
+    conway::{
+        automaton::Automaton,
+        immigration::{Immigration, Species},
+        rule::Rule,
+    },
+    rendering::colors::TerminalColors,
+    shared::{
+        frame::{Frame, Pixel},
+        usize2d::Usize2d,
+    },
+};
+
+use super::scene::{draw_cells, is_leave_key, write_pixels, Scene, SceneTransition};
+
+/// The color of the text below the board
+const CAPTION_COLOR: TerminalColors = TerminalColors::White;
+
+/// The two species Immigration variant of the Game of Life, with a cell per character and the
+/// population of each species below the board
+///
+/// Space pauses the board, r starts over with the next seed and q or escape leaves the mode
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{scene::immigration_mode::ImmigrationMode, shared::usize2d::Usize2d};
+/// # let seed = 42;
+/// let mut immigration = ImmigrationMode::new(Usize2d::new(80, 24), seed, 5);
+/// ```
+pub struct ImmigrationMode {
+    size: Usize2d,
+    automaton: Immigration,
+    seed: u64,
+    is_paused: bool,
+    ticks_per_generation: usize,
+    tick_count: usize,
+}
+impl ImmigrationMode {
+    /// Create the mode with a random grid that fills the terminal above the caption
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the terminal
+    /// * `seed` - the seed of the first grid
+    /// * `ticks_per_generation` - the number of ticks before the next generation
+    pub fn new(size: Usize2d, seed: u64, ticks_per_generation: usize) -> Self {
+        assert!(
+            ticks_per_generation > 0,
+            "The board needs at least one tick per generation"
+        );
+        ImmigrationMode {
+            size,
+            automaton: ImmigrationMode::grid(size, seed),
+            seed,
+            is_paused: false,
+            ticks_per_generation,
+            tick_count: 0,
+        }
+    }
+    /// Get the automaton that is shown
+    pub fn automaton(&self) -> &Immigration {
+        &self.automaton
+    }
+    /// Create a random grid for a terminal, leaving the bottom row for the caption
+    fn grid(size: Usize2d, seed: u64) -> Immigration {
+        let (x_len, y_len) = (size.x.max(1), size.y.saturating_sub(1).max(1));
+        Immigration::random(x_len, y_len, seed, Rule::conway())
+    }
+    /// The line at the bottom of the screen, with the population of each species in its color
+    fn caption(&self) -> Vec<Pixel> {
+        let text = |text: &str, color: TerminalColors| -> Vec<Pixel> {
+            text.chars()
+                .map(|ch| Pixel::new(ch, TerminalColors::Default, color))
+                .collect()
+        };
+        let mut caption = text("Immigration - ", CAPTION_COLOR);
+        for species in [Species::Red, Species::Blue] {
+            let population = format!("{} {} ", species, self.automaton.population(species));
+            caption.extend(text(&population, species.color()));
+        }
+        let status = match self.is_paused {
+            true => "paused",
+            false => "running",
+        };
+        caption.extend(text(
+            &format!("- round {} {} - space r q", self.automaton.rounds(), status),
+            CAPTION_COLOR,
+        ));
+        caption
+    }
+}
+impl Scene for ImmigrationMode {
+    fn tick(&mut self) -> bool {
+        if self.is_paused {
+            return false;
+        }
+        self.tick_count += 1;
+        if self.tick_count < self.ticks_per_generation {
+            return false;
+        }
+        self.tick_count = 0;
+        self.automaton.next();
+        true
+    }
+    fn handle_key(&mut self, event: KeyEvent) -> SceneTransition {
+        if !event.is_down {
+            return SceneTransition::Stay;
+        }
+        match event.command {
+            key if is_leave_key(key) => return SceneTransition::Leave,
+            ' ' => self.is_paused = !self.is_paused,
+            'r' | 'R' => {
+                self.seed = self.seed.wrapping_add(1);
+                self.automaton = ImmigrationMode::grid(self.size, self.seed);
+            }
+            _ => {}
+        }
+        SceneTransition::Stay
+    }
+    fn frame(&self) -> Frame {
+        let mut frame = vec![vec![Pixel::default(); self.size.x]; self.size.y];
+        draw_cells(&mut frame, self.automaton.cells(), |cell| {
+            cell.map(|species| Pixel::new('█', TerminalColors::Default, species.color()))
+        });
+        let caption = self.caption();
+        let left = self.size.x.saturating_sub(caption.len()) / 2;
+        write_pixels(
+            &mut frame,
+            Usize2d::new(left, self.size.y.saturating_sub(1)),
+            &caption,
+        );
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        console::input_record::KeyEvent,
+        conway::immigration::Species,
+        rendering::colors::TerminalColors,
+        scene::scene::{Scene, SceneTransition},
+        shared::usize2d::Usize2d,
+    };
+
+    use super::ImmigrationMode;
+
+    #[test]
+    fn frame() {
+        let mode = ImmigrationMode::new(Usize2d::new(60, 12), 4, 1);
+        let frame = mode.frame();
+        assert_eq!(frame.len(), 12);
+        for species in [Species::Red, Species::Blue] {
+            let drawn = frame[..11]
+                .iter()
+                .flatten()
+                .filter(|pixel| pixel.char() == '█' && pixel.foreground_color() == species.color())
+                .count();
+            assert_eq!(drawn, mode.automaton().population(species), "{}", species);
+        }
+        let caption: String = frame[11].iter().map(|pixel| pixel.char()).collect();
+        let expected = format!(
+            "Immigration - red {} blue {} - round 0 running",
+            mode.automaton().population(Species::Red),
+            mode.automaton().population(Species::Blue)
+        );
+        assert!(caption.contains(&expected), "{:?}", caption);
+        let red_label = frame[11]
+            .iter()
+            .find(|pixel| pixel.char() == 'r' && pixel.foreground_color() == TerminalColors::Red);
+        assert!(red_label.is_some(), "The counts are drawn in their colors");
+    }
+
+    #[test]
+    fn keys() {
+        let mut mode = ImmigrationMode::new(Usize2d::new(30, 10), 4, 2);
+        let test_cases = [
+            ("first tick", None, false, 0),
+            ("second tick", None, true, 1),
+            ("pause", Some(' '), false, 1),
+            ("paused", None, false, 1),
+            ("continue", Some(' '), false, 1),
+            ("running", None, false, 1),
+            ("next generation", None, true, 2),
+            ("reseed", Some('r'), false, 0),
+        ];
+        for (test_case, key, redraw, rounds) in test_cases {
+            let redrawn = match key {
+                Some(key) => {
+                    let transition = mode.handle_key(KeyEvent::key_down(key));
+                    assert_eq!(transition, SceneTransition::Stay, "{}", test_case);
+                    false
+                }
+                None => mode.tick(),
+            };
+            assert_eq!(redrawn, redraw, "{}", test_case);
+            assert_eq!(mode.automaton().rounds(), rounds, "{}", test_case);
+        }
+        assert_eq!(
+            mode.handle_key(KeyEvent::key_down('q')),
+            SceneTransition::Leave
+        );
+    }
+}
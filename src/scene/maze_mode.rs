@@ -0,0 +1,376 @@
+use std::time::Duration;
+
+use crate::{
+    console::input_record::KeyEvent,
+    conway::options::step_speed,
+    maze::{
+        generator::{GenerationAlgorithm, MazeGenerator},
+        maze::{Maze, MazeCell},
+        solver::{MazeSolver, SearchAlgorithm},
+    },
+    rendering::colors::TerminalColors,
+    shared::{
+        frame::{Frame, Pixel},
+        usize2d::Usize2d,
+    },
+    utils::{
+        clock::Clock,
+        timer::{TimerId, Timers},
+    },
+};
+
+use super::scene::{draw_cells, is_leave_key, write_pixels, Scene, SceneTransition};
+
+/// The color of the walls of the maze
+const WALL_COLOR: TerminalColors = TerminalColors::Grey;
+/// The color of the cell the generator carved last
+const CARVING_COLOR: TerminalColors = TerminalColors::Red;
+/// The color of the cells the search explored
+const EXPLORED_COLOR: TerminalColors = TerminalColors::DarkBlue;
+/// The color of the path from the start to the goal
+const PATH_COLOR: TerminalColors = TerminalColors::Yellow;
+/// The color of the text below the maze
+const CAPTION_COLOR: TerminalColors = TerminalColors::White;
+
+/// What the maze mode animates at the moment
+enum Phase {
+    /// The maze is carved a passage per step
+    Generating(Box<MazeGenerator>),
+    /// The finished maze is searched from the top left to the bottom right a cell per step
+    Solving(Maze, MazeSolver),
+}
+
+/// A maze that is carved and then solved a step at a time
+///
+/// The steps follow the speeds of the Game of Life, + and - make them faster and slower. g and
+/// s switch the algorithm of the generation and the search, r carves a new maze with the next
+/// seed, space pauses and q or escape leaves the mode
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use tic_tac_toe2::{maze::{generator::GenerationAlgorithm, solver::SearchAlgorithm}, scene::maze_mode::MazeMode, shared::usize2d::Usize2d, utils::clock::SystemClock};
+/// # let size = Usize2d::new(80, 24);
+/// # let seed = 42;
+/// let mut maze = MazeMode::new(
+///     size,
+///     seed,
+///     GenerationAlgorithm::Backtracker,
+///     SearchAlgorithm::AStar,
+///     Duration::from_millis(50),
+///     Box::new(SystemClock),
+/// );
+/// ```
+pub struct MazeMode {
+    size: Usize2d,
+    seed: u64,
+    generation: GenerationAlgorithm,
+    search: SearchAlgorithm,
+    phase: Phase,
+    is_paused: bool,
+    round_duration: Duration,
+    clock: Box<dyn Clock>,
+    timers: Timers<()>,
+    step_timer: TimerId,
+}
+impl MazeMode {
+    /// Create the mode with a maze that fills the terminal above the caption
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the terminal
+    /// * `seed` - the seed of the first maze
+    /// * `generation` - how the maze is carved
+    /// * `search` - how the path through the maze is searched
+    /// * `round_duration` - the time between two steps
+    /// * `clock` - the clock the steps are timed by
+    pub fn new(
+        size: Usize2d,
+        seed: u64,
+        generation: GenerationAlgorithm,
+        search: SearchAlgorithm,
+        round_duration: Duration,
+        clock: Box<dyn Clock>,
+    ) -> Self {
+        let mut timers = Timers::default();
+        let step_timer = timers.repeating(round_duration, (), clock.now());
+        MazeMode {
+            size,
+            seed,
+            generation,
+            search,
+            phase: MazeMode::carve(size, generation, seed),
+            is_paused: false,
+            round_duration,
+            clock,
+            timers,
+            step_timer,
+        }
+    }
+    /// Get the maze as it is carved so far
+    pub fn maze(&self) -> &Maze {
+        match &self.phase {
+            Phase::Generating(generator) => generator.maze(),
+            Phase::Solving(maze, _) => maze,
+        }
+    }
+    /// Get the search, once the maze is carved
+    pub fn solver(&self) -> Option<&MazeSolver> {
+        match &self.phase {
+            Phase::Generating(_) => None,
+            Phase::Solving(_, solver) => Some(solver),
+        }
+    }
+    pub fn round_duration(&self) -> Duration {
+        self.round_duration
+    }
+    /// Start carving the largest maze that fits in a terminal above the caption
+    fn carve(size: Usize2d, generation: GenerationAlgorithm, seed: u64) -> Phase {
+        let columns = (size.x.saturating_sub(1) / 2).max(1);
+        let rows = (size.y.saturating_sub(2) / 2).max(1);
+        Phase::Generating(Box::new(MazeGenerator::new(
+            columns, rows, generation, seed,
+        )))
+    }
+    /// Search the maze from the top left to the bottom right cell
+    fn solve(maze: Maze, search: SearchAlgorithm) -> Phase {
+        let goal = (maze.columns() - 1, maze.rows() - 1);
+        let solver = MazeSolver::new(&maze, (0, 0), goal, search);
+        Phase::Solving(maze, solver)
+    }
+    /// Carve the next passage or explore the next cell
+    fn step(&mut self) {
+        match &mut self.phase {
+            Phase::Generating(generator) => {
+                if !generator.step() {
+                    self.phase = MazeMode::solve(generator.maze().clone(), self.search);
+                }
+            }
+            Phase::Solving(maze, solver) => {
+                solver.step(maze);
+            }
+        }
+    }
+    /// Change the time between two steps along the speeds of the Game of Life
+    fn change_speed(&mut self, step: i64) {
+        self.round_duration = step_speed(self.round_duration, step);
+        self.timers
+            .set_interval(self.step_timer, self.round_duration, self.clock.now());
+    }
+    /// The line at the bottom of the screen
+    fn caption(&self) -> String {
+        let progress = match &self.phase {
+            Phase::Generating(_) => format!("carving with {}", self.generation),
+            Phase::Solving(_, solver) => match solver.path() {
+                Some(path) => format!(
+                    "{} found a path of {} cells, explored {}",
+                    self.search,
+                    path.len(),
+                    solver.explored_count()
+                ),
+                None => format!("{} explored {}", self.search, solver.explored_count()),
+            },
+        };
+        let status = match self.is_paused {
+            true => " paused",
+            false => "",
+        };
+        format!(
+            "Maze - {} - {} ms{} - space + - g s r q",
+            progress,
+            self.round_duration.as_millis(),
+            status
+        )
+    }
+}
+impl Scene for MazeMode {
+    fn tick(&mut self) -> bool {
+        let steps = self.timers.tick(self.clock.now()).len();
+        if self.is_paused || steps == 0 {
+            return false;
+        }
+        self.step();
+        true
+    }
+    fn handle_key(&mut self, event: KeyEvent) -> SceneTransition {
+        if !event.is_down {
+            return SceneTransition::Stay;
+        }
+        match event.command {
+            key if is_leave_key(key) => return SceneTransition::Leave,
+            ' ' => self.is_paused = !self.is_paused,
+            '+' => self.change_speed(1),
+            '-' => self.change_speed(-1),
+            'g' | 'G' => {
+                self.generation = match self.generation {
+                    GenerationAlgorithm::Backtracker => GenerationAlgorithm::Prim,
+                    GenerationAlgorithm::Prim => GenerationAlgorithm::Backtracker,
+                };
+                self.phase = MazeMode::carve(self.size, self.generation, self.seed);
+            }
+            's' | 'S' => {
+                self.search = match self.search {
+                    SearchAlgorithm::BreadthFirst => SearchAlgorithm::AStar,
+                    SearchAlgorithm::AStar => SearchAlgorithm::BreadthFirst,
+                };
+                if let Phase::Solving(maze, _) = &self.phase {
+                    self.phase = MazeMode::solve(maze.clone(), self.search);
+                }
+            }
+            'r' | 'R' => {
+                self.seed = self.seed.wrapping_add(1);
+                self.phase = MazeMode::carve(self.size, self.generation, self.seed);
+            }
+            _ => {}
+        }
+        SceneTransition::Stay
+    }
+    fn frame(&self) -> Frame {
+        let mut frame = vec![vec![Pixel::default(); self.size.x]; self.size.y];
+        let block = |color: TerminalColors| Pixel::new('█', TerminalColors::Default, color);
+        draw_cells(&mut frame, self.maze().walls(), |is_wall| {
+            is_wall.then(|| block(WALL_COLOR))
+        });
+        // The characters to color over the maze, as `(x, y)` in the frame
+        let mut marked: Vec<((usize, usize), TerminalColors)> = vec![];
+        match &self.phase {
+            Phase::Generating(generator) => {
+                marked.push((Maze::position(generator.current()), CARVING_COLOR));
+            }
+            Phase::Solving(maze, solver) => {
+                for y in 0..maze.rows() {
+                    for x in 0..maze.columns() {
+                        if solver.is_explored((x, y)) {
+                            marked.push((Maze::position((x, y)), EXPLORED_COLOR));
+                        }
+                    }
+                }
+                let path: &[MazeCell] = solver.path().unwrap_or_default();
+                for cell in path {
+                    marked.push((Maze::position(*cell), PATH_COLOR));
+                }
+                for pair in path.windows(2) {
+                    marked.push((Maze::wall_between(pair[0], pair[1]), PATH_COLOR));
+                }
+            }
+        }
+        for ((x, y), color) in marked {
+            write_pixels(&mut frame, Usize2d::new(x, y), &[block(color)]);
+        }
+        let caption: Vec<Pixel> = self
+            .caption()
+            .chars()
+            .map(|ch| Pixel::new(ch, TerminalColors::Default, CAPTION_COLOR))
+            .collect();
+        let left = self.size.x.saturating_sub(caption.len()) / 2;
+        write_pixels(
+            &mut frame,
+            Usize2d::new(left, self.size.y.saturating_sub(1)),
+            &caption,
+        );
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        console::input_record::KeyEvent,
+        maze::{generator::GenerationAlgorithm, solver::SearchAlgorithm},
+        rendering::colors::TerminalColors,
+        scene::scene::{Scene, SceneTransition},
+        shared::usize2d::Usize2d,
+        utils::clock::VirtualClock,
+    };
+
+    use super::MazeMode;
+
+    fn mode(clock: &VirtualClock) -> MazeMode {
+        MazeMode::new(
+            Usize2d::new(80, 12),
+            5,
+            GenerationAlgorithm::Backtracker,
+            SearchAlgorithm::AStar,
+            Duration::from_millis(100),
+            Box::new(clock.clone()),
+        )
+    }
+
+    #[test]
+    fn carves_then_solves() {
+        let clock = VirtualClock::new();
+        let mut mode = mode(&clock);
+        assert_eq!((mode.maze().columns(), mode.maze().rows()), (39, 5));
+        assert!(!mode.tick(), "The first step waits for the speed");
+        for _ in 0..39 * 5 {
+            clock.advance(Duration::from_millis(100));
+            assert!(mode.tick());
+        }
+        assert!(mode.solver().is_some(), "The maze is carved");
+        while !mode.solver().unwrap().is_done() {
+            clock.advance(Duration::from_millis(100));
+            mode.tick();
+        }
+        let path = mode.solver().unwrap().path().unwrap().to_vec();
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(38, 4)));
+        let frame = mode.frame();
+        assert_eq!(frame[1][1].foreground_color(), TerminalColors::Yellow);
+        assert_eq!(frame[0][0].foreground_color(), TerminalColors::Grey);
+        let caption: String = frame[11].iter().map(|pixel| pixel.char()).collect();
+        let expected = format!("A* found a path of {} cells", path.len());
+        assert!(caption.contains(&expected), "{:?}", caption);
+    }
+
+    #[test]
+    fn keys() {
+        let clock = VirtualClock::new();
+        let mut mode = mode(&clock);
+        let test_cases = [
+            ("faster", '+', 50),
+            ("fastest", '+', 50),
+            ("slower", '-', 100),
+            ("slower again", '-', 250),
+        ];
+        for (test_case, key, millis) in test_cases {
+            mode.handle_key(KeyEvent::key_down(key));
+            assert_eq!(
+                mode.round_duration(),
+                Duration::from_millis(millis),
+                "{}",
+                test_case
+            );
+        }
+        clock.advance(Duration::from_millis(100));
+        assert!(!mode.tick(), "The new speed is used");
+        clock.advance(Duration::from_millis(150));
+        assert!(mode.tick());
+        mode.handle_key(KeyEvent::key_down(' '));
+        clock.advance(Duration::from_millis(250));
+        assert!(!mode.tick(), "Paused");
+        mode.handle_key(KeyEvent::key_down(' '));
+        for _ in 0..39 * 5 {
+            clock.advance(Duration::from_millis(250));
+            mode.tick();
+        }
+        mode.handle_key(KeyEvent::key_down('s'));
+        let solver = mode.solver().expect("The maze is carved");
+        assert_eq!(solver.algorithm(), SearchAlgorithm::BreadthFirst);
+        assert_eq!(solver.explored_count(), 0, "The search starts over");
+        mode.handle_key(KeyEvent::key_down('g'));
+        assert!(mode.solver().is_none(), "A new maze is carved");
+        let caption: String = mode.frame()[11].iter().map(|pixel| pixel.char()).collect();
+        assert!(
+            caption.contains("carving with Prim's - 250 ms"),
+            "{:?}",
+            caption
+        );
+        assert_eq!(
+            mode.handle_key(KeyEvent::key_down('q')),
+            SceneTransition::Leave
+        );
+    }
+}
@@ -0,0 +1,382 @@
+use rand::Rng;
+
+use crate::{
+    console::input_record::KeyEvent,
+    conway::{
+        bit_grid::BitGrid, rule::Rule, settings::Boundary, shared_clipboard::SharedClipboard,
+    },
+    rendering::colors::TerminalColors,
+    shared::{
+        frame::{Frame, Pixel},
+        usize2d::Usize2d,
+    },
+    utils::rng::{RngService, GRID_STREAM},
+};
+
+use super::scene::{is_leave_key, write_pixels, Scene, SceneTransition};
+
+/// The color of the text above the boards and below them
+const CAPTION_COLOR: TerminalColors = TerminalColors::White;
+/// The color of the living cells
+const CELL_COLOR: TerminalColors = TerminalColors::LightGreen;
+/// The background of the cells that are not the same on both boards
+const DIFFERENCE_COLOR: TerminalColors = TerminalColors::HotPink;
+/// The color of the column between the boards
+const DIVIDER_COLOR: TerminalColors = TerminalColors::Grey;
+
+/// How one of the boards of a race is stepped
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Racer {
+    pub rule: Rule,
+    pub boundary: Boundary,
+}
+impl Racer {
+    pub fn new(rule: Rule, boundary: Boundary) -> Self {
+        Racer { rule, boundary }
+    }
+}
+
+/// The same seed under two rules or boundaries side by side, stepped in lockstep so that the
+/// boards can be compared generation by generation
+///
+/// The cells that are not the same on both boards are highlighted on both of them. Space pauses
+/// the boards, h shows or hides the highlight, r starts over with the next seed, p starts over
+/// with the pattern on the clipboard and q or escape leaves the mode
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{conway::settings::Boundary, scene::race_mode::{RaceMode, Racer}, shared::usize2d::Usize2d, Rule};
+/// # let size = Usize2d::new(80, 24);
+/// # let seed = 42;
+/// let wrap = Racer::new(Rule::conway(), Boundary::Wrap);
+/// let dead = Racer::new(Rule::conway(), Boundary::Dead);
+/// let mut race = RaceMode::new(size, seed, 5, [wrap, dead]);
+/// ```
+pub struct RaceMode {
+    size: Usize2d,
+    racers: [Racer; 2],
+    boards: [BitGrid; 2],
+    seed: u64,
+    rounds: u64,
+    is_paused: bool,
+    is_highlighted: bool,
+    ticks_per_generation: usize,
+    tick_count: usize,
+    clipboard: SharedClipboard,
+}
+impl RaceMode {
+    /// Create the mode with the same random grid on both boards, each filling half of the
+    /// terminal between the title row and the caption
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the terminal
+    /// * `seed` - the seed of the first grid
+    /// * `ticks_per_generation` - the number of ticks before the next generation
+    /// * `racers` - how the left board and the right board are stepped
+    pub fn new(size: Usize2d, seed: u64, ticks_per_generation: usize, racers: [Racer; 2]) -> Self {
+        assert!(
+            ticks_per_generation > 0,
+            "The boards need at least one tick per generation"
+        );
+        RaceMode {
+            size,
+            racers,
+            boards: RaceMode::grids(size, seed, racers),
+            seed,
+            rounds: 0,
+            is_paused: false,
+            is_highlighted: true,
+            ticks_per_generation,
+            tick_count: 0,
+            clipboard: SharedClipboard::default(),
+        }
+    }
+    /// Paste from a clipboard that is shared with the editor and the other scenes
+    pub fn set_clipboard(&mut self, clipboard: SharedClipboard) {
+        self.clipboard = clipboard;
+    }
+    /// Get the left board and the right board
+    pub fn boards(&self) -> &[BitGrid; 2] {
+        &self.boards
+    }
+    pub fn rounds(&self) -> u64 {
+        self.rounds
+    }
+    /// Count the cells that are alive on one board and dead on the other
+    pub fn difference(&self) -> usize {
+        let [left, right] = &self.boards;
+        (0..left.height())
+            .flat_map(|y| (0..left.width()).map(move |x| (x, y)))
+            .filter(|(x, y)| left.get(*x, *y) != right.get(*x, *y))
+            .count()
+    }
+    /// Start over with the pattern on the clipboard in the middle of empty boards, the cells that
+    /// do not fit are left out
+    fn paste(&mut self) {
+        let Some(pattern) = self.clipboard.get() else {
+            return;
+        };
+        let (x_len, y_len) = RaceMode::board_size(self.size);
+        let left = x_len.saturating_sub(pattern.width()) / 2;
+        let top = y_len.saturating_sub(pattern.height()) / 2;
+        let mut cells = vec![vec![false; x_len]; y_len];
+        for (y, row) in pattern.cells.iter().enumerate() {
+            for (x, is_alive) in row.iter().enumerate() {
+                if let Some(cell) = cells.get_mut(top + y).and_then(|row| row.get_mut(left + x)) {
+                    *cell = *is_alive;
+                }
+            }
+        }
+        self.boards = self
+            .racers
+            .map(|racer| BitGrid::from_cells(&cells, racer.boundary));
+        self.rounds = 0;
+    }
+    /// Create the same random grid for both boards, with half of the terminal but the divider
+    /// for each and the top and bottom rows for the captions
+    fn grids(size: Usize2d, seed: u64, racers: [Racer; 2]) -> [BitGrid; 2] {
+        let (x_len, y_len) = RaceMode::board_size(size);
+        let mut rng = RngService::new(seed).stream(GRID_STREAM);
+        let cells: Vec<Vec<bool>> = (0..y_len)
+            .map(|_| (0..x_len).map(|_| rng.gen::<bool>()).collect())
+            .collect();
+        racers.map(|racer| BitGrid::from_cells(&cells, racer.boundary))
+    }
+    fn board_size(size: Usize2d) -> (usize, usize) {
+        (
+            (size.x.saturating_sub(1) / 2).max(1),
+            size.y.saturating_sub(2).max(1),
+        )
+    }
+    /// The name of a board and its population, above the board
+    fn title(&self, index: usize) -> String {
+        let racer = self.racers[index];
+        format!(
+            "{} {} - alive {}",
+            racer.rule,
+            racer.boundary,
+            self.boards[index].population()
+        )
+    }
+    /// The line at the bottom of the screen
+    fn caption(&self) -> String {
+        let status = match self.is_paused {
+            true => "paused",
+            false => "running",
+        };
+        format!(
+            "Race - {} cells differ - round {} {} - space h r p q",
+            self.difference(),
+            self.rounds,
+            status
+        )
+    }
+}
+impl Scene for RaceMode {
+    fn tick(&mut self) -> bool {
+        if self.is_paused {
+            return false;
+        }
+        self.tick_count += 1;
+        if self.tick_count < self.ticks_per_generation {
+            return false;
+        }
+        self.tick_count = 0;
+        let [left, right] = &self.boards;
+        self.boards = [
+            left.step(self.racers[0].rule),
+            right.step(self.racers[1].rule),
+        ];
+        self.rounds += 1;
+        true
+    }
+    fn handle_key(&mut self, event: KeyEvent) -> SceneTransition {
+        if !event.is_down {
+            return SceneTransition::Stay;
+        }
+        match event.command {
+            key if is_leave_key(key) => return SceneTransition::Leave,
+            ' ' => self.is_paused = !self.is_paused,
+            'h' | 'H' => self.is_highlighted = !self.is_highlighted,
+            'r' | 'R' => {
+                self.seed = self.seed.wrapping_add(1);
+                self.boards = RaceMode::grids(self.size, self.seed, self.racers);
+                self.rounds = 0;
+            }
+            'p' | 'P' => self.paste(),
+            _ => {}
+        }
+        SceneTransition::Stay
+    }
+    fn frame(&self) -> Frame {
+        let mut frame = vec![vec![Pixel::default(); self.size.x]; self.size.y];
+        let text = |text: String, color: TerminalColors| -> Vec<Pixel> {
+            text.chars()
+                .map(|ch| Pixel::new(ch, TerminalColors::Default, color))
+                .collect()
+        };
+        let (x_len, y_len) = RaceMode::board_size(self.size);
+        let [left, right] = &self.boards;
+        for (index, board) in self.boards.iter().enumerate() {
+            let x_offset = index * (x_len + 1);
+            write_pixels(
+                &mut frame,
+                Usize2d::new(x_offset, 0),
+                &text(self.title(index), CAPTION_COLOR),
+            );
+            for y in 0..y_len {
+                for x in 0..x_len {
+                    let is_different = left.get(x, y) != right.get(x, y);
+                    let background = match self.is_highlighted && is_different {
+                        true => DIFFERENCE_COLOR,
+                        false => TerminalColors::Default,
+                    };
+                    let ch = match board.get(x, y) {
+                        true => '█',
+                        false => ' ',
+                    };
+                    let pixel = Pixel::new(ch, background, CELL_COLOR);
+                    write_pixels(&mut frame, Usize2d::new(x_offset + x, y + 1), &[pixel]);
+                }
+            }
+        }
+        for y in 0..y_len {
+            let divider = Pixel::new('│', TerminalColors::Default, DIVIDER_COLOR);
+            write_pixels(&mut frame, Usize2d::new(x_len, y + 1), &[divider]);
+        }
+        let caption = text(self.caption(), CAPTION_COLOR);
+        let left = self.size.x.saturating_sub(caption.len()) / 2;
+        write_pixels(
+            &mut frame,
+            Usize2d::new(left, self.size.y.saturating_sub(1)),
+            &caption,
+        );
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        console::input_record::KeyEvent,
+        conway::{
+            clipboard::Clipboard, rule::Rule, settings::Boundary, shared_clipboard::SharedClipboard,
+        },
+        rendering::colors::TerminalColors,
+        scene::scene::{Scene, SceneTransition},
+        shared::usize2d::Usize2d,
+    };
+
+    use super::{RaceMode, Racer};
+
+    #[test]
+    fn differences_are_highlighted() {
+        let wrap = Racer::new(Rule::conway(), Boundary::Wrap);
+        let test_cases = [
+            ("same racers", wrap, false),
+            (
+                "boundaries",
+                Racer::new(Rule::conway(), Boundary::Dead),
+                true,
+            ),
+            (
+                "rules",
+                Racer::new(Rule::new(&[3, 6], &[2, 3]), Boundary::Wrap),
+                true,
+            ),
+        ];
+        for (test_case, right, is_different) in test_cases {
+            let mut mode = RaceMode::new(Usize2d::new(41, 12), 7, 1, [wrap, right]);
+            assert_eq!(mode.difference(), 0, "{}: the same seed", test_case);
+            for _ in 0..5 {
+                mode.tick();
+            }
+            assert_eq!(mode.rounds(), 5, "{}", test_case);
+            assert_eq!(mode.difference() > 0, is_different, "{}", test_case);
+
+            let frame = mode.frame();
+            let highlighted = frame[1..11]
+                .iter()
+                .flatten()
+                .filter(|pixel| pixel.background_color() == TerminalColors::HotPink)
+                .count();
+            assert_eq!(
+                highlighted,
+                2 * mode.difference(),
+                "{}: on both boards",
+                test_case
+            );
+            let alive = |columns: std::ops::Range<usize>| {
+                frame[1..11]
+                    .iter()
+                    .flat_map(|row| &row[columns.clone()])
+                    .filter(|pixel| pixel.char() == '█')
+                    .count() as u64
+            };
+            let [left, right] = mode.boards();
+            assert_eq!(alive(0..20), left.population(), "{}", test_case);
+            assert_eq!(alive(21..41), right.population(), "{}", test_case);
+            assert_eq!(frame[1][20].char(), '│', "{}", test_case);
+
+            let caption: String = frame[11].iter().map(|pixel| pixel.char()).collect();
+            let expected = format!("{} cells differ - round 5", mode.difference());
+            assert!(caption.contains(&expected), "{}: {:?}", test_case, caption);
+        }
+    }
+
+    #[test]
+    fn keys() {
+        let racers = [
+            Racer::new(Rule::conway(), Boundary::Wrap),
+            Racer::new(Rule::conway(), Boundary::Dead),
+        ];
+        let mut mode = RaceMode::new(Usize2d::new(41, 12), 7, 1, racers);
+        for _ in 0..5 {
+            mode.tick();
+        }
+        let test_cases = [
+            ("release", false, ' ', SceneTransition::Stay),
+            ("pause", true, ' ', SceneTransition::Stay),
+            ("hide the highlight", true, 'h', SceneTransition::Stay),
+            ("leave", true, 'q', SceneTransition::Leave),
+        ];
+        for (test_case, is_down, key, expected) in test_cases {
+            let event = KeyEvent {
+                is_down,
+                ..KeyEvent::key_down(key)
+            };
+            assert_eq!(mode.handle_key(event), expected, "{}", test_case);
+        }
+        assert!(!mode.tick(), "The boards are paused");
+        assert!(
+            mode.frame()
+                .iter()
+                .flatten()
+                .all(|pixel| pixel.background_color() != TerminalColors::HotPink),
+            "The highlight is hidden"
+        );
+        mode.handle_key(KeyEvent::key_down('r'));
+        assert_eq!(mode.rounds(), 0);
+        assert_eq!(mode.difference(), 0);
+
+        let clipboard = SharedClipboard::default();
+        mode.set_clipboard(clipboard.clone());
+        let random = mode.boards().clone();
+        mode.handle_key(KeyEvent::key_down('p'));
+        assert_eq!(mode.boards(), &random, "Nothing to paste");
+        let glider = Clipboard::from_text(".O\n..O\nOOO").unwrap();
+        clipboard.set(glider, &Rule::conway()).unwrap();
+        mode.handle_key(KeyEvent::key_down('p'));
+        assert_eq!(mode.rounds(), 0);
+        for board in mode.boards() {
+            assert_eq!(
+                board.population(),
+                5,
+                "The pattern is pasted on both boards"
+            );
+            assert!(board.get(10, 5), "In the middle of the board");
+        }
+    }
+}
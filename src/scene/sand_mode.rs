@@ -0,0 +1,281 @@
+use crate::{
+    console::input_record::{KeyEvent, MouseEvent},
+    conway::{
+        automaton::Automaton,
+        sand::{Material, Sandbox},
+    },
+    rendering::colors::TerminalColors,
+    shared::{
+        frame::{Frame, Pixel},
+        usize2d::{Coord, Usize2d},
+    },
+};
+
+use super::scene::{draw_cells, is_leave_key, write_pixels, Scene, SceneTransition};
+
+/// The color of the text below the sandbox
+const CAPTION_COLOR: TerminalColors = TerminalColors::White;
+
+/// A falling sand toy, where sand, water and walls are painted with the keyboard or the mouse
+///
+/// i, j, k and l move the cursor and enter paints the material of the brush under it. The keys
+/// 1 to 4 pick sand, water, wall or empty for the brush. Holding the left mouse button paints
+/// along the mouse. Space pauses, c empties the sandbox and q or escape leaves the mode
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{console::input_record::MouseEvent, scene::sand_mode::SandMode, shared::usize2d::{Coord, Usize2d}};
+/// let mut sand = SandMode::new(Usize2d::new(80, 24), 2);
+/// sand.handle_mouse(MouseEvent::press(Coord::new(40, 0)));
+/// ```
+pub struct SandMode {
+    size: Usize2d,
+    sandbox: Sandbox,
+    cursor: Coord,
+    brush: Material,
+    /// The cell the mouse last painted while the left button is held down
+    last_painted: Option<Coord>,
+    is_paused: bool,
+    ticks_per_generation: usize,
+    tick_count: usize,
+}
+impl SandMode {
+    /// Create the mode with an empty sandbox that fills the terminal above the caption
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the terminal
+    /// * `ticks_per_generation` - the number of ticks before the grains move
+    pub fn new(size: Usize2d, ticks_per_generation: usize) -> Self {
+        assert!(
+            ticks_per_generation > 0,
+            "The sandbox needs at least one tick per generation"
+        );
+        let sandbox = Sandbox::new(size.x.max(1), size.y.saturating_sub(1).max(1));
+        SandMode {
+            size,
+            cursor: Coord::new(sandbox.size().x / 2, 0),
+            sandbox,
+            brush: Material::Sand,
+            last_painted: None,
+            is_paused: false,
+            ticks_per_generation,
+            tick_count: 0,
+        }
+    }
+    /// Get the sandbox that is shown
+    pub fn sandbox(&self) -> &Sandbox {
+        &self.sandbox
+    }
+    /// Paint with the mouse, a drag paints every cell on the line from the cell before so that
+    /// fast movements leave no gaps
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - the mouse event, with its position in the frame
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        if !event.is_left_down() {
+            self.last_painted = None;
+            return;
+        }
+        let from = match (event.is_move(), self.last_painted) {
+            (true, Some(last)) => last,
+            _ => event.pos,
+        };
+        let steps = from
+            .x
+            .abs_diff(event.pos.x)
+            .max(from.y.abs_diff(event.pos.y));
+        for step in 0..=steps {
+            let along = |from: usize, to: usize| match steps {
+                0 => to,
+                _ => (from as f32 + (to as f32 - from as f32) * step as f32 / steps as f32).round()
+                    as usize,
+            };
+            let (x, y) = (along(from.x, event.pos.x), along(from.y, event.pos.y));
+            self.sandbox.paint(x, y, self.brush);
+        }
+        self.last_painted = Some(event.pos);
+    }
+    /// Move the cursor, keeping it inside the sandbox
+    fn move_cursor(&mut self, dx: isize, dy: isize) {
+        let size = self.sandbox.size();
+        let step =
+            |pos: usize, delta: isize, len: usize| pos.saturating_add_signed(delta).min(len - 1);
+        self.cursor = Coord::new(
+            step(self.cursor.x, dx, size.x),
+            step(self.cursor.y, dy, size.y),
+        );
+    }
+    /// The line at the bottom of the screen
+    fn caption(&self) -> String {
+        let status = match self.is_paused {
+            true => "paused",
+            false => "running",
+        };
+        format!(
+            "Falling sand - brush {} - {} - ijkl enter 1-4 space c q",
+            self.brush, status
+        )
+    }
+}
+impl Scene for SandMode {
+    fn tick(&mut self) -> bool {
+        if self.is_paused {
+            return false;
+        }
+        self.tick_count += 1;
+        if self.tick_count < self.ticks_per_generation {
+            return false;
+        }
+        self.tick_count = 0;
+        self.sandbox.next();
+        true
+    }
+    fn handle_key(&mut self, event: KeyEvent) -> SceneTransition {
+        if !event.is_down {
+            return SceneTransition::Stay;
+        }
+        match event.command {
+            key if is_leave_key(key) => return SceneTransition::Leave,
+            ' ' => self.is_paused = !self.is_paused,
+            'i' => self.move_cursor(0, -1),
+            'j' => self.move_cursor(-1, 0),
+            'k' => self.move_cursor(0, 1),
+            'l' => self.move_cursor(1, 0),
+            '\r' | '\n' => self.sandbox.paint(self.cursor.x, self.cursor.y, self.brush),
+            'c' | 'C' => self.sandbox.clear(),
+            key => {
+                let picked = key
+                    .to_digit(10)
+                    .and_then(|digit| Material::ALL.get((digit as usize).checked_sub(1)?));
+                if let Some(material) = picked {
+                    self.brush = *material;
+                }
+            }
+        }
+        SceneTransition::Stay
+    }
+    fn frame(&self) -> Frame {
+        let mut frame = vec![vec![Pixel::default(); self.size.x]; self.size.y];
+        draw_cells(&mut frame, self.sandbox.cells(), |material| {
+            material
+                .color()
+                .map(|color| Pixel::new('█', TerminalColors::Default, color))
+        });
+        let cursor_color = self.brush.color().unwrap_or(CAPTION_COLOR);
+        write_pixels(
+            &mut frame,
+            self.cursor,
+            &[Pixel::new('+', TerminalColors::Default, cursor_color)],
+        );
+        let caption: Vec<Pixel> = self
+            .caption()
+            .chars()
+            .map(|ch| Pixel::new(ch, TerminalColors::Default, CAPTION_COLOR))
+            .collect();
+        let left = self.size.x.saturating_sub(caption.len()) / 2;
+        write_pixels(
+            &mut frame,
+            Usize2d::new(left, self.size.y.saturating_sub(1)),
+            &caption,
+        );
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        console::input_record::{KeyEvent, MouseEvent},
+        conway::{automaton::Automaton, sand::Material},
+        rendering::colors::TerminalColors,
+        scene::scene::{Scene, SceneTransition},
+        shared::usize2d::{Coord, Usize2d},
+    };
+
+    use super::SandMode;
+
+    #[test]
+    fn keyboard_painting() {
+        let mut mode = SandMode::new(Usize2d::new(60, 6), 1);
+        mode.handle_key(KeyEvent::key_down(' '));
+        let test_cases = [
+            ("sand", vec!['\r'], Material::Sand, Coord::new(30, 0)),
+            (
+                "moved",
+                vec!['k', 'l', '\r'],
+                Material::Sand,
+                Coord::new(31, 1),
+            ),
+            (
+                "water",
+                vec!['2', 'j', 'j', '\r'],
+                Material::Water,
+                Coord::new(29, 1),
+            ),
+            (
+                "wall",
+                vec!['3', 'i', '\r'],
+                Material::Wall,
+                Coord::new(29, 0),
+            ),
+            (
+                "stays inside",
+                vec!['i', '\r'],
+                Material::Wall,
+                Coord::new(29, 0),
+            ),
+        ];
+        for (test_case, keys, material, cell) in test_cases {
+            for key in keys {
+                let transition = mode.handle_key(KeyEvent::key_down(key));
+                assert_eq!(transition, SceneTransition::Stay, "{}", test_case);
+            }
+            assert_eq!(
+                mode.sandbox().cells()[cell.y][cell.x],
+                material,
+                "{}",
+                test_case
+            );
+        }
+        assert!(!mode.tick(), "The sandbox is paused");
+        mode.handle_key(KeyEvent::key_down('c'));
+        assert_eq!(mode.sandbox().count(Material::Empty), 60 * 5);
+        assert_eq!(
+            mode.handle_key(KeyEvent::key_down('q')),
+            SceneTransition::Leave
+        );
+    }
+
+    #[test]
+    fn mouse_painting() {
+        let mut mode = SandMode::new(Usize2d::new(60, 6), 1);
+        mode.handle_key(KeyEvent::key_down('3'));
+        mode.handle_mouse(MouseEvent::press(Coord::new(2, 4)));
+        mode.handle_mouse(MouseEvent::drag(Coord::new(8, 4)));
+        mode.handle_mouse(MouseEvent::release(Coord::new(8, 4)));
+        mode.handle_mouse(MouseEvent::drag(Coord::new(20, 4)));
+        assert_eq!(
+            mode.sandbox().count(Material::Wall),
+            8,
+            "A line from 2 to 8 and a new line after the release"
+        );
+        mode.handle_key(KeyEvent::key_down('1'));
+        mode.handle_mouse(MouseEvent::press(Coord::new(5, 0)));
+        for _ in 0..5 {
+            mode.tick();
+        }
+        assert_eq!(
+            mode.sandbox().cells()[3][5],
+            Material::Sand,
+            "Sand lands on the wall"
+        );
+        let frame = mode.frame();
+        assert_eq!(frame[3][5].foreground_color(), TerminalColors::Yellow);
+        assert_eq!(frame[4][5].foreground_color(), TerminalColors::Grey);
+        let caption: String = frame[5].iter().map(|pixel| pixel.char()).collect();
+        assert!(caption.contains("brush sand - running"), "{:?}", caption);
+    }
+}
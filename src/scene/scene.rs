@@ -0,0 +1,279 @@
+use std::{thread, time::Duration};
+
+use crate::{
+    console::{
+        input_record::{KeyEvent, QUIT_KEY},
+        input_source::InputSource,
+    },
+    handler::{handle::Handle, handle_error::HandleError},
+    shared::{
+        frame::{Frame, Pixel},
+        usize2d::{Coord, Usize2d},
+    },
+};
+
+/// The time between two ticks of a scene that is shown with `run_scene`
+pub const SCENE_TICK: Duration = Duration::from_millis(50);
+/// The most key events a scene handles in one tick, the rest wait for the next one
+const MAX_EVENTS_PER_TICK: usize = 64;
+
+/// What a scene wants to happen after it handled a key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SceneTransition {
+    /// Keep showing the scene
+    Stay,
+    /// Close the scene and continue with the next one
+    Leave,
+}
+
+/// A screen of the program that owns the whole terminal while it is shown, like the title
+/// screen before a game
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{console::{input_record::QUIT_KEY, input_source::{InputSource, ScriptedInput}}, handler::handle_error::HandleError, scene::{scene::{write_frame, Scene, SceneTransition}, title_screen::TitleScreen}, shared::usize2d::Usize2d, MemoryHandle};
+/// # fn main() -> Result<(), HandleError> {
+/// # let mut input = ScriptedInput::from_keys(&QUIT_KEY.to_string());
+/// # let mut scene = TitleScreen::new(Usize2d::new(80, 24), 42, 10).unwrap();
+/// # let mut handle = MemoryHandle::new();
+/// loop {
+///     if let Some(event) = input.poll() {
+///         if scene.handle_key(event) == SceneTransition::Leave {
+///             break;
+///         }
+///     }
+///     if scene.tick() {
+///         write_frame(&scene.frame(), &mut handle)?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait Scene {
+    /// Advance the scene by one iteration of the loop
+    ///
+    /// # Returns
+    ///
+    /// `true` if the scene changed and should be redrawn
+    fn tick(&mut self) -> bool;
+    /// React to a key
+    fn handle_key(&mut self, event: KeyEvent) -> SceneTransition;
+    /// Draw the scene with its layers combined, the first row is the top of the terminal
+    fn frame(&self) -> Frame;
+    /// Check if the scene is left without a key, like the title screen that starts the demo
+    /// once nobody touched it for a while. Asked after every tick
+    fn transition(&mut self) -> SceneTransition {
+        SceneTransition::Stay
+    }
+}
+
+/// Check if a key leaves a scene: `q`, escape or the quit chord
+pub fn is_leave_key(key: char) -> bool {
+    matches!(key, 'q' | 'Q' | '\x1b' | QUIT_KEY)
+}
+
+/// Copy pixels into a row of a frame, clipping the ones that do not fit
+pub fn write_pixels(frame: &mut Frame, origin: Usize2d, pixels: &[Pixel]) {
+    let Some(row) = frame.get_mut(origin.y) else {
+        return;
+    };
+    for (column, pixel) in pixels.iter().enumerate() {
+        if let Some(target) = row.get_mut(origin.x + column) {
+            *target = pixel.clone();
+        }
+    }
+}
+
+/// Draw a grid of cells with a character per cell, the top left cell at the top left of the
+/// frame
+///
+/// # Arguments
+///
+/// * `frame` - the frame to draw on, the cells that do not fit are left out
+/// * `cells` - the grid, indexed as `cells[y][x]`
+/// * `pixel` - the pixel of a cell, `None` to leave the frame as it is
+pub fn draw_cells<T>(frame: &mut Frame, cells: &[Vec<T>], pixel: impl Fn(&T) -> Option<Pixel>) {
+    for (y, row) in cells.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            if let Some(pixel) = pixel(cell) {
+                write_pixels(frame, Usize2d::new(x, y), &[pixel]);
+            }
+        }
+    }
+}
+
+/// Write a frame to a handle with its top left corner at the top left of the terminal
+///
+/// # Returns
+///
+/// A result indicating if the write succeeded
+pub fn write_frame(frame: &Frame, handle: &mut dyn Handle) -> Result<(), HandleError> {
+    write_frame_at(frame, Coord::new(1, 1), handle)
+}
+
+/// Write a frame to a handle with its top left corner at a location, like the top left of a
+/// panel. Neighboring pixels with the same colors are written together
+///
+/// # Returns
+///
+/// A result indicating if the write succeeded
+pub fn write_frame_at(
+    frame: &Frame,
+    origin: Coord,
+    handle: &mut dyn Handle,
+) -> Result<(), HandleError> {
+    for (row, pixels) in frame.iter().enumerate() {
+        handle.set_cursor_location(origin + Coord::new(0, row))?;
+        let mut column = 0;
+        while column < pixels.len() {
+            let first = &pixels[column];
+            let run: String = pixels[column..]
+                .iter()
+                .take_while(|pixel| {
+                    pixel.background_color() == first.background_color()
+                        && pixel.foreground_color() == first.foreground_color()
+                })
+                .map(|pixel| pixel.char())
+                .collect();
+            handle.set_background_color(first.background_color())?;
+            handle.set_foreground_color(first.foreground_color())?;
+            handle
+                .write_all(run.as_bytes())
+                .map_err(|_| HandleError::WriteFailed)?;
+            column += run.chars().count();
+        }
+    }
+    handle.flush().map_err(|_| HandleError::WriteFailed)
+}
+
+/// Show a scene until it is left. The keys of the input are handed to the scene, which is
+/// drawn again after a key or a tick that changed it
+///
+/// # Arguments
+///
+/// * `scene` - the scene to show, it is drawn once before the first tick
+/// * `input` - where the keys come from
+/// * `handle` - the terminal the scene is drawn on
+/// * `tick` - the time between two ticks, e.g. `SCENE_TICK`
+///
+/// # Returns
+///
+/// A result indicating if the scene could be drawn
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use tic_tac_toe2::{console::input_source::ScriptedInput, handler::handle_error::HandleError, scene::{scene::run_scene, title_screen::{TitleMenuItem, TitleScreen}}, shared::usize2d::Usize2d, MemoryHandle};
+/// # fn main() -> Result<(), HandleError> {
+/// # let mut handle = MemoryHandle::new();
+/// let mut title = TitleScreen::new(Usize2d::new(80, 24), 42, 10).unwrap();
+/// run_scene(&mut title, &mut ScriptedInput::from_keys("s\r"), &mut handle, Duration::ZERO)?;
+/// assert_eq!(title.choice(), Some(TitleMenuItem::Demo));
+/// # Ok(())
+/// # }
+/// ```
+pub fn run_scene(
+    scene: &mut dyn Scene,
+    input: &mut dyn InputSource,
+    handle: &mut dyn Handle,
+    tick: Duration,
+) -> Result<(), HandleError> {
+    write_frame(&scene.frame(), handle)?;
+    loop {
+        let events = input.drain(MAX_EVENTS_PER_TICK);
+        let has_keys = !events.is_empty();
+        for event in events {
+            if scene.handle_key(event) == SceneTransition::Leave {
+                return Ok(());
+            }
+        }
+        if scene.tick() || has_keys {
+            write_frame(&scene.frame(), handle)?;
+        }
+        if scene.transition() == SceneTransition::Leave {
+            return Ok(());
+        }
+        thread::sleep(tick);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use crate::{
+        console::input_source::ScriptedInput,
+        handler::{memory_handle::MemoryHandle, shared_handle::SharedHandle},
+        scene::title_screen::{TitleMenuItem, TitleScreen},
+        shared::usize2d::Usize2d,
+    };
+
+    use super::run_scene;
+
+    #[test]
+    fn run() {
+        let test_cases = [
+            ("play", "\r", TitleMenuItem::Play),
+            ("demo", "s\r", TitleMenuItem::Demo),
+            (
+                "keys after the choice are left",
+                "w\rs",
+                TitleMenuItem::Quit,
+            ),
+            ("quit chord", "x\x11", TitleMenuItem::Quit),
+        ];
+        for (test_case, keys, expected) in test_cases {
+            let mut title = TitleScreen::new(Usize2d::new(40, 10), 3, 1).unwrap();
+            let mut input = ScriptedInput::from_keys(keys);
+            let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+            let mut handle = SharedHandle::init(buffer.clone());
+            assert!(
+                run_scene(&mut title, &mut input, &mut handle, Duration::ZERO).is_ok(),
+                "{}",
+                test_case
+            );
+            assert_eq!(title.choice(), Some(expected), "{}", test_case);
+            let content = buffer.lock().unwrap().get_buffer_content();
+            assert!(
+                String::from_utf8_lossy(&content).contains("Play"),
+                "{}: the scene is drawn",
+                test_case
+            );
+        }
+        let mut input = ScriptedInput::from_keys("w\rs");
+        let mut title = TitleScreen::new(Usize2d::new(40, 10), 3, 1).unwrap();
+        run_scene(
+            &mut title,
+            &mut input,
+            &mut MemoryHandle::new(),
+            Duration::ZERO,
+        )
+        .unwrap();
+        assert_eq!(
+            input.remaining(),
+            1,
+            "The keys after leaving wait for the next scene"
+        );
+
+        let mut title = TitleScreen::new(Usize2d::new(40, 10), 3, 1).unwrap();
+        title.set_idle_timeout(Duration::ZERO);
+        let mut input = ScriptedInput::from_keys("");
+        run_scene(
+            &mut title,
+            &mut input,
+            &mut MemoryHandle::new(),
+            Duration::ZERO,
+        )
+        .unwrap();
+        assert_eq!(
+            title.choice(),
+            Some(TitleMenuItem::Demo),
+            "An idle title screen leaves for the demo"
+        );
+    }
+}
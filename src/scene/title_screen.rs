@@ -0,0 +1,376 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    console::input_record::{KeyEvent, QUIT_KEY},
+    panel::background::{BackgroundProvider, ConwayBackground},
+    rendering::{
+        colors::TerminalColors,
+        fonts::{block_font::BlockFont, small_font::SMALL_FONT, standard_font::STANDARD_FONT},
+        hud::{Menu, MenuAction, Widget},
+        message_helper::{MessageHelper, MessageHelperErr},
+        message_style::MessageStyle,
+    },
+    shared::{
+        frame::{Frame, Pixel},
+        usize2d::Usize2d,
+    },
+    utils::clock::{Clock, SystemClock},
+};
+
+use super::scene::{Scene, SceneTransition};
+
+/// The name that is written in block letters
+const TITLE: &str = "tic tac toe";
+/// The color of the block letters
+const TITLE_COLOR: TerminalColors = TerminalColors::White;
+/// The number of empty rows between the title and the menu
+const MENU_GAP: usize = 2;
+/// The time without input before the attract mode starts
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The choices of the title menu
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TitleMenuItem {
+    Play,
+    /// Watch the attract mode
+    Demo,
+    Quit,
+}
+impl Display for TitleMenuItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TitleMenuItem::Play => write!(f, "Play"),
+            TitleMenuItem::Demo => write!(f, "Demo"),
+            TitleMenuItem::Quit => write!(f, "Quit"),
+        }
+    }
+}
+
+/// The items of the title menu in the order they are listed
+pub const TITLE_MENU_ITEMS: [TitleMenuItem; 3] = [
+    TitleMenuItem::Play,
+    TitleMenuItem::Demo,
+    TitleMenuItem::Quit,
+];
+
+/// The first scene of the program, the name of the crate over a Game of Life that runs slowly
+/// behind it
+///
+/// The background is a `ConwayBackground` with a cell per character. The spaces of the block
+/// letters leave the background visible. The title menu below the letters is left once an item
+/// is chosen, see `choice`
+///
+/// The title screen is idle once no key arrived for the idle timeout. It is then left with the
+/// demo as its choice, which starts the `AttractMode`
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{rendering::message_helper::MessageHelperErr, scene::title_screen::TitleScreen, shared::usize2d::Usize2d};
+/// # fn main() -> Result<(), MessageHelperErr> {
+/// # let seed = 42;
+/// let mut title = TitleScreen::new(Usize2d::new(80, 24), seed, 10)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TitleScreen {
+    size: Usize2d,
+    background: ConwayBackground,
+    title_rows: Vec<Vec<char>>,
+    menu: Menu<TitleMenuItem>,
+    choice: Option<TitleMenuItem>,
+    clock: Box<dyn Clock>,
+    idle_timeout: Duration,
+    last_input: Instant,
+}
+impl TitleScreen {
+    /// Create the title screen
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the terminal
+    /// * `seed` - the seed of the first background
+    /// * `ticks_per_generation` - the number of ticks before the background advances a round
+    ///
+    /// # Returns
+    ///
+    /// The title screen, in the small font if the title does not fit the terminal in the
+    /// standard font
+    pub fn new(
+        size: Usize2d,
+        seed: u64,
+        ticks_per_generation: usize,
+    ) -> Result<Self, MessageHelperErr> {
+        let font: &'static dyn BlockFont =
+            match TITLE.chars().count() * STANDARD_FONT.width() <= size.x {
+                true => &STANDARD_FONT,
+                false => &SMALL_FONT,
+            };
+        let style = MessageStyle::builder().font(font).build();
+        let title_rows = MessageHelper::render_rows(TITLE, &style)?
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        Ok(TitleScreen {
+            size,
+            background: ConwayBackground::new(size, seed, ticks_per_generation),
+            title_rows,
+            menu: Menu::new(&TITLE_MENU_ITEMS),
+            choice: None,
+            clock: Box::new(SystemClock),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            last_input: Instant::now(),
+        })
+    }
+    /// Get the item of the title menu that was chosen, `None` while the title screen is shown
+    pub fn choice(&self) -> Option<TitleMenuItem> {
+        self.choice
+    }
+    /// Get the round of the background, which starts at 0 for every new seed
+    pub fn rounds(&self) -> u64 {
+        self.background.rounds()
+    }
+    /// Get the seed of the background that is shown
+    pub fn seed(&self) -> u64 {
+        self.background.seed()
+    }
+    /// Set the clock the idle time is measured with, which also restarts the idle time
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+        self.wake();
+    }
+    /// Set the time without input before the title screen is idle
+    pub fn set_idle_timeout(&mut self, idle_timeout: Duration) {
+        self.idle_timeout = idle_timeout;
+    }
+    /// Check if no key arrived for the idle timeout
+    pub fn is_idle(&self) -> bool {
+        self.clock.now().duration_since(self.last_input) >= self.idle_timeout
+    }
+    /// Restart the idle time, e.g. when the attract mode returns to the title screen
+    pub fn wake(&mut self) {
+        self.last_input = self.clock.now();
+    }
+    /// The top row of the title, which is centered together with the menu
+    fn title_top(&self) -> usize {
+        let height = self.title_rows.len() + MENU_GAP + TITLE_MENU_ITEMS.len();
+        self.size.y.saturating_sub(height) / 2
+    }
+}
+impl Scene for TitleScreen {
+    fn tick(&mut self) -> bool {
+        self.background.tick()
+    }
+    fn handle_key(&mut self, event: KeyEvent) -> SceneTransition {
+        self.wake();
+        if !event.is_down {
+            return SceneTransition::Stay;
+        }
+        if event.command == QUIT_KEY {
+            self.choice = Some(TitleMenuItem::Quit);
+            return SceneTransition::Leave;
+        }
+        match self.menu.handle_key(event.command) {
+            MenuAction::Confirmed(item) => {
+                self.choice = Some(item);
+                SceneTransition::Leave
+            }
+            _ => SceneTransition::Stay,
+        }
+    }
+    fn frame(&self) -> Frame {
+        let mut frame = self.background.frame(self.size);
+
+        let top = self.title_top();
+        for (index, row) in self.title_rows.iter().enumerate() {
+            let left = self.size.x.saturating_sub(row.len()) / 2;
+            let Some(line) = frame.get_mut(top + index) else {
+                break;
+            };
+            for (column, ch) in row.iter().enumerate() {
+                if *ch == ' ' {
+                    continue;
+                }
+                if let Some(pixel) = line.get_mut(left + column) {
+                    *pixel = Pixel::new(*ch, TerminalColors::Default, TITLE_COLOR);
+                }
+            }
+        }
+
+        let rows = self.menu.rows();
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let left = self.size.x.saturating_sub(width) / 2;
+        let menu_top = top + self.title_rows.len() + MENU_GAP;
+        for (index, row) in rows.iter().enumerate() {
+            let Some(line) = frame.get_mut(menu_top + index) else {
+                break;
+            };
+            for (column, ch) in row.chars().enumerate() {
+                if let Some(pixel) = line.get_mut(left + column) {
+                    *pixel = Pixel::new(ch, TerminalColors::Default, TITLE_COLOR);
+                }
+            }
+        }
+        frame
+    }
+    fn transition(&mut self) -> SceneTransition {
+        if !self.is_idle() {
+            return SceneTransition::Stay;
+        }
+        self.choice = Some(TitleMenuItem::Demo);
+        SceneTransition::Leave
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use crate::{
+        console::input_record::KeyEvent,
+        handler::{memory_handle::MemoryHandle, shared_handle::SharedHandle},
+        rendering::colors::TerminalColors,
+        scene::scene::{write_frame, Scene, SceneTransition},
+        shared::{frame::Frame, usize2d::Usize2d},
+        utils::clock::VirtualClock,
+    };
+
+    use super::{TitleMenuItem, TitleScreen};
+
+    fn text(frame: &Frame) -> Vec<String> {
+        frame
+            .iter()
+            .map(|row| row.iter().map(|pixel| pixel.char()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn layers() {
+        let test_cases = [
+            ("standard font", Usize2d::new(80, 24), 5),
+            ("small font", Usize2d::new(50, 12), 3),
+        ];
+        for (test_case, size, font_height) in test_cases {
+            let title = TitleScreen::new(size, 7, 1).unwrap();
+            let frame = title.frame();
+            assert_eq!(frame.len(), size.y, "{}", test_case);
+            let rows = text(&frame);
+            let menu = rows
+                .iter()
+                .position(|row| row.contains("> Play"))
+                .expect("The menu should be shown");
+            let top = (size.y - font_height - 5) / 2;
+            assert_eq!(menu, top + font_height + 2, "{}", test_case);
+            assert!(rows[menu + 2].contains("  Quit"), "{}", test_case);
+
+            let letter = frame[top]
+                .iter()
+                .find(|pixel| pixel.foreground_color() == TerminalColors::White)
+                .expect("The title should be drawn");
+            assert_ne!(letter.char(), ' ', "{}", test_case);
+            assert!(
+                frame.iter().flatten().any(|pixel| {
+                    pixel.char() == '█' && pixel.foreground_color() == TerminalColors::DarkGrey
+                }),
+                "{}: the living cells should be drawn dimmed",
+                test_case
+            );
+        }
+    }
+
+    #[test]
+    fn background_runs_slowly() {
+        let mut title = TitleScreen::new(Usize2d::new(40, 10), 3, 3).unwrap();
+        let redraws: Vec<bool> = (0..6).map(|_| title.tick()).collect();
+        assert_eq!(redraws, [false, false, true, false, false, true]);
+        assert_eq!(title.rounds(), 2);
+
+        let mut title = TitleScreen::new(Usize2d::new(1, 1), 3, 1).unwrap();
+        title.tick();
+        assert_eq!(title.seed(), 4, "A stable background starts over");
+    }
+
+    #[test]
+    fn menu() {
+        let released = KeyEvent {
+            is_down: false,
+            ..KeyEvent::key_down('\r')
+        };
+        let test_cases = [
+            ("play", "\r", Some(TitleMenuItem::Play)),
+            ("demo", "s\n", Some(TitleMenuItem::Demo)),
+            ("quit", "w\r", Some(TitleMenuItem::Quit)),
+            ("quit chord", "\x11", Some(TitleMenuItem::Quit)),
+            ("other keys", "x ", None),
+        ];
+        for (test_case, keys, expected) in test_cases {
+            let mut title = TitleScreen::new(Usize2d::new(40, 10), 3, 1).unwrap();
+            assert_eq!(title.handle_key(released), SceneTransition::Stay);
+            let transitions: Vec<SceneTransition> = keys
+                .chars()
+                .map(|key| title.handle_key(KeyEvent::key_down(key)))
+                .collect();
+            let expected_transition = match expected {
+                Some(_) => SceneTransition::Leave,
+                None => SceneTransition::Stay,
+            };
+            assert_eq!(
+                transitions.last(),
+                Some(&expected_transition),
+                "{}",
+                test_case
+            );
+            assert_eq!(title.choice(), expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn idle() {
+        let clock = VirtualClock::new();
+        let mut title = TitleScreen::new(Usize2d::new(40, 10), 3, 1).unwrap();
+        title.set_clock(Box::new(clock.clone()));
+        title.set_idle_timeout(Duration::from_secs(10));
+        let released = KeyEvent {
+            is_down: false,
+            ..KeyEvent::key_down('x')
+        };
+        let test_cases = [
+            ("waiting", 9, None, false),
+            ("timed out", 1, None, true),
+            ("input", 5, Some(released), false),
+            ("after the input", 9, None, false),
+            ("timed out again", 1, None, true),
+        ];
+        for (test_case, seconds, event, expected) in test_cases {
+            clock.advance(Duration::from_secs(seconds));
+            if let Some(event) = event {
+                title.handle_key(event);
+            }
+            assert_eq!(title.is_idle(), expected, "{}", test_case);
+            let transition = match expected {
+                true => SceneTransition::Leave,
+                false => SceneTransition::Stay,
+            };
+            assert_eq!(title.transition(), transition, "{}", test_case);
+        }
+        assert_eq!(title.choice(), Some(TitleMenuItem::Demo));
+        title.wake();
+        assert!(!title.is_idle(), "Returning from the attract mode");
+    }
+
+    #[test]
+    fn write() {
+        let title = TitleScreen::new(Usize2d::new(40, 10), 3, 1).unwrap();
+        let buffer = Arc::new(Mutex::new(MemoryHandle::new()));
+        let mut handle = SharedHandle::init(buffer.clone());
+        assert!(write_frame(&title.frame(), &mut handle).is_ok());
+        let content = buffer.lock().unwrap().get_buffer_content();
+        assert!(String::from_utf8_lossy(&content).contains("> Play"));
+    }
+}
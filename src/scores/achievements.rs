@@ -0,0 +1,299 @@
+use std::fmt::Display;
+#[cfg(feature = "serde")]
+use std::{fs, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::conway::save::SaveError;
+use crate::tic_tac_toe::board::{Mark, Outcome};
+
+/// The number of generations that have to be run over all games for `Achievement::Marathon`
+pub const MARATHON_GENERATIONS: u64 = 10_000;
+
+/// Something that happened in one of the games that can unlock an achievement
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameEvent {
+    /// Generations of a Conway game were run
+    Generations(u64),
+    /// A pattern from the library was placed on a Conway board
+    PatternPlaced(&'static str),
+    /// A tic-tac-toe game ended, with the mark of the local player
+    TicTacToe(Outcome, Mark),
+}
+
+/// The achievements that can be unlocked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Achievement {
+    GliderGun,
+    Marathon,
+    Undefeated,
+}
+/// All achievements in the order they are listed
+pub const ACHIEVEMENTS: [Achievement; 3] = [
+    Achievement::GliderGun,
+    Achievement::Marathon,
+    Achievement::Undefeated,
+];
+impl Achievement {
+    /// The name the achievement is stored by in the achievements file
+    pub fn id(&self) -> &'static str {
+        match self {
+            Achievement::GliderGun => "glider_gun",
+            Achievement::Marathon => "marathon",
+            Achievement::Undefeated => "undefeated",
+        }
+    }
+    /// Explain how the achievement is unlocked
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::GliderGun => "Place a glider gun",
+            Achievement::Marathon => "Run 10,000 generations",
+            Achievement::Undefeated => "Win tic-tac-toe without ever losing",
+        }
+    }
+}
+impl Display for Achievement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Achievement::GliderGun => write!(f, "Glider factory"),
+            Achievement::Marathon => write!(f, "Marathon"),
+            Achievement::Undefeated => write!(f, "Undefeated"),
+        }
+    }
+}
+
+/// The unlocked achievements and the progress towards the others, kept over all games
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Achievements {
+    unlocked: Vec<Achievement>,
+    pub total_generations: u64,
+    pub tic_tac_toe_losses: u64,
+}
+impl Achievements {
+    /// Apply an event to the progress of the achievements
+    ///
+    /// # Returns
+    ///
+    /// The achievements that were unlocked by the event
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tic_tac_toe2::scores::achievements::{Achievements, GameEvent};
+    /// # let mut achievements = Achievements::default();
+    /// for achievement in achievements.record(GameEvent::Generations(1)) {
+    ///     println!("Unlocked {}", achievement);
+    /// }
+    /// ```
+    pub fn record(&mut self, event: GameEvent) -> Vec<Achievement> {
+        let mut reached = Vec::new();
+        match event {
+            GameEvent::Generations(generations) => {
+                self.total_generations += generations;
+                if self.total_generations >= MARATHON_GENERATIONS {
+                    reached.push(Achievement::Marathon);
+                }
+            }
+            GameEvent::PatternPlaced(name) => {
+                if name == "gosper-glider-gun" {
+                    reached.push(Achievement::GliderGun);
+                }
+            }
+            GameEvent::TicTacToe(Outcome::Won(mark), player) if mark != player => {
+                self.tic_tac_toe_losses += 1;
+            }
+            GameEvent::TicTacToe(Outcome::Won(_), _) => {
+                if self.tic_tac_toe_losses == 0 {
+                    reached.push(Achievement::Undefeated);
+                }
+            }
+            GameEvent::TicTacToe(_, _) => (),
+        }
+        reached.retain(|achievement| !self.is_unlocked(*achievement));
+        self.unlocked.extend(reached.iter().copied());
+        reached
+    }
+    /// Check if an achievement was unlocked
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+    /// Describe every achievement on a line of its own, with a mark if it is unlocked
+    pub fn lines(&self) -> Vec<String> {
+        ACHIEVEMENTS
+            .iter()
+            .map(|achievement| {
+                let mark = if self.is_unlocked(*achievement) {
+                    'x'
+                } else {
+                    ' '
+                };
+                format!(
+                    "[{}] {:<15} {}",
+                    mark,
+                    achievement.to_string(),
+                    achievement.description()
+                )
+            })
+            .collect()
+    }
+}
+
+/// The achievements as they are written to the achievements file
+#[cfg(feature = "serde")]
+#[derive(Debug, Serialize, Deserialize)]
+struct AchievementsFile {
+    unlocked: Vec<String>,
+    total_generations: u64,
+    tic_tac_toe_losses: u64,
+}
+
+#[cfg(feature = "serde")]
+impl Achievements {
+    /// Write the achievements to a file as JSON, through a temporary file like `SavedGame::save`
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        let file = AchievementsFile {
+            unlocked: self
+                .unlocked
+                .iter()
+                .map(|achievement| achievement.id().to_string())
+                .collect(),
+            total_generations: self.total_generations,
+            tic_tac_toe_losses: self.tic_tac_toe_losses,
+        };
+        let json = serde_json::to_string(&file).map_err(|_| SaveError::BadFormat)?;
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+    /// Read the achievements that were written with `save`. Without a file nothing was unlocked
+    /// yet
+    pub fn load(path: &Path) -> Result<Self, SaveError> {
+        if !path.exists() {
+            return Ok(Achievements::default());
+        }
+        let json = fs::read_to_string(path).map_err(|_| SaveError::ReadFailed)?;
+        let file: AchievementsFile =
+            serde_json::from_str(&json).map_err(|_| SaveError::BadFormat)?;
+        let unlocked = file
+            .unlocked
+            .iter()
+            .map(|id| {
+                ACHIEVEMENTS
+                    .into_iter()
+                    .find(|achievement| achievement.id() == id)
+                    .ok_or(SaveError::BadFormat)
+            })
+            .collect::<Result<Vec<Achievement>, SaveError>>()?;
+        Ok(Achievements {
+            unlocked,
+            total_generations: file.total_generations,
+            tic_tac_toe_losses: file.tic_tac_toe_losses,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tic_tac_toe::board::{Mark, Outcome};
+
+    use super::{Achievement, Achievements, GameEvent};
+
+    #[test]
+    fn record() {
+        let test_cases = [
+            (
+                "some generations",
+                vec![GameEvent::Generations(9_999)],
+                vec![],
+            ),
+            (
+                "generations add up",
+                vec![GameEvent::Generations(9_999), GameEvent::Generations(1)],
+                vec![Achievement::Marathon],
+            ),
+            (
+                "unlocked once",
+                vec![
+                    GameEvent::Generations(10_000),
+                    GameEvent::Generations(10_000),
+                ],
+                vec![Achievement::Marathon],
+            ),
+            (
+                "glider gun",
+                vec![
+                    GameEvent::PatternPlaced("glider"),
+                    GameEvent::PatternPlaced("gosper-glider-gun"),
+                ],
+                vec![Achievement::GliderGun],
+            ),
+            (
+                "win",
+                vec![
+                    GameEvent::TicTacToe(Outcome::Draw, Mark::X),
+                    GameEvent::TicTacToe(Outcome::Won(Mark::X), Mark::X),
+                ],
+                vec![Achievement::Undefeated],
+            ),
+            (
+                "win after a loss",
+                vec![
+                    GameEvent::TicTacToe(Outcome::Won(Mark::X), Mark::O),
+                    GameEvent::TicTacToe(Outcome::Won(Mark::O), Mark::O),
+                ],
+                vec![],
+            ),
+        ];
+        for (test_case, events, expected) in test_cases {
+            let mut achievements = Achievements::default();
+            let mut unlocked = Vec::new();
+            for event in events {
+                unlocked.extend(achievements.record(event));
+            }
+            assert_eq!(unlocked, expected, "{}", test_case);
+        }
+    }
+
+    #[test]
+    fn lines() {
+        let mut achievements = Achievements::default();
+        achievements.record(GameEvent::Generations(10_000));
+        assert_eq!(
+            achievements.lines(),
+            vec![
+                "[ ] Glider factory  Place a glider gun",
+                "[x] Marathon        Run 10,000 generations",
+                "[ ] Undefeated      Win tic-tac-toe without ever losing",
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn save_and_load() {
+        use std::{env, fs, process};
+
+        use crate::conway::save::SaveError;
+
+        let path = env::temp_dir().join(format!("achievements_test_{}.json", process::id()));
+        let _ = fs::remove_file(&path);
+        let mut achievements = Achievements::load(&path).expect("Nothing is unlocked yet");
+        achievements.record(GameEvent::PatternPlaced("gosper-glider-gun"));
+        achievements.record(GameEvent::Generations(12));
+        assert_eq!(achievements.save(&path), Ok(()));
+        let loaded = Achievements::load(&path);
+        fs::write(
+            &path,
+            r#"{"unlocked":["pacifist"],"total_generations":0,"tic_tac_toe_losses":0}"#,
+        )
+        .unwrap();
+        let unknown = Achievements::load(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded, Ok(achievements));
+        assert_eq!(unknown, Err(SaveError::BadFormat));
+    }
+}
@@ -0,0 +1,195 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conway::save::SaveError,
+    rendering::hud::{Column, Table, Widget},
+    tic_tac_toe::board::{Mark, Outcome},
+};
+
+/// The results and best scores of all games, kept in a JSON file between sessions
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreBoard {
+    pub tic_tac_toe_wins: u64,
+    pub tic_tac_toe_losses: u64,
+    pub tic_tac_toe_draws: u64,
+    pub snake_high_score: u64,
+    /// The most generations a Conway game ran before it was stable or stopped
+    pub longest_conway_run: u64,
+    /// The seed of the longest Conway game, so that it can be watched again
+    pub longest_conway_seed: u64,
+}
+impl ScoreBoard {
+    /// Count the result of a finished tic-tac-toe game
+    ///
+    /// # Arguments
+    ///
+    /// * `outcome` - the outcome of the game, games that are still in progress are ignored
+    /// * `player` - the mark of the local player
+    pub fn record_tic_tac_toe(&mut self, outcome: Outcome, player: Mark) {
+        match outcome {
+            Outcome::InProgress => (),
+            Outcome::Won(mark) if mark == player => self.tic_tac_toe_wins += 1,
+            Outcome::Won(_) => self.tic_tac_toe_losses += 1,
+            Outcome::Draw => self.tic_tac_toe_draws += 1,
+        }
+    }
+    /// Keep a snake score if it beats the high score
+    ///
+    /// # Returns
+    ///
+    /// `true` if the score is the new high score
+    pub fn record_snake(&mut self, score: u64) -> bool {
+        if score <= self.snake_high_score {
+            return false;
+        }
+        self.snake_high_score = score;
+        true
+    }
+    /// Keep a Conway game if it ran longer than the longest run
+    ///
+    /// # Returns
+    ///
+    /// `true` if the game is the new longest run
+    pub fn record_conway_run(&mut self, rounds: u64, seed: u64) -> bool {
+        if rounds <= self.longest_conway_run {
+            return false;
+        }
+        self.longest_conway_run = rounds;
+        self.longest_conway_seed = seed;
+        true
+    }
+    /// Describe the scores as a table with a line per score under a line of headers
+    pub fn table(&self) -> Vec<String> {
+        let rows = || {
+            vec![
+                vec![
+                    "Tic-tac-toe W/L/D".to_string(),
+                    format!(
+                        "{}/{}/{}",
+                        self.tic_tac_toe_wins, self.tic_tac_toe_losses, self.tic_tac_toe_draws
+                    ),
+                ],
+                vec![
+                    "Snake high score".to_string(),
+                    self.snake_high_score.to_string(),
+                ],
+                vec![
+                    "Longest Conway run".to_string(),
+                    format!(
+                        "{} (seed {})",
+                        self.longest_conway_run, self.longest_conway_seed
+                    ),
+                ],
+            ]
+        };
+        Table::new(
+            vec![Column::left("Game").width(20), Column::left("Score")],
+            rows,
+        )
+        .rows()
+    }
+    /// Write the scores to a file as JSON, through a temporary file like `SavedGame::save`
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        let json = serde_json::to_string(self).map_err(|_| SaveError::BadFormat)?;
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+    /// Read the scores that were written with `save`. Without a file nothing was scored yet
+    pub fn load(path: &Path) -> Result<Self, SaveError> {
+        if !path.exists() {
+            return Ok(ScoreBoard::default());
+        }
+        let json = fs::read_to_string(path).map_err(|_| SaveError::ReadFailed)?;
+        serde_json::from_str(&json).map_err(|_| SaveError::BadFormat)
+    }
+    /// Load the scores, apply a change and write them back
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::path::Path;
+    /// # use tic_tac_toe2::{conway::save::SaveError, scores::score_board::ScoreBoard};
+    /// # fn main() -> Result<(), SaveError> {
+    /// # let path = Path::new("scores.json");
+    /// ScoreBoard::update(path, |scores| scores.record_snake(12))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update<T>(
+        path: &Path,
+        change: impl FnOnce(&mut ScoreBoard) -> T,
+    ) -> Result<T, SaveError> {
+        let mut scores = ScoreBoard::load(path)?;
+        let result = change(&mut scores);
+        scores.save(path)?;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::{
+        conway::save::SaveError,
+        tic_tac_toe::board::{Mark, Outcome},
+    };
+
+    use super::ScoreBoard;
+
+    #[test]
+    fn record() {
+        let mut scores = ScoreBoard::default();
+        let outcomes = [
+            Outcome::Won(Mark::O),
+            Outcome::Won(Mark::X),
+            Outcome::Draw,
+            Outcome::InProgress,
+            Outcome::Won(Mark::O),
+        ];
+        for outcome in outcomes {
+            scores.record_tic_tac_toe(outcome, Mark::O);
+        }
+        let test_cases = [
+            ("first snake score", scores.record_snake(10), true),
+            ("lower snake score", scores.record_snake(4), false),
+            ("equal snake score", scores.record_snake(10), false),
+            ("first conway run", scores.record_conway_run(80, 3), true),
+            ("shorter conway run", scores.record_conway_run(20, 4), false),
+            ("longer conway run", scores.record_conway_run(120, 5), true),
+        ];
+        for (test_case, actual, expected) in test_cases {
+            assert_eq!(actual, expected, "{}", test_case);
+        }
+        assert_eq!(
+            scores.table(),
+            vec![
+                "Game                 Score",
+                "Tic-tac-toe W/L/D    2/1/1",
+                "Snake high score     10",
+                "Longest Conway run   120 (seed 5)",
+            ]
+        );
+    }
+
+    #[test]
+    fn update_file() {
+        let path = env::temp_dir().join(format!("scores_test_{}.json", process::id()));
+        let _ = fs::remove_file(&path);
+        let first = ScoreBoard::update(&path, |scores| scores.record_snake(7));
+        let second = ScoreBoard::update(&path, |scores| scores.record_snake(5));
+        let loaded = ScoreBoard::load(&path);
+        fs::write(&path, "not json").unwrap();
+        let bad = ScoreBoard::load(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(first, Ok(true));
+        assert_eq!(second, Ok(false), "The high score is kept in the file");
+        assert_eq!(loaded.map(|scores| scores.snake_high_score), Ok(7));
+        assert_eq!(bad, Err(SaveError::BadFormat));
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Mul},
+};
+
+use super::usize2d::Usize2d;
+
+/// A position or velocity that can move by less than a full cell per tick
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Float2d {
+    pub x: f32,
+    pub y: f32,
+}
+impl Float2d {
+    pub fn new(x: f32, y: f32) -> Self {
+        Float2d { x, y }
+    }
+    /// Get the cell that this position falls in
+    ///
+    /// # Returns
+    ///
+    /// `Some(Usize2d)` with the rounded down coordinate, or `None` if either axis is negative
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::shared::float2d::Float2d;
+    /// let coord = Float2d::new(2.7, 1.2).to_coord().unwrap();
+    /// assert!(coord.x == 2 && coord.y == 1);
+    /// assert!(Float2d::new(-0.5, 1.0).to_coord().is_none());
+    /// ```
+    pub fn to_coord(&self) -> Option<Usize2d> {
+        if self.x < 0.0 || self.y < 0.0 {
+            return None;
+        }
+        Some(Usize2d::new(
+            self.x.floor() as usize,
+            self.y.floor() as usize,
+        ))
+    }
+}
+impl From<Usize2d> for Float2d {
+    fn from(value: Usize2d) -> Self {
+        Float2d::new(value.x as f32, value.y as f32)
+    }
+}
+impl Display for Float2d {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "x: {:.2}, y: {:.2}", self.x, self.y)
+    }
+}
+impl Add for Float2d {
+    type Output = Float2d;
+    fn add(self, rhs: Self) -> Self::Output {
+        Float2d {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+impl Mul<f32> for Float2d {
+    type Output = Float2d;
+    fn mul(self, rhs: f32) -> Self::Output {
+        Float2d {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::shared::usize2d::Usize2d;
+
+    use super::Float2d;
+
+    #[test]
+    fn to_coord() {
+        let test_cases = vec![
+            (1, Float2d::new(0.0, 0.0), Some((0, 0))),
+            (2, Float2d::new(2.7, 1.2), Some((2, 1))),
+            (3, Float2d::new(-0.1, 1.0), None),
+            (4, Float2d::new(1.0, -3.0), None),
+        ];
+        for (test_case, position, expected) in test_cases {
+            let actual = position.to_coord().map(|coord| (coord.x, coord.y));
+            assert_eq!(
+                actual, expected,
+                "Test case {}: The cell for {} is wrong",
+                test_case, position
+            );
+        }
+    }
+    #[test]
+    fn arithmetic() {
+        let position = Float2d::from(Usize2d::new(1, 2));
+        let velocity = Float2d::new(0.5, -1.0);
+        assert_eq!(position + velocity * 2.0, Float2d::new(2.0, 0.0));
+    }
+}
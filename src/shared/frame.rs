@@ -1,6 +1,11 @@
+use std::sync::Arc;
+
 use crate::rendering::colors::TerminalColors;
 
 pub type Frame = Vec<Vec<Pixel>>;
+/// A frame that is passed around without copying its pixels. A holder that changes it gets its
+/// own copy through `Arc::make_mut`, only if the frame is still shared
+pub type SharedFrame = Arc<Frame>;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Pixel {
@@ -33,10 +38,15 @@ impl Pixel {
     /// # Examples
     ///
     /// ```
+    /// # use tic_tac_toe2::{Pixel, TerminalColors};
     /// let pixel = Pixel::new('A', TerminalColors::Red, TerminalColors::Blue);
     ///
     /// ```
-    fn new(char: char, background_color: TerminalColors, foreground_color: TerminalColors) -> Self {
+    pub fn new(
+        char: char,
+        background_color: TerminalColors,
+        foreground_color: TerminalColors,
+    ) -> Self {
         Pixel {
             char,
             background_color,
@@ -57,17 +67,30 @@ impl Pixel {
     /// # Examples
     ///
     /// ```
+    /// # use tic_tac_toe2::{Pixel, TerminalColors};
     /// let pixel = Pixel::new('A', TerminalColors::Red, TerminalColors::Blue);
     /// let new = pixel.next('B');
-    /// assert_eq!(pixel.background_color, new.background_color);
-    /// assert_eq!(pixel.foreground_color, new.foreground_color);
-    /// assert_ne!(pixel.char, new.char)
+    /// assert_eq!(pixel.background_color(), new.background_color());
+    /// assert_eq!(pixel.foreground_color(), new.foreground_color());
+    /// assert_ne!(pixel.char(), new.char())
     /// ```
     pub fn next(&self, char: char) -> Self {
         let mut next = self.clone();
         next.char = char;
         next
     }
+    /// Get the character of the pixel
+    pub fn char(&self) -> char {
+        self.char
+    }
+    /// Get the background color of the pixel
+    pub fn background_color(&self) -> TerminalColors {
+        self.background_color
+    }
+    /// Get the foreground color of the pixel
+    pub fn foreground_color(&self) -> TerminalColors {
+        self.foreground_color
+    }
 }
 
 #[cfg(test)]
@@ -114,4 +137,12 @@ mod tests {
         assert_eq!(next.background_color, TerminalColors::Red);
         assert_eq!(next.foreground_color, TerminalColors::White);
     }
+
+    #[test]
+    fn getters() {
+        let actual = Pixel::new('T', TerminalColors::Red, TerminalColors::White);
+        assert_eq!(actual.char(), 'T');
+        assert_eq!(actual.background_color(), TerminalColors::Red);
+        assert_eq!(actual.foreground_color(), TerminalColors::White);
+    }
 }
@@ -0,0 +1,122 @@
+use std::{
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    rendering::colors::TerminalColors,
+    shared::frame::{Frame, Pixel},
+};
+
+/// Get the characters of a frame, a line per row with the spaces at the end of the rows left out
+pub fn frame_text(frame: &Frame) -> String {
+    frame
+        .iter()
+        .map(|row| {
+            let line: String = row.iter().map(Pixel::char).collect();
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+/// Get the characters of a frame with their colors as ANSI escapes, so that a terminal shows the
+/// frame the way it was drawn, like with `cat`
+///
+/// The colors are reset at the end of every row, so that a row does not color the rest of the
+/// terminal
+pub fn frame_ansi(frame: &Frame) -> String {
+    let mut ansi = String::new();
+    for row in frame {
+        let mut colors = None;
+        for pixel in row {
+            let pixel_colors = (pixel.background_color(), pixel.foreground_color());
+            if colors != Some(pixel_colors) {
+                ansi.push_str(&color_escape(48, pixel_colors.0));
+                ansi.push_str(&color_escape(38, pixel_colors.1));
+                colors = Some(pixel_colors);
+            }
+            ansi.push(pixel.char());
+        }
+        ansi.push_str("\x1b[0m\n");
+    }
+    ansi
+}
+
+/// Get the escape that sets a color of the 256 color palette
+///
+/// # Arguments
+///
+/// * `layer` - 38 for the foreground or 48 for the background
+/// * `color` - the color, `TerminalColors::Default` sets the color of the terminal
+fn color_escape(layer: u8, color: TerminalColors) -> String {
+    let mut escape = String::new();
+    let _ = match color {
+        TerminalColors::Default => write!(escape, "\x1b[{}m", layer + 1),
+        color => write!(escape, "\x1b[{};5;{}m", layer, color as i32),
+    };
+    escape
+}
+
+/// Write a frame to a plain text file and to a file with ANSI colors, next to each other
+///
+/// # Arguments
+///
+/// * `frame` - the frame
+/// * `path` - the path of the files, its extension is replaced with `txt` and `ans`
+///
+/// # Returns
+///
+/// The paths of the text file and of the ANSI file
+pub fn write_frame_dump(frame: &Frame, path: &Path) -> io::Result<(PathBuf, PathBuf)> {
+    let text_path = path.with_extension("txt");
+    let ansi_path = path.with_extension("ans");
+    fs::write(&text_path, frame_text(frame))?;
+    fs::write(&ansi_path, frame_ansi(frame))?;
+    Ok((text_path, ansi_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::{
+        rendering::colors::TerminalColors as TC,
+        shared::frame::{Frame, Pixel},
+    };
+
+    use super::{frame_ansi, frame_text, write_frame_dump};
+
+    #[test]
+    fn dumps() {
+        let red = Pixel::new('#', TC::Red, TC::White);
+        let frame: Frame = vec![
+            vec![red.clone(), red.next('!'), Pixel::default()],
+            vec![Pixel::default(); 3],
+        ];
+        let test_cases = [
+            ("text", frame_text(&frame), "#!\n\n"),
+            (
+                "ansi",
+                frame_ansi(&frame),
+                "\x1b[48;5;160m\x1b[38;5;231m#!\x1b[49m\x1b[39m \x1b[0m\n\
+                 \x1b[49m\x1b[39m   \x1b[0m\n",
+            ),
+        ];
+        for (test_case, actual, expected) in test_cases {
+            assert_eq!(actual, expected, "{}", test_case);
+        }
+
+        let dir = env::temp_dir().join(format!("frame_dump_test_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let written = write_frame_dump(&frame, &dir.join("board.png"));
+        let files = [dir.join("board.txt"), dir.join("board.ans")].map(fs::read_to_string);
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(
+            written.ok(),
+            Some((dir.join("board.txt"), dir.join("board.ans")))
+        );
+        assert_eq!(files[0].as_deref().ok(), Some(frame_text(&frame).as_str()));
+        assert_eq!(files[1].as_deref().ok(), Some(frame_ansi(&frame).as_str()));
+    }
+}
@@ -11,6 +11,7 @@ use super::usize2d::{Coord, Usize2d};
 /// # Example
 ///
 /// ```
+/// # use tic_tac_toe2::shared::{square::Square, usize2d::Usize2d};
 /// let sqr = Square::new(Usize2d::new(1,1), Usize2d::new(3,4));
 /// ```
 /// O O O O O
@@ -29,9 +30,6 @@ use super::usize2d::{Coord, Usize2d};
 pub struct Square {
     top_left: Usize2d,
     bottom_right: Usize2d,
-
-    top_right: Usize2d,
-    bottom_left: Usize2d,
 }
 
 impl Square {
@@ -48,6 +46,7 @@ impl Square {
     /// # Example
     ///
     /// ```
+    /// # use tic_tac_toe2::shared::{square::Square, usize2d::Usize2d};
     /// let top_left= Usize2d::new(0, 0);
     /// let bottom_right= Usize2d::new(10, 69);
     ///
@@ -67,10 +66,8 @@ impl Square {
             bottom_right.y
         );
         Square {
-            top_left: top_left.clone(),
-            top_right: Usize2d::new(bottom_right.x, top_left.y),
-            bottom_right: bottom_right.clone(),
-            bottom_left: Usize2d::new(top_left.x, bottom_right.y),
+            top_left,
+            bottom_right,
         }
     }
 
@@ -86,6 +83,7 @@ impl Square {
     /// # Example
     ///
     /// ```
+    /// # use tic_tac_toe2::shared::{square::Square, usize2d::Usize2d};
     /// let top_left= Usize2d::new(0, 0);
     /// let bottom_right= Usize2d::new(10, 69);
     ///
@@ -112,6 +110,7 @@ impl Square {
     /// # Example
     ///
     /// ```
+    /// # use tic_tac_toe2::shared::{square::Square, usize2d::Usize2d};
     /// let top_left= Usize2d::new(0, 0);
     /// let bottom_right= Usize2d::new(10, 69);
     /// let square= Square::new(top_left, bottom_right);
@@ -120,7 +119,7 @@ impl Square {
     /// let bottom_right= Usize2d::new(10, 69);
     /// let square2= Square::new(top_left, bottom_right);
     ///
-    /// let overlaps= square.overlaps_with(square2);
+    /// let overlaps= square.overlaps_with(&square2);
     /// ```
     pub fn overlaps_with(&self, other: &Square) -> bool {
         !(other.bottom_right.x < self.top_left.x
@@ -129,6 +128,41 @@ impl Square {
             || other.top_left.y > self.bottom_right.y)
     }
 
+    /// Get the area shared by two squares
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the other square to intersect with
+    ///
+    /// # Returns
+    /// `Some(Square)` covering the overlap, or `None` if the squares do not overlap
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tic_tac_toe2::shared::{square::Square, usize2d::Usize2d};
+    /// let square = Square::new(Usize2d::new(0, 0), Usize2d::new(5, 5));
+    /// let square2 = Square::new(Usize2d::new(3, 4), Usize2d::new(10, 10));
+    ///
+    /// let overlap = square.intersection(&square2).unwrap();
+    /// assert!(overlap.width() == 3 && overlap.height() == 2);
+    /// ```
+    pub fn intersection(&self, other: &Square) -> Option<Square> {
+        if !self.overlaps_with(other) {
+            return None;
+        }
+        Some(Square::new(
+            Usize2d::new(
+                self.top_left.x.max(other.top_left.x),
+                self.top_left.y.max(other.top_left.y),
+            ),
+            Usize2d::new(
+                self.bottom_right.x.min(other.bottom_right.x),
+                self.bottom_right.y.min(other.bottom_right.y),
+            ),
+        ))
+    }
+
     /// Get the width of the square
     ///
     /// # Returns
@@ -137,6 +171,7 @@ impl Square {
     /// # Example
     ///
     /// ```
+    /// # use tic_tac_toe2::shared::{square::Square, usize2d::Usize2d};
     /// let top_left= Usize2d::new(0, 0);
     /// let bottom_right= Usize2d::new(10, 69);
     /// let square= Square::new(top_left, bottom_right);
@@ -155,6 +190,7 @@ impl Square {
     /// # Example
     ///
     /// ```
+    /// # use tic_tac_toe2::shared::{square::Square, usize2d::Usize2d};
     /// let top_left= Usize2d::new(0, 0);
     /// let bottom_right= Usize2d::new(10, 69);
     /// let square= Square::new(top_left, bottom_right);
@@ -173,6 +209,7 @@ impl Square {
     /// # Example
     ///
     /// ```
+    /// # use tic_tac_toe2::shared::{square::Square, usize2d::Usize2d};
     /// let top_left= Usize2d::new(0, 0);
     /// let bottom_right= Usize2d::new(10, 69);
     /// let square= Square::new(top_left, bottom_right);
@@ -200,6 +237,47 @@ pub mod test {
 
     use crate::shared::{square::Square, usize2d::Usize2d};
 
+    #[test]
+    fn intersection() {
+        let test_cases = vec![
+            (
+                1,
+                Square::new(Usize2d::new(0, 0), Usize2d::new(5, 5)),
+                Square::new(Usize2d::new(3, 4), Usize2d::new(10, 10)),
+                Some(((3, 4), (5, 5))),
+            ),
+            (
+                2,
+                Square::new(Usize2d::new(1, 1), Usize2d::new(5, 5)),
+                Square::new(Usize2d::new(0, 0), Usize2d::new(10, 10)),
+                Some(((1, 1), (5, 5))),
+            ),
+            (
+                3,
+                Square::new(Usize2d::new(0, 0), Usize2d::new(5, 5)),
+                Square::new(Usize2d::new(5, 0), Usize2d::new(6, 0)),
+                Some(((5, 0), (5, 0))),
+            ),
+            (
+                4,
+                Square::new(Usize2d::new(0, 0), Usize2d::new(5, 5)),
+                Square::new(Usize2d::new(9, 9), Usize2d::new(10, 10)),
+                None,
+            ),
+        ];
+        for (test_case, square, other, expected) in test_cases {
+            let actual = square.intersection(&other).map(|overlap| {
+                let (top_left, bottom_right) = overlap.get_boundary();
+                ((top_left.x, top_left.y), (bottom_right.x, bottom_right.y))
+            });
+            assert_eq!(
+                actual, expected,
+                "Test case {}: The intersection of {} and {} is wrong",
+                test_case, square, other
+            );
+        }
+    }
+
     #[test]
     fn overlap() {
         let test_cases = vec![
@@ -322,7 +400,7 @@ pub mod test {
         ];
 
         for (i, (coordinate, expected_result)) in test_cases.iter().enumerate() {
-            let result = square.clone().is_in_square(coordinate.clone());
+            let result = square.clone().is_in_square(*coordinate);
             assert_eq!(
                 result, *expected_result,
                 "Test case {}: Got: {:?}, Expected: {:?}, With: {} and {}",
@@ -363,7 +441,7 @@ pub mod test {
         let bottom_right = Usize2d::new(17, 21);
 
         let result = catch_unwind(|| {
-            let _ = Square::new(top_left.clone(), bottom_right.clone());
+            let _ = Square::new(top_left, bottom_right);
         });
 
         assert!(result.is_err(), "Expected panic, but no panic occurred");
@@ -386,7 +464,7 @@ pub mod test {
         let bottom_right = Usize2d::new(17, 21);
 
         let result = catch_unwind(|| {
-            let _ = Square::new(top_left.clone(), bottom_right.clone());
+            let _ = Square::new(top_left, bottom_right);
         });
 
         assert!(result.is_err(), "Expected panic, but no panic occurred");
@@ -0,0 +1,470 @@
+use crate::{
+    handler::{handle::Handle, memory_handle::MemoryHandle},
+    rendering::colors::TerminalColors,
+    shared::usize2d::Usize2d,
+};
+
+/// Every color a handle can set, to read the color codes of the escape sequences back
+const COLORS: [TerminalColors; 13] = [
+    TerminalColors::LightGreen,
+    TerminalColors::Red,
+    TerminalColors::White,
+    TerminalColors::Black,
+    TerminalColors::HotPink,
+    TerminalColors::Yellow,
+    TerminalColors::DarkBlue,
+    TerminalColors::Blue,
+    TerminalColors::Cyan,
+    TerminalColors::Orange,
+    TerminalColors::DarkGrey,
+    TerminalColors::Grey,
+    TerminalColors::Default,
+];
+
+/// A character on the screen with its colors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    pub character: char,
+    pub background: TerminalColors,
+    pub foreground: TerminalColors,
+}
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            character: ' ',
+            background: TerminalColors::Default,
+            foreground: TerminalColors::Default,
+        }
+    }
+}
+
+/// What a handle shows once it was flushed, read back from a `MemoryHandle` or from the escape
+/// sequences written to a terminal
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Screen {
+    rows: Vec<Vec<Cell>>,
+}
+impl Screen {
+    /// Read the flushed content of a `MemoryHandle`
+    pub fn from_memory_handle(handle: &MemoryHandle) -> Self {
+        let backgrounds = handle.get_background_colors();
+        let foregrounds = handle.get_foreground_colors();
+        let rows = handle
+            .buffer
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, byte)| Cell {
+                        character: *byte as char,
+                        background: backgrounds[y].get(x).copied().unwrap_or_default(),
+                        foreground: foregrounds[y].get(x).copied().unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .collect();
+        Screen { rows }
+    }
+    /// Play the output of a terminal handle back: text, cursor moves, 256 color sequences,
+    /// default colors, resets and clearing the screen. Other sequences, like the title, are skipped
+    ///
+    /// The cursor sequences are read in the coordinates of the handles, so `ESC[y;xH` moves to
+    /// `Usize2d::new(x, y)`
+    pub fn from_ansi(output: &[u8]) -> Self {
+        let text = String::from_utf8_lossy(output);
+        let mut screen = Screen::default();
+        let mut cursor = Usize2d::default();
+        let mut pen = Cell::default();
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' if chars.peek() == Some(&'[') => {
+                    chars.next();
+                    let mut parameters = String::new();
+                    let mut command = '\0';
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            command = c;
+                            break;
+                        }
+                        parameters.push(c);
+                    }
+                    let numbers: Vec<i64> = parameters
+                        .split(';')
+                        .map(|number| number.parse().unwrap_or(0))
+                        .collect();
+                    match (command, numbers.as_slice()) {
+                        ('H', [y, x]) => cursor = Usize2d::new(*x as usize, *y as usize),
+                        ('m', [38, 5, code]) => pen.foreground = color(*code),
+                        ('m', [48, 5, code]) => pen.background = color(*code),
+                        ('m', [0]) => {
+                            pen.foreground = TerminalColors::Default;
+                            pen.background = TerminalColors::Default;
+                        }
+                        ('m', [39]) => pen.foreground = TerminalColors::Default,
+                        ('m', [49]) => pen.background = TerminalColors::Default,
+                        ('J', [2]) => screen.rows.clear(),
+                        _ => (),
+                    }
+                }
+                // An operating system command, like the title, ends with a bell
+                '\x1b' if chars.peek() == Some(&']') => {
+                    for c in chars.by_ref() {
+                        if c == '\x07' {
+                            break;
+                        }
+                    }
+                }
+                '\x07' => (),
+                '\n' => cursor = Usize2d::new(0, cursor.y + 1),
+                c => {
+                    screen.set(
+                        cursor,
+                        Cell {
+                            character: c,
+                            ..pen
+                        },
+                    );
+                    cursor.x += 1;
+                }
+            }
+        }
+        screen
+    }
+    /// Get the cell at a location, a location nothing was written to is blank
+    pub fn cell(&self, x: usize, y: usize) -> Cell {
+        self.rows
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or_default()
+    }
+    /// Get the characters of a row from a column on, as many as `len`
+    pub fn text(&self, x: usize, y: usize, len: usize) -> String {
+        (x..x + len).map(|x| self.cell(x, y).character).collect()
+    }
+    fn set(&mut self, location: Usize2d, cell: Cell) {
+        if self.rows.len() <= location.y {
+            self.rows.resize(location.y + 1, Vec::new());
+        }
+        let row = &mut self.rows[location.y];
+        if row.len() <= location.x {
+            row.resize(location.x + 1, Cell::default());
+        }
+        row[location.x] = cell;
+    }
+}
+
+fn color(code: i64) -> TerminalColors {
+    COLORS
+        .into_iter()
+        .find(|color| *color as i64 == code)
+        .unwrap_or_default()
+}
+
+/// Reads back what a handle shows
+pub type ReadScreen<H> = Box<dyn Fn(&H) -> Screen>;
+
+/// A check every `Handle` has to pass: what is done to a fresh handle and what the screen shows
+/// once it was flushed
+struct Check {
+    name: &'static str,
+    draw: fn(&mut dyn Handle),
+    expect: fn(&Screen) -> Result<(), String>,
+}
+
+fn expect_eq<T: PartialEq + std::fmt::Debug>(
+    actual: T,
+    expected: T,
+    what: &str,
+) -> Result<(), String> {
+    match actual == expected {
+        true => Ok(()),
+        false => Err(format!(
+            "{}: expected {:?}, got {:?}",
+            what, expected, actual
+        )),
+    }
+}
+
+const CHECKS: [Check; 7] = [
+    Check {
+        name: "write_to_location",
+        draw: |handle| {
+            let _ = handle.write_to_location(b"ab", Usize2d::new(2, 1));
+        },
+        expect: |screen| expect_eq(screen.text(1, 1, 4), " ab ".to_string(), "the text"),
+    },
+    Check {
+        name: "cursor moves",
+        draw: |handle| {
+            let _ = handle.set_cursor_location(Usize2d::new(1, 2));
+            let _ = handle.write(b"x");
+            let _ = handle.write(b"y");
+            let _ = handle.set_cursor_location(Usize2d::new(0, 0));
+            let _ = handle.write(b"z");
+        },
+        expect: |screen| {
+            expect_eq(screen.text(0, 2, 3), " xy".to_string(), "a write continues")?;
+            expect_eq(screen.cell(0, 0).character, 'z', "the moved cursor")
+        },
+    },
+    Check {
+        name: "colors",
+        draw: |handle| {
+            let _ = handle.set_background_color(TerminalColors::Black);
+            let _ = handle.set_foreground_color(TerminalColors::Red);
+            let _ = handle.write_to_location(b"c", Usize2d::new(0, 0));
+        },
+        expect: |screen| {
+            let cell = screen.cell(0, 0);
+            expect_eq(cell.background, TerminalColors::Black, "the background")?;
+            expect_eq(cell.foreground, TerminalColors::Red, "the foreground")
+        },
+    },
+    Check {
+        name: "colors stay set",
+        draw: |handle| {
+            let _ = handle.set_background_color(TerminalColors::Blue);
+            let _ = handle.set_foreground_color(TerminalColors::White);
+            let _ = handle.write_to_location(b"d", Usize2d::new(0, 0));
+            let _ = handle.set_foreground_color(TerminalColors::Yellow);
+            let _ = handle.write_to_location(b"e", Usize2d::new(1, 0));
+        },
+        expect: |screen| {
+            let colors = |x| (screen.cell(x, 0).background, screen.cell(x, 0).foreground);
+            expect_eq(
+                colors(0),
+                (TerminalColors::Blue, TerminalColors::White),
+                "d",
+            )?;
+            expect_eq(
+                colors(1),
+                (TerminalColors::Blue, TerminalColors::Yellow),
+                "e",
+            )
+        },
+    },
+    Check {
+        name: "colors across flushes",
+        draw: |handle| {
+            let _ = handle.set_background_color(TerminalColors::Red);
+            let _ = handle.write_to_location(b"f", Usize2d::new(0, 0));
+            let _ = handle.flush();
+            let _ = handle.set_background_color(TerminalColors::Red);
+            let _ = handle.write_to_location(b"g", Usize2d::new(1, 0));
+        },
+        expect: |screen| expect_eq(screen.cell(1, 0).background, TerminalColors::Red, "g"),
+    },
+    Check {
+        name: "reset colors",
+        draw: |handle| {
+            let _ = handle.set_background_color(TerminalColors::Cyan);
+            let _ = handle.set_foreground_color(TerminalColors::Cyan);
+            let _ = handle.reset_colors();
+            let _ = handle.write_to_location(b"h", Usize2d::new(0, 0));
+        },
+        expect: |screen| {
+            let cell = screen.cell(0, 0);
+            expect_eq(
+                (cell.background, cell.foreground),
+                (TerminalColors::Default, TerminalColors::Default),
+                "the colors",
+            )
+        },
+    },
+    Check {
+        name: "clearing",
+        draw: |handle| {
+            let _ = handle.write_to_location(b"gone", Usize2d::new(0, 0));
+            let _ = handle.flush();
+            let _ = handle.clear_screen();
+            let _ = handle.write_to_location(b"hi", Usize2d::new(0, 1));
+        },
+        expect: |screen| {
+            expect_eq(screen.text(0, 0, 4), "    ".to_string(), "the cleared row")?;
+            expect_eq(screen.text(0, 1, 2), "hi".to_string(), "the new text")
+        },
+    },
+];
+
+/// Run the checks every `Handle` has to pass
+///
+/// Each check gets a fresh handle, draws to it, flushes it and reads the screen back
+///
+/// # Arguments
+///
+/// * `new_handle` - creates a handle and a function that reads back what it shows
+///
+/// # Returns
+///
+/// The checks that failed with the reason, empty if the handle conforms
+///
+/// # Example
+///
+/// ```
+/// let failures = check_handle(|| {
+///     let screen = |handle: &MemoryHandle| Screen::from_memory_handle(handle);
+///     (MemoryHandle::new(), Box::new(screen))
+/// });
+/// assert!(failures.is_empty(), "{:?}", failures);
+/// ```
+pub fn check_handle<H: Handle>(mut new_handle: impl FnMut() -> (H, ReadScreen<H>)) -> Vec<String> {
+    let mut failures = Vec::new();
+    for check in &CHECKS {
+        let (mut handle, screen) = new_handle();
+        (check.draw)(&mut handle);
+        let _ = handle.flush();
+        if let Err(reason) = (check.expect)(&screen(&handle)) {
+            failures.push(format!("{}: {}", check.name, reason));
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        handler::{
+            memory_handle::MemoryHandle, shared_handle::SharedHandle, std_io_handle::StdIOHandle,
+        },
+        rendering::colors::TerminalColors as TC,
+    };
+
+    use super::{check_handle, Screen};
+
+    /// A writer that keeps everything written to it
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn memory_handle_conforms() {
+        let failures = check_handle(|| {
+            let screen = |handle: &MemoryHandle| Screen::from_memory_handle(handle);
+            (MemoryHandle::new(), Box::new(screen))
+        });
+        assert!(failures.is_empty(), "{:#?}", failures);
+    }
+
+    #[test]
+    fn shared_handle_conforms() {
+        let failures = check_handle(|| {
+            let memory = Arc::new(Mutex::new(MemoryHandle::new()));
+            let read = memory.clone();
+            let screen = move |_: &SharedHandle| Screen::from_memory_handle(&read.lock().unwrap());
+            (SharedHandle::init(memory), Box::new(screen))
+        });
+        assert!(failures.is_empty(), "{:#?}", failures);
+    }
+
+    #[test]
+    fn std_io_handle_conforms() {
+        for is_synchronized in [false, true] {
+            let failures = check_handle(|| {
+                let output = Output::default();
+                let handle = StdIOHandle::with_writer(Box::new(output.clone()), is_synchronized);
+                let screen = move |_: &StdIOHandle| Screen::from_ansi(&output.0.lock().unwrap());
+                (handle, Box::new(screen))
+            });
+            assert!(failures.is_empty(), "{} {:#?}", is_synchronized, failures);
+        }
+    }
+
+    #[test]
+    fn file_handle_conforms() {
+        let path = std::env::temp_dir().join(format!(
+            "handle_conformance_test_{}.ansi",
+            std::process::id()
+        ));
+        let failures = check_handle(|| {
+            let file = fs::File::create(&path).unwrap();
+            let path = path.clone();
+            let screen = move |_: &StdIOHandle| Screen::from_ansi(&fs::read(&path).unwrap());
+            (
+                StdIOHandle::with_writer(Box::new(file), false),
+                Box::new(screen),
+            )
+        });
+        let _ = fs::remove_file(&path);
+        assert!(failures.is_empty(), "{:#?}", failures);
+    }
+
+    #[cfg(feature = "crossterm-backend")]
+    #[test]
+    fn crossterm_handle_draws_like_std_io_handle() {
+        use crate::{
+            handler::{
+                crossterm_handle::CrosstermHandle, cursor_style::CursorStyle, handle::Handle,
+            },
+            shared::usize2d::Usize2d,
+        };
+
+        // Terminals count from 1, crossterm can not write the 0 that `StdIOHandle` writes for
+        // the first row and column, so the drawing starts at 1
+        let draw = |handle: &mut dyn Handle| {
+            let _ = handle.set_alternate_screen(true);
+            let _ = handle.clear_screen();
+            let _ = handle.set_title("title");
+            let _ = handle.set_cursor_visible(false);
+            let _ = handle.set_background_color(TC::Black);
+            let _ = handle.set_foreground_color(TC::Red);
+            let _ = handle.write_to_location(b"ab", Usize2d::new(2, 1));
+            let _ = handle.set_foreground_color(TC::Default);
+            let _ = handle.write(b"c");
+            let _ = handle.flush();
+            let _ = handle.reset_colors();
+            let _ = handle.write_to_location(b"hi", Usize2d::new(1, 3));
+            let _ = handle.set_cursor_style(CursorStyle::default());
+            let _ = handle.bell();
+            let _ = handle.flush();
+        };
+        let std_io = Output::default();
+        draw(&mut StdIOHandle::with_writer(
+            Box::new(std_io.clone()),
+            false,
+        ));
+        let crossterm = Output::default();
+        draw(&mut CrosstermHandle::with_writer(Box::new(
+            crossterm.clone(),
+        )));
+
+        let screen = Screen::from_ansi(&crossterm.0.lock().unwrap());
+        assert_eq!(screen, Screen::from_ansi(&std_io.0.lock().unwrap()));
+        assert_eq!(screen.text(2, 1, 3), "abc");
+        assert_eq!(screen.cell(3, 1).foreground, TC::Red);
+        assert_eq!(screen.cell(4, 1).foreground, TC::Default);
+        assert_eq!(screen.text(1, 3, 2), "hi");
+    }
+
+    #[test]
+    fn reads_ansi() {
+        let screen = Screen::from_ansi(
+            b"\x1b]0;title\x07\x1b[48;5;232m\x1b[1;2Hab\x1b[0mc\x1b[48;5;232md\x1b[49me",
+        );
+        assert_eq!(screen.text(0, 1, 7), "  abcde");
+        let test_cases = [
+            ("colored", 2, TC::Black),
+            ("reset", 4, TC::Default),
+            ("default color", 6, TC::Default),
+        ];
+        for (test_case, x, expected) in test_cases {
+            assert_eq!(screen.cell(x, 1).background, expected, "{}", test_case);
+        }
+    }
+}
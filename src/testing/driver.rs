@@ -0,0 +1,290 @@
+use std::{
+    sync::{
+        mpsc::{channel, Sender},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use crate::{
+    console::input_record::KeyEvent,
+    conway::{conways_game::ConwaysGame, print_mode::PrintMode},
+    handler::{memory_handle::MemoryHandle, shared_handle::SharedHandle},
+    panel::{
+        command_enum::PanelCommandEnum,
+        frame_channel::{frame_channel, FrameSender, FRAME_QUEUE_CAPACITY},
+        panel::Panel,
+    },
+    rendering::render_object::RenderObjects,
+    shared::square::Square,
+    utils::clock::{Clock, VirtualClock},
+};
+
+use super::snapshot::{assert_snapshot, format_memory_handle};
+
+/// Something with a loop that the `TestDriver` can run one iteration at a time
+pub trait Simulated {
+    /// Run one iteration of the loop
+    ///
+    /// # Returns
+    ///
+    /// `false` once the loop has stopped
+    fn tick(&mut self) -> bool;
+}
+impl Simulated for ConwaysGame {
+    fn tick(&mut self) -> bool {
+        ConwaysGame::tick(self)
+    }
+}
+impl Simulated for Panel {
+    fn tick(&mut self) -> bool {
+        Panel::tick(self).expect("The panel should not fail while it is simulated")
+    }
+}
+
+/// Runs a game or panel against a `MemoryHandle` on a virtual clock
+///
+/// Each tick moves the clock forward by a fixed duration, delivers the inputs scripted for that
+/// tick and runs one iteration of the loop, so tests never have to sleep
+///
+/// # Example
+///
+/// ```
+/// let mut driver = TestDriver::conway(4, 3, 55, PrintMode::PRETTY, Duration::from_millis(100));
+/// driver.advance(5);
+/// driver.press_key('m');
+/// driver.assert_snapshot("conway_after_mode_switch");
+/// ```
+pub struct TestDriver<T: Simulated, I> {
+    clock: VirtualClock,
+    tick_duration: Duration,
+    memory_handle: Arc<Mutex<MemoryHandle>>,
+    target: T,
+    input_sender: Sender<I>,
+    script: Vec<(u64, I)>,
+    tick_count: u64,
+    is_finished: bool,
+}
+impl TestDriver<ConwaysGame, KeyEvent> {
+    /// Create a driver for a Conway game where every tick is one round long
+    ///
+    /// # Arguments
+    ///
+    /// * `x_len` - number of cells in a row
+    /// * `y_len` - number of rows in the grid
+    /// * `seed` - the seed the board is filled with
+    /// * `mode` - the print mode the game starts in
+    /// * `round_duration` - the time between generations and the time that passes per tick
+    pub fn conway(
+        x_len: usize,
+        y_len: usize,
+        seed: u64,
+        mode: PrintMode,
+        round_duration: Duration,
+    ) -> Self {
+        let clock = VirtualClock::new();
+        let memory_handle = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (input_sender, receiver) = channel();
+        let mut game = ConwaysGame::builder(x_len, y_len)
+            .seed(seed)
+            .render_mode(mode)
+            .speed(round_duration)
+            .receiver(receiver)
+            .handle(Box::new(SharedHandle::init(memory_handle.clone())))
+            .build()
+            .unwrap();
+        game.set_clock(Box::new(clock.clone()));
+        game.start();
+        TestDriver::new(clock, round_duration, memory_handle, game, input_sender)
+    }
+    /// Press a key and run one tick so that the game handles it
+    pub fn press_key(&mut self, key: char) {
+        self.press(KeyEvent::key_down(key));
+    }
+}
+impl TestDriver<Panel, PanelCommandEnum> {
+    /// Create a driver for a panel
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - the area of the panel
+    /// * `tick_duration` - the time that passes per tick
+    ///
+    /// # Returns
+    ///
+    /// The driver and the sender that frames for the panel can be sent with
+    pub fn panel(area: Square, tick_duration: Duration) -> (Self, FrameSender<RenderObjects>) {
+        let clock = VirtualClock::new();
+        let memory_handle = Arc::new(Mutex::new(MemoryHandle::new()));
+        let (frame_sender, frame_receiver) = frame_channel(FRAME_QUEUE_CAPACITY);
+        let (input_sender, command_receiver) = channel();
+        let panel = Panel::init_with_clock(
+            area,
+            frame_receiver,
+            command_receiver,
+            Box::new(SharedHandle::init(memory_handle.clone())),
+            Box::new(clock.clone()),
+        )
+        .expect("The panel should be created");
+        let driver = TestDriver::new(clock, tick_duration, memory_handle, panel, input_sender);
+        (driver, frame_sender)
+    }
+}
+impl<T: Simulated, I> TestDriver<T, I> {
+    fn new(
+        clock: VirtualClock,
+        tick_duration: Duration,
+        memory_handle: Arc<Mutex<MemoryHandle>>,
+        target: T,
+        input_sender: Sender<I>,
+    ) -> Self {
+        TestDriver {
+            clock,
+            tick_duration,
+            memory_handle,
+            target,
+            input_sender,
+            script: Vec::new(),
+            tick_count: 0,
+            is_finished: false,
+        }
+    }
+    /// Script an input to be sent right before a tick runs
+    ///
+    /// # Arguments
+    ///
+    /// * `tick` - the number of the tick, counting from 1
+    /// * `input` - the input to send
+    pub fn script(mut self, tick: u64, input: I) -> Self {
+        self.script.push((tick, input));
+        self
+    }
+    /// Send an input and run one tick so that it is handled
+    pub fn press(&mut self, input: I) {
+        let _ = self.input_sender.send(input);
+        self.advance(1);
+    }
+    /// Run a number of ticks, moving the clock forward by the tick duration before each one
+    ///
+    /// Ticks after the loop stopped only move the clock
+    pub fn advance(&mut self, ticks: u64) {
+        for _ in 0..ticks {
+            self.tick_count += 1;
+            self.clock.advance(self.tick_duration);
+            let tick = self.tick_count;
+            let (due, later): (Vec<_>, Vec<_>) = std::mem::take(&mut self.script)
+                .into_iter()
+                .partition(|(at, _)| *at <= tick);
+            self.script = later;
+            for (_, input) in due {
+                let _ = self.input_sender.send(input);
+            }
+            if !self.is_finished {
+                self.is_finished = !self.target.tick();
+            }
+        }
+    }
+    /// Move the clock forward without running the loop, then run one tick
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.clock.advance(duration);
+        self.advance(1);
+    }
+    pub fn tick_count(&self) -> u64 {
+        self.tick_count
+    }
+    pub fn is_finished(&self) -> bool {
+        self.is_finished
+    }
+    pub fn clock(&self) -> &dyn Clock {
+        &self.clock
+    }
+    pub fn target(&self) -> &T {
+        &self.target
+    }
+    /// Get the flushed content of the handle formatted by `format_memory_handle`
+    pub fn frame(&self) -> String {
+        let handle = self
+            .memory_handle
+            .lock()
+            .expect("The memory handle should not be poisoned");
+        format_memory_handle(&handle)
+    }
+    /// Get only the characters of the flushed content
+    pub fn text(&self) -> String {
+        let handle = self
+            .memory_handle
+            .lock()
+            .expect("The memory handle should not be poisoned");
+        String::from_utf8_lossy(&handle.get_buffer_content()).to_string()
+    }
+    /// Compare the current frame against a stored snapshot, see `snapshot::assert_snapshot`
+    pub fn assert_snapshot(&self, name: &str) {
+        assert_snapshot(name, &self.frame());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        conway::print_mode::PrintMode,
+        panel::command_enum::PanelCommandEnum,
+        shared::{square::Square, usize2d::Usize2d},
+    };
+
+    use super::TestDriver;
+
+    #[test]
+    fn conway_ticks_and_keys() {
+        let mut driver =
+            TestDriver::conway(4, 3, 55, PrintMode::PRETTY, Duration::from_millis(100));
+        driver.advance(1);
+        assert!(!driver.is_finished());
+        driver.press_key('m');
+        driver.assert_snapshot("driver_conway_mode_switch");
+
+        driver.advance(20);
+        assert!(
+            driver.is_finished(),
+            "The board should be stable well within 20 rounds"
+        );
+        assert_eq!(driver.tick_count(), 22);
+    }
+
+    #[test]
+    fn conway_quit_from_script() {
+        let mut driver = TestDriver::conway(4, 3, 55, PrintMode::PRETTY, Duration::from_secs(1))
+            .script(2, crate::console::input_record::KeyEvent::key_down('q'));
+        driver.advance(1);
+        assert!(!driver.is_finished());
+        driver.advance(1);
+        assert!(
+            driver.is_finished(),
+            "The scripted quit should stop the game"
+        );
+    }
+
+    #[test]
+    fn panel_toast_expires_on_virtual_time() {
+        let area = Square::new(Usize2d::new(0, 0), Usize2d::new(9, 1));
+        let (mut driver, _frame_sender) = TestDriver::panel(area, Duration::from_millis(10));
+        driver.press(PanelCommandEnum::ShowToast(
+            "saved".to_string(),
+            Duration::from_millis(200),
+        ));
+        assert_eq!(driver.text(), "    saved \n          ");
+
+        driver.advance(18);
+        assert_eq!(
+            driver.text(),
+            "    saved \n          ",
+            "The toast should still be visible after 190ms"
+        );
+        driver.advance_time(Duration::from_millis(100));
+        assert_eq!(driver.text(), "          \n          ");
+
+        driver.press(PanelCommandEnum::KillProcess);
+        assert!(driver.is_finished());
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    console::input_record::KeyEvent,
+    conway::{conways_game::ConwaysGame, print_mode::PrintMode},
+    handler::{memory_handle::MemoryHandle, shared_handle::SharedHandle},
+    rendering::colors::TerminalColors,
+};
+
+/// Set this environment variable to write the actual output over the stored snapshots
+pub const UPDATE_ENV_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// One step of a scripted scenario
+#[derive(Clone, Copy, Debug)]
+pub enum ScenarioStep {
+    /// Press a key
+    Key(char),
+    /// Advance the game by a number of rounds
+    Ticks(usize),
+}
+
+/// Run a scripted Conway scenario against a `MemoryHandle` and capture the final frame
+///
+/// # Arguments
+///
+/// * `x_len` - number of cells in a row
+/// * `y_len` - number of rows in the grid
+/// * `seed` - the seed the board is filled with
+/// * `mode` - the print mode the game starts in
+/// * `steps` - the inputs and ticks to apply before the frame is captured
+///
+/// # Returns
+///
+/// The final frame formatted by `format_memory_handle`
+///
+/// # Example
+///
+/// ```
+/// let frame = run_conway_scenario(4, 3, 55, PrintMode::PRETTY, &[ScenarioStep::Ticks(2)]);
+/// assert_snapshot("conway_pretty", &frame);
+/// ```
+pub fn run_conway_scenario(
+    x_len: usize,
+    y_len: usize,
+    seed: u64,
+    mode: PrintMode,
+    steps: &[ScenarioStep],
+) -> String {
+    let memory_handle = Arc::new(Mutex::new(MemoryHandle::new()));
+    let (_sender, receiver) = std::sync::mpsc::channel();
+    let mut game = ConwaysGame::builder(x_len, y_len)
+        .seed(seed)
+        .render_mode(mode)
+        .speed(Duration::from_secs(1))
+        .receiver(receiver)
+        .handle(Box::new(SharedHandle::init(memory_handle.clone())))
+        .build()
+        .unwrap();
+    for step in steps {
+        match step {
+            ScenarioStep::Key(key) => game.handle_key(KeyEvent::key_down(*key)),
+            ScenarioStep::Ticks(count) => {
+                for _ in 0..*count {
+                    game.step();
+                }
+            }
+        }
+    }
+    game.render();
+    let handle = memory_handle
+        .lock()
+        .expect("The memory handle should not be poisoned");
+    format_memory_handle(&handle)
+}
+
+/// Format the flushed content of a `MemoryHandle` as readable text
+///
+/// The characters are listed first, with a `|` at the end of every row so that trailing spaces
+/// are visible, followed by the background and foreground colors as one letter per cell
+///
+/// # Example
+///
+/// ```
+/// --- text ---
+/// ab|
+/// --- background ---
+/// rr
+/// --- foreground ---
+/// ww
+/// ```
+pub fn format_memory_handle(handle: &MemoryHandle) -> String {
+    let text = String::from_utf8_lossy(&handle.get_buffer_content()).to_string();
+    let mut lines = vec!["--- text ---".to_string()];
+    if !text.is_empty() {
+        lines.extend(text.split('\n').map(|row| format!("{}|", row)));
+    }
+    lines.push("--- background ---".to_string());
+    lines.extend(format_colors(handle.get_background_colors()));
+    lines.push("--- foreground ---".to_string());
+    lines.extend(format_colors(handle.get_foreground_colors()));
+    lines.join("\n") + "\n"
+}
+
+fn format_colors(colors: Vec<Vec<TerminalColors>>) -> Vec<String> {
+    colors
+        .iter()
+        .map(|row| row.iter().map(|color| color_code(*color)).collect())
+        .collect()
+}
+
+fn color_code(color: TerminalColors) -> char {
+    match color {
+        TerminalColors::Default => '.',
+        TerminalColors::LightGreen => 'g',
+        TerminalColors::Red => 'r',
+        TerminalColors::White => 'w',
+        TerminalColors::Black => 'k',
+        TerminalColors::HotPink => 'p',
+        TerminalColors::Yellow => 'y',
+        TerminalColors::DarkBlue => 'd',
+        TerminalColors::Blue => 'b',
+        TerminalColors::Cyan => 'c',
+        TerminalColors::Orange => 'o',
+        TerminalColors::DarkGrey => 'e',
+        TerminalColors::Grey => 'a',
+    }
+}
+
+/// Compare output against the snapshot stored in `src/testing/snapshots/<name>.snap`
+///
+/// Panics with a line by line diff if the output does not match. When `UPDATE_SNAPSHOTS` is set
+/// the snapshot is written instead
+///
+/// # Arguments
+///
+/// * `name` - the name of the snapshot file without the extension
+/// * `actual` - the output produced by the test
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    if env::var_os(UPDATE_ENV_VAR).is_some() {
+        fs::write(&path, actual)
+            .unwrap_or_else(|_| panic!("Failed to write snapshot {}", path.display()));
+        return;
+    }
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) => expected,
+        Err(_) => panic!(
+            "Snapshot {} does not exist. Run the test with {}=1 to create it",
+            path.display(),
+            UPDATE_ENV_VAR
+        ),
+    };
+    if let Some(diff) = diff(&expected, actual) {
+        panic!(
+            "Snapshot `{}` does not match. Run the test with {}=1 to accept the new output\n{}",
+            name, UPDATE_ENV_VAR, diff
+        );
+    }
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("testing")
+        .join("snapshots")
+        .join(format!("{}.snap", name))
+}
+
+/// Describe the lines that differ between two outputs
+///
+/// # Returns
+///
+/// `None` if the outputs are the same, otherwise every differing line with the expected line
+/// prefixed by `-` and the actual line prefixed by `+`
+pub fn diff(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let mut result = vec![];
+    for index in 0..expected_lines.len().max(actual_lines.len()) {
+        let expected_line = expected_lines.get(index);
+        let actual_line = actual_lines.get(index);
+        if expected_line == actual_line {
+            continue;
+        }
+        result.push(format!("line {}:", index + 1));
+        if let Some(line) = expected_line {
+            result.push(format!("  - {}", line));
+        }
+        if let Some(line) = actual_line {
+            result.push(format!("  + {}", line));
+        }
+    }
+    if result.is_empty() {
+        result.push("The outputs only differ in their line endings".to_string());
+    }
+    Some(result.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use crate::{
+        conway::print_mode::PrintMode,
+        handler::{handle::Handle, memory_handle::MemoryHandle},
+        rendering::colors::TerminalColors,
+        shared::usize2d::Coord,
+    };
+
+    use super::{assert_snapshot, diff, format_memory_handle, run_conway_scenario, ScenarioStep};
+
+    #[test]
+    fn format_handle() {
+        let mut handle = MemoryHandle::new();
+        let _ = handle.set_background_color(TerminalColors::Red);
+        let _ = handle.set_foreground_color(TerminalColors::White);
+        let _ = handle.write_to_location(b"ab ", Coord::new(1, 0));
+        let _ = handle.flush();
+        assert_eq!(
+            format_memory_handle(&handle),
+            "--- text ---\n ab |\n--- background ---\n.rrr\n--- foreground ---\n.www\n"
+        );
+    }
+
+    #[test]
+    fn diff_lines() {
+        assert_eq!(diff("a\nb\n", "a\nb\n"), None);
+        assert_eq!(
+            diff("a\nb\nc", "a\nx"),
+            Some("line 2:\n  - b\n  + x\nline 3:\n  - c".to_string())
+        );
+    }
+
+    #[test]
+    fn conway_pretty() {
+        let frame = run_conway_scenario(4, 3, 55, PrintMode::PRETTY, &[ScenarioStep::Ticks(1)]);
+        assert_snapshot("conway_pretty", &frame);
+    }
+
+    #[test]
+    fn conway_debug_paused() {
+        let frame = run_conway_scenario(
+            3,
+            2,
+            55,
+            PrintMode::PRETTY,
+            &[
+                ScenarioStep::Key('m'),
+                ScenarioStep::Ticks(2),
+                ScenarioStep::Key(' '),
+            ],
+        );
+        assert_snapshot("conway_debug_paused", &frame);
+    }
+
+    // The help lists the export keys, which are only bound with the export-image feature
+    #[cfg(feature = "export-image")]
+    #[test]
+    fn conway_help() {
+        let frame = run_conway_scenario(
+            8,
+            6,
+            55,
+            PrintMode::PRETTY,
+            &[ScenarioStep::Key('?'), ScenarioStep::Ticks(1)],
+        );
+        assert_snapshot("conway_help", &frame);
+    }
+
+    // Saving and the high scores are disabled in the menu without serde
+    #[cfg(feature = "serde")]
+    #[test]
+    fn conway_pause_menu() {
+        let frame = run_conway_scenario(
+            8,
+            6,
+            55,
+            PrintMode::PRETTY,
+            &[
+                ScenarioStep::Key('\x1b'),
+                ScenarioStep::Key('s'),
+                ScenarioStep::Ticks(1),
+            ],
+        );
+        assert_snapshot("conway_pause_menu", &frame);
+    }
+}
@@ -0,0 +1,117 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::board::{Board, Mark, Outcome, CELL_COUNT, LINES};
+
+/// The center cell, which is on the most lines
+const CENTER: usize = 4;
+
+/// Pick a move for the player whose turn it is
+///
+/// The player wins if it can, blocks a line the other player is about to finish, takes the
+/// center and otherwise plays a random free cell. That is not perfect play, which keeps demo
+/// games from always ending in a draw
+///
+/// # Arguments
+///
+/// * `board` - the board to play on
+/// * `rng` - the random numbers for the free cell, e.g. the `AI_STREAM`
+///
+/// # Returns
+///
+/// The cell to play, or `None` if the game is over
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{tic_tac_toe::{ai::choose_move, board::MoveError}, utils::rng::{RngService, AI_STREAM}, Board};
+/// # fn main() -> Result<(), MoveError> {
+/// # let (seed, mut board) = (42, Board::default());
+/// let mut rng = RngService::new(seed).stream(AI_STREAM);
+/// if let Some(cell) = choose_move(&board, &mut rng) {
+///     board.play(board.turn(), cell)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn choose_move<R: Rng>(board: &Board, rng: &mut R) -> Option<usize> {
+    if board.outcome() != Outcome::InProgress {
+        return None;
+    }
+    let mark = board.turn();
+    let free: Vec<usize> = (0..CELL_COUNT)
+        .filter(|cell| board.cell(*cell).is_none())
+        .collect();
+    finishing_cell(board, mark)
+        .or_else(|| finishing_cell(board, mark.other()))
+        .or_else(|| free.contains(&CENTER).then_some(CENTER))
+        .or_else(|| free.choose(rng).copied())
+}
+
+/// Find the free cell of a line where a mark already has the other two cells
+fn finishing_cell(board: &Board, mark: Mark) -> Option<usize> {
+    LINES.iter().find_map(|line| {
+        let marked = line
+            .iter()
+            .filter(|cell| board.cell(**cell) == Some(mark))
+            .count();
+        let free: Vec<usize> = line
+            .iter()
+            .copied()
+            .filter(|cell| board.cell(*cell).is_none())
+            .collect();
+        match (marked, free.as_slice()) {
+            (2, [cell]) => Some(*cell),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tic_tac_toe::board::{Board, Outcome},
+        utils::rng::{RngService, AI_STREAM},
+    };
+
+    use super::choose_move;
+
+    fn board(moves: &[usize]) -> Board {
+        let mut board = Board::default();
+        for cell in moves {
+            board.play(board.turn(), *cell).unwrap();
+        }
+        board
+    }
+
+    #[test]
+    fn choices() {
+        let test_cases = [
+            ("center first", vec![], Some(4)),
+            ("win", vec![0, 3, 1, 4], Some(2)),
+            ("block", vec![4, 0, 8], Some(2)),
+            ("win before block", vec![4, 0, 3, 1, 8], Some(2)),
+            ("game over", vec![0, 3, 1, 4, 2], None),
+        ];
+        for (test_case, moves, expected) in test_cases {
+            let mut rng = RngService::new(1).stream(AI_STREAM);
+            assert_eq!(
+                choose_move(&board(&moves), &mut rng),
+                expected,
+                "{}",
+                test_case
+            );
+        }
+    }
+
+    #[test]
+    fn plays_to_the_end() {
+        for seed in 0..20 {
+            let mut rng = RngService::new(seed).stream(AI_STREAM);
+            let mut board = Board::default();
+            while let Some(cell) = choose_move(&board, &mut rng) {
+                board.play(board.turn(), cell).unwrap();
+            }
+            assert_ne!(board.outcome(), Outcome::InProgress, "seed {}", seed);
+        }
+    }
+}
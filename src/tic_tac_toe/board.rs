@@ -0,0 +1,200 @@
+use std::fmt::Display;
+#[cfg(feature = "export-image")]
+use std::{fs, io, path::Path};
+
+#[cfg(feature = "export-image")]
+use crate::export::svg::board_svg;
+
+/// The number of cells on a tic-tac-toe board
+pub const CELL_COUNT: usize = 9;
+/// The size of a cell of an exported board
+#[cfg(feature = "export-image")]
+const SVG_CELL_SIZE: usize = 120;
+/// The rows, columns and diagonals that win the game, as cell indexes
+pub(crate) const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mark {
+    X,
+    O,
+}
+impl Mark {
+    pub fn other(&self) -> Mark {
+        match self {
+            Mark::X => Mark::O,
+            Mark::O => Mark::X,
+        }
+    }
+}
+impl Display for Mark {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mark::X => write!(f, "X"),
+            Mark::O => write!(f, "O"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MoveError {
+    OutOfBounds,
+    CellTaken,
+    NotYourTurn,
+    GameOver,
+}
+impl Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfBounds => write!(f, "The cell is not on the board"),
+            Self::CellTaken => write!(f, "The cell is already taken"),
+            Self::NotYourTurn => write!(f, "It is not your turn"),
+            Self::GameOver => write!(f, "The game is over"),
+        }
+    }
+}
+
+/// The state of the game after a move
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    InProgress,
+    Won(Mark),
+    Draw,
+}
+
+/// A tic-tac-toe board, X always starts
+///
+/// The cells are numbered 0 to 8 from the top left to the bottom right, row by row
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{tic_tac_toe::board::MoveError, Board, Mark};
+/// # fn main() -> Result<(), MoveError> {
+/// let mut board = Board::default();
+/// board.play(Mark::X, 4)?;
+/// board.play(Mark::O, 0)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    cells: [Option<Mark>; CELL_COUNT],
+    turn: Mark,
+    outcome: Outcome,
+}
+impl Default for Board {
+    fn default() -> Self {
+        Board {
+            cells: [None; CELL_COUNT],
+            turn: Mark::X,
+            outcome: Outcome::InProgress,
+        }
+    }
+}
+impl Board {
+    /// Get the mark in a cell, `None` if the cell is empty or not on the board
+    pub fn cell(&self, index: usize) -> Option<Mark> {
+        self.cells.get(index).copied().flatten()
+    }
+    /// The mark that plays next
+    pub fn turn(&self) -> Mark {
+        self.turn
+    }
+    pub fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+    /// Check if a move is allowed without playing it
+    pub fn validate(&self, mark: Mark, index: usize) -> Result<(), MoveError> {
+        if self.outcome != Outcome::InProgress {
+            return Err(MoveError::GameOver);
+        }
+        if mark != self.turn {
+            return Err(MoveError::NotYourTurn);
+        }
+        if index >= CELL_COUNT {
+            return Err(MoveError::OutOfBounds);
+        }
+        if self.cells[index].is_some() {
+            return Err(MoveError::CellTaken);
+        }
+        Ok(())
+    }
+    /// Place a mark and pass the turn to the other player
+    ///
+    /// # Returns
+    ///
+    /// The state of the game after the move, or why the move is not allowed
+    pub fn play(&mut self, mark: Mark, index: usize) -> Result<Outcome, MoveError> {
+        self.validate(mark, index)?;
+        self.cells[index] = Some(mark);
+        self.turn = mark.other();
+        let is_won = LINES
+            .iter()
+            .any(|line| line.iter().all(|cell| self.cells[*cell] == Some(mark)));
+        self.outcome = if is_won {
+            Outcome::Won(mark)
+        } else if self.cells.iter().all(|cell| cell.is_some()) {
+            Outcome::Draw
+        } else {
+            Outcome::InProgress
+        };
+        Ok(self.outcome)
+    }
+    /// Write the board to an SVG file, with the marks drawn as lines and circles
+    #[cfg(feature = "export-image")]
+    pub fn export_svg(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, board_svg(self, SVG_CELL_SIZE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Board, Mark, MoveError, Outcome};
+
+    #[test]
+    fn validate_moves() {
+        let mut board = Board::default();
+        assert_eq!(board.play(Mark::O, 0), Err(MoveError::NotYourTurn));
+        assert_eq!(board.play(Mark::X, 9), Err(MoveError::OutOfBounds));
+        assert_eq!(board.play(Mark::X, 4), Ok(Outcome::InProgress));
+        assert_eq!(board.play(Mark::O, 4), Err(MoveError::CellTaken));
+        assert_eq!(board.cell(4), Some(Mark::X));
+        assert_eq!(board.turn(), Mark::O);
+    }
+
+    #[test]
+    fn outcomes() {
+        let test_cases = vec![
+            (1, vec![0, 3, 1, 4, 2], Outcome::Won(Mark::X)),
+            (2, vec![0, 2, 1, 4, 3, 6], Outcome::Won(Mark::O)),
+            (3, vec![0, 1, 2, 4, 3, 5, 7, 6, 8], Outcome::Draw),
+            (4, vec![0, 1], Outcome::InProgress),
+        ];
+        for (test_case, moves, expected) in test_cases {
+            let mut board = Board::default();
+            for index in moves {
+                let _ = board.play(board.turn(), index);
+            }
+            assert_eq!(
+                board.outcome(),
+                expected,
+                "Test case {}: The outcome is wrong",
+                test_case
+            );
+        }
+        let mut board = Board::default();
+        for index in [0, 3, 1, 4, 2] {
+            let _ = board.play(board.turn(), index);
+        }
+        assert_eq!(board.play(Mark::O, 5), Err(MoveError::GameOver));
+    }
+}
@@ -16,15 +16,17 @@ use std::str::FromStr;
 /// # Examples
 ///
 /// ```
+/// # use std::env;
+/// # use tic_tac_toe2::utils::arg_helper::read_config;
 /// let args: Vec<String> = env::args().collect();
 /// let _x_len: usize = read_config(&args, "--x-len".to_string(), 10);
 ///
 /// ```
-pub fn read_config<T>(args: &Vec<String>, arg: String, default: T) -> T
+pub fn read_config<T>(args: &[String], arg: String, default: T) -> T
 where
     T: FromStr + Clone + Copy,
 {
-    let mut out: T = default.clone();
+    let mut out: T = default;
     for i in 1..args.len() {
         if args[i].as_str() != arg {
             continue;
@@ -40,16 +42,52 @@ where
     out
 }
 
+/// Read an argument that has no default from the input args list
+///
+/// # Arguments
+///
+/// * `args` - all args received from the input
+/// * `arg` - name of the argument
+///
+/// # Returns
+///
+/// The argument value, or `None` if it was not passed in or is invalid
+///
+/// # Examples
+///
+/// ```
+/// # use std::env;
+/// # use tic_tac_toe2::{conway::patterns::PatternPlacement, utils::arg_helper::read_optional_config};
+/// let args: Vec<String> = env::args().collect();
+/// let pattern: Option<PatternPlacement> = read_optional_config(&args, "--pattern".to_string());
+/// ```
+pub fn read_optional_config<T>(args: &[String], arg: String) -> Option<T>
+where
+    T: FromStr,
+{
+    let mut out: Option<T> = None;
+    for i in 1..args.len() {
+        if args[i].as_str() != arg {
+            continue;
+        }
+        if let Some(val) = args.get(i + 1) {
+            out = val.parse().ok();
+            if out.is_none() {
+                eprint!("invalid value for {}. Ignoring it.", arg);
+            }
+        }
+    }
+
+    out
+}
+
 #[cfg(test)]
 mod tests {
-    use super::read_config;
+    use super::{read_config, read_optional_config};
 
     #[test]
     fn read_success() {
-        let mut args: Vec<String> = Vec::new();
-        args.push("--".to_string());
-        args.push("--test".to_string());
-        args.push("100".to_string());
+        let args: Vec<String> = vec!["--".to_string(), "--test".to_string(), "100".to_string()];
 
         let x: usize = read_config(&args, "--test".to_string(), 10000);
         assert_eq!(x, 100, "Expected value of 100");
@@ -57,12 +95,24 @@ mod tests {
 
     #[test]
     fn read_failed() {
-        let mut args: Vec<String> = Vec::new();
-        args.push("--".to_string());
-        args.push("--test2".to_string());
-        args.push("100".to_string());
+        let args: Vec<String> = vec!["--".to_string(), "--test2".to_string(), "100".to_string()];
 
         let x: usize = read_config(&args, "test".to_string(), 10000);
         assert_eq!(x, 10000, "Expected value of 100");
     }
+
+    #[test]
+    fn read_optional() {
+        let args: Vec<String> = vec!["--", "--pattern", "glider@1,2", "--seed", "x"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let pattern: Option<String> = read_optional_config(&args, "--pattern".to_string());
+        assert_eq!(pattern, Some("glider@1,2".to_string()));
+        let seed: Option<u64> = read_optional_config(&args, "--seed".to_string());
+        assert_eq!(seed, None, "An invalid value should be ignored");
+        let missing: Option<u64> = read_optional_config(&args, "--x-len".to_string());
+        assert_eq!(missing, None);
+    }
 }
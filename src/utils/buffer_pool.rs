@@ -0,0 +1,119 @@
+use crate::shared::frame::Pixel;
+
+/// The number of free buffers a pool keeps by default, enough for a double buffered grid
+pub const DEFAULT_FREE_BUFFERS: usize = 2;
+
+/// A 2d buffer of rows, like a grid of cells or a `Frame`
+pub type Buffer<T> = Vec<Vec<T>>;
+/// A pool for the buffers of frames
+pub type FramePool = BufferPool<Pixel>;
+
+/// Recycles the allocations of 2d buffers that are replaced every generation or frame
+///
+/// A buffer that is no longer needed is given back to the pool, the next `take` fills it again
+/// instead of allocating a new one. The pool only keeps a few free buffers, the others are
+/// dropped
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::utils::buffer_pool::BufferPool;
+/// # let (x_len, y_len) = (8, 8);
+/// # let mut current = vec![vec![false; x_len]; y_len];
+/// let mut pool = BufferPool::default();
+/// let mut next = pool.take(x_len, y_len, false);
+/// // ... fill in the next generation
+/// pool.give(std::mem::replace(&mut current, next));
+/// ```
+#[derive(Debug)]
+pub struct BufferPool<T> {
+    free: Vec<Buffer<T>>,
+    max_free: usize,
+    allocations: u64,
+}
+impl<T: Clone> Default for BufferPool<T> {
+    fn default() -> Self {
+        BufferPool::new(DEFAULT_FREE_BUFFERS)
+    }
+}
+impl<T: Clone> BufferPool<T> {
+    /// Create a pool without buffers
+    ///
+    /// # Arguments
+    ///
+    /// * `max_free` - the number of free buffers that are kept for the next `take`
+    pub fn new(max_free: usize) -> Self {
+        BufferPool {
+            free: Vec::new(),
+            max_free,
+            allocations: 0,
+        }
+    }
+    /// Get a buffer of a size with every value set to `fill`, recycled if the pool has one
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the length of every row
+    /// * `height` - the number of rows
+    /// * `fill` - the value of every cell
+    pub fn take(&mut self, width: usize, height: usize, fill: T) -> Buffer<T> {
+        let Some(mut buffer) = self.free.pop() else {
+            self.allocations += 1;
+            return vec![vec![fill; width]; height];
+        };
+        buffer.truncate(height);
+        for row in buffer.iter_mut() {
+            row.clear();
+            row.resize(width, fill.clone());
+        }
+        buffer.resize(height, vec![fill; width]);
+        buffer
+    }
+    /// Give a buffer back to the pool so that its allocation can be used again
+    pub fn give(&mut self, buffer: Buffer<T>) {
+        if self.free.len() < self.max_free {
+            self.free.push(buffer);
+        }
+    }
+    /// Get the number of buffers that had to be allocated because the pool had none free
+    pub fn allocations(&self) -> u64 {
+        self.allocations
+    }
+    /// Get the number of free buffers in the pool
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BufferPool;
+
+    #[test]
+    fn recycles_buffers() {
+        let mut pool = BufferPool::new(1);
+        let first = pool.take(3, 2, false);
+        pool.give(first);
+        let test_cases = [
+            ("same size", 3, 2, vec![vec![true; 3]; 2]),
+            ("smaller", 2, 1, vec![vec![true; 2]]),
+            ("larger", 4, 3, vec![vec![true; 4]; 3]),
+        ];
+        for (test_case, width, height, expected) in test_cases {
+            let buffer = pool.take(width, height, true);
+            assert_eq!(buffer, expected, "{}", test_case);
+            pool.give(buffer);
+        }
+        assert_eq!(pool.allocations(), 1, "Only the first buffer is allocated");
+    }
+
+    #[test]
+    fn keeps_a_few_free() {
+        let mut pool = BufferPool::new(2);
+        let buffers: Vec<_> = (0..3).map(|_| pool.take(2, 2, 0u8)).collect();
+        for buffer in buffers {
+            pool.give(buffer);
+        }
+        assert_eq!((pool.allocations(), pool.free_count()), (3, 2));
+    }
+}
@@ -0,0 +1,97 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A source of the current time
+///
+/// Loops that need the time ask their clock instead of calling `Instant::now` so that tests can
+/// swap in a `VirtualClock` and move time forward without sleeping
+pub trait Clock: Send + Debug {
+    fn now(&self) -> Instant;
+}
+
+/// The clock used outside of tests, backed by `Instant::now`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when it is told to
+///
+/// Clones share the same time, so the test can keep one clone and hand the other to the code
+/// being tested
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use tic_tac_toe2::utils::clock::{Clock, VirtualClock};
+/// let clock = VirtualClock::new();
+/// let start = clock.now();
+/// clock.advance(Duration::from_secs(5));
+/// assert_eq!(clock.now() - start, Duration::from_secs(5));
+/// ```
+#[derive(Clone, Debug)]
+pub struct VirtualClock {
+    now: Arc<Mutex<Instant>>,
+}
+impl Default for VirtualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VirtualClock {
+    pub fn new() -> Self {
+        VirtualClock {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+    /// Move the time of the clock and all of its clones forward
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self
+            .now
+            .lock()
+            .expect("The virtual clock should not be poisoned");
+        *now += duration;
+    }
+}
+impl Clock for VirtualClock {
+    fn now(&self) -> Instant {
+        *self
+            .now
+            .lock()
+            .expect("The virtual clock should not be poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Clock, VirtualClock};
+
+    #[test]
+    fn virtual_clock() {
+        let clock = VirtualClock::new();
+        let shared = clock.clone();
+        let start = clock.now();
+        assert_eq!(
+            clock.now(),
+            start,
+            "The virtual clock should not move by itself"
+        );
+
+        shared.advance(Duration::from_millis(250));
+        assert_eq!(
+            clock.now() - start,
+            Duration::from_millis(250),
+            "Clones should share the same time"
+        );
+    }
+}
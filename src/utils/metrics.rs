@@ -0,0 +1,236 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::{Display, Formatter},
+    time::{Duration, Instant},
+};
+
+/// The number of generations that were stepped
+pub const GENERATIONS: &str = "generations";
+/// The number of key commands that were handled
+pub const COMMANDS: &str = "commands";
+/// The number of live cells
+pub const POPULATION: &str = "population";
+/// The time a frame spent on the key inputs and the timers
+pub const UPDATE_TIME: &str = "update_time";
+/// The time a frame spent drawing the game
+pub const RENDER_TIME: &str = "render_time";
+/// The number of frames that a renderer dropped because it fell behind
+pub const DROPPED_FRAMES: &str = "dropped_frames";
+/// The number of samples that are kept of a series, the oldest sample is dropped after that
+pub const SERIES_LENGTH: usize = 120;
+
+/// Collects the numbers of a session by name
+///
+/// Counters only go up, gauges keep their latest and their highest value and series keep their
+/// latest `SERIES_LENGTH` durations. The session starts when the metrics are created or reset, so
+/// that the elapsed time can be reported with the numbers
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::{utils::{clock::{Clock, SystemClock}, metrics::{Metrics, GENERATIONS, POPULATION}}, ConwaysGame};
+/// # let (clock, seed) = (SystemClock, 42);
+/// # let game = ConwaysGame::headless(16, 16, seed);
+/// let mut metrics = Metrics::new(clock.now());
+/// metrics.increment(GENERATIONS);
+/// metrics.gauge(POPULATION, game.population() as u64);
+/// println!("{}", metrics.summary(seed, clock.now()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    started: Instant,
+    counters: BTreeMap<&'static str, u64>,
+    gauges: BTreeMap<&'static str, Gauge>,
+    series: BTreeMap<&'static str, VecDeque<Duration>>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Gauge {
+    latest: u64,
+    peak: u64,
+}
+
+impl Metrics {
+    pub fn new(started: Instant) -> Self {
+        Metrics {
+            started,
+            counters: BTreeMap::new(),
+            gauges: BTreeMap::new(),
+            series: BTreeMap::new(),
+        }
+    }
+    /// Forget all numbers and start a new session
+    pub fn reset(&mut self, started: Instant) {
+        *self = Metrics::new(started);
+    }
+    /// Add one to a counter
+    pub fn increment(&mut self, name: &'static str) {
+        self.add(name, 1);
+    }
+    /// Add an amount to a counter
+    pub fn add(&mut self, name: &'static str, amount: u64) {
+        *self.counters.entry(name).or_default() += amount;
+    }
+    /// Get the value of a counter, 0 if it was never changed
+    pub fn count(&self, name: &str) -> u64 {
+        self.counters.get(name).copied().unwrap_or(0)
+    }
+    /// Set the latest value of a gauge
+    pub fn gauge(&mut self, name: &'static str, value: u64) {
+        let gauge = self.gauges.entry(name).or_default();
+        gauge.latest = value;
+        gauge.peak = gauge.peak.max(value);
+    }
+    /// Get the latest value of a gauge, 0 if it was never set
+    pub fn latest(&self, name: &str) -> u64 {
+        self.gauges.get(name).map(|gauge| gauge.latest).unwrap_or(0)
+    }
+    /// Get the highest value of a gauge, 0 if it was never set
+    pub fn peak(&self, name: &str) -> u64 {
+        self.gauges.get(name).map(|gauge| gauge.peak).unwrap_or(0)
+    }
+    /// Add a duration to a series, like the time a frame took
+    pub fn sample(&mut self, name: &'static str, duration: Duration) {
+        let series = self.series.entry(name).or_default();
+        if series.len() == SERIES_LENGTH {
+            series.pop_front();
+        }
+        series.push_back(duration);
+    }
+    /// Get the durations of a series from the oldest to the newest, empty if it was never sampled
+    pub fn samples(&self, name: &str) -> Vec<Duration> {
+        self.series
+            .get(name)
+            .map(|series| series.iter().copied().collect())
+            .unwrap_or_default()
+    }
+    /// Summarize the session up to a point in time
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - the seed the session was started with, to run it again
+    /// * `now` - the end of the session
+    pub fn summary(&self, seed: u64, now: Instant) -> SessionSummary {
+        SessionSummary {
+            seed,
+            generations: self.count(GENERATIONS),
+            elapsed: now.saturating_duration_since(self.started),
+            peak_population: self.peak(POPULATION),
+            commands: self.count(COMMANDS),
+        }
+    }
+}
+
+/// The numbers that are printed when a session ends
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SessionSummary {
+    pub seed: u64,
+    pub generations: u64,
+    pub elapsed: Duration,
+    pub peak_population: u64,
+    pub commands: u64,
+}
+impl SessionSummary {
+    /// The average number of generations per second, 0 if no time passed
+    pub fn generations_per_second(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.generations as f64 / secs
+    }
+}
+impl Display for SessionSummary {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        writeln!(f, "Session summary")?;
+        writeln!(f, "  Seed:             {}", self.seed)?;
+        writeln!(f, "  Generations:      {}", self.generations)?;
+        writeln!(f, "  Time:             {:.1}s", self.elapsed.as_secs_f64())?;
+        writeln!(
+            f,
+            "  Generations/sec:  {:.1}",
+            self.generations_per_second()
+        )?;
+        writeln!(f, "  Peak population:  {}", self.peak_population)?;
+        write!(f, "  Commands issued:  {}", self.commands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{
+        Metrics, SessionSummary, COMMANDS, GENERATIONS, POPULATION, RENDER_TIME, SERIES_LENGTH,
+    };
+
+    #[test]
+    fn counters_and_gauges() {
+        let start = Instant::now();
+        let mut metrics = Metrics::new(start);
+        assert_eq!(metrics.count(GENERATIONS), 0);
+        assert_eq!(metrics.peak(POPULATION), 0);
+
+        metrics.increment(GENERATIONS);
+        metrics.add(GENERATIONS, 9);
+        metrics.increment(COMMANDS);
+        for population in [4, 12, 7] {
+            metrics.gauge(POPULATION, population);
+        }
+        assert_eq!(metrics.latest(POPULATION), 7);
+        assert_eq!(
+            metrics.summary(55, start + Duration::from_secs(4)),
+            SessionSummary {
+                seed: 55,
+                generations: 10,
+                elapsed: Duration::from_secs(4),
+                peak_population: 12,
+                commands: 1,
+            }
+        );
+
+        metrics.reset(start);
+        assert_eq!(metrics.count(GENERATIONS), 0);
+    }
+
+    #[test]
+    fn series() {
+        let mut metrics = Metrics::new(Instant::now());
+        assert!(metrics.samples(RENDER_TIME).is_empty());
+        for millis in 0..SERIES_LENGTH as u64 + 5 {
+            metrics.sample(RENDER_TIME, Duration::from_millis(millis));
+        }
+        let samples = metrics.samples(RENDER_TIME);
+        assert_eq!(samples.len(), SERIES_LENGTH);
+        assert_eq!(
+            (samples[0], samples[SERIES_LENGTH - 1]),
+            (
+                Duration::from_millis(5),
+                Duration::from_millis(SERIES_LENGTH as u64 + 4)
+            ),
+            "The oldest samples are dropped"
+        );
+    }
+
+    #[test]
+    fn summary_text() {
+        let summary = SessionSummary {
+            seed: 55,
+            generations: 10,
+            elapsed: Duration::from_secs(4),
+            peak_population: 12,
+            commands: 3,
+        };
+        assert_eq!(summary.generations_per_second(), 2.5);
+        assert_eq!(
+            summary.to_string(),
+            "Session summary\n  Seed:             55\n  Generations:      10\n  Time:             4.0s\n  \
+             Generations/sec:  2.5\n  Peak population:  12\n  Commands issued:  3"
+        );
+        let instant = SessionSummary {
+            elapsed: Duration::ZERO,
+            ..summary
+        };
+        assert_eq!(instant.generations_per_second(), 0.0);
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// The counts of a `Progress`, shared by its clones
+#[derive(Debug)]
+struct Counts {
+    done: AtomicU64,
+    total: AtomicU64,
+}
+
+/// How far a long operation got, like a fast forward, a seed sweep or the export of a GIF
+///
+/// Clones share the counts, so the thread that does the work advances one clone while the thread
+/// that draws a `ProgressBar` reads another
+///
+/// # Example
+///
+/// ```
+/// # use std::thread;
+/// # use tic_tac_toe2::{conway::seed_sweep::SeedSweep, utils::{clock::{Clock, SystemClock}, progress::Progress}};
+/// # let clock = SystemClock;
+/// # let seeds: Vec<u64> = (0..10).collect();
+/// # let sweep = SeedSweep::new(8, 8, 100);
+/// let progress = Progress::new(seeds.len() as u64, clock.now());
+/// let worker = progress.clone();
+/// thread::spawn(move || for seed in seeds {
+///     sweep.run_seed(seed);
+///     worker.advance(1);
+/// });
+/// println!("{:?} left", progress.eta(clock.now()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Progress {
+    counts: Arc<Counts>,
+    started: Instant,
+}
+impl Progress {
+    /// Create the progress of an operation that just started
+    ///
+    /// # Arguments
+    ///
+    /// * `total` - the number of steps of the operation
+    /// * `started` - when the operation started
+    pub fn new(total: u64, started: Instant) -> Self {
+        Progress {
+            counts: Arc::new(Counts {
+                done: AtomicU64::new(0),
+                total: AtomicU64::new(total),
+            }),
+            started,
+        }
+    }
+    /// Count steps as done
+    pub fn advance(&self, steps: u64) {
+        self.counts.done.fetch_add(steps, Ordering::Relaxed);
+    }
+    /// Set the number of steps that are done
+    pub fn set(&self, done: u64) {
+        self.counts.done.store(done, Ordering::Relaxed);
+    }
+    /// Get the number of steps that are done and the number of steps of the operation
+    pub fn get(&self) -> (u64, u64) {
+        (
+            self.counts.done.load(Ordering::Relaxed),
+            self.counts.total.load(Ordering::Relaxed),
+        )
+    }
+    pub fn is_finished(&self) -> bool {
+        let (done, total) = self.get();
+        done >= total
+    }
+    /// Estimate how long the rest of the operation takes, assuming the steps that are left take
+    /// as long as the steps that are done took on average
+    ///
+    /// # Returns
+    ///
+    /// `None` before the first step is done
+    pub fn eta(&self, now: Instant) -> Option<Duration> {
+        let (done, total) = self.get();
+        if done == 0 {
+            return None;
+        }
+        let elapsed = now.saturating_duration_since(self.started);
+        let left = total.saturating_sub(done);
+        Some(elapsed.mul_f64(left as f64 / done as f64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::Progress;
+
+    #[test]
+    fn estimates_the_time_left() {
+        let start = Instant::now();
+        let progress = Progress::new(10, start);
+        let at = |secs| start + Duration::from_secs(secs);
+        assert_eq!(progress.eta(at(1)), None, "Nothing was done yet");
+
+        let worker = progress.clone();
+        thread::spawn(move || worker.advance(2)).join().unwrap();
+        assert_eq!(progress.get(), (2, 10), "The clones share the counts");
+        let test_cases = [
+            ("a fifth in 4s", 2, 4, Some(16)),
+            ("half in 5s", 5, 5, Some(5)),
+            ("done", 10, 30, Some(0)),
+        ];
+        for (test_case, done, secs, expected) in test_cases {
+            progress.set(done);
+            assert_eq!(
+                progress.eta(at(secs)),
+                expected.map(Duration::from_secs),
+                "{}",
+                test_case
+            );
+        }
+        assert!(progress.is_finished());
+    }
+}
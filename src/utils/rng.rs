@@ -0,0 +1,193 @@
+use std::str::FromStr;
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// The stream used to fill a new board
+pub const GRID_STREAM: &str = "grid";
+/// The stream used to place food and other pickups
+pub const FOOD_STREAM: &str = "food";
+/// The stream used by computer players to break ties between equally good moves
+pub const AI_STREAM: &str = "ai";
+
+/// Hands out independent random number streams that are all derived from one master seed
+///
+/// Every stream is a ChaCha8 generator seeded with the master seed, with the ChaCha stream id
+/// derived from the name of the stream. Systems that use different names never share random
+/// numbers, and running again with the same master seed reproduces the whole session
+///
+/// # Example
+///
+/// ```
+/// # use rand::Rng;
+/// # use tic_tac_toe2::utils::rng::{RngService, FOOD_STREAM, GRID_STREAM};
+/// let rng = RngService::new(42);
+/// eprintln!("{}", rng);
+///
+/// let mut grid_rng = rng.stream(GRID_STREAM);
+/// let mut food_rng = rng.stream(FOOD_STREAM);
+/// let is_alive: bool = grid_rng.gen();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RngService {
+    master_seed: u64,
+}
+impl RngService {
+    pub fn new(master_seed: u64) -> Self {
+        RngService { master_seed }
+    }
+    /// Create a service with a master seed taken from the operating system
+    ///
+    /// The seed should be logged so that the session can be reproduced later
+    pub fn from_entropy() -> Self {
+        RngService::new(rand::thread_rng().next_u64())
+    }
+    pub fn master_seed(&self) -> u64 {
+        self.master_seed
+    }
+    /// Get the random number stream with a name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the stream e.g. `GRID_STREAM`
+    ///
+    /// # Returns
+    ///
+    /// A new `ChaCha8Rng` positioned at the start of the stream. Asking for the same name twice
+    /// returns two generators that produce the same numbers
+    pub fn stream(&self, name: &str) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.master_seed);
+        rng.set_stream(Self::stream_id(name));
+        rng
+    }
+    /// Get the id of a named stream
+    ///
+    /// The grid stream uses the default ChaCha stream so that a board for a seed is the same as
+    /// from before the streams were named. Every other name is hashed with 64 bit FNV-1a, which
+    /// does not change between Rust versions or runs
+    fn stream_id(name: &str) -> u64 {
+        if name == GRID_STREAM {
+            return 0;
+        }
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in name.as_bytes() {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        hash
+    }
+}
+impl std::fmt::Display for RngService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Master seed: {}", self.master_seed)
+    }
+}
+
+/// The master seed that is asked for on the command line, a number or `random`
+///
+/// # Example
+///
+/// ```
+/// # use tic_tac_toe2::utils::{arg_helper::read_config, rng::SeedArg};
+/// # let args: Vec<String> = std::env::args().collect();
+/// let seed: SeedArg = read_config(&args, "--seed".to_string(), SeedArg::Random);
+/// let rng = seed.service();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeedArg {
+    Fixed(u64),
+    /// A seed taken from the operating system, see `RngService::from_entropy`
+    Random,
+}
+impl SeedArg {
+    /// Create the service with the seed, taking a new one from the operating system for `Random`
+    pub fn service(self) -> RngService {
+        match self {
+            SeedArg::Fixed(seed) => RngService::new(seed),
+            SeedArg::Random => RngService::from_entropy(),
+        }
+    }
+}
+impl FromStr for SeedArg {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("random") {
+            return Ok(SeedArg::Random);
+        }
+        s.parse()
+            .map(SeedArg::Fixed)
+            .map_err(|_| format!("`{}` is not a seed or `random`", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::{RngService, SeedArg, AI_STREAM, FOOD_STREAM, GRID_STREAM};
+
+    fn sample(mut rng: ChaCha8Rng) -> Vec<u32> {
+        (0..8).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn streams_are_reproducible() {
+        let first = RngService::new(42);
+        let second = RngService::new(42);
+        for name in [GRID_STREAM, FOOD_STREAM, AI_STREAM] {
+            assert_eq!(
+                sample(first.stream(name)),
+                sample(second.stream(name)),
+                "The {} stream should be the same for the same master seed",
+                name
+            );
+        }
+        assert_ne!(
+            sample(first.stream(FOOD_STREAM)),
+            sample(RngService::new(43).stream(FOOD_STREAM)),
+            "Different master seeds should give different streams"
+        );
+    }
+
+    #[test]
+    fn streams_are_independent() {
+        let rng = RngService::new(7);
+        assert_ne!(
+            sample(rng.stream(GRID_STREAM)),
+            sample(rng.stream(FOOD_STREAM))
+        );
+        assert_ne!(
+            sample(rng.stream(FOOD_STREAM)),
+            sample(rng.stream(AI_STREAM))
+        );
+    }
+
+    #[test]
+    fn grid_stream_matches_plain_seed() {
+        let rng = RngService::new(55);
+        assert_eq!(
+            sample(rng.stream(GRID_STREAM)),
+            sample(ChaCha8Rng::seed_from_u64(55)),
+            "Existing seeds should keep producing the same boards"
+        );
+    }
+
+    #[test]
+    fn seed_args() {
+        let test_cases = [
+            ("number", "55", Ok(SeedArg::Fixed(55))),
+            ("random", "random", Ok(SeedArg::Random)),
+            ("upper case", "Random", Ok(SeedArg::Random)),
+            (
+                "negative",
+                "-1",
+                Err("`-1` is not a seed or `random`".to_string()),
+            ),
+        ];
+        for (test_case, text, expected) in test_cases {
+            assert_eq!(text.parse::<SeedArg>(), expected, "{}", test_case);
+        }
+        assert_eq!(SeedArg::Fixed(55).service(), RngService::new(55));
+    }
+}
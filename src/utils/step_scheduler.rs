@@ -0,0 +1,177 @@
+use std::time::{Duration, Instant};
+
+/// The most generations that are stepped in one tick to catch up
+pub const MAX_CATCH_UP_STEPS: u32 = 4;
+/// The longest time the board is not drawn while the steps are behind
+pub const MAX_SKIPPED_RENDER_TIME: Duration = Duration::from_millis(250);
+/// How much a new measurement moves the average step cost, in percent
+const COST_WEIGHT_PERCENT: u32 = 20;
+
+/// Schedules the generations of a running game and keeps it close to real time when the steps
+/// are slow
+///
+/// Every round duration a generation is due. A tick steps every generation that is due, up to
+/// `MAX_CATCH_UP_STEPS`, so a slow step delays the next generations instead of dropping them.
+/// While the steps are behind, the ticks skip drawing the board, but the board is drawn at least
+/// every `MAX_SKIPPED_RENDER_TIME`. A game that falls further behind than it can catch up drops
+/// the generations it is late by and starts over from the current time
+///
+/// Like `Timers` the scheduler never looks at the clock, the owner passes in the current time
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use tic_tac_toe2::{utils::{clock::{Clock, SystemClock}, step_scheduler::StepScheduler}, ConwaysGame};
+/// # let (clock, round_duration) = (SystemClock, Duration::from_millis(100));
+/// # let mut game = ConwaysGame::headless(16, 16, 42);
+/// let mut scheduler = StepScheduler::new(round_duration, clock.now());
+/// for _ in 0..scheduler.due_steps(clock.now()) {
+///     let started = clock.now();
+///     game.step();
+///     scheduler.record_step(clock.now() - started);
+/// }
+/// if scheduler.should_render(clock.now()) {
+///     game.render();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct StepScheduler {
+    interval: Duration,
+    /// When the next generation is due
+    next_due: Instant,
+    last_render: Instant,
+    /// The moving average of the time a step takes, `None` before the first step
+    step_cost: Option<Duration>,
+    /// The generations that were due but could not be caught up with
+    dropped: u64,
+}
+impl StepScheduler {
+    /// Create a scheduler whose first generation is due after one round duration
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - the round duration
+    /// * `now` - the current time
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        StepScheduler {
+            interval,
+            next_due: now + interval,
+            last_render: now,
+            step_cost: None,
+            dropped: 0,
+        }
+    }
+    /// Change the round duration, the next generation is due one new round duration from now
+    pub fn set_interval(&mut self, interval: Duration, now: Instant) {
+        self.interval = interval;
+        self.next_due = now + interval;
+    }
+    /// Get the number of generations to step now, and count them as stepped
+    pub fn due_steps(&mut self, now: Instant) -> u32 {
+        if now < self.next_due {
+            return 0;
+        }
+        let interval = self.interval.max(Duration::from_nanos(1));
+        let owed = ((now - self.next_due).as_nanos() / interval.as_nanos() + 1) as u64;
+        let steps = owed.min(MAX_CATCH_UP_STEPS as u64);
+        if owed > steps && owed - steps > MAX_CATCH_UP_STEPS as u64 {
+            // Too far behind to catch up, continue from now
+            self.dropped += owed - steps;
+            self.next_due = now + interval;
+        } else {
+            self.next_due += interval * steps as u32;
+        }
+        steps as u32
+    }
+    /// Measure how long a step took
+    pub fn record_step(&mut self, cost: Duration) {
+        self.step_cost = Some(match self.step_cost {
+            None => cost,
+            Some(average) => {
+                (average * (100 - COST_WEIGHT_PERCENT) + cost * COST_WEIGHT_PERCENT) / 100
+            }
+        });
+    }
+    /// Check if the generations are falling behind real time, either because generations are
+    /// still due after the steps of this tick or because a step takes longer than a round
+    pub fn is_behind(&self, now: Instant) -> bool {
+        self.next_due <= now || self.step_cost.is_some_and(|cost| cost > self.interval)
+    }
+    /// Check if the board should be drawn this tick, and count it as drawn if so
+    pub fn should_render(&mut self, now: Instant) -> bool {
+        let is_skipped = self.next_due <= now
+            && now.saturating_duration_since(self.last_render) < MAX_SKIPPED_RENDER_TIME;
+        if !is_skipped {
+            self.last_render = now;
+        }
+        !is_skipped
+    }
+    /// Get the average time a step takes, `None` before the first step
+    pub fn step_cost(&self) -> Option<Duration> {
+        self.step_cost
+    }
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+    /// Get the number of generations that were dropped because the game was too far behind
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{StepScheduler, MAX_CATCH_UP_STEPS};
+
+    #[test]
+    fn catches_up() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(100);
+        let mut scheduler = StepScheduler::new(interval, start);
+        let test_cases = [
+            ("not due", 50, 0),
+            ("due", 100, 1),
+            ("same tick", 100, 0),
+            ("three late", 420, 3),
+            ("on time", 500, 1),
+            ("too far behind", 2000, MAX_CATCH_UP_STEPS),
+            ("started over", 2100, 1),
+        ];
+        for (test_case, millis, expected) in test_cases {
+            let now = start + Duration::from_millis(millis);
+            assert_eq!(scheduler.due_steps(now), expected, "{}", test_case);
+        }
+        assert_eq!(scheduler.dropped(), 11);
+    }
+
+    #[test]
+    fn skips_renders_when_behind() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(10);
+        let mut scheduler = StepScheduler::new(interval, start);
+        let at = |millis| start + Duration::from_millis(millis);
+        assert!(scheduler.should_render(at(5)));
+        assert!(!scheduler.is_behind(at(5)));
+
+        // A tick that only catches up with part of the generations skips the render
+        assert_eq!(scheduler.due_steps(at(60)), MAX_CATCH_UP_STEPS);
+        assert!(scheduler.is_behind(at(60)));
+        assert!(!scheduler.should_render(at(60)));
+        assert!(
+            scheduler.should_render(at(300)),
+            "The board is drawn now and then while behind"
+        );
+
+        let mut slow = StepScheduler::new(interval, start);
+        slow.record_step(Duration::from_millis(5));
+        assert!(!slow.is_behind(start));
+        for _ in 0..5 {
+            slow.record_step(Duration::from_millis(40));
+        }
+        assert!(slow.is_behind(start), "Steps take longer than a round");
+        assert!(slow.step_cost().unwrap() > interval);
+    }
+}
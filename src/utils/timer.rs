@@ -0,0 +1,288 @@
+use std::time::{Duration, Instant};
+
+/// Identifies a timer that was scheduled on a `Timers` instance
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId(usize);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimerKind {
+    Once,
+    Repeating(Duration),
+}
+
+#[derive(Debug)]
+struct Timer<E> {
+    id: TimerId,
+    kind: TimerKind,
+    delay: Duration,
+    deadline: Instant,
+    event: E,
+}
+
+/// A collection of one-shot and repeating timers that produce events when they are due
+///
+/// The timers never look at the clock themselves. The owner passes in the current time on every
+/// `tick` which keeps them deterministic in tests
+///
+/// # Example
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use tic_tac_toe2::utils::timer::Timers;
+/// # struct Game;
+/// # impl Game {
+/// #     fn next(&mut self) {}
+/// #     fn pause(&mut self) {}
+/// # }
+/// # let mut game = Game;
+/// #[derive(Clone)]
+/// enum GameTimer {
+///     Step,
+///     AutoPause,
+/// }
+/// let now = Instant::now();
+/// let mut timers = Timers::default();
+/// timers.repeating(Duration::from_millis(100), GameTimer::Step, now);
+/// let idle = timers.once(Duration::from_secs(60), GameTimer::AutoPause, now);
+///
+/// // on input
+/// timers.restart(idle, Instant::now());
+///
+/// for event in timers.tick(Instant::now()) {
+///     match event {
+///         GameTimer::Step => game.next(),
+///         GameTimer::AutoPause => game.pause(),
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Timers<E> {
+    timers: Vec<Timer<E>>,
+    next_id: usize,
+}
+impl<E> Default for Timers<E> {
+    fn default() -> Self {
+        Timers {
+            timers: Vec::new(),
+            next_id: 0,
+        }
+    }
+}
+impl<E: Clone> Timers<E> {
+    /// Schedule an event to be produced once after a delay
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - how long to wait before the event is produced
+    /// * `event` - the event to produce
+    /// * `now` - the current time
+    ///
+    /// # Returns
+    ///
+    /// The `TimerId` that can be used to cancel or restart the timer
+    pub fn once(&mut self, delay: Duration, event: E, now: Instant) -> TimerId {
+        self.schedule(TimerKind::Once, delay, event, now)
+    }
+    /// Schedule an event to be produced every `interval`
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - the time between events
+    /// * `event` - the event to produce
+    /// * `now` - the current time
+    ///
+    /// # Returns
+    ///
+    /// The `TimerId` that can be used to cancel or restart the timer
+    pub fn repeating(&mut self, interval: Duration, event: E, now: Instant) -> TimerId {
+        self.schedule(TimerKind::Repeating(interval), interval, event, now)
+    }
+    fn schedule(&mut self, kind: TimerKind, delay: Duration, event: E, now: Instant) -> TimerId {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+        self.timers.push(Timer {
+            id,
+            kind,
+            delay,
+            deadline: now + delay,
+            event,
+        });
+        id
+    }
+    /// Stop a timer before it fires
+    ///
+    /// # Returns
+    ///
+    /// `true` if the timer was still scheduled
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != len
+    }
+    /// Start the countdown of a timer again from `now`, e.g. to reset an idle timer on input
+    ///
+    /// # Returns
+    ///
+    /// `true` if the timer was still scheduled
+    pub fn restart(&mut self, id: TimerId, now: Instant) -> bool {
+        match self.timers.iter_mut().find(|timer| timer.id == id) {
+            Some(timer) => {
+                timer.deadline = now + timer.delay;
+                true
+            }
+            None => false,
+        }
+    }
+    /// Change the interval of a repeating timer. The next event is scheduled `interval` from `now`
+    ///
+    /// # Returns
+    ///
+    /// `true` if the timer was still scheduled
+    pub fn set_interval(&mut self, id: TimerId, interval: Duration, now: Instant) -> bool {
+        match self.timers.iter_mut().find(|timer| timer.id == id) {
+            Some(timer) => {
+                if let TimerKind::Repeating(_) = timer.kind {
+                    timer.kind = TimerKind::Repeating(interval);
+                }
+                timer.delay = interval;
+                timer.deadline = now + interval;
+                true
+            }
+            None => false,
+        }
+    }
+    /// Test if a timer is still scheduled
+    pub fn is_scheduled(&self, id: TimerId) -> bool {
+        self.timers.iter().any(|timer| timer.id == id)
+    }
+    /// Get the time left until a timer fires
+    pub fn remaining(&self, id: TimerId, now: Instant) -> Option<Duration> {
+        self.timers
+            .iter()
+            .find(|timer| timer.id == id)
+            .map(|timer| timer.deadline.saturating_duration_since(now))
+    }
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+    /// Collect the events of every timer that is due
+    ///
+    /// One-shot timers are removed once they fire. Repeating timers fire at most once per tick and
+    /// are rescheduled one interval after their deadline, or one interval after `now` if the owner
+    /// fell behind, so that a stall does not cause a burst of events
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - the current time
+    ///
+    /// # Returns
+    ///
+    /// The events that are due, in the order of their deadlines
+    pub fn tick(&mut self, now: Instant) -> Vec<E> {
+        let mut due: Vec<(Instant, E)> = Vec::new();
+        for timer in self.timers.iter_mut() {
+            if timer.deadline > now {
+                continue;
+            }
+            due.push((timer.deadline, timer.event.clone()));
+            if let TimerKind::Repeating(interval) = timer.kind {
+                timer.deadline += interval;
+                if timer.deadline <= now {
+                    timer.deadline = now + interval;
+                }
+            }
+        }
+        self.timers
+            .retain(|timer| timer.kind != TimerKind::Once || timer.deadline > now);
+        due.sort_by_key(|(deadline, _)| *deadline);
+        due.into_iter().map(|(_, event)| event).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::Timers;
+
+    #[test]
+    fn once() {
+        let start = Instant::now();
+        let mut timers = Timers::default();
+        let id = timers.once(Duration::from_millis(50), "pause", start);
+
+        assert!(timers.tick(start + Duration::from_millis(49)).is_empty());
+        assert_eq!(
+            timers.tick(start + Duration::from_millis(50)),
+            vec!["pause"]
+        );
+        assert!(
+            !timers.is_scheduled(id),
+            "A one-shot timer should be removed after it fired"
+        );
+        assert!(timers.tick(start + Duration::from_secs(1)).is_empty());
+    }
+
+    #[test]
+    fn repeating() {
+        let start = Instant::now();
+        let mut timers = Timers::default();
+        timers.repeating(Duration::from_millis(10), "step", start);
+
+        let test_cases = vec![
+            (1, 5, 0),
+            (2, 10, 1),
+            (3, 15, 0),
+            (4, 20, 1),
+            (5, 95, 1),
+            (6, 100, 0),
+            (7, 105, 1),
+        ];
+        for (test_case, elapsed, expected) in test_cases {
+            let events = timers.tick(start + Duration::from_millis(elapsed));
+            assert_eq!(
+                events.len(),
+                expected,
+                "Test case {}: The number of events after {}ms is wrong",
+                test_case,
+                elapsed
+            );
+        }
+    }
+
+    #[test]
+    fn cancel_restart_and_order() {
+        let start = Instant::now();
+        let mut timers = Timers::default();
+        let idle = timers.once(Duration::from_millis(30), "idle", start);
+        let food = timers.once(Duration::from_millis(20), "food", start);
+        timers.once(Duration::from_millis(10), "first", start);
+
+        assert!(timers.restart(idle, start + Duration::from_millis(25)));
+        assert_eq!(
+            timers.remaining(idle, start + Duration::from_millis(25)),
+            Some(Duration::from_millis(30))
+        );
+        assert_eq!(
+            timers.tick(start + Duration::from_millis(40)),
+            vec!["first", "food"],
+            "Events should be ordered by their deadline"
+        );
+        assert!(!timers.cancel(food), "The food timer already fired");
+        assert!(timers.cancel(idle));
+        assert!(timers.is_empty());
+        assert!(!timers.restart(idle, start));
+    }
+
+    #[test]
+    fn set_interval() {
+        let start = Instant::now();
+        let mut timers = Timers::default();
+        let step = timers.repeating(Duration::from_millis(100), "step", start);
+        assert!(timers.set_interval(step, Duration::from_millis(10), start));
+        assert_eq!(timers.tick(start + Duration::from_millis(10)), vec!["step"]);
+    }
+}
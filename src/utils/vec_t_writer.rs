@@ -1,5 +1,3 @@
-use std::usize;
-
 /// Write to an existing `Vec<T>` with a new `Vec<T>` where T is the type
 ///
 /// # Arguments
@@ -17,9 +15,10 @@ use std::usize;
 /// # Examples
 ///
 /// ```
+/// # use tic_tac_toe2::utils::vec_t_writer::write_vec_to_vec;
 /// let original_vec: Vec<u8> = "original".as_bytes().to_vec();
 /// let to_write_vec: Vec<u8> = "new string".as_bytes().to_vec();
-/// let result = write_to_location(original_vec.clone(), to_write_vec.clone(), 4, b' ');
+/// let result = write_vec_to_vec(original_vec.clone(), to_write_vec.clone(), 4, b' ');
 /// assert_eq!(result, "orignew string".as_bytes().to_vec());
 /// ```
 ///
@@ -51,7 +50,7 @@ mod write_vec_to_vec_tests {
 
     #[test]
     fn write_u8_to_location_scenarios() {
-        let test_cases = vec![
+        let test_cases = [
             ("Hello ", "World", 6, "Hello World"),
             ("Rust", " is great", 4, "Rust is great"),
             ("Foo Baz", "Bar", 0, "Bar Baz"),
@@ -98,7 +97,7 @@ mod write_vec_to_vec_tests {
     #[test]
     fn write_enum_to_location_scenarios() {
         let default = TerminalColors::Black;
-        let test_cases = vec![
+        let test_cases = [
             (
                 vec![TerminalColors::Red, TerminalColors::Black],
                 vec![TerminalColors::HotPink],
@@ -106,8 +105,8 @@ mod write_vec_to_vec_tests {
                 vec![
                     TerminalColors::Red,
                     TerminalColors::Black,
-                    default.clone(),
-                    default.clone(),
+                    default,
+                    default,
                     TerminalColors::HotPink,
                 ],
             ),
@@ -120,12 +119,7 @@ mod write_vec_to_vec_tests {
         ];
 
         for (i, (original, to_write, location, expected)) in test_cases.iter().enumerate() {
-            let result = write_vec_to_vec(
-                original.clone(),
-                to_write.clone(),
-                *location,
-                default.clone(),
-            );
+            let result = write_vec_to_vec(original.clone(), to_write.clone(), *location, default);
 
             assert_eq!(
                 &result, expected,
@@ -153,10 +147,11 @@ mod write_vec_to_vec_tests {
 /// # Examples
 ///
 /// ```
+/// # use tic_tac_toe2::utils::vec_t_writer::write_t_to_vec;
 /// let original_vec: Vec<u8> = "original string".as_bytes().to_vec();
-/// let to_write: u8 = b"A";
+/// let to_write: u8 = b'A';
 /// let result = write_t_to_vec(original_vec.clone(), to_write, 4,2, b' ');
-/// assert_eq!(result, "origAA string".as_bytes().to_vec());
+/// assert_eq!(result, "origAAal string".as_bytes().to_vec());
 ///
 /// ```
 pub fn write_t_to_vec<T: Copy>(
@@ -175,7 +170,7 @@ pub fn write_t_to_vec<T: Copy>(
 
     let mut new: Vec<T> = Vec::with_capacity(original.len().max(index + len));
     new.extend_from_slice(&original[0..index]);
-    new.extend(std::iter::repeat(t_to_write).take(len));
+    new.extend(std::iter::repeat_n(t_to_write, len));
     if original.len() > len + index {
         new.extend_from_slice(&original[len + index..]);
     }
@@ -187,7 +182,7 @@ mod write_t_to_vec_tests {
 
     #[test]
     fn write_u8() {
-        let test_cases = vec![
+        let test_cases = [
             ("Hello", 6_usize, 4_usize, b'P', b'V', "HelloPVVVV"),
             ("Hello", 2_usize, 1_usize, b'P', b'V', "HeVlo"),
             ("Hello", 2_usize, 10_usize, b'P', b'V', "HeVVVVVVVVVV"),
@@ -247,9 +242,10 @@ mod write_t_to_vec_tests {
 /// # Examples
 ///
 /// ```
-/// let original_vec: Vec<u8> = "original string".as_bytes().to_vec();
-/// let to_write: u8 = b"A";
-/// pad_vec(original_vec, 20, to_write);
+/// # use tic_tac_toe2::utils::vec_t_writer::pad_vec;
+/// let mut original_vec: Vec<u8> = "original string".as_bytes().to_vec();
+/// let to_write: u8 = b'A';
+/// pad_vec(&mut original_vec, 20, to_write);
 /// assert_eq!(original_vec, "original stringAAAAA".as_bytes().to_vec());
 ///
 /// ```
@@ -257,7 +253,7 @@ pub fn pad_vec<T: Copy>(original: &mut Vec<T>, len: usize, default: T) {
     if len < original.len() {
         return;
     }
-    original.extend(std::iter::repeat(default).take(len - original.len()));
+    original.extend(std::iter::repeat_n(default, len - original.len()));
 }
 
 #[cfg(test)]
@@ -266,7 +262,7 @@ mod pad_vec_tests {
 
     #[test]
     fn pad() {
-        let test_cases = vec![
+        let test_cases = [
             ("Hello", 6_usize, b' ', "Hello "),
             ("Hello", 3_usize, b' ', "Hello"),
             ("Hello", 10_usize, b'A', "HelloAAAAA"),